@@ -145,12 +145,12 @@ generate_account_operations_routing() {
         cat << EOF
         # ACCOUNT operations - Route to node1 only for consistency
         # This prevents "API key no longer valid" errors caused by chain inconsistency
-        location ~ ^/(data/list|data/generate)$ {
+        location ~ ^(/v1)?/(data/list|data/search|data/generate)$ {
             # Handle CORS preflight (OPTIONS) requests
             if (\$request_method = OPTIONS) {
                 add_header Access-Control-Allow-Origin * always;
                 add_header Access-Control-Allow-Methods "GET, POST, OPTIONS" always;
-                add_header Access-Control-Allow-Headers "Content-Type, Authorization, X-Signature" always;
+                add_header Access-Control-Allow-Headers "Content-Type, Authorization, X-Signature, X-Nonce, X-Timestamp, X-Request-Id, Idempotency-Key" always;
                 add_header Content-Length 0;
                 return 204;
             }
@@ -35,6 +35,7 @@ for i in {1..3}; do
   curl -s -X POST "$NODE_URL/data/submit" \
     -H "Authorization: Bearer $API_KEY" \
     -H "Content-Type: application/json" \
+    -H "X-Nonce: $(uuidgen)" \
     -d '{"label": "warmup", "data": "test"}' > /dev/null
   sleep 0.5
 done
@@ -57,6 +58,7 @@ for i in $(seq 1 $ITERATIONS); do
     -X POST "$NODE_URL/data/submit" \
     -H "Authorization: Bearer $API_KEY" \
     -H "Content-Type: application/json" \
+    -H "X-Nonce: $(uuidgen)" \
     -d "{\"label\": \"perf_test_$i\", \"data\": \"Lorem ipsum dolor sit amet, consectetur adipiscing elit. $(date)\"}")
 
   HTTP_CODE=$(echo "$RESPONSE" | grep "HTTP_CODE:" | cut -d: -f2)
@@ -6,13 +6,19 @@ use base64::{engine::general_purpose, Engine as _};
 use chrono::Utc;
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use zeroize::Zeroizing;
 
 use crate::config::Config;
-use crate::constants::{NONCE_SIZE_BYTES, SESSION_EXPIRY_SECONDS};
+use crate::constants::{
+    MIN_SESSION_TTL_SECONDS, NONCE_SIZE_BYTES, SESSION_EXPIRY_SECONDS,
+    SESSION_REFRESH_GRACE_SECONDS,
+};
 use crate::crypto::{
-    constant_time_compare_bytes, decode_api_key, derive_session_encryption_key, hash_api_key,
-    validate_api_key,
+    api_key_kdf, constant_time_compare_bytes, decode_api_key, derive_session_encryption_key,
+    hash_api_key_with_kdf, parse_api_key_hash, validate_api_key,
 };
+use crate::storage::TokenRevocationStore;
 use crate::types::{GoudChainError, Result};
 
 /// Encrypt API key with session secret for storage in JWT
@@ -20,9 +26,9 @@ fn encrypt_api_key_for_jwt(api_key: &[u8], config: &Config) -> Result<String> {
     let session_secret = &config.session_secret;
 
     // Derive AES-256 key from session secret using HKDF (domain separation)
-    let key_bytes = derive_session_encryption_key(session_secret);
+    let key_bytes = Zeroizing::new(derive_session_encryption_key(session_secret));
 
-    let cipher = Aes256Gcm::new(&key_bytes.into());
+    let cipher = Aes256Gcm::new(&(*key_bytes).into());
 
     // Generate random nonce
     let nonce_bytes: [u8; NONCE_SIZE_BYTES] = rand::random();
@@ -40,13 +46,17 @@ fn encrypt_api_key_for_jwt(api_key: &[u8], config: &Config) -> Result<String> {
 }
 
 /// Decrypt API key from JWT encrypted_api_key field
-pub fn decrypt_api_key_from_jwt(encrypted_api_key: &str, config: &Config) -> Result<Vec<u8>> {
+/// The plaintext key is wiped from memory when the returned buffer is dropped
+pub fn decrypt_api_key_from_jwt(
+    encrypted_api_key: &str,
+    config: &Config,
+) -> Result<Zeroizing<Vec<u8>>> {
     let session_secret = &config.session_secret;
 
     // Derive AES-256 key from session secret using HKDF (domain separation)
-    let key_bytes = derive_session_encryption_key(session_secret);
+    let key_bytes = Zeroizing::new(derive_session_encryption_key(session_secret));
 
-    let cipher = Aes256Gcm::new(&key_bytes.into());
+    let cipher = Aes256Gcm::new(&(*key_bytes).into());
 
     // Decode base64 - use generic error for all failures
     let combined = general_purpose::STANDARD
@@ -69,7 +79,35 @@ pub fn decrypt_api_key_from_jwt(encrypted_api_key: &str, config: &Config) -> Res
         .decrypt(&nonce, ciphertext)
         .map_err(|_| GoudChainError::AuthenticationFailed)?;
 
-    Ok(plaintext)
+    Ok(Zeroizing::new(plaintext))
+}
+
+/// Access granted by a session token
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenScope {
+    /// List, search and decrypt data
+    Read,
+    /// Submit and delete data
+    Write,
+    /// Read and write (default)
+    #[default]
+    All,
+}
+
+impl TokenScope {
+    /// Check whether this scope grants `required` access
+    pub fn allows(self, required: TokenScope) -> bool {
+        self == TokenScope::All || self == required
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Write => "write",
+            Self::All => "all",
+        }
+    }
 }
 
 /// JWT Claims structure
@@ -80,13 +118,82 @@ pub struct Claims {
     pub encrypted_api_key: String, // AES-GCM(api_key, SESSION_SECRET) - for server-side decryption
     pub exp: i64,                  // Expiration timestamp
     pub iat: i64,                  // Issued at timestamp
+    pub jti: String,               // Unique token ID (for revocation on logout/refresh)
+    #[serde(default)]
+    pub scope: TokenScope, // Granted access (tokens without a scope keep full access)
+    #[serde(default)]
+    pub remember_me: bool, // Long-lived read-only token (capped by REMEMBER_ME_TTL_SECONDS)
+}
+
+/// How long a session token lives, and whether it is a long-lived "remember me" token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionLifetime {
+    pub ttl_seconds: i64,
+    pub remember_me: bool,
+}
+
+impl SessionLifetime {
+    /// Default lifetime (1 hour, or MAX_SESSION_TTL_SECONDS if that is shorter)
+    pub fn standard(config: &Config) -> Self {
+        Self {
+            ttl_seconds: SESSION_EXPIRY_SECONDS.min(config.max_session_ttl_seconds),
+            remember_me: false,
+        }
+    }
+
+    /// Lifetime requested at login, validated against the configured maximum
+    /// Remember-me tokens default to REMEMBER_ME_TTL_SECONDS, which also caps them
+    pub fn requested(ttl_seconds: Option<i64>, remember_me: bool, config: &Config) -> Result<Self> {
+        let (default, max) = if remember_me {
+            (
+                config.remember_me_ttl_seconds,
+                config.remember_me_ttl_seconds,
+            )
+        } else {
+            (
+                Self::standard(config).ttl_seconds,
+                config.max_session_ttl_seconds,
+            )
+        };
+        let ttl_seconds = ttl_seconds.unwrap_or(default);
+        if !(MIN_SESSION_TTL_SECONDS..=max).contains(&ttl_seconds) {
+            return Err(GoudChainError::InvalidRequestBody(format!(
+                "ttl_seconds must be between {} and {}",
+                MIN_SESSION_TTL_SECONDS, max
+            )));
+        }
+        Ok(Self {
+            ttl_seconds,
+            remember_me,
+        })
+    }
+
+    /// Lifetime of an existing token (kept when it is refreshed)
+    pub fn of(claims: &Claims) -> Self {
+        Self {
+            ttl_seconds: claims.exp - claims.iat,
+            remember_me: claims.remember_me,
+        }
+    }
+
+    /// Scope granted for a requested scope (remember-me tokens are always read-only)
+    pub fn restrict(self, scope: TokenScope) -> TokenScope {
+        if self.remember_me {
+            TokenScope::Read
+        } else {
+            scope
+        }
+    }
 }
 
 /// Generate a JWT session token from API key
+/// The token expires `lifetime.ttl_seconds` from now; remember-me tokens are issued read-only
 pub fn generate_session_token(
     account_id: String,
     api_key: &[u8],
     api_key_hash: String,
+    scope: TokenScope,
+    lifetime: SessionLifetime,
     config: &Config,
 ) -> Result<String> {
     let now = Utc::now().timestamp();
@@ -98,8 +205,11 @@ pub fn generate_session_token(
         sub: account_id,
         api_key_hash,
         encrypted_api_key,
-        exp: now + SESSION_EXPIRY_SECONDS,
+        exp: now + lifetime.ttl_seconds,
         iat: now,
+        jti: uuid::Uuid::new_v4().to_string(),
+        scope: lifetime.restrict(scope),
+        remember_me: lifetime.remember_me,
     };
 
     let jwt_secret = &config.jwt_secret;
@@ -113,28 +223,93 @@ pub fn generate_session_token(
 
 /// Verify and decode a JWT session token
 pub fn verify_session_token(token: &str, config: &Config) -> Result<Claims> {
-    let validation = Validation::default();
-    let jwt_secret = &config.jwt_secret;
+    decode_session_token(token, &Validation::default(), config)
+}
+
+/// Verify a JWT session token for refresh, accepting tokens that expired within the grace window
+pub fn verify_session_token_for_refresh(token: &str, config: &Config) -> Result<Claims> {
+    let mut validation = Validation::default();
+    validation.leeway = SESSION_REFRESH_GRACE_SECONDS as u64;
+    decode_session_token(token, &validation, config)
+}
 
-    decode::<Claims>(token, &DecodingKey::from_secret(jwt_secret), &validation)
+/// Decode a session token and check its lifetime against the current configuration
+/// Lowering MAX_SESSION_TTL_SECONDS or REMEMBER_ME_TTL_SECONDS rejects longer-lived tokens
+fn decode_session_token(token: &str, validation: &Validation, config: &Config) -> Result<Claims> {
+    let jwt_secret = &config.jwt_secret;
+    let claims = decode::<Claims>(token, &DecodingKey::from_secret(jwt_secret), validation)
         .map(|data| data.claims)
-        .map_err(|e| GoudChainError::Unauthorized(format!("Invalid token: {}", e)))
+        .map_err(|e| GoudChainError::Unauthorized(format!("Invalid token: {}", e)))?;
+
+    let max_ttl = if claims.remember_me {
+        config.remember_me_ttl_seconds
+    } else {
+        config.max_session_ttl_seconds
+    };
+    if claims.exp - claims.iat > max_ttl {
+        return Err(GoudChainError::Unauthorized(
+            "Session token lifetime exceeds the configured maximum".to_string(),
+        ));
+    }
+    if claims.remember_me && claims.scope != TokenScope::Read {
+        return Err(GoudChainError::Unauthorized(
+            "Remember-me session tokens must be read-only".to_string(),
+        ));
+    }
+    Ok(claims)
+}
+
+/// Reject session tokens that were revoked by logout or refresh
+pub fn ensure_token_not_revoked(claims: &Claims, revocations: &TokenRevocationStore) -> Result<()> {
+    if revocations.is_revoked(&claims.jti)? {
+        return Err(GoudChainError::Unauthorized(
+            "Session token has been revoked".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Reject credentials whose API key was retired by key rotation
+fn ensure_api_key_not_rotated(revoked: bool) -> Result<()> {
+    if revoked {
+        return Err(GoudChainError::Unauthorized(
+            "API key has been rotated".to_string(),
+        ));
+    }
+    Ok(())
 }
 
 /// Extract API key or session token from Authorization header
 pub enum AuthMethod {
-    ApiKey(Vec<u8>),      // Raw API key bytes
-    SessionToken(Claims), // Decoded JWT claims
+    ApiKey(Zeroizing<Vec<u8>>), // Raw API key bytes (wiped on drop)
+    SessionToken(Claims),       // Decoded JWT claims
+}
+
+impl AuthMethod {
+    /// Scope granted by this credential (API keys always have full access)
+    pub fn scope(&self) -> TokenScope {
+        match self {
+            Self::ApiKey(_) => TokenScope::All,
+            Self::SessionToken(claims) => claims.scope,
+        }
+    }
+
+    /// Reject credentials whose scope does not grant `required` access
+    pub fn require_scope(&self, required: TokenScope) -> Result<()> {
+        if !self.scope().allows(required) {
+            return Err(GoudChainError::InsufficientScope(
+                required.as_str().to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 // Deprecated: extract_auth() for tiny_http - no longer used with axum architecture
 // Use extract_auth_from_headers() instead
 
-/// Extract authentication from axum HeaderMap
-pub fn extract_auth_from_headers(
-    headers: &axum::http::HeaderMap,
-    config: &Config,
-) -> Result<AuthMethod> {
+/// Extract the Bearer token from the Authorization header
+pub fn extract_bearer_token(headers: &axum::http::HeaderMap) -> Result<&str> {
     // Get Authorization header
     let auth_header = headers
         .get("authorization")
@@ -144,53 +319,85 @@ pub fn extract_auth_from_headers(
         GoudChainError::Unauthorized("Invalid Authorization header encoding".to_string())
     })?;
 
-    // Check if it's a Bearer token
-    if let Some(token) = auth_value.strip_prefix("Bearer ") {
-        // Try to decode as JWT first
-        if let Ok(claims) = verify_session_token(token, config) {
-            return Ok(AuthMethod::SessionToken(claims));
-        }
+    auth_value.strip_prefix("Bearer ").ok_or_else(|| {
+        GoudChainError::Unauthorized(
+            "Invalid Authorization header format. Expected: Bearer <token>".to_string(),
+        )
+    })
+}
 
-        // Otherwise, treat as base64-encoded API key
-        let api_key = decode_api_key(token)
-            .map_err(|_| GoudChainError::Unauthorized("Invalid API key format".to_string()))?;
+/// Extract authentication from axum HeaderMap
+/// Session tokens revoked by logout or refresh are rejected even before they expire, and
+/// API keys retired by rotation are rejected along with every session token issued for them
+pub fn extract_auth_from_headers(
+    headers: &axum::http::HeaderMap,
+    config: &Config,
+    revocations: &TokenRevocationStore,
+) -> Result<AuthMethod> {
+    let token = extract_bearer_token(headers)?;
 
-        if !validate_api_key(&api_key) {
-            return Err(GoudChainError::Unauthorized(
-                "Invalid API key length".to_string(),
-            ));
-        }
+    // Try to decode as JWT first
+    if let Ok(claims) = verify_session_token(token, config) {
+        ensure_token_not_revoked(&claims, revocations)?;
+        ensure_api_key_not_rotated(revocations.is_key_hash_revoked(&claims.api_key_hash)?)?;
+        return Ok(AuthMethod::SessionToken(claims));
+    }
 
-        return Ok(AuthMethod::ApiKey(api_key));
+    // Otherwise, treat as base64-encoded API key
+    let api_key = decode_api_key(token)
+        .map_err(|_| GoudChainError::Unauthorized("Invalid API key format".to_string()))?;
+
+    if !validate_api_key(&api_key) {
+        return Err(GoudChainError::Unauthorized(
+            "Invalid API key length".to_string(),
+        ));
     }
+    ensure_api_key_kdf_accepted(&api_key, config)?;
+    ensure_api_key_not_rotated(revocations.is_api_key_revoked(&api_key)?)?;
+
+    Ok(AuthMethod::ApiKey(api_key))
+}
 
-    Err(GoudChainError::Unauthorized(
-        "Invalid Authorization header format. Expected: Bearer <token>".to_string(),
-    ))
+/// Refuse keys whose Argon2id header names parameters this node is not configured for
+/// Checked before any hashing, so a key cannot choose what the node spends on it
+pub fn ensure_api_key_kdf_accepted(api_key: &[u8], config: &Config) -> Result<()> {
+    if !config.accepts_api_key_kdf(&api_key_kdf(api_key)) {
+        return Err(GoudChainError::Unauthorized(
+            "API key hashing parameters are not accepted by this node".to_string(),
+        ));
+    }
+    Ok(())
 }
 
 /// Verify API key hash with optional pre-computed hash (optimization)
+/// The stored hash's version prefix picks the scheme, so HKDF and Argon2id hashes both verify
 pub fn verify_api_key_hash_precomputed(
     computed_hash_hex: Option<&str>,
     api_key: &[u8],
     expected_hash_hex: &str,
 ) -> Result<()> {
-    // Use provided hash or compute it
-    let computed_hash_hex = match computed_hash_hex {
-        Some(hash) => hash.to_string(),
-        None => {
-            // Compute hash of provided API key (expensive: 100k iterations)
-            let hash_bytes = hash_api_key(api_key);
-            hex::encode(hash_bytes)
-        }
+    let (expected_kdf, expected_hash) = parse_api_key_hash(expected_hash_hex)
+        .ok_or_else(|| GoudChainError::Unauthorized("Invalid hash format".to_string()))?;
+
+    // Use provided hash or compute it under the stored scheme
+    let computed_hash = match computed_hash_hex {
+        Some(hash) => match parse_api_key_hash(hash) {
+            Some((kdf, hash)) if kdf == expected_kdf => hash,
+            Some(_) => {
+                return Err(GoudChainError::Unauthorized(
+                    "API key does not match".to_string(),
+                ))
+            }
+            None => {
+                return Err(GoudChainError::Internal(
+                    "Invalid computed hash format".to_string(),
+                ))
+            }
+        },
+        // Compute hash of provided API key (expensive by design)
+        None => hash_api_key_with_kdf(api_key, &expected_kdf).to_vec(),
     };
 
-    // Decode both hashes to bytes
-    let computed_hash = hex::decode(&computed_hash_hex)
-        .map_err(|_| GoudChainError::Internal("Invalid computed hash format".to_string()))?;
-    let expected_hash = hex::decode(expected_hash_hex)
-        .map_err(|_| GoudChainError::Unauthorized("Invalid hash format".to_string()))?;
-
     // Constant-time comparison of raw bytes (prevents timing attacks)
     if !constant_time_compare_bytes(&computed_hash, &expected_hash) {
         return Err(GoudChainError::Unauthorized(
@@ -204,40 +411,23 @@ pub fn verify_api_key_hash_precomputed(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
-
-    fn test_config() -> Config {
-        let mut node_to_validator = HashMap::new();
-        node_to_validator.insert("test-node".to_string(), "Validator_1".to_string());
-
-        let mut validator_to_address = HashMap::new();
-        validator_to_address.insert("Validator_1".to_string(), "localhost:8080".to_string());
-
-        Config {
-            node_id: "test-node".to_string(),
-            http_port: "8080".to_string(),
-            p2p_port: 9000,
-            peers: vec![],
-            jwt_secret: b"test_jwt_secret_min_32_bytes_long_123456".to_vec(),
-            session_secret: b"test_session_secret_min_32_bytes_long".to_vec(),
-            validator_config: crate::config::ValidatorConfig {
-                validators: vec!["Validator_1".to_string()],
-                node_to_validator,
-                validator_to_address,
-            },
-        }
-    }
 
     #[test]
     fn test_generate_verify_token() {
-        let config = test_config();
+        let config = Config::for_tests();
         let account_id = "test-account".to_string();
         let api_key = b"test_api_key_12345678901234567890";
         let api_key_hash = "test-hash".to_string();
 
-        let token =
-            generate_session_token(account_id.clone(), api_key, api_key_hash.clone(), &config)
-                .unwrap();
+        let token = generate_session_token(
+            account_id.clone(),
+            api_key,
+            api_key_hash.clone(),
+            TokenScope::All,
+            SessionLifetime::standard(&config),
+            &config,
+        )
+        .unwrap();
 
         let claims = verify_session_token(&token, &config).unwrap();
         assert_eq!(claims.sub, account_id);
@@ -246,12 +436,12 @@ mod tests {
         // Verify we can decrypt the API key from the token
         let decrypted_api_key =
             decrypt_api_key_from_jwt(&claims.encrypted_api_key, &config).unwrap();
-        assert_eq!(decrypted_api_key, api_key);
+        assert_eq!(decrypted_api_key.as_slice(), api_key);
     }
 
     #[test]
     fn test_expired_token() {
-        let config = test_config();
+        let config = Config::for_tests();
         let account_id = "test-account".to_string();
         let api_key = b"test_api_key_12345678901234567890";
         let api_key_hash = "test-hash".to_string();
@@ -264,6 +454,9 @@ mod tests {
             encrypted_api_key,
             exp: Utc::now().timestamp() - 3600, // Expired 1 hour ago
             iat: Utc::now().timestamp() - 7200,
+            jti: uuid::Uuid::new_v4().to_string(),
+            scope: TokenScope::All,
+            remember_me: false,
         };
 
         let jwt_secret = &config.jwt_secret;
@@ -289,16 +482,45 @@ mod tests {
         assert!(verify_api_key_hash_precomputed(None, api_key, "wrong_hash").is_err());
     }
 
+    #[test]
+    fn test_verify_dispatches_on_hash_prefix() {
+        use crate::crypto::{generate_api_key_with_kdf, hash_api_key_hex};
+        use crate::types::{ApiKeyKdf, Argon2Params};
+
+        let kdf = ApiKeyKdf::Argon2id(Argon2Params {
+            memory_kib: 64,
+            iterations: 1,
+            parallelism: 1,
+        });
+        let argon2_key = generate_api_key_with_kdf(&kdf);
+        let argon2_hash = hash_api_key_hex(&argon2_key);
+        assert!(verify_api_key_hash_precomputed(None, &argon2_key, &argon2_hash).is_ok());
+        assert!(
+            verify_api_key_hash_precomputed(Some(&argon2_hash), &argon2_key, &argon2_hash).is_ok()
+        );
+
+        // Existing HKDF hashes keep validating next to Argon2id ones
+        let legacy_key = crate::crypto::generate_api_key();
+        let legacy_hash = hash_api_key_hex(&legacy_key);
+        assert!(verify_api_key_hash_precomputed(None, &legacy_key, &legacy_hash).is_ok());
+        assert!(verify_api_key_hash_precomputed(None, &legacy_key, &argon2_hash).is_err());
+        assert!(verify_api_key_hash_precomputed(None, &argon2_key, &legacy_hash).is_err());
+        assert!(
+            verify_api_key_hash_precomputed(Some(&legacy_hash), &argon2_key, &argon2_hash).is_err()
+        );
+    }
+
     #[test]
     fn test_encrypt_decrypt_roundtrip_with_hkdf() {
-        let config = test_config();
+        let config = Config::for_tests();
         let api_key = b"test_api_key_32_bytes_exactly_ok";
 
         let encrypted = encrypt_api_key_for_jwt(api_key, &config).unwrap();
         let decrypted = decrypt_api_key_from_jwt(&encrypted, &config).unwrap();
 
         assert_eq!(
-            decrypted, api_key,
+            decrypted.as_slice(),
+            api_key,
             "Roundtrip encryption/decryption must preserve API key"
         );
     }
@@ -307,7 +529,7 @@ mod tests {
     fn test_different_session_secrets_produce_different_ciphertexts() {
         let api_key = b"test_api_key_32_bytes_exactly_ok";
 
-        let config1 = test_config();
+        let config1 = Config::for_tests();
         let mut config2 = config1.clone();
         config2.session_secret = b"different_session_secret_32bytes".to_vec();
 
@@ -327,7 +549,7 @@ mod tests {
 
     #[test]
     fn test_session_secret_rotation_invalidates_tokens() {
-        let config_old = test_config();
+        let config_old = Config::for_tests();
         let api_key = b"test_api_key_32_bytes_exactly_ok";
 
         let encrypted = encrypt_api_key_for_jwt(api_key, &config_old).unwrap();
@@ -341,4 +563,249 @@ mod tests {
             "Token encrypted with old secret must fail after rotation"
         );
     }
+
+    fn token_expired_at(exp: i64, config: &Config) -> String {
+        let claims = Claims {
+            sub: "test-account".to_string(),
+            api_key_hash: "test-hash".to_string(),
+            encrypted_api_key: encrypt_api_key_for_jwt(b"test_api_key_32_bytes_exactly_ok", config)
+                .unwrap(),
+            exp,
+            iat: exp - SESSION_EXPIRY_SECONDS,
+            jti: uuid::Uuid::new_v4().to_string(),
+            scope: TokenScope::All,
+            remember_me: false,
+        };
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(&config.jwt_secret),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_refresh_grace_window() {
+        let config = Config::for_tests();
+        let now = Utc::now().timestamp();
+
+        // Recently expired: rejected for normal auth, accepted for refresh
+        let recent = token_expired_at(now - (SESSION_REFRESH_GRACE_SECONDS - 60), &config);
+        assert!(verify_session_token(&recent, &config).is_err());
+        assert!(verify_session_token_for_refresh(&recent, &config).is_ok());
+
+        // Expired beyond the grace window: rejected everywhere
+        let stale = token_expired_at(now - SESSION_REFRESH_GRACE_SECONDS - 60, &config);
+        assert!(verify_session_token_for_refresh(&stale, &config).is_err());
+    }
+
+    #[test]
+    fn test_revoked_token_rejected_before_expiry() {
+        use rocksdb::{Options, DB};
+        use std::sync::Arc;
+
+        let config = Config::for_tests();
+        let path = format!("/tmp/goud_auth_revocation_test_{}", rand::random::<u64>());
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let revocations = TokenRevocationStore::new(Arc::new(DB::open(&opts, path).unwrap()));
+
+        let token = generate_session_token(
+            "test-account".to_string(),
+            b"test_api_key_32_bytes_exactly_ok",
+            "test-hash".to_string(),
+            TokenScope::All,
+            SessionLifetime::standard(&config),
+            &config,
+        )
+        .unwrap();
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert(
+            "authorization",
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+
+        assert!(matches!(
+            extract_auth_from_headers(&headers, &config, &revocations),
+            Ok(AuthMethod::SessionToken(_))
+        ));
+
+        let claims = verify_session_token(&token, &config).unwrap();
+        revocations.revoke(&claims.jti, claims.exp).unwrap();
+        assert!(matches!(
+            extract_auth_from_headers(&headers, &config, &revocations),
+            Err(GoudChainError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_rotated_api_key_and_its_sessions_rejected() {
+        use crate::crypto::{encode_api_key, generate_api_key};
+        use rocksdb::{Options, DB};
+        use std::sync::Arc;
+
+        let config = Config::for_tests();
+        let path = format!("/tmp/goud_auth_rotation_test_{}", rand::random::<u64>());
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let revocations = TokenRevocationStore::new(Arc::new(DB::open(&opts, path).unwrap()));
+
+        let api_key = generate_api_key();
+        let bearer = |token: String| {
+            let mut headers = axum::http::HeaderMap::new();
+            headers.insert(
+                "authorization",
+                format!("Bearer {}", token).parse().unwrap(),
+            );
+            headers
+        };
+        let key_headers = bearer(encode_api_key(&api_key));
+        let session_headers = bearer(
+            generate_session_token(
+                "test-account".to_string(),
+                &api_key,
+                "old-hash".to_string(),
+                TokenScope::All,
+                SessionLifetime::standard(&config),
+                &config,
+            )
+            .unwrap(),
+        );
+        assert!(extract_auth_from_headers(&key_headers, &config, &revocations).is_ok());
+        assert!(extract_auth_from_headers(&session_headers, &config, &revocations).is_ok());
+
+        revocations.revoke_api_key(&api_key, "old-hash").unwrap();
+        for headers in [key_headers, session_headers] {
+            assert!(matches!(
+                extract_auth_from_headers(&headers, &config, &revocations),
+                Err(GoudChainError::Unauthorized(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_api_key_kdf_must_be_configured() {
+        use crate::crypto::generate_api_key_with_kdf;
+        use crate::types::{ApiKeyKdf, Argon2Params};
+
+        let configured = Argon2Params {
+            memory_kib: 64,
+            iterations: 1,
+            parallelism: 1,
+        };
+        let config = Config {
+            api_key_kdf: ApiKeyKdf::Argon2id(configured),
+            ..Config::for_tests()
+        };
+
+        let issued = generate_api_key_with_kdf(&config.api_key_kdf);
+        assert!(ensure_api_key_kdf_accepted(&issued, &config).is_ok());
+        let legacy = crate::crypto::generate_api_key();
+        assert!(ensure_api_key_kdf_accepted(&legacy, &config).is_ok());
+
+        // A key naming its own (costlier) parameters is refused without being hashed
+        let chosen = generate_api_key_with_kdf(&ApiKeyKdf::Argon2id(Argon2Params {
+            memory_kib: crate::constants::ARGON2_MAX_MEMORY_KIB,
+            ..configured
+        }));
+        assert!(matches!(
+            ensure_api_key_kdf_accepted(&chosen, &config),
+            Err(GoudChainError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_token_scope() {
+        let config = Config::for_tests();
+        let token = generate_session_token(
+            "test-account".to_string(),
+            b"test_api_key_32_bytes_exactly_ok",
+            "test-hash".to_string(),
+            TokenScope::Read,
+            SessionLifetime::standard(&config),
+            &config,
+        )
+        .unwrap();
+
+        let auth = AuthMethod::SessionToken(verify_session_token(&token, &config).unwrap());
+        assert_eq!(auth.scope(), TokenScope::Read);
+        assert!(auth.require_scope(TokenScope::Read).is_ok());
+        assert!(matches!(
+            auth.require_scope(TokenScope::Write),
+            Err(GoudChainError::InsufficientScope(_))
+        ));
+
+        assert!(AuthMethod::ApiKey(Zeroizing::new(vec![0u8; 32]))
+            .require_scope(TokenScope::Write)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_requested_session_lifetime() {
+        let config = Config::for_tests();
+
+        let default = SessionLifetime::requested(None, false, &config).unwrap();
+        assert_eq!(default.ttl_seconds, SESSION_EXPIRY_SECONDS);
+        let short = SessionLifetime::requested(Some(300), false, &config).unwrap();
+        assert_eq!(short.ttl_seconds, 300);
+        assert!(SessionLifetime::requested(Some(30), false, &config).is_err());
+        assert!(SessionLifetime::requested(
+            Some(config.max_session_ttl_seconds + 1),
+            false,
+            &config
+        )
+        .is_err());
+
+        // Remember-me tokens may outlive normal sessions up to their own cap
+        let remember = SessionLifetime::requested(None, true, &config).unwrap();
+        assert_eq!(remember.ttl_seconds, config.remember_me_ttl_seconds);
+        assert!(SessionLifetime::requested(
+            Some(config.remember_me_ttl_seconds + 1),
+            true,
+            &config
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_remember_me_token_is_read_only_and_capped() {
+        let config = Config::for_tests();
+        let lifetime = SessionLifetime::requested(None, true, &config).unwrap();
+        let token = generate_session_token(
+            "test-account".to_string(),
+            b"test_api_key_32_bytes_exactly_ok",
+            "test-hash".to_string(),
+            TokenScope::All,
+            lifetime,
+            &config,
+        )
+        .unwrap();
+
+        let claims = verify_session_token(&token, &config).unwrap();
+        assert!(claims.remember_me);
+        assert_eq!(claims.scope, TokenScope::Read);
+        assert_eq!(SessionLifetime::of(&claims), lifetime);
+
+        // Lowering the cap rejects tokens issued under the old one
+        let mut lowered = config.clone();
+        lowered.remember_me_ttl_seconds = lifetime.ttl_seconds - 1;
+        assert!(matches!(
+            verify_session_token(&token, &lowered),
+            Err(GoudChainError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_claims_without_scope_default_to_all() {
+        let claims: Claims = serde_json::from_value(serde_json::json!({
+            "sub": "test-account",
+            "api_key_hash": "test-hash",
+            "encrypted_api_key": "",
+            "exp": 0,
+            "iat": 0,
+            "jti": "legacy"
+        }))
+        .unwrap();
+        assert_eq!(claims.scope, TokenScope::All);
+    }
 }
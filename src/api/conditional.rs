@@ -0,0 +1,72 @@
+//! Conditional GET support for polled read endpoints.
+//! Layer 5: Presentation - ETag generation and If-None-Match handling.
+//!
+//! Handlers derive a cheap ETag from the state that determines their body (usually the
+//! latest block hash) and answer 304 Not Modified before doing any serialization work.
+
+use axum::http::header::{ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+
+/// Build a strong ETag from the values a response body depends on
+pub fn etag_for(parts: &[&str]) -> String {
+    let mut hasher = blake3::Hasher::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        hasher.update(&[0]); // Separator so ["ab", "c"] and ["a", "bc"] differ
+    }
+    format!("\"{}\"", hasher.finalize().to_hex())
+}
+
+/// True if the request's If-None-Match header lists `etag` (weak comparison, as RFC 9110 requires)
+pub fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+/// 304 Not Modified carrying the current ETag
+pub fn not_modified(etag: &str) -> Response {
+    (StatusCode::NOT_MODIFIED, [(ETAG, etag.to_string())]).into_response()
+}
+
+/// Attach the ETag to a full response so clients can revalidate next time
+pub fn with_etag(response: impl IntoResponse, etag: &str) -> Response {
+    ([(ETAG, etag.to_string())], response).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_etag_depends_on_every_part() {
+        let etag = etag_for(&["hash", "0"]);
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+        assert_eq!(etag, etag_for(&["hash", "0"]));
+        assert_ne!(etag, etag_for(&["hash", "1"]));
+        assert_ne!(etag_for(&["ab", "c"]), etag_for(&["a", "bc"]));
+    }
+
+    #[test]
+    fn test_if_none_match_parsing() {
+        let etag = etag_for(&["hash"]);
+        let mut headers = HeaderMap::new();
+        assert!(!if_none_match(&headers, &etag));
+
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_static("\"other\""));
+        assert!(!if_none_match(&headers, &etag));
+
+        let listed = format!("\"other\", W/{}", etag);
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_str(&listed).unwrap());
+        assert!(if_none_match(&headers, &etag));
+
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_static("*"));
+        assert!(if_none_match(&headers, &etag));
+    }
+}
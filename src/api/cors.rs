@@ -0,0 +1,131 @@
+//! CORS for nodes serving browsers directly.
+//! Layer 5: Presentation - `CorsLayer` built from `CorsConfig` (ENABLE_CORS=true).
+//!
+//! Behind nginx the proxy adds the CORS headers (nginx/cors.conf), so the layer stays off by
+//! default; enabling it there would send `Access-Control-Allow-Origin` twice and browsers reject
+//! duplicated values. Enable it only when clients reach the node without the proxy.
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::config::CorsConfig;
+use crate::constants::{
+    DEPRECATION_HEADER, IDEMPOTENCY_KEY_HEADER, IDEMPOTENCY_REPLAYED_HEADER, REQUEST_ID_HEADER,
+    REQUEST_NONCE_HEADER, REQUEST_SIGNATURE_HEADER, REQUEST_TIMESTAMP_HEADER,
+};
+
+/// Request headers browser clients may send
+const ALLOWED_HEADERS: [&str; 7] = [
+    "content-type",
+    "authorization",
+    REQUEST_SIGNATURE_HEADER,
+    REQUEST_NONCE_HEADER,
+    REQUEST_TIMESTAMP_HEADER,
+    REQUEST_ID_HEADER,
+    IDEMPOTENCY_KEY_HEADER,
+];
+
+/// Response headers browser clients may read
+const EXPOSED_HEADERS: [&str; 8] = [
+    REQUEST_ID_HEADER,
+    IDEMPOTENCY_REPLAYED_HEADER,
+    DEPRECATION_HEADER,
+    "link",
+    "etag",
+    "x-ratelimit-limit",
+    "x-ratelimit-remaining",
+    "x-ratelimit-reset",
+];
+
+/// Build the CORS layer for the configured origins
+pub fn cors_layer(config: &CorsConfig) -> CorsLayer {
+    let allow_origin = if config.allows_any_origin() {
+        AllowOrigin::any()
+    } else {
+        // Origins are validated at startup, so every entry is a valid header value
+        AllowOrigin::list(
+            config
+                .allowed_origins
+                .iter()
+                .filter_map(|origin| HeaderValue::from_str(origin).ok()),
+        )
+    };
+
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::DELETE,
+            Method::OPTIONS,
+        ])
+        .allow_headers(ALLOWED_HEADERS.map(HeaderName::from_static))
+        .expose_headers(EXPOSED_HEADERS.map(HeaderName::from_static))
+        .max_age(std::time::Duration::from_secs(3600))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::header;
+    use axum::{body::Body, extract::Request, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app(origins: &[&str]) -> Router {
+        let config = CorsConfig {
+            allowed_origins: origins.iter().map(|origin| origin.to_string()).collect(),
+        };
+        Router::new()
+            .route("/health", get(|| async { "ok" }))
+            .layer(cors_layer(&config))
+    }
+
+    async fn allowed_origin(app: Router, request: Request<Body>) -> Option<String> {
+        let response = app.oneshot(request).await.unwrap();
+        response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .map(|value| value.to_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn test_preflight_allows_listed_origin_only() {
+        let preflight = |origin: &str| {
+            Request::builder()
+                .method(Method::OPTIONS)
+                .uri("/health")
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                .header(
+                    header::ACCESS_CONTROL_REQUEST_HEADERS,
+                    "authorization,x-nonce",
+                )
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let listed = app(&["http://localhost:3000"]);
+        assert_eq!(
+            allowed_origin(listed.clone(), preflight("http://localhost:3000")).await,
+            Some("http://localhost:3000".to_string())
+        );
+        assert_eq!(
+            allowed_origin(listed, preflight("https://evil.example")).await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_any_origin_gets_wildcard() {
+        let request = Request::builder()
+            .uri("/health")
+            .header(header::ORIGIN, "https://app.example.com")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(
+            allowed_origin(app(&["*"]), request).await,
+            Some("*".to_string())
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! Idempotency keys for block-creating writes.
+//! Layer 5: Presentation - `Idempotency-Key` middleware for `/account/create` and `/data/submit`.
+//!
+//! Every successful write produces a block, so a client that timed out can't tell whether a
+//! retry would duplicate it. With an `Idempotency-Key` header the first successful response is
+//! stored for IDEMPOTENCY_KEY_TTL_SECONDS and a retry with the same key and body gets it back
+//! (marked `Idempotency-Replayed: true`) without running the handler again. Failed responses are
+//! not stored, so a retry after an error runs normally. Responses carrying a secret (the API key
+//! from `/account/create`) are never stored: a retry gets `409 IDEMPOTENCY_KEY_USED` naming the
+//! account instead.
+
+use axum::body::Body;
+use axum::extract::{OriginalUri, Request};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::{error, info};
+
+use crate::api::security_audit::extract_client_ip;
+use crate::config::Config;
+use crate::constants::{
+    IDEMPOTENCY_KEY_HEADER, IDEMPOTENCY_REPLAYED_HEADER, MAX_IDEMPOTENCY_KEY_LENGTH,
+};
+use crate::storage::idempotency_store::{IdempotencyReservation, StoredResponse};
+use crate::storage::IdempotencyStore;
+use crate::types::GoudChainError;
+
+/// Response extension marking a response that carries a secret (a newly issued API key)
+/// The middleware stores only the account ID for it, so the secret never reaches disk
+#[derive(Debug, Clone)]
+pub struct SecretResponse {
+    pub account_id: String,
+}
+
+/// Accept only short, printable ASCII keys
+fn is_valid_idempotency_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_IDEMPOTENCY_KEY_LENGTH
+        && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Hash the key together with the endpoint and the caller, so keys never collide across
+/// endpoints or clients (the credential for authenticated calls, the client IP otherwise)
+fn scoped_key_hash(method: &str, uri: &str, headers: &HeaderMap, key: &str) -> String {
+    let caller = headers
+        .get(header::AUTHORIZATION)
+        .map(|value| value.as_bytes().to_vec())
+        .unwrap_or_else(|| format!("ip:{}", extract_client_ip(headers)).into_bytes());
+
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(uri.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(&caller);
+    hasher.update(b"\n");
+    hasher.update(key.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Rebuild a stored response for a retry
+fn replay(stored: StoredResponse) -> Response {
+    let status = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let mut response = (status, stored.body).into_response();
+    let headers = response.headers_mut();
+    if let Some(value) = stored
+        .content_type
+        .and_then(|content_type| HeaderValue::from_str(&content_type).ok())
+    {
+        headers.insert(header::CONTENT_TYPE, value);
+    }
+    headers.insert(
+        IDEMPOTENCY_REPLAYED_HEADER,
+        HeaderValue::from_static("true"),
+    );
+    response
+}
+
+/// Replay the stored response for a repeated `Idempotency-Key`, or run the request and store its
+/// response if it succeeds
+///
+/// Requests without the header pass straight through. Needs the `IdempotencyStore` and `Config`
+/// extensions; without them the middleware is a no-op.
+pub async fn idempotency_middleware(request: Request, next: Next) -> Response {
+    if !request.headers().contains_key(IDEMPOTENCY_KEY_HEADER) {
+        return next.run(request).await;
+    }
+    let key = request
+        .headers()
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|key| is_valid_idempotency_key(key))
+        .map(str::to_string);
+    let Some(key) = key else {
+        return GoudChainError::InvalidRequestBody(format!(
+            "Idempotency-Key must be 1-{} printable ASCII characters",
+            MAX_IDEMPOTENCY_KEY_LENGTH
+        ))
+        .into_response();
+    };
+
+    let store = request.extensions().get::<Arc<IdempotencyStore>>().cloned();
+    let config = request.extensions().get::<Arc<Config>>().cloned();
+    let (Some(store), Some(config)) = (store, config) else {
+        return next.run(request).await;
+    };
+
+    let (parts, body) = request.into_parts();
+    // Nested routers see the path without their prefix; scope by the full one
+    let uri = parts
+        .extensions
+        .get::<OriginalUri>()
+        .map_or(&parts.uri, |original| &original.0);
+    let key_hash = scoped_key_hash(
+        parts.method.as_str(),
+        &uri.to_string(),
+        &parts.headers,
+        &key,
+    );
+
+    // Read the body under the same limit the handler's extractor would apply
+    let body = match axum::body::to_bytes(body, config.max_request_body_bytes()).await {
+        Ok(body) => body,
+        Err(_) => return StatusCode::PAYLOAD_TOO_LARGE.into_response(),
+    };
+    let fingerprint = hex::encode(Sha256::digest(&body));
+
+    let Some(reservation) = IdempotencyStore::reserve(&store, &key_hash) else {
+        return GoudChainError::IdempotencyKeyInUse.into_response();
+    };
+
+    match store.get(&key_hash) {
+        Ok(Some(stored)) if stored.fingerprint == fingerprint => {
+            info!(
+                status = stored.status,
+                "Replaying response for idempotency key"
+            );
+            return replay(stored);
+        }
+        Ok(Some(_)) => return GoudChainError::IdempotencyKeyMismatch.into_response(),
+        Ok(None) => {}
+        Err(e) => {
+            error!(error = %e, "Failed to look up idempotency key");
+            return e.into_response();
+        }
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    if !response.status().is_success() {
+        return response;
+    }
+
+    // The secret goes to this caller only; retries learn which account was created
+    if let Some(SecretResponse { account_id }) =
+        response.extensions().get::<SecretResponse>().cloned()
+    {
+        let used = GoudChainError::IdempotencyKeyUsed { account_id }.into_response();
+        store_response(reservation, fingerprint, used).await;
+        return response;
+    }
+
+    store_response(reservation, fingerprint, response).await
+}
+
+/// Buffer a response, record it for the reserved key and hand it back for sending
+async fn store_response(
+    reservation: IdempotencyReservation,
+    fingerprint: String,
+    response: Response,
+) -> Response {
+    let (parts, body) = response.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            error!(error = %e, "Failed to buffer response for idempotency key");
+            return GoudChainError::Internal("Failed to read response body".to_string())
+                .into_response();
+        }
+    };
+    let content_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    if let Err(e) = reservation.complete(
+        fingerprint,
+        parts.status.as_u16(),
+        content_type,
+        body.to_vec(),
+    ) {
+        error!(error = %e, "Failed to store response for idempotency key");
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idempotency_key_validation() {
+        assert!(is_valid_idempotency_key(
+            "5f0c2b9e-8a4d-4c1e-9f1a-2b3c4d5e6f70"
+        ));
+        assert!(!is_valid_idempotency_key(""));
+        assert!(!is_valid_idempotency_key("has space"));
+        assert!(!is_valid_idempotency_key(
+            &"k".repeat(MAX_IDEMPOTENCY_KEY_LENGTH + 1)
+        ));
+    }
+
+    #[test]
+    fn test_key_hash_is_scoped_to_caller_and_endpoint() {
+        let mut alice = HeaderMap::new();
+        alice.insert(header::AUTHORIZATION, "Bearer alice".parse().unwrap());
+        let mut bob = HeaderMap::new();
+        bob.insert(header::AUTHORIZATION, "Bearer bob".parse().unwrap());
+
+        let base = scoped_key_hash("POST", "/data/submit", &alice, "k");
+        assert_eq!(base, scoped_key_hash("POST", "/data/submit", &alice, "k"));
+        assert_ne!(base, scoped_key_hash("POST", "/data/submit", &bob, "k"));
+        assert_ne!(
+            base,
+            scoped_key_hash("POST", "/account/create", &alice, "k")
+        );
+        assert_ne!(base, scoped_key_hash("POST", "/data/submit", &alice, "k2"));
+    }
+
+    #[tokio::test]
+    async fn test_secret_responses_are_not_stored() {
+        use axum::routing::post;
+        use axum::{Extension, Router};
+        use rocksdb::{Options, DB};
+        use tower::ServiceExt;
+
+        let path = format!("/tmp/goud_idempotency_mw_test_{}", rand::random::<u64>());
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let store = Arc::new(IdempotencyStore::new(Arc::new(
+            DB::open(&opts, path).unwrap(),
+        )));
+
+        let app = Router::new()
+            .route(
+                "/account/create",
+                post(|| async {
+                    let mut response =
+                        (StatusCode::CREATED, "{\"api_key\":\"secret\"}").into_response();
+                    response.extensions_mut().insert(SecretResponse {
+                        account_id: "acct-1".to_string(),
+                    });
+                    response
+                }),
+            )
+            .layer(axum::middleware::from_fn(idempotency_middleware))
+            .layer(Extension(Arc::clone(&store)))
+            .layer(Extension(Arc::new(Config::for_tests())));
+        let request = || {
+            Request::post("/account/create")
+                .header(IDEMPOTENCY_KEY_HEADER, "k")
+                .body(Body::from("{}"))
+                .unwrap()
+        };
+
+        let first = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(first.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(first.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"{\"api_key\":\"secret\"}");
+
+        let retry = app.oneshot(request()).await.unwrap();
+        assert_eq!(retry.status(), StatusCode::CONFLICT);
+        let body = axum::body::to_bytes(retry.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "IDEMPOTENCY_KEY_USED");
+        assert_eq!(body["details"]["account_id"], "acct-1");
+        assert!(!body.to_string().contains("secret"));
+    }
+}
@@ -1,24 +1,201 @@
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::net::TcpStream;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+
+use axum::body::Bytes;
+use axum::http::HeaderMap;
+use http_body_util::{BodyExt, Full};
+use hyper::{header, Request, Uri};
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::TokioExecutor;
+use once_cell::sync::Lazy;
+use tokio::sync::RwLock;
 use tokio::time::{sleep, Duration};
 use tracing::{info, warn};
 
+use super::request_id::current_request_id;
+use crate::constants::{
+    FORWARD_POOL_IDLE_TIMEOUT_SECONDS, FORWARD_POOL_MAX_IDLE_PER_HOST,
+    HTTP_CONNECT_TIMEOUT_SECONDS, HTTP_RESPONSE_TIMEOUT_SECONDS, REQUEST_ID_HEADER,
+    REQUEST_NONCE_HEADER, REQUEST_SIGNATURE_HEADER, REQUEST_TIMESTAMP_HEADER,
+    VALIDATOR_FORWARD_ATTEMPTS, VALIDATOR_FORWARD_BACKOFF_MS,
+};
+use crate::domain::Blockchain;
 use crate::types::{GoudChainError, Result};
 
-/// Forward an HTTP request to another node (async)
-/// Used when current node is not the PoA validator
-pub async fn forward_request_to_node(
-    target_node: &str,
+/// Extract auth header value for forwarding
+pub fn extract_auth_header(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string())
+}
+
+/// Collect the replay-protection headers to pass through when forwarding to the validator
+pub fn extract_replay_headers(headers: &HeaderMap) -> Vec<(&'static str, String)> {
+    [
+        REQUEST_SIGNATURE_HEADER,
+        REQUEST_NONCE_HEADER,
+        REQUEST_TIMESTAMP_HEADER,
+    ]
+    .into_iter()
+    .filter_map(|name| {
+        headers
+            .get(name)
+            .and_then(|h| h.to_str().ok())
+            .map(|value| (name, value.to_string()))
+    })
+    .collect()
+}
+
+/// Forward a write to the validator for the next block, retrying if it is unreachable
+///
+/// The validator is re-resolved before every attempt, so a block produced while we back off
+/// moves the retry to the new validator. HTTP responses (including auth failures) are returned
+/// as-is. Only failures to connect are retried, ending in `ValidatorUnavailable`: once the
+/// request was sent the validator may already have committed it, so a resend could fail replay
+/// protection or duplicate the write, and that error is returned instead.
+pub async fn forward_to_validator(
+    blockchain: &RwLock<Blockchain>,
     method: &str,
     path: &str,
     body: &str,
     content_type: &str,
+    auth_header: Option<&str>,
+    extra_headers: &[(&str, String)],
 ) -> Result<(u16, String)> {
-    forward_request_with_headers(target_node, method, path, body, content_type, None, None).await
+    let mut last_failure = String::new();
+
+    for attempt in 0..VALIDATOR_FORWARD_ATTEMPTS {
+        if attempt > 0 {
+            let backoff = VALIDATOR_FORWARD_BACKOFF_MS * 2_u64.pow(attempt - 1);
+            warn!(
+                attempt = attempt + 1,
+                backoff_ms = backoff,
+                "Retrying forward to validator"
+            );
+            sleep(Duration::from_millis(backoff)).await;
+        }
+
+        let (validator, validator_addr) = {
+            let chain = blockchain.read().await;
+            let next_block_number = chain.chain.last().map(|b| b.index + 1).unwrap_or(1);
+            let validator = chain
+                .validator_config
+                .get_validator_for_block(next_block_number);
+            let validator_addr = chain.validator_config.get_validator_address(&validator);
+            (validator, validator_addr)
+        };
+        let validator_addr = validator_addr
+            .ok_or_else(|| GoudChainError::Internal(format!("Unknown validator: {}", validator)))?;
+
+        match forward_request_with_headers(
+            &validator_addr,
+            method,
+            path,
+            body,
+            content_type,
+            auth_header,
+            extra_headers,
+        )
+        .await
+        {
+            Ok(response) => return Ok(response),
+            Err(GoudChainError::PeerUnreachable(e)) => {
+                warn!(validator = %validator, error = %e, "Forward to validator failed");
+                last_failure = format!("{} unreachable: {}", validator, e);
+            }
+            Err(e) => {
+                warn!(validator = %validator, error = %e, "Forward to validator failed after sending");
+                return Err(e);
+            }
+        }
+    }
+
+    Err(GoudChainError::ValidatorUnavailable(format!(
+        "{} (after {} attempts)",
+        last_failure, VALIDATOR_FORWARD_ATTEMPTS
+    )))
+}
+
+/// Pool-wide forwarding counters, exported through `/metrics/prometheus`
+struct ForwardPoolStats {
+    requests: AtomicU64,
+    connections_opened: AtomicU64,
+    failures: AtomicU64,
+}
+
+static FORWARD_POOL_STATS: ForwardPoolStats = ForwardPoolStats {
+    requests: AtomicU64::new(0),
+    connections_opened: AtomicU64::new(0),
+    failures: AtomicU64::new(0),
+};
+
+/// HTTP connector that counts every new TCP connection the pool has to open
+#[derive(Clone)]
+struct CountingConnector {
+    inner: HttpConnector,
 }
 
-/// Forward an HTTP request with optional Authorization and X-Signature headers (async)
-/// Used when current node is not the PoA validator
+impl tower::Service<Uri> for CountingConnector {
+    type Response = <HttpConnector as tower::Service<Uri>>::Response;
+    type Error = <HttpConnector as tower::Service<Uri>>::Error;
+    type Future = <HttpConnector as tower::Service<Uri>>::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        FORWARD_POOL_STATS
+            .connections_opened
+            .fetch_add(1, Ordering::Relaxed);
+        self.inner.call(uri)
+    }
+}
+
+/// Keep-alive client shared by every forward, so repeated writes reuse validator connections
+static FORWARD_CLIENT: Lazy<Client<CountingConnector, Full<Bytes>>> = Lazy::new(|| {
+    let mut connector = HttpConnector::new();
+    connector.set_nodelay(true);
+    connector.set_connect_timeout(Some(Duration::from_secs(HTTP_CONNECT_TIMEOUT_SECONDS)));
+
+    Client::builder(TokioExecutor::new())
+        .pool_idle_timeout(Duration::from_secs(FORWARD_POOL_IDLE_TIMEOUT_SECONDS))
+        .pool_max_idle_per_host(FORWARD_POOL_MAX_IDLE_PER_HOST)
+        .build(CountingConnector { inner: connector })
+});
+
+/// Render forwarding pool counters in Prometheus text format
+pub fn forward_pool_prometheus_metrics() -> String {
+    let requests = FORWARD_POOL_STATS.requests.load(Ordering::Relaxed);
+    let connections_opened = FORWARD_POOL_STATS
+        .connections_opened
+        .load(Ordering::Relaxed);
+    let failures = FORWARD_POOL_STATS.failures.load(Ordering::Relaxed);
+
+    format!(
+        "# HELP goud_forward_requests_total Requests forwarded to validator nodes\n\
+         # TYPE goud_forward_requests_total counter\n\
+         goud_forward_requests_total {}\n\
+         # HELP goud_forward_connections_opened_total New connections opened by the forwarding pool\n\
+         # TYPE goud_forward_connections_opened_total counter\n\
+         goud_forward_connections_opened_total {}\n\
+         # HELP goud_forward_connections_reused_total Forwards served by an idle pooled connection\n\
+         # TYPE goud_forward_connections_reused_total counter\n\
+         goud_forward_connections_reused_total {}\n\
+         # HELP goud_forward_failures_total Forwards that failed before a response was received\n\
+         # TYPE goud_forward_failures_total counter\n\
+         goud_forward_failures_total {}\n",
+        requests,
+        connections_opened,
+        requests.saturating_sub(connections_opened),
+        failures
+    )
+}
+
+/// Forward an HTTP request with optional Authorization and extra passthrough headers (async)
+/// Used when current node is not the PoA validator; the current request ID travels along
 pub async fn forward_request_with_headers(
     target_node: &str,
     method: &str,
@@ -26,7 +203,7 @@ pub async fn forward_request_with_headers(
     body: &str,
     content_type: &str,
     auth_header: Option<&str>,
-    signature_header: Option<&str>,
+    extra_headers: &[(&str, String)],
 ) -> Result<(u16, String)> {
     info!(
         target_node = %target_node,
@@ -35,11 +212,21 @@ pub async fn forward_request_with_headers(
     );
 
     // Parse target node URL (format: "node_name:port" or "http://hostname:port")
-    let target_addr = if target_node.starts_with("http://") {
-        target_node.trim_start_matches("http://").to_string()
-    } else {
-        target_node.to_string()
-    };
+    let target_addr = target_node.trim_start_matches("http://");
+    let uri: Uri = format!("http://{}{}", target_addr, path)
+        .parse()
+        .map_err(|e| GoudChainError::Internal(format!("Invalid forward URI: {}", e)))?;
+
+    // Keep the caller's correlation ID so the validator logs under the same one
+    let mut headers = extra_headers.to_vec();
+    if let Some(request_id) = current_request_id() {
+        if !headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case(REQUEST_ID_HEADER))
+        {
+            headers.push((REQUEST_ID_HEADER, request_id));
+        }
+    }
 
     // Retry logic for connection with exponential backoff
     use crate::constants::{HTTP_INITIAL_BACKOFF_MS, HTTP_MAX_BACKOFF_MS, HTTP_MAX_RETRIES};
@@ -58,167 +245,216 @@ pub async fn forward_request_with_headers(
             sleep(Duration::from_millis(backoff)).await;
         }
 
-        match TcpStream::connect(&target_addr).await {
-            Ok(stream) => {
-                return perform_http_request(
-                    stream,
-                    method,
-                    path,
-                    body,
-                    content_type,
-                    &target_addr,
-                    auth_header,
-                    signature_header,
-                )
-                .await;
+        FORWARD_POOL_STATS.requests.fetch_add(1, Ordering::Relaxed);
+        let request = build_request(
+            method,
+            uri.clone(),
+            body,
+            content_type,
+            auth_header,
+            &headers,
+        )?;
+
+        match perform_http_request(request).await {
+            Err(ForwardError::Connect(e)) => {
+                // Connection never opened, so the request is safe to resend
+                FORWARD_POOL_STATS.failures.fetch_add(1, Ordering::Relaxed);
+                last_error = Some(e);
             }
-            Err(e) => {
-                // Check if error is transient
-                if e.kind() == std::io::ErrorKind::WouldBlock
-                    || e.kind() == std::io::ErrorKind::ConnectionRefused
-                {
-                    last_error = Some(e);
-                    continue;
-                }
-
-                // For other errors, fail immediately
+            Err(ForwardError::Failed(e)) => {
+                FORWARD_POOL_STATS.failures.fetch_add(1, Ordering::Relaxed);
                 return Err(GoudChainError::PeerConnectionFailed(format!(
-                    "Failed to connect to {}: {}",
+                    "Request to {} failed: {}",
                     target_addr, e
                 )));
             }
+            Ok(response) => return Ok(response),
         }
     }
 
-    // All retries exhausted
-    Err(GoudChainError::PeerConnectionFailed(format!(
+    // All retries exhausted without ever connecting
+    Err(GoudChainError::PeerUnreachable(format!(
         "Failed to connect to {} after {} retries: {}",
         target_addr,
         HTTP_MAX_RETRIES,
-        last_error.unwrap()
+        last_error.unwrap_or_default()
     )))
 }
 
-/// Perform the actual HTTP request over an established TCP stream (async)
-#[allow(clippy::too_many_arguments)]
-async fn perform_http_request(
-    mut stream: TcpStream,
+/// Why a pooled request failed: connect failures can be retried, anything later cannot
+enum ForwardError {
+    Connect(String),
+    Failed(String),
+}
+
+/// Build the forwarded request with optional Authorization and passthrough headers
+fn build_request(
     method: &str,
-    path: &str,
+    uri: Uri,
     body: &str,
     content_type: &str,
-    target_addr: &str,
     auth_header: Option<&str>,
-    signature_header: Option<&str>,
-) -> Result<(u16, String)> {
-    // Build HTTP request with optional Authorization and X-Signature headers
-    let auth_line = auth_header
-        .map(|h| format!("Authorization: {}\r\n", h))
-        .unwrap_or_default();
-
-    let signature_line = signature_header
-        .map(|h| format!("X-Signature: {}\r\n", h))
-        .unwrap_or_default();
-
-    let request = format!(
-        "{} {} HTTP/1.1\r\n\
-         Host: {}\r\n\
-         Content-Type: {}\r\n\
-         Content-Length: {}\r\n\
-         {}{}Connection: close\r\n\
-         \r\n\
-         {}",
-        method,
-        path,
-        target_addr.split(':').next().unwrap_or("localhost"),
-        content_type,
-        body.len(),
-        auth_line,
-        signature_line,
-        body
-    );
-
-    // Send request with timeout
-    tokio::time::timeout(
-        Duration::from_secs(10),
-        stream.write_all(request.as_bytes()),
-    )
-    .await
-    .map_err(|_| GoudChainError::Internal("Write timeout".to_string()))?
-    .map_err(GoudChainError::IoError)?;
-
-    // Read response with timeout
-    let mut response = String::new();
-    tokio::time::timeout(
-        Duration::from_secs(30),
-        stream.read_to_string(&mut response),
-    )
-    .await
-    .map_err(|_| GoudChainError::Internal("Read timeout".to_string()))?
-    .map_err(GoudChainError::IoError)?;
-
-    // Parse HTTP response
-    parse_http_response(&response)
-}
+    extra_headers: &[(&str, String)],
+) -> Result<Request<Full<Bytes>>> {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, content_type);
 
-/// Parse HTTP response to extract status code and body
-fn parse_http_response(response: &str) -> Result<(u16, String)> {
-    // Split headers and body
-    let parts: Vec<&str> = response.splitn(2, "\r\n\r\n").collect();
-    if parts.is_empty() {
-        return Err(GoudChainError::Internal(
-            "Invalid HTTP response: no headers".to_string(),
-        ));
+    if let Some(auth) = auth_header {
+        builder = builder.header(header::AUTHORIZATION, auth);
+    }
+    for (name, value) in extra_headers {
+        builder = builder.header(*name, value.as_str());
     }
 
-    let headers = parts[0];
-    let body = parts.get(1).unwrap_or(&"").to_string();
+    builder
+        .body(Full::new(Bytes::copy_from_slice(body.as_bytes())))
+        .map_err(|e| GoudChainError::Internal(format!("Invalid forward request: {}", e)))
+}
 
-    // Parse status line (e.g., "HTTP/1.1 200 OK")
-    let status_line = headers.lines().next().ok_or_else(|| {
-        GoudChainError::Internal("Invalid HTTP response: no status line".to_string())
-    })?;
+/// Send the request over a pooled keep-alive connection and collect the response body
+async fn perform_http_request(
+    request: Request<Full<Bytes>>,
+) -> std::result::Result<(u16, String), ForwardError> {
+    let exchange = async {
+        let response = FORWARD_CLIENT.request(request).await.map_err(|e| {
+            if e.is_connect() {
+                ForwardError::Connect(e.to_string())
+            } else {
+                ForwardError::Failed(e.to_string())
+            }
+        })?;
 
-    let status_parts: Vec<&str> = status_line.split_whitespace().collect();
-    if status_parts.len() < 2 {
-        return Err(GoudChainError::Internal(format!(
-            "Invalid HTTP status line: {}",
-            status_line
-        )));
-    }
+        let status = response.status().as_u16();
+        let body = response
+            .into_body()
+            .collect()
+            .await
+            .map_err(|e| ForwardError::Failed(e.to_string()))?
+            .to_bytes();
 
-    let status_code = status_parts[1]
-        .parse::<u16>()
-        .map_err(|e| GoudChainError::Internal(format!("Invalid status code: {}", e)))?;
+        Ok((status, String::from_utf8_lossy(&body).into_owned()))
+    };
 
-    Ok((status_code, body))
+    tokio::time::timeout(Duration::from_secs(HTTP_RESPONSE_TIMEOUT_SECONDS), exchange)
+        .await
+        .map_err(|_| ForwardError::Failed("Response timeout".to_string()))?
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_parse_http_response() {
-        let response = "HTTP/1.1 200 OK\r\n\
-                       Content-Type: application/json\r\n\
-                       \r\n\
-                       {\"status\":\"success\"}";
+    #[tokio::test]
+    async fn test_unreachable_validator_is_reported_as_unavailable() {
+        // Reserve a port, then close it so every connection is refused
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let closed_addr = listener.local_addr().unwrap().to_string();
+        drop(listener);
 
-        let (status, body) = parse_http_response(response).unwrap();
-        assert_eq!(status, 200);
-        assert_eq!(body, "{\"status\":\"success\"}");
+        let mut config = crate::config::ValidatorConfig::single("node1");
+        config
+            .validator_to_address
+            .insert("Validator_1".to_string(), closed_addr);
+        let blockchain = RwLock::new(Blockchain::new("node1".to_string(), config).unwrap());
+
+        let err = forward_to_validator(
+            &blockchain,
+            "POST",
+            "/account/create",
+            "{}",
+            "application/json",
+            None,
+            &[],
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, GoudChainError::ValidatorUnavailable(_)));
+        assert_eq!(err.status_code(), 503);
     }
 
-    #[test]
-    fn test_parse_http_response_422() {
-        let response = "HTTP/1.1 422 Unprocessable Entity\r\n\
-                       Content-Type: application/json\r\n\
-                       \r\n\
-                       {\"error\":\"Not authorized\"}";
+    #[tokio::test]
+    async fn test_sent_forward_is_not_retried() {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Arc;
+        use tokio::io::AsyncReadExt;
+
+        // The validator accepts the request, then drops the connection without answering
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let accepted = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&accepted);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf).await;
+            }
+        });
+
+        let mut config = crate::config::ValidatorConfig::single("node1");
+        config
+            .validator_to_address
+            .insert("Validator_1".to_string(), addr);
+        let blockchain = RwLock::new(Blockchain::new("node1".to_string(), config).unwrap());
+
+        let err = forward_to_validator(
+            &blockchain,
+            "POST",
+            "/account/create",
+            "{}",
+            "application/json",
+            None,
+            &[],
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, GoudChainError::PeerConnectionFailed(_)));
+        assert_eq!(accepted.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_forwards_reuse_pooled_connection() {
+        use axum::extract::ConnectInfo;
+        use std::net::SocketAddr;
+
+        // Echo the client's port: the same port twice means the same TCP connection
+        let app = axum::Router::new().route(
+            "/echo",
+            axum::routing::post(|ConnectInfo(peer): ConnectInfo<SocketAddr>| async move {
+                peer.port().to_string()
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .unwrap();
+        });
+
+        let extra_headers = [("X-Forwarded-For", "10.0.0.1".to_string())];
+        let forward = || {
+            forward_request_with_headers(
+                &addr,
+                "POST",
+                "/echo",
+                "{}",
+                "application/json",
+                Some("Bearer test"),
+                &extra_headers,
+            )
+        };
+        let (first_status, first_port) = forward().await.unwrap();
+        let (second_status, second_port) = forward().await.unwrap();
 
-        let (status, body) = parse_http_response(response).unwrap();
-        assert_eq!(status, 422);
-        assert_eq!(body, "{\"error\":\"Not authorized\"}");
+        assert_eq!(first_status, 200);
+        assert_eq!(second_status, 200);
+        assert_eq!(first_port, second_port);
+        assert!(forward_pool_prometheus_metrics().contains("goud_forward_requests_total"));
     }
 }
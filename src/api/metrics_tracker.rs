@@ -0,0 +1,130 @@
+//! Operations-per-second tracking for the metrics endpoints
+//!
+//! A ring of one-second buckets covering `OPERATIONS_RATE_WINDOW_SECONDS`. Each bucket
+//! remembers which second it holds; a write that lands on a stale bucket rotates it
+//! (resets the count) before incrementing, so the hot path is a couple of atomics
+//! and reads simply skip buckets that fell out of the window.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::constants::OPERATIONS_RATE_WINDOW_SECONDS;
+
+struct Bucket {
+    second: AtomicU64,
+    count: AtomicU64,
+}
+
+/// Sliding-window counter of account and collection writes
+pub struct OperationsTracker {
+    buckets: [Bucket; OPERATIONS_RATE_WINDOW_SECONDS],
+}
+
+impl OperationsTracker {
+    pub const fn new() -> Self {
+        Self {
+            buckets: [const {
+                Bucket {
+                    second: AtomicU64::new(0),
+                    count: AtomicU64::new(0),
+                }
+            }; OPERATIONS_RATE_WINDOW_SECONDS],
+        }
+    }
+
+    /// Record `count` operations written now
+    pub fn record(&self, count: u64) {
+        self.record_at(count, unix_seconds());
+    }
+
+    /// Average operations per second over the sliding window
+    pub fn operations_per_second(&self) -> f64 {
+        self.operations_per_second_at(unix_seconds())
+    }
+
+    fn record_at(&self, count: u64, now: u64) {
+        let bucket = &self.buckets[now as usize % OPERATIONS_RATE_WINDOW_SECONDS];
+        let second = bucket.second.load(Ordering::Acquire);
+
+        // Rotate a bucket still holding an older second. Only the writer that wins the
+        // exchange resets it; a concurrent increment in between may be lost, which is
+        // acceptable for a throughput estimate.
+        if second != now
+            && bucket
+                .second
+                .compare_exchange(second, now, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+        {
+            bucket.count.store(0, Ordering::Release);
+        }
+
+        bucket.count.fetch_add(count, Ordering::Relaxed);
+    }
+
+    fn operations_per_second_at(&self, now: u64) -> f64 {
+        let window = OPERATIONS_RATE_WINDOW_SECONDS as u64;
+        let total: u64 = self
+            .buckets
+            .iter()
+            .filter(|bucket| now.saturating_sub(bucket.second.load(Ordering::Acquire)) < window)
+            .map(|bucket| bucket.count.load(Ordering::Relaxed))
+            .sum();
+
+        total as f64 / window as f64
+    }
+}
+
+impl Default for OperationsTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn unix_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+static OPERATIONS_TRACKER: OperationsTracker = OperationsTracker::new();
+
+pub fn global_operations_tracker() -> &'static OperationsTracker {
+    &OPERATIONS_TRACKER
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operations_per_second_over_window() {
+        let tracker = OperationsTracker::new();
+        let start = 1_700_000_000;
+
+        for offset in 0..30 {
+            tracker.record_at(2, start + offset);
+        }
+
+        // 60 operations across the 60-second window
+        assert_eq!(tracker.operations_per_second_at(start + 29), 1.0);
+
+        // Buckets older than the window no longer count (seconds 15..=29 remain)
+        assert_eq!(tracker.operations_per_second_at(start + 74), 0.5);
+        assert_eq!(tracker.operations_per_second_at(start + 120), 0.0);
+    }
+
+    #[test]
+    fn test_stale_bucket_is_rotated() {
+        let tracker = OperationsTracker::new();
+        let start = 1_700_000_000;
+
+        tracker.record_at(5, start);
+        // Same slot one full window later must reset rather than accumulate
+        tracker.record_at(1, start + OPERATIONS_RATE_WINDOW_SECONDS as u64);
+
+        let per_second =
+            tracker.operations_per_second_at(start + OPERATIONS_RATE_WINDOW_SECONDS as u64);
+        assert!((per_second - 1.0 / 60.0).abs() < f64::EPSILON);
+    }
+}
@@ -1,25 +1,37 @@
+use crate::config::Config;
 use crate::constants::{
     API_SERVER_URL_GCP, API_SERVER_URL_LOCAL, ENVIRONMENT_GCP, ENVIRONMENT_LOCAL,
 };
 use std::env;
-use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::openapi::{Server, ServerBuilder};
 use utoipa::{Modify, OpenApi};
 
 pub mod auth;
+pub mod conditional;
+pub mod cors;
+pub mod idempotency;
 pub mod internal_client;
+pub mod metrics_tracker;
 pub mod rate_limiter;
+pub mod request_id;
 pub mod request_signature;
 pub mod routes;
 pub mod schemas;
+pub mod security_audit;
+pub mod timeout;
+pub mod versioning;
 pub mod websocket;
 
 // Re-export commonly used functions
-pub use rate_limiter::{RateLimitResult, RateLimiter};
+pub use metrics_tracker::global_operations_tracker;
+pub use rate_limiter::{RateLimitCategory, RateLimitResult, RateLimiter};
 pub use websocket::WebSocketBroadcaster;
 
 // OpenAPI tags for route grouping
-use routes::{ACCOUNT_TAG, AUDIT_TAG, DATA_TAG, HEALTH_TAG, METRICS_TAG, TEST_TAG};
+use routes::{
+    ACCOUNT_TAG, ADMIN_TAG, AUDIT_TAG, BATCH_TAG, DATA_TAG, HEALTH_TAG, METRICS_TAG, TEST_TAG,
+};
 
 /// Goud Chain API Documentation
 #[derive(OpenApi)]
@@ -27,7 +39,7 @@ use routes::{ACCOUNT_TAG, AUDIT_TAG, DATA_TAG, HEALTH_TAG, METRICS_TAG, TEST_TAG
     info(
         title = "Goud Chain API",
         version = "0.1.0",
-        description = "Encrypted blockchain with API key-based authentication using Proof of Authority (PoA) consensus.\n\n## Features\n- **End-to-End Encryption**: All data encrypted with user's API key\n- **Proof of Authority**: Fast, deterministic block creation with validator rotation\n- **Immutable Storage**: Blockchain-backed tamper-proof data storage\n- **Rate Limiting**: Intelligent rate limiting with progressive penalties\n- **Audit Logging**: Privacy-preserving operational security logs\n- **Real-time Updates**: WebSocket support for live blockchain events\n\n## Authentication\nSupports two authentication methods:\n1. **API Key**: Direct authentication with base64-encoded API key (header: `Authorization: Bearer <api_key>`)\n2. **Session Token**: JWT token obtained from `/account/login` endpoint (header: `Authorization: Bearer <jwt>`)\n\nAPI keys are shown only once during account creation and cannot be recovered.\n\n## WebSocket Real-time Updates\n**Endpoint:** `ws://<server>/ws?token=<api_key>`\n\nConnect to receive real-time event notifications:\n- `blockchain_update` - New block added to chain\n- `collection_update` - New encrypted collection created\n- `peer_update` - P2P network topology changed\n- `metrics_update` - System metrics updated\n- `audit_log_update` - New audit log entry (user-specific)\n\n**Client Messages:**\n- `{\"type\": \"subscribe\", \"event\": \"blockchain_update\"}` - Subscribe to event type\n- `{\"type\": \"unsubscribe\", \"event\": \"blockchain_update\"}` - Unsubscribe from event\n- `{\"type\": \"ping\"}` - Keep-alive ping\n\n**Server Messages:**\n- `{\"type\": \"event\", \"event\": \"blockchain_update\", ...data}` - Event notification\n- `{\"type\": \"pong\"}` - Pong response\n- `{\"type\": \"subscribed\", \"event\": \"...\"}` - Subscription confirmed\n\nAuthentication: Provide base64-encoded API key as `token` query parameter.",
+        description = "Encrypted blockchain with API key-based authentication using Proof of Authority (PoA) consensus.\n\n## Features\n- **End-to-End Encryption**: All data encrypted with user's API key\n- **Proof of Authority**: Fast, deterministic block creation with validator rotation\n- **Immutable Storage**: Blockchain-backed tamper-proof data storage\n- **Rate Limiting**: Intelligent rate limiting with progressive penalties\n- **Audit Logging**: Privacy-preserving operational security logs\n- **Real-time Updates**: WebSocket support for live blockchain events\n\n## Authentication\nSupports two authentication methods:\n1. **API Key**: Direct authentication with base64-encoded API key (header: `Authorization: Bearer <api_key>`)\n2. **Session Token**: JWT token obtained from `/account/login` endpoint (header: `Authorization: Bearer <jwt>`)\n\nAPI keys are shown only once during account creation and cannot be recovered.\n\n## WebSocket Real-time Updates\n**Endpoint:** `ws://<server>/ws?token=<api_key or session_token>`\n\nConnect to receive real-time event notifications:\n- `blockchain_update` - New block added to chain\n- `collection_update` - New encrypted collection created\n- `peer_update` - P2P network topology changed (peers discovered, parked, restored or blacklisted; carries `peer_count` and `peers`)\n- `metrics_update` - System metrics changed (same fields as `/metrics`, checked every `METRICS_UPDATE_INTERVAL_SECONDS`)\n- `audit_log_update` - New audit log entry (delivered only to the authenticated account)\n- `chain_reorg` - A synced fork replaced part of the chain (`old_tip`, `new_tip`, `depth` = blocks rolled back)\n\n**Client Messages:**\n- `{\"type\": \"subscribe\", \"event\": \"blockchain_update\"}` - Subscribe to event type\n- `{\"type\": \"subscribe\", \"event\": \"collection_update\", \"filter\": {\"account\": \"<api_key_hash>\"}}` - Only events matching the filter (`account` and/or `collection_id`; `account` must be your own)\n- `{\"type\": \"unsubscribe\", \"event\": \"blockchain_update\"}` - Unsubscribe from event\n- `{\"type\": \"ping\"}` - Keep-alive ping\n- `{\"type\": \"reauth\", \"token\": \"<session_token>\"}` - Extend a session-token connection with a fresh token for the same account\n\n**Server Messages:**\n- `{\"type\": \"event\", \"event\": \"blockchain_update\", ...data}` - Event notification\n- `{\"type\": \"pong\"}` - Pong response\n- `{\"type\": \"subscribed\", \"event\": \"...\"}` - Subscription confirmed\n- `{\"type\": \"reauthenticated\", \"expires_at\": 1704070800}` - Reauth accepted\n- `{\"type\": \"error\", \"message\": \"...\"}` - Subscription or reauth rejected\n\nAuthentication: Provide a base64-encoded API key or session token as `token` query parameter. Unauthenticated connections only receive public events. Invalid tokens are closed right after the upgrade with close code 1008, and session-token connections are closed with 1008 when the token expires unless a `reauth` arrives first.",
     ),
     modifiers(&ApiModifier),
     tags(
@@ -36,6 +48,8 @@ use routes::{ACCOUNT_TAG, AUDIT_TAG, DATA_TAG, HEALTH_TAG, METRICS_TAG, TEST_TAG
         (name = HEALTH_TAG, description = "Blockchain health, sync status, and peer management"),
         (name = METRICS_TAG, description = "System metrics, statistics, and monitoring"),
         (name = AUDIT_TAG, description = "Operational security audit logs (privacy-preserving)"),
+        (name = ADMIN_TAG, description = "Operator storage integrity checks and ban administration"),
+        (name = BATCH_TAG, description = "Several API calls in one round trip"),
         (name = TEST_TAG, description = "Test endpoints for demonstrating replay protection")
     )
 )]
@@ -73,6 +87,14 @@ impl Modify for ApiModifier {
                         .build(),
                 ),
             );
+            components.add_security_scheme(
+                "admin_token",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                    "X-Admin-Token",
+                    "Operator credential (the node's ADMIN_TOKEN) for /admin endpoints. \
+                     Distinct from user API keys and session tokens.",
+                ))),
+            );
         }
 
         // Configure servers based on environment
@@ -81,6 +103,42 @@ impl Modify for ApiModifier {
     }
 }
 
+/// Append this node's active request limits (the same values `GET /limits` returns) to the API description
+pub fn document_limits(openapi: &mut utoipa::openapi::OpenApi, config: &Config) {
+    let limits = routes::health::limits_for(config);
+    let rate = &limits.rate_limits;
+    let section = format!(
+        "\n\n## Limits\n\
+         Active limits on this node (also returned by `GET /limits`):\n\
+         - **Payload**: up to {} bytes of `data` per collection; larger payloads get `422 VALIDATION_FAILED`\n\
+         - **Request body**: up to {} bytes ({} for `/data/submit_batch`); larger bodies get `413` before they are read\n\
+         - **Labels**: up to {} bytes\n\
+         - **Batch**: up to {} collections per `/data/submit_batch`, {} sub-requests per `/batch`\n\
+         - **Timeout**: requests running longer than {} seconds get `504 REQUEST_TIMEOUT`\n\
+         - **Rate limits** ({}, requests/sec per API key; `429` above): submit {}, batch {}, list {}, search {}, decrypt {}, delete {}, account create {} (per IP), account exists {} (per IP), API key hashes {} (per IP)",
+        limits.max_payload_bytes,
+        limits.max_request_body_bytes,
+        limits.max_batch_request_body_bytes,
+        limits.max_label_length,
+        limits.max_batch_items,
+        limits.max_batch_requests,
+        limits.request_timeout_seconds,
+        rate.mode,
+        rate.submit_per_second,
+        rate.batch_per_second,
+        rate.list_per_second,
+        rate.search_per_second,
+        rate.decrypt_per_second,
+        rate.delete_per_second,
+        rate.account_create_per_second,
+        rate.account_exists_per_second,
+        rate.key_hash_per_second,
+    );
+
+    let description = openapi.info.description.get_or_insert_with(String::new);
+    description.push_str(&section);
+}
+
 /// Get API server URLs based on environment
 fn get_api_servers() -> Vec<Server> {
     let environment = env::var("ENV").unwrap_or_else(|_| {
@@ -8,16 +8,30 @@
 //! 4. **4th violation:** Permanent write ban (reads allowed)
 //! 5. **5th violation:** Complete blacklist + 24-hour IP ban
 //!
+//! **Endpoint Groups:** Each [`RateLimitCategory`] has its own configurable limit and
+//! counter (see `RateLimitConfig`); penalties escalate per API key across all groups.
+//! Unauthenticated public reads are limited per client IP by [`public_rate_limit_middleware`].
+//!
+//! **Modes:** Fixed 1-second windows (default) or a token bucket that refills at the
+//! per-second limit and lets compliant clients burst up to `limit × burst_seconds`.
+//!
 //! **Performance:** <500µs overhead per request (in-memory LRU cache)
 
-use chrono::Utc;
+use axum::extract::Request;
+use axum::http::HeaderName;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use ipnet::IpNet;
 use std::collections::HashSet;
+use std::net::IpAddr;
 use std::sync::Arc;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
+use crate::api::security_audit::extract_client_ip;
+use crate::config::{RateLimitConfig, RateLimitMode};
 use crate::crypto::hash_api_key;
 use crate::storage::{BanLevel, RateLimitStore};
-use crate::types::Result;
+use crate::types::{GoudChainError, Result};
 
 /// Rate limit check result
 #[derive(Debug, Clone)]
@@ -43,50 +57,154 @@ pub enum RateLimitResult {
     },
 }
 
+/// Endpoint group a request is rate limited under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitCategory {
+    Submit,
+    Batch,
+    List,
+    Search,
+    Decrypt,
+    Delete,
+    AccountCreate,
+    AccountExists,
+    Public,
+    KeyHash,
+}
+
+impl RateLimitCategory {
+    /// Storage bucket name (keeps counters for different groups separate)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Submit => "submit",
+            Self::Batch => "batch",
+            Self::List => "list",
+            Self::Search => "search",
+            Self::Decrypt => "decrypt",
+            Self::Delete => "delete",
+            Self::AccountCreate => "account_create",
+            Self::AccountExists => "account_exists",
+            Self::Public => "public",
+            Self::KeyHash => "key_hash",
+        }
+    }
+
+    /// Whether write bans apply to this group (reads stay available until complete blacklist)
+    /// Key hashing precedes reads as well as writes, so it is treated as a read
+    pub fn is_write(&self) -> bool {
+        !matches!(
+            self,
+            Self::List | Self::Search | Self::Public | Self::KeyHash
+        )
+    }
+}
+
 /// Rate limiter with graduated penalty enforcement
 pub struct RateLimiter {
     store: Arc<RateLimitStore>,
     bypass_keys: HashSet<String>, // Whitelisted API key hashes
+    bypass_networks: Vec<IpNet>,  // Whitelisted source IP ranges
+    limits: RateLimitConfig,
 }
 
 impl RateLimiter {
-    /// Create a new rate limiter with optional bypass whitelist
-    pub fn new(store: Arc<RateLimitStore>, bypass_keys: Vec<String>) -> Self {
+    /// Create a new rate limiter with optional bypass whitelists and per-group limits
+    /// `bypass_cidrs` accepts IPv4/IPv6 CIDR ranges or bare addresses; malformed entries are skipped
+    pub fn new(
+        store: Arc<RateLimitStore>,
+        bypass_keys: Vec<String>,
+        bypass_cidrs: Vec<String>,
+        limits: RateLimitConfig,
+    ) -> Self {
         let bypass_set: HashSet<String> = bypass_keys.into_iter().collect();
+        let bypass_networks = parse_bypass_networks(&bypass_cidrs);
 
         info!(
             bypass_count = bypass_set.len(),
+            bypass_network_count = bypass_networks.len(),
+            limits = ?limits,
             "RateLimiter initialized with bypass whitelist"
         );
 
         Self {
             store,
             bypass_keys: bypass_set,
+            bypass_networks,
+            limits,
+        }
+    }
+
+    /// Store holding the limiter's violations and bans
+    pub fn store(&self) -> &Arc<RateLimitStore> {
+        &self.store
+    }
+
+    /// Check whether the client IP falls inside an allowlisted range
+    fn is_bypassed_ip(&self, client_ip: &str) -> bool {
+        if self.bypass_networks.is_empty() {
+            return false;
+        }
+
+        client_ip
+            .parse::<IpAddr>()
+            .map(|ip| self.bypass_networks.iter().any(|net| net.contains(&ip)))
+            .unwrap_or(false)
+    }
+
+    /// Configured requests per second for an endpoint group
+    pub fn limit_for(&self, category: RateLimitCategory) -> u32 {
+        match category {
+            RateLimitCategory::Submit => self.limits.submit_per_second,
+            RateLimitCategory::Batch => self.limits.batch_per_second,
+            RateLimitCategory::List => self.limits.list_per_second,
+            RateLimitCategory::Search => self.limits.search_per_second,
+            RateLimitCategory::Decrypt => self.limits.decrypt_per_second,
+            RateLimitCategory::Delete => self.limits.delete_per_second,
+            RateLimitCategory::AccountCreate => self.limits.account_create_per_second,
+            RateLimitCategory::AccountExists => self.limits.account_exists_per_second,
+            RateLimitCategory::Public => self.limits.public_per_second,
+            RateLimitCategory::KeyHash => self.limits.key_hash_per_second,
+        }
+    }
+
+    /// Limit advertised in headers: the per-second limit, or the bucket capacity in token mode
+    fn advertised_limit(&self, category: RateLimitCategory) -> u32 {
+        match self.limits.mode {
+            RateLimitMode::SlidingWindow => self.limit_for(category),
+            RateLimitMode::TokenBucket => self
+                .limit_for(category)
+                .saturating_mul(self.limits.burst_seconds),
+        }
+    }
+
+    /// Result used when the limiter itself fails (fail-open: availability over security)
+    pub fn fail_open(&self, category: RateLimitCategory) -> RateLimitResult {
+        let limit = self.advertised_limit(category);
+        RateLimitResult::Allowed {
+            limit,
+            remaining: limit,
+            reset_at: self.store.clock().now() + 60,
         }
     }
 
+    /// Check a group limited per client IP rather than per API key
+    pub fn check_ip_limit(
+        &self,
+        client_ip: &str,
+        category: RateLimitCategory,
+    ) -> Result<RateLimitResult> {
+        self.check_limit(&hash_ip(client_ip), client_ip, category)
+    }
+
     /// Check if request should be allowed, warned, or blocked
     /// Returns rate limit result with headers and ban information
     pub fn check_limit(
         &self,
         api_key_hash: &str,
         client_ip: &str,
-        is_write: bool,
+        category: RateLimitCategory,
     ) -> Result<RateLimitResult> {
-        // 1. Check bypass whitelist
-        if self.bypass_keys.contains(api_key_hash) {
-            debug!(
-                api_key_hash = %api_key_hash,
-                "Request bypassed - whitelisted API key"
-            );
-            return Ok(RateLimitResult::Allowed {
-                limit: 9999,
-                remaining: 9999,
-                reset_at: Utc::now().timestamp() + 60,
-            });
-        }
-
-        // 2. Check IP ban (applies to all requests from this IP)
+        // 1. Check IP ban (applies to all requests from this IP, whitelisted or not)
         let ip_hash = hash_ip(client_ip);
         if self.store.is_ip_banned(&ip_hash)? {
             warn!(
@@ -100,12 +218,12 @@ impl RateLimiter {
             });
         }
 
-        // 3. Check API key ban status
+        // 2. Check API key ban status
         if let Some(ban_record) = self.store.get_ban_status(api_key_hash)? {
             let ban_level = ban_record.level;
 
             // Check if ban blocks this operation type
-            let is_blocked = if is_write {
+            let is_blocked = if category.is_write() {
                 ban_level.blocks_writes()
             } else {
                 ban_level.blocks_reads()
@@ -113,7 +231,7 @@ impl RateLimiter {
 
             if is_blocked {
                 let retry_after = match ban_record.expires_at {
-                    Some(expiry) => (expiry - Utc::now().timestamp()).max(0) as u64,
+                    Some(expiry) => (expiry - self.store.clock().now()).max(0) as u64,
                     None => 86400 * 365, // 1 year for permanent bans
                 };
 
@@ -122,7 +240,7 @@ impl RateLimiter {
                 warn!(
                     api_key_hash = %api_key_hash,
                     ban_level = ?ban_level,
-                    is_write = is_write,
+                    category = ?category,
                     "Request blocked - API key banned"
                 );
 
@@ -134,28 +252,59 @@ impl RateLimiter {
             }
         }
 
-        // 4. Check rate limit
-        let limit = if is_write {
-            crate::constants::RATE_LIMIT_WRITE_PER_SECOND
-        } else {
-            crate::constants::RATE_LIMIT_READ_PER_SECOND
-        };
+        // 3. Check bypass whitelists (API key hash or source IP range); never lifts a ban
+        if self.bypass_keys.contains(api_key_hash) || self.is_bypassed_ip(client_ip) {
+            debug!(
+                api_key_hash = %api_key_hash,
+                ip = %client_ip,
+                "Request bypassed - whitelisted API key or IP range"
+            );
+            return Ok(RateLimitResult::Allowed {
+                limit: 9999,
+                remaining: 9999,
+                reset_at: self.store.clock().now() + 60,
+            });
+        }
 
-        let current_count = self.store.increment_request_count(api_key_hash, is_write)?;
-        let remaining = limit.saturating_sub(current_count);
+        // 4. Check rate limit for this endpoint group
+        let (limit, remaining, exceeded) = match self.limits.mode {
+            RateLimitMode::SlidingWindow => {
+                let limit = self.limit_for(category);
+                let current_count = self
+                    .store
+                    .increment_request_count(api_key_hash, category.as_str())?;
+                (
+                    limit,
+                    limit.saturating_sub(current_count),
+                    current_count > limit,
+                )
+            }
+            RateLimitMode::TokenBucket => {
+                let refill_per_second = self.limit_for(category);
+                let capacity = self.advertised_limit(category);
+                let bucket = self.store.consume_token(
+                    api_key_hash,
+                    category.as_str(),
+                    capacity,
+                    refill_per_second,
+                )?;
+                (capacity, bucket.remaining, !bucket.allowed)
+            }
+        };
 
-        let now = Utc::now().timestamp();
+        let now = self.store.clock().now();
         let window_seconds = crate::constants::RATE_LIMIT_WINDOW_SECONDS as i64;
         let reset_at = now + window_seconds;
 
-        if current_count > limit {
+        if exceeded {
             // Rate limit exceeded - record violation and escalate penalty
             let violations = self.store.record_violation(api_key_hash)?;
 
             warn!(
                 api_key_hash = %api_key_hash,
-                current_count = current_count,
                 limit = limit,
+                mode = ?self.limits.mode,
+                category = ?category,
                 violation_count = violations.count,
                 "Rate limit exceeded"
             );
@@ -233,7 +382,7 @@ impl RateLimiter {
                 ("X-RateLimit-Remaining".to_string(), remaining.to_string()),
                 (
                     "X-RateLimit-Reset".to_string(),
-                    (Utc::now().timestamp() + *cooldown_secs as i64).to_string(),
+                    (self.store.clock().now() + *cooldown_secs as i64).to_string(),
                 ),
                 (
                     "X-RateLimit-Violation".to_string(),
@@ -263,6 +412,76 @@ impl RateLimiter {
     }
 }
 
+/// Rate limit unauthenticated public reads (`/chain`, `/peers`, `/stats`, `/metrics`, ...) by
+/// client IP under [`RateLimitCategory::Public`] and advertise the limit in `X-RateLimit-*` headers
+/// The IP is the one `client_ip_middleware` resolved, so it must run further out.
+///
+/// Needs the `RateLimiter` extension; without it the middleware is a no-op.
+pub async fn public_rate_limit_middleware(request: Request, next: Next) -> Response {
+    let Some(rate_limiter) = request.extensions().get::<Arc<RateLimiter>>().cloned() else {
+        return next.run(request).await;
+    };
+
+    let client_ip = extract_client_ip(request.headers());
+    let result = match rate_limiter.check_ip_limit(&client_ip, RateLimitCategory::Public) {
+        Ok(result) => result,
+        Err(e) => {
+            error!(error = %e, "Rate limit check failed, allowing request");
+            rate_limiter.fail_open(RateLimitCategory::Public)
+        }
+    };
+
+    let mut response = match &result {
+        RateLimitResult::Blocked {
+            retry_after,
+            violation_count,
+            ..
+        } => {
+            warn!(
+                client_ip = %client_ip,
+                path = %request.uri().path(),
+                violation_count = violation_count,
+                "Public read blocked by rate limiter"
+            );
+            GoudChainError::RateLimitExceeded {
+                retry_after: *retry_after,
+                violation_count: *violation_count,
+            }
+            .into_response()
+        }
+        _ => next.run(request).await,
+    };
+
+    let response_headers = response.headers_mut();
+    for (name, value) in rate_limiter.create_headers(&result) {
+        if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(name.as_bytes()), value.parse()) {
+            response_headers.insert(name, value);
+        }
+    }
+    response
+}
+
+/// Parse CIDR ranges (or bare addresses as single-host ranges), logging and skipping bad entries
+fn parse_bypass_networks(entries: &[String]) -> Vec<IpNet> {
+    entries
+        .iter()
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let parsed = entry
+                .parse::<IpNet>()
+                .or_else(|_| entry.parse::<IpAddr>().map(IpNet::from));
+            match parsed {
+                Ok(net) => Some(net),
+                Err(_) => {
+                    warn!(entry = %entry, "Ignoring malformed rate limit bypass CIDR");
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
 /// Hash IP address for storage (privacy-preserving)
 fn hash_ip(ip: &str) -> String {
     let hash_bytes = hash_api_key(ip.as_bytes());
@@ -278,7 +497,7 @@ mod tests {
         let temp_dir = std::env::temp_dir().join(format!("test_limiter_{}", rand::random::<u64>()));
         let db = DB::open_default(&temp_dir).expect("Failed to open test DB");
         let store = Arc::new(RateLimitStore::new(Arc::new(db)));
-        RateLimiter::new(store, vec![])
+        RateLimiter::new(store, vec![], vec![], RateLimitConfig::default())
     }
 
     #[test]
@@ -288,15 +507,170 @@ mod tests {
         let store = Arc::new(RateLimitStore::new(Arc::new(db)));
 
         let whitelisted_key = "whitelisted_key_123";
-        let limiter = RateLimiter::new(store, vec![whitelisted_key.to_string()]);
+        let limiter = RateLimiter::new(
+            store,
+            vec![whitelisted_key.to_string()],
+            vec![],
+            RateLimitConfig::default(),
+        );
 
         // Whitelisted key should always be allowed
         let result = limiter
-            .check_limit(whitelisted_key, "127.0.0.1", true)
+            .check_limit(whitelisted_key, "127.0.0.1", RateLimitCategory::Submit)
             .unwrap();
         assert!(matches!(result, RateLimitResult::Allowed { .. }));
     }
 
+    #[test]
+    fn test_parse_bypass_networks_skips_malformed() {
+        let entries: Vec<String> = [
+            "10.0.0.0/8",
+            " 2001:db8::/32 ",
+            "192.168.1.7",
+            "not-an-ip",
+            "10.0.0.0/40",
+            "",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let networks = parse_bypass_networks(&entries);
+        assert_eq!(networks.len(), 3);
+        assert_eq!(networks[2].to_string(), "192.168.1.7/32");
+    }
+
+    #[test]
+    fn test_bypass_ip_ranges() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("test_bypass_ip_{}", rand::random::<u64>()));
+        let db = DB::open_default(&temp_dir).expect("Failed to open test DB");
+        let store = Arc::new(RateLimitStore::new(Arc::new(db)));
+        let limiter = RateLimiter::new(
+            Arc::clone(&store),
+            vec![],
+            vec!["10.1.0.0/16".to_string(), "fd00::/8".to_string()],
+            RateLimitConfig::default(),
+        );
+
+        assert!(limiter.is_bypassed_ip("10.1.42.7"));
+        assert!(limiter.is_bypassed_ip("fd12:3456::1"));
+        assert!(!limiter.is_bypassed_ip("10.2.0.1"));
+        assert!(!limiter.is_bypassed_ip("2001:db8::1"));
+        assert!(!limiter.is_bypassed_ip("unknown"));
+
+        // Allowlisted ranges skip limits but never an active ban
+        let result = limiter
+            .check_limit("internal_job", "10.1.0.9", RateLimitCategory::Submit)
+            .unwrap();
+        assert!(matches!(
+            result,
+            RateLimitResult::Allowed { limit: 9999, .. }
+        ));
+        store
+            .apply_ban("internal_job", BanLevel::PermanentWriteBan)
+            .unwrap();
+        let result = limiter
+            .check_limit("internal_job", "10.1.0.9", RateLimitCategory::Submit)
+            .unwrap();
+        assert!(matches!(result, RateLimitResult::Blocked { .. }));
+    }
+
+    #[test]
+    fn test_bypass_does_not_lift_ip_ban() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("test_bypass_ban_{}", rand::random::<u64>()));
+        let db = DB::open_default(&temp_dir).expect("Failed to open test DB");
+        let store = Arc::new(RateLimitStore::new(Arc::new(db)));
+        let limiter = RateLimiter::new(
+            Arc::clone(&store),
+            vec!["whitelisted_key".to_string()],
+            vec!["10.1.0.0/16".to_string()],
+            RateLimitConfig::default(),
+        );
+
+        store.ban_ip(&hash_ip("10.1.0.9")).unwrap();
+        let result = limiter
+            .check_limit("whitelisted_key", "10.1.0.9", RateLimitCategory::List)
+            .unwrap();
+        assert!(matches!(
+            result,
+            RateLimitResult::Blocked {
+                ban_level: BanLevel::CompleteBlacklist,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_endpoint_groups_use_configured_limits() {
+        let temp_dir = std::env::temp_dir().join(format!("test_groups_{}", rand::random::<u64>()));
+        let db = DB::open_default(&temp_dir).expect("Failed to open test DB");
+        let store = Arc::new(RateLimitStore::new(Arc::new(db)));
+        let limits = RateLimitConfig {
+            decrypt_per_second: 2,
+            list_per_second: 50,
+            ..RateLimitConfig::default()
+        };
+        let limiter = RateLimiter::new(Arc::clone(&store), vec![], vec![], limits);
+
+        assert_eq!(limiter.limit_for(RateLimitCategory::Decrypt), 2);
+        assert_eq!(limiter.limit_for(RateLimitCategory::List), 50);
+        assert!(matches!(
+            limiter.fail_open(RateLimitCategory::Decrypt),
+            RateLimitResult::Allowed {
+                limit: 2,
+                remaining: 2,
+                ..
+            }
+        ));
+
+        // Write blocks stop write groups but leave read groups available
+        let key = "group_key";
+        store.apply_ban(key, BanLevel::WriteBlock5Min).unwrap();
+
+        for category in [RateLimitCategory::Submit, RateLimitCategory::Decrypt] {
+            let result = limiter.check_limit(key, "127.0.0.1", category).unwrap();
+            assert!(matches!(
+                result,
+                RateLimitResult::Blocked {
+                    ban_level: BanLevel::WriteBlock5Min,
+                    ..
+                }
+            ));
+        }
+        let result = limiter
+            .check_limit(key, "127.0.0.1", RateLimitCategory::List)
+            .unwrap();
+        assert!(matches!(result, RateLimitResult::Allowed { limit: 50, .. }));
+    }
+
+    #[test]
+    fn test_token_bucket_mode_reports_burst_capacity() {
+        let temp_dir = std::env::temp_dir().join(format!("test_bucket_{}", rand::random::<u64>()));
+        let db = DB::open_default(&temp_dir).expect("Failed to open test DB");
+        let store = Arc::new(RateLimitStore::new(Arc::new(db)));
+        let limits = RateLimitConfig {
+            mode: RateLimitMode::TokenBucket,
+            burst_seconds: 4,
+            submit_per_second: 5,
+            ..RateLimitConfig::default()
+        };
+        let limiter = RateLimiter::new(store, vec![], vec![], limits);
+
+        let result = limiter
+            .check_limit("bucket_key", "127.0.0.1", RateLimitCategory::Submit)
+            .unwrap();
+        assert!(matches!(
+            result,
+            RateLimitResult::Allowed {
+                limit: 20,
+                remaining: 19,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn test_header_generation() {
         let limiter = create_test_limiter();
@@ -321,4 +695,99 @@ mod tests {
         let headers = limiter.create_headers(&warning);
         assert!(headers.iter().any(|(k, _)| k == "X-RateLimit-Warning"));
     }
+
+    #[tokio::test]
+    async fn test_public_reads_are_limited_by_ip() {
+        use axum::{body::Body, middleware, routing::get, Extension, Router};
+        use tower::ServiceExt;
+
+        let temp_dir = std::env::temp_dir().join(format!("test_public_{}", rand::random::<u64>()));
+        let db = DB::open_default(&temp_dir).expect("Failed to open test DB");
+        let limits = RateLimitConfig {
+            public_per_second: 1,
+            ..RateLimitConfig::default()
+        };
+        let limiter = Arc::new(RateLimiter::new(
+            Arc::new(RateLimitStore::new(Arc::new(db))),
+            vec![],
+            vec![],
+            limits,
+        ));
+        let app = Router::new()
+            .route("/stats", get(|| async { "ok" }))
+            .layer(middleware::from_fn(public_rate_limit_middleware))
+            .layer(Extension(limiter));
+        let request = |ip: &str| {
+            Request::builder()
+                .uri("/stats")
+                .header("x-real-ip", ip)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let first = app.clone().oneshot(request("10.0.0.1")).await.unwrap();
+        assert_eq!(first.status(), 200);
+        assert_eq!(first.headers()["X-RateLimit-Limit"], "1");
+        assert_eq!(first.headers()["X-RateLimit-Remaining"], "0");
+
+        // Over the limit: first violation warns, the next is refused
+        let warned = app.clone().oneshot(request("10.0.0.1")).await.unwrap();
+        assert_eq!(warned.status(), 200);
+        assert!(warned.headers().contains_key("X-RateLimit-Warning"));
+        let blocked = app.clone().oneshot(request("10.0.0.1")).await.unwrap();
+        assert_eq!(blocked.status(), 429);
+        assert!(blocked.headers().contains_key("Retry-After"));
+
+        // Other clients keep their own budget
+        let other = app.oneshot(request("10.0.0.2")).await.unwrap();
+        assert_eq!(other.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_public_reads_ignore_forwarding_headers_from_untrusted_peers() {
+        use crate::api::security_audit::client_ip_middleware;
+        use crate::config::Config;
+        use axum::extract::connect_info::MockConnectInfo;
+        use axum::{body::Body, middleware, routing::get, Extension, Router};
+        use std::net::SocketAddr;
+        use tower::ServiceExt;
+
+        let temp_dir =
+            std::env::temp_dir().join(format!("test_public_peer_{}", rand::random::<u64>()));
+        let db = DB::open_default(&temp_dir).expect("Failed to open test DB");
+        let limits = RateLimitConfig {
+            public_per_second: 1,
+            ..RateLimitConfig::default()
+        };
+        let limiter = Arc::new(RateLimiter::new(
+            Arc::new(RateLimitStore::new(Arc::new(db))),
+            vec![],
+            vec![],
+            limits,
+        ));
+        let peer: SocketAddr = "203.0.113.7:40000".parse().unwrap();
+        let app = Router::new()
+            .route("/stats", get(|| async { "ok" }))
+            .layer(middleware::from_fn(public_rate_limit_middleware))
+            .layer(middleware::from_fn(client_ip_middleware))
+            .layer(Extension(limiter))
+            .layer(Extension(Arc::new(Config::for_tests())))
+            .layer(MockConnectInfo(peer));
+        let request = |ip: &str| {
+            Request::builder()
+                .uri("/stats")
+                .header("x-real-ip", ip)
+                .header("x-forwarded-for", ip)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        // Rotating the claimed address does not buy a fresh budget
+        let first = app.clone().oneshot(request("10.0.0.1")).await.unwrap();
+        assert_eq!(first.status(), 200);
+        let warned = app.clone().oneshot(request("10.0.0.2")).await.unwrap();
+        assert!(warned.headers().contains_key("X-RateLimit-Warning"));
+        let blocked = app.oneshot(request("10.0.0.3")).await.unwrap();
+        assert_eq!(blocked.status(), 429);
+    }
 }
@@ -0,0 +1,178 @@
+//! Per-request correlation IDs.
+//! Layer 5: Presentation - `x-request-id` middleware and propagation to forwarded requests.
+//!
+//! Every request runs inside a `request` tracing span carrying its ID, so all log lines a
+//! request produces share it. A write forwarded to the validator carries the same ID, and the
+//! validator honors it, so one ID follows the request across nodes.
+
+use axum::extract::Request;
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+use std::time::Instant;
+use tracing::{info, info_span, Instrument};
+
+use crate::constants::{MAX_REQUEST_ID_LENGTH, REQUEST_ID_HEADER};
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: String;
+}
+
+/// Accept an inbound ID only if it is short and made of log-safe characters
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LENGTH
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+}
+
+/// ID of the request the current task is serving, if any
+/// Tasks spawned from a handler do not inherit it
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Honor or generate `x-request-id`, run the request inside a span carrying it, and echo it back
+pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| is_valid_request_id(id))
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    // Only log-safe characters get here, so the value is always a valid header
+    let header_value = HeaderValue::from_str(&request_id).expect("request ID is a valid header");
+    request
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, header_value.clone());
+
+    let span = info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+    let started = Instant::now();
+
+    let mut response = CURRENT_REQUEST_ID
+        .scope(request_id, next.run(request))
+        .instrument(span.clone())
+        .await;
+
+    span.in_scope(|| {
+        info!(
+            status = response.status().as_u16(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "Request completed"
+        );
+    });
+
+    response
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, header_value);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/id",
+                get(|| async { current_request_id().unwrap_or_default() }),
+            )
+            .layer(middleware::from_fn(request_id_middleware))
+    }
+
+    async fn request_id_of(request: Request<Body>) -> (String, String) {
+        let response = app().oneshot(request).await.unwrap();
+        let header = response.headers()[REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (header, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_inbound_request_id_is_honored() {
+        let request = Request::builder()
+            .uri("/id")
+            .header(REQUEST_ID_HEADER, "client-trace-42")
+            .body(Body::empty())
+            .unwrap();
+
+        let (header, handler_saw) = request_id_of(request).await;
+        assert_eq!(header, "client-trace-42");
+        assert_eq!(handler_saw, "client-trace-42");
+    }
+
+    #[tokio::test]
+    async fn test_missing_or_unsafe_request_id_is_replaced() {
+        let request = Request::builder().uri("/id").body(Body::empty()).unwrap();
+        let (generated, handler_saw) = request_id_of(request).await;
+        assert!(uuid::Uuid::parse_str(&generated).is_ok());
+        assert_eq!(handler_saw, generated);
+
+        let request = Request::builder()
+            .uri("/id")
+            .header(REQUEST_ID_HEADER, "id with spaces")
+            .body(Body::empty())
+            .unwrap();
+        let (replaced, _) = request_id_of(request).await;
+        assert_ne!(replaced, "id with spaces");
+        assert!(uuid::Uuid::parse_str(&replaced).is_ok());
+
+        assert!(current_request_id().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_forwarded_request_keeps_request_id() {
+        // The "validator" reports the ID it logged under
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let validator_addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            axum::serve(listener, app()).await.unwrap();
+        });
+
+        let entry_node = Router::new()
+            .route(
+                "/forward",
+                get(move || async move {
+                    crate::api::internal_client::forward_request_with_headers(
+                        &validator_addr,
+                        "GET",
+                        "/id",
+                        "",
+                        "application/json",
+                        None,
+                        &[],
+                    )
+                    .await
+                    .unwrap()
+                    .1
+                }),
+            )
+            .layer(middleware::from_fn(request_id_middleware));
+
+        let request = Request::builder()
+            .uri("/forward")
+            .header(REQUEST_ID_HEADER, "cross-node-7")
+            .body(Body::empty())
+            .unwrap();
+        let response = entry_node.oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body.as_ref(), b"cross-node-7");
+    }
+}
@@ -3,17 +3,29 @@
 //!
 //! **Security Features:**
 //! - Ed25519 signature verification
-//! - Nonce-based replay protection (5-minute expiration)
+//! - Nonce-based replay protection (5-minute expiration, 409 on reuse for header nonces)
 //! - Timestamp validation for request freshness
 //! - Generic error messages to prevent information leakage
+//!
+//! Two formats are supported: `SignedRequest<T>` wraps the payload in the JSON body, while
+//! `RequestSignature` carries the signature in `X-Signature`/`X-Nonce`/`X-Timestamp` headers
+//! so write endpoints keep their plain request bodies.
 
+use axum::http::HeaderMap;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::error;
 use utoipa::ToSchema;
 
-use crate::constants::REQUEST_TIMESTAMP_TOLERANCE_SECONDS;
+use crate::config::Config;
+use crate::constants::{
+    API_VERSION_PREFIX, REQUEST_NONCE_HEADER, REQUEST_SIGNATURE_HEADER, REQUEST_TIMESTAMP_HEADER,
+    REQUEST_TIMESTAMP_TOLERANCE_SECONDS,
+};
 use crate::crypto::verify_signature;
-use crate::storage::NonceStore;
+use crate::domain::UserAccount;
+use crate::storage::{NonceReservation, NonceStore};
 use crate::types::{GoudChainError, Result};
 
 /// Signed request wrapper for replay-protected operations
@@ -80,6 +92,172 @@ impl<T: Serialize> SignedRequest<T> {
     }
 }
 
+/// Header-based request signature for write endpoints
+/// Clients sign `{method}{path}{body}{nonce}{timestamp}` with the key registered on their account;
+/// `path` may be the `/v1` path or its unversioned alias
+#[derive(Debug, Clone)]
+pub struct RequestSignature {
+    pub signature: String,
+    pub nonce: String,
+    pub timestamp: i64,
+}
+
+impl RequestSignature {
+    /// Extract signature headers (None if the request carries no X-Signature header)
+    pub fn from_headers(headers: &HeaderMap) -> Result<Option<Self>> {
+        let Some(signature) = header_str(headers, REQUEST_SIGNATURE_HEADER) else {
+            return Ok(None);
+        };
+
+        let nonce = header_str(headers, REQUEST_NONCE_HEADER).ok_or_else(|| {
+            GoudChainError::InvalidRequestSignature("Missing X-Nonce header".to_string())
+        })?;
+        let timestamp = header_str(headers, REQUEST_TIMESTAMP_HEADER)
+            .and_then(|value| value.parse::<i64>().ok())
+            .ok_or_else(|| {
+                GoudChainError::InvalidRequestSignature(
+                    "Missing or invalid X-Timestamp header".to_string(),
+                )
+            })?;
+
+        Ok(Some(Self {
+            signature,
+            nonce,
+            timestamp,
+        }))
+    }
+
+    /// Construct the canonical message covered by the signature
+    /// Format: {method}{path}{body}{nonce}{timestamp}
+    fn canonical_message(&self, method: &str, path: &str, body: &str) -> String {
+        format!("{}{}{}{}{}", method, path, body, self.nonce, self.timestamp)
+    }
+
+    /// Verify the signature against `public_key` with timestamp and nonce checks
+    /// The nonce is not recorded here: callers record it once the operation succeeds
+    pub fn verify(
+        &self,
+        method: &str,
+        path: &str,
+        body: &str,
+        public_key: &str,
+        nonce_store: &NonceStore,
+    ) -> Result<()> {
+        // 1. Validate timestamp (request freshness)
+        validate_request_timestamp(self.timestamp)?;
+
+        // 2. Check nonce (replay protection)
+        ensure_nonce_unused(&self.nonce, nonce_store)?;
+
+        // 3. Verify signature over either form of the path: both name the same endpoint,
+        // and forwarding to the validator drops the version prefix
+        let unversioned = path
+            .strip_prefix(API_VERSION_PREFIX)
+            .filter(|rest| rest.starts_with('/'))
+            .unwrap_or(path);
+        let versioned = format!("{}{}", API_VERSION_PREFIX, unversioned);
+        let verified = [unversioned, versioned.as_str()].into_iter().any(|path| {
+            let canonical_message = self.canonical_message(method, path, body);
+            verify_signature(canonical_message.as_bytes(), &self.signature, public_key).is_ok()
+        });
+        if !verified {
+            return Err(GoudChainError::AuthenticationFailed); // Generic error
+        }
+        Ok(())
+    }
+}
+
+/// Read the mandatory X-Nonce header and reserve the nonce for this request
+/// The handler commits the reservation once the operation succeeds; dropping it instead
+/// leaves the nonce reusable, and concurrent requests with the nonce are refused meanwhile
+pub fn require_fresh_nonce(
+    headers: &HeaderMap,
+    nonce_store: &Arc<NonceStore>,
+) -> Result<NonceReservation> {
+    let nonce = header_str(headers, REQUEST_NONCE_HEADER)
+        .filter(|nonce| !nonce.is_empty())
+        .ok_or_else(|| {
+            GoudChainError::InvalidRequestSignature("Missing X-Nonce header".to_string())
+        })?;
+
+    reserve_nonce(&nonce, nonce_store)
+}
+
+/// Reserve a verified request signature's nonce until its operation completes
+/// Unsigned requests have nothing to reserve; commit the reservation with `commit_used_nonce`.
+pub fn reserve_signature_nonce(
+    signature: Option<&RequestSignature>,
+    nonce_store: &Arc<NonceStore>,
+) -> Result<Option<NonceReservation>> {
+    signature
+        .map(|signature| reserve_nonce(&signature.nonce, nonce_store))
+        .transpose()
+}
+
+/// Claim a nonce for this request, refusing one that is used or held by a concurrent request
+fn reserve_nonce(nonce: &str, nonce_store: &Arc<NonceStore>) -> Result<NonceReservation> {
+    NonceStore::reserve(nonce_store, nonce)?.ok_or(GoudChainError::ReplayDetected)
+}
+
+/// Reject a nonce that is still inside its replay window
+fn ensure_nonce_unused(nonce: &str, nonce_store: &NonceStore) -> Result<()> {
+    if nonce_store.is_nonce_used(nonce)? {
+        return Err(GoudChainError::ReplayDetected);
+    }
+    Ok(())
+}
+
+/// Verify the request signature for a write endpoint
+/// Unsigned requests are accepted unless REQUIRE_REQUEST_SIGNATURES is enabled;
+/// signed requests are always verified against the account's registered request key
+pub fn enforce_request_signature(
+    signature: Option<&RequestSignature>,
+    method: &str,
+    path: &str,
+    body: &str,
+    account: &UserAccount,
+    config: &Config,
+    nonce_store: &NonceStore,
+) -> Result<()> {
+    let Some(signature) = signature else {
+        if config.require_request_signatures {
+            return Err(GoudChainError::InvalidRequestSignature(
+                "Signed request required".to_string(),
+            ));
+        }
+        return Ok(());
+    };
+
+    let public_key = account
+        .request_public_key
+        .as_deref()
+        .ok_or(GoudChainError::AuthenticationFailed)?;
+
+    signature.verify(method, path, body, public_key, nonce_store)
+}
+
+/// Commit a reserved nonce once its operation has succeeded
+/// A failure here is only logged because the operation itself has already been applied
+pub fn commit_used_nonce(reservation: NonceReservation) {
+    if let Err(e) = reservation.commit() {
+        error!(error = %e, "Failed to record request nonce");
+    }
+}
+
+/// Parse a raw JSON request body
+/// Signed endpoints take the raw body so the exact signed bytes are verified and forwarded
+pub fn parse_json_body<T: serde::de::DeserializeOwned>(body: &str) -> Result<T> {
+    serde_json::from_str(body).map_err(|e| GoudChainError::InvalidRequestBody(e.to_string()))
+}
+
+/// Read a header value as a trimmed string
+fn header_str(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+}
+
 /// Validate request timestamp is within acceptable window
 pub fn validate_request_timestamp(timestamp: i64) -> Result<()> {
     let now = Utc::now().timestamp();
@@ -103,14 +281,13 @@ mod tests {
     use super::*;
     use crate::crypto::{generate_signing_key, get_public_key_hex, sign_message};
     use rocksdb::{Options, DB};
-    use std::sync::Arc;
 
-    fn create_test_nonce_store() -> NonceStore {
+    fn create_test_nonce_store() -> Arc<NonceStore> {
         let path = format!("/tmp/goud_request_sig_test_{}", rand::random::<u64>());
         let mut opts = Options::default();
         opts.create_if_missing(true);
         let db = Arc::new(DB::open(&opts, path).unwrap());
-        NonceStore::new(db)
+        Arc::new(NonceStore::new(db))
     }
 
     #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -283,4 +460,156 @@ mod tests {
         assert!(canonical.contains("1705318200"));
         assert!(canonical.starts_with("{"));
     }
+
+    fn signed_headers(
+        signing_key: &ed25519_dalek::SigningKey,
+        method: &str,
+        path: &str,
+        body: &str,
+        nonce: &str,
+    ) -> HeaderMap {
+        let timestamp = Utc::now().timestamp();
+        let message = format!("{}{}{}{}{}", method, path, body, nonce, timestamp);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            REQUEST_SIGNATURE_HEADER,
+            sign_message(message.as_bytes(), signing_key)
+                .parse()
+                .unwrap(),
+        );
+        headers.insert(REQUEST_NONCE_HEADER, nonce.parse().unwrap());
+        headers.insert(
+            REQUEST_TIMESTAMP_HEADER,
+            timestamp.to_string().parse().unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_header_signature_verification() {
+        let nonce_store = create_test_nonce_store();
+        let signing_key = generate_signing_key();
+        let public_key = get_public_key_hex(&signing_key);
+        let body = r#"{"label":"test","data":"{}"}"#;
+        let nonce = uuid::Uuid::new_v4().to_string();
+
+        let headers = signed_headers(&signing_key, "POST", "/data/submit", body, &nonce);
+        let signature = RequestSignature::from_headers(&headers).unwrap().unwrap();
+
+        // Tampered body or path must fail
+        assert!(signature
+            .verify("POST", "/data/submit", "{}", &public_key, &nonce_store)
+            .is_err());
+        assert!(signature
+            .verify("POST", "/data/other", body, &public_key, &nonce_store)
+            .is_err());
+
+        assert!(signature
+            .verify("POST", "/data/submit", body, &public_key, &nonce_store)
+            .is_ok());
+
+        // Replay should fail once the nonce has been recorded
+        nonce_store.record_nonce(&nonce).unwrap();
+        assert!(matches!(
+            signature.verify("POST", "/data/submit", body, &public_key, &nonce_store),
+            Err(GoudChainError::ReplayDetected)
+        ));
+    }
+
+    #[test]
+    fn test_require_fresh_nonce() {
+        let nonce_store = create_test_nonce_store();
+        let nonce = uuid::Uuid::new_v4().to_string();
+
+        assert!(matches!(
+            require_fresh_nonce(&HeaderMap::new(), &nonce_store),
+            Err(GoudChainError::InvalidRequestSignature(_))
+        ));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_NONCE_HEADER, nonce.parse().unwrap());
+
+        // A concurrent request with the nonce is refused while the first one runs
+        let reservation = require_fresh_nonce(&headers, &nonce_store).unwrap();
+        assert!(matches!(
+            require_fresh_nonce(&headers, &nonce_store),
+            Err(GoudChainError::ReplayDetected)
+        ));
+
+        // A failed request does not consume the nonce
+        drop(reservation);
+        let reservation = require_fresh_nonce(&headers, &nonce_store).unwrap();
+
+        commit_used_nonce(reservation);
+        assert!(matches!(
+            require_fresh_nonce(&headers, &nonce_store),
+            Err(GoudChainError::ReplayDetected)
+        ));
+    }
+
+    #[test]
+    fn test_reserve_signature_nonce() {
+        let nonce_store = create_test_nonce_store();
+        assert!(reserve_signature_nonce(None, &nonce_store)
+            .unwrap()
+            .is_none());
+
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let headers = signed_headers(&generate_signing_key(), "POST", "/x", "{}", &nonce);
+        let signature = RequestSignature::from_headers(&headers).unwrap().unwrap();
+
+        let reservation = reserve_signature_nonce(Some(&signature), &nonce_store)
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            reserve_signature_nonce(Some(&signature), &nonce_store),
+            Err(GoudChainError::ReplayDetected)
+        ));
+
+        commit_used_nonce(reservation);
+        assert!(nonce_store.is_nonce_used(&nonce).unwrap());
+    }
+
+    #[test]
+    fn test_header_signature_missing_nonce() {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_SIGNATURE_HEADER, "abcd".parse().unwrap());
+
+        assert!(matches!(
+            RequestSignature::from_headers(&headers),
+            Err(GoudChainError::InvalidRequestSignature(_))
+        ));
+        assert!(RequestSignature::from_headers(&HeaderMap::new())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_header_signature_accepts_either_path_form() {
+        let nonce_store = create_test_nonce_store();
+        let signing_key = generate_signing_key();
+        let public_key = get_public_key_hex(&signing_key);
+        let timestamp = Utc::now().timestamp();
+
+        let signed = |path: &str| RequestSignature {
+            signature: sign_message(
+                format!("POST{}{{}}nonce-1{}", path, timestamp).as_bytes(),
+                &signing_key,
+            ),
+            nonce: "nonce-1".to_string(),
+            timestamp,
+        };
+
+        for signed_path in ["/v1/data/submit", "/data/submit"] {
+            for request_path in ["/v1/data/submit", "/data/submit"] {
+                assert!(signed(signed_path)
+                    .verify("POST", request_path, "{}", &public_key, &nonce_store)
+                    .is_ok());
+            }
+        }
+        assert!(signed("/v1/data/submit_batch")
+            .verify("POST", "/data/submit", "{}", &public_key, &nonce_store)
+            .is_err());
+    }
 }
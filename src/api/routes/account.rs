@@ -1,52 +1,64 @@
 use axum::{
-    extract::Extension,
+    extract::{Extension, Path},
     http::{HeaderMap, StatusCode},
+    middleware,
     response::IntoResponse,
     Json,
 };
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{error, info, warn};
-use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_axum::router::{OpenApiRouter, UtoipaMethodRouterExt};
+use utoipa_axum::routes;
 
+use crate::api::auth::{
+    decrypt_api_key_from_jwt, ensure_token_not_revoked, extract_bearer_token,
+    generate_session_token, verify_session_token, verify_session_token_for_refresh, AuthMethod,
+    SessionLifetime, TokenScope,
+};
+use crate::api::idempotency::{idempotency_middleware, SecretResponse};
+use crate::api::internal_client::{
+    extract_auth_header, extract_replay_headers, forward_to_validator,
+};
+use crate::api::request_signature::{
+    commit_used_nonce, enforce_request_signature, parse_json_body, reserve_signature_nonce,
+    RequestSignature,
+};
 use crate::api::schemas::SubmitDataState;
 use crate::api::schemas::{
-    CreateAccountRequest, CreateAccountResponse, ErrorResponse, LoginRequest, LoginResponse,
+    AccountExistsRequest, AccountExistsResponse, AccountPublicKeyResponse, CreateAccountRequest,
+    CreateAccountResponse, ErrorResponse, LoginRequest, LoginResponse, MessageResponse,
+    RotateApiKeyResponse, UpdateAccountMetadataRequest, UpdateAccountMetadataResponse,
+};
+use crate::api::security_audit::{
+    authenticate, extract_client_ip, hash_request_api_key, record_security_event,
 };
-use crate::api::{RateLimitResult, RateLimiter};
+use crate::api::{global_operations_tracker, RateLimitCategory, RateLimitResult, RateLimiter};
 use crate::config::Config;
-use crate::constants::{CHECKPOINT_INTERVAL, SESSION_EXPIRY_SECONDS};
-use crate::crypto::{encode_api_key, generate_api_key, generate_signing_key, hash_api_key_hex};
+use crate::constants::{LOGIN_MIN_RESPONSE_MILLIS, SESSION_REFRESH_GRACE_SECONDS};
+use crate::crypto::{
+    api_key_kdf, encode_api_key, generate_api_key_with_kdf, generate_signing_key, hash_api_key_hex,
+    validate_public_key,
+};
 use crate::domain::{Blockchain, UserAccount};
 use crate::network::P2PNode;
+use crate::storage::NonceStore;
 use crate::types::*;
 
 use super::ACCOUNT_TAG;
 
-/// Account routes
+/// Account routes (account creation honors `Idempotency-Key`)
 pub fn router() -> OpenApiRouter {
     OpenApiRouter::new()
-        .routes(routes!(create_account))
+        .routes(routes!(create_account).layer(middleware::from_fn(idempotency_middleware)))
         .routes(routes!(login))
-}
-
-/// Extract client IP from headers (X-Real-IP or X-Forwarded-For)
-fn extract_client_ip(headers: &HeaderMap) -> String {
-    if let Some(ip) = headers.get("x-real-ip") {
-        if let Ok(ip_str) = ip.to_str() {
-            return ip_str.to_string();
-        }
-    }
-
-    if let Some(forwarded) = headers.get("x-forwarded-for") {
-        if let Ok(forwarded_str) = forwarded.to_str() {
-            if let Some(first_ip) = forwarded_str.split(',').next() {
-                return first_ip.trim().to_string();
-            }
-        }
-    }
-
-    "unknown".to_string()
+        .routes(routes!(account_exists))
+        .routes(routes!(refresh_session))
+        .routes(routes!(logout))
+        .routes(routes!(get_account_public_key))
+        .routes(routes!(update_account_metadata))
+        .routes(routes!(rotate_api_key))
 }
 
 /// Helper to add rate limit headers to response
@@ -71,15 +83,22 @@ fn add_rate_limit_headers(
 /// Generates a new blockchain account with a unique API key for authentication.
 /// The API key is cryptographically derived and shown only once - it must be stored securely by the client.
 /// Account creation is rate-limited by client IP address to prevent abuse.
+/// With an `Idempotency-Key` header, a retry with the same key and body from the same client IP
+/// gets 409 naming the created account instead of creating another one; the API key is not replayed.
 #[utoipa::path(
     post,
     path = "/create",
     tag = ACCOUNT_TAG,
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Client-chosen key that makes retries of this request safe")
+    ),
     request_body = CreateAccountRequest,
     responses(
         (status = 201, description = "Account created successfully", body = CreateAccountResponse),
         (status = 400, description = "Invalid request or account already exists", body = ErrorResponse),
         (status = 403, description = "Only current validator can create accounts", body = ErrorResponse),
+        (status = 409, description = "A request with the same Idempotency-Key is still in progress, or already created an account (IDEMPOTENCY_KEY_USED)", body = ErrorResponse),
+        (status = 422, description = "Idempotency-Key was already used with a different request body", body = ErrorResponse),
         (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -88,10 +107,14 @@ async fn create_account(
     headers: HeaderMap,
     Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
     Extension(p2p): Extension<Arc<P2PNode>>,
+    Extension(config): Extension<Arc<Config>>,
     Extension(rate_limiter): Extension<Arc<RateLimiter>>,
     Extension(state): Extension<SubmitDataState>,
     Json(request): Json<CreateAccountRequest>,
 ) -> Result<impl IntoResponse> {
+    // Refuse writes while the node is in read-only maintenance mode
+    blockchain.read().await.ensure_writable()?;
+
     let audit_logger = &state.audit_logger;
     let ws_broadcaster = &state.ws_broadcaster;
 
@@ -100,17 +123,14 @@ async fn create_account(
     let ip_hash = hash_api_key_hex(client_ip.as_bytes());
 
     // Check rate limit (write operation - account creation)
-    let rate_limit_result = match rate_limiter.check_limit(&ip_hash, &client_ip, true) {
-        Ok(result) => result,
-        Err(e) => {
-            error!(error = %e, "Rate limit check failed, allowing account creation");
-            RateLimitResult::Allowed {
-                limit: 10,
-                remaining: 10,
-                reset_at: chrono::Utc::now().timestamp() + 60,
+    let rate_limit_result =
+        match rate_limiter.check_limit(&ip_hash, &client_ip, RateLimitCategory::AccountCreate) {
+            Ok(result) => result,
+            Err(e) => {
+                error!(error = %e, "Rate limit check failed, allowing account creation");
+                rate_limiter.fail_open(RateLimitCategory::AccountCreate)
             }
-        }
-    };
+        };
 
     // Handle rate limit result
     match &rate_limit_result {
@@ -125,6 +145,17 @@ async fn create_account(
                 violation_count = violation_count,
                 "Account creation blocked by rate limiter"
             );
+            record_security_event(
+                &state,
+                &config,
+                AuditEventType::RateLimited,
+                &client_ip,
+                serde_json::json!({
+                    "endpoint": "/account/create",
+                    "ban_level": format!("{:?}", ban_level),
+                    "violation_count": violation_count,
+                }),
+            );
             let error = GoudChainError::RateLimitExceeded {
                 retry_after: *retry_after,
                 violation_count: *violation_count,
@@ -151,28 +182,34 @@ async fn create_account(
         }
     }
 
-    // Check if this node is the authorized validator
-    use crate::domain::blockchain::{get_current_validator, is_authorized_validator};
+    request.validate()?;
+
+    // Validate the request signing key before any block work
+    if let Some(public_key) = &request.public_key {
+        validate_public_key(public_key).map_err(|_| {
+            GoudChainError::InvalidRequestBody(
+                "public_key must be a hex-encoded Ed25519 public key".to_string(),
+            )
+        })?;
+    }
 
+    // Check if this node is the authorized validator
     let blockchain_guard = blockchain.read().await;
     let next_block_number = blockchain_guard
         .chain
         .last()
         .map(|b| b.index + 1)
         .unwrap_or(1);
-    let is_validator = is_authorized_validator(
-        &blockchain_guard.node_id,
-        next_block_number,
-        &blockchain_guard.validator_config,
-    );
+    let is_validator = blockchain_guard
+        .validator_config
+        .is_node_authorized(&blockchain_guard.node_id, next_block_number);
     let node_id = blockchain_guard.node_id.clone();
     let validator_config = blockchain_guard.validator_config.clone();
     drop(blockchain_guard);
 
     if !is_validator {
         // Forward request to the correct validator
-        let expected_validator =
-            get_current_validator(&validator_config.validators, next_block_number);
+        let expected_validator = validator_config.get_validator_for_block(next_block_number);
         warn!(
             current_node = %node_id,
             expected_validator = %expected_validator,
@@ -180,61 +217,52 @@ async fn create_account(
             "Forwarding account creation to validator node"
         );
 
-        match validator_config.get_validator_address(&expected_validator) {
-            Some(validator_addr) => {
-                use crate::api::internal_client::forward_request_to_node;
-
-                let body = serde_json::to_string(&request).map_err(|e| {
-                    GoudChainError::Internal(format!("Serialization failed: {}", e))
-                })?;
+        let body = serde_json::to_string(&request)
+            .map_err(|e| GoudChainError::Internal(format!("Serialization failed: {}", e)))?;
 
-                match forward_request_to_node(
-                    &validator_addr,
-                    "POST",
-                    "/account/create",
-                    &body,
-                    "application/json",
+        return match forward_to_validator(
+            &blockchain,
+            "POST",
+            "/account/create",
+            &body,
+            "application/json",
+            None,
+            &[],
+        )
+        .await
+        {
+            Ok((status_code, response_body)) => {
+                info!(status = status_code, "Forwarded request successfully");
+                // The validator's reply carries the new API key; keep it out of idempotency storage
+                let account_id = serde_json::from_str::<serde_json::Value>(&response_body)
+                    .ok()
+                    .and_then(|body| body["account_id"].as_str().map(str::to_string));
+                let mut response = (
+                    StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK),
+                    [(axum::http::header::CONTENT_TYPE, "application/json")],
+                    response_body,
                 )
-                .await
-                {
-                    Ok((status_code, response_body)) => {
-                        info!(
-                            validator = %expected_validator,
-                            status = status_code,
-                            "Forwarded request successfully"
-                        );
-                        return Ok((
-                            StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK),
-                            [(axum::http::header::CONTENT_TYPE, "application/json")],
-                            response_body,
-                        )
-                            .into_response());
-                    }
-                    Err(e) => {
-                        error!(error = %e, "Failed to forward request to validator");
-                        return Err(GoudChainError::Internal(format!(
-                            "Failed to forward to validator: {}",
-                            e
-                        )));
-                    }
+                    .into_response();
+                if let Some(account_id) = account_id {
+                    response
+                        .extensions_mut()
+                        .insert(SecretResponse { account_id });
                 }
+                Ok(response)
             }
-            None => {
-                error!(validator = %expected_validator, "Unknown validator address");
-                return Err(GoudChainError::Internal(format!(
-                    "Unknown validator: {}",
-                    expected_validator
-                )));
+            Err(e) => {
+                error!(error = %e, "Failed to forward request to validator");
+                Err(e)
             }
-        }
+        };
     }
 
     // This node IS the validator - proceed with block creation
-    let api_key = generate_api_key();
+    let api_key = generate_api_key_with_kdf(&config.api_key_kdf);
     let signing_key = generate_signing_key();
 
     // Create user account
-    match UserAccount::new(&api_key, &signing_key, request.metadata) {
+    match UserAccount::new(&api_key, &signing_key, request.metadata, request.public_key) {
         Ok(account) => {
             let account_id = account.account_id.clone();
 
@@ -251,9 +279,7 @@ async fn create_account(
                             }
 
                             // Save checkpoint if needed
-                            #[allow(unknown_lints)]
-                            #[allow(clippy::manual_is_multiple_of)]
-                            if block.index % CHECKPOINT_INTERVAL == 0 {
+                            if blockchain_guard.is_checkpoint_index(block.index) {
                                 if let Err(e) = p2p
                                     .blockchain_store
                                     .save_checkpoint(block.index, &block.hash)
@@ -264,6 +290,7 @@ async fn create_account(
 
                             let block_index = block.index;
                             drop(blockchain_guard);
+                            global_operations_tracker().record(1);
 
                             let response = CreateAccountResponse {
                                 account_id: account_id.clone(),
@@ -286,8 +313,11 @@ async fn create_account(
 
                             // Add rate limit headers to success response
                             let rate_headers = rate_limiter.create_headers(&rate_limit_result);
-                            let response_obj =
+                            let mut response_obj =
                                 (StatusCode::CREATED, Json(response)).into_response();
+                            response_obj
+                                .extensions_mut()
+                                .insert(SecretResponse { account_id });
 
                             // Broadcast block in background
                             let block_arc = Arc::new(block);
@@ -327,11 +357,587 @@ async fn create_account(
     }
 }
 
+/// Update account metadata
+///
+/// Replaces the authenticated account's metadata. The chain is append-only, so this stores a
+/// new revision of the account record; account lookups resolve to the newest revision.
+/// Send `null` to clear the metadata. Requires direct API key authentication.
+#[utoipa::path(
+    put,
+    path = "/metadata",
+    tag = ACCOUNT_TAG,
+    request_body = UpdateAccountMetadataRequest,
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "Metadata updated", body = UpdateAccountMetadataResponse),
+        (status = 400, description = "Metadata is not a JSON object or is nested too deeply", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant write access", body = ErrorResponse),
+        (status = 409, description = "Nonce already used (replay detected)", body = ErrorResponse),
+        (status = 413, description = "Metadata exceeds 4 KB", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+async fn update_account_metadata(
+    headers: HeaderMap,
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(p2p): Extension<Arc<P2PNode>>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
+    Extension(state): Extension<SubmitDataState>,
+    Extension(nonce_store): Extension<Arc<NonceStore>>,
+    body: String,
+) -> Result<impl IntoResponse> {
+    // Refuse writes while the node is in read-only maintenance mode
+    blockchain.read().await.ensure_writable()?;
+
+    // Extract Authorization and signature headers (needed for verification and forwarding)
+    let auth_header_value = extract_auth_header(&headers);
+    let request_signature = RequestSignature::from_headers(&headers)?;
+    let request: UpdateAccountMetadataRequest = parse_json_body(&body)?;
+
+    let auth = authenticate(&headers, &config, &state)?;
+    auth.require_scope(TokenScope::Write)?;
+
+    // The API key is needed to encrypt the metadata and the account envelope
+    let (api_key, api_key_hash) = match auth {
+        AuthMethod::ApiKey(key) => {
+            let hash =
+                hash_request_api_key(&rate_limiter, &extract_client_ip(&headers), &key).await?;
+            (key, hash)
+        }
+        AuthMethod::SessionToken(_) => {
+            return Err(GoudChainError::Unauthorized(
+                "Direct API key required for metadata updates".to_string(),
+            ));
+        }
+    };
+
+    let client_ip = extract_client_ip(&headers);
+
+    // Check rate limit (write operation, shares the submit budget)
+    let rate_limit_result =
+        match rate_limiter.check_limit(&api_key_hash, &client_ip, RateLimitCategory::Submit) {
+            Ok(result) => result,
+            Err(e) => {
+                error!(error = %e, "Rate limit check failed, allowing request");
+                rate_limiter.fail_open(RateLimitCategory::Submit)
+            }
+        };
+
+    match &rate_limit_result {
+        RateLimitResult::Blocked {
+            ban_level,
+            retry_after,
+            violation_count,
+        } => {
+            warn!(
+                api_key_hash = %api_key_hash,
+                ban_level = ?ban_level,
+                violation_count = violation_count,
+                "Metadata update blocked by rate limiter"
+            );
+            record_security_event(
+                &state,
+                &config,
+                AuditEventType::RateLimited,
+                &client_ip,
+                serde_json::json!({
+                    "endpoint": "/account/metadata",
+                    "ban_level": format!("{:?}", ban_level),
+                    "violation_count": violation_count,
+                }),
+            );
+            let error = GoudChainError::ApiKeyBanned {
+                ban_level: format!("{:?}", ban_level),
+                expires_at: chrono::Utc::now().timestamp() + *retry_after as i64,
+            };
+            let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+            let response = error.into_response();
+            return Ok(add_rate_limit_headers(response, rate_headers));
+        }
+        RateLimitResult::Warning {
+            violation_count, ..
+        } => {
+            warn!(
+                api_key_hash = %api_key_hash,
+                violation_count = violation_count,
+                "Rate limit warning on metadata update"
+            );
+        }
+        RateLimitResult::Allowed { remaining, .. } => {
+            info!(
+                api_key_hash = %api_key_hash,
+                remaining = remaining,
+                "Metadata update rate limit check passed"
+            );
+        }
+    }
+
+    request.validate()?;
+
+    // Verify account exists (the newest revision carries the registered request key)
+    let blockchain_guard = blockchain.read().await;
+    let account =
+        match blockchain_guard.find_account_with_hash(&api_key, Some(api_key_hash.clone())) {
+            Some(account) => account,
+            None => {
+                return Err(GoudChainError::AccountNotFound);
+            }
+        };
+
+    enforce_request_signature(
+        request_signature.as_ref(),
+        "PUT",
+        "/account/metadata",
+        &body,
+        &account,
+        &config,
+        &nonce_store,
+    )?;
+    let nonce_reservation = reserve_signature_nonce(request_signature.as_ref(), &nonce_store)?;
+
+    // Check if this node is the authorized validator
+    let next_block_number = blockchain_guard
+        .chain
+        .last()
+        .map(|b| b.index + 1)
+        .unwrap_or(1);
+    let is_validator = blockchain_guard
+        .validator_config
+        .is_node_authorized(&blockchain_guard.node_id, next_block_number);
+    let node_id = blockchain_guard.node_id.clone();
+    let validator_config = blockchain_guard.validator_config.clone();
+    let signing_key = blockchain_guard.node_signing_key.clone();
+    drop(blockchain_guard);
+
+    if !is_validator {
+        // Forward request to the correct validator
+        let expected_validator = validator_config.get_validator_for_block(next_block_number);
+        warn!(
+            current_node = %node_id,
+            expected_validator = %expected_validator,
+            next_block = next_block_number,
+            "Forwarding metadata update to validator node"
+        );
+
+        return match forward_to_validator(
+            &blockchain,
+            "PUT",
+            "/account/metadata",
+            &body,
+            "application/json",
+            auth_header_value.as_deref(),
+            &extract_replay_headers(&headers),
+        )
+        .await
+        {
+            Ok((status_code, response_body)) => {
+                info!(
+                    status = status_code,
+                    "Forwarded metadata update successfully"
+                );
+                Ok((
+                    StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK),
+                    [(axum::http::header::CONTENT_TYPE, "application/json")],
+                    response_body,
+                )
+                    .into_response())
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to forward metadata update to validator");
+                Err(e)
+            }
+        };
+    }
+
+    // This node IS the validator - append the account revision and create block
+    let signing_key = signing_key
+        .ok_or_else(|| GoudChainError::Internal("Node signing key not available".to_string()))?;
+
+    let revision = account
+        .with_metadata(&api_key, &signing_key, request.metadata)
+        .inspect_err(|e| error!(error = %e, "Failed to create account revision"))?;
+    let account_id = revision.account_id.clone();
+
+    let mut blockchain_guard = blockchain.write().await;
+    blockchain_guard
+        .add_account_with_key(revision, api_key.clone())
+        .inspect_err(|e| error!(error = %e, "Failed to add account revision"))?;
+    let block = blockchain_guard
+        .add_block()
+        .inspect_err(|e| error!(error = %e, "Failed to add block"))?;
+
+    if let Err(e) = p2p.blockchain_store.save_block(&block) {
+        error!(error = %e, "Failed to save block to RocksDB");
+    }
+
+    if blockchain_guard.is_checkpoint_index(block.index) {
+        if let Err(e) = p2p
+            .blockchain_store
+            .save_checkpoint(block.index, &block.hash)
+        {
+            error!(error = %e, "Failed to save checkpoint");
+        }
+    }
+
+    let block_index = block.index;
+    drop(blockchain_guard);
+    global_operations_tracker().record(1);
+
+    if let Some(reservation) = nonce_reservation {
+        commit_used_nonce(reservation);
+    }
+
+    // Audit log
+    if let Err(e) = state.audit_logger.log(
+        &api_key,
+        AuditEventType::AccountMetadataUpdated,
+        None,
+        &client_ip,
+        serde_json::json!({"account_id": account_id, "block": block_index}),
+    ) {
+        error!(error = %e, "Failed to log metadata update audit event");
+    }
+
+    let response = UpdateAccountMetadataResponse {
+        message: "Account metadata updated".to_string(),
+        account_id,
+        block_number: block_index,
+    };
+    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+    let response_obj = Json(response).into_response();
+
+    // Broadcast block to peers and WebSocket subscribers in background
+    let block_arc = Arc::new(block);
+    let p2p_clone = Arc::clone(&p2p);
+    let block_ref = Arc::clone(&block_arc);
+    tokio::spawn(async move {
+        p2p_clone.broadcast_block(&block_ref).await;
+    });
+
+    let ws_clone = Arc::clone(&state.ws_broadcaster);
+    let bhash = block_arc.hash.clone();
+    tokio::spawn(async move {
+        ws_clone
+            .broadcast_blockchain_update(block_index, bhash)
+            .await;
+    });
+
+    Ok(add_rate_limit_headers(response_obj, rate_headers))
+}
+
+/// Refuse a rotated-away key, and its session tokens, on this node from now on
+fn revoke_rotated_key(state: &SubmitDataState, api_key: &[u8], api_key_hash: &str) {
+    if let Err(e) = state
+        .token_revocations
+        .revoke_api_key(api_key, api_key_hash)
+    {
+        error!(error = %e, "Failed to record rotated API key revocation");
+    }
+}
+
+/// Rotate API key
+///
+/// Issues a new API key for the authenticated account and revokes the current one. In one
+/// block, every live collection is re-encrypted under the new key (collection IDs are kept),
+/// outgoing shares are re-granted under new grant IDs, and the account record moves to the
+/// new key. The old key stops authenticating once the block is added. Blocks written before
+/// the rotation stay decryptable with the old key, and shares other accounts sealed to the
+/// old key are not carried over. Requires direct API key authentication.
+#[utoipa::path(
+    post,
+    path = "/rotate_key",
+    tag = ACCOUNT_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "Key rotated; the response holds the new API key", body = RotateApiKeyResponse),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant write access", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 409, description = "Nonce already used (replay detected)", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+async fn rotate_api_key(
+    headers: HeaderMap,
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(p2p): Extension<Arc<P2PNode>>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
+    Extension(state): Extension<SubmitDataState>,
+    Extension(nonce_store): Extension<Arc<NonceStore>>,
+    body: String,
+) -> Result<impl IntoResponse> {
+    // Refuse writes while the node is in read-only maintenance mode
+    blockchain.read().await.ensure_writable()?;
+
+    // Extract Authorization and signature headers (needed for verification and forwarding)
+    let auth_header_value = extract_auth_header(&headers);
+    let request_signature = RequestSignature::from_headers(&headers)?;
+
+    let auth = authenticate(&headers, &config, &state)?;
+    auth.require_scope(TokenScope::Write)?;
+
+    // The old API key is needed to decrypt everything that moves to the new key
+    let (api_key, api_key_hash) = match auth {
+        AuthMethod::ApiKey(key) => {
+            let hash =
+                hash_request_api_key(&rate_limiter, &extract_client_ip(&headers), &key).await?;
+            (key, hash)
+        }
+        AuthMethod::SessionToken(_) => {
+            return Err(GoudChainError::Unauthorized(
+                "Direct API key required for key rotation".to_string(),
+            ));
+        }
+    };
+
+    let client_ip = extract_client_ip(&headers);
+
+    // Check rate limit (write operation, shares the submit budget)
+    let rate_limit_result =
+        match rate_limiter.check_limit(&api_key_hash, &client_ip, RateLimitCategory::Submit) {
+            Ok(result) => result,
+            Err(e) => {
+                error!(error = %e, "Rate limit check failed, allowing request");
+                rate_limiter.fail_open(RateLimitCategory::Submit)
+            }
+        };
+
+    match &rate_limit_result {
+        RateLimitResult::Blocked {
+            ban_level,
+            retry_after,
+            violation_count,
+        } => {
+            warn!(
+                api_key_hash = %api_key_hash,
+                ban_level = ?ban_level,
+                violation_count = violation_count,
+                "Key rotation blocked by rate limiter"
+            );
+            record_security_event(
+                &state,
+                &config,
+                AuditEventType::RateLimited,
+                &client_ip,
+                serde_json::json!({
+                    "endpoint": "/account/rotate_key",
+                    "ban_level": format!("{:?}", ban_level),
+                    "violation_count": violation_count,
+                }),
+            );
+            let error = GoudChainError::ApiKeyBanned {
+                ban_level: format!("{:?}", ban_level),
+                expires_at: chrono::Utc::now().timestamp() + *retry_after as i64,
+            };
+            let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+            let response = error.into_response();
+            return Ok(add_rate_limit_headers(response, rate_headers));
+        }
+        RateLimitResult::Warning {
+            violation_count, ..
+        } => {
+            warn!(
+                api_key_hash = %api_key_hash,
+                violation_count = violation_count,
+                "Rate limit warning on key rotation"
+            );
+        }
+        RateLimitResult::Allowed { remaining, .. } => {
+            info!(
+                api_key_hash = %api_key_hash,
+                remaining = remaining,
+                "Key rotation rate limit check passed"
+            );
+        }
+    }
+
+    // Verify account exists (the newest revision carries the registered request key)
+    let blockchain_guard = blockchain.read().await;
+    let account =
+        match blockchain_guard.find_account_with_hash(&api_key, Some(api_key_hash.clone())) {
+            Some(account) => account,
+            None => {
+                return Err(GoudChainError::AccountNotFound);
+            }
+        };
+
+    enforce_request_signature(
+        request_signature.as_ref(),
+        "POST",
+        "/account/rotate_key",
+        &body,
+        &account,
+        &config,
+        &nonce_store,
+    )?;
+    let nonce_reservation = reserve_signature_nonce(request_signature.as_ref(), &nonce_store)?;
+
+    // Check if this node is the authorized validator
+    let next_block_number = blockchain_guard
+        .chain
+        .last()
+        .map(|b| b.index + 1)
+        .unwrap_or(1);
+    let is_validator = blockchain_guard
+        .validator_config
+        .is_node_authorized(&blockchain_guard.node_id, next_block_number);
+    let node_id = blockchain_guard.node_id.clone();
+    let validator_config = blockchain_guard.validator_config.clone();
+    let signing_key = blockchain_guard.node_signing_key.clone();
+    drop(blockchain_guard);
+
+    if !is_validator {
+        // Forward request to the correct validator
+        let expected_validator = validator_config.get_validator_for_block(next_block_number);
+        warn!(
+            current_node = %node_id,
+            expected_validator = %expected_validator,
+            next_block = next_block_number,
+            "Forwarding key rotation to validator node"
+        );
+
+        return match forward_to_validator(
+            &blockchain,
+            "POST",
+            "/account/rotate_key",
+            &body,
+            "application/json",
+            auth_header_value.as_deref(),
+            &extract_replay_headers(&headers),
+        )
+        .await
+        {
+            Ok((status_code, response_body)) => {
+                info!(status = status_code, "Forwarded key rotation successfully");
+                if (200..300).contains(&status_code) {
+                    revoke_rotated_key(&state, &api_key, &api_key_hash);
+                }
+                Ok((
+                    StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK),
+                    [(axum::http::header::CONTENT_TYPE, "application/json")],
+                    response_body,
+                )
+                    .into_response())
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to forward key rotation to validator");
+                Err(e)
+            }
+        };
+    }
+
+    // This node IS the validator - queue the rotation and create block
+    let signing_key = signing_key
+        .ok_or_else(|| GoudChainError::Internal("Node signing key not available".to_string()))?;
+    let new_api_key = generate_api_key_with_kdf(&config.api_key_kdf);
+    let account_id = account.account_id.clone();
+
+    // One write lock covers the scan and the block, so no write under the old key slips between
+    let mut blockchain_guard = blockchain.write().await;
+    let rotation = blockchain_guard
+        .queue_key_rotation(&account, &api_key, new_api_key.clone(), &signing_key)
+        .inspect_err(|e| error!(error = %e, "Failed to prepare key rotation"))?;
+    let block = blockchain_guard
+        .add_block()
+        .inspect_err(|e| error!(error = %e, "Failed to add block"))?;
+
+    if let Err(e) = p2p.blockchain_store.save_block(&block) {
+        error!(error = %e, "Failed to save block to RocksDB");
+    }
+
+    if blockchain_guard.is_checkpoint_index(block.index) {
+        if let Err(e) = p2p
+            .blockchain_store
+            .save_checkpoint(block.index, &block.hash)
+        {
+            error!(error = %e, "Failed to save checkpoint");
+        }
+    }
+
+    let block_index = block.index;
+    drop(blockchain_guard);
+    global_operations_tracker().record(1 + rotation.reencrypted_collections as u64);
+
+    if let Some(reservation) = nonce_reservation {
+        commit_used_nonce(reservation);
+    }
+    revoke_rotated_key(&state, &api_key, &api_key_hash);
+
+    // Audit log (under the new key: the old key's log is no longer reachable)
+    if let Err(e) = state.audit_logger.log(
+        &new_api_key,
+        AuditEventType::ApiKeyRotated,
+        None,
+        &client_ip,
+        serde_json::json!({
+            "account_id": account_id,
+            "block": block_index,
+            "reencrypted_collections": rotation.reencrypted_collections,
+        }),
+    ) {
+        error!(error = %e, "Failed to log key rotation audit event");
+    }
+
+    info!(
+        reencrypted_collections = rotation.reencrypted_collections,
+        regranted_shares = rotation.regranted_shares.len(),
+        "API key rotated"
+    );
+
+    let response = RotateApiKeyResponse {
+        account_id,
+        api_key: encode_api_key(&new_api_key),
+        block_number: block_index,
+        reencrypted_collections: rotation.reencrypted_collections,
+        regranted_shares: rotation
+            .regranted_shares
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+        warning: "SAVE THIS API KEY SECURELY. The previous key no longer authenticates."
+            .to_string(),
+    };
+    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+    let response_obj = Json(response).into_response();
+
+    // Broadcast block to peers and WebSocket subscribers in background
+    let block_arc = Arc::new(block);
+    let p2p_clone = Arc::clone(&p2p);
+    let block_ref = Arc::clone(&block_arc);
+    tokio::spawn(async move {
+        p2p_clone.broadcast_block(&block_ref).await;
+    });
+
+    let ws_clone = Arc::clone(&state.ws_broadcaster);
+    let bhash = block_arc.hash.clone();
+    tokio::spawn(async move {
+        ws_clone
+            .broadcast_blockchain_update(block_index, bhash)
+            .await;
+    });
+
+    Ok(add_rate_limit_headers(response_obj, rate_headers))
+}
+
 /// Login with API key
 ///
 /// Authenticates a user with their API key and returns a JWT session token.
 /// The session token can be used for subsequent API requests instead of the raw API key.
-/// Session tokens expire after 1 hour and must be refreshed by logging in again.
+/// Request a `read` scope to mint a least-privilege token for dashboards or untrusted frontends.
+/// Session tokens expire after 1 hour unless `ttl_seconds` asks for another lifetime (up to
+/// MAX_SESSION_TTL_SECONDS); use `/account/refresh` to extend a session without the API key.
+/// `remember_me` issues a long-lived read-only token (up to REMEMBER_ME_TTL_SECONDS).
 #[utoipa::path(
     post,
     path = "/login",
@@ -339,6 +945,7 @@ async fn create_account(
     request_body = LoginRequest,
     responses(
         (status = 200, description = "Login successful", body = LoginResponse),
+        (status = 400, description = "Requested ttl_seconds is out of range", body = ErrorResponse),
         (status = 401, description = "Invalid API key or account not found", body = ErrorResponse),
         (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
@@ -348,29 +955,65 @@ async fn login(
     headers: HeaderMap,
     Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
     Extension(config): Extension<Arc<Config>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
     Extension(state): Extension<SubmitDataState>,
     Json(request): Json<LoginRequest>,
+) -> Result<Json<LoginResponse>> {
+    // Every outcome (success, unknown key, malformed key) waits out the same floor,
+    // so response time does not tell callers which one happened
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(LOGIN_MIN_RESPONSE_MILLIS);
+    let result = attempt_login(
+        &headers,
+        &blockchain,
+        &config,
+        &rate_limiter,
+        &state,
+        request,
+    )
+    .await;
+    tokio::time::sleep_until(deadline).await;
+    result
+}
+
+/// Authenticate a login request and mint its session token
+async fn attempt_login(
+    headers: &HeaderMap,
+    blockchain: &RwLock<Blockchain>,
+    config: &Config,
+    rate_limiter: &Arc<RateLimiter>,
+    state: &SubmitDataState,
+    request: LoginRequest,
 ) -> Result<Json<LoginResponse>> {
     let audit_logger = &state.audit_logger;
+    let client_ip = extract_client_ip(headers);
+    let lifetime = SessionLifetime::requested(request.ttl_seconds, request.remember_me, config)?;
+    let scope = lifetime.restrict(request.scope);
 
-    // Decode API key (fast operation, minimal timing leak)
+    // Decode API key (fast operation, minimal timing leak); keys naming Argon2id parameters
+    // this node does not use are refused like malformed ones, before any hashing
     let api_key = match crate::crypto::decode_api_key(&request.api_key) {
-        Ok(key) => key,
-        Err(_) => {
+        Ok(key) if config.accepts_api_key_kdf(&api_key_kdf(&key)) => key,
+        _ => {
             // Still perform expensive hash on dummy data for timing consistency
-            crate::crypto::dummy_hash_for_timing(b"invalid_api_key_format_dummy_32b");
+            dummy_hash_off_thread().await;
             crate::crypto::dummy_constant_time_compare();
+            record_security_event(
+                state,
+                config,
+                AuditEventType::LoginFailed,
+                &client_ip,
+                serde_json::json!({"reason": "invalid_api_key_format"}),
+            );
             return Err(GoudChainError::AuthenticationFailed);
         }
     };
 
-    // ALWAYS hash API key (expensive: 100k iterations) - prevents timing leak
-    let api_key_hash_hex = hash_api_key_hex(&api_key);
+    // ALWAYS hash API key (expensive by design) - prevents timing leak
+    let api_key_hash_hex = hash_request_api_key(rate_limiter, &client_ip, &api_key).await?;
 
-    // Find account
+    // Find account (full scan: lookup time must not depend on where the account sits)
     let blockchain_guard = blockchain.read().await;
-    let account_option =
-        blockchain_guard.find_account_with_hash(&api_key, Some(api_key_hash_hex.clone()));
+    let account_option = blockchain_guard.find_account_uniform(&api_key, &api_key_hash_hex);
 
     // Always perform constant-time comparison (even if account doesn't exist)
     let auth_success = match &account_option {
@@ -393,6 +1036,13 @@ async fn login(
     if !auth_success {
         drop(blockchain_guard);
         info!("Authentication failed");
+        record_security_event(
+            state,
+            config,
+            AuditEventType::LoginFailed,
+            &client_ip,
+            serde_json::json!({"reason": "invalid_credentials"}),
+        );
         return Err(GoudChainError::AuthenticationFailed); // Generic error
     }
 
@@ -400,29 +1050,37 @@ async fn login(
     let account = account_option.unwrap(); // Safe: verified auth_success
     let api_key_hash = api_key_hash_hex;
 
-    match crate::api::auth::generate_session_token(
+    match generate_session_token(
         account.account_id.clone(),
         &api_key,
         api_key_hash,
-        &config,
+        scope,
+        lifetime,
+        config,
     ) {
         Ok(token) => {
             let response = LoginResponse {
                 session_token: token,
-                expires_in: SESSION_EXPIRY_SECONDS,
+                expires_in: lifetime.ttl_seconds,
                 account_id: account.account_id.clone(),
+                scope,
+                remember_me: lifetime.remember_me,
             };
 
             drop(blockchain_guard);
 
             // Audit log: Account login
-            let client_ip = extract_client_ip(&headers);
             if let Err(e) = audit_logger.log(
                 &api_key,
                 AuditEventType::AccountLogin,
                 None,
                 &client_ip,
-                serde_json::json!({"account_id": account.account_id}),
+                serde_json::json!({
+                    "account_id": account.account_id,
+                    "scope": scope,
+                    "ttl_seconds": lifetime.ttl_seconds,
+                    "remember_me": lifetime.remember_me,
+                }),
             ) {
                 error!(error = %e, "Failed to log login audit event");
             }
@@ -436,3 +1094,390 @@ async fn login(
         }
     }
 }
+
+/// Check whether an API key belongs to an account
+///
+/// Lets clients validate a stored API key before logging in. Every answer tells the caller
+/// whether a key is live, so the endpoint is off unless ENABLE_ACCOUNT_EXISTS=true (404
+/// otherwise), limited per client IP (RATE_LIMIT_ACCOUNT_EXISTS_PER_SEC, default 1/s, with
+/// bans applying as for writes; forwarding headers only count from TRUSTED_PROXIES), and answers take the same time whatever the outcome: the
+/// lookup scans the whole chain and responses are padded like logins. Malformed, unknown and
+/// rotated-away keys all report `exists: false`.
+#[utoipa::path(
+    post,
+    path = "/exists",
+    tag = ACCOUNT_TAG,
+    request_body = AccountExistsRequest,
+    responses(
+        (status = 200, description = "Lookup complete", body = AccountExistsResponse),
+        (status = 404, description = "Account existence checks are disabled on this node", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 500, description = "Rate limit state unavailable", body = ErrorResponse)
+    )
+)]
+async fn account_exists(
+    headers: HeaderMap,
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
+    Extension(state): Extension<SubmitDataState>,
+    Json(request): Json<AccountExistsRequest>,
+) -> Result<impl IntoResponse> {
+    if !config.account_exists_enabled {
+        return Err(GoudChainError::DataNotFound(
+            "Account existence checks are disabled on this node".to_string(),
+        ));
+    }
+
+    let client_ip = extract_client_ip(&headers);
+    let ip_hash = hash_api_key_hex(client_ip.as_bytes());
+
+    // Fails closed: without a working limiter this would be an unthrottled oracle
+    let rate_limit_result =
+        rate_limiter.check_limit(&ip_hash, &client_ip, RateLimitCategory::AccountExists)?;
+    if let RateLimitResult::Blocked {
+        ban_level,
+        retry_after,
+        violation_count,
+    } = &rate_limit_result
+    {
+        warn!(
+            client_ip = %client_ip,
+            ban_level = ?ban_level,
+            violation_count = violation_count,
+            "Account existence check blocked by rate limiter"
+        );
+        record_security_event(
+            &state,
+            &config,
+            AuditEventType::RateLimited,
+            &client_ip,
+            serde_json::json!({
+                "endpoint": "/account/exists",
+                "ban_level": format!("{:?}", ban_level),
+                "violation_count": violation_count,
+            }),
+        );
+        let error = GoudChainError::RateLimitExceeded {
+            retry_after: *retry_after,
+            violation_count: *violation_count,
+        };
+        let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+        return Ok(add_rate_limit_headers(error.into_response(), rate_headers));
+    }
+
+    // Same floor as login, so response time does not tell the outcomes apart
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(LOGIN_MIN_RESPONSE_MILLIS);
+    let api_key = crate::crypto::decode_api_key(&request.api_key)
+        .ok()
+        .filter(|key| config.accepts_api_key_kdf(&api_key_kdf(key)));
+    let exists = match api_key {
+        Some(api_key) => {
+            let api_key_hash = hash_request_api_key(&rate_limiter, &client_ip, &api_key).await?;
+            lookup_account_exists(&*blockchain.read().await, &api_key, &api_key_hash)
+        }
+        None => {
+            // A malformed key still pays for a hash
+            dummy_hash_off_thread().await;
+            false
+        }
+    };
+    tokio::time::sleep_until(deadline).await;
+
+    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+    let response = Json(AccountExistsResponse { exists }).into_response();
+    Ok(add_rate_limit_headers(response, rate_headers))
+}
+
+/// Whether an API key opens a live account (the lookup never stops early)
+fn lookup_account_exists(blockchain: &Blockchain, api_key: &[u8], api_key_hash: &str) -> bool {
+    blockchain
+        .find_account_uniform(api_key, api_key_hash)
+        .is_some()
+}
+
+/// Spend a key hash's worth of work on the blocking pool, for timing consistency
+async fn dummy_hash_off_thread() {
+    let _ = tokio::task::spawn_blocking(|| {
+        crate::crypto::dummy_hash_for_timing(b"invalid_api_key_format_dummy_32b")
+    })
+    .await;
+}
+
+/// Refresh a session token
+///
+/// Exchanges a session token for a new one with the same scope and lifetime without re-submitting
+/// the API key.
+/// Tokens that expired less than 5 minutes ago can still be refreshed.
+/// The old token is revoked so each token can only be refreshed once. Revocations are kept by the
+/// node that received the request and are not replicated to other nodes.
+#[utoipa::path(
+    post,
+    path = "/refresh",
+    tag = ACCOUNT_TAG,
+    security(
+        ("bearer_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Session refreshed successfully", body = LoginResponse),
+        (status = 401, description = "Missing, invalid, expired or revoked session token", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+async fn refresh_session(
+    headers: HeaderMap,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(state): Extension<SubmitDataState>,
+) -> Result<Json<LoginResponse>> {
+    let token = extract_bearer_token(&headers)?;
+    let claims = verify_session_token_for_refresh(token, &config)?;
+
+    // Consume the old token before issuing a new one, so concurrent refreshes of the same
+    // token can't both succeed. Revocations are node-local (see TokenRevocationStore)
+    if !state
+        .token_revocations
+        .revoke_once(&claims.jti, claims.exp + SESSION_REFRESH_GRACE_SECONDS)?
+    {
+        return Err(GoudChainError::Unauthorized(
+            "Session token has been revoked".to_string(),
+        ));
+    }
+
+    let api_key = decrypt_api_key_from_jwt(&claims.encrypted_api_key, &config)?;
+    let lifetime = SessionLifetime::of(&claims);
+    let session_token = generate_session_token(
+        claims.sub.clone(),
+        &api_key,
+        claims.api_key_hash,
+        claims.scope,
+        lifetime,
+        &config,
+    )?;
+
+    // Audit log: Session refreshed
+    let client_ip = extract_client_ip(&headers);
+    if let Err(e) = state.audit_logger.log(
+        &api_key,
+        AuditEventType::SessionRefreshed,
+        None,
+        &client_ip,
+        serde_json::json!({"account_id": claims.sub}),
+    ) {
+        error!(error = %e, "Failed to log session refresh audit event");
+    }
+
+    info!("Session refreshed");
+    Ok(Json(LoginResponse {
+        session_token,
+        expires_in: lifetime.ttl_seconds,
+        account_id: claims.sub,
+        scope: claims.scope,
+        remember_me: lifetime.remember_me,
+    }))
+}
+
+/// Logout
+///
+/// Revokes the session token used to authenticate this request.
+/// The token is rejected from then on, even before its natural expiry.
+#[utoipa::path(
+    post,
+    path = "/logout",
+    tag = ACCOUNT_TAG,
+    security(
+        ("bearer_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Logged out successfully", body = MessageResponse),
+        (status = 401, description = "Missing, invalid, expired or revoked session token", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+async fn logout(
+    headers: HeaderMap,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(state): Extension<SubmitDataState>,
+) -> Result<Json<MessageResponse>> {
+    let token = extract_bearer_token(&headers)?;
+    let claims = verify_session_token(token, &config)?;
+    ensure_token_not_revoked(&claims, &state.token_revocations)?;
+
+    // Revocation entry expires together with the token
+    state.token_revocations.revoke(&claims.jti, claims.exp)?;
+
+    // Audit log: Account logout
+    let client_ip = extract_client_ip(&headers);
+    match decrypt_api_key_from_jwt(&claims.encrypted_api_key, &config) {
+        Ok(api_key) => {
+            if let Err(e) = state.audit_logger.log(
+                &api_key,
+                AuditEventType::AccountLogout,
+                None,
+                &client_ip,
+                serde_json::json!({"account_id": claims.sub}),
+            ) {
+                error!(error = %e, "Failed to log logout audit event");
+            }
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to decrypt API key for logout audit event");
+        }
+    }
+
+    info!("User logged out");
+    Ok(Json(MessageResponse {
+        message: "Logged out successfully".to_string(),
+    }))
+}
+
+/// Look up an account's public keys
+///
+/// Returns the public keys another account needs for cryptographic operations with this one:
+/// the client Ed25519 key that verifies its signed requests and its X25519 sharing key.
+/// Callers must be authenticated so account IDs can't be probed anonymously. Accounts created
+/// before the key directory existed return 404. Supports both API key and session token authentication.
+#[utoipa::path(
+    get,
+    path = "/{account_id}/pubkey",
+    tag = ACCOUNT_TAG,
+    params(
+        ("account_id" = String, Path, description = "Account ID to look up", example = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8")
+    ),
+    security(
+        ("bearer_token" = []),
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "Public keys retrieved successfully", body = AccountPublicKeyResponse),
+        (status = 401, description = "Missing or invalid authentication", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant read access", body = ErrorResponse),
+        (status = 404, description = "No public keys published for this account", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse)
+    )
+)]
+async fn get_account_public_key(
+    headers: HeaderMap,
+    Path(account_id): Path<String>,
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
+    Extension(state): Extension<SubmitDataState>,
+) -> Result<impl IntoResponse> {
+    let auth = authenticate(&headers, &config, &state)?;
+    auth.require_scope(TokenScope::Read)?;
+
+    let (api_key, api_key_hash) = match auth {
+        AuthMethod::ApiKey(key) => {
+            let hash =
+                hash_request_api_key(&rate_limiter, &extract_client_ip(&headers), &key).await?;
+            (key, hash)
+        }
+        AuthMethod::SessionToken(claims) => {
+            let key =
+                decrypt_api_key_from_jwt(&claims.encrypted_api_key, &config).map_err(|e| {
+                    GoudChainError::Unauthorized(format!(
+                        "Failed to decrypt API key from session token: {}",
+                        e
+                    ))
+                })?;
+            (key, claims.api_key_hash)
+        }
+    };
+
+    // Check rate limit (read operation)
+    let client_ip = extract_client_ip(&headers);
+    let rate_limit_result =
+        match rate_limiter.check_limit(&api_key_hash, &client_ip, RateLimitCategory::List) {
+            Ok(result) => result,
+            Err(e) => {
+                error!(error = %e, "Rate limit check failed, allowing request");
+                rate_limiter.fail_open(RateLimitCategory::List)
+            }
+        };
+
+    if let RateLimitResult::Blocked {
+        ban_level,
+        retry_after,
+        violation_count,
+    } = &rate_limit_result
+    {
+        warn!(
+            api_key_hash = %api_key_hash,
+            ban_level = ?ban_level,
+            violation_count = violation_count,
+            "Public key lookup blocked by rate limiter"
+        );
+        record_security_event(
+            &state,
+            &config,
+            AuditEventType::RateLimited,
+            &client_ip,
+            serde_json::json!({
+                "endpoint": "/account/{account_id}/pubkey",
+                "ban_level": format!("{:?}", ban_level),
+                "violation_count": violation_count,
+            }),
+        );
+        let error = GoudChainError::ApiKeyBanned {
+            ban_level: format!("{:?}", ban_level),
+            expires_at: chrono::Utc::now().timestamp() + *retry_after as i64,
+        };
+        let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+        return Ok(add_rate_limit_headers(error.into_response(), rate_headers));
+    }
+
+    let blockchain_guard = blockchain.read().await;
+    if blockchain_guard
+        .find_account_with_hash(&api_key, Some(api_key_hash))
+        .is_none()
+    {
+        return Err(GoudChainError::AccountNotFound);
+    }
+
+    let entry = blockchain_guard
+        .find_account_public_keys(&account_id)
+        .ok_or_else(|| GoudChainError::DataNotFound(account_id.clone()))?;
+    drop(blockchain_guard);
+
+    let response = AccountPublicKeyResponse {
+        account_id: entry.account_id,
+        public_key: entry.request_public_key,
+        sharing_public_key: entry.sharing_public_key,
+    };
+    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+    Ok(add_rate_limit_headers(
+        Json(response).into_response(),
+        rate_headers,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ValidatorConfig;
+
+    #[test]
+    fn test_lookup_account_exists() {
+        let validator_config = ValidatorConfig::single("node1");
+        let mut blockchain = Blockchain::new("node1".to_string(), validator_config).unwrap();
+
+        let api_key = crate::crypto::generate_api_key();
+        let account = UserAccount::new(&api_key, &generate_signing_key(), None, None).unwrap();
+        blockchain
+            .add_account_with_key(account, api_key.clone())
+            .unwrap();
+        blockchain.add_block().unwrap();
+
+        assert!(lookup_account_exists(
+            &blockchain,
+            &api_key,
+            &hash_api_key_hex(&api_key)
+        ));
+        let unknown = crate::crypto::generate_api_key();
+        assert!(!lookup_account_exists(
+            &blockchain,
+            &unknown,
+            &hash_api_key_hex(&unknown)
+        ));
+    }
+}
@@ -0,0 +1,281 @@
+//! Operator endpoints for storage integrity checks, checkpoints, backups and rate-limit bans.
+//! Layer 5: Presentation - Authenticated maintenance API
+//!
+//! Every endpoint is guarded by ADMIN_TOKEN (`X-Admin-Token`) rather than a user
+//! credential: any account can be created freely, and a banned user's own key must never
+//! be able to lift its ban or switch the node's maintenance state.
+
+use axum::{
+    extract::{Extension, Path},
+    http::HeaderMap,
+    Json,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, warn};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::api::schemas::{
+    BackupResponse, BanStatusResponse, CheckpointResponse, ClearBanResponse, ErrorResponse,
+    ReadOnlyModeRequest, ReadOnlyModeResponse, SubmitDataState, VerifyCheckpointsResponse,
+};
+use crate::api::security_audit::authenticate_admin;
+use crate::api::RateLimiter;
+use crate::config::Config;
+use crate::domain::Blockchain;
+use crate::network::P2PNode;
+use crate::types::Result;
+
+use super::ADMIN_TAG;
+
+/// Admin routes
+pub fn router() -> OpenApiRouter {
+    OpenApiRouter::new()
+        .routes(routes!(handle_verify_checkpoints))
+        .routes(routes!(handle_create_checkpoint))
+        .routes(routes!(handle_create_backup))
+        .routes(routes!(handle_set_read_only))
+        .routes(routes!(handle_get_ban, handle_clear_ban))
+}
+
+/// Verify stored checkpoints
+///
+/// Recomputes the hash of every stored block at a checkpoint index and compares it
+/// with the block's stored hash and the persisted checkpoint. Mismatches point to
+/// on-disk corruption; the node does not attempt any repair. Requires the node's
+/// ADMIN_TOKEN in `X-Admin-Token`.
+#[utoipa::path(
+    get,
+    path = "/verify",
+    tag = ADMIN_TAG,
+    security(
+        ("admin_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Verification report (check `valid`)", body = VerifyCheckpointsResponse),
+        (status = 401, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 404, description = "ADMIN_TOKEN is not configured on this node", body = ErrorResponse),
+        (status = 500, description = "Stored chain could not be read", body = ErrorResponse)
+    )
+)]
+async fn handle_verify_checkpoints(
+    headers: HeaderMap,
+    Extension(p2p): Extension<Arc<P2PNode>>,
+    Extension(state): Extension<SubmitDataState>,
+    Extension(config): Extension<Arc<Config>>,
+) -> Result<Json<VerifyCheckpointsResponse>> {
+    authenticate_admin(&headers, &config, &state)?;
+
+    let report = p2p
+        .blockchain_store
+        .verify_checkpoints(config.checkpoint_interval)?;
+    Ok(Json(report.into()))
+}
+
+/// Checkpoint the chain tip
+///
+/// Records a checkpoint at this node's current tip without waiting for the next
+/// `CHECKPOINT_INTERVAL` block (useful before maintenance). Reorgs never cross the
+/// latest checkpoint, so every block up to the tip becomes final on this node.
+/// Checkpoints are node-local: other nodes keep their own. Requires the node's ADMIN_TOKEN
+/// in `X-Admin-Token`.
+#[utoipa::path(
+    post,
+    path = "/checkpoint",
+    tag = ADMIN_TAG,
+    security(
+        ("admin_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Checkpoint recorded at the chain tip", body = CheckpointResponse),
+        (status = 401, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 404, description = "ADMIN_TOKEN is not configured on this node", body = ErrorResponse),
+        (status = 500, description = "Checkpoint could not be saved", body = ErrorResponse)
+    )
+)]
+async fn handle_create_checkpoint(
+    headers: HeaderMap,
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(p2p): Extension<Arc<P2PNode>>,
+    Extension(state): Extension<SubmitDataState>,
+    Extension(config): Extension<Arc<Config>>,
+) -> Result<Json<CheckpointResponse>> {
+    authenticate_admin(&headers, &config, &state)?;
+
+    // Held while saving, so the persisted checkpoint is still the tip when it lands
+    let mut blockchain = blockchain.write().await;
+    let tip = blockchain.create_checkpoint()?;
+    p2p.blockchain_store
+        .save_checkpoint(tip.index, &tip.hash)
+        .inspect_err(|e| error!(error = %e, "Failed to save checkpoint"))?;
+
+    Ok(Json(CheckpointResponse {
+        index: tip.index,
+        hash: tip.hash,
+        checkpoint_count: blockchain.checkpoints.len(),
+        checkpoint_interval: blockchain.checkpoint_interval,
+    }))
+}
+
+/// Back up the database
+///
+/// Takes a consistent, incremental RocksDB backup of this node while it keeps serving
+/// (the WAL is flushed first). Backups are written to the node's backup directory and
+/// only the most recent ones are kept. Restore with `goud_chain backup restore` while
+/// the node is stopped. Requires the node's ADMIN_TOKEN in `X-Admin-Token`.
+#[utoipa::path(
+    post,
+    path = "/backup",
+    tag = ADMIN_TAG,
+    security(
+        ("admin_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Backup created and verified", body = BackupResponse),
+        (status = 401, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 404, description = "ADMIN_TOKEN is not configured on this node", body = ErrorResponse),
+        (status = 500, description = "Backup failed", body = ErrorResponse)
+    )
+)]
+async fn handle_create_backup(
+    headers: HeaderMap,
+    Extension(p2p): Extension<Arc<P2PNode>>,
+    Extension(state): Extension<SubmitDataState>,
+    Extension(config): Extension<Arc<Config>>,
+) -> Result<Json<BackupResponse>> {
+    authenticate_admin(&headers, &config, &state)?;
+
+    let backup = p2p.blockchain_store.create_backup(&config.backup_path())?;
+    Ok(Json(backup.into()))
+}
+
+/// Switch read-only maintenance mode
+///
+/// While read-only, this node refuses every write (account creation, metadata updates, key
+/// rotation, submissions, deletes and sharing) with `503 READ_ONLY` and creates no blocks;
+/// reads, audit queries and syncing blocks from peers continue. Audit events are buffered
+/// and written once writes resume. The switch is node-local and lasts until the next
+/// restart (`READ_ONLY` sets the initial state); flip every validator to stop new blocks
+/// cluster-wide. Requires the node's ADMIN_TOKEN in `X-Admin-Token`.
+#[utoipa::path(
+    post,
+    path = "/readonly",
+    tag = ADMIN_TAG,
+    request_body = ReadOnlyModeRequest,
+    security(
+        ("admin_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Maintenance mode updated", body = ReadOnlyModeResponse),
+        (status = 401, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 404, description = "ADMIN_TOKEN is not configured on this node", body = ErrorResponse)
+    )
+)]
+async fn handle_set_read_only(
+    headers: HeaderMap,
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(state): Extension<SubmitDataState>,
+    Extension(config): Extension<Arc<Config>>,
+    Json(request): Json<ReadOnlyModeRequest>,
+) -> Result<Json<ReadOnlyModeResponse>> {
+    authenticate_admin(&headers, &config, &state)?;
+
+    let mut blockchain = blockchain.write().await;
+    if blockchain.read_only != request.read_only {
+        warn!(
+            read_only = request.read_only,
+            "Read-only maintenance mode switched"
+        );
+    }
+    blockchain.read_only = request.read_only;
+
+    Ok(Json(ReadOnlyModeResponse {
+        read_only: blockchain.read_only,
+        pending_collections: blockchain.pending_collections.len(),
+    }))
+}
+
+/// Inspect an API key's ban
+///
+/// Returns the key's active rate-limit ban (if any) and the violation history that
+/// escalates its penalties. Requires the node's ADMIN_TOKEN in `X-Admin-Token`; user
+/// API keys and session tokens are not accepted.
+#[utoipa::path(
+    get,
+    path = "/bans/{api_key_hash}",
+    tag = ADMIN_TAG,
+    params(
+        ("api_key_hash" = String, Path, description = "Hash of the banned API key (as in the rate-limit logs)")
+    ),
+    security(
+        ("admin_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Ban status and violation history", body = BanStatusResponse),
+        (status = 401, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 404, description = "ADMIN_TOKEN is not configured on this node", body = ErrorResponse),
+        (status = 500, description = "Rate-limit store could not be read", body = ErrorResponse)
+    )
+)]
+async fn handle_get_ban(
+    headers: HeaderMap,
+    Path(api_key_hash): Path<String>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
+    Extension(state): Extension<SubmitDataState>,
+    Extension(config): Extension<Arc<Config>>,
+) -> Result<Json<BanStatusResponse>> {
+    authenticate_admin(&headers, &config, &state)?;
+
+    let store = rate_limiter.store();
+    Ok(Json(BanStatusResponse {
+        ban: store.get_ban_status(&api_key_hash)?.map(Into::into),
+        violations: store.get_violations(&api_key_hash)?.into(),
+        api_key_hash,
+    }))
+}
+
+/// Lift an API key's ban
+///
+/// Deletes the key's ban and resets its violation history, so its next violation starts
+/// again from the first penalty level. The account and its data are untouched. Requires
+/// the node's ADMIN_TOKEN in `X-Admin-Token`. Bans are node-local: clear the key on every
+/// node that banned it.
+#[utoipa::path(
+    delete,
+    path = "/bans/{api_key_hash}",
+    tag = ADMIN_TAG,
+    params(
+        ("api_key_hash" = String, Path, description = "Hash of the banned API key (as in the rate-limit logs)")
+    ),
+    security(
+        ("admin_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Ban lifted and violations reset", body = ClearBanResponse),
+        (status = 401, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 404, description = "ADMIN_TOKEN is not configured on this node", body = ErrorResponse),
+        (status = 500, description = "Rate-limit store could not be updated", body = ErrorResponse)
+    )
+)]
+async fn handle_clear_ban(
+    headers: HeaderMap,
+    Path(api_key_hash): Path<String>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
+    Extension(state): Extension<SubmitDataState>,
+    Extension(config): Extension<Arc<Config>>,
+) -> Result<Json<ClearBanResponse>> {
+    authenticate_admin(&headers, &config, &state)?;
+
+    let store = rate_limiter.store();
+    let cleared_ban = store.get_ban_status(&api_key_hash)?;
+    let cleared_violations = store.get_violations(&api_key_hash)?.count;
+    store
+        .clear_ban(&api_key_hash)
+        .inspect_err(|e| error!(error = %e, "Failed to clear ban"))?;
+
+    Ok(Json(ClearBanResponse {
+        api_key_hash,
+        cleared_ban: cleared_ban.map(Into::into),
+        cleared_violations,
+    }))
+}
@@ -1,30 +1,103 @@
 use axum::{
+    body::Body,
     extract::{Extension, Query},
-    http::HeaderMap,
+    http::{header, HeaderMap},
     response::IntoResponse,
     Json,
 };
+use futures_util::stream::{self, StreamExt};
+use std::convert::Infallible;
 use std::sync::Arc;
+use tracing::{error, warn};
 use utoipa_axum::{router::OpenApiRouter, routes};
+use zeroize::Zeroizing;
 
-use crate::api::auth::{decrypt_api_key_from_jwt, extract_auth_from_headers, AuthMethod};
+use crate::api::auth::{decrypt_api_key_from_jwt, AuthMethod, TokenScope};
 use crate::api::schemas::SubmitDataState;
-use crate::api::schemas::{AuditLogQuery, ErrorResponse};
+use crate::api::schemas::{AuditExportQuery, AuditLogQuery, ErrorResponse};
+use crate::api::security_audit::{
+    authenticate, extract_client_ip, hash_request_api_key, record_security_event,
+};
+use crate::api::{RateLimitCategory, RateLimitResult, RateLimiter};
 use crate::config::Config;
+use crate::constants::AUDIT_EXPORT_FILENAME_PREFIX;
 use crate::types::*;
 
 use super::AUDIT_TAG;
 
+/// CSV header row for audit exports
+const CSV_HEADER: &str = "event_type,timestamp,collection_id,ip_hash,metadata\n";
+
 /// Audit log routes
 pub fn router() -> OpenApiRouter {
-    OpenApiRouter::new().routes(routes!(handle_get_audit_logs))
+    OpenApiRouter::new()
+        .routes(routes!(handle_get_audit_logs))
+        .routes(routes!(handle_export_audit_logs))
+}
+
+/// Helper to add rate limit headers to response
+fn add_rate_limit_headers(
+    mut response: axum::response::Response,
+    headers: Vec<(String, String)>,
+) -> axum::response::Response {
+    let response_headers = response.headers_mut();
+    for (key, value) in headers {
+        if let Ok(header_value) = value.parse() {
+            response_headers.insert(
+                axum::http::HeaderName::from_bytes(key.as_bytes()).unwrap(),
+                header_value,
+            );
+        }
+    }
+    response
+}
+
+/// Resolve the caller's API key from either an API key or a session token
+fn authenticated_api_key(
+    headers: &HeaderMap,
+    state: &SubmitDataState,
+    config: &Config,
+) -> Result<Zeroizing<Vec<u8>>> {
+    let auth = authenticate(headers, config, state)?;
+    auth.require_scope(TokenScope::Read)?;
+
+    // Support both API keys and session tokens
+    match auth {
+        AuthMethod::ApiKey(key) => Ok(key),
+        AuthMethod::SessionToken(claims) => {
+            decrypt_api_key_from_jwt(&claims.encrypted_api_key, config).map_err(|e| {
+                GoudChainError::Unauthorized(format!(
+                    "Failed to decrypt API key from session token: {}",
+                    e
+                ))
+            })
+        }
+    }
+}
+
+/// Build a query filter from the shared query parameters
+/// Unknown event types are ignored rather than rejected
+fn build_filter(
+    event_type: Option<&str>,
+    start_ts: Option<i64>,
+    end_ts: Option<i64>,
+    include_invalidated: Option<bool>,
+) -> AuditLogFilter {
+    AuditLogFilter {
+        event_type: event_type.and_then(|s| s.parse().ok()),
+        start_ts,
+        end_ts,
+        include_invalidated: include_invalidated.unwrap_or(false),
+    }
 }
 
 /// Get audit logs
 ///
 /// Returns operational security audit logs for the authenticated user.
-/// Logs include account creation, login, data submission, data listing, and decryption events.
+/// Logs include account creation, login, data submission, data listing, decryption, and deletion events.
 /// All logs are encrypted per-user and stored on the blockchain for tamper-proof auditing.
+/// Entries past the node's retention window (AUDIT_RETENTION_DAYS) are left out unless
+/// `include_invalidated=true`, which reads the older blocks as well.
 #[utoipa::path(
     get,
     path = "/",
@@ -37,6 +110,7 @@ pub fn router() -> OpenApiRouter {
     responses(
         (status = 200, description = "Audit logs retrieved successfully", body = AuditLogResponse),
         (status = 401, description = "Missing or invalid authentication", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant read access", body = ErrorResponse),
         (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -48,49 +122,17 @@ async fn handle_get_audit_logs(
     Extension(config): Extension<Arc<Config>>,
 ) -> Result<impl IntoResponse> {
     let audit_logger = &state.audit_logger;
-
-    // Extract authentication
-    let auth = extract_auth_from_headers(&headers, &config)?;
-
-    // Support both API keys and session tokens
-    let api_key = match auth {
-        AuthMethod::ApiKey(key) => key,
-        AuthMethod::SessionToken(claims) => {
-            match decrypt_api_key_from_jwt(&claims.encrypted_api_key, &config) {
-                Ok(key) => key,
-                Err(e) => {
-                    return Err(GoudChainError::Unauthorized(format!(
-                        "Failed to decrypt API key from session token: {}",
-                        e
-                    )));
-                }
-            }
-        }
-    };
+    let api_key = authenticated_api_key(&headers, &state, &config)?;
 
     // Parse query parameters
-    let start_ts = params.start_ts;
-    let end_ts = params.end_ts;
-    let event_type_str = params.event_type.as_deref();
     let page = params.page.unwrap_or(0);
     let page_size = params.page_size.unwrap_or(50).min(100);
-
-    // Parse event type filter
-    let event_type = event_type_str.and_then(|s| match s {
-        "AccountCreated" => Some(AuditEventType::AccountCreated),
-        "DataSubmitted" => Some(AuditEventType::DataSubmitted),
-        "DataDecrypted" => Some(AuditEventType::DataDecrypted),
-        "DataListed" => Some(AuditEventType::DataListed),
-        "AccountLogin" => Some(AuditEventType::AccountLogin),
-        _ => None,
-    });
-
-    let filter = AuditLogFilter {
-        event_type,
-        start_ts,
-        end_ts,
-        include_invalidated: false,
-    };
+    let filter = build_filter(
+        params.event_type.as_deref(),
+        params.start_ts,
+        params.end_ts,
+        params.include_invalidated,
+    );
 
     // Query audit logs
     match audit_logger
@@ -101,3 +143,285 @@ async fn handle_get_audit_logs(
         Err(e) => Err(e),
     }
 }
+
+/// Export audit logs
+///
+/// Streams every audit log entry for the authenticated user that matches the filters,
+/// as CSV (`event_type,timestamp,collection_id,ip_hash,metadata`) or a JSON array.
+/// Entries are emitted in chain order, one block at a time, so large exports are not buffered.
+/// Decrypts every matching entry, so each export counts as one decrypt-group rate-limit hit, and
+/// the export itself is recorded as an `AuditLogsExported` event.
+#[utoipa::path(
+    get,
+    path = "/export",
+    tag = AUDIT_TAG,
+    params(AuditExportQuery),
+    security(
+        ("bearer_token" = []),
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "Audit log export file", content(
+            (String = "text/csv"),
+            (Vec<AuditLogEntry> = "application/json")
+        )),
+        (status = 400, description = "Unsupported export format", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid authentication", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant read access", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+async fn handle_export_audit_logs(
+    headers: HeaderMap,
+    Query(params): Query<AuditExportQuery>,
+    Extension(state): Extension<SubmitDataState>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
+) -> Result<axum::response::Response> {
+    let api_key = authenticated_api_key(&headers, &state, &config)?;
+
+    let format = params.format.as_deref().unwrap_or("csv");
+    if format != "csv" && format != "json" {
+        return Err(GoudChainError::InvalidRequestBody(format!(
+            "Unsupported export format '{}' (expected csv or json)",
+            format
+        )));
+    }
+
+    // Check rate limit (decrypts the whole log - one hit per export)
+    let client_ip = extract_client_ip(&headers);
+    let api_key_hash = hash_request_api_key(&rate_limiter, &client_ip, &api_key).await?;
+    let rate_limit_result =
+        match rate_limiter.check_limit(&api_key_hash, &client_ip, RateLimitCategory::Decrypt) {
+            Ok(result) => result,
+            Err(e) => {
+                error!(error = %e, "Rate limit check failed, allowing request");
+                rate_limiter.fail_open(RateLimitCategory::Decrypt)
+            }
+        };
+    if let RateLimitResult::Blocked {
+        ban_level,
+        retry_after,
+        violation_count,
+    } = &rate_limit_result
+    {
+        warn!(
+            api_key_hash = %api_key_hash,
+            ban_level = ?ban_level,
+            violation_count = violation_count,
+            "Audit export blocked by rate limiter"
+        );
+        record_security_event(
+            &state,
+            &config,
+            AuditEventType::RateLimited,
+            &client_ip,
+            serde_json::json!({
+                "endpoint": "/audit/export",
+                "ban_level": format!("{:?}", ban_level),
+                "violation_count": violation_count,
+            }),
+        );
+        let error = GoudChainError::ApiKeyBanned {
+            ban_level: format!("{:?}", ban_level),
+            expires_at: chrono::Utc::now().timestamp() + *retry_after as i64,
+        };
+        let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+        return Ok(add_rate_limit_headers(error.into_response(), rate_headers));
+    }
+
+    // Audit log: export requested
+    if let Err(e) = state.audit_logger.log(
+        &api_key,
+        AuditEventType::AuditLogsExported,
+        None,
+        &client_ip,
+        serde_json::json!({"format": format}),
+    ) {
+        error!(error = %e, "Failed to log audit export event");
+    }
+
+    let filter = build_filter(
+        params.event_type.as_deref(),
+        params.start_ts,
+        params.end_ts,
+        params.include_invalidated,
+    );
+    let chunks = state.audit_logger.export_logs(api_key, filter).await?;
+
+    let (content_type, body) = if format == "csv" {
+        let rows = chunks.map(|entries| entries.iter().map(csv_row).collect::<String>());
+        let body = stream::once(async { CSV_HEADER.to_string() }).chain(rows);
+        (
+            "text/csv; charset=utf-8",
+            Body::from_stream(body.map(Ok::<_, Infallible>)),
+        )
+    } else {
+        let items = chunks.enumerate().map(|(i, entries)| {
+            let json = entries
+                .iter()
+                .filter_map(|entry| serde_json::to_string(entry).ok())
+                .collect::<Vec<_>>()
+                .join(",");
+            if i == 0 {
+                json
+            } else {
+                format!(",{}", json)
+            }
+        });
+        let body = stream::once(async { "[".to_string() })
+            .chain(items)
+            .chain(stream::once(async { "]".to_string() }));
+        (
+            "application/json",
+            Body::from_stream(body.map(Ok::<_, Infallible>)),
+        )
+    };
+
+    let filename = format!(
+        "{}-{}.{}",
+        AUDIT_EXPORT_FILENAME_PREFIX,
+        chrono::Utc::now().timestamp(),
+        format
+    );
+
+    let response = (
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response();
+    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+    Ok(add_rate_limit_headers(response, rate_headers))
+}
+
+/// Format one audit entry as a CSV row (metadata flattened to compact JSON)
+fn csv_row(entry: &AuditLogEntry) -> String {
+    format!(
+        "{},{},{},{},{}\n",
+        entry.event_type,
+        entry.timestamp,
+        csv_field(entry.collection_id.as_deref().unwrap_or("")),
+        csv_field(&entry.ip_hash),
+        csv_field(&entry.metadata.to_string())
+    )
+}
+
+/// Quote a CSV field when it contains separators, quotes or newlines (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::WebSocketBroadcaster;
+    use crate::config::RateLimitConfig;
+    use crate::crypto::{encode_api_key, generate_api_key};
+    use crate::domain::Blockchain;
+    use crate::storage::{AuditLogger, BlockchainStore, RateLimitStore, TokenRevocationStore};
+    use axum::http::HeaderValue;
+    use tokio::sync::RwLock;
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("{\"k\":1}"), "\"{\"\"k\"\":1}\"");
+    }
+
+    #[tokio::test]
+    async fn test_export_streams_only_own_logs_as_csv() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = Arc::new(Config::for_tests());
+        let store = Arc::new(BlockchainStore::new(data_dir.path()).unwrap());
+        let blockchain = Arc::new(RwLock::new(
+            Blockchain::new("node1".to_string(), config.validator_config.clone()).unwrap(),
+        ));
+        let audit_logger = AuditLogger::new(blockchain, Arc::clone(&store), None, None);
+
+        let api_key = generate_api_key();
+        let other_key = generate_api_key();
+        for (key, event) in [
+            (&api_key, AuditEventType::AccountCreated),
+            (&api_key, AuditEventType::DataSubmitted),
+            (&other_key, AuditEventType::AccountCreated),
+        ] {
+            audit_logger
+                .log(key, event, None, "127.0.0.1", serde_json::json!({"a": 1}))
+                .unwrap();
+        }
+        audit_logger.flush_all_batches().await.unwrap();
+
+        let state = SubmitDataState {
+            audit_logger: Arc::clone(&audit_logger),
+            ws_broadcaster: Arc::new(WebSocketBroadcaster::new()),
+            token_revocations: Arc::new(TokenRevocationStore::new(store.get_db())),
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", encode_api_key(&api_key))).unwrap(),
+        );
+        let params = AuditExportQuery {
+            format: Some("csv".to_string()),
+            event_type: None,
+            start_ts: None,
+            end_ts: None,
+            include_invalidated: None,
+        };
+
+        let rate_limiter = Arc::new(RateLimiter::new(
+            Arc::new(RateLimitStore::new(store.get_db())),
+            vec![],
+            vec![],
+            RateLimitConfig::default(),
+        ));
+
+        let response = handle_export_audit_logs(
+            headers,
+            Query(params),
+            Extension(state),
+            Extension(config),
+            Extension(rate_limiter),
+        )
+        .await
+        .unwrap();
+        assert!(response.headers().contains_key("x-ratelimit-limit"));
+
+        let disposition = response.headers()[header::CONTENT_DISPOSITION]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(disposition.starts_with("attachment; filename=\"goud-audit-logs-"));
+        assert!(disposition.ends_with(".csv\""));
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let csv = String::from_utf8(body.to_vec()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER.trim_end());
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1..].iter().all(|l| l.ends_with("\"{\"\"a\"\":1}\"")));
+
+        // The export itself is audited
+        audit_logger.flush_all_batches().await.unwrap();
+        let filter = build_filter(Some("AuditLogsExported"), None, None, None);
+        let exported = audit_logger
+            .query_logs(&api_key, filter, 0, 10)
+            .await
+            .unwrap();
+        assert_eq!(exported.total, 1);
+    }
+}
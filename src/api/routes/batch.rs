@@ -0,0 +1,375 @@
+//! Batch endpoint for pipelining mixed API calls in one round trip.
+//! Layer 5: Presentation - Dispatches sub-requests through the regular API router
+
+use axum::{
+    body::Body,
+    extract::Extension,
+    http::{header, HeaderMap, Method, Request, Uri},
+    Json, Router,
+};
+use std::sync::{Arc, OnceLock};
+use tower::ServiceExt;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::api::request_signature::parse_json_body;
+use crate::api::schemas::{BatchRequestItem, BatchResponseItem, ErrorResponse};
+use crate::constants::{
+    API_VERSION_PREFIX, MAX_BATCH_REQUESTS, REQUEST_NONCE_HEADER, REQUEST_SIGNATURE_HEADER,
+    REQUEST_TIMESTAMP_HEADER,
+};
+use crate::types::{GoudChainError, Result};
+
+use super::BATCH_TAG;
+
+/// Headers of the outer request that must not reach sub-requests as-is: they describe the
+/// batch body (length, type, signature) or its cache state, not the sub-request's
+const SUB_REQUEST_SKIPPED_HEADERS: [&str; 8] = [
+    "content-length",
+    "content-type",
+    "transfer-encoding",
+    "if-none-match",
+    "if-match",
+    REQUEST_SIGNATURE_HEADER,
+    REQUEST_NONCE_HEADER,
+    REQUEST_TIMESTAMP_HEADER,
+];
+
+/// Batch routes
+pub fn router() -> OpenApiRouter {
+    OpenApiRouter::new().routes(routes!(handle_batch))
+}
+
+/// Handle to the API router that batch sub-requests are dispatched through
+///
+/// The router holds this handle as an extension, so it is installed once the router is built.
+#[derive(Clone, Default)]
+pub struct BatchDispatcher {
+    router: Arc<OnceLock<Router>>,
+}
+
+impl BatchDispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Install the router sub-requests run through (later calls are ignored)
+    pub fn install(&self, router: Router) {
+        let _ = self.router.set(router);
+    }
+
+    /// Run one sub-request with the same middleware and handlers as a direct call
+    async fn dispatch(&self, request: Request<Body>) -> Result<BatchResponseItem> {
+        let router =
+            self.router.get().cloned().ok_or_else(|| {
+                GoudChainError::Internal("Batch router not installed".to_string())
+            })?;
+
+        let Ok(response) = router.oneshot(request).await;
+        let status = response.status().as_u16();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .map_err(|e| GoudChainError::Internal(format!("Failed to read response: {}", e)))?;
+
+        let body = if bytes.is_empty() {
+            serde_json::Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap_or_else(|_| {
+                serde_json::Value::String(String::from_utf8_lossy(&bytes).into_owned())
+            })
+        };
+
+        Ok(BatchResponseItem { status, body })
+    }
+}
+
+/// Build a sub-request carrying the caller's credentials and client headers
+/// Each sub-request gets its own nonce, `{batch nonce}:{index}`, so replaying the batch
+/// is caught by every endpoint that requires a fresh nonce
+fn build_sub_request(
+    headers: &HeaderMap,
+    index: usize,
+    item: &BatchRequestItem,
+) -> Result<Request<Body>> {
+    let method = match item.method.to_ascii_uppercase().as_str() {
+        "GET" => Method::GET,
+        "POST" => Method::POST,
+        "PUT" => Method::PUT,
+        "DELETE" => Method::DELETE,
+        other => {
+            return Err(GoudChainError::InvalidRequestBody(format!(
+                "Unsupported batch method: {}",
+                other
+            )))
+        }
+    };
+
+    if !item.path.starts_with('/') {
+        return Err(GoudChainError::InvalidRequestBody(format!(
+            "Batch path must start with '/': {}",
+            item.path
+        )));
+    }
+    let uri: Uri = item.path.parse().map_err(|_| {
+        GoudChainError::InvalidRequestBody(format!("Invalid batch path: {}", item.path))
+    })?;
+    let path = uri.path().trim_end_matches('/');
+    if path.strip_prefix(API_VERSION_PREFIX).unwrap_or(path) == "/batch" {
+        return Err(GoudChainError::InvalidRequestBody(
+            "Nested /batch requests are not allowed".to_string(),
+        ));
+    }
+
+    let mut builder = Request::builder().method(method).uri(uri);
+    for (name, value) in headers {
+        if !SUB_REQUEST_SKIPPED_HEADERS.contains(&name.as_str()) {
+            builder = builder.header(name, value);
+        }
+    }
+    if let Some(nonce) = headers
+        .get(REQUEST_NONCE_HEADER)
+        .and_then(|value| value.to_str().ok())
+    {
+        builder = builder.header(REQUEST_NONCE_HEADER, format!("{}:{}", nonce, index));
+    }
+
+    let body = match &item.body {
+        Some(body) => {
+            builder = builder.header(header::CONTENT_TYPE, "application/json");
+            Body::from(
+                serde_json::to_vec(body)
+                    .map_err(|e| GoudChainError::SerializationError(e.to_string()))?,
+            )
+        }
+        None => Body::empty(),
+    };
+
+    builder
+        .body(body)
+        .map_err(|e| GoudChainError::InvalidRequestBody(format!("Invalid batch request: {}", e)))
+}
+
+/// Run several API calls in one request
+///
+/// Executes up to 20 sub-requests in order and returns one `{status, body}` result per
+/// sub-request. Each one goes through the regular endpoint, so it is authenticated,
+/// rate-limited and forwarded to the validator exactly like a direct call, using the
+/// credentials of the batch request. A failing sub-request does not stop later ones;
+/// an invalid item (bad method or path, nested `/batch`) rejects the whole batch before
+/// anything runs. Endpoints that need `X-Nonce` get `{X-Nonce}:{index}` derived from the
+/// batch request's nonce. Sub-requests are unsigned: signature headers on the batch
+/// request are not passed on, so writes fail while `REQUIRE_REQUEST_SIGNATURES` is enabled.
+#[utoipa::path(
+    post,
+    path = "/batch",
+    tag = BATCH_TAG,
+    request_body = Vec<BatchRequestItem>,
+    security(
+        ("bearer_token" = []),
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "Results in sub-request order (check each `status`)", body = Vec<BatchResponseItem>),
+        (status = 400, description = "Invalid item, nested /batch, or too many sub-requests", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+async fn handle_batch(
+    headers: HeaderMap,
+    Extension(dispatcher): Extension<BatchDispatcher>,
+    body: String,
+) -> Result<Json<Vec<BatchResponseItem>>> {
+    let items: Vec<BatchRequestItem> = parse_json_body(&body)?;
+
+    if items.is_empty() {
+        return Err(GoudChainError::InvalidRequestBody(
+            "Batch must contain at least one request".to_string(),
+        ));
+    }
+    if items.len() > MAX_BATCH_REQUESTS {
+        return Err(GoudChainError::InvalidRequestBody(format!(
+            "Batch contains {} requests (max: {})",
+            items.len(),
+            MAX_BATCH_REQUESTS
+        )));
+    }
+
+    // Validate every item before running any, so a malformed batch has no side effects
+    let requests = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| build_sub_request(&headers, index, item))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut results = Vec::with_capacity(requests.len());
+    for request in requests {
+        results.push(dispatcher.dispatch(request).await?);
+    }
+
+    Ok(Json(results))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::schemas::SubmitDataState;
+    use crate::api::{RateLimiter, WebSocketBroadcaster};
+    use crate::config::{Config, RateLimitConfig};
+    use crate::crypto::{encode_api_key, generate_api_key, generate_signing_key, hash_api_key_hex};
+    use crate::domain::{Blockchain, UserAccount};
+    use crate::network::P2PNode;
+    use crate::storage::{
+        AuditLogger, BlockchainStore, NonceStore, RateLimitStore, TokenRevocationStore,
+    };
+    use tokio::sync::RwLock;
+
+    /// Data and batch routes wired like the node router, with one registered account
+    /// Returns the router and the account's encoded API key
+    fn test_app(data_dir: &tempfile::TempDir) -> (Router, String) {
+        let config = Config::for_tests();
+        let store = Arc::new(BlockchainStore::new(data_dir.path()).unwrap());
+        let mut blockchain =
+            Blockchain::new("node1".to_string(), config.validator_config.clone()).unwrap();
+
+        let api_key = generate_api_key();
+        let account = UserAccount::new(&api_key, &generate_signing_key(), None, None).unwrap();
+        blockchain
+            .add_account_with_key(account, api_key.clone())
+            .unwrap();
+        blockchain.add_block().unwrap();
+
+        let blockchain = Arc::new(RwLock::new(blockchain));
+        let p2p = Arc::new(P2PNode::new(
+            Arc::clone(&blockchain),
+            Arc::clone(&store),
+            vec![],
+            None,
+            crate::network::P2PTransport::new(None).unwrap(),
+            None,
+            None,
+        ));
+        let rate_limiter = Arc::new(RateLimiter::new(
+            Arc::new(RateLimitStore::new(store.get_db())),
+            vec![hash_api_key_hex(&api_key)],
+            vec![],
+            RateLimitConfig::default(),
+        ));
+        let state = SubmitDataState {
+            audit_logger: AuditLogger::new(Arc::clone(&blockchain), Arc::clone(&store), None, None),
+            ws_broadcaster: Arc::new(WebSocketBroadcaster::new()),
+            token_revocations: Arc::new(TokenRevocationStore::new(store.get_db())),
+        };
+
+        let dispatcher = BatchDispatcher::new();
+        let (router, _) = OpenApiRouter::new()
+            .nest("/data", super::super::data::router(&config))
+            .merge(router())
+            .layer(Extension(blockchain))
+            .layer(Extension(p2p))
+            .layer(Extension(Arc::new(config)))
+            .layer(Extension(rate_limiter))
+            .layer(Extension(Arc::new(NonceStore::new(store.get_db()))))
+            .layer(Extension(state))
+            .layer(Extension(dispatcher.clone()))
+            .split_for_parts();
+        dispatcher.install(router.clone());
+
+        (router, encode_api_key(&api_key))
+    }
+
+    /// POST /batch and return the status with the parsed response body
+    async fn post_batch(
+        router: &Router,
+        api_key: &str,
+        items: serde_json::Value,
+    ) -> (u16, serde_json::Value) {
+        let request = Request::post("/batch")
+            .header(header::AUTHORIZATION, format!("Bearer {}", api_key))
+            .header(REQUEST_NONCE_HEADER, uuid::Uuid::new_v4().to_string())
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(items.to_string()))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status().as_u16();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_batch_runs_sub_requests_in_order() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (router, api_key) = test_app(&data_dir);
+
+        let (status, results) = post_batch(
+            &router,
+            &api_key,
+            serde_json::json!([
+                {"method": "POST", "path": "/data/submit", "body": {"label": "notes", "data": "{\"text\": \"hello\"}"}},
+                {"method": "GET", "path": "/data/list"},
+                {"method": "POST", "path": "/data/decrypt/missing"}
+            ]),
+        )
+        .await;
+        assert_eq!(status, 200);
+
+        let results: Vec<BatchResponseItem> = serde_json::from_value(results).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].status, 201);
+
+        // The list sees the collection submitted earlier in the same batch
+        let collection_id = results[0].body["collection_id"].as_str().unwrap();
+        assert_eq!(results[1].status, 200);
+        assert!(results[1].body.to_string().contains(collection_id));
+
+        // A failing sub-request is reported in place
+        assert_eq!(results[2].status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_batch_rejects_nested_and_oversized_batches() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (router, api_key) = test_app(&data_dir);
+
+        // Nothing runs when any item is invalid
+        let (status, _) = post_batch(
+            &router,
+            &api_key,
+            serde_json::json!([
+                {"method": "POST", "path": "/data/submit", "body": {"label": "notes", "data": "{}"}},
+                {"method": "POST", "path": "/batch", "body": []}
+            ]),
+        )
+        .await;
+        assert_eq!(status, 400);
+
+        let (status, _) = post_batch(
+            &router,
+            &api_key,
+            serde_json::json!([{"method": "POST", "path": "/v1/batch", "body": []}]),
+        )
+        .await;
+        assert_eq!(status, 400);
+
+        let (status, results) = post_batch(
+            &router,
+            &api_key,
+            serde_json::json!([{"method": "GET", "path": "/data/list"}]),
+        )
+        .await;
+        assert_eq!(status, 200);
+        assert_eq!(results[0]["body"]["collections"], serde_json::json!([]));
+
+        let too_many = vec![serde_json::json!({"method": "GET", "path": "/data/list"}); 21];
+        let (status, _) = post_batch(&router, &api_key, serde_json::Value::from(too_many)).await;
+        assert_eq!(status, 400);
+
+        let (status, _) = post_batch(
+            &router,
+            &api_key,
+            serde_json::json!([{"method": "PATCH", "path": "/data/list"}]),
+        )
+        .await;
+        assert_eq!(status, 400);
+    }
+}
@@ -1,57 +1,76 @@
 use axum::{
-    extract::{Extension, Path},
+    extract::{DefaultBodyLimit, Extension, Path, Query},
     http::{HeaderMap, StatusCode},
+    middleware,
     response::IntoResponse,
     Json,
 };
+use base64::{engine::general_purpose, Engine as _};
+use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{error, info, warn};
-use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_axum::router::{OpenApiRouter, UtoipaMethodRouterExt};
+use utoipa_axum::routes;
 
-use crate::api::auth::{decrypt_api_key_from_jwt, extract_auth_from_headers, AuthMethod};
-use crate::api::internal_client::forward_request_with_headers;
+use crate::api::auth::{decrypt_api_key_from_jwt, AuthMethod, TokenScope};
+use crate::api::idempotency::idempotency_middleware;
+use crate::api::internal_client::{
+    extract_auth_header, extract_replay_headers, forward_to_validator,
+};
+use crate::api::request_signature::{
+    commit_used_nonce, enforce_request_signature, parse_json_body, require_fresh_nonce,
+    reserve_signature_nonce, RequestSignature,
+};
 use crate::api::schemas::SubmitDataState;
 use crate::api::schemas::{
-    CollectionListItem, CollectionListResponse, DecryptCollectionResponse, ErrorResponse,
+    CollectionEnvelopeResponse, CollectionListItem, CollectionListQuery, CollectionListResponse,
+    CollectionSearchQuery, DecryptAllQuery, DecryptCollectionResponse, DecryptQuery,
+    DeleteCollectionResponse, ErrorResponse, ShareCollectionRequest, ShareGrantResponse,
+    SharingKeyResponse, SubmitBatchResponse, SubmitDataDryRunResponse, SubmitDataQuery,
     SubmitDataRequest, SubmitDataResponse,
 };
-use crate::api::{RateLimitResult, RateLimiter};
+use crate::api::security_audit::{
+    authenticate, extract_client_ip, hash_request_api_key, record_security_event,
+};
+use crate::api::{
+    global_operations_tracker, RateLimitCategory, RateLimitResult, RateLimiter,
+    WebSocketBroadcaster,
+};
 use crate::config::Config;
-use crate::constants::CHECKPOINT_INTERVAL;
-use crate::crypto::hash_api_key_hex;
-use crate::domain::blockchain::{get_current_validator, is_authorized_validator};
-use crate::domain::{Blockchain, EncryptedCollection};
+use crate::constants::{
+    AUDIT_LABEL_PREFIX, BINARY_CONTENT_TYPE, DECRYPT_ALL_DEFAULT_PAGE_SIZE,
+    DECRYPT_ALL_MAX_PAGE_SIZE, DEFAULT_CONTENT_TYPE, GRANT_LABEL_PREFIX, LIST_DEFAULT_PAGE_SIZE,
+    LIST_MAX_PAGE_SIZE, MAX_BATCH_SUBMIT_ITEMS,
+};
+use crate::crypto::{sharing_public_key_hex, validate_sharing_public_key};
+use crate::domain::{Blockchain, CollectionRevision, EncryptedCollection, SharedCollection};
 use crate::network::P2PNode;
+use crate::storage::{BlockchainStore, NonceStore};
+use crate::types::validation::{media_type_essence, FieldError};
 use crate::types::*;
 
 use super::DATA_TAG;
 
 /// Data operation routes
-pub fn router() -> OpenApiRouter {
+/// Batches get a larger body limit than the global one sized for a single submit;
+/// single submits honor `Idempotency-Key`
+pub fn router(config: &Config) -> OpenApiRouter {
     OpenApiRouter::new()
-        .routes(routes!(submit_data))
+        .routes(routes!(submit_data).layer(middleware::from_fn(idempotency_middleware)))
+        .routes(
+            routes!(submit_batch)
+                .layer(DefaultBodyLimit::max(config.max_batch_request_body_bytes())),
+        )
         .routes(routes!(list_data))
+        .routes(routes!(search_data))
         .routes(routes!(decrypt_data))
-}
-
-/// Extract client IP from headers
-fn extract_client_ip(headers: &HeaderMap) -> String {
-    if let Some(ip) = headers.get("x-real-ip") {
-        if let Ok(ip_str) = ip.to_str() {
-            return ip_str.to_string();
-        }
-    }
-
-    if let Some(forwarded) = headers.get("x-forwarded-for") {
-        if let Ok(forwarded_str) = forwarded.to_str() {
-            if let Some(first_ip) = forwarded_str.split(',').next() {
-                return first_ip.trim().to_string();
-            }
-        }
-    }
-
-    "unknown".to_string()
+        .routes(routes!(get_envelope))
+        .routes(routes!(decrypt_all))
+        .routes(routes!(delete_data))
+        .routes(routes!(get_sharing_key))
+        .routes(routes!(share_data))
+        .routes(routes!(revoke_share))
 }
 
 /// Helper to add rate limit headers
@@ -71,20 +90,186 @@ fn add_rate_limit_headers(
     response
 }
 
-/// Extract auth header value for forwarding
-fn extract_auth_header(headers: &HeaderMap) -> Option<String> {
-    headers
-        .get("authorization")
-        .and_then(|h| h.to_str().ok())
-        .map(|s| s.to_string())
+/// Content type stored in decrypted collection metadata (legacy collections predate the tag)
+fn metadata_content_type(metadata: &serde_json::Value) -> String {
+    metadata["content_type"]
+        .as_str()
+        .unwrap_or(DEFAULT_CONTENT_TYPE)
+        .to_string()
+}
+
+/// Build a `?raw=true` decrypt response: the payload as the body, typed by its stored content type
+/// Binary payloads are stored as base64 and returned decoded
+fn raw_payload_response(content_type: &str, data: Vec<u8>) -> Result<axum::response::Response> {
+    let body = if media_type_essence(content_type) == BINARY_CONTENT_TYPE {
+        general_purpose::STANDARD.decode(&data)?
+    } else {
+        data
+    };
+    let header_value = axum::http::HeaderValue::from_str(content_type)
+        .map_err(|e| GoudChainError::Internal(format!("Invalid stored content type: {}", e)))?;
+
+    Ok(([(axum::http::header::CONTENT_TYPE, header_value)], body).into_response())
+}
+
+/// Shape a decrypted payload for a JSON decrypt response, returning it with its `encoding`
+/// JSON collections come back parsed; binary ones are stored as base64 text and returned as is.
+/// Anything else is returned as text, or base64 if it isn't valid UTF-8, so nothing is mangled.
+fn decrypted_payload_value(content_type: &str, data: Vec<u8>) -> (serde_json::Value, String) {
+    let essence = media_type_essence(content_type);
+    if essence == DEFAULT_CONTENT_TYPE {
+        match serde_json::from_slice(&data) {
+            Ok(value) => return (value, "json".to_string()),
+            Err(e) => warn!(error = %e, "Stored JSON payload does not parse, returning it raw"),
+        }
+    }
+
+    match String::from_utf8(data) {
+        Ok(text) if essence == BINARY_CONTENT_TYPE => (text.into(), "base64".to_string()),
+        Ok(text) => (text.into(), "text".to_string()),
+        Err(e) => (
+            general_purpose::STANDARD.encode(e.into_bytes()).into(),
+            "base64".to_string(),
+        ),
+    }
+}
+
+/// Resolve a submission's revision parent to the head of its version chain
+/// Rejects parents the caller doesn't own (reported as not found to avoid leaking existence),
+/// and heads other than `expected_parent_version` when the caller set one
+fn resolve_revision_parent(
+    blockchain: &Blockchain,
+    request: &SubmitDataRequest,
+    api_key: &[u8],
+) -> Result<Option<String>> {
+    let Some(parent_id) = &request.parent_collection_id else {
+        return Ok(None);
+    };
+
+    if blockchain.find_collection(parent_id, api_key)?.is_none() {
+        return Err(GoudChainError::DataNotFound(parent_id.clone()));
+    }
+
+    let Some(head) = blockchain.find_head_revision(parent_id, api_key) else {
+        return Ok(None);
+    };
+
+    if let Some(expected_version) = request.expected_parent_version {
+        if head.version != expected_version {
+            return Err(GoudChainError::RevisionConflict {
+                expected_version,
+                head_collection_id: head.collection.collection_id,
+                head_version: head.version,
+            });
+        }
+    }
+
+    Ok(Some(head.collection.collection_id))
+}
+
+/// What a submission's revision parent was resolved to, kept for `recheck_revision_parent`
+/// and `check_unique_label`
+struct RevisionCheck {
+    parent_collection_id: Option<String>,
+    expected_parent_version: Option<usize>,
+    resolved_head: Option<String>,
+    unique_label: Option<String>, // The label, when the caller asked for it to be unique
+}
+
+impl RevisionCheck {
+    fn new(request: &SubmitDataRequest, resolved_head: &Option<String>) -> Self {
+        Self {
+            parent_collection_id: request.parent_collection_id.clone(),
+            expected_parent_version: request.expected_parent_version,
+            resolved_head: resolved_head.clone(),
+            unique_label: request
+                .unique_label
+                .unwrap_or(false)
+                .then(|| request.label.clone()),
+        }
+    }
+}
+
+/// Reject a `unique_label` submission whose label another live collection of the caller holds
+/// Runs before encryption and again under the write lock. Best-effort: a submission forwarded
+/// to a different validator isn't in this node's pending pool until its block arrives
+fn check_unique_label(
+    blockchain: &Blockchain,
+    revision: &RevisionCheck,
+    api_key: &[u8],
+) -> Result<()> {
+    let Some(label) = &revision.unique_label else {
+        return Ok(());
+    };
+
+    match blockchain.find_label_holder(label, api_key, revision.resolved_head.as_deref()) {
+        Some(collection_id) => Err(GoudChainError::LabelConflict { collection_id }),
+        None => Ok(()),
+    }
+}
+
+/// Re-check a conditional revision under the write lock, right before it is added
+/// Another revision of the same chain may have landed while this one was being encrypted
+fn recheck_revision_parent(
+    blockchain: &Blockchain,
+    revision: &RevisionCheck,
+    api_key: &[u8],
+) -> Result<()> {
+    let (Some(parent_id), Some(expected_version)) = (
+        &revision.parent_collection_id,
+        revision.expected_parent_version,
+    ) else {
+        return Ok(());
+    };
+
+    match blockchain.find_head_revision(parent_id, api_key) {
+        Some(head) if Some(&head.collection.collection_id) != revision.resolved_head.as_ref() => {
+            Err(GoudChainError::RevisionConflict {
+                expected_version,
+                head_collection_id: head.collection.collection_id,
+                head_version: head.version,
+            })
+        }
+        _ => Ok(()),
+    }
 }
 
-/// Extract signature header value for forwarding
-fn extract_signature_header(headers: &HeaderMap) -> Option<String> {
-    headers
-        .get("x-signature")
-        .and_then(|h| h.to_str().ok())
-        .map(|s| s.to_string())
+/// Encrypt a submission as a new collection, or as a revision when a parent was resolved
+#[allow(clippy::too_many_arguments)]
+fn encrypt_submission(
+    label: String,
+    data: String,
+    compress: Option<bool>,
+    content_type: &str,
+    expires_at: Option<i64>,
+    parent_collection_id: Option<String>,
+    api_key: &[u8],
+    api_key_hash: &str,
+    signing_key: &ed25519_dalek::SigningKey,
+) -> Result<EncryptedCollection> {
+    match parent_collection_id {
+        Some(parent_id) => EncryptedCollection::new_revision(
+            parent_id,
+            label,
+            data,
+            compress,
+            content_type,
+            expires_at,
+            api_key,
+            api_key_hash.to_string(),
+            signing_key,
+        ),
+        None => EncryptedCollection::new(
+            label,
+            data,
+            compress,
+            content_type,
+            expires_at,
+            api_key,
+            api_key_hash.to_string(),
+            signing_key,
+        ),
+    }
 }
 
 /// Submit encrypted data to blockchain
@@ -92,24 +277,41 @@ fn extract_signature_header(headers: &HeaderMap) -> Option<String> {
 /// Encrypts user data and stores it on the blockchain in an immutable, tamper-proof manner.
 /// Each data submission creates a new encrypted collection with a unique ID.
 /// The data is encrypted with the user's API key, ensuring only the owner can decrypt it.
-/// Requires direct API key authentication (session tokens not supported for data submission).
+/// An optional `expires_at` hides the collection from every read endpoint once it passes;
+/// the encrypted data itself stays on the immutable chain.
+/// Requires direct API key authentication (session tokens not supported for data submission)
+/// and a unique `X-Nonce` header; replaying a nonce returns 409 Conflict.
+/// With `dry_run=true` the submission is validated and encrypted but never written: the response
+/// carries the would-be `collection_id` and encrypted size, the nonce stays unused, and the call
+/// counts against the read rate limit.
+/// With an `Idempotency-Key` header, a retry with the same key and body replays the original
+/// response (for 24 hours) instead of storing the data again.
 #[utoipa::path(
     post,
     path = "/submit",
     tag = DATA_TAG,
+    params(
+        SubmitDataQuery,
+        ("Idempotency-Key" = Option<String>, Header, description = "Client-chosen key that makes retries of this request safe")
+    ),
     request_body = SubmitDataRequest,
     security(
         ("api_key" = [])
     ),
     responses(
+        (status = 200, description = "Dry run: submission is valid, nothing was written", body = SubmitDataDryRunResponse),
         (status = 201, description = "Data submitted successfully", body = SubmitDataResponse),
-        (status = 400, description = "Invalid request or payload too large", body = ErrorResponse),
-        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
-        (status = 403, description = "Only current validator can submit data", body = ErrorResponse),
+        (status = 202, description = "Data queued for the next coalesced block (MIN_BLOCK_INTERVAL_SECONDS). The queue is held in memory until sealed: a node crash before then loses it, and the X-Nonce stays used, so retry with a new nonce", body = SubmitDataResponse),
+        (status = 400, description = "Malformed request body", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key, or missing X-Nonce header", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant write access", body = ErrorResponse),
+        (status = 409, description = "Nonce already used (replay detected), the revision chain moved past expected_parent_version, unique_label is already held by another collection, or a request with the same Idempotency-Key is still in progress", body = ErrorResponse),
+        (status = 422, description = "Field validation failed (VALIDATION_FAILED lists every violation), or Idempotency-Key was already used with a different request body", body = ErrorResponse),
         (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
+#[allow(clippy::too_many_arguments)]
 async fn submit_data(
     headers: HeaderMap,
     Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
@@ -117,18 +319,29 @@ async fn submit_data(
     Extension(config): Extension<Arc<Config>>,
     Extension(rate_limiter): Extension<Arc<RateLimiter>>,
     Extension(state): Extension<SubmitDataState>,
-    Json(request): Json<SubmitDataRequest>,
+    Extension(nonce_store): Extension<Arc<NonceStore>>,
+    Query(query): Query<SubmitDataQuery>,
+    body: String,
 ) -> Result<impl IntoResponse> {
-    // Extract Authorization header (needed for forwarding)
+    // Refuse writes while the node is in read-only maintenance mode
+    blockchain.read().await.ensure_writable()?;
+
+    // Extract Authorization and signature headers (needed for verification and forwarding)
     let auth_header_value = extract_auth_header(&headers);
+    let request_signature = RequestSignature::from_headers(&headers)?;
+    let nonce = require_fresh_nonce(&headers, &nonce_store)?;
+    let request: SubmitDataRequest = parse_json_body(&body)?;
+    let dry_run = query.dry_run.unwrap_or(false);
 
     // Extract authentication
-    let auth = extract_auth_from_headers(&headers, &config)?;
+    let auth = authenticate(&headers, &config, &state)?;
+    auth.require_scope(TokenScope::Write)?;
 
     // Get API key and hash based on auth method
     let (api_key, api_key_hash) = match auth {
         AuthMethod::ApiKey(key) => {
-            let hash = hash_api_key_hex(&key);
+            let hash =
+                hash_request_api_key(&rate_limiter, &extract_client_ip(&headers), &key).await?;
             (key, hash)
         }
         AuthMethod::SessionToken(_) => {
@@ -141,18 +354,20 @@ async fn submit_data(
     // Extract client IP for rate limiting
     let client_ip = extract_client_ip(&headers);
 
-    // Check rate limit (write operation)
-    let rate_limit_result = match rate_limiter.check_limit(&api_key_hash, &client_ip, true) {
-        Ok(result) => result,
-        Err(e) => {
-            error!(error = %e, "Rate limit check failed, allowing request");
-            RateLimitResult::Allowed {
-                limit: 10,
-                remaining: 10,
-                reset_at: chrono::Utc::now().timestamp() + 60,
-            }
-        }
+    // Check rate limit (write operation; a dry run writes nothing, so it counts as a read)
+    let rate_limit_category = if dry_run {
+        RateLimitCategory::List
+    } else {
+        RateLimitCategory::Submit
     };
+    let rate_limit_result =
+        match rate_limiter.check_limit(&api_key_hash, &client_ip, rate_limit_category) {
+            Ok(result) => result,
+            Err(e) => {
+                error!(error = %e, "Rate limit check failed, allowing request");
+                rate_limiter.fail_open(rate_limit_category)
+            }
+        };
 
     // Handle rate limit result
     match &rate_limit_result {
@@ -167,6 +382,17 @@ async fn submit_data(
                 violation_count = violation_count,
                 "Request blocked by rate limiter"
             );
+            record_security_event(
+                &state,
+                &config,
+                AuditEventType::RateLimited,
+                &client_ip,
+                serde_json::json!({
+                    "endpoint": "/data/submit",
+                    "ban_level": format!("{:?}", ban_level),
+                    "violation_count": violation_count,
+                }),
+            );
             let error = GoudChainError::ApiKeyBanned {
                 ban_level: format!("{:?}", ban_level),
                 expires_at: chrono::Utc::now().timestamp() + *retry_after as i64,
@@ -198,19 +424,69 @@ async fn submit_data(
 
     // Verify account exists
     let blockchain_guard = blockchain.read().await;
-    if blockchain_guard
-        .find_account_with_hash(&api_key, Some(api_key_hash.clone()))
-        .is_none()
-    {
-        drop(blockchain_guard);
-        return Err(GoudChainError::Unauthorized(
-            "Account not found".to_string(),
-        ));
-    }
+    let account =
+        match blockchain_guard.find_account_with_hash(&api_key, Some(api_key_hash.clone())) {
+            Some(account) => account,
+            None => {
+                return Err(GoudChainError::AccountNotFound);
+            }
+        };
+
+    // A signature's nonce is the X-Nonce reserved above
+    enforce_request_signature(
+        request_signature.as_ref(),
+        "POST",
+        "/data/submit",
+        &body,
+        &account,
+        &config,
+        &nonce_store,
+    )?;
+
+    let parent_collection_id = resolve_revision_parent(&blockchain_guard, &request, &api_key)?;
+    let revision_check = RevisionCheck::new(&request, &parent_collection_id);
+    check_unique_label(&blockchain_guard, &revision_check, &api_key)?;
     drop(blockchain_guard);
 
     // Validate request size BEFORE encryption
-    request.validate()?;
+    request.validate(config.max_payload_bytes)?;
+
+    if dry_run {
+        let signing_key = blockchain
+            .read()
+            .await
+            .node_signing_key
+            .clone()
+            .ok_or_else(|| {
+                GoudChainError::Internal("Node signing key not available".to_string())
+            })?;
+        let content_type = request.content_type().to_string();
+        let collection = encrypt_submission(
+            request.label,
+            request.data,
+            request.compress,
+            &content_type,
+            request.expires_at,
+            parent_collection_id,
+            &api_key,
+            &api_key_hash,
+            &signing_key,
+        )?;
+
+        // Envelope containers are stored as JSON, so this is the size the collection adds to a block
+        let encrypted_size_bytes = serde_json::to_vec(&collection)
+            .map_err(|e| GoudChainError::SerializationError(e.to_string()))?
+            .len();
+        let response = SubmitDataDryRunResponse {
+            message: "Dry run: submission is valid and was not stored".to_string(),
+            collection_id: collection.collection_id,
+            encrypted_size_bytes,
+        };
+
+        let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+        let response_obj = (StatusCode::OK, Json(response)).into_response();
+        return Ok(add_rate_limit_headers(response_obj, rate_headers));
+    }
 
     // Check if this node is the authorized validator
     let blockchain_guard = blockchain.read().await;
@@ -219,19 +495,16 @@ async fn submit_data(
         .last()
         .map(|b| b.index + 1)
         .unwrap_or(1);
-    let is_validator = is_authorized_validator(
-        &blockchain_guard.node_id,
-        next_block_number,
-        &blockchain_guard.validator_config,
-    );
+    let is_validator = blockchain_guard
+        .validator_config
+        .is_node_authorized(&blockchain_guard.node_id, next_block_number);
     let node_id = blockchain_guard.node_id.clone();
     let validator_config = blockchain_guard.validator_config.clone();
     drop(blockchain_guard);
 
     if !is_validator {
         // Forward request to the correct validator
-        let expected_validator =
-            get_current_validator(&validator_config.validators, next_block_number);
+        let expected_validator = validator_config.get_validator_for_block(next_block_number);
         warn!(
             current_node = %node_id,
             expected_validator = %expected_validator,
@@ -239,55 +512,34 @@ async fn submit_data(
             "Forwarding data submission to validator node"
         );
 
-        match validator_config.get_validator_address(&expected_validator) {
-            Some(validator_addr) => {
-                let body = serde_json::to_string(&request).map_err(|e| {
-                    GoudChainError::Internal(format!("Serialization failed: {}", e))
-                })?;
-
-                let signature_header = extract_signature_header(&headers);
-
-                match forward_request_with_headers(
-                    &validator_addr,
-                    "POST",
-                    "/data/submit",
-                    &body,
-                    "application/json",
-                    auth_header_value.as_deref(),
-                    signature_header.as_deref(),
+        return match forward_to_validator(
+            &blockchain,
+            "POST",
+            "/data/submit",
+            &body,
+            "application/json",
+            auth_header_value.as_deref(),
+            &extract_replay_headers(&headers),
+        )
+        .await
+        {
+            Ok((status_code, response_body)) => {
+                info!(
+                    status = status_code,
+                    "Forwarded data submission successfully"
+                );
+                Ok((
+                    StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK),
+                    [(axum::http::header::CONTENT_TYPE, "application/json")],
+                    response_body,
                 )
-                .await
-                {
-                    Ok((status_code, response_body)) => {
-                        info!(
-                            validator = %expected_validator,
-                            status = status_code,
-                            "Forwarded data submission successfully"
-                        );
-                        return Ok((
-                            StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK),
-                            [(axum::http::header::CONTENT_TYPE, "application/json")],
-                            response_body,
-                        )
-                            .into_response());
-                    }
-                    Err(e) => {
-                        error!(error = %e, "Failed to forward data submission to validator");
-                        return Err(GoudChainError::Internal(format!(
-                            "Failed to forward to validator: {}",
-                            e
-                        )));
-                    }
-                }
+                    .into_response())
             }
-            None => {
-                error!(validator = %expected_validator, "Unknown validator address");
-                return Err(GoudChainError::Internal(format!(
-                    "Unknown validator: {}",
-                    expected_validator
-                )));
+            Err(e) => {
+                error!(error = %e, "Failed to forward data submission to validator");
+                Err(e)
             }
-        }
+        };
     }
 
     // This node IS the validator - proceed with encryption and block creation
@@ -298,18 +550,53 @@ async fn submit_data(
 
     match signing_key {
         Some(key) => {
-            match EncryptedCollection::new(
+            let content_type = request.content_type().to_string();
+            match encrypt_submission(
                 request.label.clone(),
                 request.data,
+                request.compress,
+                &content_type,
+                request.expires_at,
+                parent_collection_id,
                 &api_key,
-                api_key_hash.clone(),
+                &api_key_hash,
                 &key,
             ) {
                 Ok(collection) => {
                     let collection_id = collection.collection_id.clone();
 
                     let mut blockchain_guard = blockchain.write().await;
+                    recheck_revision_parent(&blockchain_guard, &revision_check, &api_key)?;
+                    check_unique_label(&blockchain_guard, &revision_check, &api_key)?;
                     match blockchain_guard.add_collection(collection) {
+                        Ok(_) if !blockchain_guard.seal_due_now() => {
+                            // Blocks are coalesced: the sealer puts this into the next block
+                            let block_number = blockchain_guard.chain.len() as u64;
+                            drop(blockchain_guard);
+                            commit_used_nonce(nonce);
+                            global_operations_tracker().record(1);
+
+                            if let Err(e) = state.audit_logger.log(
+                                &api_key,
+                                AuditEventType::DataSubmitted,
+                                Some(collection_id.clone()),
+                                &client_ip,
+                                serde_json::json!({"block": block_number, "label": request.label, "pending": true}),
+                            ) {
+                                error!(error = %e, "Failed to log data submission audit event");
+                            }
+
+                            let response = SubmitDataResponse {
+                                message: "Data encrypted and queued for the next block".to_string(),
+                                collection_id,
+                                block_number,
+                                pending: true,
+                            };
+                            let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+                            let response_obj =
+                                (StatusCode::ACCEPTED, Json(response)).into_response();
+                            Ok(add_rate_limit_headers(response_obj, rate_headers))
+                        }
                         Ok(_) => {
                             match blockchain_guard.add_block() {
                                 Ok(block) => {
@@ -317,9 +604,7 @@ async fn submit_data(
                                         error!(error = %e, "Failed to save block to RocksDB");
                                     }
 
-                                    #[allow(unknown_lints)]
-                                    #[allow(clippy::manual_is_multiple_of)]
-                                    if block.index % CHECKPOINT_INTERVAL == 0 {
+                                    if blockchain_guard.is_checkpoint_index(block.index) {
                                         if let Err(e) = p2p
                                             .blockchain_store
                                             .save_checkpoint(block.index, &block.hash)
@@ -330,12 +615,15 @@ async fn submit_data(
 
                                     let block_index = block.index;
                                     drop(blockchain_guard);
+                                    commit_used_nonce(nonce);
+                                    global_operations_tracker().record(1);
 
                                     let response = SubmitDataResponse {
                                         message: "Data encrypted and stored successfully"
                                             .to_string(),
                                         collection_id: collection_id.clone(),
                                         block_number: block_index,
+                                        pending: false,
                                     };
 
                                     // Audit log
@@ -369,7 +657,11 @@ async fn submit_data(
                                     let bhash = block_arc.hash.clone();
                                     tokio::spawn(async move {
                                         ws_clone
-                                            .broadcast_collection_update(cid, block_index)
+                                            .broadcast_collection_update(
+                                                cid,
+                                                api_key_hash,
+                                                block_index,
+                                            )
                                             .await;
                                         ws_clone
                                             .broadcast_blockchain_update(block_index, bhash)
@@ -402,74 +694,82 @@ async fn submit_data(
     }
 }
 
-/// List all data collections
+/// Submit a batch of encrypted data
 ///
-/// Returns metadata for all encrypted collections owned by the authenticated user.
-/// This endpoint does not decrypt the actual data content - use decrypt endpoint for that.
-/// Supports both API key and session token authentication.
+/// Encrypts up to 100 collections and stores them together in a single block, avoiding one block
+/// (and one HTTP round trip) per collection. Every item is validated before any encryption happens,
+/// so a single invalid or oversize item rejects the whole batch.
+/// Requires direct API key authentication (session tokens not supported for data submission).
 #[utoipa::path(
-    get,
-    path = "/list",
+    post,
+    path = "/submit_batch",
     tag = DATA_TAG,
+    request_body = Vec<SubmitDataRequest>,
     security(
-        ("bearer_token" = []),
         ("api_key" = [])
     ),
     responses(
-        (status = 200, description = "Data list retrieved successfully", body = CollectionListResponse),
-        (status = 401, description = "Missing or invalid authentication", body = ErrorResponse),
+        (status = 201, description = "Batch encrypted and stored successfully", body = SubmitBatchResponse),
+        (status = 202, description = "Batch queued for the next coalesced block (MIN_BLOCK_INTERVAL_SECONDS). The queue is held in memory until sealed: a node crash before then loses it, and a signature nonce stays used, so retry with a new nonce", body = SubmitBatchResponse),
+        (status = 400, description = "Empty batch or too many items", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant write access", body = ErrorResponse),
+        (status = 404, description = "Revision parent not found or access denied", body = ErrorResponse),
+        (status = 409, description = "Nonce already used (replay detected), the revision chain moved past expected_parent_version, or a unique_label is already held (including by another item of the batch)", body = ErrorResponse),
+        (status = 422, description = "Item validation failed (fields are prefixed with the item index, e.g. `[2].label`)", body = ErrorResponse),
         (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
-async fn list_data(
+#[allow(clippy::too_many_arguments)]
+async fn submit_batch(
     headers: HeaderMap,
     Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(p2p): Extension<Arc<P2PNode>>,
     Extension(config): Extension<Arc<Config>>,
     Extension(rate_limiter): Extension<Arc<RateLimiter>>,
     Extension(state): Extension<SubmitDataState>,
+    Extension(nonce_store): Extension<Arc<NonceStore>>,
+    body: String,
 ) -> Result<impl IntoResponse> {
-    let audit_logger = &state.audit_logger;
+    // Refuse writes while the node is in read-only maintenance mode
+    blockchain.read().await.ensure_writable()?;
+
+    // Extract Authorization and signature headers (needed for verification and forwarding)
+    let auth_header_value = extract_auth_header(&headers);
+    let request_signature = RequestSignature::from_headers(&headers)?;
+    let requests: Vec<SubmitDataRequest> = parse_json_body(&body)?;
 
     // Extract authentication
-    let auth = extract_auth_from_headers(&headers, &config)?;
+    let auth = authenticate(&headers, &config, &state)?;
+    auth.require_scope(TokenScope::Write)?;
 
-    // Support both API keys and session tokens
+    // Get API key and hash based on auth method
     let (api_key, api_key_hash) = match auth {
         AuthMethod::ApiKey(key) => {
-            let hash = hash_api_key_hex(&key);
+            let hash =
+                hash_request_api_key(&rate_limiter, &extract_client_ip(&headers), &key).await?;
             (key, hash)
         }
-        AuthMethod::SessionToken(claims) => {
-            let key = match decrypt_api_key_from_jwt(&claims.encrypted_api_key, &config) {
-                Ok(k) => k,
-                Err(e) => {
-                    return Err(GoudChainError::Unauthorized(format!(
-                        "Failed to decrypt API key from session token: {}",
-                        e
-                    )));
-                }
-            };
-            let hash = claims.api_key_hash;
-            (key, hash)
+        AuthMethod::SessionToken(_) => {
+            return Err(GoudChainError::Unauthorized(
+                "Direct API key required for data submission".to_string(),
+            ));
         }
     };
 
     // Extract client IP for rate limiting
     let client_ip = extract_client_ip(&headers);
 
-    // Check rate limit (read operation)
-    let rate_limit_result = match rate_limiter.check_limit(&api_key_hash, &client_ip, false) {
-        Ok(result) => result,
-        Err(e) => {
-            error!(error = %e, "Rate limit check failed, allowing request");
-            RateLimitResult::Allowed {
-                limit: 100,
-                remaining: 100,
-                reset_at: chrono::Utc::now().timestamp() + 60,
+    // Check rate limit (write operation - one hit for the whole batch)
+    let rate_limit_result =
+        match rate_limiter.check_limit(&api_key_hash, &client_ip, RateLimitCategory::Batch) {
+            Ok(result) => result,
+            Err(e) => {
+                error!(error = %e, "Rate limit check failed, allowing request");
+                rate_limiter.fail_open(RateLimitCategory::Batch)
             }
-        }
-    };
+        };
 
     // Handle rate limit result
     match &rate_limit_result {
@@ -482,7 +782,18 @@ async fn list_data(
                 api_key_hash = %api_key_hash,
                 ban_level = ?ban_level,
                 violation_count = violation_count,
-                "Read request blocked by rate limiter"
+                "Batch request blocked by rate limiter"
+            );
+            record_security_event(
+                &state,
+                &config,
+                AuditEventType::RateLimited,
+                &client_ip,
+                serde_json::json!({
+                    "endpoint": "/data/submit_batch",
+                    "ban_level": format!("{:?}", ban_level),
+                    "violation_count": violation_count,
+                }),
             );
             let error = GoudChainError::ApiKeyBanned {
                 ban_level: format!("{:?}", ban_level),
@@ -493,38 +804,323 @@ async fn list_data(
             return Ok(add_rate_limit_headers(response, rate_headers));
         }
         RateLimitResult::Warning {
-            violation_count, ..
+            violation_count,
+            cooldown_secs,
+            ..
         } => {
             warn!(
                 api_key_hash = %api_key_hash,
                 violation_count = violation_count,
-                "Rate limit warning on read operation"
+                cooldown_secs = cooldown_secs,
+                "Rate limit warning issued"
             );
         }
         RateLimitResult::Allowed { remaining, .. } => {
             info!(
                 api_key_hash = %api_key_hash,
                 remaining = remaining,
-                "Read rate limit check passed"
+                "Rate limit check passed"
             );
         }
     }
 
+    // Validate batch shape and every item BEFORE any encryption
+    if requests.is_empty() {
+        return Err(GoudChainError::InvalidRequestBody(
+            "Batch must contain at least one item".to_string(),
+        ));
+    }
+    if requests.len() > MAX_BATCH_SUBMIT_ITEMS {
+        return Err(GoudChainError::InvalidRequestBody(format!(
+            "Batch contains {} items (max: {})",
+            requests.len(),
+            MAX_BATCH_SUBMIT_ITEMS
+        )));
+    }
+    let errors: Vec<FieldError> = requests
+        .iter()
+        .enumerate()
+        .flat_map(|(i, request)| {
+            request
+                .field_errors(config.max_payload_bytes)
+                .into_iter()
+                .map(move |error| error.under(&format!("[{}]", i)))
+        })
+        .collect();
+    if !errors.is_empty() {
+        return Err(GoudChainError::ValidationFailed { errors });
+    }
+
+    // Verify account exists and resolve revision parents
     let blockchain_guard = blockchain.read().await;
+    let account =
+        match blockchain_guard.find_account_with_hash(&api_key, Some(api_key_hash.clone())) {
+            Some(account) => account,
+            None => {
+                return Err(GoudChainError::AccountNotFound);
+            }
+        };
 
-    // Verify account exists
-    if blockchain_guard.find_account(&api_key).is_none() {
-        return Err(GoudChainError::Unauthorized(
-            "Account not found".to_string(),
-        ));
+    enforce_request_signature(
+        request_signature.as_ref(),
+        "POST",
+        "/data/submit_batch",
+        &body,
+        &account,
+        &config,
+        &nonce_store,
+    )?;
+    let nonce_reservation = reserve_signature_nonce(request_signature.as_ref(), &nonce_store)?;
+
+    let parent_collection_ids = requests
+        .iter()
+        .map(|request| resolve_revision_parent(&blockchain_guard, request, &api_key))
+        .collect::<Result<Vec<_>>>()?;
+    let revision_checks: Vec<RevisionCheck> = requests
+        .iter()
+        .zip(&parent_collection_ids)
+        .map(|(request, head)| RevisionCheck::new(request, head))
+        .collect();
+    for revision_check in &revision_checks {
+        check_unique_label(&blockchain_guard, revision_check, &api_key)?;
     }
 
-    // Find all collections for this user
-    let collections = blockchain_guard.find_collections_by_owner(&api_key);
-    let mut result = Vec::new();
+    // Check if this node is the authorized validator
+    let next_block_number = blockchain_guard
+        .chain
+        .last()
+        .map(|b| b.index + 1)
+        .unwrap_or(1);
+    let is_validator = blockchain_guard
+        .validator_config
+        .is_node_authorized(&blockchain_guard.node_id, next_block_number);
+    let node_id = blockchain_guard.node_id.clone();
+    let validator_config = blockchain_guard.validator_config.clone();
+    let signing_key = blockchain_guard.node_signing_key.clone();
+    drop(blockchain_guard);
+
+    if !is_validator {
+        // Forward request to the correct validator
+        let expected_validator = validator_config.get_validator_for_block(next_block_number);
+        warn!(
+            current_node = %node_id,
+            expected_validator = %expected_validator,
+            next_block = next_block_number,
+            "Forwarding batch submission to validator node"
+        );
+
+        return match forward_to_validator(
+            &blockchain,
+            "POST",
+            "/data/submit_batch",
+            &body,
+            "application/json",
+            auth_header_value.as_deref(),
+            &extract_replay_headers(&headers),
+        )
+        .await
+        {
+            Ok((status_code, response_body)) => {
+                info!(
+                    status = status_code,
+                    "Forwarded batch submission successfully"
+                );
+                Ok((
+                    StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK),
+                    [(axum::http::header::CONTENT_TYPE, "application/json")],
+                    response_body,
+                )
+                    .into_response())
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to forward batch submission to validator");
+                Err(e)
+            }
+        };
+    }
+
+    // This node IS the validator - encrypt every item, then create a single block
+    let signing_key = signing_key
+        .ok_or_else(|| GoudChainError::Internal("Node signing key not available".to_string()))?;
 
+    let mut collections = Vec::with_capacity(requests.len());
+    let mut labels = Vec::with_capacity(requests.len());
+    for (request, parent_collection_id) in requests.into_iter().zip(parent_collection_ids) {
+        labels.push(request.label.clone());
+        let content_type = request.content_type().to_string();
+        let collection = encrypt_submission(
+            request.label,
+            request.data,
+            request.compress,
+            &content_type,
+            request.expires_at,
+            parent_collection_id,
+            &api_key,
+            &api_key_hash,
+            &signing_key,
+        )
+        .inspect_err(|e| error!(error = %e, "Failed to create encrypted collection"))?;
+        collections.push(collection);
+    }
+    let collection_ids: Vec<String> = collections
+        .iter()
+        .map(|c| c.collection_id.clone())
+        .collect();
+
+    let mut blockchain_guard = blockchain.write().await;
+    for revision_check in &revision_checks {
+        recheck_revision_parent(&blockchain_guard, revision_check, &api_key)?;
+        check_unique_label(&blockchain_guard, revision_check, &api_key)?;
+    }
+    // A unique label must not be reused by another item of the same batch either
+    for (position, revision_check) in revision_checks.iter().enumerate() {
+        let Some(label) = &revision_check.unique_label else {
+            continue;
+        };
+        let duplicate = labels
+            .iter()
+            .enumerate()
+            .find(|&(other, other_label)| other != position && other_label == label);
+        if let Some((other, _)) = duplicate {
+            return Err(GoudChainError::LabelConflict {
+                collection_id: collection_ids[other].clone(),
+            });
+        }
+    }
     for collection in collections {
-        let label = match collection.decrypt_metadata(&api_key) {
+        blockchain_guard
+            .add_collection(collection)
+            .inspect_err(|e| error!(error = %e, "Failed to add collection"))?;
+    }
+
+    // Blocks are coalesced: the sealer puts the batch into the next block
+    if !blockchain_guard.seal_due_now() {
+        let block_number = blockchain_guard.chain.len() as u64;
+        drop(blockchain_guard);
+        global_operations_tracker().record(collection_ids.len() as u64);
+        if let Some(reservation) = nonce_reservation {
+            commit_used_nonce(reservation);
+        }
+
+        for (collection_id, label) in collection_ids.iter().zip(&labels) {
+            if let Err(e) = state.audit_logger.log(
+                &api_key,
+                AuditEventType::DataSubmitted,
+                Some(collection_id.clone()),
+                &client_ip,
+                serde_json::json!({"block": block_number, "label": label, "pending": true}),
+            ) {
+                error!(error = %e, "Failed to log data submission audit event");
+            }
+        }
+
+        let response = SubmitBatchResponse {
+            message: format!(
+                "{} collections encrypted and queued for the next block",
+                collection_ids.len()
+            ),
+            collection_ids,
+            block_number,
+            pending: true,
+        };
+        let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+        let response_obj = (StatusCode::ACCEPTED, Json(response)).into_response();
+        return Ok(add_rate_limit_headers(response_obj, rate_headers));
+    }
+
+    let block = blockchain_guard
+        .add_block()
+        .inspect_err(|e| error!(error = %e, "Failed to add block"))?;
+
+    if let Err(e) = p2p.blockchain_store.save_block(&block) {
+        error!(error = %e, "Failed to save block to RocksDB");
+    }
+
+    if blockchain_guard.is_checkpoint_index(block.index) {
+        if let Err(e) = p2p
+            .blockchain_store
+            .save_checkpoint(block.index, &block.hash)
+        {
+            error!(error = %e, "Failed to save checkpoint");
+        }
+    }
+
+    let block_index = block.index;
+    drop(blockchain_guard);
+    global_operations_tracker().record(collection_ids.len() as u64);
+
+    if let Some(reservation) = nonce_reservation {
+        commit_used_nonce(reservation);
+    }
+
+    // Audit log (one event per collection)
+    for (collection_id, label) in collection_ids.iter().zip(&labels) {
+        if let Err(e) = state.audit_logger.log(
+            &api_key,
+            AuditEventType::DataSubmitted,
+            Some(collection_id.clone()),
+            &client_ip,
+            serde_json::json!({"block": block_index, "label": label}),
+        ) {
+            error!(error = %e, "Failed to log data submission audit event");
+        }
+    }
+
+    let response = SubmitBatchResponse {
+        message: format!(
+            "{} collections encrypted and stored successfully",
+            collection_ids.len()
+        ),
+        collection_ids: collection_ids.clone(),
+        block_number: block_index,
+        pending: false,
+    };
+    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+    let response_obj = (StatusCode::CREATED, Json(response)).into_response();
+
+    let block_arc = Arc::new(block);
+
+    // Broadcast block in background
+    let p2p_clone = Arc::clone(&p2p);
+    let block_ref = Arc::clone(&block_arc);
+    tokio::spawn(async move {
+        p2p_clone.broadcast_block(&block_ref).await;
+    });
+
+    // Broadcast WebSocket events
+    let ws_clone = Arc::clone(&state.ws_broadcaster);
+    let bhash = block_arc.hash.clone();
+    tokio::spawn(async move {
+        for cid in collection_ids {
+            ws_clone
+                .broadcast_collection_update(cid, api_key_hash.clone(), block_index)
+                .await;
+        }
+        ws_clone
+            .broadcast_blockchain_update(block_index, bhash)
+            .await;
+    });
+
+    Ok(add_rate_limit_headers(response_obj, rate_headers))
+}
+
+/// Build list items for collection revisions, keeping only head revisions unless `include_history`
+/// Block numbers come from the store's collection index, so no block scan is needed
+fn collection_list_items(
+    revisions: Vec<CollectionRevision>,
+    include_history: bool,
+    api_key: &[u8],
+    store: &BlockchainStore,
+) -> Vec<CollectionListItem> {
+    let mut result = Vec::new();
+
+    for revision in revisions {
+        if !include_history && !revision.is_head {
+            continue;
+        }
+        let collection = revision.collection;
+
+        let label = match collection.decrypt_metadata(api_key) {
             Ok(metadata) => metadata["label"]
                 .as_str()
                 .unwrap_or("[decryption failed]")
@@ -532,7 +1128,7 @@ async fn list_data(
             Err(_) => "[encrypted]".to_string(),
         };
 
-        let created_at = match collection.decrypt_metadata(&api_key) {
+        let created_at = match collection.decrypt_metadata(api_key) {
             Ok(metadata) => metadata["created_at"].as_i64().unwrap_or(0),
             Err(_) => 0,
         };
@@ -541,58 +1137,77 @@ async fn list_data(
             collection_id: collection.collection_id.clone(),
             label,
             created_at,
-            block_number: 0,
+            block_number: indexed_block_number(store, &collection.collection_id),
+            version: revision.version,
+            parent_collection_id: collection.parent_collection_id.clone(),
+            shared: false,
         });
     }
 
-    let response = CollectionListResponse {
-        collections: result.clone(),
-    };
-
-    // Audit log
-    if let Err(e) = audit_logger.log(
-        &api_key,
-        AuditEventType::DataListed,
-        None,
-        &client_ip,
-        serde_json::json!({"count": result.len()}),
-    ) {
-        error!(error = %e, "Failed to log data list audit event");
-    }
+    result
+}
 
-    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
-    let response_obj = Json(response).into_response();
-    Ok(add_rate_limit_headers(response_obj, rate_headers))
+/// List items for collections shared with the caller (keyed by grant ID, always version 1)
+fn shared_list_items(
+    shared: Vec<SharedCollection>,
+    store: &BlockchainStore,
+) -> Vec<CollectionListItem> {
+    shared
+        .into_iter()
+        .map(|shared| CollectionListItem {
+            block_number: indexed_block_number(store, &shared.grant_id),
+            collection_id: shared.grant_id,
+            label: shared.label,
+            created_at: shared.created_at,
+            version: 1,
+            parent_collection_id: None,
+            shared: true,
+        })
+        .collect()
 }
 
-/// Decrypt data from a collection
+/// Block holding a collection, from the store's collection index
+/// A block still being persisted is not indexed yet, so this falls back to 0
+fn indexed_block_number(store: &BlockchainStore, collection_id: &str) -> u64 {
+    match store.get_collection_block(collection_id) {
+        Ok(index) => index.unwrap_or(0),
+        Err(e) => {
+            warn!(error = %e, "Failed to read collection block index");
+            0
+        }
+    }
+}
+
+/// List all data collections
 ///
-/// Decrypts and returns the data content from a specific encrypted collection.
-/// Only the owner (authenticated with the correct API key or session token) can decrypt the data.
-/// This operation is computationally expensive and subject to stricter rate limits.
+/// Returns metadata for the encrypted collections owned by the authenticated user, newest first
+/// and paginated with `page`/`page_size`. The first page is followed by collections other
+/// accounts shared with them (marked `shared`, listed by grant ID).
+/// Only the latest revision of each version chain is listed unless `include_history=true`.
+/// This endpoint does not decrypt the actual data content - use decrypt endpoint for that.
+/// Supports both API key and session token authentication.
 #[utoipa::path(
-    post,
-    path = "/decrypt/{collection_id}",
+    get,
+    path = "/list",
     tag = DATA_TAG,
-    params(
-        ("collection_id" = String, Path, description = "Collection ID to decrypt", example = "550e8400-e29b-41d4-a716-446655440000")
-    ),
+    params(CollectionListQuery),
     security(
         ("bearer_token" = []),
         ("api_key" = [])
     ),
     responses(
-        (status = 200, description = "Data decrypted successfully", body = DecryptCollectionResponse),
+        (status = 200, description = "Data list retrieved successfully", body = CollectionListResponse),
         (status = 401, description = "Missing or invalid authentication", body = ErrorResponse),
-        (status = 404, description = "Collection not found or access denied", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant read access", body = ErrorResponse),
         (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
-        (status = 500, description = "Decryption failed", body = ErrorResponse)
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
-async fn decrypt_data(
+async fn list_data(
     headers: HeaderMap,
-    Path(collection_id): Path<String>,
+    Query(params): Query<CollectionListQuery>,
     Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(p2p): Extension<Arc<P2PNode>>,
     Extension(config): Extension<Arc<Config>>,
     Extension(rate_limiter): Extension<Arc<RateLimiter>>,
     Extension(state): Extension<SubmitDataState>,
@@ -600,12 +1215,14 @@ async fn decrypt_data(
     let audit_logger = &state.audit_logger;
 
     // Extract authentication
-    let auth = extract_auth_from_headers(&headers, &config)?;
+    let auth = authenticate(&headers, &config, &state)?;
+    auth.require_scope(TokenScope::Read)?;
 
     // Support both API keys and session tokens
     let (api_key, api_key_hash) = match auth {
         AuthMethod::ApiKey(key) => {
-            let hash = hash_api_key_hex(&key);
+            let hash =
+                hash_request_api_key(&rate_limiter, &extract_client_ip(&headers), &key).await?;
             (key, hash)
         }
         AuthMethod::SessionToken(claims) => {
@@ -626,19 +1243,188 @@ async fn decrypt_data(
     // Extract client IP for rate limiting
     let client_ip = extract_client_ip(&headers);
 
-    // Check rate limit (write operation - decryption is expensive)
-    let rate_limit_result = match rate_limiter.check_limit(&api_key_hash, &client_ip, true) {
-        Ok(result) => result,
-        Err(e) => {
-            error!(error = %e, "Rate limit check failed, allowing request");
-            RateLimitResult::Allowed {
-                limit: 10,
-                remaining: 10,
-                reset_at: chrono::Utc::now().timestamp() + 60,
+    // Check rate limit (read operation)
+    let rate_limit_result =
+        match rate_limiter.check_limit(&api_key_hash, &client_ip, RateLimitCategory::List) {
+            Ok(result) => result,
+            Err(e) => {
+                error!(error = %e, "Rate limit check failed, allowing request");
+                rate_limiter.fail_open(RateLimitCategory::List)
             }
+        };
+
+    // Handle rate limit result
+    match &rate_limit_result {
+        RateLimitResult::Blocked {
+            ban_level,
+            retry_after,
+            violation_count,
+        } => {
+            warn!(
+                api_key_hash = %api_key_hash,
+                ban_level = ?ban_level,
+                violation_count = violation_count,
+                "Read request blocked by rate limiter"
+            );
+            record_security_event(
+                &state,
+                &config,
+                AuditEventType::RateLimited,
+                &client_ip,
+                serde_json::json!({
+                    "endpoint": "/data/list",
+                    "ban_level": format!("{:?}", ban_level),
+                    "violation_count": violation_count,
+                }),
+            );
+            let error = GoudChainError::ApiKeyBanned {
+                ban_level: format!("{:?}", ban_level),
+                expires_at: chrono::Utc::now().timestamp() + *retry_after as i64,
+            };
+            let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+            let response = error.into_response();
+            return Ok(add_rate_limit_headers(response, rate_headers));
+        }
+        RateLimitResult::Warning {
+            violation_count, ..
+        } => {
+            warn!(
+                api_key_hash = %api_key_hash,
+                violation_count = violation_count,
+                "Rate limit warning on read operation"
+            );
+        }
+        RateLimitResult::Allowed { remaining, .. } => {
+            info!(
+                api_key_hash = %api_key_hash,
+                remaining = remaining,
+                "Read rate limit check passed"
+            );
+        }
+    }
+
+    let blockchain_guard = blockchain.read().await;
+
+    // Verify account exists
+    if blockchain_guard.find_account(&api_key).is_none() {
+        return Err(GoudChainError::AccountNotFound);
+    }
+
+    // Find one page of this user's collections, scanning newest blocks first
+    let include_history = params.include_history.unwrap_or(false);
+    let page = params.page.unwrap_or(0);
+    let page_size = params
+        .page_size
+        .unwrap_or(LIST_DEFAULT_PAGE_SIZE)
+        .clamp(1, LIST_MAX_PAGE_SIZE);
+    let revisions = blockchain_guard.find_collection_revisions_paged(
+        &api_key,
+        include_history,
+        page.saturating_mul(page_size),
+        page_size,
+    );
+    let mut result =
+        collection_list_items(revisions, include_history, &api_key, &p2p.blockchain_store);
+    if page == 0 {
+        result.extend(shared_list_items(
+            blockchain_guard.find_shared_collections(&api_key),
+            &p2p.blockchain_store,
+        ));
+    }
+
+    let response = CollectionListResponse {
+        collections: result.clone(),
+    };
+
+    // Audit log
+    if let Err(e) = audit_logger.log(
+        &api_key,
+        AuditEventType::DataListed,
+        None,
+        &client_ip,
+        serde_json::json!({"count": result.len()}),
+    ) {
+        error!(error = %e, "Failed to log data list audit event");
+    }
+
+    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+    let response_obj = Json(response).into_response();
+    Ok(add_rate_limit_headers(response_obj, rate_headers))
+}
+
+/// Search data collections by label
+///
+/// Returns metadata for collections whose label exactly matches `label`.
+/// Matching compares per-collection label blind indexes, so only matching collections are decrypted.
+/// Only the latest revision of each version chain is returned unless `include_history=true`.
+/// Supports both API key and session token authentication.
+#[utoipa::path(
+    get,
+    path = "/search",
+    tag = DATA_TAG,
+    params(CollectionSearchQuery),
+    security(
+        ("bearer_token" = []),
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "Matching collections retrieved successfully", body = CollectionListResponse),
+        (status = 401, description = "Missing or invalid authentication", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant read access", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+async fn search_data(
+    headers: HeaderMap,
+    Query(params): Query<CollectionSearchQuery>,
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(p2p): Extension<Arc<P2PNode>>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
+    Extension(state): Extension<SubmitDataState>,
+) -> Result<impl IntoResponse> {
+    let audit_logger = &state.audit_logger;
+
+    // Extract authentication
+    let auth = authenticate(&headers, &config, &state)?;
+    auth.require_scope(TokenScope::Read)?;
+
+    // Support both API keys and session tokens
+    let (api_key, api_key_hash) = match auth {
+        AuthMethod::ApiKey(key) => {
+            let hash =
+                hash_request_api_key(&rate_limiter, &extract_client_ip(&headers), &key).await?;
+            (key, hash)
+        }
+        AuthMethod::SessionToken(claims) => {
+            let key = match decrypt_api_key_from_jwt(&claims.encrypted_api_key, &config) {
+                Ok(k) => k,
+                Err(e) => {
+                    return Err(GoudChainError::Unauthorized(format!(
+                        "Failed to decrypt API key from session token: {}",
+                        e
+                    )));
+                }
+            };
+            let hash = claims.api_key_hash;
+            (key, hash)
         }
     };
 
+    // Extract client IP for rate limiting
+    let client_ip = extract_client_ip(&headers);
+
+    // Check rate limit (read operation)
+    let rate_limit_result =
+        match rate_limiter.check_limit(&api_key_hash, &client_ip, RateLimitCategory::Search) {
+            Ok(result) => result,
+            Err(e) => {
+                error!(error = %e, "Rate limit check failed, allowing request");
+                rate_limiter.fail_open(RateLimitCategory::Search)
+            }
+        };
+
     // Handle rate limit result
     match &rate_limit_result {
         RateLimitResult::Blocked {
@@ -650,7 +1436,18 @@ async fn decrypt_data(
                 api_key_hash = %api_key_hash,
                 ban_level = ?ban_level,
                 violation_count = violation_count,
-                "Decrypt request blocked by rate limiter"
+                "Search request blocked by rate limiter"
+            );
+            record_security_event(
+                &state,
+                &config,
+                AuditEventType::RateLimited,
+                &client_ip,
+                serde_json::json!({
+                    "endpoint": "/data/search",
+                    "ban_level": format!("{:?}", ban_level),
+                    "violation_count": violation_count,
+                }),
             );
             let error = GoudChainError::ApiKeyBanned {
                 ban_level: format!("{:?}", ban_level),
@@ -666,54 +1463,2385 @@ async fn decrypt_data(
             warn!(
                 api_key_hash = %api_key_hash,
                 violation_count = violation_count,
-                "Rate limit warning on decrypt operation"
+                "Rate limit warning on search operation"
             );
         }
         RateLimitResult::Allowed { remaining, .. } => {
             info!(
                 api_key_hash = %api_key_hash,
                 remaining = remaining,
-                "Decrypt rate limit check passed"
+                "Search rate limit check passed"
             );
         }
     }
 
     let blockchain_guard = blockchain.read().await;
 
-    // Find collection
-    match blockchain_guard.find_collection(&collection_id, &api_key) {
-        Some(collection) => {
-            // Decrypt metadata and payload
-            match (
-                collection.decrypt_metadata(&api_key),
-                collection.decrypt_payload(&api_key),
-            ) {
-                (Ok(metadata), Ok(data)) => {
-                    let response = DecryptCollectionResponse {
-                        collection_id: collection.collection_id.clone(),
-                        label: metadata["label"].as_str().unwrap_or("unknown").to_string(),
-                        data,
-                        created_at: metadata["created_at"].as_i64().unwrap_or(0),
-                    };
+    // Verify account exists
+    if blockchain_guard
+        .find_account_with_hash(&api_key, Some(api_key_hash.clone()))
+        .is_none()
+    {
+        return Err(GoudChainError::AccountNotFound);
+    }
 
-                    // Audit log
-                    if let Err(e) = audit_logger.log(
-                        &api_key,
-                        AuditEventType::DataDecrypted,
-                        Some(collection.collection_id.clone()),
-                        &client_ip,
-                        serde_json::json!({"success": true}),
-                    ) {
-                        error!(error = %e, "Failed to log data decryption audit event");
-                    }
+    let matches = blockchain_guard.find_collections_by_label_index(&params.label, &api_key);
+    drop(blockchain_guard);
 
-                    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
-                    let response_obj = Json(response).into_response();
-                    Ok(add_rate_limit_headers(response_obj, rate_headers))
+    let include_history = params.include_history.unwrap_or(false);
+    let revisions = Blockchain::annotate_revisions(matches);
+    let result = collection_list_items(revisions, include_history, &api_key, &p2p.blockchain_store);
+
+    // Audit log
+    if let Err(e) = audit_logger.log(
+        &api_key,
+        AuditEventType::DataListed,
+        None,
+        &client_ip,
+        serde_json::json!({"count": result.len(), "search": true}),
+    ) {
+        error!(error = %e, "Failed to log data search audit event");
+    }
+
+    let response = CollectionListResponse {
+        collections: result,
+    };
+    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+    let response_obj = Json(response).into_response();
+    Ok(add_rate_limit_headers(response_obj, rate_headers))
+}
+
+/// Decrypt data from a collection
+///
+/// Decrypts and returns the data content from a specific encrypted collection.
+/// Only the owner (authenticated with the correct API key or session token) can decrypt the data.
+/// This operation is computationally expensive and subject to stricter rate limits.
+/// Requires a unique `X-Nonce` header; replaying a nonce returns 409 Conflict.
+#[utoipa::path(
+    post,
+    path = "/decrypt/{collection_id}",
+    tag = DATA_TAG,
+    params(
+        ("collection_id" = String, Path, description = "Collection ID to decrypt", example = "550e8400-e29b-41d4-a716-446655440000"),
+        DecryptQuery
+    ),
+    security(
+        ("bearer_token" = []),
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "Data decrypted successfully (raw payload with its stored Content-Type when `raw=true`)", body = DecryptCollectionResponse),
+        (status = 401, description = "Missing or invalid authentication, or missing X-Nonce header", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant read access", body = ErrorResponse),
+        (status = 404, description = "Collection not found or access denied", body = ErrorResponse),
+        (status = 410, description = "Collection was pruned from this node", body = ErrorResponse),
+        (status = 409, description = "Nonce already used (replay detected)", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 500, description = "Decryption failed", body = ErrorResponse),
+        (status = 504, description = "Request exceeded REQUEST_TIMEOUT_SECONDS", body = ErrorResponse)
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+async fn decrypt_data(
+    headers: HeaderMap,
+    Path(collection_id): Path<String>,
+    Query(query): Query<DecryptQuery>,
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
+    Extension(state): Extension<SubmitDataState>,
+    Extension(nonce_store): Extension<Arc<NonceStore>>,
+) -> Result<impl IntoResponse> {
+    let audit_logger = &state.audit_logger;
+    let nonce = require_fresh_nonce(&headers, &nonce_store)?;
+
+    // Extract authentication
+    let auth = authenticate(&headers, &config, &state)?;
+    auth.require_scope(TokenScope::Read)?;
+
+    // Support both API keys and session tokens
+    let (api_key, api_key_hash) = match auth {
+        AuthMethod::ApiKey(key) => {
+            let hash =
+                hash_request_api_key(&rate_limiter, &extract_client_ip(&headers), &key).await?;
+            (key, hash)
+        }
+        AuthMethod::SessionToken(claims) => {
+            let key = match decrypt_api_key_from_jwt(&claims.encrypted_api_key, &config) {
+                Ok(k) => k,
+                Err(e) => {
+                    return Err(GoudChainError::Unauthorized(format!(
+                        "Failed to decrypt API key from session token: {}",
+                        e
+                    )));
                 }
-                _ => Err(GoudChainError::DecryptionFailed),
-            }
+            };
+            let hash = claims.api_key_hash;
+            (key, hash)
         }
-        None => Err(GoudChainError::DataNotFound(collection_id)),
+    };
+
+    // Extract client IP for rate limiting
+    let client_ip = extract_client_ip(&headers);
+
+    // Check rate limit (write operation - decryption is expensive)
+    let rate_limit_result =
+        match rate_limiter.check_limit(&api_key_hash, &client_ip, RateLimitCategory::Decrypt) {
+            Ok(result) => result,
+            Err(e) => {
+                error!(error = %e, "Rate limit check failed, allowing request");
+                rate_limiter.fail_open(RateLimitCategory::Decrypt)
+            }
+        };
+
+    // Handle rate limit result
+    match &rate_limit_result {
+        RateLimitResult::Blocked {
+            ban_level,
+            retry_after,
+            violation_count,
+        } => {
+            warn!(
+                api_key_hash = %api_key_hash,
+                ban_level = ?ban_level,
+                violation_count = violation_count,
+                "Decrypt request blocked by rate limiter"
+            );
+            record_security_event(
+                &state,
+                &config,
+                AuditEventType::RateLimited,
+                &client_ip,
+                serde_json::json!({
+                    "endpoint": "/data/decrypt/{collection_id}",
+                    "ban_level": format!("{:?}", ban_level),
+                    "violation_count": violation_count,
+                }),
+            );
+            let error = GoudChainError::ApiKeyBanned {
+                ban_level: format!("{:?}", ban_level),
+                expires_at: chrono::Utc::now().timestamp() + *retry_after as i64,
+            };
+            let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+            let response = error.into_response();
+            return Ok(add_rate_limit_headers(response, rate_headers));
+        }
+        RateLimitResult::Warning {
+            violation_count, ..
+        } => {
+            warn!(
+                api_key_hash = %api_key_hash,
+                violation_count = violation_count,
+                "Rate limit warning on decrypt operation"
+            );
+        }
+        RateLimitResult::Allowed { remaining, .. } => {
+            info!(
+                api_key_hash = %api_key_hash,
+                remaining = remaining,
+                "Decrypt rate limit check passed"
+            );
+        }
+    }
+
+    let blockchain_guard = blockchain.read().await;
+
+    // Verify account exists (a rotated-away key no longer resolves)
+    if blockchain_guard
+        .find_account_with_hash(&api_key, Some(api_key_hash.clone()))
+        .is_none()
+    {
+        return Err(GoudChainError::AccountNotFound);
+    }
+
+    // Find collection
+    match blockchain_guard.find_collection(&collection_id, &api_key)? {
+        Some(collection) => {
+            // Decrypt metadata and payload
+            match (
+                collection.decrypt_metadata(&api_key),
+                collection.decrypt_payload_bytes(&api_key),
+            ) {
+                (Ok(metadata), Ok(data)) => {
+                    let content_type = metadata_content_type(&metadata);
+                    let response_obj = if query.raw.unwrap_or(false) {
+                        raw_payload_response(&content_type, data)?
+                    } else {
+                        let (data, encoding) = decrypted_payload_value(&content_type, data);
+                        Json(DecryptCollectionResponse {
+                            collection_id: collection.collection_id.clone(),
+                            label: metadata["label"].as_str().unwrap_or("unknown").to_string(),
+                            data,
+                            encoding,
+                            content_type,
+                            expires_at: metadata["expires_at"].as_i64(),
+                            shared: false,
+                            created_at: metadata["created_at"].as_i64().unwrap_or(0),
+                        })
+                        .into_response()
+                    };
+
+                    // Audit log
+                    if let Err(e) = audit_logger.log(
+                        &api_key,
+                        AuditEventType::DataDecrypted,
+                        Some(collection.collection_id.clone()),
+                        &client_ip,
+                        serde_json::json!({"success": true}),
+                    ) {
+                        error!(error = %e, "Failed to log data decryption audit event");
+                    }
+                    commit_used_nonce(nonce);
+
+                    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+                    Ok(add_rate_limit_headers(response_obj, rate_headers))
+                }
+                _ => Err(GoudChainError::DecryptionFailed),
+            }
+        }
+        None => {
+            // Not owned by the caller - it may be a grant another account shared with them
+            let shared = blockchain_guard
+                .find_shared_collections(&api_key)
+                .into_iter()
+                .find(|shared| shared.grant_id == collection_id)
+                .ok_or(GoudChainError::DataNotFound(collection_id))?;
+
+            let response_obj = if query.raw.unwrap_or(false) {
+                raw_payload_response(&shared.content_type, shared.data.into_bytes())?
+            } else {
+                let (data, encoding) =
+                    decrypted_payload_value(&shared.content_type, shared.data.into_bytes());
+                Json(DecryptCollectionResponse {
+                    collection_id: shared.grant_id.clone(),
+                    label: shared.label,
+                    data,
+                    encoding,
+                    content_type: shared.content_type,
+                    expires_at: shared.expires_at,
+                    shared: true,
+                    created_at: shared.created_at,
+                })
+                .into_response()
+            };
+
+            if let Err(e) = audit_logger.log(
+                &api_key,
+                AuditEventType::DataDecrypted,
+                Some(shared.grant_id),
+                &client_ip,
+                serde_json::json!({
+                    "success": true,
+                    "shared": true,
+                    "source_collection_id": shared.collection_id,
+                }),
+            ) {
+                error!(error = %e, "Failed to log data decryption audit event");
+            }
+            commit_used_nonce(nonce);
+
+            let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+            Ok(add_rate_limit_headers(response_obj, rate_headers))
+        }
+    }
+}
+
+/// Fetch a collection's encrypted envelope
+///
+/// Returns the collection exactly as stored on chain - ciphertexts, nonce, MAC, salt and label
+/// blind index - without decrypting it, so clients can decrypt locally with the key derived
+/// from their API key. Ownership is still checked, and tombstoned or expired collections are
+/// not returned. Rate-limited as a read.
+#[utoipa::path(
+    get,
+    path = "/{collection_id}/envelope",
+    tag = DATA_TAG,
+    params(
+        ("collection_id" = String, Path, description = "Collection ID", example = "550e8400-e29b-41d4-a716-446655440000")
+    ),
+    security(
+        ("bearer_token" = []),
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "Encrypted envelope retrieved successfully", body = CollectionEnvelopeResponse),
+        (status = 401, description = "Missing or invalid authentication", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant read access", body = ErrorResponse),
+        (status = 404, description = "Collection not found or access denied", body = ErrorResponse),
+        (status = 410, description = "Collection was pruned from this node", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+async fn get_envelope(
+    headers: HeaderMap,
+    Path(collection_id): Path<String>,
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(p2p): Extension<Arc<P2PNode>>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
+    Extension(state): Extension<SubmitDataState>,
+) -> Result<impl IntoResponse> {
+    let audit_logger = &state.audit_logger;
+
+    // Extract authentication
+    let auth = authenticate(&headers, &config, &state)?;
+    auth.require_scope(TokenScope::Read)?;
+
+    // Support both API keys and session tokens
+    let (api_key, api_key_hash) = match auth {
+        AuthMethod::ApiKey(key) => {
+            let hash =
+                hash_request_api_key(&rate_limiter, &extract_client_ip(&headers), &key).await?;
+            (key, hash)
+        }
+        AuthMethod::SessionToken(claims) => {
+            let key = match decrypt_api_key_from_jwt(&claims.encrypted_api_key, &config) {
+                Ok(k) => k,
+                Err(e) => {
+                    return Err(GoudChainError::Unauthorized(format!(
+                        "Failed to decrypt API key from session token: {}",
+                        e
+                    )));
+                }
+            };
+            let hash = claims.api_key_hash;
+            (key, hash)
+        }
+    };
+
+    // Extract client IP for rate limiting
+    let client_ip = extract_client_ip(&headers);
+
+    // Check rate limit (read operation - nothing is decrypted)
+    let rate_limit_result =
+        match rate_limiter.check_limit(&api_key_hash, &client_ip, RateLimitCategory::List) {
+            Ok(result) => result,
+            Err(e) => {
+                error!(error = %e, "Rate limit check failed, allowing request");
+                rate_limiter.fail_open(RateLimitCategory::List)
+            }
+        };
+
+    // Handle rate limit result
+    match &rate_limit_result {
+        RateLimitResult::Blocked {
+            ban_level,
+            retry_after,
+            violation_count,
+        } => {
+            warn!(
+                api_key_hash = %api_key_hash,
+                ban_level = ?ban_level,
+                violation_count = violation_count,
+                "Envelope request blocked by rate limiter"
+            );
+            record_security_event(
+                &state,
+                &config,
+                AuditEventType::RateLimited,
+                &client_ip,
+                serde_json::json!({
+                    "endpoint": "/data/{collection_id}/envelope",
+                    "ban_level": format!("{:?}", ban_level),
+                    "violation_count": violation_count,
+                }),
+            );
+            let error = GoudChainError::ApiKeyBanned {
+                ban_level: format!("{:?}", ban_level),
+                expires_at: chrono::Utc::now().timestamp() + *retry_after as i64,
+            };
+            let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+            let response = error.into_response();
+            return Ok(add_rate_limit_headers(response, rate_headers));
+        }
+        RateLimitResult::Warning {
+            violation_count, ..
+        } => {
+            warn!(
+                api_key_hash = %api_key_hash,
+                violation_count = violation_count,
+                "Rate limit warning on envelope fetch"
+            );
+        }
+        RateLimitResult::Allowed { remaining, .. } => {
+            info!(
+                api_key_hash = %api_key_hash,
+                remaining = remaining,
+                "Envelope rate limit check passed"
+            );
+        }
+    }
+
+    let blockchain_guard = blockchain.read().await;
+
+    // Verify account exists (a rotated-away key no longer resolves)
+    if blockchain_guard
+        .find_account_with_hash(&api_key, Some(api_key_hash.clone()))
+        .is_none()
+    {
+        return Err(GoudChainError::AccountNotFound);
+    }
+
+    let collection = blockchain_guard
+        .find_collection(&collection_id, &api_key)?
+        .ok_or_else(|| GoudChainError::DataNotFound(collection_id.clone()))?;
+    drop(blockchain_guard);
+
+    let response = CollectionEnvelopeResponse {
+        block_number: indexed_block_number(&p2p.blockchain_store, &collection.collection_id),
+        compression: collection.compression.as_str().to_string(),
+        collection_id: collection.collection_id,
+        encrypted_metadata: collection.encrypted_metadata,
+        encrypted_payload: collection.encrypted_payload,
+        mac: collection.mac,
+        nonce: collection.nonce,
+        user_salt: collection.user_salt,
+        signature: collection.signature,
+        public_key: collection.public_key,
+        label_index: collection.label_index,
+        parent_collection_id: collection.parent_collection_id,
+    };
+
+    // Audit log
+    if let Err(e) = audit_logger.log(
+        &api_key,
+        AuditEventType::EnvelopeFetched,
+        Some(response.collection_id.clone()),
+        &client_ip,
+        serde_json::json!({"success": true}),
+    ) {
+        error!(error = %e, "Failed to log envelope fetch audit event");
+    }
+
+    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+    let response_obj = Json(response).into_response();
+    Ok(add_rate_limit_headers(response_obj, rate_headers))
+}
+
+/// Decrypt all data collections
+///
+/// Decrypts and returns the latest revision of every collection owned by the authenticated user in
+/// one call, replacing a `/data/list` call followed by one `/data/decrypt/{id}` call per collection.
+/// Supports `labels` filtering and `page`/`page_size` pagination to bound the response size.
+/// Counts as a single decrypt-group rate-limit hit regardless of how many collections are returned.
+#[utoipa::path(
+    post,
+    path = "/decrypt_all",
+    tag = DATA_TAG,
+    params(DecryptAllQuery),
+    security(
+        ("bearer_token" = []),
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "Data decrypted successfully", body = Vec<DecryptCollectionResponse>),
+        (status = 401, description = "Missing or invalid authentication", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant read access", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 500, description = "Decryption failed", body = ErrorResponse),
+        (status = 504, description = "Request exceeded REQUEST_TIMEOUT_SECONDS", body = ErrorResponse)
+    )
+)]
+async fn decrypt_all(
+    headers: HeaderMap,
+    Query(params): Query<DecryptAllQuery>,
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
+    Extension(state): Extension<SubmitDataState>,
+) -> Result<impl IntoResponse> {
+    let audit_logger = &state.audit_logger;
+
+    // Extract authentication
+    let auth = authenticate(&headers, &config, &state)?;
+    auth.require_scope(TokenScope::Read)?;
+
+    // Support both API keys and session tokens
+    let (api_key, api_key_hash) = match auth {
+        AuthMethod::ApiKey(key) => {
+            let hash =
+                hash_request_api_key(&rate_limiter, &extract_client_ip(&headers), &key).await?;
+            (key, hash)
+        }
+        AuthMethod::SessionToken(claims) => {
+            let key = match decrypt_api_key_from_jwt(&claims.encrypted_api_key, &config) {
+                Ok(k) => k,
+                Err(e) => {
+                    return Err(GoudChainError::Unauthorized(format!(
+                        "Failed to decrypt API key from session token: {}",
+                        e
+                    )));
+                }
+            };
+            let hash = claims.api_key_hash;
+            (key, hash)
+        }
+    };
+
+    // Extract client IP for rate limiting
+    let client_ip = extract_client_ip(&headers);
+
+    // Check rate limit (write operation - one hit for the whole export)
+    let rate_limit_result =
+        match rate_limiter.check_limit(&api_key_hash, &client_ip, RateLimitCategory::Decrypt) {
+            Ok(result) => result,
+            Err(e) => {
+                error!(error = %e, "Rate limit check failed, allowing request");
+                rate_limiter.fail_open(RateLimitCategory::Decrypt)
+            }
+        };
+
+    // Handle rate limit result
+    match &rate_limit_result {
+        RateLimitResult::Blocked {
+            ban_level,
+            retry_after,
+            violation_count,
+        } => {
+            warn!(
+                api_key_hash = %api_key_hash,
+                ban_level = ?ban_level,
+                violation_count = violation_count,
+                "Bulk decrypt request blocked by rate limiter"
+            );
+            record_security_event(
+                &state,
+                &config,
+                AuditEventType::RateLimited,
+                &client_ip,
+                serde_json::json!({
+                    "endpoint": "/data/decrypt_all",
+                    "ban_level": format!("{:?}", ban_level),
+                    "violation_count": violation_count,
+                }),
+            );
+            let error = GoudChainError::ApiKeyBanned {
+                ban_level: format!("{:?}", ban_level),
+                expires_at: chrono::Utc::now().timestamp() + *retry_after as i64,
+            };
+            let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+            let response = error.into_response();
+            return Ok(add_rate_limit_headers(response, rate_headers));
+        }
+        RateLimitResult::Warning {
+            violation_count, ..
+        } => {
+            warn!(
+                api_key_hash = %api_key_hash,
+                violation_count = violation_count,
+                "Rate limit warning on bulk decrypt operation"
+            );
+        }
+        RateLimitResult::Allowed { remaining, .. } => {
+            info!(
+                api_key_hash = %api_key_hash,
+                remaining = remaining,
+                "Bulk decrypt rate limit check passed"
+            );
+        }
+    }
+
+    // Parse query parameters
+    let labels: Option<HashSet<&str>> = params.labels.as_deref().map(|labels| {
+        labels
+            .split(',')
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .collect()
+    });
+    let page = params.page.unwrap_or(0);
+    let page_size = params
+        .page_size
+        .unwrap_or(DECRYPT_ALL_DEFAULT_PAGE_SIZE)
+        .clamp(1, DECRYPT_ALL_MAX_PAGE_SIZE);
+
+    let blockchain_guard = blockchain.read().await;
+
+    // Verify account exists
+    if blockchain_guard
+        .find_account_with_hash(&api_key, Some(api_key_hash.clone()))
+        .is_none()
+    {
+        return Err(GoudChainError::AccountNotFound);
+    }
+
+    // Superseded revisions are left out, matching /data/list without include_history
+    // Filter on metadata first so only the requested page pays for payload decryption
+    let matching: Vec<(EncryptedCollection, serde_json::Value)> = blockchain_guard
+        .find_collection_revisions(&api_key)
+        .into_iter()
+        .filter(|revision| revision.is_head)
+        .filter_map(|revision| {
+            let collection = revision.collection;
+            let metadata = collection.decrypt_metadata(&api_key).ok()?;
+            let label = metadata["label"].as_str()?;
+            if label.starts_with(AUDIT_LABEL_PREFIX) {
+                return None;
+            }
+            if labels
+                .as_ref()
+                .is_some_and(|labels| !labels.contains(label))
+            {
+                return None;
+            }
+            Some((collection, metadata))
+        })
+        .collect();
+    drop(blockchain_guard);
+
+    let mut result = Vec::new();
+    for (collection, metadata) in matching
+        .into_iter()
+        .skip(page.saturating_mul(page_size))
+        .take(page_size)
+    {
+        // Yield between payloads so REQUEST_TIMEOUT_SECONDS can stop a long export
+        tokio::task::yield_now().await;
+        let data = collection
+            .decrypt_payload_bytes(&api_key)
+            .map_err(|_| GoudChainError::DecryptionFailed)?;
+        let content_type = metadata_content_type(&metadata);
+        let (data, encoding) = decrypted_payload_value(&content_type, data);
+        result.push(DecryptCollectionResponse {
+            collection_id: collection.collection_id,
+            label: metadata["label"].as_str().unwrap_or("unknown").to_string(),
+            data,
+            encoding,
+            content_type,
+            expires_at: metadata["expires_at"].as_i64(),
+            shared: false,
+            created_at: metadata["created_at"].as_i64().unwrap_or(0),
+        });
+    }
+
+    // Audit log (single event for the whole export)
+    if let Err(e) = audit_logger.log(
+        &api_key,
+        AuditEventType::DataDecrypted,
+        None,
+        &client_ip,
+        serde_json::json!({"success": true, "count": result.len(), "page": page}),
+    ) {
+        error!(error = %e, "Failed to log bulk decryption audit event");
+    }
+
+    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+    let response_obj = Json(result).into_response();
+    Ok(add_rate_limit_headers(response_obj, rate_headers))
+}
+
+/// Delete a data collection
+///
+/// Logically deletes an encrypted collection by appending a tombstone record to the blockchain.
+/// The original block is never modified (hash linkage is preserved), but the collection is
+/// excluded from list and decrypt results from then on. Only the owner can delete a collection.
+/// Requires direct API key authentication (session tokens not supported for deletion).
+#[utoipa::path(
+    delete,
+    path = "/{collection_id}",
+    tag = DATA_TAG,
+    params(
+        ("collection_id" = String, Path, description = "Collection ID to delete", example = "550e8400-e29b-41d4-a716-446655440000")
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "Collection deleted successfully", body = DeleteCollectionResponse),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant write access", body = ErrorResponse),
+        (status = 404, description = "Collection not found or access denied", body = ErrorResponse),
+        (status = 410, description = "Collection was pruned from this node", body = ErrorResponse),
+        (status = 409, description = "Nonce already used (replay detected)", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+async fn delete_data(
+    headers: HeaderMap,
+    Path(collection_id): Path<String>,
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(p2p): Extension<Arc<P2PNode>>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
+    Extension(state): Extension<SubmitDataState>,
+    Extension(nonce_store): Extension<Arc<NonceStore>>,
+) -> Result<impl IntoResponse> {
+    // Refuse writes while the node is in read-only maintenance mode
+    blockchain.read().await.ensure_writable()?;
+
+    // Extract Authorization and signature headers (needed for verification and forwarding)
+    let auth_header_value = extract_auth_header(&headers);
+    let request_signature = RequestSignature::from_headers(&headers)?;
+    let path = format!("/data/{}", collection_id);
+
+    // Extract authentication
+    let auth = authenticate(&headers, &config, &state)?;
+    auth.require_scope(TokenScope::Write)?;
+
+    // Get API key and hash based on auth method
+    let (api_key, api_key_hash) = match auth {
+        AuthMethod::ApiKey(key) => {
+            let hash =
+                hash_request_api_key(&rate_limiter, &extract_client_ip(&headers), &key).await?;
+            (key, hash)
+        }
+        AuthMethod::SessionToken(_) => {
+            return Err(GoudChainError::Unauthorized(
+                "Direct API key required for data deletion".to_string(),
+            ));
+        }
+    };
+
+    // Extract client IP for rate limiting
+    let client_ip = extract_client_ip(&headers);
+
+    // Check rate limit (write operation)
+    let rate_limit_result =
+        match rate_limiter.check_limit(&api_key_hash, &client_ip, RateLimitCategory::Delete) {
+            Ok(result) => result,
+            Err(e) => {
+                error!(error = %e, "Rate limit check failed, allowing request");
+                rate_limiter.fail_open(RateLimitCategory::Delete)
+            }
+        };
+
+    // Handle rate limit result
+    match &rate_limit_result {
+        RateLimitResult::Blocked {
+            ban_level,
+            retry_after,
+            violation_count,
+        } => {
+            warn!(
+                api_key_hash = %api_key_hash,
+                ban_level = ?ban_level,
+                violation_count = violation_count,
+                "Delete request blocked by rate limiter"
+            );
+            record_security_event(
+                &state,
+                &config,
+                AuditEventType::RateLimited,
+                &client_ip,
+                serde_json::json!({
+                    "endpoint": "/data/{collection_id}",
+                    "ban_level": format!("{:?}", ban_level),
+                    "violation_count": violation_count,
+                }),
+            );
+            let error = GoudChainError::ApiKeyBanned {
+                ban_level: format!("{:?}", ban_level),
+                expires_at: chrono::Utc::now().timestamp() + *retry_after as i64,
+            };
+            let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+            let response = error.into_response();
+            return Ok(add_rate_limit_headers(response, rate_headers));
+        }
+        RateLimitResult::Warning {
+            violation_count, ..
+        } => {
+            warn!(
+                api_key_hash = %api_key_hash,
+                violation_count = violation_count,
+                "Rate limit warning on delete operation"
+            );
+        }
+        RateLimitResult::Allowed { remaining, .. } => {
+            info!(
+                api_key_hash = %api_key_hash,
+                remaining = remaining,
+                "Delete rate limit check passed"
+            );
+        }
+    }
+
+    // Verify account exists and owns the (not yet deleted) collection
+    let blockchain_guard = blockchain.read().await;
+    let account =
+        match blockchain_guard.find_account_with_hash(&api_key, Some(api_key_hash.clone())) {
+            Some(account) => account,
+            None => {
+                return Err(GoudChainError::AccountNotFound);
+            }
+        };
+
+    enforce_request_signature(
+        request_signature.as_ref(),
+        "DELETE",
+        &path,
+        "",
+        &account,
+        &config,
+        &nonce_store,
+    )?;
+    let nonce_reservation = reserve_signature_nonce(request_signature.as_ref(), &nonce_store)?;
+
+    if blockchain_guard
+        .find_collection(&collection_id, &api_key)?
+        .is_none()
+    {
+        return Err(GoudChainError::DataNotFound(collection_id));
+    }
+
+    // Check if this node is the authorized validator
+    let next_block_number = blockchain_guard
+        .chain
+        .last()
+        .map(|b| b.index + 1)
+        .unwrap_or(1);
+    let is_validator = blockchain_guard
+        .validator_config
+        .is_node_authorized(&blockchain_guard.node_id, next_block_number);
+    let node_id = blockchain_guard.node_id.clone();
+    let validator_config = blockchain_guard.validator_config.clone();
+    let signing_key = blockchain_guard.node_signing_key.clone();
+    drop(blockchain_guard);
+
+    if !is_validator {
+        // Forward request to the correct validator
+        let expected_validator = validator_config.get_validator_for_block(next_block_number);
+        warn!(
+            current_node = %node_id,
+            expected_validator = %expected_validator,
+            next_block = next_block_number,
+            "Forwarding data deletion to validator node"
+        );
+
+        return match forward_to_validator(
+            &blockchain,
+            "DELETE",
+            &path,
+            "",
+            "application/json",
+            auth_header_value.as_deref(),
+            &extract_replay_headers(&headers),
+        )
+        .await
+        {
+            Ok((status_code, response_body)) => {
+                info!(status = status_code, "Forwarded data deletion successfully");
+                Ok((
+                    StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK),
+                    [(axum::http::header::CONTENT_TYPE, "application/json")],
+                    response_body,
+                )
+                    .into_response())
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to forward data deletion to validator");
+                Err(e)
+            }
+        };
+    }
+
+    // This node IS the validator - append tombstone and create block
+    let signing_key = signing_key
+        .ok_or_else(|| GoudChainError::Internal("Node signing key not available".to_string()))?;
+
+    let tombstone = EncryptedCollection::new_tombstone(
+        &collection_id,
+        &api_key,
+        api_key_hash.clone(),
+        &signing_key,
+    )
+    .inspect_err(|e| error!(error = %e, "Failed to create tombstone"))?;
+
+    let mut blockchain_guard = blockchain.write().await;
+    blockchain_guard
+        .add_collection(tombstone)
+        .inspect_err(|e| error!(error = %e, "Failed to add tombstone"))?;
+    let block = blockchain_guard
+        .add_block()
+        .inspect_err(|e| error!(error = %e, "Failed to add block"))?;
+
+    if let Err(e) = p2p.blockchain_store.save_block(&block) {
+        error!(error = %e, "Failed to save block to RocksDB");
+    }
+
+    if blockchain_guard.is_checkpoint_index(block.index) {
+        if let Err(e) = p2p
+            .blockchain_store
+            .save_checkpoint(block.index, &block.hash)
+        {
+            error!(error = %e, "Failed to save checkpoint");
+        }
+    }
+
+    let block_index = block.index;
+    drop(blockchain_guard);
+    global_operations_tracker().record(1);
+
+    if let Some(reservation) = nonce_reservation {
+        commit_used_nonce(reservation);
+    }
+
+    // Audit log
+    if let Err(e) = state.audit_logger.log(
+        &api_key,
+        AuditEventType::DataDeleted,
+        Some(collection_id.clone()),
+        &client_ip,
+        serde_json::json!({"block": block_index}),
+    ) {
+        error!(error = %e, "Failed to log data deletion audit event");
+    }
+
+    let response = DeleteCollectionResponse {
+        message: "Collection deleted successfully".to_string(),
+        collection_id: collection_id.clone(),
+        block_number: block_index,
+    };
+    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+    let response_obj = Json(response).into_response();
+
+    let block_arc = Arc::new(block);
+
+    // Broadcast block in background
+    let p2p_clone = Arc::clone(&p2p);
+    let block_ref = Arc::clone(&block_arc);
+    tokio::spawn(async move {
+        p2p_clone.broadcast_block(&block_ref).await;
+    });
+
+    // Broadcast WebSocket events
+    let ws_clone = Arc::clone(&state.ws_broadcaster);
+    let bhash = block_arc.hash.clone();
+    tokio::spawn(async move {
+        ws_clone
+            .broadcast_collection_update(collection_id, api_key_hash, block_index)
+            .await;
+        ws_clone
+            .broadcast_blockchain_update(block_index, bhash)
+            .await;
+    });
+
+    Ok(add_rate_limit_headers(response_obj, rate_headers))
+}
+
+/// Authenticate a sharing write, which (like deletion) requires a direct API key
+async fn require_api_key(
+    headers: &HeaderMap,
+    config: &Config,
+    state: &SubmitDataState,
+    rate_limiter: &Arc<RateLimiter>,
+) -> Result<(zeroize::Zeroizing<Vec<u8>>, String)> {
+    let auth = authenticate(headers, config, state)?;
+    auth.require_scope(TokenScope::Write)?;
+
+    match auth {
+        AuthMethod::ApiKey(key) => {
+            let hash =
+                hash_request_api_key(rate_limiter, &extract_client_ip(headers), &key).await?;
+            Ok((key, hash))
+        }
+        AuthMethod::SessionToken(_) => Err(GoudChainError::Unauthorized(
+            "Direct API key required for sharing".to_string(),
+        )),
+    }
+}
+
+/// Check a sharing write against the rate limiter
+/// A blocked request is returned as the ready-made error response
+fn check_share_rate_limit(
+    rate_limiter: &RateLimiter,
+    state: &SubmitDataState,
+    config: &Config,
+    api_key_hash: &str,
+    client_ip: &str,
+    category: RateLimitCategory,
+    endpoint: &str,
+) -> std::result::Result<RateLimitResult, Box<axum::response::Response>> {
+    let rate_limit_result = match rate_limiter.check_limit(api_key_hash, client_ip, category) {
+        Ok(result) => result,
+        Err(e) => {
+            error!(error = %e, "Rate limit check failed, allowing request");
+            rate_limiter.fail_open(category)
+        }
+    };
+
+    if let RateLimitResult::Blocked {
+        ban_level,
+        retry_after,
+        violation_count,
+    } = &rate_limit_result
+    {
+        warn!(
+            api_key_hash = %api_key_hash,
+            ban_level = ?ban_level,
+            violation_count = violation_count,
+            "Share request blocked by rate limiter"
+        );
+        record_security_event(
+            state,
+            config,
+            AuditEventType::RateLimited,
+            client_ip,
+            serde_json::json!({
+                "endpoint": endpoint,
+                "ban_level": format!("{:?}", ban_level),
+                "violation_count": violation_count,
+            }),
+        );
+        let error = GoudChainError::ApiKeyBanned {
+            ban_level: format!("{:?}", ban_level),
+            expires_at: chrono::Utc::now().timestamp() + *retry_after as i64,
+        };
+        let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+        return Err(Box::new(add_rate_limit_headers(
+            error.into_response(),
+            rate_headers,
+        )));
+    }
+
+    Ok(rate_limit_result)
+}
+
+/// Forward a sharing write to the validator for the next block
+async fn forward_share_to_validator(
+    blockchain: &RwLock<Blockchain>,
+    validator_config: &crate::config::ValidatorConfig,
+    next_block_number: u64,
+    method: &str,
+    path: &str,
+    body: &str,
+    headers: &HeaderMap,
+) -> Result<axum::response::Response> {
+    let expected_validator = validator_config.get_validator_for_block(next_block_number);
+    warn!(
+        expected_validator = %expected_validator,
+        next_block = next_block_number,
+        "Forwarding share request to validator node"
+    );
+
+    let (status_code, response_body) = forward_to_validator(
+        blockchain,
+        method,
+        path,
+        body,
+        "application/json",
+        extract_auth_header(headers).as_deref(),
+        &extract_replay_headers(headers),
+    )
+    .await
+    .inspect_err(|e| error!(error = %e, "Failed to forward share request to validator"))?;
+
+    Ok((
+        StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK),
+        [(axum::http::header::CONTENT_TYPE, "application/json")],
+        response_body,
+    )
+        .into_response())
+}
+
+/// Append a single grant record in a new block, persist it and broadcast it
+async fn commit_share_block(
+    blockchain: &RwLock<Blockchain>,
+    p2p: &Arc<P2PNode>,
+    ws_broadcaster: &Arc<WebSocketBroadcaster>,
+    record: EncryptedCollection,
+) -> Result<u64> {
+    let record_id = record.collection_id.clone();
+    let owner_api_key_hash = record.owner_api_key_hash.clone();
+    let mut blockchain_guard = blockchain.write().await;
+    blockchain_guard
+        .add_collection(record)
+        .inspect_err(|e| error!(error = %e, "Failed to add share record"))?;
+    let block = blockchain_guard
+        .add_block()
+        .inspect_err(|e| error!(error = %e, "Failed to add block"))?;
+    let is_checkpoint = blockchain_guard.is_checkpoint_index(block.index);
+    drop(blockchain_guard);
+
+    if let Err(e) = p2p.blockchain_store.save_block(&block) {
+        error!(error = %e, "Failed to save block to RocksDB");
+    }
+
+    if is_checkpoint {
+        if let Err(e) = p2p
+            .blockchain_store
+            .save_checkpoint(block.index, &block.hash)
+        {
+            error!(error = %e, "Failed to save checkpoint");
+        }
+    }
+    global_operations_tracker().record(1);
+
+    let block_index = block.index;
+    let block_arc = Arc::new(block);
+
+    let p2p_clone = Arc::clone(p2p);
+    let block_ref = Arc::clone(&block_arc);
+    tokio::spawn(async move {
+        p2p_clone.broadcast_block(&block_ref).await;
+    });
+
+    let ws_clone = Arc::clone(ws_broadcaster);
+    let bhash = block_arc.hash.clone();
+    tokio::spawn(async move {
+        ws_clone
+            .broadcast_collection_update(record_id, owner_api_key_hash, block_index)
+            .await;
+        ws_clone
+            .broadcast_blockchain_update(block_index, bhash)
+            .await;
+    });
+
+    Ok(block_index)
+}
+
+/// Get the caller's sharing key
+///
+/// Returns the X25519 public key other accounts pass to `POST /data/{collection_id}/share`
+/// to share a collection with the caller. The key is derived from the API key, so it is
+/// stable and nothing new is stored. Supports both API key and session token authentication.
+#[utoipa::path(
+    get,
+    path = "/sharing_key",
+    tag = DATA_TAG,
+    security(
+        ("bearer_token" = []),
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "Sharing key retrieved successfully", body = SharingKeyResponse),
+        (status = 401, description = "Missing or invalid authentication", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant read access", body = ErrorResponse)
+    )
+)]
+async fn get_sharing_key(
+    headers: HeaderMap,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(state): Extension<SubmitDataState>,
+) -> Result<impl IntoResponse> {
+    let auth = authenticate(&headers, &config, &state)?;
+    auth.require_scope(TokenScope::Read)?;
+
+    let api_key = match auth {
+        AuthMethod::ApiKey(key) => key,
+        AuthMethod::SessionToken(claims) => {
+            decrypt_api_key_from_jwt(&claims.encrypted_api_key, &config).map_err(|e| {
+                GoudChainError::Unauthorized(format!(
+                    "Failed to decrypt API key from session token: {}",
+                    e
+                ))
+            })?
+        }
+    };
+
+    Ok(Json(SharingKeyResponse {
+        sharing_public_key: sharing_public_key_hex(&api_key),
+    }))
+}
+
+/// Share a collection with another account
+///
+/// Seals a read-only copy of the collection to the recipient's sharing key (from their
+/// `GET /data/sharing_key`) and appends it to the blockchain as a `GRANT:` record. The
+/// recipient sees it in `/data/list` marked `shared` and decrypts it by grant ID; no API key
+/// is ever exchanged. The copy is a snapshot: later revisions are not shared automatically.
+/// Requires direct API key authentication.
+#[utoipa::path(
+    post,
+    path = "/{collection_id}/share",
+    tag = DATA_TAG,
+    request_body = ShareCollectionRequest,
+    params(
+        ("collection_id" = String, Path, description = "Collection ID to share", example = "550e8400-e29b-41d4-a716-446655440000")
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 201, description = "Collection shared successfully", body = ShareGrantResponse),
+        (status = 400, description = "Invalid recipient sharing key", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant write access", body = ErrorResponse),
+        (status = 404, description = "Collection not found or access denied", body = ErrorResponse),
+        (status = 410, description = "Collection was pruned from this node", body = ErrorResponse),
+        (status = 409, description = "Nonce already used (replay detected)", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+async fn share_data(
+    headers: HeaderMap,
+    Path(collection_id): Path<String>,
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(p2p): Extension<Arc<P2PNode>>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
+    Extension(state): Extension<SubmitDataState>,
+    Extension(nonce_store): Extension<Arc<NonceStore>>,
+    body: String,
+) -> Result<impl IntoResponse> {
+    // Refuse writes while the node is in read-only maintenance mode
+    blockchain.read().await.ensure_writable()?;
+
+    let request_signature = RequestSignature::from_headers(&headers)?;
+    let path = format!("/data/{}/share", collection_id);
+    let request: ShareCollectionRequest = parse_json_body(&body)?;
+    validate_sharing_public_key(&request.recipient_public_key)?;
+
+    let (api_key, api_key_hash) = require_api_key(&headers, &config, &state, &rate_limiter).await?;
+    let client_ip = extract_client_ip(&headers);
+    let rate_limit_result = match check_share_rate_limit(
+        &rate_limiter,
+        &state,
+        &config,
+        &api_key_hash,
+        &client_ip,
+        RateLimitCategory::Submit,
+        "/data/{collection_id}/share",
+    ) {
+        Ok(result) => result,
+        Err(response) => return Ok(*response),
+    };
+
+    let blockchain_guard = blockchain.read().await;
+    let account = blockchain_guard
+        .find_account_with_hash(&api_key, Some(api_key_hash.clone()))
+        .ok_or(GoudChainError::AccountNotFound)?;
+
+    enforce_request_signature(
+        request_signature.as_ref(),
+        "POST",
+        &path,
+        &body,
+        &account,
+        &config,
+        &nonce_store,
+    )?;
+    let nonce_reservation = reserve_signature_nonce(request_signature.as_ref(), &nonce_store)?;
+
+    let source = blockchain_guard
+        .find_collection(&collection_id, &api_key)?
+        .ok_or_else(|| GoudChainError::DataNotFound(collection_id.clone()))?;
+
+    let next_block_number = blockchain_guard
+        .chain
+        .last()
+        .map(|b| b.index + 1)
+        .unwrap_or(1);
+    let is_validator = blockchain_guard
+        .validator_config
+        .is_node_authorized(&blockchain_guard.node_id, next_block_number);
+    let validator_config = blockchain_guard.validator_config.clone();
+    let signing_key = blockchain_guard.node_signing_key.clone();
+    drop(blockchain_guard);
+
+    if !is_validator {
+        return forward_share_to_validator(
+            &blockchain,
+            &validator_config,
+            next_block_number,
+            "POST",
+            &path,
+            &body,
+            &headers,
+        )
+        .await;
+    }
+
+    let signing_key = signing_key
+        .ok_or_else(|| GoudChainError::Internal("Node signing key not available".to_string()))?;
+    let grant = EncryptedCollection::new_grant(
+        &source,
+        &request.recipient_public_key,
+        &api_key,
+        api_key_hash,
+        &signing_key,
+    )
+    .inspect_err(|e| error!(error = %e, "Failed to create share grant"))?;
+    let grant_id = grant.collection_id.clone();
+
+    let block_index = commit_share_block(&blockchain, &p2p, &state.ws_broadcaster, grant).await?;
+
+    if let Some(reservation) = nonce_reservation {
+        commit_used_nonce(reservation);
+    }
+
+    if let Err(e) = state.audit_logger.log(
+        &api_key,
+        AuditEventType::DataShared,
+        Some(collection_id.clone()),
+        &client_ip,
+        serde_json::json!({"grant_id": grant_id, "block": block_index}),
+    ) {
+        error!(error = %e, "Failed to log data share audit event");
+    }
+
+    let response = ShareGrantResponse {
+        message: "Collection shared successfully".to_string(),
+        grant_id,
+        collection_id,
+        block_number: block_index,
+    };
+    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+    let response_obj = (StatusCode::CREATED, Json(response)).into_response();
+    Ok(add_rate_limit_headers(response_obj, rate_headers))
+}
+
+/// Revoke a collection share
+///
+/// Appends a tombstone for the grant that is also sealed to its recipient, so the shared copy
+/// disappears from the recipient's list and decrypt results. As with deletion, the grant block
+/// itself is never modified. Only the account that created the grant can revoke it.
+/// Requires direct API key authentication.
+#[utoipa::path(
+    delete,
+    path = "/{collection_id}/share/{grant_id}",
+    tag = DATA_TAG,
+    params(
+        ("collection_id" = String, Path, description = "Shared collection ID", example = "550e8400-e29b-41d4-a716-446655440000"),
+        ("grant_id" = String, Path, description = "Grant ID returned when sharing", example = "750e8400-e29b-41d4-a716-446655440000")
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "Share revoked successfully", body = ShareGrantResponse),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 403, description = "Session token scope does not grant write access", body = ErrorResponse),
+        (status = 404, description = "Grant not found, already revoked or access denied", body = ErrorResponse),
+        (status = 409, description = "Nonce already used (replay detected)", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+async fn revoke_share(
+    headers: HeaderMap,
+    Path((collection_id, grant_id)): Path<(String, String)>,
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(p2p): Extension<Arc<P2PNode>>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
+    Extension(state): Extension<SubmitDataState>,
+    Extension(nonce_store): Extension<Arc<NonceStore>>,
+) -> Result<impl IntoResponse> {
+    // Refuse writes while the node is in read-only maintenance mode
+    blockchain.read().await.ensure_writable()?;
+
+    let request_signature = RequestSignature::from_headers(&headers)?;
+    let path = format!("/data/{}/share/{}", collection_id, grant_id);
+
+    let (api_key, api_key_hash) = require_api_key(&headers, &config, &state, &rate_limiter).await?;
+    let client_ip = extract_client_ip(&headers);
+    let rate_limit_result = match check_share_rate_limit(
+        &rate_limiter,
+        &state,
+        &config,
+        &api_key_hash,
+        &client_ip,
+        RateLimitCategory::Delete,
+        "/data/{collection_id}/share/{grant_id}",
+    ) {
+        Ok(result) => result,
+        Err(response) => return Ok(*response),
+    };
+
+    let blockchain_guard = blockchain.read().await;
+    let account = blockchain_guard
+        .find_account_with_hash(&api_key, Some(api_key_hash.clone()))
+        .ok_or(GoudChainError::AccountNotFound)?;
+
+    enforce_request_signature(
+        request_signature.as_ref(),
+        "DELETE",
+        &path,
+        "",
+        &account,
+        &config,
+        &nonce_store,
+    )?;
+    let nonce_reservation = reserve_signature_nonce(request_signature.as_ref(), &nonce_store)?;
+
+    // The grant must belong to the collection named in the path
+    let grant_label = format!("{}{}", GRANT_LABEL_PREFIX, collection_id);
+    let grant = blockchain_guard
+        .find_grant(&grant_id, &api_key)
+        .filter(|grant| grant.matches_label(&api_key, &grant_label))
+        .ok_or_else(|| GoudChainError::DataNotFound(grant_id.clone()))?;
+
+    let next_block_number = blockchain_guard
+        .chain
+        .last()
+        .map(|b| b.index + 1)
+        .unwrap_or(1);
+    let is_validator = blockchain_guard
+        .validator_config
+        .is_node_authorized(&blockchain_guard.node_id, next_block_number);
+    let validator_config = blockchain_guard.validator_config.clone();
+    let signing_key = blockchain_guard.node_signing_key.clone();
+    drop(blockchain_guard);
+
+    if !is_validator {
+        return forward_share_to_validator(
+            &blockchain,
+            &validator_config,
+            next_block_number,
+            "DELETE",
+            &path,
+            "",
+            &headers,
+        )
+        .await;
+    }
+
+    let signing_key = signing_key
+        .ok_or_else(|| GoudChainError::Internal("Node signing key not available".to_string()))?;
+    let revocation =
+        EncryptedCollection::new_grant_revocation(&grant, &api_key, api_key_hash, &signing_key)
+            .inspect_err(|e| error!(error = %e, "Failed to create grant revocation"))?
+            .ok_or_else(|| GoudChainError::DataNotFound(grant_id.clone()))?;
+
+    let block_index =
+        commit_share_block(&blockchain, &p2p, &state.ws_broadcaster, revocation).await?;
+
+    if let Some(reservation) = nonce_reservation {
+        commit_used_nonce(reservation);
+    }
+
+    if let Err(e) = state.audit_logger.log(
+        &api_key,
+        AuditEventType::ShareRevoked,
+        Some(collection_id.clone()),
+        &client_ip,
+        serde_json::json!({"grant_id": grant_id, "block": block_index}),
+    ) {
+        error!(error = %e, "Failed to log share revocation audit event");
+    }
+
+    let response = ShareGrantResponse {
+        message: "Share revoked successfully".to_string(),
+        grant_id,
+        collection_id,
+        block_number: block_index,
+    };
+    let rate_headers = rate_limiter.create_headers(&rate_limit_result);
+    let response_obj = Json(response).into_response();
+    Ok(add_rate_limit_headers(response_obj, rate_headers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::auth::{generate_session_token, SessionLifetime};
+    use crate::api::internal_client::forward_request_with_headers;
+    use crate::api::WebSocketBroadcaster;
+    use crate::config::{RateLimitConfig, ValidatorConfig};
+    use crate::constants::MAX_LABEL_LENGTH;
+    use crate::crypto::{
+        encode_api_key, generate_api_key, generate_signing_key, get_public_key_hex,
+        hash_api_key_hex, sign_message,
+    };
+    use crate::domain::UserAccount;
+    use crate::storage::{AuditLogger, IdempotencyStore, RateLimitStore, TokenRevocationStore};
+    use chrono::Utc;
+    use ed25519_dalek::SigningKey;
+    use serde_json::json;
+    use zeroize::Zeroizing;
+
+    /// Handler test config with request signatures enforced
+    fn test_config() -> Config {
+        Config {
+            require_request_signatures: true,
+            ..Config::for_tests()
+        }
+    }
+
+    /// Serve the data routes on a local port with an account registered for signed requests
+    /// Returns the node address, the account API key and its request signing key
+    async fn start_node(data_dir: &tempfile::TempDir) -> (String, Zeroizing<Vec<u8>>, SigningKey) {
+        start_node_with_config(data_dir, test_config()).await
+    }
+
+    async fn start_node_with_config(
+        data_dir: &tempfile::TempDir,
+        config: Config,
+    ) -> (String, Zeroizing<Vec<u8>>, SigningKey) {
+        let validator_config = ValidatorConfig::single("node1");
+        let store = Arc::new(BlockchainStore::new(data_dir.path()).unwrap());
+        let mut blockchain =
+            Blockchain::new("node1".to_string(), validator_config.clone()).unwrap();
+
+        let api_key = generate_api_key();
+        let request_key = generate_signing_key();
+        let account = UserAccount::new(
+            &api_key,
+            &generate_signing_key(),
+            None,
+            Some(get_public_key_hex(&request_key)),
+        )
+        .unwrap();
+        blockchain
+            .add_account_with_key(account, api_key.clone())
+            .unwrap();
+        blockchain.add_block().unwrap();
+        blockchain.read_only = config.read_only;
+        blockchain.min_block_interval_seconds = config.min_block_interval_seconds;
+        blockchain.block_seal_threshold = config.block_seal_threshold;
+
+        let config = Arc::new(config);
+
+        let blockchain = Arc::new(RwLock::new(blockchain));
+        let p2p = Arc::new(P2PNode::new(
+            Arc::clone(&blockchain),
+            Arc::clone(&store),
+            vec![],
+            None,
+            crate::network::P2PTransport::new(None).unwrap(),
+            None,
+            None,
+        ));
+        let rate_limiter = Arc::new(RateLimiter::new(
+            Arc::new(RateLimitStore::new(store.get_db())),
+            vec![hash_api_key_hex(&api_key)],
+            vec![],
+            RateLimitConfig::default(),
+        ));
+        let nonce_store = Arc::new(NonceStore::new(store.get_db()));
+        let state = SubmitDataState {
+            audit_logger: AuditLogger::new(Arc::clone(&blockchain), Arc::clone(&store), None, None),
+            ws_broadcaster: Arc::new(WebSocketBroadcaster::new()),
+            token_revocations: Arc::new(TokenRevocationStore::new(store.get_db())),
+        };
+
+        let (router, _) = OpenApiRouter::new()
+            .nest("/data", router(&config))
+            .nest("/v1/data", router(&config))
+            .layer(Extension(blockchain))
+            .layer(Extension(p2p))
+            .layer(DefaultBodyLimit::max(config.max_request_body_bytes()))
+            .layer(Extension(config))
+            .layer(Extension(rate_limiter))
+            .layer(Extension(nonce_store))
+            .layer(Extension(Arc::new(IdempotencyStore::new(store.get_db()))))
+            .layer(Extension(state))
+            .split_for_parts();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        (addr, api_key, request_key)
+    }
+
+    /// Send a signed POST /data/submit request and return the response status
+    async fn signed_submit(
+        addr: &str,
+        api_key: &[u8],
+        request_key: &SigningKey,
+        body: &str,
+        nonce: &str,
+    ) -> u16 {
+        signed_submit_with_body(addr, api_key, request_key, body, nonce)
+            .await
+            .0
+    }
+
+    /// Send a signed POST /data/submit request and return the status with the parsed body
+    async fn signed_submit_with_body(
+        addr: &str,
+        api_key: &[u8],
+        request_key: &SigningKey,
+        body: &str,
+        nonce: &str,
+    ) -> (u16, serde_json::Value) {
+        let timestamp = Utc::now().timestamp();
+        let message = format!("POST/data/submit{}{}{}", body, nonce, timestamp);
+        let auth = format!("Bearer {}", encode_api_key(api_key));
+        let headers = [
+            ("X-Signature", sign_message(message.as_bytes(), request_key)),
+            ("X-Nonce", nonce.to_string()),
+            ("X-Timestamp", timestamp.to_string()),
+        ];
+
+        let (status, response) = forward_request_with_headers(
+            addr,
+            "POST",
+            "/data/submit",
+            body,
+            "application/json",
+            Some(&auth),
+            &headers,
+        )
+        .await
+        .unwrap();
+        (status, serde_json::from_str(&response).unwrap_or_default())
+    }
+
+    #[test]
+    fn test_decrypted_payload_value_follows_content_type() {
+        let (value, encoding) =
+            decrypted_payload_value("application/json; charset=utf-8", br#"{"a":1}"#.to_vec());
+        assert_eq!(value, serde_json::json!({"a": 1}));
+        assert_eq!(encoding, "json");
+
+        // Invalid stored JSON falls back to the raw text
+        let (value, encoding) = decrypted_payload_value(DEFAULT_CONTENT_TYPE, b"{oops".to_vec());
+        assert_eq!(value, "{oops");
+        assert_eq!(encoding, "text");
+
+        let (value, encoding) = decrypted_payload_value(BINARY_CONTENT_TYPE, b"AAEC".to_vec());
+        assert_eq!(value, "AAEC");
+        assert_eq!(encoding, "base64");
+
+        let (value, encoding) = decrypted_payload_value("text/plain", "héllo".as_bytes().to_vec());
+        assert_eq!(value, "héllo");
+        assert_eq!(encoding, "text");
+
+        // Non-UTF-8 bytes survive the round trip as base64
+        let (value, encoding) = decrypted_payload_value("text/plain", vec![0xff, 0xfe]);
+        assert_eq!(value, "//4=");
+        assert_eq!(encoding, "base64");
+    }
+
+    #[tokio::test]
+    async fn test_signed_submit_under_v1_prefix() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, request_key) = start_node(&data_dir).await;
+        let auth = format!("Bearer {}", encode_api_key(&api_key));
+
+        // Sign the path as requested, then the unversioned alias, both sent to /v1
+        for (i, signed_path) in ["/v1/data/submit", "/data/submit"].into_iter().enumerate() {
+            let body = format!(r#"{{"label":"v1-{}","data":"x"}}"#, i);
+            let nonce = uuid::Uuid::new_v4().to_string();
+            let timestamp = Utc::now().timestamp();
+            let message = format!("POST{}{}{}{}", signed_path, body, nonce, timestamp);
+            let headers = [
+                (
+                    "X-Signature",
+                    sign_message(message.as_bytes(), &request_key),
+                ),
+                ("X-Nonce", nonce),
+                ("X-Timestamp", timestamp.to_string()),
+            ];
+
+            let (status, _) = forward_request_with_headers(
+                &addr,
+                "POST",
+                "/v1/data/submit",
+                &body,
+                "application/json",
+                Some(&auth),
+                &headers,
+            )
+            .await
+            .unwrap();
+            assert_eq!(status, 201, "signed path {}", signed_path);
+        }
+    }
+
+    /// Send a signed POST /data/submit with an Idempotency-Key (fresh nonce each time, like a client retry)
+    async fn idempotent_submit(
+        addr: &str,
+        api_key: &[u8],
+        request_key: &SigningKey,
+        body: &str,
+        idempotency_key: &str,
+    ) -> (u16, serde_json::Value) {
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let timestamp = Utc::now().timestamp();
+        let message = format!("POST/data/submit{}{}{}", body, nonce, timestamp);
+        let auth = format!("Bearer {}", encode_api_key(api_key));
+        let headers = [
+            ("X-Signature", sign_message(message.as_bytes(), request_key)),
+            ("X-Nonce", nonce),
+            ("X-Timestamp", timestamp.to_string()),
+            ("Idempotency-Key", idempotency_key.to_string()),
+        ];
+
+        let (status, response) = forward_request_with_headers(
+            addr,
+            "POST",
+            "/data/submit",
+            body,
+            "application/json",
+            Some(&auth),
+            &headers,
+        )
+        .await
+        .unwrap();
+        (status, serde_json::from_str(&response).unwrap_or_default())
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_replays_original_submit() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, request_key) = start_node(&data_dir).await;
+        let body = r#"{"label":"invoice","data":"{\"amount\":42}"}"#;
+
+        let (status, first) =
+            idempotent_submit(&addr, &api_key, &request_key, body, "retry-1").await;
+        assert_eq!(status, 201);
+
+        // The retry gets the same collection back instead of a second block
+        let (status, second) =
+            idempotent_submit(&addr, &api_key, &request_key, body, "retry-1").await;
+        assert_eq!(status, 201);
+        assert_eq!(first, second);
+
+        // Reusing the key for a different body is refused
+        let other = r#"{"label":"invoice","data":"{\"amount\":43}"}"#;
+        let (status, error) =
+            idempotent_submit(&addr, &api_key, &request_key, other, "retry-1").await;
+        assert_eq!(status, 422);
+        assert_eq!(error["code"], "IDEMPOTENCY_KEY_MISMATCH");
+
+        // A fresh key stores a new collection
+        let (status, third) =
+            idempotent_submit(&addr, &api_key, &request_key, body, "retry-2").await;
+        assert_eq!(status, 201);
+        assert_ne!(first["collection_id"], third["collection_id"]);
+    }
+
+    // Multi-threaded so both revisions are really in flight at once
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_concurrent_revisions_of_same_version_conflict() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, request_key) = start_node(&data_dir).await;
+
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let (status, created) = signed_submit_with_body(
+            &addr,
+            &api_key,
+            &request_key,
+            r#"{"label":"doc","data":"{\"v\":1}"}"#,
+            &nonce,
+        )
+        .await;
+        assert_eq!(status, 201);
+        let first_id = created["collection_id"].as_str().unwrap().to_string();
+
+        // Two clients edit version 1 at the same time: only one revision may land on it
+        let revise = |text: &'static str| {
+            let body = format!(
+                r#"{{"label":"doc","data":"{{\"v\":\"{}\"}}","parent_collection_id":"{}","expected_parent_version":1}}"#,
+                text, first_id
+            );
+            let (addr, api_key, request_key) = (addr.clone(), api_key.clone(), request_key.clone());
+            async move {
+                let nonce = uuid::Uuid::new_v4().to_string();
+                signed_submit_with_body(&addr, &api_key, &request_key, &body, &nonce).await
+            }
+        };
+        let (a, b) = tokio::join!(revise("a"), revise("b"));
+
+        let (accepted, rejected) = if a.0 == 201 { (a, b) } else { (b, a) };
+        assert_eq!(accepted.0, 201);
+        assert_eq!(rejected.0, 409);
+        assert_eq!(rejected.1["code"], "REVISION_CONFLICT");
+        assert_eq!(rejected.1["details"]["head_version"], 2);
+        assert_eq!(
+            rejected.1["details"]["head_collection_id"],
+            accepted.1["collection_id"]
+        );
+
+        // Retrying on the reported head succeeds
+        let head_id = accepted.1["collection_id"].as_str().unwrap();
+        let body = format!(
+            r#"{{"label":"doc","data":"{{\"v\":\"merged\"}}","parent_collection_id":"{}","expected_parent_version":2}}"#,
+            head_id
+        );
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let retried = signed_submit(&addr, &api_key, &request_key, &body, &nonce).await;
+        assert_eq!(retried, 201);
+    }
+
+    #[tokio::test]
+    async fn test_unique_label_rejects_second_holder() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, request_key) = start_node(&data_dir).await;
+        let submit = |body: String| {
+            let (addr, api_key, request_key) = (addr.clone(), api_key.clone(), request_key.clone());
+            async move {
+                let nonce = uuid::Uuid::new_v4().to_string();
+                signed_submit_with_body(&addr, &api_key, &request_key, &body, &nonce).await
+            }
+        };
+
+        let (status, created) =
+            submit(r#"{"label":"settings","data":"{}","unique_label":true}"#.to_string()).await;
+        assert_eq!(status, 201);
+        let holder_id = created["collection_id"].as_str().unwrap().to_string();
+
+        let (status, rejected) =
+            submit(r#"{"label":"settings","data":"{}","unique_label":true}"#.to_string()).await;
+        assert_eq!(status, 409);
+        assert_eq!(rejected["code"], "LABEL_CONFLICT");
+        assert_eq!(rejected["details"]["collection_id"], holder_id.as_str());
+
+        // Without the flag duplicates stay allowed, and revising the holder keeps its label
+        let (status, _) = submit(r#"{"label":"other","data":"{}"}"#.to_string()).await;
+        assert_eq!(status, 201);
+        let (status, _) = submit(r#"{"label":"other","data":"{}"}"#.to_string()).await;
+        assert_eq!(status, 201);
+        let (status, _) = submit(format!(
+            r#"{{"label":"settings","data":"{{}}","unique_label":true,"parent_collection_id":"{}"}}"#,
+            holder_id
+        ))
+        .await;
+        assert_eq!(status, 201);
+    }
+
+    #[tokio::test]
+    async fn test_replayed_submit_returns_conflict() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, request_key) = start_node(&data_dir).await;
+        let body = r#"{"label":"replay-test","data":"{\"secret\":1}"}"#;
+        let nonce = uuid::Uuid::new_v4().to_string();
+
+        let first = signed_submit(&addr, &api_key, &request_key, body, &nonce).await;
+        assert_eq!(first, 201);
+
+        let replay = signed_submit(&addr, &api_key, &request_key, body, &nonce).await;
+        assert_eq!(replay, 409);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_submits_with_one_nonce_store_once() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, request_key) = start_node(&data_dir).await;
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let first_body = r#"{"label":"race-1","data":"{}"}"#;
+        let second_body = r#"{"label":"race-2","data":"{}"}"#;
+
+        // Sent together: without the reservation both would pass the replay check and be stored
+        let (first, second) = tokio::join!(
+            signed_submit(&addr, &api_key, &request_key, first_body, &nonce),
+            signed_submit(&addr, &api_key, &request_key, second_body, &nonce),
+        );
+
+        let statuses = [first, second];
+        assert_eq!(
+            statuses
+                .iter()
+                .filter(|status| (200..300).contains(*status))
+                .count(),
+            1
+        );
+        assert!(statuses.contains(&409));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_node_refuses_submit() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            read_only: true,
+            ..test_config()
+        };
+        let (addr, api_key, request_key) = start_node_with_config(&data_dir, config).await;
+        let body = r#"{"label":"notes","data":"{}"}"#;
+
+        let (status, response) = signed_submit_with_body(
+            &addr,
+            &api_key,
+            &request_key,
+            body,
+            &uuid::Uuid::new_v4().to_string(),
+        )
+        .await;
+        assert_eq!(status, 503);
+        assert_eq!(response["code"], "READ_ONLY");
+    }
+
+    #[tokio::test]
+    async fn test_coalesced_submits_share_a_block() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            min_block_interval_seconds: 3600,
+            block_seal_threshold: 2,
+            ..test_config()
+        };
+        let (addr, api_key, request_key) = start_node_with_config(&data_dir, config).await;
+
+        // The interval hasn't passed since the account's block: the first submit is queued
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let (status, queued) = signed_submit_with_body(
+            &addr,
+            &api_key,
+            &request_key,
+            r#"{"label":"first","data":"{}"}"#,
+            &nonce,
+        )
+        .await;
+        assert_eq!(status, 202);
+        assert_eq!(queued["pending"], true);
+        assert_eq!(queued["block_number"], 2);
+
+        // The second reaches the seal threshold and seals both into that block
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let (status, sealed) = signed_submit_with_body(
+            &addr,
+            &api_key,
+            &request_key,
+            r#"{"label":"second","data":"{}"}"#,
+            &nonce,
+        )
+        .await;
+        assert_eq!(status, 201);
+        assert_eq!(sealed["pending"], false);
+        assert_eq!(sealed["block_number"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_submit_writes_nothing() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, request_key) = start_node(&data_dir).await;
+        let body = r#"{"label":"preview","data":"{\"secret\":1}"}"#;
+        let nonce = uuid::Uuid::new_v4().to_string();
+
+        let timestamp = Utc::now().timestamp();
+        let message = format!("POST/data/submit{}{}{}", body, nonce, timestamp);
+        let auth = format!("Bearer {}", encode_api_key(&api_key));
+        let headers = [
+            (
+                "X-Signature",
+                sign_message(message.as_bytes(), &request_key),
+            ),
+            ("X-Nonce", nonce.clone()),
+            ("X-Timestamp", timestamp.to_string()),
+        ];
+        let (status, response) = forward_request_with_headers(
+            &addr,
+            "POST",
+            "/data/submit?dry_run=true",
+            body,
+            "application/json",
+            Some(&auth),
+            &headers,
+        )
+        .await
+        .unwrap();
+        let preview: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(status, 200);
+        assert!(preview["collection_id"].is_string());
+        assert!(preview["encrypted_size_bytes"].as_u64().unwrap() > body.len() as u64);
+
+        // No block was produced and the nonce is still unused
+        let (status, stored) =
+            signed_submit_with_body(&addr, &api_key, &request_key, body, &nonce).await;
+        assert_eq!(status, 201);
+        assert_eq!(stored["block_number"], 2);
+        assert_ne!(stored["collection_id"], preview["collection_id"]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_submit_does_not_burn_nonce() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, request_key) = start_node(&data_dir).await;
+        let nonce = uuid::Uuid::new_v4().to_string();
+
+        // Invalid label fails validation after the nonce check
+        let invalid = r#"{"label":"","data":"{}"}"#;
+        let failed = signed_submit(&addr, &api_key, &request_key, invalid, &nonce).await;
+        assert_eq!(failed, 422);
+
+        let body = r#"{"label":"retry","data":"{}"}"#;
+        let retried = signed_submit(&addr, &api_key, &request_key, body, &nonce).await;
+        assert_eq!(retried, 201);
+    }
+
+    #[tokio::test]
+    async fn test_binary_submission_requires_base64() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, request_key) = start_node(&data_dir).await;
+
+        let invalid =
+            r#"{"label":"photo","data":"not base64!","content_type":"application/octet-stream"}"#;
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let rejected = signed_submit(&addr, &api_key, &request_key, invalid, &nonce).await;
+        assert_eq!(rejected, 422);
+
+        // Binary payloads are not JSON, so only the base64 check applies
+        let valid =
+            r#"{"label":"photo","data":"aGVsbG8=","content_type":"application/octet-stream"}"#;
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let accepted = signed_submit(&addr, &api_key, &request_key, valid, &nonce).await;
+        assert_eq!(accepted, 201);
+
+        let bad_type = r#"{"label":"photo","data":"{}","content_type":"not-a-type"}"#;
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let rejected = signed_submit(&addr, &api_key, &request_key, bad_type, &nonce).await;
+        assert_eq!(rejected, 422);
+    }
+
+    #[tokio::test]
+    async fn test_submit_rejects_past_expiry() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, request_key) = start_node(&data_dir).await;
+
+        let past = Utc::now().timestamp() - 60;
+        let body = format!(r#"{{"label":"otp","data":"{{}}","expires_at":{}}}"#, past);
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let rejected = signed_submit(&addr, &api_key, &request_key, &body, &nonce).await;
+        assert_eq!(rejected, 422);
+
+        let future = Utc::now().timestamp() + 3600;
+        let body = format!(r#"{{"label":"otp","data":"{{}}","expires_at":{}}}"#, future);
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let accepted = signed_submit(&addr, &api_key, &request_key, &body, &nonce).await;
+        assert_eq!(accepted, 201);
+    }
+
+    #[tokio::test]
+    async fn test_oversize_bodies_are_rejected_before_handlers() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            max_payload_bytes: 1000,
+            ..test_config()
+        };
+        let (addr, _, _) = start_node_with_config(&data_dir, config).await;
+
+        // No credentials or nonce: any handler answer is a 4xx other than 413,
+        // so 413 means the body was refused before the handler ran
+        let post = |path: &'static str, size: usize| {
+            let addr = addr.clone();
+            async move {
+                let body = format!(r#"{{"padding":"{}"}}"#, "x".repeat(size));
+                forward_request_with_headers(
+                    &addr,
+                    "POST",
+                    path,
+                    &body,
+                    "application/json",
+                    None,
+                    &[],
+                )
+                .await
+                .unwrap()
+                .0
+            }
+        };
+
+        assert_eq!(post("/data/submit", 3000).await, 413);
+        assert_ne!(post("/data/submit", 1500).await, 413);
+
+        // Batches get the larger limit
+        assert_ne!(post("/data/submit_batch", 3000).await, 413);
+        assert_eq!(post("/data/submit_batch", 5000).await, 413);
+    }
+
+    #[test]
+    fn test_validate_uses_configured_payload_limit() {
+        let request: SubmitDataRequest =
+            serde_json::from_str(r#"{"label":"big","data":"{\"k\":\"0123456789\"}"}"#).unwrap();
+        assert!(request
+            .validate(crate::constants::DEFAULT_MAX_PAYLOAD_BYTES)
+            .is_ok());
+
+        match request.validate(8) {
+            Err(GoudChainError::ValidationFailed { errors }) => assert_eq!(
+                errors,
+                vec![FieldError::new("data", "exceeds 8 bytes").with_limit(8)]
+            ),
+            other => panic!("expected ValidationFailed, got {:?}", other),
+        }
+    }
+
+    fn submit_request(body: serde_json::Value) -> SubmitDataRequest {
+        serde_json::from_value(body).unwrap()
+    }
+
+    /// Fields and limits reported for a request breaking one rule at a time
+    fn violations(body: serde_json::Value) -> Vec<(String, Option<usize>)> {
+        submit_request(body)
+            .field_errors(1024)
+            .into_iter()
+            .map(|error| (error.field, error.limit))
+            .collect()
+    }
+
+    #[test]
+    fn test_each_validation_rule_reports_its_field() {
+        let field = |name: &str, limit: Option<usize>| vec![(name.to_string(), limit)];
+
+        assert!(violations(json!({"label": "ok", "data": "{}"})).is_empty());
+        assert_eq!(
+            violations(json!({"label": "", "data": "{}"})),
+            field("label", None)
+        );
+        assert_eq!(
+            violations(json!({"label": "bad\nlabel", "data": "{}"})),
+            field("label", None)
+        );
+        assert_eq!(
+            violations(json!({"label": "l".repeat(MAX_LABEL_LENGTH + 1), "data": "{}"})),
+            field("label", Some(MAX_LABEL_LENGTH))
+        );
+        assert_eq!(
+            violations(json!({"label": "ok", "data": "{}", "content_type": "not a type"})),
+            field("content_type", None)
+        );
+        assert_eq!(
+            violations(json!({"label": "ok", "data": "{not json"})),
+            field("data", None)
+        );
+        let deep = format!("{}{}", "[".repeat(12), "]".repeat(12));
+        assert_eq!(
+            violations(json!({"label": "ok", "data": deep})),
+            field("data", Some(10))
+        );
+        assert_eq!(
+            violations(
+                json!({"label": "ok", "data": "!!", "content_type": "application/octet-stream"})
+            ),
+            field("data", None)
+        );
+        assert_eq!(
+            violations(json!({"label": "ok", "data": format!("\"{}\"", "x".repeat(1024))})),
+            field("data", Some(1024))
+        );
+        assert_eq!(
+            violations(json!({"label": "ok", "data": "{}", "expected_parent_version": 2})),
+            field("expected_parent_version", None)
+        );
+        assert_eq!(
+            violations(json!({"label": "ok", "data": "{}", "expires_at": 1})),
+            field("expires_at", None)
+        );
+    }
+
+    #[test]
+    fn test_validation_reports_every_violation_at_once() {
+        let request = submit_request(json!({
+            "label": "",
+            "data": "{not json",
+            "expires_at": 1,
+        }));
+        let fields: Vec<String> = request
+            .field_errors(1024)
+            .into_iter()
+            .map(|error| error.field)
+            .collect();
+        assert_eq!(fields, vec!["label", "data", "expires_at"]);
+        assert!(matches!(
+            request.validate(1024),
+            Err(GoudChainError::ValidationFailed { errors }) if errors.len() == 3
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_submit_requires_nonce() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, _) = start_node(&data_dir).await;
+        let auth = format!("Bearer {}", encode_api_key(&api_key));
+
+        let (status, _) = forward_request_with_headers(
+            &addr,
+            "POST",
+            "/data/submit",
+            r#"{"label":"no-nonce","data":"{}"}"#,
+            "application/json",
+            Some(&auth),
+            &[],
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, 401);
+    }
+
+    #[tokio::test]
+    async fn test_envelope_is_returned_encrypted_to_its_owner_only() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, request_key) = start_node(&data_dir).await;
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let (status, created) = signed_submit_with_body(
+            &addr,
+            &api_key,
+            &request_key,
+            r#"{"label":"envelope","data":"{\"secret\":7}"}"#,
+            &nonce,
+        )
+        .await;
+        assert_eq!(status, 201);
+        let collection_id = created["collection_id"].as_str().unwrap();
+        let path = format!("/data/{}/envelope", collection_id);
+
+        let auth = format!("Bearer {}", encode_api_key(&api_key));
+        let (status, body) = forward_request_with_headers(
+            &addr,
+            "GET",
+            &path,
+            "",
+            "application/json",
+            Some(&auth),
+            &[],
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, 200);
+        let envelope: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(envelope["collection_id"], collection_id);
+        assert!(!body.contains("secret"));
+
+        // The client decrypts with the key derived from its own API key
+        let key = crate::crypto::global_key_cache()
+            .get_encryption_key(&api_key, crate::constants::ENCRYPTION_SALT);
+        let metadata = crate::crypto::decrypt_data_with_key(
+            envelope["encrypted_metadata"].as_str().unwrap(),
+            &key,
+        )
+        .unwrap();
+        assert!(metadata.contains("envelope"));
+
+        let other = format!("Bearer {}", encode_api_key(&generate_api_key()));
+        let (status, _) = forward_request_with_headers(
+            &addr,
+            "GET",
+            &path,
+            "",
+            "application/json",
+            Some(&other),
+            &[],
+        )
+        .await
+        .unwrap();
+        assert_ne!(status, 200);
+    }
+
+    /// Call POST /data/decrypt_all with `query` and return the status with the parsed body
+    async fn decrypt_all_with_query(
+        addr: &str,
+        api_key: &[u8],
+        query: &str,
+    ) -> (u16, serde_json::Value) {
+        let auth = format!("Bearer {}", encode_api_key(api_key));
+        let (status, response) = forward_request_with_headers(
+            addr,
+            "POST",
+            &format!("/data/decrypt_all{}", query),
+            "",
+            "application/json",
+            Some(&auth),
+            &[],
+        )
+        .await
+        .unwrap();
+        (status, serde_json::from_str(&response).unwrap_or_default())
+    }
+
+    fn decrypted_labels(body: &serde_json::Value) -> Vec<String> {
+        let mut labels: Vec<String> = body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["label"].as_str().unwrap().to_string())
+            .collect();
+        labels.sort();
+        labels
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_all_returns_only_head_revisions() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, request_key) = start_node(&data_dir).await;
+
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let (status, created) = signed_submit_with_body(
+            &addr,
+            &api_key,
+            &request_key,
+            r#"{"label":"doc","data":"{\"v\":1}"}"#,
+            &nonce,
+        )
+        .await;
+        assert_eq!(status, 201);
+        let body = format!(
+            r#"{{"label":"doc","data":"{{\"v\":2}}","parent_collection_id":"{}","expected_parent_version":1}}"#,
+            created["collection_id"].as_str().unwrap()
+        );
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let (status, revised) =
+            signed_submit_with_body(&addr, &api_key, &request_key, &body, &nonce).await;
+        assert_eq!(status, 201);
+
+        let (status, body) = decrypt_all_with_query(&addr, &api_key, "").await;
+        assert_eq!(status, 200);
+        let items = body.as_array().unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0]["collection_id"], revised["collection_id"]);
+        assert_eq!(items[0]["data"]["v"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_all_filters_labels_and_pages() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, request_key) = start_node(&data_dir).await;
+        for label in ["a", "b", "c"] {
+            let body = format!(r#"{{"label":"{}","data":"{{}}"}}"#, label);
+            let nonce = uuid::Uuid::new_v4().to_string();
+            assert_eq!(
+                signed_submit(&addr, &api_key, &request_key, &body, &nonce).await,
+                201
+            );
+        }
+
+        let (status, body) = decrypt_all_with_query(&addr, &api_key, "?labels=a,%20c").await;
+        assert_eq!(status, 200);
+        assert_eq!(decrypted_labels(&body), vec!["a", "c"]);
+
+        // Pages split the matching collections without overlap
+        let (_, first) = decrypt_all_with_query(&addr, &api_key, "?page=0&page_size=2").await;
+        let (_, second) = decrypt_all_with_query(&addr, &api_key, "?page=1&page_size=2").await;
+        assert_eq!(first.as_array().unwrap().len(), 2);
+        assert_eq!(second.as_array().unwrap().len(), 1);
+        let mut all = decrypted_labels(&first);
+        all.extend(decrypted_labels(&second));
+        all.sort();
+        assert_eq!(all, vec!["a", "b", "c"]);
+
+        let (_, past_end) = decrypt_all_with_query(&addr, &api_key, "?page=5&page_size=2").await;
+        assert!(past_end.as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_scoped_token_cannot_submit() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let (addr, api_key, _) = start_node(&data_dir).await;
+        let token = generate_session_token(
+            "test-account".to_string(),
+            &api_key,
+            hash_api_key_hex(&api_key),
+            TokenScope::Read,
+            SessionLifetime::standard(&test_config()),
+            &test_config(),
+        )
+        .unwrap();
+        let auth = format!("Bearer {}", token);
+        let nonce = [("X-Nonce", uuid::Uuid::new_v4().to_string())];
+
+        let (status, _) = forward_request_with_headers(
+            &addr,
+            "POST",
+            "/data/submit",
+            r#"{"label":"read-only","data":"{}"}"#,
+            "application/json",
+            Some(&auth),
+            &nonce,
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, 403);
+
+        let (status, _) = forward_request_with_headers(
+            &addr,
+            "GET",
+            "/data/list",
+            "",
+            "application/json",
+            Some(&auth),
+            &[],
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, 200);
     }
 }
@@ -1,29 +1,59 @@
-use axum::{extract::Extension, response::IntoResponse, Json};
+use axum::{
+    extract::{Extension, Path, Query},
+    http::{HeaderMap, StatusCode},
+    middleware,
+    response::{IntoResponse, Response},
+    Json,
+};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_axum::router::{OpenApiRouter, UtoipaMethodRouterExt};
+use utoipa_axum::routes;
 
-use crate::api::schemas::{ErrorResponse, HealthCheckResponse, MessageResponse, PeerInfoResponse};
+use crate::api::conditional::{etag_for, if_none_match, not_modified, with_etag};
+use crate::api::rate_limiter::public_rate_limit_middleware;
+use crate::api::schemas::{
+    BlockHeader, ChainPageResponse, ChainQuery, ErrorResponse, HealthCheckResponse, LimitsResponse,
+    LivenessResponse, MessageResponse, PeerInfoResponse, PeerReputation, PendingCollection,
+    PendingPoolResponse, RateLimitsInfo, ReadinessResponse, ScheduledValidator, SubmitDataState,
+    ValidatorScheduleQuery, ValidatorScheduleResponse,
+};
+use crate::api::security_audit::authenticate_admin;
+use crate::config::Config;
+use crate::constants::{
+    CHAIN_FULL_DUMP_MAX_BLOCKS, CHAIN_PAGE_DEFAULT_LIMIT, CHAIN_PAGE_MAX_LIMIT, MAX_BATCH_REQUESTS,
+    MAX_BATCH_SUBMIT_ITEMS, MAX_LABEL_LENGTH, VALIDATOR_SCHEDULE_DEFAULT_COUNT,
+    VALIDATOR_SCHEDULE_MAX_COUNT,
+};
 use crate::domain::Blockchain;
 use crate::network::P2PNode;
 use crate::types::*;
 
 use super::HEALTH_TAG;
 
-/// Health and status routes
+/// Health and status routes (everything except the health probes is rate limited by client IP)
 pub fn router() -> OpenApiRouter {
+    let public = || middleware::from_fn(public_rate_limit_middleware);
     OpenApiRouter::new()
         .routes(routes!(handle_health))
-        .routes(routes!(handle_get_chain))
-        .routes(routes!(handle_get_peers))
-        .routes(routes!(handle_sync))
-        .routes(routes!(handle_get_current_validator))
+        .routes(routes!(handle_liveness))
+        .routes(routes!(handle_readiness))
+        .routes(routes!(handle_get_chain).layer(public()))
+        .routes(routes!(handle_get_block).layer(public()))
+        .routes(routes!(handle_get_peers).layer(public()))
+        .routes(routes!(handle_sync).layer(public()))
+        .routes(routes!(handle_get_current_validator).layer(public()))
+        .routes(routes!(handle_get_validator_schedule).layer(public()))
+        .routes(routes!(handle_get_limits).layer(public()))
+        .routes(routes!(handle_get_pending))
 }
 
 /// Health check endpoint
 ///
 /// Returns the current health status of the blockchain node.
 /// Used by load balancers and monitoring systems to verify node availability.
+/// Also reports sync state, pending block contents and whether this node validates
+/// the next block, so operators don't need a separate `/validator/current` call.
 #[utoipa::path(
     get,
     path = "/health",
@@ -39,36 +69,203 @@ async fn handle_health(
 ) -> Result<Json<HealthCheckResponse>> {
     let blockchain = blockchain.read().await;
     let peers = p2p.peers.lock().await;
+    let latest_block = blockchain.chain.last().map(|b| b.index).unwrap_or(0);
+    let next_block_number = latest_block + 1;
 
     let health_response = HealthCheckResponse {
         status: "healthy".to_string(),
         node_id: blockchain.node_id.clone(),
         chain_length: blockchain.chain.len(),
         peer_count: peers.len(),
-        latest_block: blockchain.chain.last().map(|b| b.index).unwrap_or(0),
+        latest_block,
+        is_syncing: p2p.best_peer_height() > latest_block,
+        pending_collections: blockchain.pending_collections.len(),
+        pending_accounts: blockchain.pending_accounts_with_keys.len(),
+        is_current_validator: blockchain
+            .validator_config
+            .is_node_authorized(&blockchain.node_id, next_block_number),
+        next_block_number,
     };
 
     Ok(Json(health_response))
 }
 
+/// Liveness probe
+///
+/// Always returns 200 while the process can answer requests. Use it to decide
+/// whether to restart a node, not whether to route traffic to it.
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = HEALTH_TAG,
+    responses(
+        (status = 200, description = "Process is alive", body = LivenessResponse)
+    )
+)]
+async fn handle_liveness() -> Json<LivenessResponse> {
+    Json(LivenessResponse {
+        status: "alive".to_string(),
+    })
+}
+
+/// Readiness probe
+///
+/// Returns 503 until the node has caught up with a peer at least once, or its
+/// latest block is within a few blocks of the best peer tip it has seen. Load
+/// balancers should only route traffic to nodes that pass this check.
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = HEALTH_TAG,
+    responses(
+        (status = 200, description = "Node is synced and ready to serve", body = ReadinessResponse),
+        (status = 503, description = "Node is still syncing", body = ReadinessResponse)
+    )
+)]
+async fn handle_readiness(
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(p2p): Extension<Arc<P2PNode>>,
+) -> Response {
+    let latest_block = blockchain
+        .read()
+        .await
+        .chain
+        .last()
+        .map(|b| b.index)
+        .unwrap_or(0);
+    let ready = p2p.is_ready(latest_block);
+
+    let response = ReadinessResponse {
+        status: if ready { "ready" } else { "syncing" }.to_string(),
+        latest_block,
+        best_peer_height: p2p.best_peer_height(),
+        last_successful_sync: p2p.last_successful_sync(),
+    };
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(response)).into_response()
+}
+
 /// Get blockchain
 ///
-/// Returns the complete blockchain. Use with caution on large chains as this endpoint
-/// returns the entire chain history.
+/// Returns a window of block headers starting at `start`, without the encrypted
+/// envelope bodies. Follow `next_start` to page through the chain. `full=true`
+/// returns the complete blockchain instead, but only for chains of up to 1000 blocks.
+/// Send the returned ETag as `If-None-Match` to get 304 until a new block arrives.
 #[utoipa::path(
     get,
     path = "/chain",
     tag = HEALTH_TAG,
+    params(ChainQuery),
     responses(
-        (status = 200, description = "Blockchain retrieved successfully", body = serde_json::Value),
-        (status = 500, description = "Internal server error", body = ErrorResponse)
+        (status = 200, description = "Block headers retrieved successfully", body = ChainPageResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 304, description = "Chain unchanged since the ETag in If-None-Match"),
+        (status = 400, description = "Chain too large for a full dump", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+        (status = 504, description = "Request exceeded REQUEST_TIMEOUT_SECONDS", body = ErrorResponse)
     )
 )]
 async fn handle_get_chain(
     Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Query(params): Query<ChainQuery>,
+    headers: HeaderMap,
+) -> Result<Response> {
+    let chain = blockchain.read().await;
+    let tip_hash = chain.get_latest_block()?.hash.clone();
+
+    if params.full.unwrap_or(false) {
+        if chain.chain.len() > CHAIN_FULL_DUMP_MAX_BLOCKS {
+            return Err(GoudChainError::InvalidRequestBody(format!(
+                "Chain has {} blocks; full dumps are limited to {}. Page with start/limit instead",
+                chain.chain.len(),
+                CHAIN_FULL_DUMP_MAX_BLOCKS
+            )));
+        }
+        let etag = etag_for(&[&tip_hash, "full"]);
+        if if_none_match(&headers, &etag) {
+            return Ok(not_modified(&etag));
+        }
+        // Offloaded bodies are read back so the dump matches the stored chain
+        let mut dump = (*chain).clone();
+        dump.chain = chain.full_chain();
+        return Ok(with_etag(Json(dump), &etag));
+    }
+
+    let start = params.start.unwrap_or(0);
+    let limit = params
+        .limit
+        .unwrap_or(CHAIN_PAGE_DEFAULT_LIMIT)
+        .clamp(1, CHAIN_PAGE_MAX_LIMIT);
+
+    // The tip hash commits to the whole chain, so it identifies every window of it
+    let etag = etag_for(&[&tip_hash, &start.to_string(), &limit.to_string()]);
+    if if_none_match(&headers, &etag) {
+        return Ok(not_modified(&etag));
+    }
+
+    let blocks: Vec<BlockHeader> = chain
+        .chain
+        .iter()
+        .skip_while(|block| block.index < start)
+        .take(limit)
+        .map(|block| BlockHeader {
+            index: block.index,
+            hash: block.hash.clone(),
+            previous_hash: block.previous_hash.clone(),
+            timestamp: block.timestamp,
+            validator: block.validator.clone(),
+            merkle_root: block.merkle_root.clone(),
+        })
+        .collect();
+
+    let next_start = blocks
+        .last()
+        .map(|block| block.index + 1)
+        .filter(|next| chain.chain.last().is_some_and(|tip| *next <= tip.index));
+
+    let page = ChainPageResponse {
+        node_id: chain.node_id.clone(),
+        chain_length: chain.chain.len(),
+        start,
+        blocks,
+        next_start,
+    };
+    Ok(with_etag(Json(page), &etag))
+}
+
+/// Get block
+///
+/// Returns a single block by index, including its encrypted envelope data.
+#[utoipa::path(
+    get,
+    path = "/block/{index}",
+    tag = HEALTH_TAG,
+    params(
+        ("index" = u64, Path, description = "Block number")
+    ),
+    responses(
+        (status = 200, description = "Block retrieved successfully", body = serde_json::Value),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 404, description = "Block not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+async fn handle_get_block(
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Path(index): Path<u64>,
 ) -> Result<impl IntoResponse> {
     let chain = blockchain.read().await;
-    Ok(Json((*chain).clone()).into_response())
+    let block = chain
+        .chain
+        .iter()
+        .find(|block| block.index == index)
+        .ok_or_else(|| GoudChainError::DataNotFound(format!("Block {} not found", index)))?;
+    Ok(Json(chain.full_block(block).into_owned()).into_response())
 }
 
 /// Get connected peers
@@ -81,6 +278,7 @@ async fn handle_get_chain(
     tag = HEALTH_TAG,
     responses(
         (status = 200, description = "Peer list retrieved successfully", body = PeerInfoResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
@@ -88,12 +286,32 @@ async fn handle_get_peers(
     Extension(p2p): Extension<Arc<P2PNode>>,
 ) -> Result<Json<PeerInfoResponse>> {
     let peers = p2p.peers.lock().await.clone();
-    let reputation = p2p.peer_reputation.lock().await.clone();
+    let parked = p2p.parked_peers.lock().await.clone();
+    let scores = p2p.peer_reputation.lock().await.clone();
+    let liveness = p2p.peer_liveness.lock().await.clone();
+
+    let reputation = peers
+        .iter()
+        .chain(parked.iter())
+        .chain(scores.keys())
+        .map(|peer| {
+            let seen = liveness.get(peer);
+            (
+                peer.clone(),
+                PeerReputation {
+                    score: scores.get(peer).copied().unwrap_or(0),
+                    last_seen: seen.map(|l| l.last_seen),
+                    rtt_ms: seen.map(|l| l.rtt_ms),
+                },
+            )
+        })
+        .collect();
 
     let response = PeerInfoResponse {
         peers: peers.clone(),
         count: peers.len(),
         reputation,
+        parked,
     };
 
     Ok(Json(response))
@@ -110,6 +328,7 @@ async fn handle_get_peers(
     tag = HEALTH_TAG,
     responses(
         (status = 200, description = "Sync initiated successfully", body = MessageResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
         (status = 500, description = "Sync failed", body = ErrorResponse)
     )
 )]
@@ -130,21 +349,22 @@ async fn handle_sync(Extension(p2p): Extension<Arc<P2PNode>>) -> Result<Json<Mes
     tag = HEALTH_TAG,
     responses(
         (status = 200, description = "Current validator retrieved", body = serde_json::Value),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 async fn handle_get_current_validator(
     Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
 ) -> Result<Json<serde_json::Value>> {
-    use crate::domain::blockchain::{get_current_validator, is_authorized_validator};
-
     let chain = blockchain.read().await;
     let latest_block = chain.chain.last();
     let next_block_number = latest_block.map(|b| b.index + 1).unwrap_or(1);
-    let expected_validator =
-        get_current_validator(&chain.validator_config.validators, next_block_number);
-    let is_this_node =
-        is_authorized_validator(&chain.node_id, next_block_number, &chain.validator_config);
+    let expected_validator = chain
+        .validator_config
+        .get_validator_for_block(next_block_number);
+    let is_this_node = chain
+        .validator_config
+        .is_node_authorized(&chain.node_id, next_block_number);
 
     let validator_address = chain
         .validator_config
@@ -161,3 +381,357 @@ async fn handle_get_current_validator(
 
     Ok(Json(response))
 }
+
+/// Get the pending pool
+///
+/// Lists the accounts and collections buffered on this node that are waiting for the next
+/// block, with the next block number and its validator. Items stay pending while blocks are
+/// coalesced (MIN_BLOCK_INTERVAL_SECONDS), while the node is read-only, or briefly while a
+/// write is being sealed; on a node that isn't the next block's validator the pool should
+/// normally be empty, since writes are forwarded to the validator. Collection contents stay
+/// encrypted, but the pool reveals every account's write activity, so it is an operator
+/// view: only served by validator nodes, and requires the node's ADMIN_TOKEN in
+/// `X-Admin-Token`.
+#[utoipa::path(
+    get,
+    path = "/pending",
+    tag = HEALTH_TAG,
+    security(
+        ("admin_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Pending pool retrieved", body = PendingPoolResponse),
+        (status = 401, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 404, description = "ADMIN_TOKEN is not configured on this node", body = ErrorResponse),
+        (status = 422, description = "This node is not a validator", body = ErrorResponse)
+    )
+)]
+async fn handle_get_pending(
+    headers: HeaderMap,
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Extension(state): Extension<SubmitDataState>,
+    Extension(config): Extension<Arc<Config>>,
+) -> Result<Json<PendingPoolResponse>> {
+    authenticate_admin(&headers, &config, &state)?;
+
+    let chain = blockchain.read().await;
+    let next_block_number = chain.chain.last().map(|b| b.index + 1).unwrap_or(1);
+    let expected_validator = chain
+        .validator_config
+        .get_validator_for_block(next_block_number);
+    if !chain.validator_config.is_validator_node(&chain.node_id) {
+        return Err(GoudChainError::NotAuthorizedValidator {
+            node_id: chain.node_id.clone(),
+            expected_validator,
+            block_number: next_block_number,
+        });
+    }
+
+    let seconds_until_seal = chain.seconds_until_seal();
+    let pending_collections = chain
+        .pending_collections
+        .iter()
+        .map(|collection| PendingCollection {
+            collection_id: collection.collection_id.clone(),
+            size_bytes: serde_json::to_vec(collection).map_or(0, |bytes| bytes.len()),
+            revision: collection.parent_collection_id.is_some(),
+            share_grant: collection.grant.is_some(),
+        })
+        .collect();
+
+    Ok(Json(PendingPoolResponse {
+        next_block_number,
+        is_this_node_validator: chain
+            .validator_config
+            .is_node_authorized(&chain.node_id, next_block_number),
+        expected_validator,
+        read_only: chain.read_only,
+        seconds_until_seal,
+        pending_accounts: chain
+            .pending_accounts_with_keys
+            .iter()
+            .map(|(account, _)| account.account_id.clone())
+            .collect(),
+        pending_collections,
+    }))
+}
+
+/// Get upcoming validators
+///
+/// Returns the validator, node and address for each of the next `count` blocks,
+/// following the round-robin rotation. Clients can use it to pre-route a batch of
+/// writes instead of asking `/validator/current` before each one.
+#[utoipa::path(
+    get,
+    path = "/validator/schedule",
+    tag = HEALTH_TAG,
+    params(ValidatorScheduleQuery),
+    responses(
+        (status = 200, description = "Validator schedule retrieved", body = ValidatorScheduleResponse),
+        (status = 400, description = "Invalid count", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse)
+    )
+)]
+async fn handle_get_validator_schedule(
+    Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
+    Query(query): Query<ValidatorScheduleQuery>,
+) -> Result<Json<ValidatorScheduleResponse>> {
+    let count = query.count.unwrap_or(VALIDATOR_SCHEDULE_DEFAULT_COUNT);
+    if count == 0 || count > VALIDATOR_SCHEDULE_MAX_COUNT {
+        return Err(GoudChainError::InvalidRequestBody(format!(
+            "count must be between 1 and {}",
+            VALIDATOR_SCHEDULE_MAX_COUNT
+        )));
+    }
+
+    let chain = blockchain.read().await;
+    let current_block_number = chain.chain.last().map(|b| b.index).unwrap_or(0);
+    let config = &chain.validator_config;
+
+    let schedule = (current_block_number + 1..=current_block_number + count)
+        .map(|block_number| {
+            let validator = config.get_validator_for_block(block_number);
+            ScheduledValidator {
+                block_number,
+                node_id: config
+                    .get_node_for_validator(&validator)
+                    .map(str::to_string),
+                validator_address: config.get_validator_address(&validator),
+                validator,
+            }
+        })
+        .collect();
+
+    Ok(Json(ValidatorScheduleResponse {
+        current_block_number,
+        schedule,
+    }))
+}
+
+/// Get request limits
+///
+/// Returns the payload, batch and rate limits this node enforces, so clients can
+/// size requests up front instead of discovering them through 413 and 429 responses.
+#[utoipa::path(
+    get,
+    path = "/limits",
+    tag = HEALTH_TAG,
+    responses(
+        (status = 200, description = "Active limits retrieved", body = LimitsResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse)
+    )
+)]
+async fn handle_get_limits(Extension(config): Extension<Arc<Config>>) -> Json<LimitsResponse> {
+    Json(limits_for(&config))
+}
+
+/// Limits derived from a node's configuration
+pub fn limits_for(config: &Config) -> LimitsResponse {
+    let rate_limit = &config.rate_limit;
+    LimitsResponse {
+        max_payload_bytes: config.max_payload_bytes,
+        max_request_body_bytes: config.max_request_body_bytes(),
+        max_batch_request_body_bytes: config.max_batch_request_body_bytes(),
+        max_label_length: MAX_LABEL_LENGTH,
+        max_batch_items: MAX_BATCH_SUBMIT_ITEMS,
+        max_batch_requests: MAX_BATCH_REQUESTS,
+        request_timeout_seconds: config.request_timeout_seconds,
+        rate_limits: RateLimitsInfo {
+            mode: rate_limit.mode.as_str().to_string(),
+            burst_seconds: rate_limit.burst_seconds,
+            submit_per_second: rate_limit.submit_per_second,
+            batch_per_second: rate_limit.batch_per_second,
+            list_per_second: rate_limit.list_per_second,
+            search_per_second: rate_limit.search_per_second,
+            decrypt_per_second: rate_limit.decrypt_per_second,
+            delete_per_second: rate_limit.delete_per_second,
+            account_create_per_second: rate_limit.account_create_per_second,
+            account_exists_per_second: rate_limit.account_exists_per_second,
+            public_per_second: rate_limit.public_per_second,
+            key_hash_per_second: rate_limit.key_hash_per_second,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ValidatorConfig;
+    use crate::domain::block::{generate_block_salt, BlockConfig};
+    use crate::domain::Block;
+    use axum::body::to_bytes;
+    use axum::http::header::{ETAG, IF_NONE_MATCH};
+    use axum::http::StatusCode;
+
+    fn test_blockchain(blocks: u64) -> Arc<RwLock<Blockchain>> {
+        let mut chain =
+            Blockchain::new("node1".to_string(), ValidatorConfig::single("node1")).unwrap();
+
+        while (chain.chain.len() as u64) < blocks {
+            let previous = chain.chain.last().unwrap();
+            let block = Block::new(BlockConfig {
+                index: previous.index + 1,
+                account_envelopes: Vec::new(),
+                collection_envelopes: Vec::new(),
+                previous_hash: previous.hash.clone(),
+                validator: "Validator_1".to_string(),
+                blind_indexes: Vec::new(),
+                block_salt: generate_block_salt(),
+            })
+            .unwrap();
+            chain.chain.push(block);
+        }
+
+        Arc::new(RwLock::new(chain))
+    }
+
+    async fn get_chain(
+        blockchain: &Arc<RwLock<Blockchain>>,
+        start: Option<u64>,
+        limit: Option<usize>,
+        full: Option<bool>,
+    ) -> Result<serde_json::Value> {
+        let response = handle_get_chain(
+            Extension(Arc::clone(blockchain)),
+            Query(ChainQuery { start, limit, full }),
+            HeaderMap::new(),
+        )
+        .await?;
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        Ok(serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_chain_pages_headers_without_envelopes() {
+        let blockchain = test_blockchain(5);
+
+        let page = get_chain(&blockchain, Some(1), Some(2), None)
+            .await
+            .unwrap();
+        assert_eq!(page["chain_length"], 5);
+        assert_eq!(page["start"], 1);
+        assert_eq!(page["next_start"], 3);
+        let blocks = page["blocks"].as_array().unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0]["index"], 1);
+        assert_eq!(blocks[1]["index"], 2);
+        assert!(blocks[0].get("encrypted_block_data").is_none());
+
+        let last = get_chain(&blockchain, Some(3), Some(10), None)
+            .await
+            .unwrap();
+        assert_eq!(last["blocks"].as_array().unwrap().len(), 2);
+        assert!(last.get("next_start").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_full_dump_is_refused_for_large_chains() {
+        let small = test_blockchain(3);
+        let full = get_chain(&small, None, None, Some(true)).await.unwrap();
+        assert_eq!(full["chain"].as_array().unwrap().len(), 3);
+
+        let large = test_blockchain(CHAIN_FULL_DUMP_MAX_BLOCKS as u64 + 1);
+        let result = get_chain(&large, None, None, Some(true)).await;
+        assert!(matches!(result, Err(GoudChainError::InvalidRequestBody(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_block_returns_full_block_or_not_found() {
+        let blockchain = test_blockchain(3);
+
+        let response = handle_get_block(Extension(Arc::clone(&blockchain)), Path(2))
+            .await
+            .unwrap()
+            .into_response();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let block: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(block["index"], 2);
+        assert!(block.get("encrypted_block_data").is_some());
+
+        let missing = handle_get_block(Extension(blockchain), Path(9)).await;
+        assert!(matches!(missing, Err(GoudChainError::DataNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_chain_not_modified_until_new_block() {
+        let blockchain = test_blockchain(2);
+        let query = || {
+            Query(ChainQuery {
+                start: None,
+                limit: None,
+                full: None,
+            })
+        };
+
+        let first = handle_get_chain(
+            Extension(Arc::clone(&blockchain)),
+            query(),
+            HeaderMap::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        let etag = first.headers().get(ETAG).unwrap().clone();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_NONE_MATCH, etag.clone());
+        let repeat = handle_get_chain(Extension(Arc::clone(&blockchain)), query(), headers.clone())
+            .await
+            .unwrap();
+        assert_eq!(repeat.status(), StatusCode::NOT_MODIFIED);
+
+        // A different window of the same chain has its own ETag
+        let other_window = handle_get_chain(
+            Extension(Arc::clone(&blockchain)),
+            Query(ChainQuery {
+                start: Some(1),
+                limit: None,
+                full: None,
+            }),
+            headers.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(other_window.status(), StatusCode::OK);
+
+        let extended = test_blockchain(3);
+        let after_block = handle_get_chain(Extension(extended), query(), headers)
+            .await
+            .unwrap();
+        assert_eq!(after_block.status(), StatusCode::OK);
+        assert_ne!(after_block.headers().get(ETAG).unwrap(), etag);
+    }
+
+    #[tokio::test]
+    async fn test_validator_schedule_follows_rotation() {
+        let blockchain = test_blockchain(3);
+        let schedule = |count| {
+            handle_get_validator_schedule(
+                Extension(Arc::clone(&blockchain)),
+                Query(ValidatorScheduleQuery { count }),
+            )
+        };
+
+        let Json(response) = schedule(Some(3)).await.unwrap();
+        assert_eq!(response.current_block_number, 2);
+        let block_numbers: Vec<u64> = response.schedule.iter().map(|s| s.block_number).collect();
+        assert_eq!(block_numbers, vec![3, 4, 5]);
+        assert_eq!(response.schedule[0].validator, "Validator_1");
+        assert_eq!(response.schedule[0].node_id.as_deref(), Some("node1"));
+
+        let Json(default) = schedule(None).await.unwrap();
+        assert_eq!(
+            default.schedule.len() as u64,
+            VALIDATOR_SCHEDULE_DEFAULT_COUNT
+        );
+
+        assert!(matches!(
+            schedule(Some(0)).await,
+            Err(GoudChainError::InvalidRequestBody(_))
+        ));
+        assert!(matches!(
+            schedule(Some(VALIDATOR_SCHEDULE_MAX_COUNT + 1)).await,
+            Err(GoudChainError::InvalidRequestBody(_))
+        ));
+    }
+}
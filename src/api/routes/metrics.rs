@@ -1,84 +1,188 @@
-use axum::{extract::Extension, http::StatusCode, Json};
-use std::collections::HashMap;
+use axum::{
+    extract::Extension,
+    http::{HeaderMap, StatusCode},
+    middleware,
+    response::Response,
+    Json,
+};
+use chrono::Utc;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
-use utoipa_axum::{router::OpenApiRouter, routes};
-
-use crate::api::schemas::{ChainStatsResponse, ErrorResponse, NodeMetricsResponse, VolumeMetrics};
-use crate::constants::DATA_DIRECTORY;
+use utoipa_axum::router::{OpenApiRouter, UtoipaMethodRouterExt};
+use utoipa_axum::routes;
+
+use crate::api::conditional::{etag_for, if_none_match, not_modified, with_etag};
+use crate::api::global_operations_tracker;
+use crate::api::internal_client::forward_pool_prometheus_metrics;
+use crate::api::rate_limiter::public_rate_limit_middleware;
+use crate::api::schemas::{
+    ChainStatsResponse, ErrorResponse, NodeMetricsResponse, ValidatorProduction, VolumeMetrics,
+};
+use crate::api::websocket::dropped_slow_consumers;
+use crate::config::Config;
+use crate::constants::{METRICS_ETAG_WINDOW_SECONDS, ROCKSDB_DIRECTORY_NAME};
 use crate::domain::Blockchain;
 use crate::network::P2PNode;
+use crate::types::metrics::{BLOCK_PRODUCTION_SECONDS, DECRYPT_SECONDS, ENCRYPTION_SECONDS};
 use crate::types::*;
 
 use super::METRICS_TAG;
 
-/// Metrics and statistics routes
+/// Metrics and statistics routes (rate limited by client IP)
 pub fn router() -> OpenApiRouter {
+    let public = || middleware::from_fn(public_rate_limit_middleware);
     OpenApiRouter::new()
-        .routes(routes!(handle_get_stats))
-        .routes(routes!(handle_get_metrics))
-        .routes(routes!(handle_get_prometheus_metrics))
+        .routes(
+            routes!(handle_get_stats)
+                .layer(public())
+                .layer(Extension(Arc::new(StatsCache::default()))),
+        )
+        .routes(routes!(handle_get_metrics).layer(public()))
+        .routes(routes!(handle_get_prometheus_metrics).layer(public()))
 }
 
 /// Get blockchain statistics
 ///
 /// Returns comprehensive statistics about the blockchain including block counts,
 /// collection counts, account counts, and validator distribution.
+/// Results are cached per chain tip, so repeated calls between blocks cost no chain scan.
+/// Send the returned ETag as `If-None-Match` to get 304 until a new block arrives.
 #[utoipa::path(
     get,
     path = "/stats",
     tag = METRICS_TAG,
     responses(
         (status = 200, description = "Statistics retrieved successfully", body = ChainStatsResponse),
-        (status = 500, description = "Internal server error", body = ErrorResponse)
+        (status = 304, description = "Chain unchanged since the ETag in If-None-Match"),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+        (status = 504, description = "Request exceeded REQUEST_TIMEOUT_SECONDS", body = ErrorResponse)
     )
 )]
 async fn handle_get_stats(
     Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
-) -> Result<Json<ChainStatsResponse>> {
+    Extension(cache): Extension<Arc<StatsCache>>,
+    headers: HeaderMap,
+) -> Result<Response> {
     let chain = blockchain.read().await;
 
-    let total_blocks = chain.chain.len() as u64;
-    let mut total_collections = 0u64;
-    let mut total_accounts = 0u64;
-    let mut validator_distribution: HashMap<String, u64> = HashMap::new();
+    // Stats are derived purely from the chain, which the tip hash commits to
+    let etag = etag_for(&[&chain.get_latest_block()?.hash]);
+    if if_none_match(&headers, &etag) {
+        return Ok(not_modified(&etag));
+    }
 
-    for block in &chain.chain {
-        total_collections += block.get_collection_count().unwrap_or(0) as u64;
-        total_accounts += block.get_account_count().unwrap_or(0) as u64;
+    Ok(with_etag(Json(cache.get_or_compute(&chain)?), &etag))
+}
 
-        *validator_distribution
-            .entry(block.validator.clone())
-            .or_insert(0) += 1;
+/// Last `/stats` response, reused until the chain tip (or pruning boundary) changes
+#[derive(Default)]
+pub struct StatsCache {
+    entry: Mutex<Option<(String, ChainStatsResponse)>>,
+}
+
+impl StatsCache {
+    /// Stats for the chain as it is now, recomputed only after the chain has changed
+    fn get_or_compute(&self, chain: &Blockchain) -> Result<ChainStatsResponse> {
+        let key = format!("{}:{}", chain.get_latest_block()?.hash, chain.pruned_before);
+        let mut entry = self.entry.lock().unwrap();
+        if let Some((cached_key, stats)) = entry.as_ref() {
+            if *cached_key == key {
+                return Ok(stats.clone());
+            }
+        }
+
+        let stats = chain_stats(chain);
+        *entry = Some((key, stats.clone()));
+        Ok(stats)
     }
+}
+
+/// Build chain statistics from the running aggregates
+fn chain_stats(chain: &Blockchain) -> ChainStatsResponse {
+    let aggregates = chain.chain_aggregates();
 
-    let avg_block_time = if chain.chain.len() > 1 {
-        let mut total_time = 0.0;
-        for i in 1..chain.chain.len() {
-            total_time += (chain.chain[i].timestamp - chain.chain[i - 1].timestamp) as f64;
+    // Block intervals telescope, so the mean is the span over the number of intervals
+    let avg_block_time = match (chain.chain.first(), chain.chain.last()) {
+        (Some(first), Some(last)) if chain.chain.len() > 1 => {
+            (last.timestamp - first.timestamp) as f64 / (chain.chain.len() - 1) as f64
         }
-        total_time / (chain.chain.len() - 1) as f64
-    } else {
-        0.0
+        _ => 0.0,
     };
 
-    let stats = ChainStatsResponse {
-        total_blocks,
-        total_collections,
-        total_accounts,
+    ChainStatsResponse {
+        total_blocks: aggregates.total_blocks,
+        total_collections: aggregates.total_collections,
+        total_accounts: aggregates.total_accounts,
         avg_block_time_seconds: avg_block_time,
-        validator_distribution,
-    };
+        validator_distribution: aggregates.validator_distribution,
+        validator_production: validator_production(chain),
+    }
+}
+
+/// Compare each validator's rotation slots in the chain with the blocks it produced
+///
+/// Rotation assigns block `i` to validator `i % n`, so expected counts come from block indexes
+/// and actual counts from each block's `validator`. Blocks signed out of turn (e.g. produced
+/// under an earlier validator set) show up as missed slots of the validator they displaced.
+fn validator_production(chain: &Blockchain) -> BTreeMap<String, ValidatorProduction> {
+    let validator_config = &chain.validator_config;
+    let mut production: BTreeMap<String, ValidatorProduction> = validator_config
+        .validators
+        .iter()
+        .map(|validator| (validator.clone(), ValidatorProduction::default()))
+        .collect();
+    if validator_config.validators.is_empty() {
+        return production;
+    }
 
-    Ok(Json(stats))
+    for block in &chain.chain {
+        production
+            .entry(validator_config.get_validator_for_block(block.index))
+            .or_default()
+            .expected_blocks += 1;
+        production
+            .entry(block.validator.clone())
+            .or_default()
+            .actual_blocks += 1;
+    }
+
+    for entry in production.values_mut() {
+        entry.missed_slots = entry.expected_blocks.saturating_sub(entry.actual_blocks);
+    }
+    production
 }
 
-/// Collect volume metrics from filesystem
-fn collect_volume_metrics() -> Option<VolumeMetrics> {
-    let data_path = Path::new(DATA_DIRECTORY);
+/// Render per-validator production as Prometheus counters and gauges
+fn validator_prometheus_metrics(production: &BTreeMap<String, ValidatorProduction>) -> String {
+    let mut blocks = String::from(
+        "# HELP goud_validator_blocks_total Blocks in the chain produced by each validator\n\
+         # TYPE goud_validator_blocks_total counter\n",
+    );
+    let mut missed = String::from(
+        "# HELP goud_validator_missed_slots Rotation slots of each validator filled by another validator\n\
+         # TYPE goud_validator_missed_slots gauge\n",
+    );
+    for (validator, entry) in production {
+        // Validator names come from config and block headers; escape them as label values
+        let label = validator.replace('\\', "\\\\").replace('"', "\\\"");
+        blocks.push_str(&format!(
+            "goud_validator_blocks_total{{validator=\"{}\"}} {}\n",
+            label, entry.actual_blocks
+        ));
+        missed.push_str(&format!(
+            "goud_validator_missed_slots{{validator=\"{}\"}} {}\n",
+            label, entry.missed_slots
+        ));
+    }
+    format!("{}{}", blocks, missed)
+}
 
+/// Collect volume metrics from filesystem
+fn collect_volume_metrics(data_path: &Path) -> Option<VolumeMetrics> {
     // Check if data directory exists
     if !data_path.exists() {
         return None;
@@ -89,7 +193,7 @@ fn collect_volume_metrics() -> Option<VolumeMetrics> {
     let disk_used_mb = disk_used_bytes / (1024 * 1024);
 
     // Check if RocksDB is present
-    let rocksdb_path = data_path.join("rocksdb");
+    let rocksdb_path = data_path.join(ROCKSDB_DIRECTORY_NAME);
     let rocksdb_present = rocksdb_path.exists() && rocksdb_path.is_dir();
 
     // Count SST files if RocksDB exists
@@ -102,7 +206,7 @@ fn collect_volume_metrics() -> Option<VolumeMetrics> {
     Some(VolumeMetrics {
         disk_used_bytes,
         disk_used_mb,
-        mount_path: DATA_DIRECTORY.to_string(),
+        mount_path: data_path.display().to_string(),
         rocksdb_present,
         sst_file_count,
     })
@@ -164,57 +268,82 @@ fn count_sst_files(rocksdb_path: &Path) -> Option<u64> {
 ///
 /// Returns real-time system metrics including blockchain statistics, network health,
 /// performance data, and volume storage metrics. JSON format suitable for dashboards and monitoring tools.
+/// The ETag also changes every 5 seconds, so an `If-None-Match` poll may return 304
+/// with counters that are up to 5 seconds old.
 #[utoipa::path(
     get,
     path = "/metrics",
     tag = METRICS_TAG,
     responses(
         (status = 200, description = "Metrics retrieved successfully", body = NodeMetricsResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
+        (status = 304, description = "Chain and peers unchanged within the current cache window"),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 async fn handle_get_metrics(
     Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
     Extension(p2p): Extension<Arc<P2PNode>>,
-) -> Result<Json<NodeMetricsResponse>> {
-    use crate::crypto::global_key_cache;
-
+    Extension(config): Extension<Arc<Config>>,
+    headers: HeaderMap,
+) -> Result<Response> {
     let chain = blockchain.read().await;
     let peers = p2p.peers.lock().await;
 
     let latest_block = chain.chain.last();
 
-    let mut total_operations = 0u64;
-    for block in &chain.chain {
-        if let Ok(count) = block.get_account_count() {
-            total_operations += count as u64;
-        }
-        if let Ok(count) = block.get_collection_count() {
-            total_operations += count as u64;
-        }
+    // Checked before the chain scan and data directory walk below
+    let window = Utc::now().timestamp() / METRICS_ETAG_WINDOW_SECONDS;
+    let etag = etag_for(&[
+        latest_block.map(|b| b.hash.as_str()).unwrap_or_default(),
+        &peers.len().to_string(),
+        &window.to_string(),
+    ]);
+    if if_none_match(&headers, &etag) {
+        return Ok(not_modified(&etag));
     }
 
+    Ok(with_etag(
+        Json(build_node_metrics(&chain, peers.len(), &config.data_dir)),
+        &etag,
+    ))
+}
+
+/// Compute the node metrics served by /metrics and pushed as metrics_update events
+pub fn build_node_metrics(
+    chain: &Blockchain,
+    peer_count: usize,
+    data_dir: &Path,
+) -> NodeMetricsResponse {
+    use crate::crypto::global_key_cache;
+
+    let latest_block = chain.chain.last();
+
+    let aggregates = chain.chain_aggregates();
+    let total_operations = aggregates.total_accounts + aggregates.total_collections;
+
     let cache = global_key_cache();
     let cache_stats = cache.stats();
     let cache_hit_rate = cache_stats.hit_rate();
 
     // Collect volume metrics
-    let volume_metrics = collect_volume_metrics();
+    let volume_metrics = collect_volume_metrics(data_dir);
 
-    let metrics = NodeMetricsResponse {
+    NodeMetricsResponse {
         node_id: chain.node_id.clone(),
         chain_length: chain.chain.len() as u64,
-        peer_count: peers.len(),
+        peer_count,
         latest_block_index: latest_block.map(|b| b.index).unwrap_or(0),
         latest_block_timestamp: latest_block.map(|b| b.timestamp).unwrap_or(0),
         status: "healthy".to_string(),
         total_operations,
         cache_hit_rate,
-        operations_per_second: 0.0,
+        cache_evictions: cache_stats.evictions,
+        cache_expirations: cache_stats.expirations,
+        operations_per_second: global_operations_tracker().operations_per_second(),
+        dropped_slow_consumers: dropped_slow_consumers(),
         volume_metrics,
-    };
-
-    Ok(Json(metrics))
+    }
 }
 
 /// Get Prometheus metrics
@@ -227,12 +356,14 @@ async fn handle_get_metrics(
     tag = METRICS_TAG,
     responses(
         (status = 200, description = "Prometheus metrics in text format", content_type = "text/plain"),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 async fn handle_get_prometheus_metrics(
     Extension(blockchain): Extension<Arc<RwLock<Blockchain>>>,
     Extension(p2p): Extension<Arc<P2PNode>>,
+    Extension(config): Extension<Arc<Config>>,
 ) -> Result<(
     StatusCode,
     [(axum::http::HeaderName, &'static str); 1],
@@ -256,15 +387,23 @@ async fn handle_get_prometheus_metrics(
          goud_latest_block_index {}\n\
          # HELP goud_latest_block_timestamp Timestamp of the latest block\n\
          # TYPE goud_latest_block_timestamp gauge\n\
-         goud_latest_block_timestamp {}\n",
+         goud_latest_block_timestamp {}\n\
+         # HELP goud_operations_per_second Account and collection writes per second (60s window)\n\
+         # TYPE goud_operations_per_second gauge\n\
+         goud_operations_per_second {:.4}\n\
+         # HELP goud_websocket_dropped_slow_consumers_total WebSocket connections dropped for falling behind\n\
+         # TYPE goud_websocket_dropped_slow_consumers_total counter\n\
+         goud_websocket_dropped_slow_consumers_total {}\n",
         chain.chain.len(),
         peers.len(),
         latest_block.map(|b| b.index).unwrap_or(0),
-        latest_block.map(|b| b.timestamp).unwrap_or(0)
+        latest_block.map(|b| b.timestamp).unwrap_or(0),
+        global_operations_tracker().operations_per_second(),
+        dropped_slow_consumers()
     );
 
     // Collect volume metrics
-    let volume_metrics = if let Some(vm) = collect_volume_metrics() {
+    let volume_metrics = if let Some(vm) = collect_volume_metrics(&config.data_dir) {
         format!(
             "# HELP goud_volume_disk_used_bytes Disk space used by blockchain data in bytes\n\
              # TYPE goud_volume_disk_used_bytes gauge\n\
@@ -288,7 +427,28 @@ async fn handle_get_prometheus_metrics(
     };
 
     let cache_metrics = global_key_cache().prometheus_metrics();
-    let all_metrics = format!("{}\n{}\n{}", node_metrics, volume_metrics, cache_metrics);
+    let latency_metrics = [
+        &BLOCK_PRODUCTION_SECONDS,
+        &ENCRYPTION_SECONDS,
+        &DECRYPT_SECONDS,
+    ]
+    .iter()
+    .map(|histogram| histogram.render_prometheus())
+    .collect::<Vec<_>>()
+    .join("\n");
+    let forward_metrics = forward_pool_prometheus_metrics();
+    let p2p_metrics = p2p.prometheus_metrics().await;
+    let validator_metrics = validator_prometheus_metrics(&validator_production(&chain));
+    let all_metrics = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}\n{}",
+        node_metrics,
+        volume_metrics,
+        cache_metrics,
+        forward_metrics,
+        p2p_metrics,
+        validator_metrics,
+        latency_metrics
+    );
 
     Ok((
         StatusCode::OK,
@@ -299,3 +459,81 @@ async fn handle_get_prometheus_metrics(
         all_metrics,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ValidatorConfig;
+    use std::collections::HashMap;
+
+    fn two_validator_chain(block_validators: &[&str]) -> Blockchain {
+        let validators = vec!["Validator_1".to_string(), "Validator_2".to_string()];
+        let config = ValidatorConfig {
+            validators,
+            node_to_validator: HashMap::new(),
+            validator_to_address: HashMap::new(),
+            validator_public_keys: HashMap::new(),
+        };
+        let mut chain = Blockchain::new("node1".to_string(), config).unwrap();
+        for (offset, validator) in block_validators.iter().enumerate() {
+            let mut block = chain.chain[0].clone();
+            block.index = offset as u64 + 1;
+            block.validator = validator.to_string();
+            chain.chain.push(block);
+        }
+        chain
+    }
+
+    #[test]
+    fn test_validator_production_counts_missed_slots() {
+        // Block 2 belongs to Validator_1 but was produced by Validator_2
+        let chain = two_validator_chain(&["Validator_2", "Validator_2"]);
+        let production = validator_production(&chain);
+
+        let first = &production["Validator_1"];
+        assert_eq!((first.expected_blocks, first.actual_blocks), (2, 1));
+        assert_eq!(first.missed_slots, 1);
+        let second = &production["Validator_2"];
+        assert_eq!((second.expected_blocks, second.actual_blocks), (1, 2));
+        assert_eq!(second.missed_slots, 0);
+
+        let metrics = validator_prometheus_metrics(&production);
+        assert!(metrics.contains("goud_validator_blocks_total{validator=\"Validator_2\"} 2\n"));
+        assert!(metrics.contains("goud_validator_missed_slots{validator=\"Validator_1\"} 1\n"));
+    }
+
+    #[test]
+    fn test_stats_cache_refreshes_after_new_block() {
+        let config = ValidatorConfig::single("test-node");
+        let mut chain = Blockchain::new("test-node".to_string(), config).unwrap();
+        let cache = StatsCache::default();
+
+        let before = cache.get_or_compute(&chain).unwrap();
+        assert_eq!(before.total_blocks, 1);
+        assert_eq!(before.total_accounts, 0);
+
+        let api_key = crate::crypto::generate_api_key();
+        let signing_key = crate::crypto::generate_signing_key();
+        let account = crate::domain::UserAccount::new(&api_key, &signing_key, None, None).unwrap();
+        chain.add_account_with_key(account, api_key).unwrap();
+        chain.add_block().unwrap();
+
+        // The new tip invalidates the cached response; totals were advanced incrementally
+        let after = cache.get_or_compute(&chain).unwrap();
+        assert_eq!(after.total_blocks, 2);
+        assert_eq!(after.total_accounts, 1);
+        assert_eq!(after.validator_distribution["Validator_1"], 2);
+        assert_eq!(chain.aggregates.tip_hash, chain.chain[1].hash);
+    }
+
+    #[test]
+    fn test_idle_validator_is_reported() {
+        let chain = two_validator_chain(&[]);
+        let production = validator_production(&chain);
+
+        // Only genesis exists: Validator_2 has no slots yet but is still listed
+        assert_eq!(production["Validator_1"].actual_blocks, 1);
+        assert_eq!(production["Validator_2"].expected_blocks, 0);
+        assert_eq!(production["Validator_2"].actual_blocks, 0);
+    }
+}
@@ -1,6 +1,8 @@
 // Route module exports
 pub mod account;
+pub mod admin;
 pub mod audit;
+pub mod batch;
 pub mod data;
 pub mod health;
 pub mod metrics;
@@ -15,3 +17,5 @@ pub const DATA_TAG: &str = "Data Operations";
 pub const HEALTH_TAG: &str = "Health & Status";
 pub const METRICS_TAG: &str = "Metrics & Analytics";
 pub const AUDIT_TAG: &str = "Audit Logs";
+pub const ADMIN_TAG: &str = "Administration";
+pub const BATCH_TAG: &str = "Batch Operations";
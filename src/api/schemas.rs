@@ -1,18 +1,25 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use utoipa::{IntoParams, ToSchema};
 
+use crate::api::auth::TokenScope;
 use crate::api::WebSocketBroadcaster;
-use crate::storage::AuditLogger;
+use crate::domain::RegrantedShare;
+use crate::storage::{
+    AuditLogger, BackupInfo, BanRecord, CheckpointMismatch, CheckpointReport, TokenRevocationStore,
+    ViolationRecord,
+};
+use crate::types::validation::FieldError;
 
 // ========== SHARED STATE ==========
 
-/// Shared state for handlers that need audit logging and WebSocket broadcasting
+/// Shared state for handlers that need audit logging, WebSocket broadcasting and session revocation checks
 #[derive(Clone)]
 pub struct SubmitDataState {
     pub audit_logger: Arc<AuditLogger>,
     pub ws_broadcaster: Arc<WebSocketBroadcaster>,
+    pub token_revocations: Arc<TokenRevocationStore>,
 }
 
 // ========== REQUEST SCHEMAS ==========
@@ -20,9 +27,63 @@ pub struct SubmitDataState {
 /// Account creation request
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateAccountRequest {
-    /// Optional account metadata (any valid JSON structure)
+    /// Optional account metadata (JSON object, up to 4 KB serialized)
     #[schema(example = json!({"username": "alice", "email": "alice@example.com"}))]
     pub metadata: Option<serde_json::Value>,
+
+    /// Optional client Ed25519 public key (hex) used to verify signed write requests
+    #[schema(example = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+}
+
+impl CreateAccountRequest {
+    /// Validate the optional metadata (DoS Protection)
+    pub fn validate(&self) -> crate::types::Result<()> {
+        self.metadata
+            .as_ref()
+            .map_or(Ok(()), validate_account_metadata)
+    }
+}
+
+/// Account metadata update request
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateAccountMetadataRequest {
+    /// Replacement metadata (JSON object, up to 4 KB serialized); `null` clears it
+    #[schema(example = json!({"username": "alice", "email": "alice@example.org"}))]
+    pub metadata: Option<serde_json::Value>,
+}
+
+impl UpdateAccountMetadataRequest {
+    /// Validate the replacement metadata (DoS Protection)
+    pub fn validate(&self) -> crate::types::Result<()> {
+        self.metadata
+            .as_ref()
+            .map_or(Ok(()), validate_account_metadata)
+    }
+}
+
+/// Account metadata must be a JSON object within the size and nesting limits
+fn validate_account_metadata(metadata: &serde_json::Value) -> crate::types::Result<()> {
+    use crate::constants::MAX_ACCOUNT_METADATA_BYTES;
+    use crate::types::validation::validate_json_structure;
+    use crate::types::GoudChainError;
+
+    if !metadata.is_object() {
+        return Err(GoudChainError::InvalidJson(
+            "Account metadata must be a JSON object".to_string(),
+        ));
+    }
+
+    let serialized = metadata.to_string();
+    if serialized.len() > MAX_ACCOUNT_METADATA_BYTES {
+        return Err(GoudChainError::PayloadTooLarge {
+            actual_bytes: serialized.len(),
+            max_bytes: MAX_ACCOUNT_METADATA_BYTES,
+        });
+    }
+
+    validate_json_structure(&serialized)
 }
 
 /// Login request with API key
@@ -31,6 +92,36 @@ pub struct LoginRequest {
     /// API key obtained during account creation (base64-encoded)
     #[schema(example = "Z291ZF9hYmMxMjM0NTY3ODkw")]
     pub api_key: String,
+
+    /// Access granted to the session token (`read`, `write` or `all`, default `all`)
+    #[serde(default)]
+    pub scope: TokenScope,
+
+    /// Session token lifetime in seconds (default 3600, at most MAX_SESSION_TTL_SECONDS, or
+    /// REMEMBER_ME_TTL_SECONDS for remember-me tokens)
+    #[schema(example = 900, minimum = 60)]
+    #[serde(default)]
+    pub ttl_seconds: Option<i64>,
+
+    /// Issue a long-lived "remember me" token; these are always read-only
+    #[serde(default)]
+    pub remember_me: bool,
+}
+
+/// Account existence check (POST /account/exists)
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct AccountExistsRequest {
+    /// API key to check (base64-encoded)
+    #[schema(example = "Z291ZF9hYmMxMjM0NTY3ODkw")]
+    pub api_key: String,
+}
+
+/// Whether an API key belongs to a live account
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AccountExistsResponse {
+    /// False for unknown, malformed and rotated-away keys alike
+    #[schema(example = true)]
+    pub exists: bool,
 }
 
 /// Data submission request
@@ -40,43 +131,150 @@ pub struct SubmitDataRequest {
     #[schema(example = "medical-records", min_length = 1, max_length = 100)]
     pub label: String,
 
-    /// Plaintext data to encrypt and store (max 10MB, must be valid JSON string)
+    /// Plaintext data to encrypt and store (max 10MB; valid JSON for `application/json`, base64 for `application/octet-stream`)
     #[schema(example = r#"{"diagnosis": "healthy", "date": "2025-01-15"}"#)]
     pub data: String,
+
+    /// Collection ID this submission revises (must be owned by the caller)
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_collection_id: Option<String>,
+
+    /// Version the revision is based on (the `version` of the chain head the caller last read)
+    /// The submission is rejected with 409 if the chain has moved past it since
+    #[schema(example = json!(null), minimum = 1)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_parent_version: Option<usize>,
+
+    /// Compress the payload with zstd before encryption (omit for auto: only payloads over 1 KiB)
+    #[schema(example = json!(null))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compress: Option<bool>,
+
+    /// MIME type of the payload (default `application/json`)
+    #[schema(example = "application/json")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+
+    /// Unix timestamp after which the collection is no longer returned (must be in the future)
+    /// Expiry is logical: the encrypted data stays on-chain but the API treats it as deleted
+    #[schema(example = json!(null))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+
+    /// Reject the submission with 409 if another live collection of the caller already has this label
+    /// Revising the collection that holds the label is allowed. Best-effort: two concurrent
+    /// submissions to different validators may both succeed before either is in a block
+    #[schema(example = json!(null))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unique_label: Option<bool>,
 }
 
 impl SubmitDataRequest {
+    /// Content type recorded with the collection, falling back to JSON when omitted
+    pub fn content_type(&self) -> &str {
+        self.content_type
+            .as_deref()
+            .unwrap_or(crate::constants::DEFAULT_CONTENT_TYPE)
+    }
+
     /// Validate request size limits and input format (DoS Protection)
-    pub fn validate(&self) -> crate::types::Result<()> {
-        use crate::types::validation::{validate_json_structure, validate_label};
-        use crate::types::GoudChainError;
+    /// `max_payload_bytes` is the node's configured limit (`Config::max_payload_bytes`).
+    /// Every violation is reported at once as `ValidationFailed`
+    pub fn validate(&self, max_payload_bytes: usize) -> crate::types::Result<()> {
+        let errors = self.field_errors(max_payload_bytes);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(crate::types::GoudChainError::ValidationFailed { errors })
+        }
+    }
 
-        const MAX_COLLECTION_SIZE_BYTES: usize = 10_000_000; // 10MB
-        const MAX_LABEL_LENGTH: usize = 100;
+    /// Every rule this request breaks, in field order (empty when it is valid)
+    pub fn field_errors(&self, max_payload_bytes: usize) -> Vec<FieldError> {
+        use crate::constants::{BINARY_CONTENT_TYPE, DEFAULT_CONTENT_TYPE, MAX_LABEL_LENGTH};
+        use crate::types::validation::{
+            media_type_essence, validate_base64_payload, validate_content_type,
+            validate_json_structure, validate_label,
+        };
 
-        validate_label(&self.label)?;
+        let mut errors = Vec::new();
 
+        if let Err(e) = validate_label(&self.label) {
+            errors.push(FieldError::from_error("label", e));
+        }
         if self.label.len() > MAX_LABEL_LENGTH {
-            return Err(GoudChainError::PayloadTooLarge {
-                actual_bytes: self.label.len(),
-                max_bytes: MAX_LABEL_LENGTH,
-            });
+            errors.push(
+                FieldError::new("label", format!("exceeds {} characters", MAX_LABEL_LENGTH))
+                    .with_limit(MAX_LABEL_LENGTH),
+            );
         }
 
-        validate_json_structure(&self.data)?;
+        // Only the two well-known types have a payload format we can check
+        let content_type = self.content_type();
+        match validate_content_type(content_type) {
+            Ok(()) => {
+                let format_check = match media_type_essence(content_type).as_str() {
+                    DEFAULT_CONTENT_TYPE => validate_json_structure(&self.data),
+                    BINARY_CONTENT_TYPE => validate_base64_payload(&self.data),
+                    _ => Ok(()),
+                };
+                if let Err(e) = format_check {
+                    errors.push(FieldError::from_error("data", e));
+                }
+            }
+            Err(e) => errors.push(FieldError::from_error("content_type", e)),
+        }
 
-        let data_size = self.data.len();
-        if data_size > MAX_COLLECTION_SIZE_BYTES {
-            return Err(GoudChainError::PayloadTooLarge {
-                actual_bytes: data_size,
-                max_bytes: MAX_COLLECTION_SIZE_BYTES,
-            });
+        if self.data.len() > max_payload_bytes {
+            errors.push(
+                FieldError::new("data", format!("exceeds {} bytes", max_payload_bytes))
+                    .with_limit(max_payload_bytes),
+            );
         }
 
-        Ok(())
+        if self.expected_parent_version.is_some() && self.parent_collection_id.is_none() {
+            errors.push(FieldError::new(
+                "expected_parent_version",
+                "requires parent_collection_id",
+            ));
+        }
+
+        if let Some(expires_at) = self.expires_at {
+            if expires_at <= chrono::Utc::now().timestamp() {
+                errors.push(FieldError::new("expires_at", "is not in the future"));
+            }
+        }
+
+        errors
     }
 }
 
+/// Collection share request
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ShareCollectionRequest {
+    /// Recipient's X25519 sharing public key (hex), from their `GET /data/sharing_key`
+    #[schema(example = "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")]
+    pub recipient_public_key: String,
+}
+
+/// One sub-request of a `POST /batch` call
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchRequestItem {
+    /// HTTP method (`GET`, `POST`, `PUT` or `DELETE`)
+    #[schema(example = "POST")]
+    pub method: String,
+
+    /// Endpoint path, optionally with a query string (`/batch` itself is not allowed)
+    #[schema(example = "/data/submit")]
+    pub path: String,
+
+    /// JSON request body (omit for requests without one)
+    #[schema(example = json!({"label": "notes", "data": "{\"text\": \"hello\"}"}))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+}
+
 // ========== RESPONSE SCHEMAS ==========
 
 /// Standard message response
@@ -109,6 +307,428 @@ pub struct HealthCheckResponse {
     /// Latest block index
     #[schema(example = 41)]
     pub latest_block: u64,
+
+    /// True while a peer has reported a block beyond our latest block
+    #[schema(example = false)]
+    pub is_syncing: bool,
+
+    /// Collections waiting to be included in the next block
+    #[schema(example = 0)]
+    pub pending_collections: usize,
+
+    /// Accounts waiting to be included in the next block
+    #[schema(example = 0)]
+    pub pending_accounts: usize,
+
+    /// Whether this node is the authorized validator for the next block
+    #[schema(example = true)]
+    pub is_current_validator: bool,
+
+    /// Index of the next block to be created
+    #[schema(example = 42)]
+    pub next_block_number: u64,
+}
+
+/// Liveness probe response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LivenessResponse {
+    /// Always "alive" when the process is responding
+    #[schema(example = "alive")]
+    pub status: String,
+}
+
+/// Readiness probe response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReadinessResponse {
+    /// "ready" or "syncing"
+    #[schema(example = "ready")]
+    pub status: String,
+
+    /// Latest block index
+    #[schema(example = 41)]
+    pub latest_block: u64,
+
+    /// Highest block index seen from any peer (0 until a peer reports one)
+    #[schema(example = 41)]
+    pub best_peer_height: u64,
+
+    /// Unix timestamp of the last sync that caught up with a peer
+    #[schema(example = 1704067200)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_successful_sync: Option<i64>,
+}
+
+/// One block slot in the validator rotation
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ScheduledValidator {
+    /// Block number this validator will produce
+    #[schema(example = 42)]
+    pub block_number: u64,
+
+    /// Validator responsible for the block
+    #[schema(example = "Validator_1")]
+    pub validator: String,
+
+    /// Node running the validator, if it is mapped
+    #[schema(example = "node1")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+
+    /// Address writes for this block should be sent to, if known
+    #[schema(example = "node1:8080")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validator_address: Option<String>,
+}
+
+/// Writes buffered on this node, waiting for the next block (GET /pending)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PendingPoolResponse {
+    /// Index of the next block to be created
+    #[schema(example = 42)]
+    pub next_block_number: u64,
+
+    /// Validator scheduled to create the next block
+    #[schema(example = "Validator_1")]
+    pub expected_validator: String,
+
+    /// Whether this node creates the next block (a node that doesn't should have nothing pending)
+    #[schema(example = true)]
+    pub is_this_node_validator: bool,
+
+    /// Whether this node is in read-only maintenance mode (pending data waits until it ends)
+    #[schema(example = false)]
+    pub read_only: bool,
+
+    /// Seconds until coalesced submissions are sealed (absent unless MIN_BLOCK_INTERVAL_SECONDS is set)
+    #[schema(example = json!(null))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seconds_until_seal: Option<u64>,
+
+    /// Account IDs waiting to be written
+    pub pending_accounts: Vec<String>,
+
+    /// Collections waiting to be written
+    pub pending_collections: Vec<PendingCollection>,
+}
+
+/// A collection waiting for the next block (contents stay encrypted)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PendingCollection {
+    /// Collection ID
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub collection_id: String,
+
+    /// Serialized size the collection adds to the block
+    #[schema(example = 512)]
+    pub size_bytes: usize,
+
+    /// Whether it revises an earlier collection
+    #[schema(example = false)]
+    pub revision: bool,
+
+    /// Whether it is a share grant or grant revocation
+    #[schema(example = false)]
+    pub share_grant: bool,
+}
+
+/// Request limits enforced by this node
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LimitsResponse {
+    /// Largest collection payload (`data` field) accepted, in bytes
+    #[schema(example = 10000000)]
+    pub max_payload_bytes: usize,
+
+    /// Largest HTTP request body accepted, in bytes
+    #[schema(example = 20000000)]
+    pub max_request_body_bytes: usize,
+
+    /// Largest /data/submit_batch request body accepted, in bytes
+    #[schema(example = 40000000)]
+    pub max_batch_request_body_bytes: usize,
+
+    /// Longest collection label accepted, in bytes
+    #[schema(example = 100)]
+    pub max_label_length: usize,
+
+    /// Collections accepted per /data/submit_batch call
+    #[schema(example = 100)]
+    pub max_batch_items: usize,
+
+    /// Sub-requests accepted per /batch call
+    #[schema(example = 20)]
+    pub max_batch_requests: usize,
+
+    /// Longest a request may run before it is answered with 504, in seconds
+    #[schema(example = 30)]
+    pub request_timeout_seconds: u64,
+
+    /// Rate limits per endpoint group
+    pub rate_limits: RateLimitsInfo,
+}
+
+/// Checkpoint verification report (GET /admin/verify)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct VerifyCheckpointsResponse {
+    /// True when every checkpoint matches the stored chain
+    pub valid: bool,
+
+    /// Stored chain length
+    #[schema(example = 250)]
+    pub chain_length: u64,
+
+    /// Checkpoints checked (expected indexes plus any stray stored checkpoints)
+    #[schema(example = 2)]
+    pub checked: usize,
+
+    /// Checkpoints that do not match the stored chain
+    pub mismatches: Vec<CheckpointMismatchInfo>,
+}
+
+impl From<CheckpointReport> for VerifyCheckpointsResponse {
+    fn from(report: CheckpointReport) -> Self {
+        Self {
+            valid: report.is_valid(),
+            chain_length: report.chain_length,
+            checked: report.checked,
+            mismatches: report.mismatches.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A checkpoint that does not match the stored chain
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CheckpointMismatchInfo {
+    /// Block index of the checkpoint
+    #[schema(example = 100)]
+    pub index: u64,
+
+    /// Persisted checkpoint hash (absent if the checkpoint is missing)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint_hash: Option<String>,
+
+    /// Hash recomputed from the stored block (absent if the block is missing or unreadable)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<String>,
+
+    /// What does not match
+    #[schema(example = "Checkpoint does not match block hash")]
+    pub reason: String,
+}
+
+impl From<CheckpointMismatch> for CheckpointMismatchInfo {
+    fn from(mismatch: CheckpointMismatch) -> Self {
+        Self {
+            index: mismatch.index,
+            checkpoint_hash: mismatch.checkpoint_hash,
+            block_hash: mismatch.block_hash,
+            reason: mismatch.reason,
+        }
+    }
+}
+
+/// Checkpoint created on demand (POST /admin/checkpoint)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CheckpointResponse {
+    /// Block index of the checkpoint (the chain tip when it was taken)
+    #[schema(example = 57)]
+    pub index: u64,
+
+    /// Hash of the checkpointed block
+    #[schema(example = "9c1e...")]
+    pub hash: String,
+
+    /// Checkpoints this node now holds
+    #[schema(example = 1)]
+    pub checkpoint_count: usize,
+
+    /// Blocks between automatic checkpoints (CHECKPOINT_INTERVAL)
+    #[schema(example = 100)]
+    pub checkpoint_interval: u64,
+}
+
+/// Switch read-only maintenance mode (POST /admin/readonly)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReadOnlyModeRequest {
+    /// true refuses writes with 503, false resumes them
+    #[schema(example = true)]
+    pub read_only: bool,
+}
+
+/// Maintenance mode after a switch (POST /admin/readonly)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReadOnlyModeResponse {
+    /// Whether this node now refuses writes
+    #[schema(example = true)]
+    pub read_only: bool,
+
+    /// Collections accepted before the switch, written in the next block once writes resume
+    #[schema(example = 0)]
+    pub pending_collections: usize,
+}
+
+/// Completed database backup (POST /admin/backup)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BackupResponse {
+    /// Backup ID (always increasing; restore uses the latest)
+    #[schema(example = 3)]
+    pub backup_id: u32,
+
+    /// When the backup was taken (Unix seconds)
+    #[schema(example = 1704067200)]
+    pub timestamp: i64,
+
+    /// Backup size in bytes (files shared with older backups count towards each)
+    #[schema(example = 5242880)]
+    pub size_bytes: u64,
+
+    /// Files in the backup
+    #[schema(example = 12)]
+    pub num_files: u32,
+}
+
+impl From<BackupInfo> for BackupResponse {
+    fn from(backup: BackupInfo) -> Self {
+        Self {
+            backup_id: backup.backup_id,
+            timestamp: backup.timestamp,
+            size_bytes: backup.size_bytes,
+            num_files: backup.num_files,
+        }
+    }
+}
+
+/// An API key's active rate-limit ban
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BanInfo {
+    /// Penalty level (WriteBlock5Min, WriteBlock1Hr, PermanentWriteBan or CompleteBlacklist)
+    #[schema(example = "WriteBlock1Hr")]
+    pub level: String,
+
+    /// When the ban was applied (Unix seconds)
+    #[schema(example = 1704067200)]
+    pub created_at: i64,
+
+    /// When the ban lifts on its own (Unix seconds, null for permanent bans)
+    #[schema(example = 1704070800)]
+    pub expires_at: Option<i64>,
+}
+
+impl From<BanRecord> for BanInfo {
+    fn from(ban: BanRecord) -> Self {
+        Self {
+            level: format!("{:?}", ban.level),
+            created_at: ban.created_at,
+            expires_at: ban.expires_at,
+        }
+    }
+}
+
+/// Rate-limit violations that escalate an API key's penalties
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ViolationInfo {
+    /// Violations recorded since the history was last cleared
+    #[schema(example = 3)]
+    pub count: u32,
+
+    /// Most recent violation (Unix seconds, 0 if none)
+    #[schema(example = 1704067200)]
+    pub last_violation_timestamp: i64,
+
+    /// Times of the last five violations (Unix seconds)
+    pub violation_timestamps: Vec<i64>,
+}
+
+impl From<ViolationRecord> for ViolationInfo {
+    fn from(violations: ViolationRecord) -> Self {
+        Self {
+            count: violations.count,
+            last_violation_timestamp: violations.last_violation_timestamp,
+            violation_timestamps: violations.violation_timestamps,
+        }
+    }
+}
+
+/// Ban status of an API key (GET /admin/bans/{api_key_hash})
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BanStatusResponse {
+    #[schema(example = "a3f5...")]
+    pub api_key_hash: String,
+
+    /// Active ban (null if the key is not banned or its ban has expired)
+    pub ban: Option<BanInfo>,
+
+    pub violations: ViolationInfo,
+}
+
+/// Result of lifting a ban (DELETE /admin/bans/{api_key_hash})
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ClearBanResponse {
+    #[schema(example = "a3f5...")]
+    pub api_key_hash: String,
+
+    /// Ban that was lifted (null if the key was not banned)
+    pub cleared_ban: Option<BanInfo>,
+
+    /// Violations dropped from the key's history
+    #[schema(example = 3)]
+    pub cleared_violations: u32,
+}
+
+/// Per-API-key rate limits (requests per second for each endpoint group)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RateLimitsInfo {
+    /// `sliding_window` or `token_bucket`
+    #[schema(example = "sliding_window")]
+    pub mode: String,
+
+    /// Token bucket capacity in seconds of traffic at the per-second limit
+    #[schema(example = 3)]
+    pub burst_seconds: u32,
+
+    #[schema(example = 10)]
+    pub submit_per_second: u32,
+
+    #[schema(example = 10)]
+    pub batch_per_second: u32,
+
+    #[schema(example = 100)]
+    pub list_per_second: u32,
+
+    #[schema(example = 100)]
+    pub search_per_second: u32,
+
+    #[schema(example = 5)]
+    pub decrypt_per_second: u32,
+
+    #[schema(example = 10)]
+    pub delete_per_second: u32,
+
+    /// Keyed by client IP rather than API key
+    #[schema(example = 2)]
+    pub account_create_per_second: u32,
+
+    /// Keyed by client IP rather than API key
+    #[schema(example = 1)]
+    pub account_exists_per_second: u32,
+
+    /// Public reads such as `/chain`, `/peers`, `/stats` and `/metrics`, keyed by client IP
+    #[schema(example = 20)]
+    pub public_per_second: u32,
+
+    /// API key hashes computed for one client IP, across every endpoint
+    #[schema(example = 50)]
+    pub key_hash_per_second: u32,
+}
+
+/// Upcoming validator rotation
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ValidatorScheduleResponse {
+    /// Index of the latest block on this node
+    #[schema(example = 41)]
+    pub current_block_number: u64,
+
+    /// Validators for the next blocks, starting at `current_block_number + 1`
+    pub schedule: Vec<ScheduledValidator>,
 }
 
 /// Account creation response
@@ -132,7 +752,7 @@ pub struct CreateAccountResponse {
 /// Login response with session token
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct LoginResponse {
-    /// JWT session token (expires in 1 hour)
+    /// JWT session token (expires after `expires_in` seconds)
     #[schema(example = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9...")]
     pub session_token: String,
 
@@ -143,6 +763,29 @@ pub struct LoginResponse {
     /// Account ID
     #[schema(example = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8")]
     pub account_id: String,
+
+    /// Access granted to the session token
+    pub scope: TokenScope,
+
+    /// Whether this is a long-lived read-only "remember me" token
+    #[schema(example = false)]
+    pub remember_me: bool,
+}
+
+/// Public keys published for an account (never includes private material or the API key hash)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AccountPublicKeyResponse {
+    /// Account ID
+    #[schema(example = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8")]
+    pub account_id: String,
+
+    /// Client Ed25519 public key (hex) that verifies the account's signed requests, if registered
+    #[schema(example = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")]
+    pub public_key: Option<String>,
+
+    /// X25519 sharing public key (hex) for `POST /data/{collection_id}/share`
+    #[schema(example = "8f40c5adb68f25624ae5b214ea767a6ec94d829d3d7b5e1ad1ba6f3e2138285f")]
+    pub sharing_public_key: String,
 }
 
 /// Data submission response
@@ -155,9 +798,63 @@ pub struct SubmitDataResponse {
     #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
     pub collection_id: String,
 
-    /// Block number where data was stored
+    /// Block number where data was stored (the block it is expected in while `pending`)
+    #[schema(example = 42)]
+    pub block_number: u64,
+
+    /// True when the node coalesces blocks (MIN_BLOCK_INTERVAL_SECONDS) and the data is queued
+    /// for the next block rather than already in one; the queue is in memory only, so a node
+    /// crash before the block is sealed loses it
+    #[schema(example = false)]
+    pub pending: bool,
+}
+
+/// Dry-run data submission response (nothing is written to the chain)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SubmitDataDryRunResponse {
+    /// Success message
+    pub message: String,
+
+    /// Collection ID the submission was encrypted under (not reserved: a real submission gets a new one)
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub collection_id: String,
+
+    /// Serialized size of the encrypted collection as it would be stored in a block
+    #[schema(example = 1024)]
+    pub encrypted_size_bytes: usize,
+}
+
+/// Batch data submission response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SubmitBatchResponse {
+    /// Success message
+    #[schema(example = "3 collections encrypted and stored successfully")]
+    pub message: String,
+
+    /// Collection IDs in the same order as the submitted items
+    #[schema(example = json!(["550e8400-e29b-41d4-a716-446655440000", "660e8400-e29b-41d4-a716-446655440000"]))]
+    pub collection_ids: Vec<String>,
+
+    /// Block number containing every collection in the batch (expected block while `pending`)
     #[schema(example = 42)]
     pub block_number: u64,
+
+    /// True when the batch is queued for the next coalesced block rather than already in one
+    /// (in memory only until sealed, so a node crash before then loses it)
+    #[schema(example = false)]
+    pub pending: bool,
+}
+
+/// Result of one `POST /batch` sub-request
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchResponseItem {
+    /// HTTP status code the endpoint answered with
+    #[schema(example = 201)]
+    pub status: u16,
+
+    /// Response body: parsed JSON, a string for non-JSON bodies, or `null` if empty
+    #[schema(example = json!({"collection_id": "550e8400-e29b-41d4-a716-446655440000", "label": "notes", "block_number": 7}))]
+    pub body: serde_json::Value,
 }
 
 /// Collection list item (metadata only, no decrypted data)
@@ -178,6 +875,19 @@ pub struct CollectionListItem {
     /// Block number where collection was created
     #[schema(example = 42)]
     pub block_number: u64,
+
+    /// Revision number within the collection's version chain (1 = first version)
+    #[schema(example = 3)]
+    pub version: usize,
+
+    /// Collection ID of the previous revision (absent for the first version)
+    #[schema(example = "440e8400-e29b-41d4-a716-446655440000")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_collection_id: Option<String>,
+
+    /// True for a read-only copy another account shared with you (`collection_id` is the grant ID)
+    #[schema(example = false)]
+    pub shared: bool,
 }
 
 /// Collection list response
@@ -198,15 +908,188 @@ pub struct DecryptCollectionResponse {
     #[schema(example = "medical-records")]
     pub label: String,
 
-    /// Decrypted data (JSON string)
-    #[schema(example = r#"{"diagnosis": "healthy", "date": "2025-01-15"}"#)]
-    pub data: String,
+    /// Decrypted data: the parsed value for `application/json`, otherwise a string (see `encoding`)
+    #[schema(example = json!({"diagnosis": "healthy", "date": "2025-01-15"}))]
+    pub data: serde_json::Value,
+
+    /// How `data` is encoded: `json` (parsed value), `text` (UTF-8 string) or `base64`
+    #[schema(example = "json")]
+    pub encoding: String,
+
+    /// MIME type recorded at submission (`application/json` for legacy collections)
+    #[schema(example = "application/json")]
+    pub content_type: String,
+
+    /// Unix timestamp after which the collection stops being returned (absent = never expires)
+    #[schema(example = json!(null))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+
+    /// True for a read-only copy another account shared with you
+    #[schema(example = false)]
+    pub shared: bool,
 
     /// Creation timestamp (Unix timestamp)
     #[schema(example = 1705318200)]
     pub created_at: i64,
 }
 
+/// Encrypted collection envelope returned without server-side decryption
+/// Metadata and payload are AES-256-GCM ciphertexts under the key derived from the owner's API key
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CollectionEnvelopeResponse {
+    /// Collection ID
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub collection_id: String,
+
+    /// Encrypted metadata (label, tags, created_at, content_type, expires_at)
+    pub encrypted_metadata: String,
+
+    /// Encrypted payload
+    pub encrypted_payload: String,
+
+    /// HMAC over the ciphertexts (hex)
+    pub mac: String,
+
+    /// Encryption nonce (hex)
+    pub nonce: String,
+
+    /// Per-collection salt (hex)
+    pub user_salt: String,
+
+    /// Ed25519 signature by the submitting account (hex)
+    pub signature: String,
+
+    /// Public key the signature verifies against (hex)
+    pub public_key: String,
+
+    /// Codec applied to the payload before encryption: `none` or `zstd`
+    #[schema(example = "none")]
+    pub compression: String,
+
+    /// Label blind index used for exact-match search (absent for legacy collections)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_index: Option<String>,
+
+    /// Previous revision this collection supersedes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_collection_id: Option<String>,
+
+    /// Block containing the collection
+    #[schema(example = 42)]
+    pub block_number: u64,
+}
+
+/// Collection deletion response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DeleteCollectionResponse {
+    /// Success message
+    pub message: String,
+
+    /// Deleted collection ID
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub collection_id: String,
+
+    /// Block number where the tombstone was stored
+    #[schema(example = 43)]
+    pub block_number: u64,
+}
+
+/// Account metadata update response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UpdateAccountMetadataResponse {
+    /// Success message
+    pub message: String,
+
+    /// Account whose metadata was replaced
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub account_id: String,
+
+    /// Block number where the new account revision was stored
+    #[schema(example = 44)]
+    pub block_number: u64,
+}
+
+/// API key rotation response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RotateApiKeyResponse {
+    /// Account whose key was rotated (unchanged)
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub account_id: String,
+
+    /// New API key (base64-encoded, shown only once - store securely)
+    #[schema(example = "Z291ZF9hYmMxMjM0NTY3ODkw")]
+    pub api_key: String,
+
+    /// Block number holding the re-keyed account and collections
+    #[schema(example = 45)]
+    pub block_number: u64,
+
+    /// Collections re-encrypted under the new key (IDs are unchanged)
+    #[schema(example = 3)]
+    pub reencrypted_collections: usize,
+
+    /// Outgoing shares re-issued under the new key (old grant IDs are revoked)
+    pub regranted_shares: Vec<RegrantedShareInfo>,
+
+    /// Security warning message
+    #[schema(example = "SAVE THIS API KEY SECURELY. The previous key no longer authenticates.")]
+    pub warning: String,
+}
+
+/// A share re-issued during key rotation
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RegrantedShareInfo {
+    /// Shared collection ID
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub collection_id: String,
+
+    /// Revoked grant ID under the previous key
+    #[schema(example = "750e8400-e29b-41d4-a716-446655440000")]
+    pub previous_grant_id: String,
+
+    /// Replacement grant ID (use it to revoke the share later)
+    #[schema(example = "850e8400-e29b-41d4-a716-446655440000")]
+    pub grant_id: String,
+}
+
+impl From<RegrantedShare> for RegrantedShareInfo {
+    fn from(share: RegrantedShare) -> Self {
+        Self {
+            collection_id: share.collection_id,
+            previous_grant_id: share.previous_grant_id,
+            grant_id: share.grant_id,
+        }
+    }
+}
+
+/// Share grant response (creation and revocation)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ShareGrantResponse {
+    /// Success message
+    pub message: String,
+
+    /// Grant ID (the recipient's handle for the shared copy)
+    #[schema(example = "750e8400-e29b-41d4-a716-446655440000")]
+    pub grant_id: String,
+
+    /// Shared collection ID
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub collection_id: String,
+
+    /// Block number where the grant (or its revocation) was stored
+    #[schema(example = 44)]
+    pub block_number: u64,
+}
+
+/// Sharing key response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SharingKeyResponse {
+    /// X25519 public key (hex) other accounts share collections to
+    #[schema(example = "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")]
+    pub sharing_public_key: String,
+}
+
 /// Peer information response
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct PeerInfoResponse {
@@ -218,9 +1101,83 @@ pub struct PeerInfoResponse {
     #[schema(example = 2)]
     pub count: usize,
 
-    /// Peer reputation scores (positive = trusted, negative = suspicious)
-    #[schema(example = json!({"node2:9000": 10, "node3:9000": 5}))]
-    pub reputation: HashMap<String, i32>,
+    /// Per-peer reputation and liveness (active and parked peers)
+    #[schema(example = json!({"node2:9000": {"score": 10, "last_seen": 1704067200, "rtt_ms": 3}}))]
+    pub reputation: HashMap<String, PeerReputation>,
+
+    /// Peers parked after failing heartbeats (pinged periodically and restored when they answer)
+    #[schema(example = json!(["node4:9000"]))]
+    pub parked: Vec<String>,
+}
+
+/// Reputation and heartbeat status for a single peer
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PeerReputation {
+    /// Reputation score (positive = trusted, negative = suspicious)
+    #[schema(example = 10)]
+    pub score: i32,
+
+    /// Unix timestamp of the last answered ping (absent until the peer answers one)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 1704067200)]
+    pub last_seen: Option<i64>,
+
+    /// Round-trip time of the last answered ping in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 3)]
+    pub rtt_ms: Option<u64>,
+}
+
+/// Block header (block metadata without the encrypted envelope bodies)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BlockHeader {
+    /// Block number
+    #[schema(example = 42)]
+    pub index: u64,
+
+    /// Block hash
+    #[schema(example = "00a1b2c3d4e5f6...")]
+    pub hash: String,
+
+    /// Hash of the previous block
+    #[schema(example = "009f8e7d6c5b4a...")]
+    pub previous_hash: String,
+
+    /// Unix timestamp of block creation
+    #[schema(example = 1704067200)]
+    pub timestamp: i64,
+
+    /// Node that created the block
+    #[schema(example = "Validator_1")]
+    pub validator: String,
+
+    /// Merkle root of the block's encrypted collections
+    #[schema(example = "5f4dcc3b5aa765...")]
+    pub merkle_root: String,
+}
+
+/// Paginated window of block headers
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ChainPageResponse {
+    /// Node identifier
+    #[schema(example = "node1")]
+    pub node_id: String,
+
+    /// Total blocks in chain
+    #[schema(example = 250)]
+    pub chain_length: usize,
+
+    /// Index of the first block in this window
+    #[schema(example = 0)]
+    pub start: u64,
+
+    /// Block headers in ascending index order
+    pub blocks: Vec<BlockHeader>,
+
+    /// Start index of the next window (absent at the chain tip)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 100)]
+    pub next_start: Option<u64>,
 }
 
 /// Chain statistics response
@@ -245,6 +1202,26 @@ pub struct ChainStatsResponse {
     /// Block distribution by validator (node_id -> block_count)
     #[schema(example = json!({"node1": 50, "node2": 30, "node3": 20}))]
     pub validator_distribution: HashMap<String, u64>,
+
+    /// Blocks each validator's rotation slots called for versus blocks it actually produced
+    /// Includes configured validators that have produced nothing yet
+    pub validator_production: BTreeMap<String, ValidatorProduction>,
+}
+
+/// Expected versus actual block production for one validator
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct ValidatorProduction {
+    /// Blocks in the chain whose rotation slot belongs to this validator
+    #[schema(example = 34)]
+    pub expected_blocks: u64,
+
+    /// Blocks in the chain produced by this validator
+    #[schema(example = 33)]
+    pub actual_blocks: u64,
+
+    /// Slots of this validator filled by another validator (expected minus actual, floored at 0)
+    #[schema(example = 1)]
+    pub missed_slots: u64,
 }
 
 /// Node performance metrics
@@ -278,14 +1255,26 @@ pub struct NodeMetricsResponse {
     #[schema(example = 200)]
     pub total_operations: u64,
 
-    /// Merkle tree cache hit rate (0.0 - 1.0)
+    /// Derived key cache hit rate (0.0 - 1.0)
     #[schema(example = 0.85)]
     pub cache_hit_rate: f64,
 
-    /// Operations processed per second (placeholder)
-    #[schema(example = 0.0)]
+    /// Derived key cache entries dropped because the cache was full
+    #[schema(example = 0)]
+    pub cache_evictions: u64,
+
+    /// Derived key cache entries dropped after exceeding their TTL
+    #[schema(example = 12)]
+    pub cache_expirations: u64,
+
+    /// Account and collection writes per second, averaged over the last 60 seconds
+    #[schema(example = 1.5)]
     pub operations_per_second: f64,
 
+    /// WebSocket connections dropped for falling too far behind on events
+    #[schema(example = 0)]
+    pub dropped_slow_consumers: u64,
+
     /// Volume storage metrics
     #[schema(example = json!({"disk_used_bytes": 1048576, "disk_used_mb": 1, "mount_path": "/data", "rocksdb_present": true}))]
     pub volume_metrics: Option<VolumeMetrics>,
@@ -318,13 +1307,106 @@ pub struct VolumeMetrics {
 /// API error response
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ErrorResponse {
-    /// Error message
-    #[schema(example = "Invalid API key")]
+    /// Error message (for humans; may change between releases)
+    #[schema(example = "Unauthorized: Account not found")]
     pub error: String,
+
+    /// Stable machine-readable error code
+    #[schema(example = "ACCOUNT_NOT_FOUND")]
+    pub code: String,
+
+    /// Structured fields for errors that carry them (e.g. `retry_after` when rate limited)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"retry_after": 3, "violation_count": 2}))]
+    pub details: Option<serde_json::Value>,
 }
 
 // ========== QUERY PARAMETERS ==========
 
+/// Chain window query parameters
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ChainQuery {
+    /// Index of the first block to return (default: 0)
+    #[param(example = 0, minimum = 0)]
+    pub start: Option<u64>,
+
+    /// Maximum blocks to return (1-1000, default: 100)
+    #[param(example = 100, minimum = 1, maximum = 1000)]
+    pub limit: Option<usize>,
+
+    /// Return the complete blockchain instead of headers (chains up to 1000 blocks only)
+    #[param(example = false)]
+    pub full: Option<bool>,
+}
+
+/// Validator schedule query parameters
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ValidatorScheduleQuery {
+    /// Number of upcoming blocks to include (1-100, default: 10)
+    #[param(example = 10, minimum = 1, maximum = 100)]
+    pub count: Option<u64>,
+}
+
+/// Data submission query parameters
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SubmitDataQuery {
+    /// Validate and encrypt without storing anything (default: false)
+    #[param(example = false)]
+    pub dry_run: Option<bool>,
+}
+
+/// Collection list query parameters
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct CollectionListQuery {
+    /// Include superseded revisions (default: only the latest revision of each collection)
+    #[param(example = false)]
+    pub include_history: Option<bool>,
+
+    /// Page number (zero-indexed)
+    #[param(example = 0, minimum = 0)]
+    pub page: Option<usize>,
+
+    /// Page size (1-500)
+    #[param(example = 100, minimum = 1, maximum = 500)]
+    pub page_size: Option<usize>,
+}
+
+/// Collection search query parameters
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct CollectionSearchQuery {
+    /// Exact label to match
+    #[param(example = "medical-records")]
+    pub label: String,
+
+    /// Include superseded revisions (default: only the latest revision of each collection)
+    #[param(example = false)]
+    pub include_history: Option<bool>,
+}
+
+/// Single-collection decryption query parameters
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DecryptQuery {
+    /// Return the payload as the response body with its stored Content-Type instead of JSON
+    #[param(example = false)]
+    pub raw: Option<bool>,
+}
+
+/// Bulk decryption query parameters
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DecryptAllQuery {
+    /// Comma-separated labels to include (default: all labels)
+    #[param(example = "medical-records,insurance")]
+    pub labels: Option<String>,
+
+    /// Page number (zero-indexed)
+    #[param(example = 0, minimum = 0)]
+    pub page: Option<usize>,
+
+    /// Page size (1-100)
+    #[param(example = 50, minimum = 1, maximum = 100)]
+    pub page_size: Option<usize>,
+}
+
 /// Audit log query parameters
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct AuditLogQuery {
@@ -352,4 +1434,32 @@ pub struct AuditLogQuery {
     /// Page size (1-100)
     #[param(example = 50, minimum = 1, maximum = 100)]
     pub page_size: Option<usize>,
+
+    /// Include entries past the audit retention window (returned with `invalidated: true`)
+    #[param(example = false)]
+    pub include_invalidated: Option<bool>,
+}
+
+/// Query parameters for audit log export
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AuditExportQuery {
+    /// Export format: `csv` (default) or `json`
+    #[param(example = "csv")]
+    pub format: Option<String>,
+
+    /// Filter by event type
+    #[param(example = "DataSubmitted")]
+    pub event_type: Option<String>,
+
+    /// Start timestamp (Unix timestamp)
+    #[param(example = 1705318200)]
+    pub start_ts: Option<i64>,
+
+    /// End timestamp (Unix timestamp)
+    #[param(example = 1705404600)]
+    pub end_ts: Option<i64>,
+
+    /// Include entries past the audit retention window
+    #[param(example = false)]
+    pub include_invalidated: Option<bool>,
 }
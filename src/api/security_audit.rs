@@ -0,0 +1,260 @@
+//! Node-level security audit stream.
+//! Layer 5: Presentation - Records failed logins, rate-limit blocks and rejected credentials.
+//!
+//! These requests carry no trusted API key, so their audit entries are encrypted under
+//! a key derived from SESSION_SECRET rather than a user's key. Logging is best effort:
+//! failures are traced and never change the response sent to the client.
+
+use axum::extract::{ConnectInfo, Request};
+use axum::http::{HeaderMap, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use ipnet::IpNet;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use tracing::{error, warn};
+use zeroize::Zeroizing;
+
+use crate::api::auth::{extract_auth_from_headers, AuthMethod};
+use crate::api::rate_limiter::{RateLimitCategory, RateLimitResult, RateLimiter};
+use crate::api::schemas::SubmitDataState;
+use crate::config::Config;
+use crate::constants::ADMIN_TOKEN_HEADER;
+use crate::crypto::{constant_time_compare_bytes, derive_node_audit_key, hash_api_key_hex};
+use crate::types::{AuditEventType, GoudChainError, Result};
+
+/// Resolve the client address of a request arriving from socket peer `peer`
+/// X-Real-IP / X-Forwarded-For are only believed when `peer` is a trusted proxy; otherwise
+/// any client could pick the address its rate limits, bans and audit throttles are keyed on.
+/// In X-Forwarded-For the rightmost address that is not a trusted proxy is the client.
+pub fn resolve_client_ip(peer: IpAddr, headers: &HeaderMap, trusted_proxies: &[IpNet]) -> IpAddr {
+    let is_trusted = |ip: &IpAddr| trusted_proxies.iter().any(|net| net.contains(ip));
+    if !is_trusted(&peer) {
+        return peer;
+    }
+
+    let real_ip = headers
+        .get("x-real-ip")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok());
+    if let Some(ip) = real_ip {
+        return ip;
+    }
+
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|chain| {
+            chain
+                .rsplit(',')
+                .filter_map(|hop| hop.trim().parse::<IpAddr>().ok())
+                .find(|ip| !is_trusted(ip))
+        })
+        .unwrap_or(peer)
+}
+
+/// Replace the forwarding headers with the resolved client address (see `resolve_client_ip`)
+/// Handlers read the client IP with `extract_client_ip`, so once this has run `X-Real-IP` only
+/// ever holds an address the node trusts. Requests without a socket peer (batch sub-requests
+/// dispatched in-process) keep the headers already resolved for their parent request.
+/// Needs the `Config` extension for TRUSTED_PROXIES; without it no proxy is trusted.
+pub async fn client_ip_middleware(mut request: Request, next: Next) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+
+    if let Some(peer) = peer {
+        let client_ip = match request.extensions().get::<Arc<Config>>() {
+            Some(config) => resolve_client_ip(peer, request.headers(), &config.trusted_proxies),
+            None => peer,
+        };
+        let headers = request.headers_mut();
+        headers.remove("x-forwarded-for");
+        if let Ok(value) = HeaderValue::from_str(&client_ip.to_string()) {
+            headers.insert("x-real-ip", value);
+        }
+    }
+
+    next.run(request).await
+}
+
+/// Extract client IP from headers (X-Real-IP or X-Forwarded-For)
+/// Behind `client_ip_middleware` this is the trusted client address
+pub fn extract_client_ip(headers: &HeaderMap) -> String {
+    if let Some(ip) = headers.get("x-real-ip") {
+        if let Ok(ip_str) = ip.to_str() {
+            return ip_str.to_string();
+        }
+    }
+
+    if let Some(forwarded) = headers.get("x-forwarded-for") {
+        if let Ok(forwarded_str) = forwarded.to_str() {
+            if let Some(first_ip) = forwarded_str.split(',').next() {
+                return first_ip.trim().to_string();
+            }
+        }
+    }
+
+    "unknown".to_string()
+}
+
+/// Record a security event in the node-level audit stream
+pub fn record_security_event(
+    state: &SubmitDataState,
+    config: &Config,
+    event_type: AuditEventType,
+    client_ip: &str,
+    metadata: serde_json::Value,
+) {
+    let node_audit_key = Zeroizing::new(derive_node_audit_key(&config.session_secret));
+    if let Err(e) =
+        state
+            .audit_logger
+            .log_security_event(&*node_audit_key, event_type, client_ip, metadata)
+    {
+        error!(error = %e, event_type = %event_type, "Failed to log security audit event");
+    }
+}
+
+/// Hash a request's API key for account lookup
+/// Hashing is slow by design (Argon2id, or 100k HKDF iterations for older keys), so each
+/// client IP may only start RATE_LIMIT_KEY_HASH_PER_SEC hashes (checked before any work),
+/// and the hash runs on the blocking pool instead of an async worker.
+pub async fn hash_request_api_key(
+    rate_limiter: &Arc<RateLimiter>,
+    client_ip: &str,
+    api_key: &[u8],
+) -> Result<String> {
+    let rate_limiter = Arc::clone(rate_limiter);
+    let client_ip = client_ip.to_string();
+    let api_key = Zeroizing::new(api_key.to_vec());
+
+    tokio::task::spawn_blocking(move || {
+        let result = rate_limiter
+            .check_ip_limit(&client_ip, RateLimitCategory::KeyHash)
+            .unwrap_or_else(|e| {
+                error!(error = %e, "Rate limit check failed, allowing request");
+                rate_limiter.fail_open(RateLimitCategory::KeyHash)
+            });
+        if let RateLimitResult::Blocked {
+            retry_after,
+            violation_count,
+            ..
+        } = result
+        {
+            warn!(client_ip = %client_ip, "API key hashing blocked by rate limiter");
+            return Err(GoudChainError::RateLimitExceeded {
+                retry_after,
+                violation_count,
+            });
+        }
+        Ok(hash_api_key_hex(&api_key))
+    })
+    .await
+    .map_err(|e| GoudChainError::Internal(format!("API key hashing task failed: {}", e)))?
+}
+
+/// Authenticate a request, recording an `Unauthorized` event when credentials are rejected
+pub fn authenticate(
+    headers: &HeaderMap,
+    config: &Config,
+    state: &SubmitDataState,
+) -> Result<AuthMethod> {
+    extract_auth_from_headers(headers, config, &state.token_revocations).inspect_err(|e| {
+        record_security_event(
+            state,
+            config,
+            AuditEventType::Unauthorized,
+            &extract_client_ip(headers),
+            serde_json::json!({"reason": e.to_string()}),
+        );
+    })
+}
+
+/// Check the operator credential (`X-Admin-Token` against ADMIN_TOKEN), recording an
+/// `Unauthorized` event when it is missing or wrong
+/// User API keys and session tokens are never accepted; without ADMIN_TOKEN the
+/// endpoints answer 404 as if they did not exist.
+pub fn authenticate_admin(
+    headers: &HeaderMap,
+    config: &Config,
+    state: &SubmitDataState,
+) -> Result<()> {
+    let Some(expected) = config.admin_token.as_deref() else {
+        return Err(GoudChainError::DataNotFound(
+            "Admin endpoints are disabled on this node (set ADMIN_TOKEN)".to_string(),
+        ));
+    };
+
+    let provided = headers
+        .get(ADMIN_TOKEN_HEADER)
+        .map(|value| value.as_bytes())
+        .unwrap_or_default();
+    if constant_time_compare_bytes(provided, expected) {
+        return Ok(());
+    }
+
+    let reason = if provided.is_empty() {
+        "Missing admin token"
+    } else {
+        "Invalid admin token"
+    };
+    record_security_event(
+        state,
+        config,
+        AuditEventType::Unauthorized,
+        &extract_client_ip(headers),
+        serde_json::json!({"reason": reason}),
+    );
+    Err(GoudChainError::Unauthorized(reason.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forwarded(real_ip: Option<&str>, forwarded_for: Option<&str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(ip) = real_ip {
+            headers.insert("x-real-ip", HeaderValue::from_str(ip).unwrap());
+        }
+        if let Some(chain) = forwarded_for {
+            headers.insert("x-forwarded-for", HeaderValue::from_str(chain).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_forwarding_headers_ignored_from_untrusted_peers() {
+        let peer: IpAddr = "203.0.113.7".parse().unwrap();
+        let headers = forwarded(Some("10.9.9.9"), Some("10.9.9.9"));
+
+        assert_eq!(resolve_client_ip(peer, &headers, &[]), peer);
+        let proxies = vec!["172.16.0.0/12".parse().unwrap()];
+        assert_eq!(resolve_client_ip(peer, &headers, &proxies), peer);
+    }
+
+    #[test]
+    fn test_forwarding_headers_believed_from_trusted_proxies() {
+        let proxy: IpAddr = "172.18.0.2".parse().unwrap();
+        let proxies = vec!["172.16.0.0/12".parse().unwrap()];
+
+        let headers = forwarded(Some("198.51.100.4"), None);
+        assert_eq!(
+            resolve_client_ip(proxy, &headers, &proxies),
+            "198.51.100.4".parse::<IpAddr>().unwrap()
+        );
+
+        // A client-supplied first hop is skipped: the rightmost untrusted hop is the client
+        let headers = forwarded(None, Some("10.9.9.9, 198.51.100.4, 172.18.0.3"));
+        assert_eq!(
+            resolve_client_ip(proxy, &headers, &proxies),
+            "198.51.100.4".parse::<IpAddr>().unwrap()
+        );
+
+        // Unparseable headers fall back to the proxy itself
+        let headers = forwarded(Some("not-an-ip"), None);
+        assert_eq!(resolve_client_ip(proxy, &headers, &proxies), proxy);
+    }
+}
@@ -0,0 +1,103 @@
+//! Request timeouts.
+//! Layer 5: Presentation - Caps how long an API request may run (REQUEST_TIMEOUT_SECONDS).
+//!
+//! `TimeoutLayer` drops a handler that runs past the limit and answers with a bare 504;
+//! `timeout_error_middleware`, layered outside it, turns that into the usual JSON error with code
+//! `REQUEST_TIMEOUT`. The timeout can only fire at await points, so long CPU-bound loops (bulk
+//! decryption) yield between items to stay cancellable.
+
+use axum::extract::Request;
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+use std::time::Duration;
+use tower_http::timeout::TimeoutLayer;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::constants::DEFAULT_REQUEST_TIMEOUT_SECONDS;
+use crate::types::GoudChainError;
+
+/// Timeout layer for the configured limit (answers 504 Gateway Timeout)
+pub fn timeout_layer(config: &Config) -> TimeoutLayer {
+    TimeoutLayer::with_status_code(
+        StatusCode::GATEWAY_TIMEOUT,
+        Duration::from_secs(config.request_timeout_seconds),
+    )
+}
+
+/// Replace the bare 504 `TimeoutLayer` produces with a `REQUEST_TIMEOUT` error body
+///
+/// Handlers never return an empty 504 themselves, so a 504 without a content type is a timeout.
+/// Reports the limit from the `Config` extension (the default limit without it).
+pub async fn timeout_error_middleware(request: Request, next: Next) -> Response {
+    let timeout_seconds = request
+        .extensions()
+        .get::<Arc<Config>>()
+        .map(|config| config.request_timeout_seconds);
+
+    let response = next.run(request).await;
+    if response.status() != StatusCode::GATEWAY_TIMEOUT
+        || response.headers().contains_key(header::CONTENT_TYPE)
+    {
+        return response;
+    }
+
+    let timeout_seconds = timeout_seconds.unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECONDS);
+    warn!(timeout_seconds = timeout_seconds, "Request timed out");
+    GoudChainError::RequestTimeout { timeout_seconds }.into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(10)).await;
+                    "done"
+                }),
+            )
+            .route("/fast", get(|| async { "done" }))
+            .layer(TimeoutLayer::with_status_code(
+                StatusCode::GATEWAY_TIMEOUT,
+                Duration::from_millis(50),
+            ))
+            .layer(middleware::from_fn(timeout_error_middleware))
+    }
+
+    #[tokio::test]
+    async fn test_slow_request_gets_timeout_error() {
+        let response = app()
+            .oneshot(Request::builder().uri("/slow").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "REQUEST_TIMEOUT");
+        // Without the Config extension the default limit is reported
+        assert_eq!(
+            body["details"]["timeout_seconds"],
+            crate::constants::DEFAULT_REQUEST_TIMEOUT_SECONDS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fast_request_is_untouched() {
+        let response = app()
+            .oneshot(Request::builder().uri("/fast").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,142 @@
+//! API versioning.
+//! Layer 5: Presentation - `/v1` path prefix and the deprecated unversioned aliases.
+//!
+//! Every API route is served under API_VERSION_PREFIX, which is what the OpenAPI spec documents.
+//! The same routes stay reachable without the prefix so existing clients keep working; those
+//! aliases appear in the spec as deprecated copies (struck through in RapiDoc), and their
+//! responses carry `Deprecation: true` plus a `Link` to the versioned path.
+
+use axum::extract::Request;
+use axum::http::{header, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use utoipa::openapi::path::Operation;
+use utoipa::openapi::{Deprecated, OpenApi};
+
+use crate::constants::{API_VERSION_PREFIX, DEPRECATION_HEADER};
+
+/// Add a deprecated, unversioned copy of every versioned path to the spec
+///
+/// Copies get an `_unversioned` operation ID suffix so generated SDKs keep unique method names.
+pub fn document_unversioned_aliases(openapi: &mut OpenApi) {
+    let aliases: Vec<_> = openapi
+        .paths
+        .paths
+        .iter()
+        .filter_map(|(path, item)| {
+            let alias = path.strip_prefix(API_VERSION_PREFIX)?;
+            if !alias.starts_with('/') {
+                return None;
+            }
+
+            let mut item = item.clone();
+            for operation in [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+                &mut item.options,
+                &mut item.head,
+                &mut item.patch,
+                &mut item.trace,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                deprecate(operation, path);
+            }
+            Some((alias.to_string(), item))
+        })
+        .collect();
+
+    openapi.paths.paths.extend(aliases);
+}
+
+fn deprecate(operation: &mut Operation, versioned_path: &str) {
+    operation.deprecated = Some(Deprecated::True);
+    if let Some(id) = operation.operation_id.as_mut() {
+        id.push_str("_unversioned");
+    }
+    let note = format!("Deprecated alias of `{}`.", versioned_path);
+    operation.description = Some(match operation.description.take() {
+        Some(description) => format!("{}\n\n{}", note, description),
+        None => note,
+    });
+}
+
+/// Mark responses from unversioned aliases deprecated and point at the versioned path
+pub async fn deprecated_alias_middleware(request: Request, next: Next) -> Response {
+    let successor = format!(
+        "<{}{}>; rel=\"successor-version\"",
+        API_VERSION_PREFIX,
+        request.uri().path()
+    );
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert(DEPRECATION_HEADER, HeaderValue::from_static("true"));
+    if let Ok(link) = HeaderValue::from_str(&successor) {
+        headers.insert(header::LINK, link);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use tower::ServiceExt;
+    use utoipa::openapi::path::{HttpMethod, OperationBuilder, PathItem};
+    use utoipa::openapi::{OpenApiBuilder, PathsBuilder};
+
+    #[test]
+    fn test_unversioned_aliases_are_documented_as_deprecated() {
+        let operation = OperationBuilder::new()
+            .operation_id(Some("list_data"))
+            .build();
+        let mut openapi = OpenApiBuilder::new()
+            .paths(
+                PathsBuilder::new()
+                    .path("/v1/data/list", PathItem::new(HttpMethod::Get, operation))
+                    .path(
+                        "/api-docs/openapi.json",
+                        PathItem::new(HttpMethod::Get, OperationBuilder::new().build()),
+                    ),
+            )
+            .build();
+
+        document_unversioned_aliases(&mut openapi);
+
+        let versioned = openapi.paths.paths["/v1/data/list"].get.as_ref().unwrap();
+        assert!(!matches!(versioned.deprecated, Some(Deprecated::True)));
+        assert_eq!(versioned.operation_id.as_deref(), Some("list_data"));
+
+        let alias = openapi.paths.paths["/data/list"].get.as_ref().unwrap();
+        assert!(matches!(alias.deprecated, Some(Deprecated::True)));
+        assert_eq!(alias.operation_id.as_deref(), Some("list_data_unversioned"));
+        assert_eq!(openapi.paths.paths.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_alias_responses_carry_deprecation_headers() {
+        let app = Router::new()
+            .route("/data/list", get(|| async { "ok" }))
+            .layer(middleware::from_fn(deprecated_alias_middleware));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/data/list?page=2")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[DEPRECATION_HEADER], "true");
+        assert_eq!(
+            response.headers()[header::LINK],
+            "</v1/data/list>; rel=\"successor-version\""
+        );
+    }
+}
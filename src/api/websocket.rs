@@ -1,22 +1,37 @@
 use axum::{
     extract::{
-        ws::{Message, WebSocket},
+        ws::{close_code, CloseFrame, Message, WebSocket},
         Query, WebSocketUpgrade,
     },
+    http::HeaderMap,
     response::Response,
     Extension,
 };
-use futures_util::{sink::SinkExt, stream::StreamExt};
+use futures_util::{
+    sink::SinkExt,
+    stream::{SplitSink, StreamExt},
+};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, watch, Mutex, Notify, RwLock};
+use tokio::time::Duration;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use crate::api::auth::{
+    ensure_api_key_kdf_accepted, ensure_token_not_revoked, verify_session_token,
+};
+use crate::api::rate_limiter::RateLimiter;
+use crate::api::schemas::NodeMetricsResponse;
+use crate::api::security_audit::{extract_client_ip, hash_request_api_key};
 use crate::config::Config;
+use crate::constants::{WEBSOCKET_CLOSE_TIMEOUT_SECONDS, WEBSOCKET_SEND_BUFFER};
 use crate::crypto::decode_api_key;
-use crate::types::Result;
+use crate::storage::TokenRevocationStore;
+use crate::types::{GoudChainError, Result};
 
 // ========== EVENT TYPES ==========
 
@@ -35,6 +50,35 @@ pub enum EventType {
     MetricsUpdate,
     /// New audit log entry
     AuditLogUpdate,
+    /// Chain tip replaced by a fork that rolled back some of our blocks
+    ChainReorg,
+}
+
+/// Optional narrowing of a subscription; every field that is set must match the event
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct SubscriptionFilter {
+    /// Only events about this account (API key hash); must be the connection's own account
+    pub account: Option<String>,
+    /// Only events about this collection
+    pub collection_id: Option<String>,
+}
+
+impl SubscriptionFilter {
+    /// Check whether an event falls inside this filter
+    /// Events that don't carry a filtered field never match it
+    fn matches(&self, scope: &EventScope) -> bool {
+        let field_matches =
+            |filter: &Option<String>, value: &Option<String>| filter.is_none() || filter == value;
+        field_matches(&self.account, &scope.account)
+            && field_matches(&self.collection_id, &scope.collection_id)
+    }
+}
+
+/// Who an event concerns, matched against subscription filters (never sent to clients)
+#[derive(Debug, Clone, Default)]
+struct EventScope {
+    account: Option<String>,
+    collection_id: Option<String>,
 }
 
 // ========== MESSAGE TYPES ==========
@@ -43,12 +87,18 @@ pub enum EventType {
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ClientMessage {
-    /// Subscribe to an event type
-    Subscribe { event: EventType },
+    /// Subscribe to an event type, optionally narrowed by a filter
+    Subscribe {
+        event: EventType,
+        #[serde(default)]
+        filter: SubscriptionFilter,
+    },
     /// Unsubscribe from an event type
     Unsubscribe { event: EventType },
     /// Ping to keep connection alive
     Ping,
+    /// Present a fresh session token before the current one expires
+    Reauth { token: String },
 }
 
 /// Messages sent from server to client
@@ -64,24 +114,38 @@ pub enum ServerMessage {
     /// Pong response to ping
     Pong,
     /// Error message
-    #[allow(dead_code)]
     Error { message: String },
     /// Subscription confirmed
     Subscribed { event: EventType },
     /// Unsubscription confirmed
     Unsubscribed { event: EventType },
+    /// Re-authentication accepted; the connection now lasts until `expires_at`
+    Reauthenticated {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        expires_at: Option<i64>,
+    },
 }
 
 // ========== CONNECTION STATE ==========
 
+/// Connections dropped because their send buffer filled up
+static DROPPED_SLOW_CONSUMERS: AtomicU64 = AtomicU64::new(0);
+
+/// Number of WebSocket connections dropped for not keeping up with events
+pub fn dropped_slow_consumers() -> u64 {
+    DROPPED_SLOW_CONSUMERS.load(Ordering::Relaxed)
+}
+
 /// Represents a single WebSocket connection
 struct Connection {
-    /// Channel to send messages to this connection
-    tx: mpsc::UnboundedSender<ServerMessage>,
-    /// Set of event types this connection is subscribed to
-    subscriptions: HashSet<EventType>,
-    /// API key for authorization (optional, can be None for unauthenticated connections)
-    _api_key: Option<Vec<u8>>,
+    /// Bounded channel to send messages to this connection
+    tx: mpsc::Sender<ServerMessage>,
+    /// Signalled when the connection is dropped as a slow consumer
+    slow: Arc<Notify>,
+    /// Event types this connection is subscribed to, with the filter for each
+    subscriptions: HashMap<EventType, SubscriptionFilter>,
+    /// API key hash of the authenticated account (None for unauthenticated connections)
+    account: Option<String>,
 }
 
 // ========== WEBSOCKET BROADCASTER ==========
@@ -91,6 +155,8 @@ struct Connection {
 pub struct WebSocketBroadcaster {
     /// Map of connection_id -> Connection
     connections: Arc<RwLock<HashMap<String, Connection>>>,
+    /// Last metrics pushed as metrics_update, so unchanged snapshots aren't resent
+    last_metrics: Mutex<Option<serde_json::Value>>,
 }
 
 impl Default for WebSocketBroadcaster {
@@ -104,6 +170,7 @@ impl WebSocketBroadcaster {
     pub fn new() -> Self {
         Self {
             connections: Arc::new(RwLock::new(HashMap::new())),
+            last_metrics: Mutex::new(None),
         }
     }
 
@@ -111,13 +178,15 @@ impl WebSocketBroadcaster {
     async fn add_connection(
         &self,
         id: String,
-        tx: mpsc::UnboundedSender<ServerMessage>,
-        api_key: Option<Vec<u8>>,
+        tx: mpsc::Sender<ServerMessage>,
+        slow: Arc<Notify>,
+        account: Option<String>,
     ) {
         let connection = Connection {
             tx,
-            subscriptions: HashSet::new(),
-            _api_key: api_key,
+            slow,
+            subscriptions: HashMap::new(),
+            account,
         };
 
         let mut connections = self.connections.write().await;
@@ -133,22 +202,45 @@ impl WebSocketBroadcaster {
     }
 
     /// Subscribe a connection to an event type
-    async fn subscribe(&self, connection_id: &str, event: EventType) -> Result<()> {
+    /// Account filters are limited to the connection's own account, and audit log
+    /// updates are always narrowed to it so no connection sees another user's activity
+    async fn subscribe(
+        &self,
+        connection_id: &str,
+        event: EventType,
+        mut filter: SubscriptionFilter,
+    ) -> Result<()> {
         let mut connections = self.connections.write().await;
 
-        if let Some(conn) = connections.get_mut(connection_id) {
-            conn.subscriptions.insert(event);
-            debug!(
-                connection_id = %connection_id,
-                event = ?event,
-                "Subscribed to event"
-            );
-            Ok(())
-        } else {
-            Err(crate::types::GoudChainError::Internal(
-                "Connection not found".to_string(),
-            ))
+        let conn = connections
+            .get_mut(connection_id)
+            .ok_or_else(|| GoudChainError::Internal("Connection not found".to_string()))?;
+
+        if filter.account.is_some() && filter.account != conn.account {
+            return Err(GoudChainError::Unauthorized(
+                "Subscription filter account must be the authenticated account".to_string(),
+            ));
+        }
+        if event == EventType::AuditLogUpdate {
+            if conn.account.is_none() {
+                return Err(GoudChainError::Unauthorized(
+                    "audit_log_update requires an authenticated connection".to_string(),
+                ));
+            }
+            filter.account = conn.account.clone();
+        }
+
+        conn.subscriptions.insert(event, filter);
+        if event == EventType::MetricsUpdate {
+            // New subscribers get the next snapshot even if nothing changed since the last push
+            *self.last_metrics.lock().await = None;
         }
+        debug!(
+            connection_id = %connection_id,
+            event = ?event,
+            "Subscribed to event"
+        );
+        Ok(())
     }
 
     /// Unsubscribe a connection from an event type
@@ -164,43 +256,58 @@ impl WebSocketBroadcaster {
             );
             Ok(())
         } else {
-            Err(crate::types::GoudChainError::Internal(
-                "Connection not found".to_string(),
-            ))
+            Err(GoudChainError::Internal("Connection not found".to_string()))
         }
     }
 
-    /// Broadcast an event to all subscribed connections
-    async fn broadcast(&self, event: EventType, data: serde_json::Value) {
+    /// Broadcast an event to all subscribed connections whose filter matches
+    /// Sends never wait: a connection whose buffer is full is dropped as a slow consumer
+    /// so one stuck client can't hold up delivery to everyone else
+    async fn broadcast(&self, event: EventType, scope: EventScope, data: serde_json::Value) {
         let connections = self.connections.read().await;
         let mut failed_connections = Vec::new();
+        let mut slow_connections = Vec::new();
 
         for (id, conn) in connections.iter() {
-            if conn.subscriptions.contains(&event) {
+            let subscribed = conn
+                .subscriptions
+                .get(&event)
+                .is_some_and(|filter| filter.matches(&scope));
+            if subscribed {
                 let message = ServerMessage::Event {
                     event,
                     data: data.clone(),
                 };
 
-                if let Err(e) = conn.tx.send(message) {
-                    error!(
-                        connection_id = %id,
-                        error = %e,
-                        "Failed to send message to connection"
-                    );
-                    failed_connections.push(id.clone());
+                match conn.tx.try_send(message) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(_)) => slow_connections.push(id.clone()),
+                    Err(TrySendError::Closed(_)) => {
+                        error!(
+                            connection_id = %id,
+                            "Failed to send message to connection"
+                        );
+                        failed_connections.push(id.clone());
+                    }
                 }
             }
         }
 
-        // Clean up failed connections
+        // Clean up failed and slow connections
         drop(connections);
-        if !failed_connections.is_empty() {
+        if !failed_connections.is_empty() || !slow_connections.is_empty() {
             let mut connections = self.connections.write().await;
             for id in failed_connections {
                 connections.remove(&id);
                 warn!(connection_id = %id, "Removed failed connection");
             }
+            for id in slow_connections {
+                if let Some(conn) = connections.remove(&id) {
+                    conn.slow.notify_one();
+                    DROPPED_SLOW_CONSUMERS.fetch_add(1, Ordering::Relaxed);
+                    warn!(connection_id = %id, "Dropped slow WebSocket consumer");
+                }
+            }
         }
     }
 
@@ -212,39 +319,102 @@ impl WebSocketBroadcaster {
             "timestamp": chrono::Utc::now().timestamp(),
         });
 
-        self.broadcast(EventType::BlockchainUpdate, data).await;
+        self.broadcast(EventType::BlockchainUpdate, EventScope::default(), data)
+            .await;
         debug!(block_index = %block_index, "Broadcasted blockchain update");
     }
 
+    /// Broadcast a chain reorganization event
+    /// `depth` is how many of our blocks the adopted fork rolled back
+    pub async fn broadcast_reorg(&self, old_tip: String, new_tip: String, depth: u64) {
+        let data = serde_json::json!({
+            "old_tip": old_tip,
+            "new_tip": new_tip,
+            "depth": depth,
+            "timestamp": chrono::Utc::now().timestamp(),
+        });
+
+        self.broadcast(EventType::ChainReorg, EventScope::default(), data)
+            .await;
+        debug!(depth = %depth, "Broadcasted chain reorg");
+    }
+
     /// Broadcast a collection update event
-    pub async fn broadcast_collection_update(&self, collection_id: String, block_index: u64) {
+    /// The owner's API key hash is only used for filtering and is not sent to clients
+    pub async fn broadcast_collection_update(
+        &self,
+        collection_id: String,
+        owner_api_key_hash: String,
+        block_index: u64,
+    ) {
         let data = serde_json::json!({
             "collection_id": collection_id,
             "block_index": block_index,
             "timestamp": chrono::Utc::now().timestamp(),
         });
+        let scope = EventScope {
+            account: Some(owner_api_key_hash),
+            collection_id: Some(collection_id.clone()),
+        };
 
-        self.broadcast(EventType::CollectionUpdate, data).await;
+        self.broadcast(EventType::CollectionUpdate, scope, data)
+            .await;
         debug!(collection_id = %collection_id, "Broadcasted collection update");
     }
 
-    /// Broadcast a peer network update event
-    #[allow(dead_code)]
-    pub async fn broadcast_peer_update(&self, peer_count: usize) {
+    /// Broadcast a peer network update event with the new active peer list
+    pub async fn broadcast_peer_update(&self, peers: Vec<String>) {
+        let peer_count = peers.len();
         let data = serde_json::json!({
             "peer_count": peer_count,
+            "peers": peers,
             "timestamp": chrono::Utc::now().timestamp(),
         });
 
-        self.broadcast(EventType::PeerUpdate, data).await;
+        self.broadcast(EventType::PeerUpdate, EventScope::default(), data)
+            .await;
         debug!(peer_count = %peer_count, "Broadcasted peer update");
     }
 
-    /// Broadcast an audit log update event
+    /// Broadcast a metrics update event, skipping snapshots identical to the last one pushed
+    pub async fn broadcast_metrics_update(&self, metrics: &NodeMetricsResponse) {
+        let mut data = match serde_json::to_value(metrics) {
+            Ok(data) => data,
+            Err(e) => {
+                error!(error = %e, "Failed to serialize metrics update");
+                return;
+            }
+        };
+
+        {
+            let mut last_metrics = self.last_metrics.lock().await;
+            if last_metrics.as_ref() == Some(&data) {
+                return;
+            }
+            *last_metrics = Some(data.clone());
+        }
+
+        data["timestamp"] = serde_json::json!(chrono::Utc::now().timestamp());
+        self.broadcast(EventType::MetricsUpdate, EventScope::default(), data)
+            .await;
+        debug!(chain_length = %metrics.chain_length, "Broadcasted metrics update");
+    }
+
+    /// Check whether any connection is subscribed to an event type
+    pub async fn has_subscribers(&self, event: EventType) -> bool {
+        self.connections
+            .read()
+            .await
+            .values()
+            .any(|conn| conn.subscriptions.contains_key(&event))
+    }
+
+    /// Broadcast an audit log update event to the account it belongs to
     /// Called immediately when audit events are logged (before blockchain flush)
     /// Provides instant feedback to users while batching continues in background
     pub async fn broadcast_audit_log_update(
         &self,
+        account_hash: String,
         event_type: crate::types::AuditEventType,
         timestamp: i64,
         collection_id: Option<String>,
@@ -257,11 +427,23 @@ impl WebSocketBroadcaster {
             "metadata": metadata,
             "confirmed": false, // Not yet committed to blockchain
         });
+        let scope = EventScope {
+            account: Some(account_hash),
+            collection_id,
+        };
 
-        self.broadcast(EventType::AuditLogUpdate, data).await;
+        self.broadcast(EventType::AuditLogUpdate, scope, data).await;
         debug!(event_type = %event_type, "Broadcasted audit log update");
     }
 
+    /// Send a reply to one connection, dropping it if the connection's buffer is full
+    async fn send_to(&self, connection_id: &str, message: ServerMessage) {
+        let connections = self.connections.read().await;
+        if let Some(conn) = connections.get(connection_id) {
+            let _ = conn.tx.try_send(message);
+        }
+    }
+
     /// Get current connection count
     #[allow(dead_code)]
     pub async fn connection_count(&self) -> usize {
@@ -274,62 +456,160 @@ impl WebSocketBroadcaster {
 /// Query parameters for WebSocket connection
 #[derive(Debug, Deserialize)]
 pub struct WebSocketQuery {
-    /// JWT session token or API key for authentication
+    /// JWT session token or API key; required for account-scoped events
     pub token: Option<String>,
 }
 
+/// Account a connection authenticated as, and when its credential lapses
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TokenIdentity {
+    /// API key hash of the account
+    account: String,
+    /// Session token expiry (Unix seconds); API keys never expire
+    expires_at: Option<i64>,
+}
+
+/// Resolve a connection token (session token or API key) to its account and expiry
+/// API keys are hashed under the same per-IP limit as HTTP requests
+async fn identify_token(token: &str, auth: &SocketCredentials) -> Result<TokenIdentity> {
+    let config = &auth.config;
+    let session_error = match verify_session_token(token, config) {
+        Ok(claims) => {
+            ensure_token_not_revoked(&claims, &auth.revocations)?;
+            return Ok(TokenIdentity {
+                account: claims.api_key_hash,
+                expires_at: Some(claims.exp),
+            });
+        }
+        Err(e) => e,
+    };
+
+    // Not a valid session token, so it must be an API key
+    let api_key = decode_api_key(token).map_err(|_| session_error)?;
+    ensure_api_key_kdf_accepted(&api_key, config)?;
+    Ok(TokenIdentity {
+        account: hash_request_api_key(&auth.rate_limiter, &auth.client_ip, &api_key).await?,
+        expires_at: None,
+    })
+}
+
+/// What a connection needs to check tokens: node config, revocations and the rate limit
+/// applied to API key hashing for the connection's client IP
+struct SocketCredentials {
+    config: Arc<Config>,
+    revocations: Arc<TokenRevocationStore>,
+    rate_limiter: Arc<RateLimiter>,
+    client_ip: String,
+}
+
+/// Everything needed to check a `reauth` message against the connection's credentials
+struct SocketAuth {
+    account: Option<String>,
+    expires_at: watch::Sender<Option<i64>>,
+    credentials: SocketCredentials,
+}
+
 /// Handle WebSocket upgrade request
+/// An invalid or expired token is closed with a policy-violation close frame right after
+/// the upgrade; connections without a token are allowed but only receive public events
 pub async fn handle_websocket_upgrade(
     ws: WebSocketUpgrade,
+    headers: HeaderMap,
     Query(params): Query<WebSocketQuery>,
     Extension(broadcaster): Extension<Arc<WebSocketBroadcaster>>,
     Extension(config): Extension<Arc<Config>>,
+    Extension(revocations): Extension<Arc<TokenRevocationStore>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
 ) -> Response {
-    // Extract and validate API key from query parameter
-    let api_key = if let Some(token) = params.token {
-        // Try to decode as API key
-        match decode_api_key(&token) {
-            Ok(key) => Some(key),
-            Err(_) => {
-                // Could also try to decode as JWT session token here
-                // For now, we'll just accept API keys
-                warn!("Invalid token provided for WebSocket connection");
-                None
-            }
+    let credentials = SocketCredentials {
+        config,
+        revocations,
+        rate_limiter,
+        client_ip: extract_client_ip(&headers),
+    };
+    let identity = match params.token {
+        Some(token) => identify_token(&token, &credentials).await.map(Some),
+        None => Ok(None),
+    };
+    let identity = match identity {
+        Ok(identity) => identity,
+        Err(e) => {
+            warn!(error = %e, "Rejecting WebSocket connection with invalid token");
+            return ws.on_upgrade(|socket| async move {
+                let (mut sender, _receiver) = socket.split();
+                close_connection(&mut sender, "Invalid or expired token").await;
+            });
         }
-    } else {
-        None
     };
 
-    // For now, allow unauthenticated connections but track API key if provided
-    // In production, you might want to reject unauthenticated connections
     let connection_id = Uuid::new_v4().to_string();
 
-    ws.on_upgrade(move |socket| handle_socket(socket, connection_id, api_key, broadcaster, config))
+    ws.on_upgrade(move |socket| {
+        handle_socket(socket, connection_id, identity, broadcaster, credentials)
+    })
+}
+
+/// Resolve once a session expiry passes; never resolves for credentials without one
+async fn session_expired(expires_at: Option<i64>) {
+    match expires_at {
+        Some(expires_at) => {
+            let remaining = (expires_at - chrono::Utc::now().timestamp()).max(0) as u64;
+            tokio::time::sleep(Duration::from_secs(remaining)).await;
+        }
+        None => std::future::pending().await,
+    }
 }
 
 /// Handle an individual WebSocket connection
 async fn handle_socket(
     socket: WebSocket,
     connection_id: String,
-    api_key: Option<Vec<u8>>,
+    identity: Option<TokenIdentity>,
     broadcaster: Arc<WebSocketBroadcaster>,
-    _config: Arc<Config>,
+    credentials: SocketCredentials,
 ) {
     let (mut sender, mut receiver) = socket.split();
 
-    // Create channel for sending messages to this connection
-    let (tx, mut rx) = mpsc::unbounded_channel::<ServerMessage>();
+    // Create bounded channel for sending messages to this connection
+    let (tx, mut rx) = mpsc::channel::<ServerMessage>(WEBSOCKET_SEND_BUFFER);
+    let slow = Arc::new(Notify::new());
+
+    // Session expiry, moved forward by `reauth` messages
+    let account = identity.as_ref().map(|identity| identity.account.clone());
+    let (expires_tx, mut expires_rx) =
+        watch::channel(identity.and_then(|identity| identity.expires_at));
+    let auth = SocketAuth {
+        account: account.clone(),
+        expires_at: expires_tx,
+        credentials,
+    };
 
     // Register connection
     broadcaster
-        .add_connection(connection_id.clone(), tx, api_key)
+        .add_connection(connection_id.clone(), tx, Arc::clone(&slow), account)
         .await;
 
     // Spawn task to forward messages from channel to WebSocket
     let connection_id_clone = connection_id.clone();
     let mut send_task = tokio::spawn(async move {
-        while let Some(message) = rx.recv().await {
+        let close_reason = loop {
+            let expires_at = *expires_rx.borrow_and_update();
+            let message = tokio::select! {
+                biased;
+                _ = slow.notified() => break "Too slow to receive events",
+                _ = session_expired(expires_at) => break "Session token expired",
+                changed = expires_rx.changed() => {
+                    if changed.is_err() {
+                        return;
+                    }
+                    continue;
+                }
+                message = rx.recv() => match message {
+                    Some(message) => message,
+                    None => return,
+                },
+            };
+
             let json = match serde_json::to_string(&message) {
                 Ok(j) => j,
                 Err(e) => {
@@ -338,14 +618,22 @@ async fn handle_socket(
                 }
             };
 
-            if sender.send(Message::Text(json.into())).await.is_err() {
+            let sent = tokio::select! {
+                biased;
+                _ = slow.notified() => break "Too slow to receive events",
+                sent = sender.send(Message::Text(json.into())) => sent,
+            };
+            if sent.is_err() {
                 error!(
                     connection_id = %connection_id_clone,
                     "Failed to send message, connection likely closed"
                 );
-                break;
+                return;
             }
-        }
+        };
+
+        info!(connection_id = %connection_id_clone, reason = close_reason, "Closing WebSocket connection");
+        close_connection(&mut sender, close_reason).await;
     });
 
     // Handle incoming messages from client
@@ -362,6 +650,7 @@ async fn handle_socket(
                                 &connection_id_clone,
                                 client_msg,
                                 &broadcaster_clone,
+                                &auth,
                             )
                             .await;
                         }
@@ -399,41 +688,375 @@ async fn handle_socket(
     info!(connection_id = %connection_id, "WebSocket connection closed");
 }
 
-/// Handle a client message (subscribe, unsubscribe, ping)
+/// Close a connection with a policy-violation frame explaining why
+/// Bounded by a timeout, since a client that stopped reading may never accept the frame
+async fn close_connection(sender: &mut SplitSink<WebSocket, Message>, reason: &'static str) {
+    let close = Message::Close(Some(CloseFrame {
+        code: close_code::POLICY,
+        reason: reason.into(),
+    }));
+    let _ = tokio::time::timeout(
+        Duration::from_secs(WEBSOCKET_CLOSE_TIMEOUT_SECONDS),
+        sender.send(close),
+    )
+    .await;
+}
+
+/// Check a `reauth` token and extend the connection's session to its expiry
+/// The token must belong to the account the connection was opened with
+async fn reauthenticate(auth: &SocketAuth, token: &str) -> Result<Option<i64>> {
+    let identity = identify_token(token, &auth.credentials).await?;
+    if auth.account.as_ref() != Some(&identity.account) {
+        return Err(GoudChainError::Unauthorized(
+            "Reauthentication must use the connection's account".to_string(),
+        ));
+    }
+
+    auth.expires_at.send_replace(identity.expires_at);
+    Ok(identity.expires_at)
+}
+
+/// Handle a client message (subscribe, unsubscribe, ping, reauth)
 async fn handle_client_message(
     connection_id: &str,
     message: ClientMessage,
     broadcaster: &Arc<WebSocketBroadcaster>,
+    auth: &SocketAuth,
 ) {
     match message {
-        ClientMessage::Subscribe { event } => {
-            if let Err(e) = broadcaster.subscribe(connection_id, event).await {
-                error!(error = %e, "Failed to subscribe");
-            } else {
-                // Send confirmation
-                let connections = broadcaster.connections.read().await;
-                if let Some(conn) = connections.get(connection_id) {
-                    let _ = conn.tx.send(ServerMessage::Subscribed { event });
+        ClientMessage::Subscribe { event, filter } => {
+            let reply = match broadcaster.subscribe(connection_id, event, filter).await {
+                Ok(()) => ServerMessage::Subscribed { event },
+                Err(e) => {
+                    warn!(error = %e, "Subscription rejected");
+                    ServerMessage::Error {
+                        message: e.to_string(),
+                    }
                 }
-            }
+            };
+
+            // Send confirmation or the rejection reason
+            broadcaster.send_to(connection_id, reply).await;
         }
         ClientMessage::Unsubscribe { event } => {
             if let Err(e) = broadcaster.unsubscribe(connection_id, event).await {
                 error!(error = %e, "Failed to unsubscribe");
             } else {
                 // Send confirmation
-                let connections = broadcaster.connections.read().await;
-                if let Some(conn) = connections.get(connection_id) {
-                    let _ = conn.tx.send(ServerMessage::Unsubscribed { event });
-                }
+                broadcaster
+                    .send_to(connection_id, ServerMessage::Unsubscribed { event })
+                    .await;
             }
         }
         ClientMessage::Ping => {
             // Send pong
-            let connections = broadcaster.connections.read().await;
-            if let Some(conn) = connections.get(connection_id) {
-                let _ = conn.tx.send(ServerMessage::Pong);
+            broadcaster
+                .send_to(connection_id, ServerMessage::Pong)
+                .await;
+        }
+        ClientMessage::Reauth { token } => {
+            let reply = match reauthenticate(auth, &token).await {
+                Ok(expires_at) => ServerMessage::Reauthenticated { expires_at },
+                Err(e) => {
+                    warn!(error = %e, "Reauthentication rejected");
+                    ServerMessage::Error {
+                        message: e.to_string(),
+                    }
+                }
+            };
+            broadcaster.send_to(connection_id, reply).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::auth::{generate_session_token, SessionLifetime, TokenScope};
+    use crate::config::RateLimitConfig;
+    use crate::storage::RateLimitStore;
+    use rocksdb::{Options, DB};
+
+    async fn connect(
+        broadcaster: &WebSocketBroadcaster,
+        account: Option<&str>,
+    ) -> (String, mpsc::Receiver<ServerMessage>) {
+        let (tx, rx) = mpsc::channel(WEBSOCKET_SEND_BUFFER);
+        let id = Uuid::new_v4().to_string();
+        broadcaster
+            .add_connection(
+                id.clone(),
+                tx,
+                Arc::new(Notify::new()),
+                account.map(str::to_string),
+            )
+            .await;
+        (id, rx)
+    }
+
+    fn received_collections(rx: &mut mpsc::Receiver<ServerMessage>) -> Vec<String> {
+        let mut ids = Vec::new();
+        while let Ok(message) = rx.try_recv() {
+            if let ServerMessage::Event { data, .. } = message {
+                ids.push(data["collection_id"].as_str().unwrap().to_string());
             }
         }
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_collection_updates_respect_filters() {
+        let broadcaster = WebSocketBroadcaster::new();
+        let (everything, mut everything_rx) = connect(&broadcaster, None).await;
+        let (own, mut own_rx) = connect(&broadcaster, Some("alice")).await;
+        let (single, mut single_rx) = connect(&broadcaster, None).await;
+
+        broadcaster
+            .subscribe(
+                &everything,
+                EventType::CollectionUpdate,
+                SubscriptionFilter::default(),
+            )
+            .await
+            .unwrap();
+        let alice_only = SubscriptionFilter {
+            account: Some("alice".to_string()),
+            collection_id: None,
+        };
+        broadcaster
+            .subscribe(&own, EventType::CollectionUpdate, alice_only)
+            .await
+            .unwrap();
+        let one_collection = SubscriptionFilter {
+            account: None,
+            collection_id: Some("c2".to_string()),
+        };
+        broadcaster
+            .subscribe(&single, EventType::CollectionUpdate, one_collection)
+            .await
+            .unwrap();
+
+        broadcaster
+            .broadcast_collection_update("c1".to_string(), "alice".to_string(), 1)
+            .await;
+        broadcaster
+            .broadcast_collection_update("c2".to_string(), "bob".to_string(), 2)
+            .await;
+
+        assert_eq!(received_collections(&mut everything_rx), vec!["c1", "c2"]);
+        assert_eq!(received_collections(&mut own_rx), vec!["c1"]);
+        assert_eq!(received_collections(&mut single_rx), vec!["c2"]);
+    }
+
+    #[tokio::test]
+    async fn test_audit_updates_are_scoped_to_account() {
+        let broadcaster = WebSocketBroadcaster::new();
+        let (anonymous, _anonymous_rx) = connect(&broadcaster, None).await;
+        let (alice, mut alice_rx) = connect(&broadcaster, Some("alice")).await;
+        let (bob, mut bob_rx) = connect(&broadcaster, Some("bob")).await;
+
+        // Audit events need an account, and account filters can't name someone else
+        assert!(broadcaster
+            .subscribe(
+                &anonymous,
+                EventType::AuditLogUpdate,
+                SubscriptionFilter::default()
+            )
+            .await
+            .is_err());
+        let snoop = SubscriptionFilter {
+            account: Some("alice".to_string()),
+            collection_id: None,
+        };
+        assert!(broadcaster
+            .subscribe(&bob, EventType::CollectionUpdate, snoop)
+            .await
+            .is_err());
+
+        for id in [&alice, &bob] {
+            broadcaster
+                .subscribe(id, EventType::AuditLogUpdate, SubscriptionFilter::default())
+                .await
+                .unwrap();
+        }
+        broadcaster
+            .broadcast_audit_log_update(
+                "alice".to_string(),
+                crate::types::AuditEventType::DataSubmitted,
+                0,
+                Some("c1".to_string()),
+                serde_json::json!({}),
+            )
+            .await;
+
+        assert_eq!(received_collections(&mut alice_rx), vec!["c1"]);
+        assert!(received_collections(&mut bob_rx).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_slow_consumer_is_dropped_without_stalling_others() {
+        let broadcaster = WebSocketBroadcaster::new();
+        let (healthy, mut healthy_rx) = connect(&broadcaster, None).await;
+        let (stuck, _stuck_rx) = connect(&broadcaster, None).await;
+        for id in [&healthy, &stuck] {
+            broadcaster
+                .subscribe(
+                    id,
+                    EventType::BlockchainUpdate,
+                    SubscriptionFilter::default(),
+                )
+                .await
+                .unwrap();
+        }
+        let dropped_before = dropped_slow_consumers();
+
+        // The stuck client never reads; the healthy one keeps up
+        for block_index in 0..=WEBSOCKET_SEND_BUFFER as u64 {
+            broadcaster
+                .broadcast_blockchain_update(block_index, "hash".to_string())
+                .await;
+            assert!(matches!(
+                healthy_rx.try_recv(),
+                Ok(ServerMessage::Event { .. })
+            ));
+        }
+
+        assert_eq!(broadcaster.connection_count().await, 1);
+        assert!(dropped_slow_consumers() > dropped_before);
+    }
+
+    fn test_metrics(chain_length: u64) -> NodeMetricsResponse {
+        NodeMetricsResponse {
+            node_id: "node1".to_string(),
+            chain_length,
+            peer_count: 2,
+            latest_block_index: chain_length.saturating_sub(1),
+            latest_block_timestamp: 0,
+            status: "healthy".to_string(),
+            total_operations: 0,
+            cache_hit_rate: 0.0,
+            cache_evictions: 0,
+            cache_expirations: 0,
+            operations_per_second: 0.0,
+            dropped_slow_consumers: 0,
+            volume_metrics: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_updates_skip_unchanged_snapshots() {
+        let broadcaster = WebSocketBroadcaster::new();
+        let (first, mut first_rx) = connect(&broadcaster, None).await;
+        assert!(!broadcaster.has_subscribers(EventType::MetricsUpdate).await);
+
+        broadcaster
+            .subscribe(
+                &first,
+                EventType::MetricsUpdate,
+                SubscriptionFilter::default(),
+            )
+            .await
+            .unwrap();
+        assert!(broadcaster.has_subscribers(EventType::MetricsUpdate).await);
+
+        broadcaster.broadcast_metrics_update(&test_metrics(1)).await;
+        broadcaster.broadcast_metrics_update(&test_metrics(1)).await;
+        broadcaster.broadcast_metrics_update(&test_metrics(2)).await;
+
+        let lengths: Vec<u64> = std::iter::from_fn(|| first_rx.try_recv().ok())
+            .filter_map(|message| match message {
+                ServerMessage::Event { data, .. } => data["chain_length"].as_u64(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(lengths, vec![1, 2]);
+
+        // A new subscriber receives the current snapshot even though it is unchanged
+        let (second, mut second_rx) = connect(&broadcaster, None).await;
+        broadcaster
+            .subscribe(
+                &second,
+                EventType::MetricsUpdate,
+                SubscriptionFilter::default(),
+            )
+            .await
+            .unwrap();
+        broadcaster.broadcast_metrics_update(&test_metrics(2)).await;
+        assert!(matches!(
+            second_rx.try_recv(),
+            Ok(ServerMessage::Event {
+                event: EventType::MetricsUpdate,
+                ..
+            })
+        ));
+    }
+
+    fn test_auth(account: &str) -> SocketAuth {
+        let config = Config::for_tests();
+        let path = format!("/tmp/goud_ws_revocation_test_{}", rand::random::<u64>());
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let db = Arc::new(DB::open(&opts, path).unwrap());
+        let revocations = TokenRevocationStore::new(Arc::clone(&db));
+        let rate_limiter = RateLimiter::new(
+            Arc::new(RateLimitStore::new(db)),
+            vec![],
+            vec![],
+            RateLimitConfig::default(),
+        );
+
+        SocketAuth {
+            account: Some(account.to_string()),
+            expires_at: watch::channel(Some(0)).0,
+            credentials: SocketCredentials {
+                config: Arc::new(config),
+                revocations: Arc::new(revocations),
+                rate_limiter: Arc::new(rate_limiter),
+                client_ip: "127.0.0.1".to_string(),
+            },
+        }
+    }
+
+    fn session_token(auth: &SocketAuth, account: &str) -> String {
+        generate_session_token(
+            "account-id".to_string(),
+            b"test_api_key_12345678901234567890",
+            account.to_string(),
+            TokenScope::Read,
+            SessionLifetime::standard(&auth.credentials.config),
+            &auth.credentials.config,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reauth_extends_session_for_same_account() {
+        let auth = test_auth("alice");
+        let expiries = auth.expires_at.subscribe();
+
+        let expires_at = reauthenticate(&auth, &session_token(&auth, "alice"))
+            .await
+            .unwrap();
+        assert!(expires_at.unwrap() > chrono::Utc::now().timestamp());
+        assert_eq!(*expiries.borrow(), expires_at);
+
+        // Another account's token, or garbage, leaves the current expiry untouched
+        assert!(reauthenticate(&auth, &session_token(&auth, "bob"))
+            .await
+            .is_err());
+        assert!(reauthenticate(&auth, "not a token").await.is_err());
+        assert_eq!(*expiries.borrow(), expires_at);
+    }
+
+    #[tokio::test]
+    async fn test_session_expiry_wait() {
+        let past = chrono::Utc::now().timestamp() - 1;
+        tokio::time::timeout(Duration::from_secs(1), session_expired(Some(past)))
+            .await
+            .unwrap();
+
+        // API key connections never expire
+        let never = tokio::time::timeout(Duration::from_millis(50), session_expired(None)).await;
+        assert!(never.is_err());
     }
 }
@@ -1,4 +1,4 @@
-//! CLI commands for migration management.
+//! CLI commands for migration and backup management.
 //!
 //! This module provides command-line interface for managing schema migrations:
 //! - `migrate status` - Show applied and pending migrations
@@ -6,10 +6,15 @@
 //! - `migrate down` - Rollback last N migrations
 //! - `migrate create <description>` - Generate new migration template
 //! - `migrate reset` - Clear all migration state (dangerous)
+//!
+//! And for restoring backups taken with `POST /admin/backup`:
+//! - `backup restore` - Replace the database with the latest backup (node must be stopped)
 
+use crate::config::Config;
 use crate::storage::{BlockchainStore, Migration, MigrationRunner, MigrationStore};
 use crate::types::Result;
 use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::{error, info};
 
@@ -26,6 +31,62 @@ pub enum Commands {
     /// Manage database schema migrations
     #[command(subcommand)]
     Migrate(MigrateCommands),
+
+    /// Restore database backups
+    #[command(subcommand)]
+    Backup(BackupCommands),
+}
+
+#[derive(Subcommand)]
+pub enum BackupCommands {
+    /// Replace the database with the latest backup (run while the node is stopped)
+    Restore {
+        /// Backup directory to restore from (default: <DATA_DIR>/backups)
+        #[arg(long)]
+        from: Option<PathBuf>,
+
+        /// Skip confirmation prompt (for automation/CI)
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+/// Execute backup CLI command
+pub fn handle_backup_command(command: &BackupCommands, config: &Config) -> Result<()> {
+    match command {
+        BackupCommands::Restore { from, yes } => {
+            let backup_dir = from.clone().unwrap_or_else(|| config.backup_path());
+            handle_restore(&backup_dir, &config.rocksdb_path(), *yes)
+        }
+    }
+}
+
+fn handle_restore(backup_dir: &Path, db_dir: &Path, yes: bool) -> Result<()> {
+    println!(
+        "\n[WARN] Replacing {} with the latest backup in {}",
+        db_dir.display(),
+        backup_dir.display()
+    );
+
+    if !yes {
+        println!("\nProceed? [y/N]: ");
+        let mut input = String::new();
+        std::io::stdin()
+            .read_line(&mut input)
+            .expect("Failed to read user input from stdin");
+
+        if input.trim().to_lowercase() != "y" {
+            println!("[CANCEL] Restore cancelled\n");
+            return Ok(());
+        }
+    }
+
+    let backup = BlockchainStore::restore_backup(backup_dir, db_dir)?;
+    println!(
+        "\n[OK] Restored backup {} ({} files, {} bytes)\n",
+        backup.backup_id, backup.num_files, backup.size_bytes
+    );
+    Ok(())
 }
 
 #[derive(Subcommand)]
@@ -1,8 +1,25 @@
+use ipnet::IpNet;
 use std::collections::HashMap;
-use std::{env, fs, path::Path};
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
 use uuid::Uuid;
 
-use crate::constants::DATA_DIRECTORY;
+use crate::constants::{
+    ARGON2_DEFAULT_ITERATIONS, ARGON2_DEFAULT_MEMORY_KIB, ARGON2_DEFAULT_PARALLELISM,
+    ARGON2_MAX_ITERATIONS, ARGON2_MAX_MEMORY_KIB, ARGON2_MAX_PARALLELISM, BACKUP_DIRECTORY_NAME,
+    DATA_DIRECTORY, DEFAULT_BLOCK_SEAL_THRESHOLD, DEFAULT_CHECKPOINT_INTERVAL, DEFAULT_LOG_LEVEL,
+    DEFAULT_MAX_REORG_DEPTH, DEFAULT_MAX_SESSION_TTL_SECONDS, DEFAULT_REMEMBER_ME_TTL_SECONDS,
+    DEFAULT_REQUEST_TIMEOUT_SECONDS, GENESIS_TIMESTAMP, MAX_AUDIT_RETENTION_DAYS,
+    MIN_ADMIN_TOKEN_BYTES, MIN_SESSION_TTL_SECONDS, RATE_LIMIT_ACCOUNT_CREATE_PER_SECOND,
+    RATE_LIMIT_ACCOUNT_EXISTS_PER_SECOND, RATE_LIMIT_BATCH_PER_SECOND, RATE_LIMIT_BURST_SECONDS,
+    RATE_LIMIT_CLEANUP_INTERVAL_SECONDS, RATE_LIMIT_DECRYPT_PER_SECOND,
+    RATE_LIMIT_DELETE_PER_SECOND, RATE_LIMIT_KEY_HASH_PER_SECOND, RATE_LIMIT_LIST_PER_SECOND,
+    RATE_LIMIT_PUBLIC_PER_SECOND, RATE_LIMIT_SEARCH_PER_SECOND, RATE_LIMIT_SUBMIT_PER_SECOND,
+    ROCKSDB_DIRECTORY_NAME,
+};
+use crate::crypto::parse_argon2_params;
+use crate::types::{ApiKeyKdf, Argon2Params};
 
 /// Validator configuration - maps node IDs to validator names and addresses
 /// This decouples the code from deployment-specific naming (node1, Validator_1, etc.)
@@ -14,6 +31,8 @@ pub struct ValidatorConfig {
     pub node_to_validator: HashMap<String, String>,
     /// Maps validator_name -> node_address (e.g., "Validator_1" -> "node1:8080")
     pub validator_to_address: HashMap<String, String>,
+    /// Maps validator_name -> hex Ed25519 public key used to verify node-signed data
+    pub validator_public_keys: HashMap<String, String>,
 }
 
 impl ValidatorConfig {
@@ -32,10 +51,189 @@ impl ValidatorConfig {
             .unwrap_or(false)
     }
 
+    /// Whether a node is mapped to one of the validators (and so ever produces blocks)
+    pub fn is_validator_node(&self, node_id: &str) -> bool {
+        self.node_to_validator
+            .get(node_id)
+            .is_some_and(|validator| self.validators.contains(validator))
+    }
+
     /// Get node address for a validator
     pub fn get_validator_address(&self, validator: &str) -> Option<String> {
         self.validator_to_address.get(validator).cloned()
     }
+
+    /// Get the node that runs a validator
+    /// If several nodes map to one validator, the lowest node ID wins so the answer is stable
+    pub fn get_node_for_validator(&self, validator: &str) -> Option<&str> {
+        self.node_to_validator
+            .iter()
+            .filter(|(_, v)| v.as_str() == validator)
+            .map(|(node, _)| node.as_str())
+            .min()
+    }
+
+    /// Get the published signing public key (hex) for a validator
+    pub fn get_validator_public_key(&self, validator: &str) -> Option<&str> {
+        self.validator_public_keys
+            .get(validator)
+            .map(String::as_str)
+    }
+
+    /// Validate that a block-producing node belongs to the validator set
+    /// Nodes without a node-to-validator mapping are non-validating and always pass
+    pub fn validate_for_node(&self, node_id: &str) -> Result<(), ConfigError> {
+        match self.node_to_validator.get(node_id) {
+            Some(validator) if !self.validators.contains(validator) => {
+                Err(ConfigError::InvalidValidatorConfig(format!(
+                    "Node {} is mapped to {} which is not in VALIDATORS ({})",
+                    node_id,
+                    validator,
+                    self.validators.join(",")
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// How request limits are enforced
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// Fixed 1-second windows: at most `limit` requests per window
+    #[default]
+    SlidingWindow,
+    /// Tokens refill at `limit`/sec up to `limit × burst_seconds`, absorbing short bursts
+    TokenBucket,
+}
+
+impl RateLimitMode {
+    /// Name used by RATE_LIMIT_MODE and reported by /limits
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RateLimitMode::SlidingWindow => "sliding_window",
+            RateLimitMode::TokenBucket => "token_bucket",
+        }
+    }
+}
+
+/// Log line format for the tracing subscriber
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines for local development
+    #[default]
+    Pretty,
+    /// One JSON object per line for log aggregators (Loki, ELK)
+    Json,
+}
+
+/// Logging settings, loaded before the rest of the configuration so its errors are logged too
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogConfig {
+    pub format: LogFormat,
+    /// `EnvFilter` directives, e.g. `info` or `goud_chain=debug,hyper=warn`
+    pub level: String,
+}
+
+impl LogConfig {
+    /// Load logging settings from environment variables
+    /// Format:
+    ///   LOG_FORMAT=pretty|json
+    ///   LOG_LEVEL=<filter directives> (falls back to RUST_LOG, then info)
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let level = env::var("LOG_LEVEL").or_else(|_| env::var("RUST_LOG")).ok();
+        Self::parse(env::var("LOG_FORMAT").ok().as_deref(), level)
+    }
+
+    fn parse(format: Option<&str>, level: Option<String>) -> Result<Self, ConfigError> {
+        let format = match format.map(|value| value.trim().to_lowercase()) {
+            None => LogFormat::default(),
+            Some(value) => match value.as_str() {
+                "pretty" => LogFormat::Pretty,
+                "json" => LogFormat::Json,
+                _ => {
+                    return Err(ConfigError::InvalidLogConfig(
+                        "LOG_FORMAT must be pretty or json".to_string(),
+                    ))
+                }
+            },
+        };
+        let level = level
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| DEFAULT_LOG_LEVEL.to_string());
+
+        Ok(Self { format, level })
+    }
+}
+
+/// Per-endpoint-group request limits (requests per second per API key)
+/// Each group has its own counter, so heavy reads never eat into the write budget
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitConfig {
+    pub mode: RateLimitMode,
+    /// Token bucket capacity in seconds of traffic at the per-second limit
+    pub burst_seconds: u32,
+    /// How often stale rate-limit windows and expired bans are purged from RocksDB
+    pub cleanup_interval_seconds: u64,
+    pub submit_per_second: u32,
+    pub batch_per_second: u32,
+    pub list_per_second: u32,
+    pub search_per_second: u32,
+    pub decrypt_per_second: u32,
+    pub delete_per_second: u32,
+    pub account_create_per_second: u32,
+    /// Account existence checks, keyed by client IP (kept low: each answer is an oracle)
+    pub account_exists_per_second: u32,
+    /// Unauthenticated public reads (/chain, /peers, /stats, /metrics...), keyed by client IP
+    pub public_per_second: u32,
+    /// API key hashes computed for one client IP (checked before hashing, which is slow by design)
+    pub key_hash_per_second: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            mode: RateLimitMode::default(),
+            burst_seconds: RATE_LIMIT_BURST_SECONDS,
+            cleanup_interval_seconds: RATE_LIMIT_CLEANUP_INTERVAL_SECONDS,
+            submit_per_second: RATE_LIMIT_SUBMIT_PER_SECOND,
+            batch_per_second: RATE_LIMIT_BATCH_PER_SECOND,
+            list_per_second: RATE_LIMIT_LIST_PER_SECOND,
+            search_per_second: RATE_LIMIT_SEARCH_PER_SECOND,
+            decrypt_per_second: RATE_LIMIT_DECRYPT_PER_SECOND,
+            delete_per_second: RATE_LIMIT_DELETE_PER_SECOND,
+            account_create_per_second: RATE_LIMIT_ACCOUNT_CREATE_PER_SECOND,
+            account_exists_per_second: RATE_LIMIT_ACCOUNT_EXISTS_PER_SECOND,
+            public_per_second: RATE_LIMIT_PUBLIC_PER_SECOND,
+            key_hash_per_second: RATE_LIMIT_KEY_HASH_PER_SECOND,
+        }
+    }
+}
+
+/// CORS settings for nodes serving browsers directly (enabled with ENABLE_CORS=true)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorsConfig {
+    /// Origins allowed to call the API (`["*"]` allows any origin)
+    pub allowed_origins: Vec<String>,
+}
+
+impl CorsConfig {
+    /// True when any origin is allowed
+    pub fn allows_any_origin(&self) -> bool {
+        self.allowed_origins.iter().any(|origin| origin == "*")
+    }
+}
+
+/// TLS settings for P2P connections (enabled with P2P_TLS_ENABLED=true)
+#[derive(Debug, Clone)]
+pub struct P2pTlsConfig {
+    /// PEM certificate chain presented to peers
+    pub cert_path: String,
+    /// PEM private key for the certificate
+    pub key_path: String,
+    /// SHA-256 fingerprints (hex) of peer certificates we trust
+    pub peer_fingerprints: Vec<String>,
 }
 
 /// Configuration for the Goud Chain node
@@ -45,9 +243,69 @@ pub struct Config {
     pub http_port: String,
     pub p2p_port: u16,
     pub peers: Vec<String>,
+    /// Local address the P2P server listens on (P2P_BIND_ADDR, default 0.0.0.0:P2P_PORT)
+    pub p2p_bind_addr: SocketAddr,
+    /// Address other nodes reach us at (e.g. "node1:9000"), announced in peer exchange and used
+    /// to skip ourselves; differs from the bind address behind NAT or container port mappings
+    pub p2p_advertise_addr: Option<String>,
+    /// Encrypt and pin P2P connections when set; plaintext otherwise
+    pub p2p_tls: Option<P2pTlsConfig>,
+    /// Answer CORS requests when set; off by default because nginx adds the headers in front
+    pub cors: Option<CorsConfig>,
+    /// Proxies allowed to report the client address in X-Real-IP / X-Forwarded-For (TRUSTED_PROXIES);
+    /// requests from any other peer are keyed on the socket address
+    pub trusted_proxies: Vec<IpNet>,
+    /// Holds the database, backups and persisted secrets; distinct per node sharing a host
+    pub data_dir: PathBuf,
+    /// Separate RocksDB for rate limits and nonces (EPHEMERAL_DB_PATH); None keeps them in the chain DB
+    pub ephemeral_db_path: Option<PathBuf>,
     pub jwt_secret: Vec<u8>,
     pub session_secret: Vec<u8>,
+    /// Operator credential for /admin endpoints (ADMIN_TOKEN); None disables them
+    pub admin_token: Option<Vec<u8>>,
     pub validator_config: ValidatorConfig,
+    pub require_request_signatures: bool,
+    /// Ed25519 seed for this node's signing key (persisted so signatures survive restarts)
+    pub node_signing_seed: [u8; 32],
+    pub rate_limit: RateLimitConfig,
+    /// Seconds between metrics_update WebSocket pushes (0 disables them)
+    pub metrics_update_interval_seconds: u64,
+    /// Largest collection payload accepted by /data/submit and each batch item
+    pub max_payload_bytes: usize,
+    /// Longest an API request may run before it is answered with 504
+    pub request_timeout_seconds: u64,
+    /// Blocks between automatic checkpoints; reorgs never cross the latest checkpoint, so this
+    /// is the finality depth (smaller finalizes sooner but leaves less room to heal forks)
+    pub checkpoint_interval: u64,
+    /// Most blocks a chain reorganization may roll back from our tip (checkpoints still apply)
+    pub max_reorg_depth: u64,
+    /// Coalesce submissions into at most one block per interval (0 seals a block per write)
+    pub min_block_interval_seconds: u64,
+    /// Pending collections that seal a coalesced block before the interval is up
+    pub block_seal_threshold: usize,
+    /// Longest session token lifetime a login may request with `ttl_seconds`
+    pub max_session_ttl_seconds: i64,
+    /// Lifetime (and cap) of read-only "remember me" session tokens
+    pub remember_me_ttl_seconds: i64,
+    /// Prune collections from blocks this many blocks older than the latest checkpoint (None keeps full history)
+    pub prune_depth: Option<u64>,
+    /// Keep at most this many recent blocks' bodies in memory; older ones are read from RocksDB on demand (None keeps all)
+    pub max_in_memory_blocks: Option<u64>,
+    /// Days audit entries stay current before they are hidden and unindexed (None keeps them all)
+    pub audit_retention_days: Option<u64>,
+    /// Hashing scheme for newly issued API keys (existing keys keep the scheme they were issued with)
+    pub api_key_kdf: ApiKeyKdf,
+    /// Argon2id parameters of keys issued under earlier settings (ARGON2_PREVIOUS_PARAMS)
+    /// Keys naming any other parameters are refused before hashing
+    pub argon2_previous_params: Vec<Argon2Params>,
+    /// Store exact owner blind indexes in new blocks instead of a bloom filter (EXACT_BLIND_INDEXES=true)
+    pub exact_blind_indexes: bool,
+    /// Serve POST /account/exists (ENABLE_ACCOUNT_EXISTS=true); off by default since it is an oracle
+    pub account_exists_enabled: bool,
+    /// Start in read-only maintenance mode (READ_ONLY=true); toggled at runtime via POST /admin/readonly
+    pub read_only: bool,
+    /// Unix timestamp of the genesis block (GENESIS_TIMESTAMP); every node in a network must agree
+    pub genesis_timestamp: i64,
 }
 
 impl Config {
@@ -65,31 +323,155 @@ impl Config {
 
         let peers = Self::parse_peers();
 
+        let p2p_bind_addr = Self::parse_p2p_bind_addr(env::var("P2P_BIND_ADDR").ok(), p2p_port)?;
+
+        let p2p_advertise_addr =
+            Self::parse_p2p_advertise_addr(env::var("P2P_ADVERTISE_ADDR").ok())?;
+
+        let p2p_tls = Self::load_p2p_tls_config()?;
+
+        let cors = Self::load_cors_config()?;
+
+        // Created up front: the secrets below are persisted into it
+        let data_dir = Self::load_data_dir()?;
+
+        // Relative paths live inside the data directory
+        let ephemeral_db_path =
+            Self::parse_ephemeral_db_path(env::var("EPHEMERAL_DB_PATH").ok(), &data_dir)?;
+
         // Load JWT secret (auto-generate if not present)
-        let jwt_secret = Self::load_jwt_secret()?;
+        let jwt_secret = Self::load_jwt_secret(&data_dir)?;
 
         // Load session secret (auto-generate if not present)
-        let session_secret = Self::load_session_secret()?;
+        let session_secret = Self::load_session_secret(&data_dir)?;
+
+        // Forwarding headers from anyone else are ignored, so clients can't pick their rate-limit key
+        let trusted_proxies = Self::parse_trusted_proxies(env::var("TRUSTED_PROXIES").ok())?;
+
+        // Separate from user credentials: no API key or session token reaches /admin
+        let admin_token = Self::parse_admin_token(env::var("ADMIN_TOKEN").ok())?;
+
+        // Load node signing key seed (auto-generate if not present)
+        let node_signing_seed = Self::load_node_signing_seed(&data_dir)?;
 
         // Load validator configuration
         let validator_config = Self::load_validator_config()?;
 
+        // Explicit node mappings declare which nodes produce blocks - reject inconsistent sets
+        // (default mappings stay lenient so node3 can run as a non-validator in 2-validator setups)
+        if env::var("VALIDATOR_NODES").is_ok() {
+            validator_config.validate_for_node(&node_id)?;
+        }
+
+        // Opt-in until clients register request signing keys (REQUIRE_REQUEST_SIGNATURES=true)
+        let require_request_signatures = Self::parse_bool_flag("REQUIRE_REQUEST_SIGNATURES");
+
+        let rate_limit = Self::load_rate_limit_config()?;
+
+        let metrics_update_interval_seconds = Self::load_metrics_update_interval()?;
+
+        let max_payload_bytes = Self::load_max_payload_bytes()?;
+
+        let request_timeout_seconds = Self::load_request_timeout()?;
+
+        let checkpoint_interval = Self::load_checkpoint_interval()?;
+
+        let max_reorg_depth = Self::load_max_reorg_depth()?;
+
+        let (min_block_interval_seconds, block_seal_threshold) = Self::load_block_interval()?;
+
+        let (max_session_ttl_seconds, remember_me_ttl_seconds) = Self::load_session_ttls()?;
+
+        let prune_depth = Self::load_prune_depth()?;
+
+        let max_in_memory_blocks =
+            Self::parse_max_in_memory_blocks(env::var("MAX_IN_MEMORY_BLOCKS").ok())?;
+
+        let audit_retention_days =
+            Self::parse_audit_retention_days(env::var("AUDIT_RETENTION_DAYS").ok())?;
+
+        let api_key_kdf = Self::load_api_key_kdf()?;
+        let argon2_previous_params =
+            Self::parse_argon2_previous_params(env::var("ARGON2_PREVIOUS_PARAMS").ok())?;
+
+        // Exact indexes skip every non-matching block but reveal how many owners a block has
+        let exact_blind_indexes = Self::parse_bool_flag("EXACT_BLIND_INDEXES");
+
+        // Tells callers whether an API key is live, so only served when asked for
+        let account_exists_enabled = Self::parse_bool_flag("ENABLE_ACCOUNT_EXISTS");
+
+        // Maintenance mode: reads keep working, writes get 503 until switched off
+        let read_only = Self::parse_bool_flag("READ_ONLY");
+
+        // Part of the genesis hash - nodes with different values can't sync with each other
+        let genesis_timestamp = Self::parse_genesis_timestamp(env::var("GENESIS_TIMESTAMP").ok())?;
+
         Ok(Config {
             node_id,
             http_port,
             p2p_port,
             peers,
+            p2p_bind_addr,
+            p2p_advertise_addr,
+            p2p_tls,
+            cors,
+            trusted_proxies,
+            data_dir,
+            ephemeral_db_path,
             jwt_secret,
             session_secret,
+            admin_token,
             validator_config,
+            require_request_signatures,
+            node_signing_seed,
+            rate_limit,
+            metrics_update_interval_seconds,
+            max_payload_bytes,
+            request_timeout_seconds,
+            checkpoint_interval,
+            max_reorg_depth,
+            min_block_interval_seconds,
+            block_seal_threshold,
+            max_session_ttl_seconds,
+            remember_me_ttl_seconds,
+            prune_depth,
+            max_in_memory_blocks,
+            audit_retention_days,
+            api_key_kdf,
+            argon2_previous_params,
+            exact_blind_indexes,
+            account_exists_enabled,
+            read_only,
+            genesis_timestamp,
         })
     }
 
+    /// RocksDB directory inside the data directory
+    pub fn rocksdb_path(&self) -> PathBuf {
+        self.data_dir.join(ROCKSDB_DIRECTORY_NAME)
+    }
+
+    /// Backup directory inside the data directory (used by POST /admin/backup and restore)
+    pub fn backup_path(&self) -> PathBuf {
+        self.data_dir.join(BACKUP_DIRECTORY_NAME)
+    }
+
+    /// Largest HTTP request body accepted (the payload plus room for its JSON encoding)
+    pub fn max_request_body_bytes(&self) -> usize {
+        self.max_payload_bytes * crate::constants::REQUEST_BODY_LIMIT_MULTIPLIER
+    }
+
+    /// Largest /data/submit_batch body accepted (batches carry several payloads)
+    pub fn max_batch_request_body_bytes(&self) -> usize {
+        self.max_request_body_bytes() * crate::constants::BATCH_BODY_LIMIT_MULTIPLIER
+    }
+
     /// Load validator configuration from environment variables
     /// Format:
     ///   VALIDATORS=Validator_1,Validator_2,Validator_3
     ///   VALIDATOR_NODES=node1:Validator_1,node2:Validator_2,node3:Validator_3
     ///   VALIDATOR_ADDRESSES=Validator_1:node1:8080,Validator_2:node2:8080,Validator_3:node3:8080
+    ///   VALIDATOR_PUBLIC_KEYS=Validator_1:<hex>,Validator_2:<hex>
     fn load_validator_config() -> Result<ValidatorConfig, ConfigError> {
         // Parse validators list
         let validators: Vec<String> = env::var("VALIDATORS")
@@ -147,15 +529,27 @@ impl Config {
             validator_to_address.insert("Validator_3".to_string(), node3_addr);
         }
 
+        // Parse validator-to-public-key mapping (optional, no defaults)
+        let mut validator_public_keys = HashMap::new();
+        if let Ok(mapping) = env::var("VALIDATOR_PUBLIC_KEYS") {
+            for entry in mapping.split(',') {
+                if let Some((validator, public_key)) = entry.split_once(':') {
+                    validator_public_keys
+                        .insert(validator.trim().to_string(), public_key.trim().to_string());
+                }
+            }
+        }
+
         Ok(ValidatorConfig {
             validators,
             node_to_validator,
             validator_to_address,
+            validator_public_keys,
         })
     }
 
     /// Load JWT secret from environment or file, auto-generate if missing
-    fn load_jwt_secret() -> Result<Vec<u8>, ConfigError> {
+    fn load_jwt_secret(data_dir: &Path) -> Result<Vec<u8>, ConfigError> {
         // Try environment variable first
         if let Ok(secret) = env::var("JWT_SECRET") {
             let secret_bytes = secret.into_bytes();
@@ -166,8 +560,8 @@ impl Config {
         }
 
         // Try to load from persistent file
-        let secret_path = format!("{}/jwt_secret", DATA_DIRECTORY);
-        if Path::new(&secret_path).exists() {
+        let secret_path = data_dir.join("jwt_secret");
+        if secret_path.exists() {
             return Ok(fs::read(&secret_path)?);
         }
 
@@ -178,7 +572,7 @@ impl Config {
     }
 
     /// Load session secret from environment or file, auto-generate if missing
-    fn load_session_secret() -> Result<Vec<u8>, ConfigError> {
+    fn load_session_secret(data_dir: &Path) -> Result<Vec<u8>, ConfigError> {
         // Try environment variable first
         if let Ok(secret) = env::var("SESSION_SECRET") {
             let secret_bytes = secret.into_bytes();
@@ -189,8 +583,8 @@ impl Config {
         }
 
         // Try to load from persistent file
-        let secret_path = format!("{}/session_secret", DATA_DIRECTORY);
-        if Path::new(&secret_path).exists() {
+        let secret_path = data_dir.join("session_secret");
+        if secret_path.exists() {
             return Ok(fs::read(&secret_path)?);
         }
 
@@ -200,6 +594,543 @@ impl Config {
         Ok(new_secret.to_vec())
     }
 
+    /// Load the node signing key seed from environment (hex) or file, auto-generate if missing
+    fn load_node_signing_seed(data_dir: &Path) -> Result<[u8; 32], ConfigError> {
+        // Try environment variable first
+        if let Ok(seed_hex) = env::var("NODE_SIGNING_KEY") {
+            return hex::decode(seed_hex.trim())
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or(ConfigError::InvalidNodeSigningKey);
+        }
+
+        // Try to load from persistent file
+        let seed_path = data_dir.join("node_signing_key");
+        if seed_path.exists() {
+            return fs::read(&seed_path)?
+                .try_into()
+                .map_err(|_| ConfigError::InvalidNodeSigningKey);
+        }
+
+        // Auto-generate and persist
+        let new_seed: [u8; 32] = rand::random();
+        fs::write(&seed_path, new_seed)?;
+        Ok(new_seed)
+    }
+
+    /// Load per-endpoint rate limits from environment variables
+    /// Format:
+    ///   RATE_LIMIT_MODE=sliding_window|token_bucket
+    ///   RATE_LIMIT_BURST_SECONDS=<n>
+    ///   RATE_LIMIT_CLEANUP_INTERVAL_SECS=<n>
+    ///   RATE_LIMIT_{SUBMIT,BATCH,LIST,SEARCH,DECRYPT,DELETE,ACCOUNT_CREATE,ACCOUNT_EXISTS,PUBLIC,KEY_HASH}_PER_SEC=<n>
+    fn load_rate_limit_config() -> Result<RateLimitConfig, ConfigError> {
+        let defaults = RateLimitConfig::default();
+
+        let mode = match env::var("RATE_LIMIT_MODE") {
+            Ok(value) => match value.trim().to_lowercase().as_str() {
+                "sliding_window" => RateLimitMode::SlidingWindow,
+                "token_bucket" => RateLimitMode::TokenBucket,
+                _ => {
+                    return Err(ConfigError::InvalidRateLimit(
+                        "RATE_LIMIT_MODE must be sliding_window or token_bucket".to_string(),
+                    ))
+                }
+            },
+            Err(_) => defaults.mode,
+        };
+
+        Ok(RateLimitConfig {
+            mode,
+            burst_seconds: Self::parse_limit("RATE_LIMIT_BURST_SECONDS", defaults.burst_seconds)?,
+            cleanup_interval_seconds: Self::parse_limit(
+                "RATE_LIMIT_CLEANUP_INTERVAL_SECS",
+                defaults.cleanup_interval_seconds as u32,
+            )? as u64,
+            submit_per_second: Self::parse_limit(
+                "RATE_LIMIT_SUBMIT_PER_SEC",
+                defaults.submit_per_second,
+            )?,
+            batch_per_second: Self::parse_limit(
+                "RATE_LIMIT_BATCH_PER_SEC",
+                defaults.batch_per_second,
+            )?,
+            list_per_second: Self::parse_limit(
+                "RATE_LIMIT_LIST_PER_SEC",
+                defaults.list_per_second,
+            )?,
+            search_per_second: Self::parse_limit(
+                "RATE_LIMIT_SEARCH_PER_SEC",
+                defaults.search_per_second,
+            )?,
+            decrypt_per_second: Self::parse_limit(
+                "RATE_LIMIT_DECRYPT_PER_SEC",
+                defaults.decrypt_per_second,
+            )?,
+            delete_per_second: Self::parse_limit(
+                "RATE_LIMIT_DELETE_PER_SEC",
+                defaults.delete_per_second,
+            )?,
+            account_create_per_second: Self::parse_limit(
+                "RATE_LIMIT_ACCOUNT_CREATE_PER_SEC",
+                defaults.account_create_per_second,
+            )?,
+            account_exists_per_second: Self::parse_limit(
+                "RATE_LIMIT_ACCOUNT_EXISTS_PER_SEC",
+                defaults.account_exists_per_second,
+            )?,
+            public_per_second: Self::parse_limit(
+                "RATE_LIMIT_PUBLIC_PER_SEC",
+                defaults.public_per_second,
+            )?,
+            key_hash_per_second: Self::parse_limit(
+                "RATE_LIMIT_KEY_HASH_PER_SEC",
+                defaults.key_hash_per_second,
+            )?,
+        })
+    }
+
+    /// Load CORS configuration from environment variables
+    /// Format:
+    ///   ENABLE_CORS=true
+    ///   CORS_ALLOWED_ORIGINS=http://localhost:3000,https://app.example.com (default "*")
+    fn load_cors_config() -> Result<Option<CorsConfig>, ConfigError> {
+        if !Self::parse_bool_flag("ENABLE_CORS") {
+            return Ok(None);
+        }
+
+        let allowed_origins = Self::parse_cors_origins(env::var("CORS_ALLOWED_ORIGINS").ok())?;
+        Ok(Some(CorsConfig { allowed_origins }))
+    }
+
+    /// Parse a comma-separated origin list: "*" alone, or scheme://host[:port] entries
+    fn parse_cors_origins(value: Option<String>) -> Result<Vec<String>, ConfigError> {
+        let origins: Vec<String> = value
+            .unwrap_or_else(|| "*".to_string())
+            .split(',')
+            .map(|origin| origin.trim().to_string())
+            .filter(|origin| !origin.is_empty())
+            .collect();
+
+        if origins.is_empty() {
+            return Err(ConfigError::InvalidCors(
+                "CORS_ALLOWED_ORIGINS must list at least one origin".to_string(),
+            ));
+        }
+        if origins.iter().any(|origin| origin == "*") {
+            return if origins.len() == 1 {
+                Ok(origins)
+            } else {
+                Err(ConfigError::InvalidCors(
+                    "\"*\" cannot be combined with other origins".to_string(),
+                ))
+            };
+        }
+
+        for origin in &origins {
+            let host = origin
+                .strip_prefix("https://")
+                .or_else(|| origin.strip_prefix("http://"));
+            let is_valid = host.is_some_and(|host| {
+                !host.is_empty()
+                    && !host.contains('/')
+                    && host.bytes().all(|b| b.is_ascii_graphic())
+            });
+            if !is_valid {
+                return Err(ConfigError::InvalidCors(format!(
+                    "origin must be scheme://host[:port] without a path, got '{}'",
+                    origin
+                )));
+            }
+        }
+        Ok(origins)
+    }
+
+    /// Load P2P TLS configuration from environment variables
+    /// Format:
+    ///   P2P_TLS_ENABLED=true
+    ///   P2P_TLS_CERT_PATH=/certs/node1.pem
+    ///   P2P_TLS_KEY_PATH=/certs/node1.key
+    ///   P2P_TLS_PEER_FINGERPRINTS=<sha256 hex>,<sha256 hex>
+    fn load_p2p_tls_config() -> Result<Option<P2pTlsConfig>, ConfigError> {
+        if !Self::parse_bool_flag("P2P_TLS_ENABLED") {
+            return Ok(None);
+        }
+
+        let required = |name: &str| {
+            env::var(name)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| {
+                    ConfigError::InvalidP2pTls(format!(
+                        "{} is required when P2P_TLS_ENABLED=true",
+                        name
+                    ))
+                })
+        };
+
+        let cert_path = required("P2P_TLS_CERT_PATH")?;
+        let key_path = required("P2P_TLS_KEY_PATH")?;
+        let peer_fingerprints: Vec<String> = required("P2P_TLS_PEER_FINGERPRINTS")?
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        Ok(Some(P2pTlsConfig {
+            cert_path,
+            key_path,
+            peer_fingerprints,
+        }))
+    }
+
+    /// Parse a positive request limit from environment variable, falling back to a default
+    fn parse_limit(name: &str, default: u32) -> Result<u32, ConfigError> {
+        match env::var(name) {
+            Ok(value) => match value.trim().parse::<u32>() {
+                Ok(limit) if limit > 0 => Ok(limit),
+                _ => Err(ConfigError::InvalidRateLimit(format!(
+                    "{} must be a positive integer",
+                    name
+                ))),
+            },
+            Err(_) => Ok(default),
+        }
+    }
+
+    /// Load the metrics_update push interval (METRICS_UPDATE_INTERVAL_SECONDS, 0 disables)
+    fn load_metrics_update_interval() -> Result<u64, ConfigError> {
+        match env::var("METRICS_UPDATE_INTERVAL_SECONDS") {
+            Ok(value) => value
+                .trim()
+                .parse()
+                .map_err(|_| ConfigError::InvalidMetricsUpdateInterval),
+            Err(_) => Ok(crate::constants::DEFAULT_METRICS_UPDATE_INTERVAL_SECONDS),
+        }
+    }
+
+    /// Load the API request timeout (REQUEST_TIMEOUT_SECONDS, default 30 seconds)
+    fn load_request_timeout() -> Result<u64, ConfigError> {
+        match env::var("REQUEST_TIMEOUT_SECONDS") {
+            Ok(value) if !value.trim().is_empty() => match value.trim().parse::<u64>() {
+                Ok(seconds) if seconds > 0 => Ok(seconds),
+                _ => Err(ConfigError::InvalidRequestTimeout),
+            },
+            _ => Ok(DEFAULT_REQUEST_TIMEOUT_SECONDS),
+        }
+    }
+
+    /// Parse the genesis block timestamp (GENESIS_TIMESTAMP, default Jan 1, 2024 00:00:00 UTC)
+    /// Must not be in the future, or the genesis block would be newer than the blocks after it
+    fn parse_genesis_timestamp(value: Option<String>) -> Result<i64, ConfigError> {
+        match value {
+            Some(value) if !value.trim().is_empty() => match value.trim().parse::<i64>() {
+                Ok(timestamp) if (0..=chrono::Utc::now().timestamp()).contains(&timestamp) => {
+                    Ok(timestamp)
+                }
+                _ => Err(ConfigError::InvalidGenesisTimestamp),
+            },
+            _ => Ok(GENESIS_TIMESTAMP),
+        }
+    }
+
+    /// Load the checkpoint interval (CHECKPOINT_INTERVAL, default 100 blocks)
+    fn load_checkpoint_interval() -> Result<u64, ConfigError> {
+        match env::var("CHECKPOINT_INTERVAL") {
+            Ok(value) if !value.trim().is_empty() => match value.trim().parse::<u64>() {
+                Ok(interval) if interval > 0 => Ok(interval),
+                _ => Err(ConfigError::InvalidCheckpointInterval),
+            },
+            _ => Ok(DEFAULT_CHECKPOINT_INTERVAL),
+        }
+    }
+
+    /// Load the reorg depth limit (MAX_REORG_DEPTH, default 20 blocks)
+    fn load_max_reorg_depth() -> Result<u64, ConfigError> {
+        match env::var("MAX_REORG_DEPTH") {
+            Ok(value) if !value.trim().is_empty() => value
+                .trim()
+                .parse()
+                .map_err(|_| ConfigError::InvalidMaxReorgDepth),
+            _ => Ok(DEFAULT_MAX_REORG_DEPTH),
+        }
+    }
+
+    /// Load block coalescing (MIN_BLOCK_INTERVAL_SECONDS, default 0 = a block per write, and
+    /// BLOCK_SEAL_THRESHOLD, default 100 pending collections)
+    fn load_block_interval() -> Result<(u64, usize), ConfigError> {
+        let interval = match env::var("MIN_BLOCK_INTERVAL_SECONDS") {
+            Ok(value) if !value.trim().is_empty() => value.trim().parse().map_err(|_| {
+                ConfigError::InvalidBlockInterval(
+                    "MIN_BLOCK_INTERVAL_SECONDS must be a non-negative integer".to_string(),
+                )
+            })?,
+            _ => 0,
+        };
+        let threshold = match env::var("BLOCK_SEAL_THRESHOLD") {
+            Ok(value) if !value.trim().is_empty() => match value.trim().parse::<usize>() {
+                Ok(threshold) if threshold > 0 => threshold,
+                _ => {
+                    return Err(ConfigError::InvalidBlockInterval(
+                        "BLOCK_SEAL_THRESHOLD must be a positive integer".to_string(),
+                    ))
+                }
+            },
+            _ => DEFAULT_BLOCK_SEAL_THRESHOLD,
+        };
+        Ok((interval, threshold))
+    }
+
+    /// Load the session token lifetime caps (MAX_SESSION_TTL_SECONDS, default 24 hours, and
+    /// REMEMBER_ME_TTL_SECONDS, default 30 days)
+    fn load_session_ttls() -> Result<(i64, i64), ConfigError> {
+        let load = |name: &str, default: i64| match env::var(name) {
+            Ok(value) if !value.trim().is_empty() => match value.trim().parse::<i64>() {
+                Ok(ttl) if ttl >= MIN_SESSION_TTL_SECONDS => Ok(ttl),
+                _ => Err(ConfigError::InvalidSessionTtl(format!(
+                    "{} must be an integer of at least {}",
+                    name, MIN_SESSION_TTL_SECONDS
+                ))),
+            },
+            _ => Ok(default),
+        };
+        let max_session_ttl = load("MAX_SESSION_TTL_SECONDS", DEFAULT_MAX_SESSION_TTL_SECONDS)?;
+        let remember_me_ttl = load("REMEMBER_ME_TTL_SECONDS", DEFAULT_REMEMBER_ME_TTL_SECONDS)?;
+        Ok((max_session_ttl, remember_me_ttl))
+    }
+
+    /// Load the pruning depth (PRUNE_DEPTH, unset or empty disables pruning)
+    fn load_prune_depth() -> Result<Option<u64>, ConfigError> {
+        match env::var("PRUNE_DEPTH") {
+            Ok(value) if !value.trim().is_empty() => value
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|_| ConfigError::InvalidPruneDepth),
+            _ => Ok(None),
+        }
+    }
+
+    /// Parse the trusted proxy list (TRUSTED_PROXIES): comma-separated CIDRs or bare addresses
+    /// Malformed entries are rejected rather than skipped, since a typo would silently change
+    /// which address every rate limit is keyed on
+    fn parse_trusted_proxies(value: Option<String>) -> Result<Vec<IpNet>, ConfigError> {
+        value
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                entry
+                    .parse::<IpNet>()
+                    .or_else(|_| entry.parse::<IpAddr>().map(IpNet::from))
+                    .map_err(|_| ConfigError::InvalidTrustedProxy(entry.to_string()))
+            })
+            .collect()
+    }
+
+    /// Parse the in-memory block cap (MAX_IN_MEMORY_BLOCKS, unset or empty keeps every block in memory)
+    fn parse_max_in_memory_blocks(value: Option<String>) -> Result<Option<u64>, ConfigError> {
+        match value {
+            Some(value) if !value.trim().is_empty() => match value.trim().parse::<u64>() {
+                Ok(blocks) if blocks > 0 => Ok(Some(blocks)),
+                _ => Err(ConfigError::InvalidMaxInMemoryBlocks),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Parse the audit retention window (AUDIT_RETENTION_DAYS, unset or empty keeps entries forever)
+    fn parse_audit_retention_days(value: Option<String>) -> Result<Option<u64>, ConfigError> {
+        match value {
+            Some(value) if !value.trim().is_empty() => match value.trim().parse::<u64>() {
+                Ok(days) if (1..=MAX_AUDIT_RETENTION_DAYS).contains(&days) => Ok(Some(days)),
+                _ => Err(ConfigError::InvalidAuditRetention),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Load the API key hashing scheme (API_KEY_KDF=argon2id (default) or hkdf)
+    /// Argon2id costs come from ARGON2_MEMORY_KIB, ARGON2_ITERATIONS and ARGON2_PARALLELISM
+    fn load_api_key_kdf() -> Result<ApiKeyKdf, ConfigError> {
+        let kdf = env::var("API_KEY_KDF").unwrap_or_default();
+        match kdf.trim().to_lowercase().as_str() {
+            "hkdf" => return Ok(ApiKeyKdf::Hkdf),
+            "" | "argon2id" => {}
+            other => {
+                return Err(ConfigError::InvalidApiKeyKdf(format!(
+                    "unknown API_KEY_KDF '{}' (expected argon2id or hkdf)",
+                    other
+                )))
+            }
+        }
+
+        let load = |name: &str, default: u32, max: u32| match env::var(name) {
+            Ok(value) => match value.trim().parse::<u32>() {
+                Ok(parsed) if (1..=max).contains(&parsed) => Ok(parsed),
+                _ => Err(ConfigError::InvalidApiKeyKdf(format!(
+                    "{} must be between 1 and {}",
+                    name, max
+                ))),
+            },
+            Err(_) => Ok(default),
+        };
+        let params = Argon2Params {
+            memory_kib: load(
+                "ARGON2_MEMORY_KIB",
+                ARGON2_DEFAULT_MEMORY_KIB,
+                ARGON2_MAX_MEMORY_KIB,
+            )?,
+            iterations: load(
+                "ARGON2_ITERATIONS",
+                ARGON2_DEFAULT_ITERATIONS,
+                ARGON2_MAX_ITERATIONS,
+            )?,
+            parallelism: load(
+                "ARGON2_PARALLELISM",
+                ARGON2_DEFAULT_PARALLELISM,
+                ARGON2_MAX_PARALLELISM,
+            )?,
+        };
+
+        // Argon2 needs at least 8 KiB of memory per lane
+        if params.memory_kib < 8 * params.parallelism {
+            return Err(ConfigError::InvalidApiKeyKdf(format!(
+                "ARGON2_MEMORY_KIB must be at least {} for {} lanes",
+                8 * params.parallelism,
+                params.parallelism
+            )));
+        }
+
+        Ok(ApiKeyKdf::Argon2id(params))
+    }
+
+    /// Parse ARGON2_PREVIOUS_PARAMS: `;`-separated parameter sets in stored-hash form
+    /// (`m=19456,t=2,p=1`), so keys issued before a retune keep authenticating
+    fn parse_argon2_previous_params(
+        value: Option<String>,
+    ) -> Result<Vec<Argon2Params>, ConfigError> {
+        value
+            .unwrap_or_default()
+            .split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                parse_argon2_params(entry).ok_or_else(|| {
+                    ConfigError::InvalidApiKeyKdf(format!(
+                        "ARGON2_PREVIOUS_PARAMS entry '{}' must look like m=19456,t=2,p=1 and stay within the maximums",
+                        entry
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Whether this node computes hashes under `kdf`
+    /// HKDF always; Argon2id only with the configured or previously configured parameters,
+    /// since a key names its own parameters and would otherwise pick the node's cost.
+    pub fn accepts_api_key_kdf(&self, kdf: &ApiKeyKdf) -> bool {
+        match kdf {
+            ApiKeyKdf::Hkdf => true,
+            ApiKeyKdf::Argon2id(params) => {
+                self.api_key_kdf == *kdf || self.argon2_previous_params.contains(params)
+            }
+        }
+    }
+
+    /// Load the data directory (DATA_DIR, default /data) and create it if missing
+    fn load_data_dir() -> Result<PathBuf, ConfigError> {
+        let data_dir = match env::var("DATA_DIR") {
+            Ok(value) if !value.trim().is_empty() => PathBuf::from(value.trim()),
+            _ => PathBuf::from(DATA_DIRECTORY),
+        };
+
+        if data_dir.exists() && !data_dir.is_dir() {
+            return Err(ConfigError::InvalidDataDir(format!(
+                "{} exists and is not a directory",
+                data_dir.display()
+            )));
+        }
+        fs::create_dir_all(&data_dir).map_err(|e| {
+            ConfigError::InvalidDataDir(format!("cannot create {}: {}", data_dir.display(), e))
+        })?;
+
+        Ok(data_dir)
+    }
+
+    /// Parse the ephemeral database path (EPHEMERAL_DB_PATH, unset shares the chain database)
+    /// Must not be the chain database itself, or wiping it would take the chain with it
+    fn parse_ephemeral_db_path(
+        value: Option<String>,
+        data_dir: &Path,
+    ) -> Result<Option<PathBuf>, ConfigError> {
+        let path = match value {
+            Some(value) if !value.trim().is_empty() => data_dir.join(value.trim()),
+            _ => return Ok(None),
+        };
+
+        let chain_db = data_dir.join(ROCKSDB_DIRECTORY_NAME);
+        if path == chain_db || path == data_dir || chain_db.starts_with(&path) {
+            return Err(ConfigError::InvalidEphemeralDbPath(format!(
+                "{} would contain the chain database",
+                path.display()
+            )));
+        }
+        if path.starts_with(&chain_db) {
+            return Err(ConfigError::InvalidEphemeralDbPath(format!(
+                "{} is inside the chain database",
+                path.display()
+            )));
+        }
+        if path.exists() && !path.is_dir() {
+            return Err(ConfigError::InvalidEphemeralDbPath(format!(
+                "{} exists and is not a directory",
+                path.display()
+            )));
+        }
+
+        Ok(Some(path))
+    }
+
+    /// Load the collection payload limit (MAX_PAYLOAD_BYTES)
+    /// Capped at the decompression bound so every accepted payload can be read back
+    fn load_max_payload_bytes() -> Result<usize, ConfigError> {
+        use crate::constants::{DEFAULT_MAX_PAYLOAD_BYTES, MAX_DECOMPRESSED_PAYLOAD_BYTES};
+
+        match env::var("MAX_PAYLOAD_BYTES") {
+            Ok(value) => match value.trim().parse::<usize>() {
+                Ok(limit) if (1..=MAX_DECOMPRESSED_PAYLOAD_BYTES).contains(&limit) => Ok(limit),
+                _ => Err(ConfigError::InvalidPayloadLimit(
+                    MAX_DECOMPRESSED_PAYLOAD_BYTES,
+                )),
+            },
+            Err(_) => Ok(DEFAULT_MAX_PAYLOAD_BYTES),
+        }
+    }
+
+    /// Parse the operator credential (ADMIN_TOKEN, unset or empty disables the admin endpoints)
+    fn parse_admin_token(value: Option<String>) -> Result<Option<Vec<u8>>, ConfigError> {
+        match value {
+            Some(value) if !value.trim().is_empty() => {
+                let token = value.trim().as_bytes().to_vec();
+                if token.len() < MIN_ADMIN_TOKEN_BYTES {
+                    return Err(ConfigError::WeakAdminToken);
+                }
+                Ok(Some(token))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Parse a boolean feature flag from environment variable (defaults to false)
+    fn parse_bool_flag(name: &str) -> bool {
+        env::var(name)
+            .map(|value| matches!(value.trim().to_lowercase().as_str(), "true" | "1" | "yes"))
+            .unwrap_or(false)
+    }
+
     /// Parse peer URLs from environment variable
     fn parse_peers() -> Vec<String> {
         env::var("PEERS")
@@ -214,6 +1145,57 @@ impl Config {
             .unwrap_or_default()
     }
 
+    /// Parse P2P_BIND_ADDR: a full socket address, or a bare IP that listens on P2P_PORT
+    fn parse_p2p_bind_addr(
+        value: Option<String>,
+        p2p_port: u16,
+    ) -> Result<SocketAddr, ConfigError> {
+        let value = value
+            .map(|addr| addr.trim().to_string())
+            .filter(|addr| !addr.is_empty());
+        let Some(value) = value else {
+            return Ok(SocketAddr::from(([0, 0, 0, 0], p2p_port)));
+        };
+
+        value
+            .parse::<SocketAddr>()
+            .or_else(|_| {
+                value
+                    .parse::<IpAddr>()
+                    .map(|ip| SocketAddr::new(ip, p2p_port))
+            })
+            .map_err(|_| {
+                ConfigError::InvalidP2pAddress(format!(
+                    "P2P_BIND_ADDR must be an IP address or IP:port, got '{}'",
+                    value
+                ))
+            })
+    }
+
+    /// Parse P2P_ADVERTISE_ADDR: host:port as peers will dial it (hostnames allowed)
+    fn parse_p2p_advertise_addr(value: Option<String>) -> Result<Option<String>, ConfigError> {
+        let value = value
+            .map(|addr| addr.trim().to_string())
+            .filter(|addr| !addr.is_empty());
+        let Some(value) = value else {
+            return Ok(None);
+        };
+
+        let is_valid = value.rsplit_once(':').is_some_and(|(host, port)| {
+            !host.is_empty()
+                && !host.contains(char::is_whitespace)
+                && port.parse::<u16>().is_ok_and(|port| port != 0)
+        });
+        if is_valid {
+            Ok(Some(value))
+        } else {
+            Err(ConfigError::InvalidP2pAddress(format!(
+                "P2P_ADVERTISE_ADDR must be host:port, got '{}'",
+                value
+            )))
+        }
+    }
+
     /// Get HTTP bind address
     pub fn http_bind_addr(&self) -> String {
         format!("0.0.0.0:{}", self.http_port)
@@ -231,9 +1213,510 @@ pub enum ConfigError {
     #[error("SESSION_SECRET must be at least 32 bytes (256 bits)")]
     WeakSessionSecret,
 
+    #[error("ADMIN_TOKEN must be at least {} bytes", MIN_ADMIN_TOKEN_BYTES)]
+    WeakAdminToken,
+
+    #[error("NODE_SIGNING_KEY must be a 32-byte hex-encoded Ed25519 seed")]
+    InvalidNodeSigningKey,
+
+    #[error("Invalid rate limit configuration: {0}")]
+    InvalidRateLimit(String),
+
+    #[error("Invalid P2P address: {0}")]
+    InvalidP2pAddress(String),
+
+    #[error("Invalid CORS configuration: {0}")]
+    InvalidCors(String),
+
+    #[error("Invalid TRUSTED_PROXIES entry: {0}")]
+    InvalidTrustedProxy(String),
+
+    #[error("Invalid P2P TLS configuration: {0}")]
+    InvalidP2pTls(String),
+
+    #[error("METRICS_UPDATE_INTERVAL_SECONDS must be a non-negative integer")]
+    InvalidMetricsUpdateInterval,
+
+    #[error("MAX_PAYLOAD_BYTES must be between 1 and {0}")]
+    InvalidPayloadLimit(usize),
+
+    #[error("REQUEST_TIMEOUT_SECONDS must be a positive integer")]
+    InvalidRequestTimeout,
+
+    #[error("GENESIS_TIMESTAMP must be a Unix timestamp that is not in the future")]
+    InvalidGenesisTimestamp,
+
+    #[error("CHECKPOINT_INTERVAL must be a positive integer")]
+    InvalidCheckpointInterval,
+
+    #[error("MAX_REORG_DEPTH must be a non-negative integer")]
+    InvalidMaxReorgDepth,
+
+    #[error("Invalid block interval configuration: {0}")]
+    InvalidBlockInterval(String),
+
+    #[error("Invalid session lifetime configuration: {0}")]
+    InvalidSessionTtl(String),
+
+    #[error("PRUNE_DEPTH must be a non-negative integer")]
+    InvalidPruneDepth,
+
+    #[error("MAX_IN_MEMORY_BLOCKS must be a positive integer")]
+    InvalidMaxInMemoryBlocks,
+
+    #[error(
+        "AUDIT_RETENTION_DAYS must be between 1 and {}",
+        MAX_AUDIT_RETENTION_DAYS
+    )]
+    InvalidAuditRetention,
+
+    #[error("Invalid DATA_DIR: {0}")]
+    InvalidDataDir(String),
+
+    #[error("Invalid EPHEMERAL_DB_PATH: {0}")]
+    InvalidEphemeralDbPath(String),
+
+    #[error("Invalid API key hashing configuration: {0}")]
+    InvalidApiKeyKdf(String),
+
     #[error("Invalid validator configuration: {0}")]
     InvalidValidatorConfig(String),
 
+    #[error("Invalid logging configuration: {0}")]
+    InvalidLogConfig(String),
+
     #[error("Failed to load/save secret: {0}")]
     IoError(#[from] std::io::Error),
 }
+
+#[cfg(test)]
+impl ValidatorConfig {
+    /// Single-validator config in which `node_id` produces every block (test helper)
+    pub fn single(node_id: &str) -> Self {
+        let mut node_to_validator = HashMap::new();
+        node_to_validator.insert(node_id.to_string(), "Validator_1".to_string());
+
+        let mut validator_to_address = HashMap::new();
+        validator_to_address.insert("Validator_1".to_string(), format!("{}:8080", node_id));
+
+        ValidatorConfig {
+            validators: vec!["Validator_1".to_string()],
+            node_to_validator,
+            validator_to_address,
+            validator_public_keys: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Config {
+    /// Config for handler tests: `node1` as the only validator, fixed secrets and default limits
+    pub fn for_tests() -> Self {
+        Config {
+            node_id: "node1".to_string(),
+            http_port: "8080".to_string(),
+            p2p_port: 9000,
+            peers: vec![],
+            p2p_bind_addr: "0.0.0.0:9000".parse().unwrap(),
+            p2p_advertise_addr: None,
+            p2p_tls: None,
+            cors: None,
+            trusted_proxies: Vec::new(),
+            data_dir: std::env::temp_dir(),
+            ephemeral_db_path: None,
+            jwt_secret: b"test_jwt_secret_min_32_bytes_long_123456".to_vec(),
+            session_secret: b"test_session_secret_min_32_bytes_long".to_vec(),
+            admin_token: None,
+            validator_config: ValidatorConfig::single("node1"),
+            require_request_signatures: false,
+            node_signing_seed: [7u8; 32],
+            rate_limit: RateLimitConfig::default(),
+            metrics_update_interval_seconds: 0,
+            max_payload_bytes: crate::constants::DEFAULT_MAX_PAYLOAD_BYTES,
+            request_timeout_seconds: crate::constants::DEFAULT_REQUEST_TIMEOUT_SECONDS,
+            checkpoint_interval: crate::constants::DEFAULT_CHECKPOINT_INTERVAL,
+            max_reorg_depth: crate::constants::DEFAULT_MAX_REORG_DEPTH,
+            min_block_interval_seconds: 0,
+            block_seal_threshold: crate::constants::DEFAULT_BLOCK_SEAL_THRESHOLD,
+            max_session_ttl_seconds: crate::constants::DEFAULT_MAX_SESSION_TTL_SECONDS,
+            remember_me_ttl_seconds: crate::constants::DEFAULT_REMEMBER_ME_TTL_SECONDS,
+            prune_depth: None,
+            max_in_memory_blocks: None,
+            audit_retention_days: None,
+            api_key_kdf: ApiKeyKdf::Hkdf,
+            argon2_previous_params: Vec::new(),
+            exact_blind_indexes: false,
+            account_exists_enabled: false,
+            read_only: false,
+            genesis_timestamp: crate::constants::GENESIS_TIMESTAMP,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn five_validator_config() -> ValidatorConfig {
+        let validators: Vec<String> = (1..=5).map(|i| format!("Validator_{}", i)).collect();
+        let node_to_validator = (1..=5)
+            .map(|i| (format!("node{}", i), format!("Validator_{}", i)))
+            .collect();
+        let validator_to_address = (1..=5)
+            .map(|i| (format!("Validator_{}", i), format!("node{}:8080", i)))
+            .collect();
+
+        ValidatorConfig {
+            validators,
+            node_to_validator,
+            validator_to_address,
+            validator_public_keys: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_log_config_parsing() {
+        let defaults = LogConfig::parse(None, None).unwrap();
+        assert_eq!(defaults.format, LogFormat::Pretty);
+        assert_eq!(defaults.level, DEFAULT_LOG_LEVEL);
+
+        let json = LogConfig::parse(Some(" JSON "), Some("goud_chain=debug".to_string())).unwrap();
+        assert_eq!(json.format, LogFormat::Json);
+        assert_eq!(json.level, "goud_chain=debug");
+
+        assert!(LogConfig::parse(Some("xml"), None).is_err());
+        assert_eq!(
+            LogConfig::parse(None, Some("  ".to_string()))
+                .unwrap()
+                .level,
+            DEFAULT_LOG_LEVEL
+        );
+    }
+
+    #[test]
+    fn test_five_validator_rotation() {
+        let config = five_validator_config();
+
+        for block_number in 0..15u64 {
+            let expected = format!("Validator_{}", block_number % 5 + 1);
+            assert_eq!(config.get_validator_for_block(block_number), expected);
+        }
+    }
+
+    #[test]
+    fn test_node_for_validator() {
+        let config = five_validator_config();
+
+        assert_eq!(config.get_node_for_validator("Validator_3"), Some("node3"));
+        assert_eq!(config.get_node_for_validator("Validator_9"), None);
+    }
+
+    #[test]
+    fn test_five_validator_authorization() {
+        let config = five_validator_config();
+
+        // Block 7 belongs to Validator_3 (7 % 5 = 2) - only node3 may produce it
+        assert!(config.is_node_authorized("node3", 7));
+        for node in ["node1", "node2", "node4", "node5", "observer"] {
+            assert!(!config.is_node_authorized(node, 7));
+        }
+
+        // Each node gets exactly one block per rotation
+        for node in 1..=5 {
+            let node_id = format!("node{}", node);
+            let produced = (0..5u64)
+                .filter(|&b| config.is_node_authorized(&node_id, b))
+                .count();
+            assert_eq!(produced, 1);
+        }
+    }
+
+    #[test]
+    fn test_is_validator_node() {
+        let mut config = five_validator_config();
+
+        assert!(config.is_validator_node("node1"));
+        assert!(!config.is_validator_node("observer"));
+
+        config
+            .node_to_validator
+            .insert("node6".to_string(), "Validator_6".to_string());
+        assert!(!config.is_validator_node("node6"));
+    }
+
+    #[test]
+    fn test_validate_for_node() {
+        let mut config = five_validator_config();
+
+        assert!(config.validate_for_node("node5").is_ok());
+        // Unmapped nodes are non-validating observers
+        assert!(config.validate_for_node("observer").is_ok());
+
+        config
+            .node_to_validator
+            .insert("node6".to_string(), "Validator_6".to_string());
+        assert!(matches!(
+            config.validate_for_node("node6"),
+            Err(ConfigError::InvalidValidatorConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_p2p_bind_addr_parsing() {
+        let default = Config::parse_p2p_bind_addr(None, 9000).unwrap();
+        assert_eq!(default.to_string(), "0.0.0.0:9000");
+
+        let bare_ip = Config::parse_p2p_bind_addr(Some(" 10.0.0.5 ".to_string()), 9000).unwrap();
+        assert_eq!(bare_ip.to_string(), "10.0.0.5:9000");
+
+        let full = Config::parse_p2p_bind_addr(Some("[::]:9100".to_string()), 9000).unwrap();
+        assert_eq!(full.port(), 9100);
+
+        assert!(matches!(
+            Config::parse_p2p_bind_addr(Some("node1:9000".to_string()), 9000),
+            Err(ConfigError::InvalidP2pAddress(_))
+        ));
+    }
+
+    #[test]
+    fn test_p2p_advertise_addr_parsing() {
+        assert_eq!(Config::parse_p2p_advertise_addr(None).unwrap(), None);
+        assert_eq!(
+            Config::parse_p2p_advertise_addr(Some("  ".to_string())).unwrap(),
+            None
+        );
+        assert_eq!(
+            Config::parse_p2p_advertise_addr(Some("node1.example.com:19000".to_string()))
+                .unwrap()
+                .as_deref(),
+            Some("node1.example.com:19000")
+        );
+
+        for invalid in ["node1", ":9000", "node1:0", "node1:http", "node 1:9000"] {
+            assert!(
+                Config::parse_p2p_advertise_addr(Some(invalid.to_string())).is_err(),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_cors_origin_parsing() {
+        assert_eq!(Config::parse_cors_origins(None).unwrap(), vec!["*"]);
+        assert_eq!(
+            Config::parse_cors_origins(Some(
+                "http://localhost:3000, https://app.example.com".to_string()
+            ))
+            .unwrap(),
+            vec!["http://localhost:3000", "https://app.example.com"]
+        );
+
+        for invalid in [
+            "",
+            "*,http://localhost:3000",
+            "localhost:3000",
+            "https://app.example.com/",
+            "ftp://app.example.com",
+        ] {
+            assert!(
+                matches!(
+                    Config::parse_cors_origins(Some(invalid.to_string())),
+                    Err(ConfigError::InvalidCors(_))
+                ),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_genesis_timestamp_parsing() {
+        assert_eq!(
+            Config::parse_genesis_timestamp(None).unwrap(),
+            GENESIS_TIMESTAMP
+        );
+        assert_eq!(
+            Config::parse_genesis_timestamp(Some(" ".to_string())).unwrap(),
+            GENESIS_TIMESTAMP
+        );
+        assert_eq!(
+            Config::parse_genesis_timestamp(Some("0".to_string())).unwrap(),
+            0
+        );
+        assert_eq!(
+            Config::parse_genesis_timestamp(Some("1700000000".to_string())).unwrap(),
+            1700000000
+        );
+
+        let tomorrow = (chrono::Utc::now().timestamp() + 86400).to_string();
+        for invalid in ["-1", "soon", "1.5", tomorrow.as_str()] {
+            assert!(
+                matches!(
+                    Config::parse_genesis_timestamp(Some(invalid.to_string())),
+                    Err(ConfigError::InvalidGenesisTimestamp)
+                ),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_ephemeral_db_path_parsing() {
+        let data_dir = Path::new("/data");
+
+        assert_eq!(
+            Config::parse_ephemeral_db_path(None, data_dir).unwrap(),
+            None
+        );
+        assert_eq!(
+            Config::parse_ephemeral_db_path(Some(" ".to_string()), data_dir).unwrap(),
+            None
+        );
+        assert_eq!(
+            Config::parse_ephemeral_db_path(Some("ephemeral".to_string()), data_dir).unwrap(),
+            Some(PathBuf::from("/data/ephemeral"))
+        );
+        assert_eq!(
+            Config::parse_ephemeral_db_path(Some("/scratch/goud".to_string()), data_dir).unwrap(),
+            Some(PathBuf::from("/scratch/goud"))
+        );
+
+        for invalid in [
+            "rocksdb",
+            "/data/rocksdb/",
+            "/data",
+            "/",
+            "rocksdb/ephemeral",
+        ] {
+            assert!(
+                matches!(
+                    Config::parse_ephemeral_db_path(Some(invalid.to_string()), data_dir),
+                    Err(ConfigError::InvalidEphemeralDbPath(_))
+                ),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_trusted_proxies_parsing() {
+        assert!(Config::parse_trusted_proxies(None).unwrap().is_empty());
+        assert!(Config::parse_trusted_proxies(Some(" ".to_string()))
+            .unwrap()
+            .is_empty());
+
+        let proxies =
+            Config::parse_trusted_proxies(Some("172.16.0.0/12, 10.0.0.5,fd00::/8".to_string()))
+                .unwrap();
+        assert_eq!(proxies.len(), 3);
+        assert!(proxies[1].contains(&"10.0.0.5".parse::<IpAddr>().unwrap()));
+        assert!(!proxies[1].contains(&"10.0.0.6".parse::<IpAddr>().unwrap()));
+
+        assert!(matches!(
+            Config::parse_trusted_proxies(Some("10.0.0.0/8,nginx".to_string())),
+            Err(ConfigError::InvalidTrustedProxy(entry)) if entry == "nginx"
+        ));
+    }
+
+    #[test]
+    fn test_argon2_previous_params() {
+        assert!(Config::parse_argon2_previous_params(None)
+            .unwrap()
+            .is_empty());
+
+        let previous =
+            Config::parse_argon2_previous_params(Some("m=19456,t=2,p=1; m=4096,t=3,p=1".into()))
+                .unwrap();
+        assert_eq!(previous.len(), 2);
+        assert!(Config::parse_argon2_previous_params(Some("m=99999999,t=1,p=1".into())).is_err());
+        assert!(Config::parse_argon2_previous_params(Some("19456,2,1".into())).is_err());
+
+        let current = Argon2Params {
+            memory_kib: 8192,
+            iterations: 2,
+            parallelism: 1,
+        };
+        let config = Config {
+            api_key_kdf: ApiKeyKdf::Argon2id(current),
+            argon2_previous_params: previous.clone(),
+            ..Config::for_tests()
+        };
+        assert!(config.accepts_api_key_kdf(&ApiKeyKdf::Hkdf));
+        assert!(config.accepts_api_key_kdf(&ApiKeyKdf::Argon2id(current)));
+        assert!(config.accepts_api_key_kdf(&ApiKeyKdf::Argon2id(previous[1])));
+
+        // Keys cannot pick a costlier (or any other) parameter set
+        let costlier = Argon2Params {
+            memory_kib: crate::constants::ARGON2_MAX_MEMORY_KIB,
+            iterations: crate::constants::ARGON2_MAX_ITERATIONS,
+            parallelism: 1,
+        };
+        assert!(!config.accepts_api_key_kdf(&ApiKeyKdf::Argon2id(costlier)));
+    }
+
+    #[test]
+    fn test_max_in_memory_blocks_parsing() {
+        assert_eq!(Config::parse_max_in_memory_blocks(None).unwrap(), None);
+        assert_eq!(
+            Config::parse_max_in_memory_blocks(Some(" ".to_string())).unwrap(),
+            None
+        );
+        assert_eq!(
+            Config::parse_max_in_memory_blocks(Some("50000".to_string())).unwrap(),
+            Some(50_000)
+        );
+        for invalid in ["0", "-5", "lots"] {
+            assert!(matches!(
+                Config::parse_max_in_memory_blocks(Some(invalid.to_string())),
+                Err(ConfigError::InvalidMaxInMemoryBlocks)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_audit_retention_parsing() {
+        assert_eq!(Config::parse_audit_retention_days(None).unwrap(), None);
+        assert_eq!(
+            Config::parse_audit_retention_days(Some("".to_string())).unwrap(),
+            None
+        );
+        assert_eq!(
+            Config::parse_audit_retention_days(Some(" 90 ".to_string())).unwrap(),
+            Some(90)
+        );
+
+        let too_long = (MAX_AUDIT_RETENTION_DAYS + 1).to_string();
+        for invalid in ["0", "-1", "30d", too_long.as_str()] {
+            assert!(
+                matches!(
+                    Config::parse_audit_retention_days(Some(invalid.to_string())),
+                    Err(ConfigError::InvalidAuditRetention)
+                ),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_admin_token_parsing() {
+        assert_eq!(Config::parse_admin_token(None).unwrap(), None);
+        assert_eq!(
+            Config::parse_admin_token(Some("".to_string())).unwrap(),
+            None
+        );
+
+        let token = "a".repeat(MIN_ADMIN_TOKEN_BYTES);
+        assert_eq!(
+            Config::parse_admin_token(Some(format!(" {} ", token))).unwrap(),
+            Some(token.into_bytes())
+        );
+        assert!(matches!(
+            Config::parse_admin_token(Some("short".to_string())),
+            Err(ConfigError::WeakAdminToken)
+        ));
+    }
+}
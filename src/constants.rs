@@ -2,18 +2,33 @@
 //! This module contains all magic numbers and strings used throughout the application.
 
 // Schema versioning
-pub const SCHEMA_VERSION: &str = "v8_envelope_encryption";
+pub const SCHEMA_VERSION: &str = "v9_materialized_blind_indexes";
 
 // Storage paths
-pub const DATA_DIRECTORY: &str = "/data";
-pub const ROCKSDB_PATH: &str = "/data/rocksdb";
+pub const DATA_DIRECTORY: &str = "/data"; // Default data directory (override with DATA_DIR)
+pub const ROCKSDB_DIRECTORY_NAME: &str = "rocksdb"; // RocksDB lives in <data dir>/rocksdb
+pub const BACKUP_DIRECTORY_NAME: &str = "backups"; // RocksDB BackupEngine directory (incremental backups)
+pub const BACKUP_RETENTION_COUNT: usize = 7; // Older backups are purged after each new one
+pub const MIGRATIONS_COLUMN_FAMILY: &str = "migrations"; // Applied migration records live apart from chain data
 
 // Blockchain parameters
-pub const CHECKPOINT_INTERVAL: u64 = 100;
+pub const DEFAULT_CHECKPOINT_INTERVAL: u64 = 100; // Blocks between automatic checkpoints (override with CHECKPOINT_INTERVAL)
+pub const DEFAULT_MAX_REORG_DEPTH: u64 = 20; // Most blocks a reorg may roll back from our tip (override with MAX_REORG_DEPTH)
+pub const DEFAULT_BLOCK_SEAL_THRESHOLD: usize = 100; // Pending collections that seal a coalesced block early (override with BLOCK_SEAL_THRESHOLD)
+pub const BLOCK_SEAL_CHECK_INTERVAL_SECONDS: u64 = 1; // How often the sealer checks MIN_BLOCK_INTERVAL_SECONDS
+pub const PRUNE_INTERVAL_SECONDS: u64 = 3600; // Pruning pass every hour when PRUNE_DEPTH is set
+pub const BLOCK_OFFLOAD_INTERVAL_SECONDS: u64 = 300; // Offload pass every 5 minutes when MAX_IN_MEMORY_BLOCKS is set
 pub const TIMESTAMP_TOLERANCE_SECONDS: i64 = 14400; // Must accommodate ±4 hour jitter for timestamp obfuscation
 pub const TIMESTAMP_GRANULARITY_SECONDS: i64 = 86400; // 1 day - hides exact timing and timezone
 pub const TIMESTAMP_JITTER_SECONDS: i64 = 14400; // ±4 hours random jitter prevents pattern analysis
 
+// Per-block owner bloom filter (stored as one entry in Block::blind_indexes)
+pub const BLOOM_FILTER_PREFIX: &str = "bloom1:";
+pub const BLOOM_FILTER_BITS_PER_ENTRY: usize = 10; // With 7 probes: ~1% false positives
+pub const BLOOM_FILTER_HASH_COUNT: u32 = 7;
+pub const BLOOM_FILTER_MIN_BITS: usize = 64;
+pub const LEGACY_BLIND_INDEX_PREFIX: &str = "legacy_blind_index:"; // legacy_blind_index:{block_hash} → owner blind indexes (backfill for blocks stored without any)
+
 // Cryptography constants
 pub const ENCRYPTION_SALT: &[u8] = b"goud_chain_salt_v2";
 pub const NONCE_SIZE_BYTES: usize = 12;
@@ -28,15 +43,48 @@ pub const HKDF_ITERATIONS: u32 = 100_000; // OWASP recommended: 100k+ iterations
                                           // Used AFTER API key has been validated - iteration count doesn't affect domain separation security
 pub const HKDF_FAST_ITERATIONS: u32 = 1_000;
 
+// Argon2id API key hashing (selected with API_KEY_KDF, tuned with ARGON2_*)
+// Defaults follow the OWASP minimum: 19 MiB, 2 passes, 1 lane
+pub const ARGON2_DEFAULT_MEMORY_KIB: u32 = 19_456;
+pub const ARGON2_DEFAULT_ITERATIONS: u32 = 2;
+pub const ARGON2_DEFAULT_PARALLELISM: u32 = 1;
+// Upper bounds for configured parameters (keys name theirs, but only configured sets are computed)
+pub const ARGON2_MAX_MEMORY_KIB: u32 = 65_536;
+pub const ARGON2_MAX_ITERATIONS: u32 = 10;
+pub const ARGON2_MAX_PARALLELISM: u32 = 4;
+// Fixed salt: the hash doubles as the account lookup identity, so it must be deterministic
+pub const ARGON2_API_KEY_SALT: &[u8] = b"goud_chain_api_key_argon2id_v3";
+pub const ARGON2_HASH_PREFIX: &str = "argon2id";
+// Argon2id keys start with a header: version byte, memory KiB (u32 LE), iterations (u8), lanes (u8)
+pub const API_KEY_VERSION_ARGON2ID: u8 = 3;
+pub const API_KEY_KDF_HEADER_BYTES: usize = 7;
+
+// Derived key cache (overridable via KEY_CACHE_MAX_ENTRIES / KEY_CACHE_TTL_SECONDS)
+pub const KEY_CACHE_DEFAULT_MAX_ENTRIES: usize = 1000;
+pub const KEY_CACHE_DEFAULT_TTL_SECONDS: u64 = 600; // Rarely-used derived keys leave memory after 10 minutes
+pub const KEY_CACHE_PURGE_INTERVAL_SECONDS: u64 = 60; // Sweep expired entries every minute
+
 // API Key derivation contexts
 pub const HKDF_CONTEXT_ENCRYPTION: &[u8] = b"goud_chain_encryption_v2";
 pub const HKDF_CONTEXT_MAC: &[u8] = b"goud_chain_mac_v2";
 
 // Session secret contexts (for JWT token encryption)
 pub const HKDF_CONTEXT_SESSION_ENCRYPTION: &[u8] = b"goud_chain_session_aes_v1";
+pub const HKDF_CONTEXT_NODE_AUDIT: &[u8] = b"goud_chain_node_audit_v1";
+
+// Collection sharing contexts (X25519 sharing keys and sealed grant keys)
+pub const HKDF_CONTEXT_SHARING_KEY: &[u8] = b"goud_chain_sharing_key_v1";
+pub const HKDF_CONTEXT_SHARE_GRANT: &[u8] = b"goud_chain_share_grant_v1";
+pub const SHARE_RECIPIENT_INDEX_CONTEXT: &[u8] = b"goud_chain_share_recipient_v1";
 
 // JWT/Session
-pub const SESSION_EXPIRY_SECONDS: i64 = 3600; // 1 hour
+pub const SESSION_EXPIRY_SECONDS: i64 = 3600; // 1 hour (default when login sends no ttl_seconds)
+pub const MIN_SESSION_TTL_SECONDS: i64 = 60; // Shortest ttl_seconds accepted at login
+pub const DEFAULT_MAX_SESSION_TTL_SECONDS: i64 = 86400; // 24 hours (MAX_SESSION_TTL_SECONDS)
+pub const DEFAULT_REMEMBER_ME_TTL_SECONDS: i64 = 2_592_000; // 30 days (REMEMBER_ME_TTL_SECONDS)
+pub const SESSION_REFRESH_GRACE_SECONDS: i64 = 300; // Expired tokens can still be refreshed for 5 minutes
+pub const LOGIN_MIN_RESPONSE_MILLIS: u64 = 300; // Login responses are padded to at least this long (hides which check failed)
+pub const TOKEN_REVOCATION_CLEANUP_INTERVAL_SECONDS: u64 = 600; // Clean up expired revocations every 10 minutes
 
 // Genesis block
 pub const GENESIS_PREVIOUS_HASH: &str = "0";
@@ -56,22 +104,54 @@ pub const REPUTATION_PENALTY_INVALID_BLOCK: i32 = -5;
 // P2P Network Security
 pub const MIN_REPUTATION_THRESHOLD: i32 = -10; // Block peers below this reputation
 pub const MAX_MESSAGES_PER_MINUTE: u32 = 100;
+pub const MAX_PEERS: usize = 50; // Cap on known peers (configured + discovered)
+pub const MAX_PEERS_PER_EXCHANGE: usize = 100; // Addresses accepted from a single Peers response
+pub const PEER_EXCHANGE_INTERVAL_SECONDS: u64 = 300; // Peer-exchange round every 5 minutes
+pub const PEER_PING_INTERVAL_SECONDS: u64 = 30; // Heartbeat every 30 seconds
+pub const PEER_PING_TIMEOUT_SECONDS: u64 = 5; // Pong must arrive within 5 seconds
+pub const REPUTATION_PENALTY_MISSED_PING: i32 = -1; // ~5 minutes of silence parks a neutral peer
+pub const PEER_REPUTATION_MAX_AGE_SECONDS: i64 = 7 * 86400; // Persisted scores untouched for a week reset to neutral
 
 // P2P Connection Timeouts (async architecture - prevent hung connections)
 pub const P2P_CONNECT_TIMEOUT_SECONDS: u64 = 5;
 pub const P2P_READ_TIMEOUT_SECONDS: u64 = 10;
 pub const P2P_WRITE_TIMEOUT_SECONDS: u64 = 5;
+pub const MAX_CONCURRENT_P2P_CONNECTIONS: usize = 256; // Inbound connections handled at once
+pub const MAX_BLOCKS_PER_SYNC_RESPONSE: usize = 500; // Lagging nodes page through larger gaps
+pub const MAX_HEADERS_PER_SYNC_RESPONSE: usize = 2000; // Headers are small, so pages are larger than block pages
+pub const READINESS_MAX_BLOCKS_BEHIND: u64 = 5; // /health/ready fails while further behind the best peer tip
+pub const MAX_P2P_MESSAGE_BYTES: usize = 100_000_000; // Largest frame accepted in either direction (100MB block limit)
+pub const P2P_COMPRESSION_THRESHOLD_BYTES: usize = 16 * 1024; // Smaller P2P messages are sent uncompressed
+pub const SEEN_BLOCK_CACHE_SIZE: usize = 1024; // Recent block hashes remembered to drop duplicate gossip
+
+// Graceful Shutdown - Bound connection draining on SIGTERM (Kubernetes default grace is 30s)
+pub const SHUTDOWN_GRACE_PERIOD_SECONDS: u64 = 10;
 
 // HTTP Client Retry Settings (for internal node-to-node communication)
 pub const HTTP_MAX_RETRIES: u32 = 3;
 pub const HTTP_INITIAL_BACKOFF_MS: u64 = 50;
 pub const HTTP_MAX_BACKOFF_MS: u64 = 500;
+pub const HTTP_CONNECT_TIMEOUT_SECONDS: u64 = 10;
+pub const HTTP_RESPONSE_TIMEOUT_SECONDS: u64 = 30; // Covers the validator processing the write
+pub const FORWARD_POOL_IDLE_TIMEOUT_SECONDS: u64 = 90; // Idle validator connections are closed after this
+pub const FORWARD_POOL_MAX_IDLE_PER_HOST: usize = 8;
+pub const VALIDATOR_FORWARD_ATTEMPTS: u32 = 3; // Forwards re-resolve the validator between attempts
+pub const VALIDATOR_FORWARD_BACKOFF_MS: u64 = 250; // Doubles after each failed forward
 
 // Rate Limiting - DoS Protection
-// Request limits per API key
-pub const RATE_LIMIT_WRITE_PER_SECOND: u32 = 10; // Maximum write operations per second
-pub const RATE_LIMIT_READ_PER_SECOND: u32 = 100; // Maximum read operations per second
+// Default request limits per API key and endpoint group (override via RATE_LIMIT_{GROUP}_PER_SEC)
+pub const RATE_LIMIT_SUBMIT_PER_SECOND: u32 = 10; // POST /data/submit
+pub const RATE_LIMIT_BATCH_PER_SECOND: u32 = 10; // POST /data/submit_batch
+pub const RATE_LIMIT_LIST_PER_SECOND: u32 = 100; // GET /data/list
+pub const RATE_LIMIT_SEARCH_PER_SECOND: u32 = 100; // GET /data/search
+pub const RATE_LIMIT_DECRYPT_PER_SECOND: u32 = 5; // Decrypt single collection or all collections
+pub const RATE_LIMIT_DELETE_PER_SECOND: u32 = 10; // DELETE /data/{id}
+pub const RATE_LIMIT_ACCOUNT_CREATE_PER_SECOND: u32 = 2; // POST /account/create (keyed by IP)
+pub const RATE_LIMIT_ACCOUNT_EXISTS_PER_SECOND: u32 = 1; // POST /account/exists (keyed by IP; an enumeration target)
+pub const RATE_LIMIT_PUBLIC_PER_SECOND: u32 = 20; // Public reads: /chain, /peers, /stats, /metrics... (keyed by IP)
+pub const RATE_LIMIT_KEY_HASH_PER_SECOND: u32 = 50; // API key hashes computed per client IP (Argon2id/HKDF are slow by design)
 pub const RATE_LIMIT_WINDOW_SECONDS: u64 = 1; // Sliding window duration
+pub const RATE_LIMIT_BURST_SECONDS: u32 = 3; // Token bucket capacity = per-second limit × burst seconds
 
 // Graduated penalties (escalating ban durations)
 pub const VIOLATION_COOLDOWN_SECONDS: u64 = 30; // 1st violation: 30-second cooldown
@@ -81,16 +161,96 @@ pub const BAN_IP_24HR_SECONDS: u64 = 86400; // After complete blacklist: 24-hour
 
 // Performance tuning
 pub const RATE_LIMIT_CACHE_SIZE: usize = 10000; // LRU cache for hot API keys
+pub const RATE_LIMIT_CLEANUP_INTERVAL_SECONDS: u64 = 300; // Purge stale windows and expired bans every 5 minutes
 
 // Audit Logging - Operational Security
 pub const AUDIT_LABEL_PREFIX: &str = "AUDIT:"; // Special collection label prefix for audit logs
 pub const AUDIT_BATCH_INTERVAL_SECONDS: u64 = 10; // Flush audit logs every 10 seconds
 pub const AUDIT_BATCH_SIZE: usize = 50; // Or when 50 events accumulated
+pub const AUDIT_INDEX_PREFIX: &str = "audit_index:"; // audit_index:{account_hash} → block numbers
+pub const AUDIT_EXPORT_FILENAME_PREFIX: &str = "goud-audit-logs"; // Download name: {prefix}-{unix_ts}.{csv|json}
+pub const SECURITY_AUDIT_THROTTLE_SECONDS: i64 = 60; // Log each (IP, security event) at most once per window
+pub const SECURITY_AUDIT_MAX_TRACKED_SOURCES: usize = 4096; // Throttle entries per window; events from new sources beyond it are dropped
 pub const AUDIT_IP_HASH_LENGTH: usize = 8; // Store truncated SHA256(IP) for privacy
+pub const AUDIT_RETENTION_INTERVAL_SECONDS: u64 = 3600; // Move the retention window hourly (AUDIT_RETENTION_DAYS)
+pub const MAX_AUDIT_RETENTION_DAYS: u64 = 36500; // Keeps retention_days * 86400 * 1000 far from overflow
+
+// Collection Deletion - Logical deletion on an append-only chain
+pub const TOMBSTONE_LABEL_PREFIX: &str = "TOMBSTONE:"; // Label prefix: TOMBSTONE:{collection_id}
+
+// Collection Sharing - Re-encrypted copies granted to another account
+pub const GRANT_LABEL_PREFIX: &str = "GRANT:"; // Label prefix: GRANT:{collection_id}
+
+// Payload Compression - zstd before encryption for large collections
+pub const COMPRESSION_THRESHOLD_BYTES: usize = 1024; // Auto mode leaves payloads under 1 KiB uncompressed
+pub const ZSTD_COMPRESSION_LEVEL: i32 = 3; // zstd default: good ratio without slowing the write path
+pub const MAX_DECOMPRESSED_PAYLOAD_BYTES: usize = 10_000_000; // Ceiling for MAX_PAYLOAD_BYTES (bounds inflation)
+
+// Payload Limits - Collection size accepted by /data/submit (overridable via MAX_PAYLOAD_BYTES)
+pub const DEFAULT_MAX_PAYLOAD_BYTES: usize = 10_000_000; // 10MB
+pub const MAX_LABEL_LENGTH: usize = 100;
+pub const MAX_ACCOUNT_METADATA_BYTES: usize = 4096; // Serialized account metadata (create and update)
+pub const REQUEST_BODY_LIMIT_MULTIPLIER: usize = 2; // HTTP body cap: room for JSON escaping around the payload
+pub const BATCH_BODY_LIMIT_MULTIPLIER: usize = 2; // /data/submit_batch bodies: one block still fits a P2P frame
+pub const DEFAULT_REQUEST_TIMEOUT_SECONDS: u64 = 30; // Longest an API request may run (override with REQUEST_TIMEOUT_SECONDS)
+
+// Collection Content Types - Tag payloads for typed retrieval
+pub const DEFAULT_CONTENT_TYPE: &str = "application/json"; // Untagged and legacy collections
+pub const BINARY_CONTENT_TYPE: &str = "application/octet-stream"; // Payload is submitted as base64
+
+// Batch Submission - Amortize block creation across many collections
+pub const MAX_BATCH_SUBMIT_ITEMS: usize = 100; // Collections accepted per /data/submit_batch call
+pub const MAX_BATCH_REQUESTS: usize = 20; // Sub-requests accepted per POST /batch call
+
+// Collection Listing - Bound /data/list response size (blocks are scanned newest first)
+pub const LIST_DEFAULT_PAGE_SIZE: usize = 100;
+pub const LIST_MAX_PAGE_SIZE: usize = 500;
+
+// Bulk Decryption - Bound /data/decrypt_all response size
+pub const DECRYPT_ALL_DEFAULT_PAGE_SIZE: usize = 50;
+pub const DECRYPT_ALL_MAX_PAGE_SIZE: usize = 100;
+
+// Conditional GET - /metrics ETags stay valid for this long (its counters change constantly)
+pub const METRICS_ETAG_WINDOW_SECONDS: i64 = 5;
+
+// Chain Browsing - Bound /chain response size
+pub const CHAIN_PAGE_DEFAULT_LIMIT: usize = 100;
+pub const CHAIN_PAGE_MAX_LIMIT: usize = 1000;
+pub const CHAIN_FULL_DUMP_MAX_BLOCKS: usize = 1000; // ?full=true is refused above this chain length
+
+// Validator Schedule - Bound /validator/schedule lookahead
+pub const VALIDATOR_SCHEDULE_DEFAULT_COUNT: u64 = 10;
+pub const VALIDATOR_SCHEDULE_MAX_COUNT: u64 = 100;
+
+// WebSocket Backpressure - Slow consumers are dropped instead of buffering without bound
+pub const WEBSOCKET_SEND_BUFFER: usize = 256; // Pending messages per connection before it is dropped
+pub const WEBSOCKET_CLOSE_TIMEOUT_SECONDS: u64 = 5; // Wait for a dropped client to accept the close frame
+pub const DEFAULT_METRICS_UPDATE_INTERVAL_SECONDS: u64 = 5; // metrics_update push cadence (0 disables)
 
 // Request Signature Replay Protection - Security
 pub const REQUEST_TIMESTAMP_TOLERANCE_SECONDS: i64 = 300; // 5-minute window for request freshness
 pub const NONCE_CLEANUP_INTERVAL_SECONDS: u64 = 600; // Clean up expired nonces every 10 minutes
+pub const REQUEST_SIGNATURE_HEADER: &str = "x-signature"; // Ed25519 signature (hex) of the request
+pub const REQUEST_NONCE_HEADER: &str = "x-nonce"; // Unique per-request nonce (UUID v4 recommended)
+pub const REQUEST_TIMESTAMP_HEADER: &str = "x-timestamp"; // Unix timestamp (seconds) of signing
+
+// Idempotency Keys - Safe retries of account creation and data submission
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key"; // Client-chosen key (UUID v4 recommended)
+pub const IDEMPOTENCY_REPLAYED_HEADER: &str = "idempotency-replayed"; // Set on responses replayed from a stored key
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255; // Longer keys are rejected with 400
+pub const IDEMPOTENCY_KEY_TTL_SECONDS: i64 = 86400; // Stored responses are replayed for 24 hours
+pub const IDEMPOTENCY_CLEANUP_INTERVAL_SECONDS: u64 = 3600; // Purge expired responses every hour
+
+// Logging - Operability
+pub const DEFAULT_LOG_LEVEL: &str = "info"; // Used when neither LOG_LEVEL nor RUST_LOG is set
+
+// Request Correlation - Operability
+pub const REQUEST_ID_HEADER: &str = "x-request-id"; // Correlation ID, honored inbound and forwarded to validators
+pub const MAX_REQUEST_ID_LENGTH: usize = 128; // Longer inbound IDs are replaced with a fresh UUID
+
+// API Versioning - Interoperability
+pub const API_VERSION_PREFIX: &str = "/v1"; // Current API version; unversioned paths are deprecated aliases
+pub const DEPRECATION_HEADER: &str = "deprecation"; // Set to "true" on responses served from an unversioned alias
 
 // Environment Configuration - API Documentation
 pub const ENVIRONMENT_LOCAL: &str = "local";
@@ -99,3 +259,8 @@ pub const API_SERVER_URL_LOCAL: &str = "http://localhost:8080";
 pub const API_SERVER_URL_GCP: &str = "https://dev.goudchain.com";
 
 // Metrics - Operational Security
+pub const OPERATIONS_RATE_WINDOW_SECONDS: usize = 60; // Sliding window for operations_per_second
+
+// Admin Token - Operator-only endpoints (/admin/*)
+pub const ADMIN_TOKEN_HEADER: &str = "x-admin-token"; // Carries ADMIN_TOKEN; user API keys and session tokens are not accepted
+pub const MIN_ADMIN_TOKEN_BYTES: usize = 32; // Shorter ADMIN_TOKEN values are rejected at startup
@@ -1,11 +1,70 @@
 use base64::{engine::general_purpose, Engine as _};
+use zeroize::{Zeroize, Zeroizing};
 
-use crate::constants::API_KEY_SIZE_BYTES;
+use crate::constants::{
+    API_KEY_KDF_HEADER_BYTES, API_KEY_SIZE_BYTES, API_KEY_VERSION_ARGON2ID, ARGON2_MAX_ITERATIONS,
+    ARGON2_MAX_MEMORY_KIB, ARGON2_MAX_PARALLELISM,
+};
+use crate::types::{ApiKeyKdf, Argon2Params};
 
 /// Generate a cryptographically secure random API key (256-bit)
-pub fn generate_api_key() -> Vec<u8> {
-    let key_bytes: [u8; API_KEY_SIZE_BYTES] = rand::random();
-    key_bytes.to_vec()
+/// The returned buffer is wiped from memory when dropped
+pub fn generate_api_key() -> Zeroizing<Vec<u8>> {
+    let mut key_bytes: [u8; API_KEY_SIZE_BYTES] = rand::random();
+    let api_key = Zeroizing::new(key_bytes.to_vec());
+    key_bytes.zeroize();
+    api_key
+}
+
+/// Generate an API key hashed with `kdf`
+///
+/// Argon2id keys are prefixed with a header naming their parameters, so any node can
+/// recompute the account's hash from the key alone (and retuning later leaves old keys working).
+/// HKDF keys are the plain 256-bit form.
+pub fn generate_api_key_with_kdf(kdf: &ApiKeyKdf) -> Zeroizing<Vec<u8>> {
+    match kdf {
+        ApiKeyKdf::Hkdf => generate_api_key(),
+        ApiKeyKdf::Argon2id(params) => {
+            let mut api_key = Zeroizing::new(Vec::with_capacity(
+                API_KEY_KDF_HEADER_BYTES + API_KEY_SIZE_BYTES,
+            ));
+            api_key.push(API_KEY_VERSION_ARGON2ID);
+            api_key.extend_from_slice(&params.memory_kib.to_le_bytes());
+            // Bounded by the config loader, so these fit in a byte
+            api_key.push(params.iterations as u8);
+            api_key.push(params.parallelism as u8);
+            api_key.extend_from_slice(&generate_api_key());
+            api_key
+        }
+    }
+}
+
+/// Check Argon2id parameters against the limits a node is willing to compute
+pub fn argon2_params_in_bounds(params: &Argon2Params) -> bool {
+    (1..=ARGON2_MAX_ITERATIONS).contains(&params.iterations)
+        && (1..=ARGON2_MAX_PARALLELISM).contains(&params.parallelism)
+        && (8 * params.parallelism..=ARGON2_MAX_MEMORY_KIB).contains(&params.memory_kib)
+}
+
+/// Hashing scheme an API key was issued for (read from its header)
+/// Keys without a valid, in-bounds Argon2id header use HKDF
+pub fn api_key_kdf(api_key: &[u8]) -> ApiKeyKdf {
+    if api_key.len() != API_KEY_KDF_HEADER_BYTES + API_KEY_SIZE_BYTES
+        || api_key[0] != API_KEY_VERSION_ARGON2ID
+    {
+        return ApiKeyKdf::Hkdf;
+    }
+
+    let params = Argon2Params {
+        memory_kib: u32::from_le_bytes([api_key[1], api_key[2], api_key[3], api_key[4]]),
+        iterations: u32::from(api_key[5]),
+        parallelism: u32::from(api_key[6]),
+    };
+    if argon2_params_in_bounds(&params) {
+        ApiKeyKdf::Argon2id(params)
+    } else {
+        ApiKeyKdf::Hkdf
+    }
 }
 
 /// Encode API key as base64 for display to users
@@ -14,13 +73,16 @@ pub fn encode_api_key(api_key: &[u8]) -> String {
 }
 
 /// Decode base64-encoded API key
-pub fn decode_api_key(api_key_str: &str) -> Result<Vec<u8>, base64::DecodeError> {
-    general_purpose::STANDARD.decode(api_key_str)
+/// The returned buffer is wiped from memory when dropped
+pub fn decode_api_key(api_key_str: &str) -> Result<Zeroizing<Vec<u8>>, base64::DecodeError> {
+    general_purpose::STANDARD
+        .decode(api_key_str)
+        .map(Zeroizing::new)
 }
 
-/// Validate API key format (must be 256-bit / 32 bytes)
+/// Validate API key format (256-bit / 32 bytes, or 32 bytes behind a valid Argon2id header)
 pub fn validate_api_key(api_key: &[u8]) -> bool {
-    api_key.len() == API_KEY_SIZE_BYTES
+    api_key.len() == API_KEY_SIZE_BYTES || matches!(api_key_kdf(api_key), ApiKeyKdf::Argon2id(_))
 }
 
 #[cfg(test)]
@@ -58,6 +120,28 @@ mod tests {
         assert!(!validate_api_key(&invalid_key_too_long));
     }
 
+    #[test]
+    fn test_argon2_key_header() {
+        let params = Argon2Params {
+            memory_kib: 4096,
+            iterations: 3,
+            parallelism: 2,
+        };
+        let key = generate_api_key_with_kdf(&ApiKeyKdf::Argon2id(params));
+        assert_eq!(key.len(), API_KEY_KDF_HEADER_BYTES + API_KEY_SIZE_BYTES);
+        assert_eq!(api_key_kdf(&key), ApiKeyKdf::Argon2id(params));
+        assert!(validate_api_key(&key));
+
+        // Plain keys keep the original scheme
+        assert_eq!(api_key_kdf(&generate_api_key()), ApiKeyKdf::Hkdf);
+
+        // A header asking for more memory than the node allows is not a valid key
+        let mut greedy = key.clone();
+        greedy[1..5].copy_from_slice(&(ARGON2_MAX_MEMORY_KIB + 1).to_le_bytes());
+        assert_eq!(api_key_kdf(&greedy), ApiKeyKdf::Hkdf);
+        assert!(!validate_api_key(&greedy));
+    }
+
     #[test]
     fn test_base64_encoding_length() {
         let key = generate_api_key();
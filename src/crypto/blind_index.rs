@@ -52,6 +52,14 @@ pub fn generate_account_blind_index_with_salt(
     generate_blind_index_with_salt(api_key_hash, "account_lookup", "", block_salt)
 }
 
+/// Generate a blind index for exact-match collection label lookup
+/// Keyed with the owner's MAC key rather than the api_key_hash, so the index can't be used
+/// to brute-force labels; the per-collection user_salt keeps equal labels unlinkable
+pub fn generate_label_blind_index(mac_key: &[u8], label: &str, user_salt: &str) -> Result<String> {
+    let context = format!("label_lookup|{}", label);
+    generate_blind_index_with_salt(&hex::encode(mac_key), &context, user_salt, "")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,4 +204,27 @@ mod tests {
             "Different block salts should produce different blind indexes"
         );
     }
+
+    #[test]
+    fn test_label_blind_index() {
+        let mac_key = [7u8; 32];
+
+        let index1 = generate_label_blind_index(&mac_key, "medical-records", "salt").unwrap();
+        let index2 = generate_label_blind_index(&mac_key, "medical-records", "salt").unwrap();
+        assert_eq!(index1, index2);
+
+        // Different label, salt, or key must produce a different index
+        assert_ne!(
+            index1,
+            generate_label_blind_index(&mac_key, "insurance", "salt").unwrap()
+        );
+        assert_ne!(
+            index1,
+            generate_label_blind_index(&mac_key, "medical-records", "other").unwrap()
+        );
+        assert_ne!(
+            index1,
+            generate_label_blind_index(&[8u8; 32], "medical-records", "salt").unwrap()
+        );
+    }
 }
@@ -17,19 +17,37 @@ pub fn encrypt_data_with_key(
     encrypt_data_with_nonce(data, encryption_key, &nonce_bytes)
 }
 
+/// Encrypt raw bytes (e.g. a compressed payload) using AES-256-GCM
+pub fn encrypt_bytes_with_key(
+    data: &[u8],
+    encryption_key: &[u8; AES_KEY_SIZE_BYTES],
+) -> Result<(String, String)> {
+    // Generate random nonce
+    let nonce_bytes: [u8; NONCE_SIZE_BYTES] = rand::random();
+    encrypt_bytes_with_nonce(data, encryption_key, &nonce_bytes)
+}
+
 /// Encrypt data using AES-256-GCM with a provided nonce
 /// This is used for deterministic genesis block encryption
 pub fn encrypt_data_with_nonce(
     data: &str,
     encryption_key: &[u8; AES_KEY_SIZE_BYTES],
     nonce_bytes: &[u8; NONCE_SIZE_BYTES],
+) -> Result<(String, String)> {
+    encrypt_bytes_with_nonce(data.as_bytes(), encryption_key, nonce_bytes)
+}
+
+fn encrypt_bytes_with_nonce(
+    data: &[u8],
+    encryption_key: &[u8; AES_KEY_SIZE_BYTES],
+    nonce_bytes: &[u8; NONCE_SIZE_BYTES],
 ) -> Result<(String, String)> {
     let cipher = Aes256Gcm::new(encryption_key.into());
     let nonce = nonce_bytes.into();
 
     // Encrypt
     let ciphertext = cipher
-        .encrypt(nonce, data.as_bytes())
+        .encrypt(nonce, data)
         .map_err(|e| GoudChainError::EncryptionFailed(e.to_string()))?;
 
     // Optimize: Pre-allocate combined buffer (nonce + ciphertext)
@@ -49,6 +67,15 @@ pub fn decrypt_data_with_key(
     encrypted_payload: &str,
     encryption_key: &[u8; AES_KEY_SIZE_BYTES],
 ) -> Result<String> {
+    let plaintext = decrypt_bytes_with_key(encrypted_payload, encryption_key)?;
+    String::from_utf8(plaintext).map_err(GoudChainError::Utf8Error)
+}
+
+/// Decrypt raw bytes using AES-256-GCM (inverse of `encrypt_bytes_with_key`)
+pub fn decrypt_bytes_with_key(
+    encrypted_payload: &str,
+    encryption_key: &[u8; AES_KEY_SIZE_BYTES],
+) -> Result<Vec<u8>> {
     let cipher = Aes256Gcm::new(encryption_key.into());
 
     // Decode base64
@@ -69,11 +96,9 @@ pub fn decrypt_data_with_key(
     let nonce = nonce_array.into();
 
     // Decrypt
-    let plaintext = cipher
+    cipher
         .decrypt(nonce, ciphertext)
-        .map_err(|_| GoudChainError::DecryptionFailed)?;
-
-    String::from_utf8(plaintext).map_err(GoudChainError::Utf8Error)
+        .map_err(|_| GoudChainError::DecryptionFailed)
 }
 
 #[cfg(test)]
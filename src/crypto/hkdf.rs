@@ -1,10 +1,15 @@
+use argon2::{Algorithm, Argon2, Params, Version};
 use sha2::{Digest, Sha256};
 use subtle::ConstantTimeEq;
 
+use super::api_key::{api_key_kdf, argon2_params_in_bounds};
 use crate::constants::{
-    AES_KEY_SIZE_BYTES, ENCRYPTION_SALT, HKDF_CONTEXT_ENCRYPTION, HKDF_CONTEXT_MAC,
-    HKDF_CONTEXT_SESSION_ENCRYPTION, HKDF_FAST_ITERATIONS, HKDF_ITERATIONS,
+    AES_KEY_SIZE_BYTES, ARGON2_API_KEY_SALT, ARGON2_HASH_PREFIX, ENCRYPTION_SALT,
+    HKDF_CONTEXT_ENCRYPTION, HKDF_CONTEXT_MAC, HKDF_CONTEXT_NODE_AUDIT,
+    HKDF_CONTEXT_SESSION_ENCRYPTION, HKDF_CONTEXT_SHARE_GRANT, HKDF_CONTEXT_SHARING_KEY,
+    HKDF_FAST_ITERATIONS, HKDF_ITERATIONS,
 };
+use crate::types::{ApiKeyKdf, Argon2Params};
 
 /// HKDF-Extract: Extract a pseudorandom key from input keying material
 fn hkdf_extract(salt: &[u8], ikm: &[u8]) -> [u8; 32] {
@@ -101,9 +106,49 @@ pub fn derive_session_encryption_key(session_secret: &[u8]) -> [u8; AES_KEY_SIZE
     )
 }
 
-/// Hash an API key for storage and comparison (SLOW - 100k iterations)
+/// Derive the node-level audit key from SESSION_SECRET (FAST - 1k iterations)
 ///
-/// **Security Note:** This function uses 100,000 iterations (OWASP recommended).
+/// **Purpose:** Security events such as failed logins have no trusted API key to
+/// encrypt under, so they are logged as if owned by this derived key instead.
+/// Only operators holding SESSION_SECRET can read that stream.
+pub fn derive_node_audit_key(session_secret: &[u8]) -> [u8; AES_KEY_SIZE_BYTES] {
+    hkdf_with_iterations(
+        session_secret,
+        ENCRYPTION_SALT,
+        HKDF_CONTEXT_NODE_AUDIT,
+        HKDF_FAST_ITERATIONS,
+    )
+}
+
+/// Derive the X25519 sharing secret for an account from its API key (FAST - 1k iterations)
+///
+/// **Purpose:** Lets the node open share grants addressed to the caller without storing any
+/// extra secret; the matching public key is what other accounts share collections to.
+pub fn derive_sharing_secret(api_key: &[u8]) -> [u8; 32] {
+    hkdf_with_iterations(
+        api_key,
+        ENCRYPTION_SALT,
+        HKDF_CONTEXT_SHARING_KEY,
+        HKDF_FAST_ITERATIONS,
+    )
+}
+
+/// Derive the AES-256 key that seals one share grant from its X25519 shared secret
+///
+/// `binding` (ephemeral + recipient public keys) ties the key to this exact exchange.
+pub fn derive_share_grant_key(shared_secret: &[u8], binding: &[u8]) -> [u8; AES_KEY_SIZE_BYTES] {
+    hkdf_with_iterations(
+        shared_secret,
+        binding,
+        HKDF_CONTEXT_SHARE_GRANT,
+        HKDF_FAST_ITERATIONS,
+    )
+}
+
+/// Hash an API key for storage and comparison (SLOW)
+///
+/// **Security Note:** The scheme comes from the key itself: Argon2id keys carry their
+/// cost parameters in a header, plain 256-bit keys use HKDF with 100,000 iterations.
 /// Use for:
 /// - API key authentication/verification
 /// - Storing API key hashes in database
@@ -112,19 +157,95 @@ pub fn derive_session_encryption_key(session_secret: &[u8]) -> [u8; AES_KEY_SIZE
 /// **Performance:** ~40ms per call (intentionally slow to prevent brute-force attacks)
 /// Returns raw 32-byte hash for constant-time comparison using subtle crate.
 pub fn hash_api_key(api_key: &[u8]) -> [u8; 32] {
-    // Use HKDF with 100k iterations for API key hashing (brute-force resistance)
-    hkdf_with_iterations(
-        api_key,
-        ENCRYPTION_SALT,
-        b"api_key_hash_v2",
-        HKDF_ITERATIONS,
+    hash_api_key_with_kdf(api_key, &api_key_kdf(api_key))
+}
+
+/// Hash an API key under an explicit scheme
+pub fn hash_api_key_with_kdf(api_key: &[u8], kdf: &ApiKeyKdf) -> [u8; 32] {
+    match kdf {
+        // Use HKDF with 100k iterations for API key hashing (brute-force resistance)
+        ApiKeyKdf::Hkdf => hkdf_with_iterations(
+            api_key,
+            ENCRYPTION_SALT,
+            b"api_key_hash_v2",
+            HKDF_ITERATIONS,
+        ),
+        ApiKeyKdf::Argon2id(params) => argon2id_hash(api_key, params),
+    }
+}
+
+/// Memory-hard Argon2id hash with the fixed API key salt
+/// Parameters must already be within `argon2_params_in_bounds`
+fn argon2id_hash(api_key: &[u8], params: &Argon2Params) -> [u8; 32] {
+    let argon2_params = Params::new(
+        params.memory_kib,
+        params.iterations,
+        params.parallelism,
+        Some(32),
     )
+    .expect("Argon2 parameters are bounds-checked");
+    let mut output = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, argon2_params)
+        .hash_password_into(api_key, ARGON2_API_KEY_SALT, &mut output)
+        .expect("Argon2 salt and output lengths are fixed");
+    output
 }
 
-/// Hash an API key and return hex-encoded string (for display/storage)
+/// Hash an API key and return the stored string form (for display/storage)
+/// HKDF hashes are plain hex; Argon2id hashes are `argon2id$m=..,t=..,p=..$<hex>`
 pub fn hash_api_key_hex(api_key: &[u8]) -> String {
-    let hash = hash_api_key(api_key);
-    hex::encode(hash)
+    let kdf = api_key_kdf(api_key);
+    format_api_key_hash(&kdf, &hash_api_key_with_kdf(api_key, &kdf))
+}
+
+/// Encode a raw API key hash with its version prefix
+pub fn format_api_key_hash(kdf: &ApiKeyKdf, hash: &[u8]) -> String {
+    match kdf {
+        ApiKeyKdf::Hkdf => hex::encode(hash),
+        ApiKeyKdf::Argon2id(params) => format!(
+            "{}$m={},t={},p={}${}",
+            ARGON2_HASH_PREFIX,
+            params.memory_kib,
+            params.iterations,
+            params.parallelism,
+            hex::encode(hash)
+        ),
+    }
+}
+
+/// Split a stored API key hash into its scheme and raw bytes
+/// Returns None for malformed hashes or out-of-bounds Argon2id parameters
+pub fn parse_api_key_hash(stored: &str) -> Option<(ApiKeyKdf, Vec<u8>)> {
+    let Some(rest) = stored
+        .strip_prefix(ARGON2_HASH_PREFIX)
+        .and_then(|rest| rest.strip_prefix('$'))
+    else {
+        return hex::decode(stored).ok().map(|hash| (ApiKeyKdf::Hkdf, hash));
+    };
+
+    let (params, hash_hex) = rest.split_once('$')?;
+    let params = parse_argon2_params(params)?;
+    let hash = hex::decode(hash_hex).ok()?;
+    Some((ApiKeyKdf::Argon2id(params), hash))
+}
+
+/// Parse Argon2id parameters in their stored-hash form (`m=19456,t=2,p=1`)
+/// Returns None for malformed or out-of-bounds parameters
+pub fn parse_argon2_params(encoded: &str) -> Option<Argon2Params> {
+    let mut values = encoded.split(',').map(|part| part.split_once('='));
+    let mut field = |name: &str| match values.next() {
+        Some(Some((key, value))) if key == name => value.parse::<u32>().ok(),
+        _ => None,
+    };
+    let params = Argon2Params {
+        memory_kib: field("m")?,
+        iterations: field("t")?,
+        parallelism: field("p")?,
+    };
+    if values.next().is_some() || !argon2_params_in_bounds(&params) {
+        return None;
+    }
+    Some(params)
 }
 
 /// Constant-time comparison of byte arrays using subtle crate
@@ -183,6 +304,41 @@ mod tests {
         assert_eq!(hex1.len(), 64, "SHA-256 hex hash should be 64 hex chars");
     }
 
+    #[test]
+    fn test_argon2_api_key_hash() {
+        let params = Argon2Params {
+            memory_kib: 64,
+            iterations: 1,
+            parallelism: 1,
+        };
+        let api_key =
+            crate::crypto::api_key::generate_api_key_with_kdf(&ApiKeyKdf::Argon2id(params));
+
+        let stored = hash_api_key_hex(&api_key);
+        assert!(stored.starts_with("argon2id$m=64,t=1,p=1$"));
+        assert_eq!(
+            stored,
+            hash_api_key_hex(&api_key),
+            "Argon2 hash must be deterministic"
+        );
+
+        let (kdf, hash) = parse_api_key_hash(&stored).unwrap();
+        assert_eq!(kdf, ApiKeyKdf::Argon2id(params));
+        assert_eq!(hash, hash_api_key(&api_key));
+        assert_ne!(
+            hash_api_key_with_kdf(&api_key, &ApiKeyKdf::Hkdf),
+            hash_api_key(&api_key)
+        );
+
+        // Legacy hashes are unprefixed hex
+        let legacy = hash_api_key_hex(b"test_api_key_12345678901234567890");
+        assert_eq!(parse_api_key_hash(&legacy).unwrap().0, ApiKeyKdf::Hkdf);
+
+        assert!(parse_api_key_hash("argon2id$m=64,t=1$00").is_none());
+        assert!(parse_api_key_hash("argon2id$m=99999999,t=1,p=1$00").is_none());
+        assert!(parse_api_key_hash("not hex").is_none());
+    }
+
     #[test]
     fn test_constant_time_compare_bytes() {
         let a = b"abc123";
@@ -1,23 +1,62 @@
 use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 use lru::LruCache;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use crate::constants::AES_KEY_SIZE_BYTES;
+use crate::constants::{
+    AES_KEY_SIZE_BYTES, KEY_CACHE_DEFAULT_MAX_ENTRIES, KEY_CACHE_DEFAULT_TTL_SECONDS,
+};
 use crate::crypto::hkdf::{
     derive_encryption_key as derive_encryption_key_slow, derive_mac_key as derive_mac_key_slow,
 };
+use crate::types::SharedClock;
+
+/// Capacity and TTL settings for the derived key cache
+#[derive(Debug, Clone, Copy)]
+pub struct KeyCacheConfig {
+    pub max_entries: NonZeroUsize,
+    pub ttl: Duration,
+}
 
-// Cache configuration constants
-const CACHE_MAX_ENTRIES: usize = 1000;
-const CACHE_TTL_SECONDS: u64 = 300; // 5 minutes
+impl Default for KeyCacheConfig {
+    fn default() -> Self {
+        KeyCacheConfig {
+            max_entries: NonZeroUsize::new(KEY_CACHE_DEFAULT_MAX_ENTRIES).unwrap(),
+            ttl: Duration::from_secs(KEY_CACHE_DEFAULT_TTL_SECONDS),
+        }
+    }
+}
+
+impl KeyCacheConfig {
+    /// Load from `KEY_CACHE_MAX_ENTRIES` / `KEY_CACHE_TTL_SECONDS`, falling back to defaults
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let max_entries = std::env::var("KEY_CACHE_MAX_ENTRIES")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .and_then(NonZeroUsize::new)
+            .unwrap_or(defaults.max_entries);
+
+        let ttl = std::env::var("KEY_CACHE_TTL_SECONDS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(defaults.ttl);
+
+        KeyCacheConfig { max_entries, ttl }
+    }
+}
 
 #[derive(Clone)]
 pub struct KeyCache {
     cache: Arc<Mutex<LruCache<String, CachedKeysWithTTL>>>,
     stats: Arc<Mutex<CacheStats>>,
+    ttl: Duration,
+    clock: SharedClock,
 }
 
 /// Cached encryption and MAC keys with automatic memory zeroization
@@ -30,7 +69,7 @@ struct CachedKeys {
 /// Cache entry with TTL tracking
 struct CachedKeysWithTTL {
     keys: CachedKeys,
-    inserted_at: Instant,
+    inserted_at: i64, // Unix milliseconds
 }
 
 impl Default for KeyCache {
@@ -40,109 +79,102 @@ impl Default for KeyCache {
 }
 
 impl KeyCache {
+    /// Create a cache configured from the environment
     pub fn new() -> Self {
+        Self::with_config(KeyCacheConfig::from_env())
+    }
+
+    pub fn with_config(config: KeyCacheConfig) -> Self {
+        Self::with_clock(config, SharedClock::default())
+    }
+
+    pub fn with_clock(config: KeyCacheConfig, clock: SharedClock) -> Self {
         KeyCache {
-            cache: Arc::new(Mutex::new(LruCache::new(
-                NonZeroUsize::new(CACHE_MAX_ENTRIES).unwrap(),
-            ))),
+            cache: Arc::new(Mutex::new(LruCache::new(config.max_entries))),
             stats: Arc::new(Mutex::new(CacheStats::default())),
+            ttl: config.ttl,
+            clock,
         }
     }
 
     pub fn get_encryption_key(&self, api_key: &[u8], salt: &[u8]) -> [u8; AES_KEY_SIZE_BYTES] {
+        self.get_keys(api_key, salt).encryption_key
+    }
+
+    pub fn get_mac_key(&self, api_key: &[u8], salt: &[u8]) -> [u8; AES_KEY_SIZE_BYTES] {
+        self.get_keys(api_key, salt).mac_key
+    }
+
+    fn get_keys(&self, api_key: &[u8], salt: &[u8]) -> CachedKeys {
         let cache_key = self.make_cache_key(api_key, salt);
-        let now = Instant::now();
+        let now = self.clock.now_millis();
 
         // Try to get from cache
         {
             let mut cache = self.cache.lock().unwrap();
             if let Some(entry) = cache.get(&cache_key) {
                 // Check if entry is still valid (within TTL)
-                if now.duration_since(entry.inserted_at) < Duration::from_secs(CACHE_TTL_SECONDS) {
+                if !self.is_expired(entry, now) {
                     // Cache hit
                     self.stats.lock().unwrap().hits += 1;
-                    return entry.keys.encryption_key;
+                    return entry.keys.clone();
                 } else {
                     // Entry expired, remove it (will trigger zeroization)
                     cache.pop(&cache_key);
-                    self.stats.lock().unwrap().evictions += 1;
+                    self.stats.lock().unwrap().expirations += 1;
                 }
             }
         }
 
         // Cache miss - derive keys
         self.stats.lock().unwrap().misses += 1;
-        let encryption_key = derive_encryption_key_slow(api_key, salt);
-        let mac_key = derive_mac_key_slow(api_key, salt);
+        let keys = CachedKeys {
+            encryption_key: derive_encryption_key_slow(api_key, salt),
+            mac_key: derive_mac_key_slow(api_key, salt),
+        };
 
         // Store in cache
         {
             let mut cache = self.cache.lock().unwrap();
             let evicted = cache.push(
-                cache_key,
+                cache_key.clone(),
                 CachedKeysWithTTL {
-                    keys: CachedKeys {
-                        encryption_key,
-                        mac_key,
-                    },
+                    keys: keys.clone(),
                     inserted_at: now,
                 },
             );
-            if evicted.is_some() {
+            // `push` also returns the old value when replacing the same key
+            if matches!(evicted, Some((ref key, _)) if *key != cache_key) {
                 // LRU eviction occurred (oldest entry was zeroized)
                 self.stats.lock().unwrap().evictions += 1;
             }
         }
 
-        encryption_key
+        keys
     }
 
-    pub fn get_mac_key(&self, api_key: &[u8], salt: &[u8]) -> [u8; AES_KEY_SIZE_BYTES] {
-        let cache_key = self.make_cache_key(api_key, salt);
-        let now = Instant::now();
+    /// Whether an entry has outlived the TTL at `now` (Unix milliseconds)
+    fn is_expired(&self, entry: &CachedKeysWithTTL, now: i64) -> bool {
+        now.saturating_sub(entry.inserted_at) >= self.ttl.as_millis() as i64
+    }
 
-        // Try to get from cache
-        {
-            let mut cache = self.cache.lock().unwrap();
-            if let Some(entry) = cache.get(&cache_key) {
-                // Check if entry is still valid (within TTL)
-                if now.duration_since(entry.inserted_at) < Duration::from_secs(CACHE_TTL_SECONDS) {
-                    // Cache hit
-                    self.stats.lock().unwrap().hits += 1;
-                    return entry.keys.mac_key;
-                } else {
-                    // Entry expired, remove it (will trigger zeroization)
-                    cache.pop(&cache_key);
-                    self.stats.lock().unwrap().evictions += 1;
-                }
-            }
-        }
+    /// Drop every entry older than the TTL (keys are zeroized on drop)
+    pub fn purge_expired(&self) -> usize {
+        let now = self.clock.now_millis();
+        let mut cache = self.cache.lock().unwrap();
 
-        // Cache miss - derive keys
-        self.stats.lock().unwrap().misses += 1;
-        let encryption_key = derive_encryption_key_slow(api_key, salt);
-        let mac_key = derive_mac_key_slow(api_key, salt);
+        let expired: Vec<String> = cache
+            .iter()
+            .filter(|(_, entry)| self.is_expired(entry, now))
+            .map(|(key, _)| key.clone())
+            .collect();
 
-        // Store in cache
-        {
-            let mut cache = self.cache.lock().unwrap();
-            let evicted = cache.push(
-                cache_key,
-                CachedKeysWithTTL {
-                    keys: CachedKeys {
-                        encryption_key,
-                        mac_key,
-                    },
-                    inserted_at: now,
-                },
-            );
-            if evicted.is_some() {
-                // LRU eviction occurred (oldest entry was zeroized)
-                self.stats.lock().unwrap().evictions += 1;
-            }
+        for key in &expired {
+            cache.pop(key);
         }
 
-        mac_key
+        self.stats.lock().unwrap().expirations += expired.len() as u64;
+        expired.len()
     }
 
     fn make_cache_key(&self, api_key: &[u8], salt: &[u8]) -> String {
@@ -184,14 +216,18 @@ impl KeyCache {
              # HELP key_cache_size Current number of entries in cache\n\
              # TYPE key_cache_size gauge\n\
              key_cache_size {}\n\
-             # HELP key_cache_evictions_total Total number of cache evictions (TTL + LRU)\n\
+             # HELP key_cache_evictions_total Total number of LRU evictions (cache at capacity)\n\
              # TYPE key_cache_evictions_total counter\n\
-             key_cache_evictions_total {}\n",
+             key_cache_evictions_total {}\n\
+             # HELP key_cache_expirations_total Total number of entries removed after their TTL\n\
+             # TYPE key_cache_expirations_total counter\n\
+             key_cache_expirations_total {}\n",
             stats.hits,
             stats.misses,
             stats.hit_rate(),
             stats.size,
-            stats.evictions
+            stats.evictions,
+            stats.expirations
         )
     }
 }
@@ -201,7 +237,10 @@ pub struct CacheStats {
     pub size: usize,
     pub hits: u64,
     pub misses: u64,
+    /// Entries dropped because the cache was full
     pub evictions: u64,
+    /// Entries dropped because they outlived the TTL
+    pub expirations: u64,
 }
 
 impl CacheStats {
@@ -259,14 +298,15 @@ mod tests {
 
     #[test]
     fn test_cache_ttl_expiration() {
-        let cache = KeyCache::new();
+        let cache = KeyCache::with_config(KeyCacheConfig::default());
         let api_key = b"test_api_key_32_bytes_long_here!";
         let salt = b"test_salt";
 
         // Override TTL for testing by directly manipulating the cache
         // First, insert with a past timestamp
         let cache_key = cache.make_cache_key(api_key, salt);
-        let expired_time = Instant::now() - Duration::from_secs(CACHE_TTL_SECONDS + 1);
+        let expired_time = chrono::Utc::now().timestamp_millis()
+            - (KEY_CACHE_DEFAULT_TTL_SECONDS as i64 + 1) * 1000;
 
         {
             let mut cache_lock = cache.cache.lock().unwrap();
@@ -287,22 +327,23 @@ mod tests {
         assert_ne!(key, [1u8; AES_KEY_SIZE_BYTES]); // Should be re-derived, not the expired value
 
         let stats = cache.stats();
-        assert_eq!(stats.evictions, 1); // Entry was evicted due to TTL
+        assert_eq!(stats.expirations, 1); // Entry was dropped due to TTL
+        assert_eq!(stats.evictions, 0);
         assert_eq!(stats.misses, 1); // Counted as a miss
     }
 
     #[test]
     fn test_lru_eviction() {
-        let cache = KeyCache::new();
+        let cache = KeyCache::with_config(KeyCacheConfig::default());
 
         // Fill cache to capacity (1000 entries)
-        for i in 0..CACHE_MAX_ENTRIES {
+        for i in 0..KEY_CACHE_DEFAULT_MAX_ENTRIES {
             let api_key = format!("api_key_{:04}", i);
             let salt = b"test_salt";
             cache.get_encryption_key(api_key.as_bytes(), salt);
         }
 
-        assert_eq!(cache.stats().size, CACHE_MAX_ENTRIES);
+        assert_eq!(cache.stats().size, KEY_CACHE_DEFAULT_MAX_ENTRIES);
         assert_eq!(cache.stats().evictions, 0);
 
         // Add one more entry - should trigger LRU eviction
@@ -310,7 +351,7 @@ mod tests {
         cache.get_encryption_key(api_key, b"test_salt");
 
         let stats = cache.stats();
-        assert_eq!(stats.size, CACHE_MAX_ENTRIES); // Still at capacity
+        assert_eq!(stats.size, KEY_CACHE_DEFAULT_MAX_ENTRIES); // Still at capacity
         assert_eq!(stats.evictions, 1); // One entry was evicted
     }
 
@@ -367,5 +408,39 @@ mod tests {
         assert!(metrics.contains("key_cache_misses_total 1"));
         assert!(metrics.contains("key_cache_hit_rate 0.5000"));
         assert!(metrics.contains("key_cache_size 1"));
+        assert!(metrics.contains("key_cache_expirations_total 0"));
+    }
+
+    #[test]
+    fn test_entries_expire_when_clock_advances() {
+        use crate::types::MockClock;
+
+        let clock = Arc::new(MockClock::new(1_700_000_000));
+        let config = KeyCacheConfig {
+            max_entries: NonZeroUsize::new(10).unwrap(),
+            ttl: Duration::from_secs(600),
+        };
+        let cache = KeyCache::with_clock(config, Arc::clone(&clock).into());
+        let salt = b"test_salt";
+
+        cache.get_encryption_key(b"stale_key", salt);
+        clock.advance(300);
+        cache.get_encryption_key(b"fresh_key", salt);
+
+        // Stale entry is still within its TTL
+        assert_eq!(cache.purge_expired(), 0);
+        assert_eq!(cache.stats().size, 2);
+
+        clock.advance(301);
+        assert_eq!(cache.purge_expired(), 1);
+
+        let stats = cache.stats();
+        assert_eq!(stats.size, 1);
+        assert_eq!(stats.expirations, 1);
+        assert_eq!(stats.evictions, 0);
+
+        // Expired keys are re-derived on next access, surviving entries are hits
+        cache.get_mac_key(b"fresh_key", salt);
+        assert_eq!(cache.stats().hits, 1);
     }
 }
@@ -4,17 +4,32 @@ pub mod encryption;
 pub mod hkdf;
 pub mod key_cache;
 pub mod mac;
+pub mod sharing;
 pub mod signature;
 pub mod timing_safe;
 
 // Re-export commonly used functions
-pub use api_key::{decode_api_key, encode_api_key, generate_api_key, validate_api_key};
-pub use blind_index::generate_account_blind_index_with_salt;
-pub use encryption::{decrypt_data_with_key, encrypt_data_with_key};
+pub use api_key::{
+    api_key_kdf, decode_api_key, encode_api_key, generate_api_key_with_kdf, validate_api_key,
+};
+// Plain HKDF keys for tests; the node issues keys through generate_api_key_with_kdf
+#[allow(unused_imports)]
+pub use api_key::generate_api_key;
+pub use blind_index::{generate_account_blind_index_with_salt, generate_label_blind_index};
+pub use encryption::{
+    decrypt_bytes_with_key, decrypt_data_with_key, encrypt_bytes_with_key, encrypt_data_with_key,
+};
 pub use hkdf::{
-    constant_time_compare_bytes, derive_session_encryption_key, hash_api_key, hash_api_key_hex,
+    constant_time_compare_bytes, derive_node_audit_key, derive_session_encryption_key,
+    hash_api_key, hash_api_key_hex, hash_api_key_with_kdf, parse_api_key_hash, parse_argon2_params,
 };
 pub use key_cache::global_key_cache;
 pub use mac::{compute_mac, verify_mac};
-pub use signature::{generate_signing_key, get_public_key_hex, sign_message, verify_signature};
+pub use sharing::{
+    open_sealed, seal_for_recipient, share_recipient_index, sharing_public_key_hex,
+    validate_sharing_public_key,
+};
+pub use signature::{
+    generate_signing_key, get_public_key_hex, sign_message, validate_public_key, verify_signature,
+};
 pub use timing_safe::{dummy_constant_time_compare, dummy_hash_for_timing};
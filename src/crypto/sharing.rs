@@ -0,0 +1,134 @@
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use sha2::{Digest, Sha256};
+
+use super::encryption::{decrypt_data_with_key, encrypt_data_with_key};
+use super::hkdf::{derive_share_grant_key, derive_sharing_secret};
+use crate::constants::SHARE_RECIPIENT_INDEX_CONTEXT;
+use crate::types::{GoudChainError, Result};
+
+/// Get the X25519 sharing public key (hex) for an account
+/// Other accounts seal share grants to this key; only the matching API key can open them
+pub fn sharing_public_key_hex(api_key: &[u8]) -> String {
+    let public_key = MontgomeryPoint::mul_base_clamped(derive_sharing_secret(api_key));
+    hex::encode(public_key.to_bytes())
+}
+
+/// Decode a hex-encoded X25519 sharing public key
+fn decode_sharing_public_key(public_key_hex: &str) -> Result<MontgomeryPoint> {
+    let bytes: [u8; 32] = hex::decode(public_key_hex)
+        .map_err(GoudChainError::HexDecodingError)?
+        .try_into()
+        .map_err(|_| {
+            GoudChainError::InvalidRequestBody("Sharing public key must be 32 bytes".to_string())
+        })?;
+    Ok(MontgomeryPoint(bytes))
+}
+
+/// Check that a hex string is a usable X25519 sharing public key
+pub fn validate_sharing_public_key(public_key_hex: &str) -> Result<()> {
+    decode_sharing_public_key(public_key_hex).map(|_| ())
+}
+
+/// Discovery index for grants addressed to a sharing public key
+/// Lets the recipient find its grants without the key itself appearing on-chain
+pub fn share_recipient_index(public_key_hex: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(SHARE_RECIPIENT_INDEX_CONTEXT);
+    hasher.update(public_key_hex.to_ascii_lowercase().as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Derive the grant key from an X25519 exchange, rejecting low-order points
+/// (an all-zero shared secret would make the grant key publicly computable)
+fn grant_key(
+    secret: [u8; 32],
+    peer: MontgomeryPoint,
+    ephemeral_public: &MontgomeryPoint,
+    recipient_public: &MontgomeryPoint,
+) -> Result<[u8; 32]> {
+    let shared = peer.mul_clamped(secret);
+    if shared.to_bytes() == [0u8; 32] {
+        return Err(GoudChainError::InvalidRequestBody(
+            "Sharing public key is not a valid X25519 point".to_string(),
+        ));
+    }
+
+    let binding = [ephemeral_public.to_bytes(), recipient_public.to_bytes()].concat();
+    Ok(derive_share_grant_key(shared.as_bytes(), &binding))
+}
+
+/// Seal `plaintext` so only the holder of `recipient_public_key_hex` can open it
+/// Returns `(ephemeral_public_key_hex, sealed_base64)`
+pub fn seal_for_recipient(
+    recipient_public_key_hex: &str,
+    plaintext: &str,
+) -> Result<(String, String)> {
+    let recipient_public = decode_sharing_public_key(recipient_public_key_hex)?;
+    let ephemeral_secret: [u8; 32] = rand::random();
+    let ephemeral_public = MontgomeryPoint::mul_base_clamped(ephemeral_secret);
+
+    let key = grant_key(
+        ephemeral_secret,
+        recipient_public,
+        &ephemeral_public,
+        &recipient_public,
+    )?;
+    let (sealed, _nonce) = encrypt_data_with_key(plaintext, &key)?;
+
+    Ok((hex::encode(ephemeral_public.to_bytes()), sealed))
+}
+
+/// Open content sealed by `seal_for_recipient` with the recipient's API key
+pub fn open_sealed(api_key: &[u8], ephemeral_public_key_hex: &str, sealed: &str) -> Result<String> {
+    let ephemeral_public = decode_sharing_public_key(ephemeral_public_key_hex)?;
+    let secret = derive_sharing_secret(api_key);
+    let recipient_public = MontgomeryPoint::mul_base_clamped(secret);
+
+    let key = grant_key(
+        secret,
+        ephemeral_public,
+        &ephemeral_public,
+        &recipient_public,
+    )?;
+    decrypt_data_with_key(sealed, &key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_api_key;
+
+    #[test]
+    fn test_seal_and_open_roundtrip() {
+        let recipient = generate_api_key();
+        let public_key = sharing_public_key_hex(&recipient);
+
+        let (ephemeral, sealed) = seal_for_recipient(&public_key, "shared secret").unwrap();
+        assert_eq!(
+            open_sealed(&recipient, &ephemeral, &sealed).unwrap(),
+            "shared secret"
+        );
+
+        // Nobody else's API key opens the grant
+        assert!(open_sealed(&generate_api_key(), &ephemeral, &sealed).is_err());
+    }
+
+    #[test]
+    fn test_rejects_invalid_public_keys() {
+        assert!(validate_sharing_public_key("not hex").is_err());
+        assert!(validate_sharing_public_key("abcd").is_err());
+
+        // The all-zero point is low-order: every shared secret would be zero
+        assert!(seal_for_recipient(&"00".repeat(32), "data").is_err());
+    }
+
+    #[test]
+    fn test_recipient_index_is_deterministic() {
+        let public_key = sharing_public_key_hex(&generate_api_key());
+        assert_eq!(
+            share_recipient_index(&public_key),
+            share_recipient_index(&public_key)
+        );
+        assert_ne!(share_recipient_index(&public_key), public_key);
+    }
+}
@@ -9,9 +9,8 @@ pub fn sign_message(message: &[u8], signing_key: &SigningKey) -> String {
     hex::encode(signature.to_bytes())
 }
 
-/// Verify a signature against a message and public key
-pub fn verify_signature(message: &[u8], signature_hex: &str, public_key_hex: &str) -> Result<()> {
-    // Decode public key
+/// Decode a hex-encoded Ed25519 public key
+fn decode_public_key(public_key_hex: &str) -> Result<VerifyingKey> {
     let pk_bytes = hex::decode(public_key_hex).map_err(GoudChainError::HexDecodingError)?;
 
     if pk_bytes.len() != ED25519_PUBLIC_KEY_SIZE {
@@ -22,8 +21,18 @@ pub fn verify_signature(message: &[u8], signature_hex: &str, public_key_hex: &st
         .try_into()
         .map_err(|_| GoudChainError::InvalidSignature)?;
 
-    let verifying_key =
-        VerifyingKey::from_bytes(&pk_array).map_err(|_| GoudChainError::InvalidSignature)?;
+    VerifyingKey::from_bytes(&pk_array).map_err(|_| GoudChainError::InvalidSignature)
+}
+
+/// Check that a hex string is a valid Ed25519 public key
+pub fn validate_public_key(public_key_hex: &str) -> Result<()> {
+    decode_public_key(public_key_hex).map(|_| ())
+}
+
+/// Verify a signature against a message and public key
+pub fn verify_signature(message: &[u8], signature_hex: &str, public_key_hex: &str) -> Result<()> {
+    // Decode public key
+    let verifying_key = decode_public_key(public_key_hex)?;
 
     // Decode signature
     let sig_bytes = hex::decode(signature_hex).map_err(GoudChainError::HexDecodingError)?;
@@ -83,4 +92,12 @@ mod tests {
         let result = verify_signature(wrong_message, &signature, &public_key);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_validate_public_key() {
+        let signing_key = generate_signing_key();
+        assert!(validate_public_key(&get_public_key_hex(&signing_key)).is_ok());
+        assert!(validate_public_key("not-hex").is_err());
+        assert!(validate_public_key("abcd").is_err());
+    }
 }
@@ -4,14 +4,18 @@ use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use super::{
+    bloom::BloomFilter,
     encrypted_collection::EncryptedCollection,
-    envelope::{AccountEnvelope, BlockEnvelopeContainer, CollectionEnvelope},
+    envelope::{
+        AccountDirectoryEntry, AccountEnvelope, BlockEnvelopeContainer, CollectionEnvelope,
+    },
     user_account::UserAccount,
 };
 use crate::constants::{
-    EMPTY_MERKLE_ROOT, GENESIS_TIMESTAMP, TIMESTAMP_GRANULARITY_SECONDS, TIMESTAMP_JITTER_SECONDS,
+    EMPTY_MERKLE_ROOT, GENESIS_PREVIOUS_HASH, GENESIS_TIMESTAMP, TIMESTAMP_GRANULARITY_SECONDS,
+    TIMESTAMP_JITTER_SECONDS,
 };
-use crate::crypto::hash_api_key_hex;
+use crate::crypto::{generate_account_blind_index_with_salt, hash_api_key_hex};
 use crate::types::{GoudChainError, Result};
 
 /// Generate a random 32-byte salt for blind index generation
@@ -21,6 +25,19 @@ pub fn generate_block_salt() -> String {
     hex::encode(salt_bytes)
 }
 
+/// Exact owner blind indexes for a block, one per distinct owner hash (sorted, deduplicated)
+pub fn owner_blind_indexes<'a>(
+    owner_hashes: impl Iterator<Item = &'a str>,
+    block_salt: &str,
+) -> Result<Vec<String>> {
+    let mut indexes = owner_hashes
+        .map(|hash| generate_account_blind_index_with_salt(hash, block_salt))
+        .collect::<Result<Vec<_>>>()?;
+    indexes.sort_unstable();
+    indexes.dedup();
+    Ok(indexes)
+}
+
 /// Obfuscate timestamp to daily granularity with random jitter for privacy
 ///
 /// 1. Rounds down to the nearest day to hide exact timing and timezone
@@ -34,6 +51,22 @@ fn obfuscate_timestamp(timestamp: i64) -> i64 {
     rounded + jitter
 }
 
+/// Hash a block from its header fields (the merkle root commits to the body)
+fn hash_header_fields(
+    index: u64,
+    timestamp: i64,
+    merkle_root: &str,
+    previous_hash: &str,
+    validator: &str,
+) -> String {
+    let content = format!(
+        "{}{}{}{}{}",
+        index, timestamp, merkle_root, previous_hash, validator
+    );
+    let hash = blake3::hash(content.as_bytes());
+    hash.to_hex().to_string()
+}
+
 /// Configuration for creating a new block
 pub struct BlockConfig {
     pub index: u64,
@@ -62,6 +95,30 @@ pub struct Block {
     pub hash: String,
 }
 
+/// Block metadata without the encrypted envelope bodies
+/// The hash covers every header field, so a header chain is verifiable on its own
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockHeader {
+    pub index: u64,
+    pub hash: String,
+    pub previous_hash: String,
+    pub merkle_root: String,
+    pub timestamp: i64,
+    pub validator: String,
+}
+
+impl BlockHeader {
+    pub fn calculate_hash(&self) -> String {
+        hash_header_fields(
+            self.index,
+            self.timestamp,
+            &self.merkle_root,
+            &self.previous_hash,
+            &self.validator,
+        )
+    }
+}
+
 impl Block {
     /// Create a new zero-knowledge block with envelope encryption
     /// User data encrypted in per-user envelopes, node operators cannot decrypt
@@ -72,12 +129,41 @@ impl Block {
         } else {
             obfuscate_timestamp(Utc::now().timestamp())
         };
+        Self::with_timestamp(config, timestamp)
+    }
+
+    /// Create a block timestamped strictly after `previous_timestamp`
+    /// The obfuscated timestamp for `now` is raised to `previous_timestamp + 1` when it would
+    /// fall at or before it (jitter or a lagging clock), so peers never reject it for ordering
+    pub fn new_after(config: BlockConfig, previous_timestamp: i64, now: i64) -> Result<Self> {
+        let timestamp = obfuscate_timestamp(now).max(previous_timestamp.saturating_add(1));
+        Self::with_timestamp(config, timestamp)
+    }
 
+    /// Create the genesis block: empty, with fixed fields, so the same validator and timestamp
+    /// give the same hash on every node
+    pub fn genesis(validator: String, timestamp: i64) -> Result<Self> {
+        Self::with_timestamp(
+            BlockConfig {
+                index: 0,
+                account_envelopes: Vec::new(),
+                collection_envelopes: Vec::new(),
+                previous_hash: GENESIS_PREVIOUS_HASH.to_string(),
+                validator,
+                blind_indexes: Vec::new(),
+                block_salt: String::from("genesis_salt"),
+            },
+            timestamp,
+        )
+    }
+
+    fn with_timestamp(config: BlockConfig, timestamp: i64) -> Result<Self> {
         // Create envelope container
         let envelope_container = BlockEnvelopeContainer {
             account_envelopes: config.account_envelopes,
             collection_envelopes: config.collection_envelopes,
             validator: config.validator.clone(),
+            pruned_collection_ids: Vec::new(),
         };
 
         // Serialize envelope container to JSON and encode as Base64
@@ -146,12 +232,25 @@ impl Block {
     }
 
     pub fn calculate_hash(&self) -> String {
-        let content = format!(
-            "{}{}{}{}{}",
-            self.index, self.timestamp, self.merkle_root, self.previous_hash, self.validator
-        );
-        let hash = blake3::hash(content.as_bytes());
-        hash.to_hex().to_string()
+        hash_header_fields(
+            self.index,
+            self.timestamp,
+            &self.merkle_root,
+            &self.previous_hash,
+            &self.validator,
+        )
+    }
+
+    /// Header fields only (for header-first sync)
+    pub fn header(&self) -> BlockHeader {
+        BlockHeader {
+            index: self.index,
+            hash: self.hash.clone(),
+            previous_hash: self.previous_hash.clone(),
+            merkle_root: self.merkle_root.clone(),
+            timestamp: self.timestamp,
+            validator: self.validator.clone(),
+        }
     }
 
     /// Get the envelope container (deserialize Base64 + JSON)
@@ -166,6 +265,89 @@ impl Block {
         })
     }
 
+    /// Drop the block's collection envelopes, keeping its header and account envelopes
+    ///
+    /// The hash and merkle root are left untouched so the header still validates and
+    /// links; the merkle root no longer matches the body. Pruned collection IDs are
+    /// kept so lookups can tell pruned data from data that never existed.
+    pub fn prune_collections(&mut self) -> Result<()> {
+        let mut container = self.get_envelope_container()?;
+        if container.collection_envelopes.is_empty() {
+            return Ok(());
+        }
+
+        let pruned = std::mem::take(&mut container.collection_envelopes);
+        container
+            .pruned_collection_ids
+            .extend(pruned.into_iter().map(|env| env.collection.collection_id));
+
+        let container_json = serde_json::to_string(&container).map_err(|e| {
+            GoudChainError::Internal(format!("Failed to serialize envelope container: {}", e))
+        })?;
+        self.encrypted_block_data = general_purpose::STANDARD.encode(container_json);
+        Ok(())
+    }
+
+    /// Whether pruning dropped the collection with this ID from the block
+    pub fn has_pruned_collection(&self, collection_id: &str) -> bool {
+        self.get_envelope_container().is_ok_and(|container| {
+            container
+                .pruned_collection_ids
+                .iter()
+                .any(|id| id == collection_id)
+        })
+    }
+
+    /// Drop the block's envelope container from memory, keeping its header and blind indexes
+    ///
+    /// The body stays in RocksDB and is loaded back on demand; the header still links
+    /// and blind index checks still skip the block without it.
+    pub fn offload_body(&mut self) {
+        self.encrypted_block_data = String::new();
+    }
+
+    /// Whether the body was offloaded (every stored block has a non-empty container)
+    pub fn is_offloaded(&self) -> bool {
+        self.encrypted_block_data.is_empty()
+    }
+
+    /// Exact blind indexes of every account and collection owner in this block
+    /// Owner hashes are plaintext envelope metadata, so no API key is needed
+    pub fn owner_blind_indexes(&self) -> Result<Vec<String>> {
+        let container = self.get_envelope_container()?;
+        let hashes = container
+            .account_envelopes
+            .iter()
+            .map(|envelope| envelope.api_key_hash.as_str())
+            .chain(
+                container
+                    .collection_envelopes
+                    .iter()
+                    .map(|envelope| envelope.collection.owner_api_key_hash.as_str()),
+            );
+        owner_blind_indexes(hashes, &self.block_salt)
+    }
+
+    /// Whether the block may hold envelopes owned by `api_key_hash` (false means definitely not)
+    /// Blocks without blind indexes (lazy, pre-filter blocks) always need a decrypt attempt
+    pub fn may_contain_owner(&self, api_key_hash: &str) -> bool {
+        if self.blind_indexes.is_empty() {
+            return true;
+        }
+        let Ok(owner_index) =
+            generate_account_blind_index_with_salt(api_key_hash, &self.block_salt)
+        else {
+            return false;
+        };
+
+        self.blind_indexes
+            .iter()
+            .any(|entry| match BloomFilter::decode(entry) {
+                Some(filter) => filter.might_contain(&owner_index),
+                None => *entry == owner_index,
+            })
+    }
+
     /// Get account by API key (server-side decryption with user's API key)
     /// Returns None if no matching envelope found
     pub fn get_account(&self, api_key: &[u8]) -> Result<Option<UserAccount>> {
@@ -174,8 +356,8 @@ impl Block {
         let api_key_hash = hash_api_key_hex(api_key);
         let container = self.get_envelope_container()?;
 
-        // Find matching envelope by api_key_hash
-        for envelope in container.account_envelopes {
+        // Find matching envelope by api_key_hash (newest revision in the block wins)
+        for envelope in container.account_envelopes.into_iter().rev() {
             if envelope.api_key_hash == api_key_hash {
                 let account = decrypt_account_envelope(&envelope, api_key, &self.block_salt)?;
                 return Ok(Some(account));
@@ -201,11 +383,48 @@ impl Block {
         Ok(collections)
     }
 
+    /// Get share grants (and grant revocations) sealed to a recipient's discovery index
+    pub fn get_grants_for(&self, recipient_index: &str) -> Result<Vec<EncryptedCollection>> {
+        let container = self.get_envelope_container()?;
+
+        let grants = container
+            .collection_envelopes
+            .into_iter()
+            .map(|env| env.collection)
+            .filter(|collection| {
+                collection
+                    .grant
+                    .as_ref()
+                    .is_some_and(|grant| grant.recipient_index == recipient_index)
+            })
+            .collect();
+
+        Ok(grants)
+    }
+
+    /// Get the public-key directory entry for an account ID (no decryption needed)
+    pub fn get_account_directory_entry(
+        &self,
+        account_id: &str,
+    ) -> Result<Option<AccountDirectoryEntry>> {
+        let container = self.get_envelope_container()?;
+
+        Ok(container
+            .account_envelopes
+            .into_iter()
+            .filter_map(|envelope| envelope.directory)
+            .find(|entry| entry.account_id == account_id))
+    }
+
     /// Get account count (without decrypting)
-    /// Useful for statistics/health endpoints
+    /// Useful for statistics/health endpoints; metadata revisions are not new accounts
     pub fn get_account_count(&self) -> Result<usize> {
         let container = self.get_envelope_container()?;
-        Ok(container.account_envelopes.len())
+        Ok(container
+            .account_envelopes
+            .iter()
+            .filter(|env| !env.revision)
+            .count())
     }
 
     /// Get collection count (without decrypting)
@@ -215,6 +434,17 @@ impl Block {
         Ok(container.collection_envelopes.len())
     }
 
+    /// Get the IDs of the collections stored in this block (without decrypting)
+    /// Collection IDs are plaintext in the envelope container
+    pub fn get_collection_ids(&self) -> Result<Vec<String>> {
+        let container = self.get_envelope_container()?;
+        Ok(container
+            .collection_envelopes
+            .into_iter()
+            .map(|env| env.collection.collection_id)
+            .collect())
+    }
+
     /// Verify all encrypted data in this block (signature verification only)
     /// Does NOT decrypt user data - preserves zero-knowledge property
     /// Note: We can only verify collection signatures without decrypting accounts
@@ -256,6 +486,19 @@ mod tests {
         assert!(!block.encrypted_block_data.is_empty());
     }
 
+    #[test]
+    fn test_genesis_hash_depends_only_on_validator_and_timestamp() {
+        let genesis = Block::genesis("Validator_1".to_string(), GENESIS_TIMESTAMP).unwrap();
+        let again = Block::genesis("Validator_1".to_string(), GENESIS_TIMESTAMP).unwrap();
+        assert_eq!(genesis.hash, again.hash);
+        assert_eq!(genesis.timestamp, GENESIS_TIMESTAMP);
+        assert_eq!(genesis.previous_hash, GENESIS_PREVIOUS_HASH);
+
+        let other = Block::genesis("Validator_1".to_string(), 0).unwrap();
+        assert_eq!(other.timestamp, 0);
+        assert_ne!(other.hash, genesis.hash);
+    }
+
     #[test]
     fn test_empty_merkle_root() {
         let merkle_root = Block::calculate_merkle_root("", &[]);
@@ -1,36 +1,111 @@
 use chrono::Utc;
 use ed25519_dalek::SigningKey;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Instant;
 use tracing::{info, warn};
+use zeroize::Zeroizing;
 
 use super::{
-    block::{generate_block_salt, Block, BlockConfig},
+    block::{generate_block_salt, owner_blind_indexes, Block, BlockConfig, BlockHeader},
+    bloom::BloomFilter,
     encrypted_collection::EncryptedCollection,
+    envelope::AccountDirectoryEntry,
     user_account::UserAccount,
 };
 use crate::constants::{
-    CHECKPOINT_INTERVAL, GENESIS_PREVIOUS_HASH, SCHEMA_VERSION, TIMESTAMP_TOLERANCE_SECONDS,
+    DEFAULT_CHECKPOINT_INTERVAL, DEFAULT_CONTENT_TYPE, DEFAULT_MAX_REORG_DEPTH, GENESIS_TIMESTAMP,
+    SCHEMA_VERSION, TIMESTAMP_JITTER_SECONDS, TIMESTAMP_TOLERANCE_SECONDS,
 };
 use crate::crypto::{
     generate_account_blind_index_with_salt, generate_signing_key, hash_api_key_hex,
+    share_recipient_index, sharing_public_key_hex,
 };
-use crate::types::{GoudChainError, Result};
+use crate::types::metrics::BLOCK_PRODUCTION_SECONDS;
+use crate::types::{GoudChainError, Result, SharedClock};
 
-/// Get the current validator for a given block number (round-robin)
-/// Uses ValidatorConfig from the blockchain instance
-pub fn get_current_validator(validators: &[String], block_number: u64) -> String {
-    let index = (block_number % validators.len() as u64) as usize;
-    validators[index].clone()
+/// A collection annotated with its position in a version chain
+#[derive(Debug, Clone)]
+pub struct CollectionRevision {
+    pub collection: EncryptedCollection,
+    pub version: usize, // 1 for the first version, incremented for each revision
+    pub is_head: bool,  // True if no newer revision supersedes this one
 }
 
-/// Check if a node is authorized to be a validator for a given block
-/// Uses ValidatorConfig for node-to-validator mapping (no hardcoded deployment names)
-pub fn is_authorized_validator(
-    node_id: &str,
-    block_number: u64,
-    validator_config: &crate::config::ValidatorConfig,
-) -> bool {
-    validator_config.is_node_authorized(node_id, block_number)
+/// A read-only collection copy another account shared with the caller
+#[derive(Debug, Clone)]
+pub struct SharedCollection {
+    pub grant_id: String, // ID of the grant record (the recipient's handle for the share)
+    pub collection_id: String, // Owner's source collection
+    pub label: String,
+    pub data: String,
+    pub content_type: String,
+    pub created_at: i64,
+    pub expires_at: Option<i64>,
+}
+
+/// Running chain totals for statistics, advanced block by block instead of rescanning the chain
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainAggregates {
+    pub tip_hash: String, // Tip the totals cover (stale once the chain moves elsewhere)
+    pub pruned_before: u64, // Pruning boundary the totals were counted under
+    pub total_blocks: u64,
+    pub total_collections: u64,
+    pub total_accounts: u64,
+    pub validator_distribution: HashMap<String, u64>, // Validator -> blocks produced
+}
+
+impl ChainAggregates {
+    /// Count one more block on top of the current totals
+    fn add(&mut self, block: &Block) {
+        self.tip_hash = block.hash.clone();
+        self.total_blocks += 1;
+        self.total_collections += block.get_collection_count().unwrap_or(0) as u64;
+        self.total_accounts += block.get_account_count().unwrap_or(0) as u64;
+        *self
+            .validator_distribution
+            .entry(block.validator.clone())
+            .or_insert(0) += 1;
+    }
+}
+
+/// A share re-issued under a rotated API key
+#[derive(Debug, Clone)]
+pub struct RegrantedShare {
+    pub collection_id: String,
+    pub previous_grant_id: String, // Grant under the old key, now revoked
+    pub grant_id: String,          // Replacement grant under the new key
+}
+
+/// Records a key rotation queued for the next block
+#[derive(Debug, Clone)]
+pub struct KeyRotation {
+    pub reencrypted_collections: usize,
+    pub regranted_shares: Vec<RegrantedShare>,
+}
+
+/// Loads a block's stored copy by index (the block store, set at startup)
+/// Offloaded blocks are read back through it; without one they stay header-only
+#[derive(Clone, Default)]
+pub struct BlockLoader(Option<Arc<dyn Fn(u64) -> Result<Option<Block>> + Send + Sync>>);
+
+impl BlockLoader {
+    pub fn new(load: impl Fn(u64) -> Result<Option<Block>> + Send + Sync + 'static) -> Self {
+        Self(Some(Arc::new(load)))
+    }
+}
+
+impl fmt::Debug for BlockLoader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.0.is_some() {
+            "BlockLoader(set)"
+        } else {
+            "BlockLoader(none)"
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,18 +115,54 @@ pub struct Blockchain {
     pub node_id: String,
     pub checkpoints: Vec<String>,
     #[serde(skip)]
-    pub pending_accounts_with_keys: Vec<(UserAccount, Vec<u8>)>, // (account, api_key) for envelope creation
+    pub pending_accounts_with_keys: Vec<(UserAccount, Zeroizing<Vec<u8>>)>, // (account, api_key) for envelope creation
     #[serde(skip)]
     pub pending_collections: Vec<EncryptedCollection>,
     #[serde(skip)]
     pub node_signing_key: Option<SigningKey>,
     #[serde(skip)]
     pub validator_config: crate::config::ValidatorConfig,
+    #[serde(skip)]
+    pub checkpoint_interval: u64, // Blocks between automatic checkpoints (0 disables them)
+    #[serde(skip)]
+    pub max_reorg_depth: u64, // Most blocks a chain replacement may roll back from our tip
+    #[serde(skip)]
+    pub pruned_before: u64, // Blocks 1..pruned_before have had their collections pruned (0 = none)
+    #[serde(skip)]
+    pub offloaded_before: u64, // Blocks 1..offloaded_before may hold only their header in memory (0 = none)
+    #[serde(skip)]
+    pub block_loader: BlockLoader, // Reads offloaded bodies back from the store
+    #[serde(skip)]
+    pub exact_blind_indexes: bool, // Store exact owner blind indexes in new blocks instead of a bloom filter
+    #[serde(skip)]
+    pub legacy_blind_indexes: HashMap<String, Vec<String>>, // Block hash -> owner indexes for blocks stored without any (node-local backfill)
+    #[serde(skip)]
+    pub aggregates: ChainAggregates, // Totals for /stats, advanced as blocks are appended
+    #[serde(skip)]
+    pub read_only: bool, // Maintenance mode: no new blocks are created (peers' blocks still sync)
+    #[serde(skip)]
+    pub min_block_interval_seconds: u64, // Coalesce writes into one block per interval (0 seals on every write)
+    #[serde(skip)]
+    pub block_seal_threshold: usize, // Pending collections that seal a block before the interval is up
+    #[serde(skip)]
+    pub last_sealed_at: i64, // When this node last created a block (Unix seconds, 0 = never)
+    #[serde(skip)]
+    pub clock: SharedClock, // Time source for block timestamp checks (the system clock outside tests)
 }
 
 impl Blockchain {
     /// Create a new zero-knowledge blockchain with genesis block
     pub fn new(node_id: String, validator_config: crate::config::ValidatorConfig) -> Result<Self> {
+        Self::with_genesis_timestamp(node_id, validator_config, GENESIS_TIMESTAMP)
+    }
+
+    /// Create a new blockchain whose genesis block carries `genesis_timestamp`
+    /// Nodes only sync with peers that built the same genesis, so the whole network must agree on it
+    pub fn with_genesis_timestamp(
+        node_id: String,
+        validator_config: crate::config::ValidatorConfig,
+        genesis_timestamp: i64,
+    ) -> Result<Self> {
         info!(
             node_id = %node_id,
             schema = %SCHEMA_VERSION,
@@ -59,17 +170,9 @@ impl Blockchain {
         );
 
         let signing_key = generate_signing_key();
-        let validator = get_current_validator(&validator_config.validators, 0);
+        let validator = validator_config.get_validator_for_block(0);
 
-        let genesis = Block::new(BlockConfig {
-            index: 0,
-            account_envelopes: Vec::new(),
-            collection_envelopes: Vec::new(),
-            previous_hash: GENESIS_PREVIOUS_HASH.to_string(),
-            validator,
-            blind_indexes: Vec::new(),
-            block_salt: String::from("genesis_salt"),
-        })?;
+        let genesis = Block::genesis(validator, genesis_timestamp)?;
 
         info!(
             genesis_hash = %genesis.hash,
@@ -77,7 +180,7 @@ impl Blockchain {
             "Genesis block created - ALL NODES MUST HAVE IDENTICAL HASH"
         );
 
-        Ok(Blockchain {
+        let mut blockchain = Blockchain {
             schema_version: SCHEMA_VERSION.to_string(),
             chain: vec![genesis],
             node_id,
@@ -86,6 +189,52 @@ impl Blockchain {
             pending_collections: Vec::new(),
             node_signing_key: Some(signing_key),
             validator_config,
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+            max_reorg_depth: DEFAULT_MAX_REORG_DEPTH,
+            pruned_before: 0,
+            offloaded_before: 0,
+            block_loader: BlockLoader::default(),
+            exact_blind_indexes: false,
+            legacy_blind_indexes: HashMap::new(),
+            aggregates: ChainAggregates::default(),
+            read_only: false,
+            min_block_interval_seconds: 0,
+            block_seal_threshold: DEFAULT_BLOCK_SEAL_THRESHOLD,
+            last_sealed_at: 0,
+            clock: SharedClock::default(),
+        };
+        blockchain.refresh_aggregates();
+        Ok(blockchain)
+    }
+
+    /// Fail with ReadOnlyMode while the node is in maintenance mode
+    pub fn ensure_writable(&self) -> Result<()> {
+        if self.read_only {
+            Err(GoudChainError::ReadOnlyMode)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Whether pending data should be sealed into a block now
+    /// Always true unless blocks are coalesced: then only once MIN_BLOCK_INTERVAL_SECONDS have
+    /// passed since our last block or the pending collections reach the seal threshold
+    pub fn seal_due(&self, now: i64) -> bool {
+        self.min_block_interval_seconds == 0
+            || self.pending_collections.len() >= self.block_seal_threshold
+            || now - self.last_sealed_at >= self.min_block_interval_seconds as i64
+    }
+
+    /// `seal_due` at the current time on this chain's clock
+    pub fn seal_due_now(&self) -> bool {
+        self.seal_due(self.clock.now())
+    }
+
+    /// Seconds until coalesced submissions are sealed by the interval (None when not coalescing)
+    pub fn seconds_until_seal(&self) -> Option<u64> {
+        (self.min_block_interval_seconds > 0).then(|| {
+            let due_at = self.last_sealed_at + self.min_block_interval_seconds as i64;
+            (due_at - self.clock.now()).max(0) as u64
         })
     }
 
@@ -94,7 +243,11 @@ impl Blockchain {
         self.chain.last().ok_or(GoudChainError::EmptyBlockchain)
     }
 
-    pub fn add_account_with_key(&mut self, account: UserAccount, api_key: Vec<u8>) -> Result<()> {
+    pub fn add_account_with_key(
+        &mut self,
+        account: UserAccount,
+        api_key: Zeroizing<Vec<u8>>,
+    ) -> Result<()> {
         // Skip signature verification (happens during chain validation)
         self.pending_accounts_with_keys.push((account, api_key));
         Ok(())
@@ -106,21 +259,105 @@ impl Blockchain {
         Ok(())
     }
 
+    /// Queue an API key rotation for the next block
+    ///
+    /// Queues a re-encrypted copy of every live collection, a revoked revision of the account
+    /// under the old key and the account re-keyed to `new_api_key`. Outgoing shares are
+    /// re-granted under the new key and the old grants revoked. Shares other accounts sealed
+    /// to the old key cannot be moved: the owner has to share them again.
+    pub fn queue_key_rotation(
+        &mut self,
+        account: &UserAccount,
+        api_key: &[u8],
+        new_api_key: Zeroizing<Vec<u8>>,
+        signing_key: &SigningKey,
+    ) -> Result<KeyRotation> {
+        let api_key_hash = hash_api_key_hex(api_key);
+        let new_api_key_hash = hash_api_key_hex(&new_api_key);
+
+        // Chain order is kept so revisions still follow their parents
+        let collections = self
+            .find_collections_by_owner(api_key)
+            .iter()
+            .map(|c| c.reencrypt(api_key, &new_api_key, new_api_key_hash.clone(), signing_key))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut grants = Vec::new();
+        let mut regranted_shares = Vec::new();
+        for grant in self.find_grants_by_owner(api_key, &api_key_hash) {
+            let Some(source) = grant
+                .grant_source(api_key)
+                .and_then(|id| collections.iter().find(|c| c.collection_id == id))
+            else {
+                continue; // Source deleted or expired: nothing to re-grant
+            };
+            let Some(recipient) = grant.grant_recipient(api_key) else {
+                continue;
+            };
+            let Some(revocation) = EncryptedCollection::new_grant_revocation(
+                &grant,
+                api_key,
+                api_key_hash.clone(),
+                signing_key,
+            )?
+            else {
+                continue;
+            };
+            let replacement = EncryptedCollection::new_grant(
+                source,
+                &recipient,
+                &new_api_key,
+                new_api_key_hash.clone(),
+                signing_key,
+            )?;
+
+            regranted_shares.push(RegrantedShare {
+                collection_id: source.collection_id.clone(),
+                previous_grant_id: grant.collection_id.clone(),
+                grant_id: replacement.collection_id.clone(),
+            });
+            grants.push(revocation);
+            grants.push(replacement);
+        }
+
+        let revoked = account.revoked(signing_key);
+        let rekeyed = account.with_api_key(api_key, &new_api_key, signing_key)?;
+
+        let reencrypted_collections = collections.len();
+        for collection in collections.into_iter().chain(grants) {
+            self.add_collection(collection)?;
+        }
+        self.add_account_with_key(revoked, Zeroizing::new(api_key.to_vec()))?;
+        self.add_account_with_key(rekeyed, new_api_key)?;
+
+        Ok(KeyRotation {
+            reencrypted_collections,
+            regranted_shares,
+        })
+    }
+
     /// Create a block from pending accounts and collections
     /// Only the designated PoA validator can create blocks (enforces consensus)
     pub fn add_block(&mut self) -> Result<Block> {
         use super::envelope::{encrypt_account_envelope, CollectionEnvelope};
 
+        let started = Instant::now();
+
+        self.ensure_writable()?;
+
         if self.pending_accounts_with_keys.is_empty() && self.pending_collections.is_empty() {
             return Err(GoudChainError::NoPendingData);
         }
 
         let previous_block = self.get_latest_block()?;
         let block_number = previous_block.index + 1;
-        let validator = get_current_validator(&self.validator_config.validators, block_number);
+        let validator = self.validator_config.get_validator_for_block(block_number);
 
         // Proof of Authority: Only the designated validator can create this block
-        if !is_authorized_validator(&self.node_id, block_number, &self.validator_config) {
+        if !self
+            .validator_config
+            .is_node_authorized(&self.node_id, block_number)
+        {
             warn!(
                 node_id = %self.node_id,
                 block_number = block_number,
@@ -152,18 +389,50 @@ impl Blockchain {
             });
         }
 
-        // Lazy blind index generation (defer HMAC until search time)
-        let blind_indexes = Vec::new();
+        // Owner blind indexes let lookups skip blocks they have nothing in without decrypting
+        // them: exact indexes when configured, otherwise one compact bloom filter over them
+        let owner_indexes = owner_blind_indexes(
+            self.pending_accounts_with_keys
+                .iter()
+                .map(|(account, _)| account.api_key_hash.as_str())
+                .chain(
+                    self.pending_collections
+                        .iter()
+                        .map(|c| c.owner_api_key_hash.as_str()),
+                ),
+            &block_salt,
+        )?;
+        let blind_indexes = if self.exact_blind_indexes {
+            owner_indexes
+        } else {
+            vec![BloomFilter::from_entries(owner_indexes.iter().map(String::as_str)).encode()]
+        };
+
+        // Timestamps are jittered by up to TIMESTAMP_JITTER_SECONDS, so only a previous block
+        // further ahead than that means this node's clock is behind the previous validator's
+        let now = self.clock.now();
+        if previous_block.timestamp > now + TIMESTAMP_JITTER_SECONDS {
+            warn!(
+                block_number = block_number,
+                previous_timestamp = previous_block.timestamp,
+                now = now,
+                "Clock skew detected: previous block is ahead of this node's clock, timestamping the new block after it"
+            );
+        }
 
-        let new_block = Block::new(BlockConfig {
-            index: block_number,
-            account_envelopes,
-            collection_envelopes,
-            previous_hash: previous_block.hash.clone(),
-            validator: validator.clone(),
-            blind_indexes,
-            block_salt,
-        })?;
+        let new_block = Block::new_after(
+            BlockConfig {
+                index: block_number,
+                account_envelopes,
+                collection_envelopes,
+                previous_hash: previous_block.hash.clone(),
+                validator: validator.clone(),
+                blind_indexes,
+                block_salt,
+            },
+            previous_block.timestamp,
+            now,
+        )?;
 
         info!(
             block_number = new_block.index,
@@ -174,20 +443,43 @@ impl Blockchain {
         );
 
         self.chain.push(new_block.clone());
+        self.refresh_aggregates();
         self.pending_accounts_with_keys.clear();
         self.pending_collections.clear();
+        self.last_sealed_at = self.clock.now();
 
         // Create checkpoint
-        #[allow(unknown_lints)]
-        #[allow(clippy::manual_is_multiple_of)]
-        if new_block.index % CHECKPOINT_INTERVAL == 0 {
+        if self.is_checkpoint_index(new_block.index) {
             self.checkpoints.push(new_block.hash.clone());
             info!(block_number = new_block.index, "Checkpoint created");
         }
 
+        BLOCK_PRODUCTION_SECONDS.observe(started.elapsed());
         Ok(new_block)
     }
 
+    /// Whether `index` falls on the automatic checkpoint interval (genesis never does)
+    pub fn is_checkpoint_index(&self, index: u64) -> bool {
+        #[allow(unknown_lints)]
+        #[allow(clippy::manual_is_multiple_of)]
+        let on_interval = self.checkpoint_interval > 0 && index % self.checkpoint_interval == 0;
+        index > 0 && on_interval
+    }
+
+    /// Checkpoint the current tip ahead of the interval (e.g. before maintenance)
+    ///
+    /// Reorgs never cross the latest checkpoint, so this makes every block up to the tip
+    /// final on this node. Returns the checkpointed block; a tip that is already the
+    /// latest checkpoint is returned unchanged.
+    pub fn create_checkpoint(&mut self) -> Result<Block> {
+        let tip = self.get_latest_block()?.clone();
+        if self.checkpoints.last() != Some(&tip.hash) {
+            self.checkpoints.push(tip.hash.clone());
+            info!(block_number = tip.index, "Checkpoint created on demand");
+        }
+        Ok(tip)
+    }
+
     /// Validate the entire blockchain
     pub fn is_valid(&self) -> Result<()> {
         for i in 1..self.chain.len() {
@@ -205,7 +497,7 @@ impl Blockchain {
             }
 
             // Validate timestamp
-            let now = Utc::now().timestamp();
+            let now = self.clock.now();
             if current.timestamp > now + TIMESTAMP_TOLERANCE_SECONDS {
                 return Err(GoudChainError::FutureTimestamp(current.timestamp));
             }
@@ -213,22 +505,25 @@ impl Blockchain {
                 return Err(GoudChainError::InvalidTimestamp(i as u64));
             }
 
-            // Validate merkle root
-            if current.merkle_root
-                != Block::calculate_merkle_root(
-                    &current.encrypted_block_data,
-                    &current.blind_indexes,
-                )
-            {
-                return Err(GoudChainError::InvalidMerkleRoot(i as u64));
-            }
+            // Offloaded bodies were validated before they left memory; their headers still link
+            if !self.is_offloaded(current) {
+                // Validate merkle root (pruned bodies no longer match; their headers still link)
+                if !self.is_pruned(current.index)
+                    && current.merkle_root
+                        != Block::calculate_merkle_root(
+                            &current.encrypted_block_data,
+                            &current.blind_indexes,
+                        )
+                {
+                    return Err(GoudChainError::InvalidMerkleRoot(i as u64));
+                }
 
-            // Validate all user data signatures (no decryption - zero-knowledge)
-            current.verify_data()?;
+                // Validate all user data signatures (no decryption - zero-knowledge)
+                current.verify_data()?;
+            }
 
             // Validate validator authorization (plaintext field)
-            let expected_validator =
-                get_current_validator(&self.validator_config.validators, current.index);
+            let expected_validator = self.validator_config.get_validator_for_block(current.index);
             if current.validator != expected_validator {
                 return Err(GoudChainError::InvalidValidator {
                     index: i as u64,
@@ -240,17 +535,283 @@ impl Blockchain {
         Ok(())
     }
 
+    /// Whether the block at `index` has had its collections pruned
+    pub fn is_pruned(&self, index: u64) -> bool {
+        index > 0 && index < self.pruned_before
+    }
+
+    /// First block index a pruning run with `depth` would keep in full
+    ///
+    /// Only blocks more than `depth` blocks older than the latest checkpoint are
+    /// pruned, so reorgs (which never cross a checkpoint) never need their bodies.
+    pub fn prune_boundary(&self, depth: u64) -> u64 {
+        self.header_sync_anchor().saturating_sub(depth)
+    }
+
+    /// Prune the collections of blocks before `index` from the in-memory chain
+    /// Mirrors `BlockchainStore::prune_before`; returns how many blocks were pruned
+    /// Offloaded blocks are skipped: their collections only exist in the store
+    pub fn prune_before(&mut self, index: u64) -> Result<usize> {
+        let start = self.pruned_before.max(1);
+        let mut pruned = 0;
+        for block in self
+            .chain
+            .iter_mut()
+            .filter(|b| b.index >= start && b.index < index && !b.is_offloaded())
+        {
+            block.prune_collections()?;
+            pruned += 1;
+        }
+        self.pruned_before = self.pruned_before.max(index);
+        // Pruned blocks no longer count their collections
+        self.refresh_aggregates();
+        Ok(pruned)
+    }
+
+    /// Whether `block` sits below the offload boundary with only its header in memory
+    pub fn is_offloaded(&self, block: &Block) -> bool {
+        block.index > 0 && block.index < self.offloaded_before && block.is_offloaded()
+    }
+
+    /// First block index an offload run keeping `max_in_memory` blocks would keep in memory
+    ///
+    /// Never past the latest checkpoint, so reorgs (which never cross a checkpoint) only
+    /// replace blocks that are still in memory.
+    pub fn offload_boundary(&self, max_in_memory: u64) -> u64 {
+        (self.chain.len() as u64)
+            .saturating_sub(max_in_memory)
+            .min(self.header_sync_anchor())
+    }
+
+    /// Evict the bodies of blocks before `index` from memory (they stay in the block store)
+    /// Returns how many blocks were offloaded; does nothing without a block loader to read them back
+    pub fn offload_before(&mut self, index: u64) -> usize {
+        if self.block_loader.0.is_none() {
+            return 0;
+        }
+        let mut offloaded = 0;
+        for block in self
+            .chain
+            .iter_mut()
+            .filter(|b| b.index > 0 && b.index < index && !b.is_offloaded())
+        {
+            block.offload_body();
+            offloaded += 1;
+        }
+        self.offloaded_before = self.offloaded_before.max(index);
+        offloaded
+    }
+
+    /// `block` with its body, read back from the store if it was offloaded
+    /// Falls back to the header-only block (no envelopes) if the stored copy can't be read
+    pub fn full_block<'a>(&self, block: &'a Block) -> Cow<'a, Block> {
+        if !self.is_offloaded(block) {
+            return Cow::Borrowed(block);
+        }
+        let loaded = self.block_loader.0.as_ref().map(|load| load(block.index));
+        match loaded {
+            Some(Ok(Some(stored))) if stored.hash == block.hash && !stored.is_offloaded() => {
+                Cow::Owned(stored)
+            }
+            Some(Err(e)) => {
+                warn!(block_index = block.index, error = %e, "Failed to load offloaded block");
+                Cow::Borrowed(block)
+            }
+            _ => {
+                warn!(
+                    block_index = block.index,
+                    "Offloaded block missing from the block store"
+                );
+                Cow::Borrowed(block)
+            }
+        }
+    }
+
+    /// Every block with its body (offloaded bodies are read back from the store)
+    pub fn full_chain(&self) -> Vec<Block> {
+        self.chain
+            .iter()
+            .map(|block| self.full_block(block).into_owned())
+            .collect()
+    }
+
+    /// Chain totals for statistics: the running aggregates when they cover the current tip,
+    /// otherwise a full recount (the chain was changed without `refresh_aggregates`)
+    pub fn chain_aggregates(&self) -> ChainAggregates {
+        if self.aggregates_current() {
+            self.aggregates.clone()
+        } else {
+            self.count_aggregates()
+        }
+    }
+
+    /// Bring the running aggregates up to the tip
+    /// Appending one block is counted incrementally; any other change (reorg, pruning, blocks
+    /// pushed directly) triggers a full recount
+    pub fn refresh_aggregates(&mut self) {
+        let len = self.chain.len();
+        let extends_tip = len >= 2
+            && self.chain[len - 2].hash == self.aggregates.tip_hash
+            && self.aggregates.pruned_before == self.pruned_before;
+        if extends_tip {
+            self.aggregates.add(&self.chain[len - 1]);
+        } else if !self.aggregates_current() {
+            self.aggregates = self.count_aggregates();
+        }
+    }
+
+    fn aggregates_current(&self) -> bool {
+        self.chain
+            .last()
+            .is_some_and(|tip| tip.hash == self.aggregates.tip_hash)
+            && self.aggregates.pruned_before == self.pruned_before
+    }
+
+    fn count_aggregates(&self) -> ChainAggregates {
+        let mut aggregates = ChainAggregates {
+            pruned_before: self.pruned_before,
+            ..ChainAggregates::default()
+        };
+        for block in &self.chain {
+            aggregates.add(&self.full_block(block));
+        }
+        aggregates
+    }
+
+    /// Hash of the genesis block, identical on every correctly configured node
+    pub fn genesis_hash(&self) -> Option<String> {
+        self.chain.first().map(|genesis| genesis.hash.clone())
+    }
+
+    /// Index header sync starts from: the latest checkpoint, or genesis
+    ///
+    /// Blocks up to the latest checkpoint are final, so their headers never need comparing.
+    pub fn header_sync_anchor(&self) -> u64 {
+        self.checkpoints
+            .last()
+            .and_then(|hash| self.chain.iter().find(|b| &b.hash == hash))
+            .map(|b| b.index)
+            .unwrap_or(0)
+    }
+
+    /// Validate a peer's header chain and find where it diverges from ours
+    ///
+    /// `headers` must be contiguous and start at a block we already hold. Checks
+    /// header hashes, hash links, timestamps and validator rotation without block
+    /// bodies. Returns the index of the first header that differs from our chain,
+    /// or the index after the peer's tip if every header matches ours.
+    pub fn find_fork_point(&self, headers: &[BlockHeader]) -> Result<u64> {
+        let anchor = headers.first().ok_or(GoudChainError::BrokenChain(0))?;
+        let our_anchor = self.chain.get(anchor.index as usize);
+        if our_anchor.map(|b| &b.hash) != Some(&anchor.hash) {
+            return Err(GoudChainError::BrokenChain(anchor.index));
+        }
+
+        let now = self.clock.now();
+        for pair in headers.windows(2) {
+            let (previous, current) = (&pair[0], &pair[1]);
+
+            // Validate chain link
+            if current.index != previous.index + 1 || current.previous_hash != previous.hash {
+                return Err(GoudChainError::BrokenChain(current.index));
+            }
+
+            // Validate hash (covers every header field)
+            if current.hash != current.calculate_hash() {
+                return Err(GoudChainError::InvalidBlockHash(current.index));
+            }
+
+            // Validate timestamp
+            if current.timestamp > now + TIMESTAMP_TOLERANCE_SECONDS {
+                return Err(GoudChainError::FutureTimestamp(current.timestamp));
+            }
+            if current.timestamp < previous.timestamp {
+                return Err(GoudChainError::InvalidTimestamp(current.index));
+            }
+
+            // Validate validator rotation
+            let expected_validator = self.validator_config.get_validator_for_block(current.index);
+            if current.validator != expected_validator {
+                return Err(GoudChainError::InvalidValidator {
+                    index: current.index,
+                    expected: expected_validator,
+                    actual: current.validator.clone(),
+                });
+            }
+        }
+
+        let fork_point = headers
+            .iter()
+            .find(|header| {
+                self.chain
+                    .get(header.index as usize)
+                    .is_none_or(|block| block.hash != header.hash)
+            })
+            .map(|header| header.index)
+            .unwrap_or(headers[headers.len() - 1].index + 1);
+
+        Ok(fork_point)
+    }
+
     /// Replace the chain with a new one if it's longer and valid
-    pub fn replace_chain(&mut self, new_chain: Vec<Block>) -> Result<bool> {
-        // Don't reorganize past checkpoints
-        if let Some(last_checkpoint_hash) = self.checkpoints.last() {
-            if let Some(checkpoint_block) =
-                self.chain.iter().find(|b| &b.hash == last_checkpoint_hash)
-            {
-                if new_chain.len() <= checkpoint_block.index as usize {
-                    warn!("Chain reorganization blocked by checkpoint");
-                    return Ok(false);
-                }
+    pub fn replace_chain(&mut self, mut new_chain: Vec<Block>) -> Result<bool> {
+        let fork_point = self.fork_index(&new_chain);
+
+        // Don't reorganize past checkpoints: the candidate must keep the checkpointed block
+        let checkpoint_index = self.header_sync_anchor();
+        if checkpoint_index > 0 && fork_point <= checkpoint_index {
+            warn!(
+                checkpoint_index,
+                fork_point, "Chain reorganization blocked by checkpoint"
+            );
+            return Ok(false);
+        }
+
+        // Bound how much history a peer can roll back between checkpoints
+        let depth = self.reorg_depth(&new_chain);
+        if depth > self.max_reorg_depth {
+            warn!(
+                depth,
+                max_reorg_depth = self.max_reorg_depth,
+                fork_point,
+                "Chain reorganization blocked: deeper than MAX_REORG_DEPTH"
+            );
+            return Ok(false);
+        }
+
+        // Pruned blocks can't be revalidated, so the candidate must keep them unchanged
+        let pruned_prefix = self.pruned_before as usize;
+        if pruned_prefix > 0
+            && (new_chain.len() < pruned_prefix
+                || self
+                    .chain
+                    .iter()
+                    .zip(&new_chain)
+                    .take(pruned_prefix)
+                    .any(|(ours, theirs)| ours.hash != theirs.hash))
+        {
+            warn!("Chain reorganization blocked: candidate rewrites pruned blocks");
+            return Ok(false);
+        }
+
+        // Offloaded blocks are only held as headers, so the candidate must keep them too
+        let offloaded_prefix = self.offloaded_before as usize;
+        if offloaded_prefix > 0
+            && (new_chain.len() < offloaded_prefix
+                || self
+                    .chain
+                    .iter()
+                    .zip(&new_chain)
+                    .take(offloaded_prefix)
+                    .any(|(ours, theirs)| ours.hash != theirs.hash))
+        {
+            warn!("Chain reorganization blocked: candidate rewrites offloaded blocks");
+            return Ok(false);
+        }
+        // Keep our header-only copies so adopting the candidate doesn't reload those bodies
+        for (theirs, ours) in new_chain.iter_mut().zip(&self.chain).take(offloaded_prefix) {
+            if ours.is_offloaded() {
+                *theirs = ours.clone();
             }
         }
 
@@ -263,6 +824,19 @@ impl Blockchain {
             pending_collections: Vec::new(),
             node_signing_key: None,
             validator_config: self.validator_config.clone(),
+            checkpoint_interval: self.checkpoint_interval,
+            max_reorg_depth: self.max_reorg_depth,
+            pruned_before: self.pruned_before,
+            offloaded_before: self.offloaded_before,
+            block_loader: self.block_loader.clone(),
+            exact_blind_indexes: self.exact_blind_indexes,
+            legacy_blind_indexes: self.legacy_blind_indexes.clone(),
+            aggregates: ChainAggregates::default(),
+            read_only: false,
+            min_block_interval_seconds: 0,
+            block_seal_threshold: DEFAULT_BLOCK_SEAL_THRESHOLD,
+            last_sealed_at: 0,
+            clock: self.clock.clone(),
         };
 
         // Chain selection logic with tie-breaking
@@ -297,8 +871,9 @@ impl Blockchain {
                 let their_last = &new_chain[new_chain.len() - 1];
 
                 // Check validator authorization (plaintext field - no decryption needed)
-                let expected_validator =
-                    get_current_validator(&self.validator_config.validators, our_last.index);
+                let expected_validator = self
+                    .validator_config
+                    .get_validator_for_block(our_last.index);
 
                 let our_is_valid = our_last.validator == expected_validator;
                 let their_is_valid = their_last.validator == expected_validator;
@@ -325,27 +900,86 @@ impl Blockchain {
         if should_replace {
             // Validate the new chain before accepting
             temp_blockchain.is_valid()?;
-            info!(
-                old_length = self.chain.len(),
-                new_length = new_chain.len(),
-                old_last_hash = %self.chain.last().map(|b| b.hash.clone()).unwrap_or_default(),
-                new_last_hash = %new_chain.last().map(|b| b.hash.clone()).unwrap_or_default(),
-                "Replacing chain (tie-breaker applied if equal length)"
-            );
+            let old_last_hash = self
+                .chain
+                .last()
+                .map(|b| b.hash.clone())
+                .unwrap_or_default();
+            let new_last_hash = new_chain.last().map(|b| b.hash.clone()).unwrap_or_default();
+            if depth > 0 {
+                warn!(
+                    depth,
+                    fork_point,
+                    old_length = self.chain.len(),
+                    new_length = new_chain.len(),
+                    old_last_hash = %old_last_hash,
+                    new_last_hash = %new_last_hash,
+                    "CHAIN REORG: rolling back blocks from our tip"
+                );
+            } else {
+                info!(
+                    old_length = self.chain.len(),
+                    new_length = new_chain.len(),
+                    old_last_hash = %old_last_hash,
+                    new_last_hash = %new_last_hash,
+                    "Replacing chain (tie-breaker applied if equal length)"
+                );
+            }
             self.chain = new_chain;
+            self.refresh_aggregates();
+
+            // Rebuild checkpoints so they match the adopted chain (on-demand checkpoints
+            // survive as long as their block is still part of it)
+            let previous: HashSet<String> =
+                std::mem::take(&mut self.checkpoints).into_iter().collect();
+            let checkpoints = self
+                .chain
+                .iter()
+                .filter(|b| self.is_checkpoint_index(b.index) || previous.contains(&b.hash))
+                .map(|b| b.hash.clone())
+                .collect();
+            self.checkpoints = checkpoints;
             return Ok(true);
         }
 
         Ok(false)
     }
 
+    /// Index of the first block where `other` differs from our chain
+    /// (the shorter chain's length if one is a prefix of the other)
+    pub fn fork_index(&self, other: &[Block]) -> u64 {
+        self.chain
+            .iter()
+            .zip(other)
+            .take_while(|(ours, theirs)| ours.hash == theirs.hash)
+            .count() as u64
+    }
+
+    /// How many of our blocks adopting `other` would roll back (0 if it extends our chain)
+    pub fn reorg_depth(&self, other: &[Block]) -> u64 {
+        (self.chain.len() as u64).saturating_sub(self.fork_index(other))
+    }
+
     /// Find account by API key (requires full API key, not just hash)
     /// Searches all blocks and decrypts matching envelopes
     pub fn find_account(&self, api_key: &[u8]) -> Option<UserAccount> {
         self.find_account_with_hash(api_key, None)
     }
 
+    /// Look up an account's public keys by account ID
+    /// Newest entry wins; accounts created before the directory existed are not listed
+    pub fn find_account_public_keys(&self, account_id: &str) -> Option<AccountDirectoryEntry> {
+        self.chain.iter().rev().find_map(|block| {
+            self.full_block(block)
+                .get_account_directory_entry(account_id)
+                .ok()
+                .flatten()
+        })
+    }
+
     /// Find account with optional pre-computed hash (optimization)
+    /// Newest revision wins: metadata updates append a new envelope for the same account,
+    /// and a key rotation leaves a revoked revision under the old key (found as None)
     pub fn find_account_with_hash(
         &self,
         api_key: &[u8],
@@ -353,110 +987,1704 @@ impl Blockchain {
     ) -> Option<UserAccount> {
         let api_key_hash = api_key_hash.unwrap_or_else(|| hash_api_key_hex(api_key));
 
-        for block in &self.chain {
-            let should_search_block = if block.blind_indexes.is_empty() {
-                true
-            } else {
-                match generate_account_blind_index_with_salt(&api_key_hash, &block.block_salt) {
-                    Ok(idx) => block.blind_indexes.contains(&idx),
-                    Err(_) => false,
-                }
-            };
-
-            if should_search_block {
+        for block in self.chain.iter().rev() {
+            if self.may_contain_owner(block, &api_key_hash) {
                 // Try to decrypt account envelope with user's API key
-                if let Ok(Some(account)) = block.get_account(api_key) {
-                    return Some(account);
+                if let Ok(Some(account)) = self.full_block(block).get_account(api_key) {
+                    return account.revoked_at.is_none().then_some(account);
                 }
             }
         }
         None
     }
 
+    /// Find account with the same amount of work wherever (or whether) it sits in the chain
+    /// Unlike `find_account_with_hash` this never stops early: every block's blind index is
+    /// checked and every candidate envelope decrypted, so lookup time does not reveal the
+    /// account's position. Used by login, where timing is observable by unauthenticated callers.
+    pub fn find_account_uniform(&self, api_key: &[u8], api_key_hash: &str) -> Option<UserAccount> {
+        let mut newest: Option<UserAccount> = None;
+
+        for block in self.chain.iter().rev() {
+            if self.may_contain_owner(block, api_key_hash) {
+                let account = self.full_block(block).get_account(api_key).ok().flatten();
+                if newest.is_none() {
+                    newest = account;
+                }
+            }
+        }
+
+        newest.filter(|account| account.revoked_at.is_none())
+    }
+
     /// Find a collection by ID (requires API key to decrypt envelope and verify ownership)
-    /// Returns None if collection not found or user doesn't own it
+    /// Returns None if collection not found, user doesn't own it, it has been tombstoned or it has expired
+    /// Share grant records are not collections of their own and are never returned
+    /// Fails with `DataPruned` when the collection sits in a pruned block
     pub fn find_collection(
         &self,
         collection_id: &str,
         api_key: &[u8],
-    ) -> Option<EncryptedCollection> {
+    ) -> Result<Option<EncryptedCollection>> {
         let api_key_hash = hash_api_key_hex(api_key);
+        let collections = self.scan_collections_by_owner(api_key, &api_key_hash);
+        let tombstoned = Self::tombstoned_ids(&collections, api_key, &api_key_hash);
 
-        // Search all blocks
-        for block in &self.chain {
-            // Get collections for this user
-            if let Ok(collections) = block.get_collections_by_owner(api_key) {
-                for collection in collections {
-                    if collection.collection_id == collection_id
-                        && collection.owner_api_key_hash == api_key_hash
-                    {
-                        return Some(collection);
-                    }
-                }
-            }
+        if tombstoned.contains(collection_id) {
+            return Ok(None);
+        }
+
+        let now = Utc::now().timestamp();
+        let found = collections.into_iter().find(|collection| {
+            collection.collection_id == collection_id
+                && collection.owner_api_key_hash == api_key_hash
+                && collection.grant.is_none()
+                && !collection.is_tombstone(api_key)
+                && !collection.is_expired(api_key, now)
+        });
+        if found.is_some() {
+            return Ok(found);
+        }
+
+        match self
+            .chain
+            .iter()
+            .take_while(|b| self.is_pruned(b.index) || b.index == 0)
+            .find(|b| self.full_block(b).has_pruned_collection(collection_id))
+        {
+            Some(block) => Err(GoudChainError::DataPruned {
+                block_index: block.index,
+            }),
+            None => Ok(None),
         }
-        None
     }
 
     /// Find all collections owned by user (requires API key to access envelopes)
+    /// Tombstone records, the collections they delete, share grant records and expired
+    /// collections are excluded
     pub fn find_collections_by_owner(&self, api_key: &[u8]) -> Vec<EncryptedCollection> {
         let api_key_hash = hash_api_key_hex(api_key);
-        let mut results = Vec::new();
+        let collections = self.scan_collections_by_owner(api_key, &api_key_hash);
+        let tombstoned = Self::tombstoned_ids(&collections, api_key, &api_key_hash);
+        let now = Utc::now().timestamp();
 
-        for block in &self.chain {
-            let should_search_block = if block.blind_indexes.is_empty() {
-                true
-            } else {
-                match generate_account_blind_index_with_salt(&api_key_hash, &block.block_salt) {
-                    Ok(idx) => block.blind_indexes.contains(&idx),
-                    Err(_) => false,
-                }
-            };
+        collections
+            .into_iter()
+            .filter(|collection| {
+                !tombstoned.contains(&collection.collection_id)
+                    && collection.grant.is_none()
+                    && !collection.is_tombstone(api_key)
+                    && !collection.is_expired(api_key, now)
+            })
+            .collect()
+    }
 
-            if should_search_block {
-                // Get collections from envelope
-                if let Ok(collections) = block.get_collections_by_owner(api_key) {
-                    results.extend(collections);
-                }
-            }
+    /// Find a share grant record created by this API key (None once it has been revoked)
+    pub fn find_grant(&self, grant_id: &str, api_key: &[u8]) -> Option<EncryptedCollection> {
+        let api_key_hash = hash_api_key_hex(api_key);
+        let collections = self.scan_collections_by_owner(api_key, &api_key_hash);
+        let tombstoned = Self::tombstoned_ids(&collections, api_key, &api_key_hash);
+
+        if tombstoned.contains(grant_id) {
+            return None;
         }
 
-        results
+        collections.into_iter().find(|collection| {
+            collection.collection_id == grant_id
+                && collection.grant.is_some()
+                && !collection.is_tombstone(api_key)
+        })
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::collections::HashMap;
+    /// Find the live share grant records created by this API key
+    fn find_grants_by_owner(&self, api_key: &[u8], api_key_hash: &str) -> Vec<EncryptedCollection> {
+        let collections = self.scan_collections_by_owner(api_key, api_key_hash);
+        let tombstoned = Self::tombstoned_ids(&collections, api_key, api_key_hash);
 
-    fn test_validator_config() -> crate::config::ValidatorConfig {
-        let mut node_to_validator = HashMap::new();
-        node_to_validator.insert("test-node".to_string(), "Validator_1".to_string());
-        node_to_validator.insert("node2".to_string(), "Validator_2".to_string());
+        collections
+            .into_iter()
+            .filter(|collection| {
+                !tombstoned.contains(&collection.collection_id)
+                    && collection.grant.is_some()
+                    && !collection.is_tombstone(api_key)
+            })
+            .collect()
+    }
 
-        let mut validator_to_address = HashMap::new();
-        validator_to_address.insert("Validator_1".to_string(), "node1:8080".to_string());
-        validator_to_address.insert("Validator_2".to_string(), "node2:8080".to_string());
+    /// Find collections other accounts have shared with this API key
+    /// Grants are found by the caller's sharing key index and opened with its API key;
+    /// revoked and expired grants are skipped
+    pub fn find_shared_collections(&self, api_key: &[u8]) -> Vec<SharedCollection> {
+        let recipient_index = share_recipient_index(&sharing_public_key_hex(api_key));
+        let mut grants = Vec::new();
+        let mut revoked: HashSet<(String, String)> = HashSet::new();
 
-        crate::config::ValidatorConfig {
-            validators: vec!["Validator_1".to_string(), "Validator_2".to_string()],
-            node_to_validator,
-            validator_to_address,
+        for block in &self.chain {
+            let Ok(records) = self.full_block(block).get_grants_for(&recipient_index) else {
+                continue;
+            };
+            for record in records {
+                if record.verify(None).is_err() {
+                    continue;
+                }
+                let Some(content) = record.open_grant(api_key) else {
+                    continue;
+                };
+                // Only the grant's owner can revoke it
+                match content["revoked_grant_id"].as_str() {
+                    Some(grant_id) => {
+                        revoked.insert((record.owner_api_key_hash, grant_id.to_string()));
+                    }
+                    None => grants.push((record, content)),
+                }
+            }
         }
-    }
 
-    #[test]
-    fn test_blockchain_creation() {
-        let blockchain = Blockchain::new("test-node".to_string(), test_validator_config()).unwrap();
-        assert_eq!(blockchain.chain.len(), 1);
-        assert!(blockchain.is_valid().is_ok());
+        let now = Utc::now().timestamp();
+        grants
+            .into_iter()
+            .filter(|(record, _)| {
+                !revoked.contains(&(
+                    record.owner_api_key_hash.clone(),
+                    record.collection_id.clone(),
+                ))
+            })
+            .filter_map(|(record, content)| {
+                let expires_at = content["expires_at"].as_i64();
+                if expires_at.is_some_and(|expires_at| expires_at <= now) {
+                    return None;
+                }
+                Some(SharedCollection {
+                    grant_id: record.collection_id,
+                    collection_id: content["collection_id"].as_str()?.to_string(),
+                    label: content["label"].as_str()?.to_string(),
+                    data: content["data"].as_str()?.to_string(),
+                    content_type: content["content_type"]
+                        .as_str()
+                        .unwrap_or(DEFAULT_CONTENT_TYPE)
+                        .to_string(),
+                    created_at: content["created_at"].as_i64().unwrap_or(0),
+                    expires_at,
+                })
+            })
+            .collect()
     }
 
-    #[test]
+    /// Find user collections whose label exactly matches `label`
+    /// Compares label blind indexes, so only non-matching legacy collections need decryption
+    pub fn find_collections_by_label_index(
+        &self,
+        label: &str,
+        api_key: &[u8],
+    ) -> Vec<EncryptedCollection> {
+        self.find_collections_by_owner(api_key)
+            .into_iter()
+            .filter(|collection| collection.matches_label(api_key, label))
+            .collect()
+    }
+
+    /// ID of a live collection (latest revision, or one still pending) that holds `label`
+    /// `revising` is the head a submission revises; it and pending revisions of it don't count
+    pub fn find_label_holder(
+        &self,
+        label: &str,
+        api_key: &[u8],
+        revising: Option<&str>,
+    ) -> Option<String> {
+        let is_revised = |collection: &EncryptedCollection| {
+            revising.is_some_and(|head| {
+                collection.collection_id == head
+                    || collection.parent_collection_id.as_deref() == Some(head)
+            })
+        };
+
+        let api_key_hash = hash_api_key_hex(api_key);
+        let pending: Vec<&EncryptedCollection> = self
+            .pending_collections
+            .iter()
+            .filter(|collection| collection.owner_api_key_hash == api_key_hash)
+            .collect();
+        // Heads deleted or superseded by a pending record no longer hold their label
+        let replaced: HashSet<String> = pending
+            .iter()
+            .filter_map(|collection| {
+                if collection.is_tombstone(api_key) {
+                    collection.tombstone_target(api_key)
+                } else {
+                    collection.parent_collection_id.clone()
+                }
+            })
+            .collect();
+
+        let sealed = self
+            .find_collection_revisions(api_key)
+            .into_iter()
+            .filter(|revision| revision.is_head)
+            .map(|revision| revision.collection)
+            .filter(|collection| !replaced.contains(&collection.collection_id));
+        let pending = pending
+            .into_iter()
+            .cloned()
+            .filter(|collection| collection.grant.is_none() && !collection.is_tombstone(api_key));
+
+        sealed
+            .chain(pending)
+            .find(|collection| !is_revised(collection) && collection.matches_label(api_key, label))
+            .map(|collection| collection.collection_id)
+    }
+
+    /// Find all user collections annotated with their version number and head status
+    pub fn find_collection_revisions(&self, api_key: &[u8]) -> Vec<CollectionRevision> {
+        Self::annotate_revisions(self.find_collections_by_owner(api_key))
+    }
+
+    /// Find one page of the user's collections, newest first
+    /// Applies the same exclusions as `find_collections_by_owner` but stops scanning blocks once
+    /// `offset + limit` collections have been found
+    pub fn find_collections_by_owner_paged(
+        &self,
+        api_key: &[u8],
+        offset: usize,
+        limit: usize,
+    ) -> Vec<EncryptedCollection> {
+        self.live_collections_newest_first(api_key)
+            .skip(offset)
+            .take(limit)
+            .collect()
+    }
+
+    /// Find one page of the user's collections (newest first) with version number and head status
+    /// Superseded revisions are skipped unless `include_history`. Versions count older
+    /// revisions, so the scan continues past the page only until the page's ancestors are found
+    pub fn find_collection_revisions_paged(
+        &self,
+        api_key: &[u8],
+        include_history: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Vec<CollectionRevision> {
+        if limit == 0 {
+            return Vec::new();
+        }
+        let mut live = self.live_collections_newest_first(api_key);
+        let mut parents: HashMap<String, Option<String>> = HashMap::new();
+        let mut superseded: HashSet<String> = HashSet::new();
+        let mut page = Vec::new();
+        let mut skipped = 0;
+
+        // Children are always newer than their parents, so they are seen first
+        for collection in live.by_ref() {
+            let is_head = !superseded.contains(&collection.collection_id);
+            parents.insert(
+                collection.collection_id.clone(),
+                collection.parent_collection_id.clone(),
+            );
+            if let Some(parent_id) = &collection.parent_collection_id {
+                superseded.insert(parent_id.clone());
+            }
+
+            if !include_history && !is_head {
+                continue;
+            }
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            page.push((collection, is_head));
+            if page.len() >= limit {
+                break;
+            }
+        }
+
+        // Ancestors not seen yet are older; keep scanning only until they are all found
+        loop {
+            let missing: HashSet<String> = page
+                .iter()
+                .filter_map(|(collection, _)| {
+                    Self::first_unseen_ancestor(&collection.collection_id, &parents)
+                })
+                .collect();
+            if missing.is_empty() {
+                break;
+            }
+
+            let mut found = false;
+            for collection in live.by_ref() {
+                let collection_id = collection.collection_id.clone();
+                parents.insert(collection_id.clone(), collection.parent_collection_id);
+                if missing.contains(&collection_id) {
+                    found = true;
+                    break;
+                }
+            }
+            // Exhausted: the missing ancestors were deleted, so the chains stop there
+            if !found {
+                break;
+            }
+        }
+
+        page.into_iter()
+            .map(|(collection, is_head)| {
+                let mut version = 1;
+                let mut current = collection.parent_collection_id.as_deref();
+                // Bounded by the number of known collections to guard against cycles
+                while let Some(parent_id) = current.filter(|id| parents.contains_key(*id)) {
+                    if version > parents.len() {
+                        break;
+                    }
+                    version += 1;
+                    current = parents[parent_id].as_deref();
+                }
+                CollectionRevision {
+                    collection,
+                    version,
+                    is_head,
+                }
+            })
+            .collect()
+    }
+
+    /// First ancestor of `collection_id` that is missing from `parents` (None once the chain
+    /// reaches a collection without a parent)
+    fn first_unseen_ancestor(
+        collection_id: &str,
+        parents: &HashMap<String, Option<String>>,
+    ) -> Option<String> {
+        let mut current = parents.get(collection_id)?.as_deref();
+        let mut steps = 0;
+        while let Some(parent_id) = current {
+            match parents.get(parent_id) {
+                Some(_) if steps > parents.len() => return None,
+                Some(grandparent) => current = grandparent.as_deref(),
+                None => return Some(parent_id.to_string()),
+            }
+            steps += 1;
+        }
+        None
+    }
+
+    /// Annotate collections (in chain order) with their version number and head status
+    /// Only parents present in `collections` are counted towards a revision's version
+    pub fn annotate_revisions(collections: Vec<EncryptedCollection>) -> Vec<CollectionRevision> {
+        let mut positions: HashMap<&str, usize> = HashMap::new();
+        let mut versions = Vec::with_capacity(collections.len());
+        let mut superseded: HashSet<&str> = HashSet::new();
+
+        // Collections are in chain order, so a parent is always versioned before its children
+        for (position, collection) in collections.iter().enumerate() {
+            let parent = collection
+                .parent_collection_id
+                .as_deref()
+                .and_then(|id| positions.get(id).map(|&p| (id, p)));
+
+            let version = match parent {
+                Some((parent_id, parent_position)) => {
+                    superseded.insert(parent_id);
+                    versions[parent_position] + 1
+                }
+                None => 1,
+            };
+
+            positions.insert(collection.collection_id.as_str(), position);
+            versions.push(version);
+        }
+
+        let heads: Vec<bool> = collections
+            .iter()
+            .map(|c| !superseded.contains(c.collection_id.as_str()))
+            .collect();
+
+        collections
+            .into_iter()
+            .zip(versions)
+            .zip(heads)
+            .map(|((collection, version), is_head)| CollectionRevision {
+                collection,
+                version,
+                is_head,
+            })
+            .collect()
+    }
+
+    /// Find the newest revision in the version chain containing `label_or_id`, with its version
+    /// Accepts any revision's collection ID, or a label (resolved to its newest collection)
+    pub fn find_head_revision(
+        &self,
+        label_or_id: &str,
+        api_key: &[u8],
+    ) -> Option<CollectionRevision> {
+        let collections = self.find_collections_by_owner(api_key);
+        let head = Self::latest_revision_position(&collections, label_or_id, api_key)?;
+        Self::annotate_revisions(collections).into_iter().nth(head)
+    }
+
+    /// Position in `collections` (chain order) of the head of the chain containing `label_or_id`
+    fn latest_revision_position(
+        collections: &[EncryptedCollection],
+        label_or_id: &str,
+        api_key: &[u8],
+    ) -> Option<usize> {
+        let start = collections
+            .iter()
+            .position(|c| c.collection_id == label_or_id)
+            .or_else(|| {
+                collections.iter().rposition(|c| {
+                    c.decrypt_metadata(api_key)
+                        .ok()
+                        .is_some_and(|metadata| metadata["label"].as_str() == Some(label_or_id))
+                })
+            })?;
+
+        // Map each parent to its newest child (later collections overwrite earlier forks)
+        let mut newest_child: HashMap<&str, usize> = HashMap::new();
+        for (position, collection) in collections.iter().enumerate() {
+            if let Some(parent_id) = collection.parent_collection_id.as_deref() {
+                newest_child.insert(parent_id, position);
+            }
+        }
+
+        // Children always come later in the chain, which also guards against cycles
+        let mut current = start;
+        while let Some(&next) = newest_child.get(collections[current].collection_id.as_str()) {
+            if next <= current {
+                break;
+            }
+            current = next;
+        }
+
+        Some(current)
+    }
+
+    /// Scan all blocks for collections owned by user (including tombstones)
+    fn scan_collections_by_owner(
+        &self,
+        api_key: &[u8],
+        api_key_hash: &str,
+    ) -> Vec<EncryptedCollection> {
+        let mut results = Vec::new();
+
+        for block in &self.chain {
+            if self.may_contain_owner(block, api_key_hash) {
+                // Get collections from envelope
+                if let Ok(collections) = self.full_block(block).get_collections_by_owner(api_key) {
+                    results.extend(collections);
+                }
+            }
+        }
+
+        results
+    }
+
+    /// Lazily scan blocks newest first for the user's live collections
+    /// Excludes the same records as `find_collections_by_owner`. Tombstones are always newer than
+    /// the collections they delete, so they are seen first. Within a block, collections are
+    /// ordered by created_at then collection_id (both descending) so pages are stable.
+    fn live_collections_newest_first<'a>(
+        &'a self,
+        api_key: &'a [u8],
+    ) -> impl Iterator<Item = EncryptedCollection> + 'a {
+        let api_key_hash = hash_api_key_hex(api_key);
+        let now = Utc::now().timestamp();
+        let mut tombstoned: HashSet<String> = HashSet::new();
+
+        self.chain.iter().rev().flat_map(move |block| {
+            if !self.may_contain_owner(block, &api_key_hash) {
+                return Vec::new();
+            }
+            let Ok(mut collections) = self.full_block(block).get_collections_by_owner(api_key)
+            else {
+                return Vec::new();
+            };
+
+            // A tombstone may share a block with its target
+            for collection in &collections {
+                if collection.is_tombstone(api_key) {
+                    tombstoned.extend(collection.tombstone_target(api_key));
+                }
+            }
+            collections.retain(|collection| {
+                !tombstoned.contains(&collection.collection_id)
+                    && collection.grant.is_none()
+                    && !collection.is_tombstone(api_key)
+                    && !collection.is_expired(api_key, now)
+            });
+            collections.sort_by_cached_key(|collection| {
+                let created_at = collection
+                    .decrypt_metadata(api_key)
+                    .ok()
+                    .and_then(|metadata| metadata["created_at"].as_i64())
+                    .unwrap_or(0);
+                std::cmp::Reverse((created_at, collection.collection_id.clone()))
+            });
+            collections
+        })
+    }
+
+    /// Whether a block may hold envelopes owned by `api_key_hash`
+    /// Blocks stored without blind indexes fall back to the node-local backfill when present
+    fn may_contain_owner(&self, block: &Block, api_key_hash: &str) -> bool {
+        match self.legacy_blind_indexes.get(&block.hash) {
+            Some(indexes) if block.blind_indexes.is_empty() => {
+                generate_account_blind_index_with_salt(api_key_hash, &block.block_salt)
+                    .is_ok_and(|idx| indexes.contains(&idx))
+            }
+            _ => block.may_contain_owner(api_key_hash),
+        }
+    }
+
+    /// Build the set of collection IDs targeted by valid tombstones
+    /// A tombstone only counts if it belongs to the same owner_api_key_hash and its
+    /// MAC verifies with the owner's API key (proves the owner created it)
+    fn tombstoned_ids(
+        collections: &[EncryptedCollection],
+        api_key: &[u8],
+        api_key_hash: &str,
+    ) -> HashSet<String> {
+        let owned_ids: HashSet<&str> = collections
+            .iter()
+            .filter(|c| c.owner_api_key_hash == api_key_hash)
+            .map(|c| c.collection_id.as_str())
+            .collect();
+
+        collections
+            .iter()
+            .filter(|c| c.owner_api_key_hash == api_key_hash && c.is_tombstone(api_key))
+            .filter_map(|c| c.tombstone_target(api_key))
+            .filter(|target| owned_ids.contains(target.as_str()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn test_validator_config() -> crate::config::ValidatorConfig {
+        let mut node_to_validator = HashMap::new();
+        node_to_validator.insert("test-node".to_string(), "Validator_1".to_string());
+        node_to_validator.insert("node2".to_string(), "Validator_2".to_string());
+
+        let mut validator_to_address = HashMap::new();
+        validator_to_address.insert("Validator_1".to_string(), "node1:8080".to_string());
+        validator_to_address.insert("Validator_2".to_string(), "node2:8080".to_string());
+
+        crate::config::ValidatorConfig {
+            validators: vec!["Validator_1".to_string(), "Validator_2".to_string()],
+            node_to_validator,
+            validator_to_address,
+            validator_public_keys: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_blockchain_creation() {
+        let blockchain = Blockchain::new("test-node".to_string(), test_validator_config()).unwrap();
+        assert_eq!(blockchain.chain.len(), 1);
+        assert!(blockchain.is_valid().is_ok());
+    }
+
+    #[test]
+    fn test_genesis_timestamp_gives_reproducible_genesis() {
+        let a =
+            Blockchain::with_genesis_timestamp("node-a".to_string(), test_validator_config(), 0)
+                .unwrap();
+        let b =
+            Blockchain::with_genesis_timestamp("node-b".to_string(), test_validator_config(), 0)
+                .unwrap();
+        assert_eq!(a.chain[0].timestamp, 0);
+        assert_eq!(a.genesis_hash(), b.genesis_hash());
+        assert!(a.is_valid().is_ok());
+
+        let default = Blockchain::new("node-a".to_string(), test_validator_config()).unwrap();
+        assert_eq!(default.chain[0].timestamp, GENESIS_TIMESTAMP);
+        assert_ne!(default.genesis_hash(), a.genesis_hash());
+    }
+
+    #[test]
+    fn test_future_timestamps_are_checked_against_the_clock() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+        let api_key = crate::crypto::generate_api_key();
+        let account = UserAccount::new(&api_key, &generate_signing_key(), None, None).unwrap();
+        blockchain.add_account_with_key(account, api_key).unwrap();
+        let block_timestamp = blockchain.add_block().unwrap().timestamp;
+
+        // A node whose clock is behind by more than the tolerance sees the block as from the future
+        let clock = std::sync::Arc::new(crate::types::MockClock::new(
+            block_timestamp - TIMESTAMP_TOLERANCE_SECONDS - 1,
+        ));
+        blockchain.clock = std::sync::Arc::clone(&clock).into();
+        assert!(matches!(
+            blockchain.is_valid(),
+            Err(GoudChainError::FutureTimestamp(timestamp)) if timestamp == block_timestamp
+        ));
+
+        clock.advance(1);
+        assert!(blockchain.is_valid().is_ok());
+    }
+
+    #[test]
+    fn test_new_blocks_are_timestamped_after_previous() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+        let add_account_block = |blockchain: &mut Blockchain| {
+            let api_key = crate::crypto::generate_api_key();
+            let account = UserAccount::new(&api_key, &generate_signing_key(), None, None).unwrap();
+            blockchain.add_account_with_key(account, api_key).unwrap();
+            blockchain.add_block().unwrap().timestamp
+        };
+        let first = add_account_block(&mut blockchain);
+
+        // A clock two days behind the previous block still produces an ordered block
+        blockchain.clock =
+            std::sync::Arc::new(crate::types::MockClock::new(first - 2 * 86400)).into();
+        let second = add_account_block(&mut blockchain);
+        assert_eq!(second, first + 1);
+
+        blockchain.clock = SharedClock::default();
+        assert!(blockchain.is_valid().is_ok());
+    }
+
+    #[test]
     fn test_add_block() {
         let blockchain = Blockchain::new("test-node".to_string(), test_validator_config()).unwrap();
         assert_eq!(blockchain.chain.len(), 1); // Genesis block
         assert!(blockchain.is_valid().is_ok());
     }
+
+    #[test]
+    fn test_read_only_mode_blocks_new_blocks() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+
+        let api_key = crate::crypto::generate_api_key();
+        let account = UserAccount::new(&api_key, &generate_signing_key(), None, None).unwrap();
+        blockchain
+            .add_account_with_key(account, api_key.clone())
+            .unwrap();
+
+        blockchain.read_only = true;
+        assert!(matches!(
+            blockchain.ensure_writable(),
+            Err(GoudChainError::ReadOnlyMode)
+        ));
+        assert!(matches!(
+            blockchain.add_block(),
+            Err(GoudChainError::ReadOnlyMode)
+        ));
+        assert_eq!(blockchain.chain.len(), 1);
+
+        // Queued writes land once writes resume
+        blockchain.read_only = false;
+        assert!(blockchain.ensure_writable().is_ok());
+        assert_eq!(blockchain.add_block().unwrap().index, 1);
+    }
+
+    #[test]
+    fn test_seal_due_coalesces_until_interval_or_threshold() {
+        let mut blockchain =
+            Blockchain::new("test-node".to_string(), test_validator_config()).unwrap();
+        let clock = std::sync::Arc::new(crate::types::MockClock::new(1_700_000_000));
+        blockchain.clock = std::sync::Arc::clone(&clock).into();
+        assert!(blockchain.seal_due_now());
+        assert_eq!(blockchain.seconds_until_seal(), None);
+
+        blockchain.min_block_interval_seconds = 10;
+        blockchain.block_seal_threshold = 2;
+        blockchain.last_sealed_at = blockchain.clock.now();
+        clock.advance(9);
+        assert!(!blockchain.seal_due_now());
+        assert_eq!(blockchain.seconds_until_seal(), Some(1));
+        clock.advance(1);
+        assert!(blockchain.seal_due_now());
+        assert_eq!(blockchain.seconds_until_seal(), Some(0));
+
+        // Within the interval, reaching the seal threshold makes a block due
+        blockchain.last_sealed_at = blockchain.clock.now();
+        assert!(!blockchain.seal_due_now());
+
+        let api_key = crate::crypto::generate_api_key();
+        for label in ["first", "second"] {
+            blockchain.pending_collections.push(
+                EncryptedCollection::new(
+                    label.to_string(),
+                    "{}".to_string(),
+                    None,
+                    DEFAULT_CONTENT_TYPE,
+                    None,
+                    &api_key,
+                    hash_api_key_hex(&api_key),
+                    &generate_signing_key(),
+                )
+                .unwrap(),
+            );
+        }
+        assert!(blockchain.seal_due_now());
+    }
+
+    #[test]
+    fn test_add_block_five_validator_rotation() {
+        let validators: Vec<String> = (1..=5).map(|i| format!("Validator_{}", i)).collect();
+        let node_to_validator = (1..=5)
+            .map(|i| (format!("node{}", i), format!("Validator_{}", i)))
+            .collect();
+        let validator_config = crate::config::ValidatorConfig {
+            validators,
+            node_to_validator,
+            validator_to_address: HashMap::new(),
+            validator_public_keys: HashMap::new(),
+        };
+
+        // Block 1 belongs to Validator_2 (1 % 5 = 1)
+        let mut producer = Blockchain::new("node2".to_string(), validator_config.clone()).unwrap();
+        let mut bystander = Blockchain::new("node3".to_string(), validator_config).unwrap();
+
+        let api_key = crate::crypto::generate_api_key();
+        let signing_key = generate_signing_key();
+        let account = UserAccount::new(&api_key, &signing_key, None, None).unwrap();
+
+        bystander
+            .add_account_with_key(account.clone(), api_key.clone())
+            .unwrap();
+        assert!(matches!(
+            bystander.add_block(),
+            Err(GoudChainError::NotAuthorizedValidator { .. })
+        ));
+
+        producer.add_account_with_key(account, api_key).unwrap();
+        let block = producer.add_block().unwrap();
+        assert_eq!(block.index, 1);
+        assert_eq!(block.validator, "Validator_2");
+    }
+
+    #[test]
+    fn test_tombstone_hides_collection() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+
+        let api_key = crate::crypto::generate_api_key();
+        let api_key_hash = hash_api_key_hex(&api_key);
+        let signing_key = generate_signing_key();
+        let account = UserAccount::new(&api_key, &signing_key, None, None).unwrap();
+        blockchain
+            .add_account_with_key(account, api_key.clone())
+            .unwrap();
+
+        let kept = EncryptedCollection::new(
+            "Kept".to_string(),
+            r#"{"value": 1}"#.to_string(),
+            None,
+            "application/json",
+            None,
+            &api_key,
+            api_key_hash.clone(),
+            &signing_key,
+        )
+        .unwrap();
+        let deleted = EncryptedCollection::new(
+            "Deleted".to_string(),
+            r#"{"value": 2}"#.to_string(),
+            None,
+            "application/json",
+            None,
+            &api_key,
+            api_key_hash.clone(),
+            &signing_key,
+        )
+        .unwrap();
+        let kept_id = kept.collection_id.clone();
+        let deleted_id = deleted.collection_id.clone();
+        blockchain.add_collection(kept).unwrap();
+        blockchain.add_collection(deleted).unwrap();
+        blockchain.add_block().unwrap();
+        assert_eq!(blockchain.find_collections_by_owner(&api_key).len(), 2);
+
+        let tombstone =
+            EncryptedCollection::new_tombstone(&deleted_id, &api_key, api_key_hash, &signing_key)
+                .unwrap();
+        blockchain.add_collection(tombstone).unwrap();
+        blockchain.add_block().unwrap();
+
+        assert!(blockchain.is_valid().is_ok());
+        assert!(blockchain
+            .find_collection(&deleted_id, &api_key)
+            .unwrap()
+            .is_none());
+        assert!(blockchain
+            .find_collection(&kept_id, &api_key)
+            .unwrap()
+            .is_some());
+
+        let remaining = blockchain.find_collections_by_owner(&api_key);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].collection_id, kept_id);
+    }
+
+    #[test]
+    fn test_expired_collection_hidden() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+
+        let api_key = crate::crypto::generate_api_key();
+        let api_key_hash = hash_api_key_hex(&api_key);
+        let signing_key = generate_signing_key();
+        let account = UserAccount::new(&api_key, &signing_key, None, None).unwrap();
+        blockchain
+            .add_account_with_key(account, api_key.clone())
+            .unwrap();
+
+        let now = Utc::now().timestamp();
+        let live = EncryptedCollection::new(
+            "Share".to_string(),
+            r#"{"otp": 1}"#.to_string(),
+            None,
+            "application/json",
+            Some(now + 3600),
+            &api_key,
+            api_key_hash.clone(),
+            &signing_key,
+        )
+        .unwrap();
+        let expired = EncryptedCollection::new(
+            "Share".to_string(),
+            r#"{"otp": 2}"#.to_string(),
+            None,
+            "application/json",
+            Some(now - 1),
+            &api_key,
+            api_key_hash,
+            &signing_key,
+        )
+        .unwrap();
+        let live_id = live.collection_id.clone();
+        let expired_id = expired.collection_id.clone();
+        blockchain.add_collection(live).unwrap();
+        blockchain.add_collection(expired).unwrap();
+        blockchain.add_block().unwrap();
+
+        // The expired collection is still on-chain, just no longer returned
+        assert!(blockchain
+            .find_collection(&expired_id, &api_key)
+            .unwrap()
+            .is_none());
+        assert!(blockchain
+            .find_collection(&live_id, &api_key)
+            .unwrap()
+            .is_some());
+
+        let remaining = blockchain.find_collections_by_owner(&api_key);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].collection_id, live_id);
+    }
+
+    #[test]
+    fn test_pruned_collection_reports_pruned() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+
+        let api_key = crate::crypto::generate_api_key();
+        let api_key_hash = hash_api_key_hex(&api_key);
+        let signing_key = generate_signing_key();
+        let account = UserAccount::new(&api_key, &signing_key, None, None).unwrap();
+        blockchain
+            .add_account_with_key(account, api_key.clone())
+            .unwrap();
+
+        let old = EncryptedCollection::new(
+            "Old".to_string(),
+            r#"{"value": 1}"#.to_string(),
+            None,
+            "application/json",
+            None,
+            &api_key,
+            api_key_hash.clone(),
+            &signing_key,
+        )
+        .unwrap();
+        let old_id = old.collection_id.clone();
+        blockchain.add_collection(old).unwrap();
+        blockchain.add_block().unwrap();
+
+        let recent = EncryptedCollection::new(
+            "Recent".to_string(),
+            r#"{"value": 2}"#.to_string(),
+            None,
+            "application/json",
+            None,
+            &api_key,
+            api_key_hash,
+            &signing_key,
+        )
+        .unwrap();
+        let recent_id = recent.collection_id.clone();
+        blockchain.add_collection(recent).unwrap();
+        blockchain.add_block().unwrap();
+
+        let hash_before = blockchain.chain[1].hash.clone();
+        assert_eq!(blockchain.prune_before(2).unwrap(), 1);
+        assert_eq!(blockchain.chain[1].hash, hash_before);
+
+        assert!(matches!(
+            blockchain.find_collection(&old_id, &api_key),
+            Err(GoudChainError::DataPruned { block_index: 1 })
+        ));
+        assert!(blockchain
+            .find_collection(&recent_id, &api_key)
+            .unwrap()
+            .is_some());
+
+        // Account envelopes survive pruning and the pruned body is exempt from the
+        // merkle check (timestamps are jittered, so ordering may still fail here)
+        assert!(blockchain.find_account(&api_key).is_some());
+        assert!(!matches!(
+            blockchain.is_valid(),
+            Err(GoudChainError::InvalidMerkleRoot(_))
+        ));
+    }
+
+    #[test]
+    fn test_offloaded_blocks_load_on_demand() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+
+        let api_key = crate::crypto::generate_api_key();
+        let api_key_hash = hash_api_key_hex(&api_key);
+        let signing_key = generate_signing_key();
+        let account = UserAccount::new(&api_key, &signing_key, None, None).unwrap();
+        blockchain
+            .add_account_with_key(account, api_key.clone())
+            .unwrap();
+        let mut collection_ids = Vec::new();
+        for value in 1..=2 {
+            let collection = EncryptedCollection::new(
+                format!("Value {}", value),
+                format!(r#"{{"value": {}}}"#, value),
+                None,
+                "application/json",
+                None,
+                &api_key,
+                api_key_hash.clone(),
+                &signing_key,
+            )
+            .unwrap();
+            collection_ids.push(collection.collection_id.clone());
+            blockchain.add_collection(collection).unwrap();
+            blockchain.add_block().unwrap();
+        }
+
+        // Nothing is offloaded past the latest checkpoint
+        assert_eq!(blockchain.offload_boundary(1), 0);
+        blockchain.create_checkpoint().unwrap();
+        assert_eq!(blockchain.offload_boundary(1), 2);
+        assert_eq!(blockchain.offload_boundary(10), 0);
+
+        // Without a loader the bodies stay in memory
+        assert_eq!(blockchain.offload_before(2), 0);
+        assert!(!blockchain.chain[1].is_offloaded());
+
+        let stored = blockchain.chain.clone();
+        blockchain.block_loader =
+            BlockLoader::new(move |index| Ok(stored.get(index as usize).cloned()));
+        let hash_before = blockchain.chain[1].hash.clone();
+        let body_before = blockchain.chain[1].encrypted_block_data.clone();
+        assert_eq!(blockchain.offload_before(2), 1);
+        assert!(blockchain.chain[1].is_offloaded());
+        assert!(!blockchain.chain[0].is_offloaded()); // Genesis stays in memory
+        assert_eq!(blockchain.chain[1].hash, hash_before);
+
+        // Lookups read the offloaded body back from the store
+        assert!(blockchain.find_account(&api_key).is_some());
+        assert!(blockchain
+            .find_collection(&collection_ids[0], &api_key)
+            .unwrap()
+            .is_some());
+        assert_eq!(blockchain.find_collections_by_owner(&api_key).len(), 2);
+        assert_eq!(blockchain.count_aggregates().total_collections, 2);
+        assert_eq!(blockchain.full_chain()[1].encrypted_block_data, body_before);
+        assert!(blockchain.is_valid().is_ok());
+
+        // Adopting a longer chain keeps the offloaded prefix header-only
+        let mut theirs = blockchain.clone();
+        theirs.chain = theirs.full_chain();
+        push_linked_block(&mut theirs, 1);
+        assert!(blockchain.replace_chain(theirs.chain.clone()).unwrap());
+        assert_eq!(blockchain.chain.len(), 4);
+        assert!(blockchain.chain[1].is_offloaded());
+    }
+
+    #[test]
+    fn test_account_public_key_lookup() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+
+        let api_key = crate::crypto::generate_api_key();
+        let signing_key = generate_signing_key();
+        let request_key = crate::crypto::get_public_key_hex(&generate_signing_key());
+        let account =
+            UserAccount::new(&api_key, &signing_key, None, Some(request_key.clone())).unwrap();
+        let account_id = account.account_id.clone();
+        blockchain
+            .add_account_with_key(account, api_key.clone())
+            .unwrap();
+        blockchain.add_block().unwrap();
+
+        let entry = blockchain.find_account_public_keys(&account_id).unwrap();
+        assert_eq!(entry.request_public_key, Some(request_key));
+        assert_eq!(
+            entry.sharing_public_key,
+            crate::crypto::sharing_public_key_hex(&api_key)
+        );
+        assert!(blockchain.find_account_public_keys("unknown").is_none());
+    }
+
+    #[test]
+    fn test_account_metadata_revision_wins() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+
+        let api_key = crate::crypto::generate_api_key();
+        let account = UserAccount::new(&api_key, &generate_signing_key(), None, None).unwrap();
+        blockchain
+            .add_account_with_key(account.clone(), api_key.clone())
+            .unwrap();
+        blockchain.add_block().unwrap();
+
+        let revision = account
+            .with_metadata(
+                &api_key,
+                &generate_signing_key(),
+                Some(serde_json::json!({"username": "alice"})),
+            )
+            .unwrap();
+        blockchain
+            .add_account_with_key(revision.clone(), api_key.clone())
+            .unwrap();
+        let block = blockchain.add_block().unwrap();
+
+        let found = blockchain.find_account(&api_key).unwrap();
+        assert_eq!(found.account_id, account.account_id);
+        assert_eq!(found.metadata_encrypted, revision.metadata_encrypted);
+        assert_eq!(found.updated_at, revision.updated_at);
+
+        // The revision is not counted as another account
+        assert_eq!(block.get_account_count().unwrap(), 0);
+
+        // The full-scan lookup agrees with the early-exit one
+        let api_key_hash = hash_api_key_hex(&api_key);
+        let uniform = blockchain
+            .find_account_uniform(&api_key, &api_key_hash)
+            .unwrap();
+        assert_eq!(uniform.metadata_encrypted, revision.metadata_encrypted);
+        let unknown_key = crate::crypto::generate_api_key();
+        assert!(blockchain
+            .find_account_uniform(&unknown_key, &hash_api_key_hex(&unknown_key))
+            .is_none());
+    }
+
+    #[test]
+    fn test_bloom_filter_skips_other_owners() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+
+        let alice_key = crate::crypto::generate_api_key();
+        let bob_key = crate::crypto::generate_api_key();
+        let signing_key = generate_signing_key();
+        for key in [&alice_key, &bob_key] {
+            let account = UserAccount::new(key, &signing_key, None, None).unwrap();
+            blockchain
+                .add_account_with_key(account, key.clone())
+                .unwrap();
+            blockchain.add_block().unwrap();
+        }
+
+        let alice_hash = hash_api_key_hex(&alice_key);
+        let bob_hash = hash_api_key_hex(&bob_key);
+        let alice_block = &blockchain.chain[1];
+        let bob_block = &blockchain.chain[2];
+        assert_eq!(alice_block.blind_indexes.len(), 1);
+        assert!(alice_block.may_contain_owner(&alice_hash));
+        assert!(!alice_block.may_contain_owner(&bob_hash));
+        assert!(bob_block.may_contain_owner(&bob_hash));
+        assert!(!bob_block.may_contain_owner(&alice_hash));
+
+        // Genesis has no filter and is always searched
+        assert!(blockchain.chain[0].may_contain_owner(&alice_hash));
+
+        assert!(blockchain.find_account(&alice_key).is_some());
+        assert!(blockchain.find_account(&bob_key).is_some());
+    }
+
+    #[test]
+    fn test_blind_index_lookup_skips_other_owners_blocks() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+
+        // The target account sits in the oldest block, followed by many blocks of another owner
+        let signing_key = generate_signing_key();
+        let target = crate::crypto::generate_api_key();
+        let account = UserAccount::new(&target, &signing_key, None, None).unwrap();
+        blockchain
+            .add_account_with_key(account, target.clone())
+            .unwrap();
+        blockchain.add_block().unwrap();
+
+        let other_key = crate::crypto::generate_api_key();
+        let other_hash = hash_api_key_hex(&other_key);
+        for _ in 0..24 {
+            let collection = EncryptedCollection::new(
+                "Notes".to_string(),
+                r#"{"text": "hello"}"#.to_string(),
+                None,
+                "application/json",
+                None,
+                &other_key,
+                other_hash.clone(),
+                &signing_key,
+            )
+            .unwrap();
+            blockchain.add_collection(collection).unwrap();
+            blockchain.add_block().unwrap();
+        }
+
+        // Without a filter every block costs a decrypt attempt; count the blocks a lookup opens
+        let target_hash = hash_api_key_hex(&target);
+        let decrypt_attempts = |chain: &Blockchain| {
+            assert!(chain.find_account(&target).is_some());
+            chain
+                .chain
+                .iter()
+                .filter(|block| chain.may_contain_owner(block, &target_hash))
+                .count()
+        };
+
+        // Lazy: the same blocks stored without blind indexes
+        let mut lazy = blockchain.clone();
+        for block in &mut lazy.chain {
+            block.blind_indexes.clear();
+        }
+
+        // Backfilled: what the materialize_blind_indexes migration provides for lazy blocks
+        let mut backfilled = lazy.clone();
+        backfilled.legacy_blind_indexes = backfilled
+            .chain
+            .iter()
+            .map(|block| (block.hash.clone(), block.owner_blind_indexes().unwrap()))
+            .collect();
+
+        // Genesis and the account's block are always opened; the 24 other blocks only on a
+        // filter false positive
+        let lazy_attempts = decrypt_attempts(&lazy);
+        assert_eq!(lazy_attempts, 26);
+        assert!(decrypt_attempts(&blockchain) < lazy_attempts / 2);
+        assert!(decrypt_attempts(&backfilled) < lazy_attempts / 2);
+    }
+
+    #[test]
+    fn test_exact_blind_indexes_option() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+        blockchain.exact_blind_indexes = true;
+
+        let alice_key = crate::crypto::generate_api_key();
+        let bob_key = crate::crypto::generate_api_key();
+        let signing_key = generate_signing_key();
+        let account = UserAccount::new(&alice_key, &signing_key, None, None).unwrap();
+        blockchain
+            .add_account_with_key(account, alice_key.clone())
+            .unwrap();
+        blockchain.add_block().unwrap();
+
+        let block = &blockchain.chain[1];
+        assert_eq!(block.blind_indexes, block.owner_blind_indexes().unwrap());
+        assert!(block.may_contain_owner(&hash_api_key_hex(&alice_key)));
+        assert!(!block.may_contain_owner(&hash_api_key_hex(&bob_key)));
+        assert!(blockchain.find_account(&alice_key).is_some());
+        assert!(blockchain.find_account(&bob_key).is_none());
+    }
+
+    #[test]
+    fn test_share_grant_and_revoke() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+
+        let owner_key = crate::crypto::generate_api_key();
+        let owner_hash = hash_api_key_hex(&owner_key);
+        let recipient_key = crate::crypto::generate_api_key();
+        let signing_key = generate_signing_key();
+        for key in [&owner_key, &recipient_key] {
+            let account = UserAccount::new(key, &signing_key, None, None).unwrap();
+            blockchain
+                .add_account_with_key(account, key.clone())
+                .unwrap();
+        }
+
+        let source = EncryptedCollection::new(
+            "Notes".to_string(),
+            r#"{"text": "hello"}"#.to_string(),
+            None,
+            "application/json",
+            None,
+            &owner_key,
+            owner_hash.clone(),
+            &signing_key,
+        )
+        .unwrap();
+        let source_id = source.collection_id.clone();
+        let recipient_public_key = crate::crypto::sharing_public_key_hex(&recipient_key);
+        let grant = EncryptedCollection::new_grant(
+            &source,
+            &recipient_public_key,
+            &owner_key,
+            owner_hash.clone(),
+            &signing_key,
+        )
+        .unwrap();
+        let grant_id = grant.collection_id.clone();
+        blockchain.add_collection(source).unwrap();
+        blockchain.add_collection(grant).unwrap();
+        blockchain.add_block().unwrap();
+
+        let shared = blockchain.find_shared_collections(&recipient_key);
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].grant_id, grant_id);
+        assert_eq!(shared[0].collection_id, source_id);
+        assert_eq!(shared[0].label, "Notes");
+        assert_eq!(shared[0].data, r#"{"text": "hello"}"#);
+
+        // The grant is not one of the owner's collections, and nobody else sees it
+        assert_eq!(blockchain.find_collections_by_owner(&owner_key).len(), 1);
+        assert!(blockchain
+            .find_collection(&grant_id, &owner_key)
+            .unwrap()
+            .is_none());
+        assert!(blockchain.find_shared_collections(&owner_key).is_empty());
+
+        let grant = blockchain.find_grant(&grant_id, &owner_key).unwrap();
+        assert!(blockchain.find_grant(&grant_id, &recipient_key).is_none());
+        let revocation =
+            EncryptedCollection::new_grant_revocation(&grant, &owner_key, owner_hash, &signing_key)
+                .unwrap()
+                .unwrap();
+        blockchain.add_collection(revocation).unwrap();
+        blockchain.add_block().unwrap();
+
+        assert!(blockchain
+            .find_shared_collections(&recipient_key)
+            .is_empty());
+        assert!(blockchain.find_grant(&grant_id, &owner_key).is_none());
+        assert!(blockchain
+            .find_collection(&source_id, &owner_key)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_key_rotation_moves_account_and_collections() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+
+        let owner_key = crate::crypto::generate_api_key();
+        let owner_hash = hash_api_key_hex(&owner_key);
+        let recipient_key = crate::crypto::generate_api_key();
+        let signing_key = generate_signing_key();
+        for key in [&owner_key, &recipient_key] {
+            let account = UserAccount::new(key, &signing_key, None, None).unwrap();
+            blockchain
+                .add_account_with_key(account, key.clone())
+                .unwrap();
+        }
+
+        let source = EncryptedCollection::new(
+            "Notes".to_string(),
+            r#"{"text": "hello"}"#.to_string(),
+            None,
+            "application/json",
+            None,
+            &owner_key,
+            owner_hash.clone(),
+            &signing_key,
+        )
+        .unwrap();
+        let source_id = source.collection_id.clone();
+        let grant = EncryptedCollection::new_grant(
+            &source,
+            &crate::crypto::sharing_public_key_hex(&recipient_key),
+            &owner_key,
+            owner_hash.clone(),
+            &signing_key,
+        )
+        .unwrap();
+        let grant_id = grant.collection_id.clone();
+        blockchain.add_collection(source).unwrap();
+        blockchain.add_collection(grant).unwrap();
+        blockchain.add_block().unwrap();
+
+        let account = blockchain.find_account(&owner_key).unwrap();
+        let new_key = crate::crypto::generate_api_key();
+        let rotation = blockchain
+            .queue_key_rotation(&account, &owner_key, new_key.clone(), &signing_key)
+            .unwrap();
+        blockchain.add_block().unwrap();
+        assert_eq!(rotation.reencrypted_collections, 1);
+        assert_eq!(rotation.regranted_shares.len(), 1);
+        assert_eq!(rotation.regranted_shares[0].previous_grant_id, grant_id);
+
+        // The old key no longer resolves; the new key finds the same account
+        assert!(blockchain.find_account(&owner_key).is_none());
+        let rotated = blockchain.find_account(&new_key).unwrap();
+        assert_eq!(rotated.account_id, account.account_id);
+
+        // The collection keeps its ID and moves to the new key
+        let collection = blockchain
+            .find_collection(&source_id, &new_key)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            collection.decrypt_payload(&new_key).unwrap(),
+            r#"{"text": "hello"}"#
+        );
+
+        // The recipient sees exactly one share, under the new grant
+        let shared = blockchain.find_shared_collections(&recipient_key);
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].grant_id, rotation.regranted_shares[0].grant_id);
+        assert_eq!(shared[0].collection_id, source_id);
+        assert_eq!(shared[0].data, r#"{"text": "hello"}"#);
+    }
+
+    #[test]
+    fn test_collection_revisions() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+
+        let api_key = crate::crypto::generate_api_key();
+        let api_key_hash = hash_api_key_hex(&api_key);
+        let signing_key = generate_signing_key();
+        let account = UserAccount::new(&api_key, &signing_key, None, None).unwrap();
+        blockchain
+            .add_account_with_key(account, api_key.clone())
+            .unwrap();
+
+        let first = EncryptedCollection::new(
+            "Profile".to_string(),
+            r#"{"version": 1}"#.to_string(),
+            None,
+            "application/json",
+            None,
+            &api_key,
+            api_key_hash.clone(),
+            &signing_key,
+        )
+        .unwrap();
+        let first_id = first.collection_id.clone();
+        blockchain.add_collection(first).unwrap();
+        blockchain.add_block().unwrap();
+
+        let second = EncryptedCollection::new_revision(
+            first_id.clone(),
+            "Profile".to_string(),
+            r#"{"version": 2}"#.to_string(),
+            None,
+            "application/json",
+            None,
+            &api_key,
+            api_key_hash,
+            &signing_key,
+        )
+        .unwrap();
+        let second_id = second.collection_id.clone();
+        blockchain.add_collection(second).unwrap();
+        blockchain.add_block().unwrap();
+
+        let head = blockchain.find_head_revision(&first_id, &api_key).unwrap();
+        assert_eq!(head.collection.collection_id, second_id);
+        assert_eq!(head.version, 2);
+        let head = blockchain.find_head_revision("Profile", &api_key).unwrap();
+        assert_eq!(head.collection.collection_id, second_id);
+        assert!(blockchain.find_head_revision("Missing", &api_key).is_none());
+
+        let matches = blockchain.find_collections_by_label_index("Profile", &api_key);
+        assert_eq!(matches.len(), 2);
+        assert!(blockchain
+            .find_collections_by_label_index("Missing", &api_key)
+            .is_empty());
+
+        let revisions = blockchain.find_collection_revisions(&api_key);
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].version, 1);
+        assert!(!revisions[0].is_head);
+        assert_eq!(revisions[1].version, 2);
+        assert!(revisions[1].is_head);
+
+        // Paged revisions are newest first and still resolve versions from older blocks
+        let heads = blockchain.find_collection_revisions_paged(&api_key, false, 0, 10);
+        assert_eq!(heads.len(), 1);
+        assert_eq!(heads[0].collection.collection_id, second_id);
+        assert_eq!(heads[0].version, 2);
+        let history = blockchain.find_collection_revisions_paged(&api_key, true, 0, 10);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].collection.collection_id, first_id);
+        assert_eq!(history[1].version, 1);
+        assert!(!history[1].is_head);
+
+        // Only the head holds its label, and revising that head doesn't conflict with it
+        assert_eq!(
+            blockchain.find_label_holder("Profile", &api_key, None),
+            Some(second_id.clone())
+        );
+        assert!(blockchain
+            .find_label_holder("Profile", &api_key, Some(&second_id))
+            .is_none());
+        assert!(blockchain
+            .find_label_holder("Missing", &api_key, None)
+            .is_none());
+
+        // Pending records count before they are sealed
+        let tombstone = EncryptedCollection::new_tombstone(
+            &second_id,
+            &api_key,
+            hash_api_key_hex(&api_key),
+            &signing_key,
+        )
+        .unwrap();
+        blockchain.add_collection(tombstone).unwrap();
+        assert!(blockchain
+            .find_label_holder("Profile", &api_key, None)
+            .is_none());
+        let pending = EncryptedCollection::new(
+            "Profile".to_string(),
+            r#"{"version": 1}"#.to_string(),
+            None,
+            "application/json",
+            None,
+            &api_key,
+            hash_api_key_hex(&api_key),
+            &signing_key,
+        )
+        .unwrap();
+        let pending_id = pending.collection_id.clone();
+        blockchain.add_collection(pending).unwrap();
+        assert_eq!(
+            blockchain.find_label_holder("Profile", &api_key, None),
+            Some(pending_id)
+        );
+    }
+
+    #[test]
+    fn test_find_collections_by_owner_paged() {
+        let validator_config = crate::config::ValidatorConfig::single("test-node");
+        let mut blockchain = Blockchain::new("test-node".to_string(), validator_config).unwrap();
+
+        let api_key = crate::crypto::generate_api_key();
+        let api_key_hash = hash_api_key_hex(&api_key);
+        let signing_key = generate_signing_key();
+        let account = UserAccount::new(&api_key, &signing_key, None, None).unwrap();
+        blockchain
+            .add_account_with_key(account, api_key.clone())
+            .unwrap();
+
+        // Two blocks of two collections each; the first of them is deleted later
+        for block in 0..2 {
+            for item in 0..2 {
+                let collection = EncryptedCollection::new(
+                    format!("Item-{}-{}", block, item),
+                    r#"{"value": 1}"#.to_string(),
+                    None,
+                    "application/json",
+                    None,
+                    &api_key,
+                    api_key_hash.clone(),
+                    &signing_key,
+                )
+                .unwrap();
+                blockchain.add_collection(collection).unwrap();
+            }
+            blockchain.add_block().unwrap();
+        }
+        let all = blockchain.find_collections_by_owner(&api_key);
+        let deleted_id = all[0].collection_id.clone();
+        let tombstone =
+            EncryptedCollection::new_tombstone(&deleted_id, &api_key, api_key_hash, &signing_key)
+                .unwrap();
+        blockchain.add_collection(tombstone).unwrap();
+        blockchain.add_block().unwrap();
+
+        let newest_first = blockchain.find_collections_by_owner_paged(&api_key, 0, 10);
+        assert_eq!(newest_first.len(), 3);
+        assert!(newest_first.iter().all(|c| c.collection_id != deleted_id));
+        // Newest block first
+        assert!(newest_first[..2]
+            .iter()
+            .all(|c| all[2..].iter().any(|n| n.collection_id == c.collection_id)));
+
+        // Pages are stable and do not overlap
+        let first_page = blockchain.find_collections_by_owner_paged(&api_key, 0, 2);
+        let second_page = blockchain.find_collections_by_owner_paged(&api_key, 2, 2);
+        let paged_ids: Vec<&str> = first_page
+            .iter()
+            .chain(&second_page)
+            .map(|c| c.collection_id.as_str())
+            .collect();
+        let expected_ids: Vec<&str> = newest_first
+            .iter()
+            .map(|c| c.collection_id.as_str())
+            .collect();
+        assert_eq!(paged_ids, expected_ids);
+        assert!(blockchain
+            .find_collections_by_owner_paged(&api_key, 3, 2)
+            .is_empty());
+    }
+
+    /// Append an empty block that follows the validator rotation
+    fn push_linked_block(blockchain: &mut Blockchain, seconds_after: i64) {
+        let previous = blockchain.chain.last().unwrap().clone();
+        let index = previous.index + 1;
+        let mut block = Block::new(BlockConfig {
+            index,
+            account_envelopes: Vec::new(),
+            collection_envelopes: Vec::new(),
+            previous_hash: previous.hash.clone(),
+            validator: blockchain.validator_config.get_validator_for_block(index),
+            blind_indexes: Vec::new(),
+            block_salt: generate_block_salt(),
+        })
+        .unwrap();
+        // Jittered timestamps may run backwards; keep them ordered for validation
+        block.timestamp = previous.timestamp + seconds_after;
+        block.hash = block.calculate_hash();
+        blockchain.chain.push(block);
+    }
+
+    fn headers(blockchain: &Blockchain) -> Vec<BlockHeader> {
+        blockchain.chain.iter().map(Block::header).collect()
+    }
+
+    #[test]
+    fn test_find_fork_point_locates_divergence() {
+        let mut ours = Blockchain::new("test-node".to_string(), test_validator_config()).unwrap();
+        push_linked_block(&mut ours, 1);
+        let mut theirs = ours.clone();
+        push_linked_block(&mut ours, 1);
+        for _ in 0..3 {
+            push_linked_block(&mut theirs, 2);
+        }
+
+        assert_eq!(ours.find_fork_point(&headers(&theirs)).unwrap(), 2);
+        // A peer that only extends our chain forks right after our tip
+        assert_eq!(theirs.find_fork_point(&headers(&theirs)[..3]).unwrap(), 3);
+        assert_eq!(ours.header_sync_anchor(), 0);
+    }
+
+    #[test]
+    fn test_checkpoint_interval_and_on_demand_checkpoint() {
+        let mut ours = Blockchain::new("test-node".to_string(), test_validator_config()).unwrap();
+        ours.checkpoint_interval = 3;
+        assert!(!ours.is_checkpoint_index(0));
+        assert!(ours.is_checkpoint_index(3));
+        assert!(!ours.is_checkpoint_index(4));
+
+        push_linked_block(&mut ours, 1);
+        push_linked_block(&mut ours, 1);
+        let tip = ours.create_checkpoint().unwrap();
+        assert_eq!(tip.index, 2);
+        assert_eq!(ours.checkpoints, vec![tip.hash.clone()]);
+        assert_eq!(ours.header_sync_anchor(), 2);
+
+        // Checkpointing the same tip again is a no-op
+        ours.create_checkpoint().unwrap();
+        assert_eq!(ours.checkpoints.len(), 1);
+
+        // Adopting a longer chain keeps the on-demand checkpoint and adds interval ones
+        let mut theirs = ours.clone();
+        push_linked_block(&mut theirs, 1);
+        push_linked_block(&mut theirs, 1);
+        assert!(ours.replace_chain(theirs.chain.clone()).unwrap());
+        assert_eq!(
+            ours.checkpoints,
+            vec![tip.hash, theirs.chain[3].hash.clone()]
+        );
+    }
+
+    #[test]
+    fn test_replace_chain_enforces_reorg_limits() {
+        let mut ours = Blockchain::new("test-node".to_string(), test_validator_config()).unwrap();
+        for _ in 0..4 {
+            push_linked_block(&mut ours, 1);
+        }
+
+        // Longer fork from genesis rolls back all four of our blocks
+        let mut theirs = Blockchain::new("test-node".to_string(), test_validator_config()).unwrap();
+        for _ in 0..5 {
+            push_linked_block(&mut theirs, 2);
+        }
+        assert_eq!(ours.fork_index(&theirs.chain), 1);
+        assert_eq!(ours.reorg_depth(&theirs.chain), 4);
+
+        ours.max_reorg_depth = 3;
+        assert!(!ours.replace_chain(theirs.chain.clone()).unwrap());
+
+        // Within the limit, but the fork would replace a checkpointed block
+        ours.max_reorg_depth = 10;
+        ours.checkpoints = vec![ours.chain[2].hash.clone()];
+        assert!(!ours.replace_chain(theirs.chain.clone()).unwrap());
+
+        ours.checkpoints.clear();
+        assert!(ours.replace_chain(theirs.chain.clone()).unwrap());
+        assert_eq!(ours.reorg_depth(&theirs.chain), 0);
+    }
+
+    #[test]
+    fn test_find_fork_point_rejects_broken_link() {
+        let ours = Blockchain::new("test-node".to_string(), test_validator_config()).unwrap();
+        let mut theirs = ours.clone();
+        push_linked_block(&mut theirs, 2);
+        push_linked_block(&mut theirs, 2);
+
+        let mut peer_headers = headers(&theirs);
+        peer_headers[2].previous_hash = "0".repeat(64);
+        peer_headers[2].hash = peer_headers[2].calculate_hash();
+        assert!(matches!(
+            ours.find_fork_point(&peer_headers),
+            Err(GoudChainError::BrokenChain(2))
+        ));
+
+        // Headers must start at a block we hold
+        let mut foreign = headers(&theirs);
+        foreign[0].hash = "f".repeat(64);
+        assert!(matches!(
+            ours.find_fork_point(&foreign),
+            Err(GoudChainError::BrokenChain(0))
+        ));
+    }
+
+    #[test]
+    fn test_find_fork_point_rejects_tampered_header() {
+        let ours = Blockchain::new("test-node".to_string(), test_validator_config()).unwrap();
+        let mut theirs = ours.clone();
+        push_linked_block(&mut theirs, 2);
+
+        let mut peer_headers = headers(&theirs);
+        peer_headers[1].merkle_root = "0".repeat(64);
+        assert!(matches!(
+            ours.find_fork_point(&peer_headers),
+            Err(GoudChainError::InvalidBlockHash(1))
+        ));
+    }
+
+    #[test]
+    fn test_find_fork_point_rejects_wrong_validator() {
+        let ours = Blockchain::new("test-node".to_string(), test_validator_config()).unwrap();
+        let mut theirs = ours.clone();
+        push_linked_block(&mut theirs, 2);
+
+        // Block 1 belongs to Validator_2 in a two-validator rotation
+        let mut peer_headers = headers(&theirs);
+        peer_headers[1].validator = "Validator_1".to_string();
+        peer_headers[1].hash = peer_headers[1].calculate_hash();
+        assert!(matches!(
+            ours.find_fork_point(&peer_headers),
+            Err(GoudChainError::InvalidValidator { index: 1, .. })
+        ));
+    }
 }
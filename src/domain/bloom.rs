@@ -0,0 +1,107 @@
+//! Per-block bloom filter over owner blind indexes
+//!
+//! Lets lookups skip blocks that definitely hold nothing for an owner without
+//! decrypting them. The filter is stored as a single encoded entry in the
+//! block's `blind_indexes` (already covered by the merkle root), so blocks keep
+//! their binary format. False positives just fall through to a decrypt attempt.
+
+use base64::{engine::general_purpose, Engine as _};
+use sha2::{Digest, Sha256};
+
+use crate::constants::{
+    BLOOM_FILTER_BITS_PER_ENTRY, BLOOM_FILTER_HASH_COUNT, BLOOM_FILTER_MIN_BITS,
+    BLOOM_FILTER_PREFIX,
+};
+
+/// Fixed-size bit set with `hash_count` probes per entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilter {
+    bits: Vec<u8>,
+    hash_count: u32,
+}
+
+impl BloomFilter {
+    /// Build a filter sized for `entries` (about 1% false positives)
+    pub fn from_entries<'a>(entries: impl ExactSizeIterator<Item = &'a str>) -> Self {
+        let bit_count = (entries.len() * BLOOM_FILTER_BITS_PER_ENTRY).max(BLOOM_FILTER_MIN_BITS);
+        let mut filter = BloomFilter {
+            bits: vec![0u8; bit_count.div_ceil(8)],
+            hash_count: BLOOM_FILTER_HASH_COUNT,
+        };
+        for entry in entries {
+            for bit in filter.positions(entry) {
+                filter.bits[bit / 8] |= 1 << (bit % 8);
+            }
+        }
+        filter
+    }
+
+    /// False means the entry was definitely not added
+    pub fn might_contain(&self, entry: &str) -> bool {
+        self.positions(entry)
+            .all(|bit| self.bits[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+
+    /// Serialized form stored in `Block::blind_indexes`: `bloom1:{hash_count}:{base64 bits}`
+    pub fn encode(&self) -> String {
+        format!(
+            "{}{}:{}",
+            BLOOM_FILTER_PREFIX,
+            self.hash_count,
+            general_purpose::STANDARD.encode(&self.bits)
+        )
+    }
+
+    /// Parse an entry written by `encode` (None for exact blind indexes or malformed filters)
+    pub fn decode(encoded: &str) -> Option<Self> {
+        let (hash_count, bits) = encoded.strip_prefix(BLOOM_FILTER_PREFIX)?.split_once(':')?;
+        let hash_count = hash_count.parse().ok().filter(|count| *count > 0)?;
+        let bits = general_purpose::STANDARD.decode(bits).ok()?;
+        if bits.is_empty() {
+            return None;
+        }
+        Some(BloomFilter { bits, hash_count })
+    }
+
+    /// Bit positions for an entry (double hashing over SHA-256 of the entry)
+    fn positions(&self, entry: &str) -> impl Iterator<Item = usize> {
+        let digest = Sha256::digest(entry.as_bytes());
+        let h1 = u64::from_le_bytes(digest[..8].try_into().expect("digest is 32 bytes"));
+        let h2 = u64::from_le_bytes(digest[8..16].try_into().expect("digest is 32 bytes")) | 1;
+        let bit_count = (self.bits.len() * 8) as u64;
+        (0..u64::from(self.hash_count))
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % bit_count) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_false_negatives() {
+        let entries: Vec<String> = (0..200).map(|i| format!("owner-{}", i)).collect();
+        let filter = BloomFilter::from_entries(entries.iter().map(String::as_str));
+
+        assert!(entries.iter().all(|entry| filter.might_contain(entry)));
+
+        // Sized for ~1% false positives; allow generous slack
+        let false_positives = (0..10_000)
+            .filter(|i| filter.might_contain(&format!("stranger-{}", i)))
+            .count();
+        assert!(false_positives < 500, "{} false positives", false_positives);
+    }
+
+    #[test]
+    fn test_encode_roundtrip() {
+        let filter = BloomFilter::from_entries(["a", "b"].into_iter());
+        let encoded = filter.encode();
+        assert!(encoded.starts_with(BLOOM_FILTER_PREFIX));
+        assert_eq!(BloomFilter::decode(&encoded), Some(filter));
+
+        // Exact blind indexes and malformed entries are not filters
+        assert!(BloomFilter::decode(&"ab".repeat(32)).is_none());
+        assert!(BloomFilter::decode("bloom1:0:AAAA").is_none());
+        assert!(BloomFilter::decode("bloom1:7:").is_none());
+    }
+}
@@ -0,0 +1,140 @@
+//! Optional zstd compression of collection payloads before encryption
+//!
+//! Ciphertext doesn't compress, so payloads are compressed first and the
+//! collection records which codec was used in a one-byte marker. Collections
+//! written before compression existed carry no marker and decode as `None`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    COMPRESSION_THRESHOLD_BYTES, MAX_DECOMPRESSED_PAYLOAD_BYTES, ZSTD_COMPRESSION_LEVEL,
+};
+use crate::types::{GoudChainError, Result};
+
+/// Codec applied to a payload before encryption (serialized as a single byte)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "u8", try_from = "u8")]
+#[repr(u8)]
+pub enum PayloadCompression {
+    /// Stored as-is (legacy collections and small payloads)
+    #[default]
+    None = 0,
+    /// Compressed with zstd
+    Zstd = 1,
+}
+
+impl PayloadCompression {
+    pub fn is_none(&self) -> bool {
+        *self == Self::None
+    }
+
+    /// Codec name shown to API clients
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Zstd => "zstd",
+        }
+    }
+}
+
+impl From<PayloadCompression> for u8 {
+    fn from(compression: PayloadCompression) -> Self {
+        compression as u8
+    }
+}
+
+impl TryFrom<u8> for PayloadCompression {
+    type Error = String;
+
+    fn try_from(marker: u8) -> std::result::Result<Self, Self::Error> {
+        match marker {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Zstd),
+            other => Err(format!("Unknown payload compression marker: {}", other)),
+        }
+    }
+}
+
+/// Compress `data` according to the caller's hint
+///
+/// - `Some(true)`: always compress
+/// - `Some(false)`: never compress
+/// - `None` (auto): compress payloads of at least `COMPRESSION_THRESHOLD_BYTES`,
+///   keeping the result only if it is actually smaller
+pub fn compress_payload(data: &str, hint: Option<bool>) -> Result<(PayloadCompression, Vec<u8>)> {
+    let should_try = hint.unwrap_or(data.len() >= COMPRESSION_THRESHOLD_BYTES);
+    if !should_try {
+        return Ok((PayloadCompression::None, data.as_bytes().to_vec()));
+    }
+
+    let compressed = zstd::bulk::compress(data.as_bytes(), ZSTD_COMPRESSION_LEVEL)
+        .map_err(|e| GoudChainError::CompressionFailed(e.to_string()))?;
+
+    if hint.is_none() && compressed.len() >= data.len() {
+        return Ok((PayloadCompression::None, data.as_bytes().to_vec()));
+    }
+
+    Ok((PayloadCompression::Zstd, compressed))
+}
+
+/// Reverse `compress_payload` (inflation is capped at the maximum submit size)
+pub fn decompress_payload(compression: PayloadCompression, bytes: Vec<u8>) -> Result<String> {
+    let plaintext = decompress_payload_bytes(compression, bytes)?;
+    String::from_utf8(plaintext).map_err(GoudChainError::Utf8Error)
+}
+
+/// Reverse `compress_payload` without assuming the payload is UTF-8
+pub fn decompress_payload_bytes(
+    compression: PayloadCompression,
+    bytes: Vec<u8>,
+) -> Result<Vec<u8>> {
+    match compression {
+        PayloadCompression::None => Ok(bytes),
+        PayloadCompression::Zstd => zstd::bulk::decompress(&bytes, MAX_DECOMPRESSED_PAYLOAD_BYTES)
+            .map_err(|e| GoudChainError::CompressionFailed(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_compression_threshold() {
+        let small = r#"{"a": 1}"#;
+        let (compression, bytes) = compress_payload(small, None).unwrap();
+        assert_eq!(compression, PayloadCompression::None);
+        assert_eq!(bytes, small.as_bytes());
+
+        let large = format!(r#"{{"notes": "{}"}}"#, "repetitive text ".repeat(200));
+        let (compression, bytes) = compress_payload(&large, None).unwrap();
+        assert_eq!(compression, PayloadCompression::Zstd);
+        assert!(bytes.len() < large.len());
+        assert_eq!(decompress_payload(compression, bytes).unwrap(), large);
+    }
+
+    #[test]
+    fn test_compression_hint_overrides_auto() {
+        let small = r#"{"a": 1}"#;
+        let (compression, bytes) = compress_payload(small, Some(true)).unwrap();
+        assert_eq!(compression, PayloadCompression::Zstd);
+        assert_eq!(decompress_payload(compression, bytes).unwrap(), small);
+
+        let large = "x".repeat(4096);
+        let (compression, _) = compress_payload(&large, Some(false)).unwrap();
+        assert_eq!(compression, PayloadCompression::None);
+    }
+
+    #[test]
+    fn test_marker_serializes_as_single_byte() {
+        assert_eq!(
+            serde_json::to_string(&PayloadCompression::Zstd).unwrap(),
+            "1"
+        );
+        assert_eq!(
+            serde_json::from_str::<PayloadCompression>("0").unwrap(),
+            PayloadCompression::None
+        );
+        assert!(serde_json::from_str::<PayloadCompression>("7").is_err());
+    }
+}
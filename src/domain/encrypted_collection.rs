@@ -1,42 +1,196 @@
 use chrono::Utc;
 use ed25519_dalek::SigningKey;
 use serde::{Deserialize, Serialize};
+use std::time::Instant;
 use uuid::Uuid;
 
-use crate::constants::ENCRYPTION_SALT;
+use super::compression::{compress_payload, decompress_payload_bytes, PayloadCompression};
+use crate::constants::{
+    DEFAULT_CONTENT_TYPE, ENCRYPTION_SALT, GRANT_LABEL_PREFIX, TOMBSTONE_LABEL_PREFIX,
+};
 use crate::crypto::{
-    compute_mac, decrypt_data_with_key, encrypt_data_with_key, get_public_key_hex,
-    global_key_cache, sign_message, verify_mac, verify_signature,
+    compute_mac, constant_time_compare_bytes, decrypt_bytes_with_key, decrypt_data_with_key,
+    encrypt_bytes_with_key, encrypt_data_with_key, generate_label_blind_index, get_public_key_hex,
+    global_key_cache, open_sealed, seal_for_recipient, share_recipient_index, sign_message,
+    verify_mac, verify_signature,
 };
+use crate::types::metrics::{DECRYPT_SECONDS, ENCRYPTION_SECONDS};
 use crate::types::Result;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EncryptedCollection {
     pub collection_id: String,
     pub owner_api_key_hash: String,
-    pub encrypted_metadata: String, // JSON: {label, tags, created_at}
+    pub encrypted_metadata: String, // JSON: {label, tags, created_at, content_type, expires_at?}
     pub encrypted_payload: String,  // JSON array of data items
     pub mac: String,                // HMAC for integrity
     pub nonce: String,              // Nonce used for encryption (hex)
     pub signature: String,
     pub public_key: String,
     pub user_salt: String, // Random per-collection salt (prevents cross-block correlation)
+    /// Previous revision this collection supersedes (None for the first version)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_collection_id: Option<String>,
+    /// Blind index of the label for exact-match search (None for legacy collections)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label_index: Option<String>,
+    /// Codec applied to the payload before encryption (absent = uncompressed)
+    #[serde(default, skip_serializing_if = "PayloadCompression::is_none")]
+    pub compression: PayloadCompression,
+    /// Copy sealed to another account (only on share grants and their revocations)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grant: Option<ShareGrant>,
+}
+
+/// Content sealed to another account's X25519 sharing key
+/// The owner's fields stay encrypted under the owner's API key; this part is for the recipient
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShareGrant {
+    /// Discovery index of the recipient's sharing public key
+    pub recipient_index: String,
+    /// Ephemeral X25519 public key (hex) the content was sealed with
+    pub ephemeral_public_key: String,
+    /// Sealed JSON: the shared collection, or `{"revoked_grant_id"}` on a revocation
+    pub sealed_content: String,
 }
 
 impl EncryptedCollection {
     /// Create a new encrypted collection
     /// Note: Size validation happens at API layer before reaching this function
     /// Encryption adds ~1.33x overhead (base64 encoding + nonce + MAC)
+    /// `compress` forces zstd on or off; `None` compresses payloads over 1 KiB
+    /// `content_type` and `expires_at` are stored in the encrypted metadata
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         label: String,
         data: String,
+        compress: Option<bool>,
+        content_type: &str,
+        expires_at: Option<i64>,
+        api_key: &[u8],
+        api_key_hash: String,
+        signing_key: &SigningKey,
+    ) -> Result<Self> {
+        Self::build(
+            label,
+            data,
+            compress,
+            content_type,
+            expires_at,
+            None,
+            api_key,
+            api_key_hash,
+            signing_key,
+        )
+    }
+
+    /// Create a new revision of an existing collection
+    /// The parent ID is covered by the signature so the version chain can't be re-linked
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_revision(
+        parent_collection_id: String,
+        label: String,
+        data: String,
+        compress: Option<bool>,
+        content_type: &str,
+        expires_at: Option<i64>,
+        api_key: &[u8],
+        api_key_hash: String,
+        signing_key: &SigningKey,
+    ) -> Result<Self> {
+        Self::build(
+            label,
+            data,
+            compress,
+            content_type,
+            expires_at,
+            Some(parent_collection_id),
+            api_key,
+            api_key_hash,
+            signing_key,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn build(
+        label: String,
+        data: String,
+        compress: Option<bool>,
+        content_type: &str,
+        expires_at: Option<i64>,
+        parent_collection_id: Option<String>,
+        api_key: &[u8],
+        api_key_hash: String,
+        signing_key: &SigningKey,
+    ) -> Result<Self> {
+        let started = Instant::now();
+
+        let mut metadata = serde_json::json!({
+            "label": label,
+            "created_at": Utc::now().timestamp(),
+            "content_type": content_type,
+        });
+        if let Some(expires_at) = expires_at {
+            metadata["expires_at"] = serde_json::json!(expires_at);
+        }
+        let (compression, payload_bytes) = compress_payload(&data, compress)?;
+
+        let collection = Self::seal(
+            Uuid::new_v4().to_string(),
+            &metadata,
+            &payload_bytes,
+            compression,
+            parent_collection_id,
+            api_key,
+            api_key_hash,
+            signing_key,
+        )?;
+
+        ENCRYPTION_SECONDS.observe(started.elapsed());
+        Ok(collection)
+    }
+
+    /// Copy of this collection encrypted under `new_api_key` (key rotation)
+    /// Collection ID, revision parent, metadata and payload carry over unchanged
+    pub fn reencrypt(
+        &self,
+        api_key: &[u8],
+        new_api_key: &[u8],
+        new_api_key_hash: String,
+        signing_key: &SigningKey,
+    ) -> Result<Self> {
+        self.verify(Some(api_key))?;
+
+        let metadata = self.decrypt_metadata(api_key)?;
+        let encryption_key = global_key_cache().get_encryption_key(api_key, ENCRYPTION_SALT);
+        let payload_bytes = decrypt_bytes_with_key(&self.encrypted_payload, &encryption_key)?;
+
+        Self::seal(
+            self.collection_id.clone(),
+            &metadata,
+            &payload_bytes,
+            self.compression,
+            self.parent_collection_id.clone(),
+            new_api_key,
+            new_api_key_hash,
+            signing_key,
+        )
+    }
+
+    /// Encrypt metadata and the (already compressed) payload under `api_key`, then sign
+    #[allow(clippy::too_many_arguments)]
+    fn seal(
+        collection_id: String,
+        metadata: &serde_json::Value,
+        payload_bytes: &[u8],
+        compression: PayloadCompression,
+        parent_collection_id: Option<String>,
         api_key: &[u8],
         api_key_hash: String,
         signing_key: &SigningKey,
     ) -> Result<Self> {
         use rand::Rng;
 
-        let collection_id = Uuid::new_v4().to_string();
         let public_key = get_public_key_hex(signing_key);
 
         // Generate random per-user salt (32 bytes)
@@ -49,15 +203,11 @@ impl EncryptedCollection {
         let encryption_key = key_cache.get_encryption_key(api_key, ENCRYPTION_SALT);
         let mac_key = key_cache.get_mac_key(api_key, ENCRYPTION_SALT);
 
-        let metadata = serde_json::json!({
-            "label": label,
-            "created_at": Utc::now().timestamp(),
-        });
-        let metadata_str = serde_json::to_string(&metadata)
+        let metadata_str = serde_json::to_string(metadata)
             .map_err(|e| crate::types::GoudChainError::Internal(e.to_string()))?;
         let (encrypted_metadata, _meta_nonce) =
             encrypt_data_with_key(&metadata_str, &encryption_key)?;
-        let (encrypted_payload, nonce) = encrypt_data_with_key(&data, &encryption_key)?;
+        let (encrypted_payload, nonce) = encrypt_bytes_with_key(payload_bytes, &encryption_key)?;
 
         // Compute MAC over collection_id + encrypted data + user_salt for integrity
         let mac_message = format!(
@@ -66,38 +216,74 @@ impl EncryptedCollection {
         );
         let mac = compute_mac(&mac_key, mac_message.as_bytes());
 
-        // Sign the collection (include user_salt in signature)
-        let signature_message = format!(
-            "{}{}{}{}{}{}",
-            collection_id, api_key_hash, encrypted_metadata, encrypted_payload, mac, user_salt
-        );
-        let signature = sign_message(signature_message.as_bytes(), signing_key);
+        // Tombstones share one index so they can be recognized without decrypting metadata
+        let label = metadata["label"].as_str().unwrap_or_default();
+        let index_label = if label.starts_with(TOMBSTONE_LABEL_PREFIX) {
+            TOMBSTONE_LABEL_PREFIX
+        } else {
+            label
+        };
+        let label_index = generate_label_blind_index(&mac_key, index_label, &user_salt)?;
 
-        Ok(EncryptedCollection {
+        let mut collection = EncryptedCollection {
             collection_id,
             owner_api_key_hash: api_key_hash,
             encrypted_metadata,
             encrypted_payload,
             mac,
             nonce,
-            signature,
+            signature: String::new(),
             public_key,
             user_salt,
-        })
+            parent_collection_id,
+            label_index: Some(label_index),
+            compression,
+            grant: None,
+        };
+
+        // Sign the collection (include user_salt, parent revision and label index in signature)
+        collection.signature = sign_message(collection.signature_message().as_bytes(), signing_key);
+        Ok(collection)
     }
 
-    /// Verify the signature and MAC of this collection
-    pub fn verify(&self, api_key: Option<&[u8]>) -> Result<()> {
-        // Verify signature first (include user_salt)
-        let signature_message = format!(
-            "{}{}{}{}{}{}",
+    /// Build the message covered by the collection signature
+    /// Optional fields are only appended when present, so legacy collections sign the same bytes
+    fn signature_message(&self) -> String {
+        let compression_marker = if self.compression.is_none() {
+            String::new()
+        } else {
+            u8::from(self.compression).to_string()
+        };
+        let grant_marker = self
+            .grant
+            .as_ref()
+            .map(|grant| {
+                format!(
+                    "{}{}{}",
+                    grant.recipient_index, grant.ephemeral_public_key, grant.sealed_content
+                )
+            })
+            .unwrap_or_default();
+
+        format!(
+            "{}{}{}{}{}{}{}{}{}{}",
             self.collection_id,
             self.owner_api_key_hash,
             self.encrypted_metadata,
             self.encrypted_payload,
             self.mac,
-            self.user_salt
-        );
+            self.user_salt,
+            self.parent_collection_id.as_deref().unwrap_or_default(),
+            self.label_index.as_deref().unwrap_or_default(),
+            compression_marker,
+            grant_marker
+        )
+    }
+
+    /// Verify the signature and MAC of this collection
+    pub fn verify(&self, api_key: Option<&[u8]>) -> Result<()> {
+        // Verify signature first (include user_salt, parent revision and label index)
+        let signature_message = self.signature_message();
         verify_signature(
             signature_message.as_bytes(),
             &self.signature,
@@ -128,11 +314,196 @@ impl EncryptedCollection {
             .map_err(|e| crate::types::GoudChainError::Internal(e.to_string()))
     }
 
-    /// Decrypt the payload with the API key
+    /// Decrypt the payload with the API key (fails if it isn't UTF-8)
     pub fn decrypt_payload(&self, api_key: &[u8]) -> Result<String> {
+        String::from_utf8(self.decrypt_payload_bytes(api_key)?)
+            .map_err(crate::types::GoudChainError::Utf8Error)
+    }
+
+    /// Decrypt the payload with the API key, without assuming it is text
+    pub fn decrypt_payload_bytes(&self, api_key: &[u8]) -> Result<Vec<u8>> {
+        let started = Instant::now();
         let key_cache = global_key_cache();
         let encryption_key = key_cache.get_encryption_key(api_key, ENCRYPTION_SALT);
-        decrypt_data_with_key(&self.encrypted_payload, &encryption_key)
+        let payload_bytes = decrypt_bytes_with_key(&self.encrypted_payload, &encryption_key)?;
+        let payload = decompress_payload_bytes(self.compression, payload_bytes)?;
+
+        DECRYPT_SECONDS.observe(started.elapsed());
+        Ok(payload)
+    }
+
+    /// Create a tombstone that logically deletes `target_collection_id`
+    /// The chain stays append-only - the tombstone is just another owner-encrypted collection
+    pub fn new_tombstone(
+        target_collection_id: &str,
+        api_key: &[u8],
+        api_key_hash: String,
+        signing_key: &SigningKey,
+    ) -> Result<Self> {
+        let label = format!("{}{}", TOMBSTONE_LABEL_PREFIX, target_collection_id);
+        let data = serde_json::json!({ "deleted_collection_id": target_collection_id }).to_string();
+        Self::new(
+            label,
+            data,
+            None,
+            DEFAULT_CONTENT_TYPE,
+            None,
+            api_key,
+            api_key_hash,
+            signing_key,
+        )
+    }
+
+    /// Create a share grant: a copy of `source` sealed to `recipient_public_key`
+    /// The owner-side record (label `GRANT:{collection_id}`) remembers the recipient for revocation
+    pub fn new_grant(
+        source: &EncryptedCollection,
+        recipient_public_key: &str,
+        api_key: &[u8],
+        api_key_hash: String,
+        signing_key: &SigningKey,
+    ) -> Result<Self> {
+        let metadata = source.decrypt_metadata(api_key)?;
+        let content = serde_json::json!({
+            "collection_id": source.collection_id,
+            "label": metadata["label"],
+            "content_type": metadata["content_type"],
+            "created_at": metadata["created_at"],
+            "expires_at": metadata["expires_at"],
+            "data": source.decrypt_payload(api_key)?,
+        });
+
+        let label = format!("{}{}", GRANT_LABEL_PREFIX, source.collection_id);
+        let record =
+            serde_json::json!({ "recipient_public_key": recipient_public_key }).to_string();
+        let mut grant = Self::new(
+            label,
+            record,
+            None,
+            DEFAULT_CONTENT_TYPE,
+            None,
+            api_key,
+            api_key_hash,
+            signing_key,
+        )?;
+        grant.seal_grant(recipient_public_key, &content.to_string(), signing_key)?;
+        Ok(grant)
+    }
+
+    /// Revoke a share grant with a tombstone that is also sealed to the grant's recipient,
+    /// so the recipient's reads can see the revocation without the owner's API key
+    /// Returns None if `grant` isn't a share grant owned by `api_key`
+    pub fn new_grant_revocation(
+        grant: &EncryptedCollection,
+        api_key: &[u8],
+        api_key_hash: String,
+        signing_key: &SigningKey,
+    ) -> Result<Option<Self>> {
+        let Some(recipient_public_key) = grant.grant_recipient(api_key) else {
+            return Ok(None);
+        };
+
+        let mut tombstone =
+            Self::new_tombstone(&grant.collection_id, api_key, api_key_hash, signing_key)?;
+        let content = serde_json::json!({ "revoked_grant_id": grant.collection_id }).to_string();
+        tombstone.seal_grant(&recipient_public_key, &content, signing_key)?;
+        Ok(Some(tombstone))
+    }
+
+    /// Attach content sealed to `recipient_public_key` and re-sign (the signature covers it)
+    fn seal_grant(
+        &mut self,
+        recipient_public_key: &str,
+        content: &str,
+        signing_key: &SigningKey,
+    ) -> Result<()> {
+        let (ephemeral_public_key, sealed_content) =
+            seal_for_recipient(recipient_public_key, content)?;
+        self.grant = Some(ShareGrant {
+            recipient_index: share_recipient_index(recipient_public_key),
+            ephemeral_public_key,
+            sealed_content,
+        });
+        self.signature = sign_message(self.signature_message().as_bytes(), signing_key);
+        Ok(())
+    }
+
+    /// Sharing public key an owner-side grant record was sealed to
+    pub fn grant_recipient(&self, api_key: &[u8]) -> Option<String> {
+        self.grant.as_ref()?;
+        let record: serde_json::Value =
+            serde_json::from_str(&self.decrypt_payload(api_key).ok()?).ok()?;
+        record["recipient_public_key"].as_str().map(str::to_string)
+    }
+
+    /// Collection an owner-side grant record shares (from its `GRANT:` label)
+    pub fn grant_source(&self, api_key: &[u8]) -> Option<String> {
+        self.grant.as_ref()?;
+        let metadata = self.decrypt_metadata(api_key).ok()?;
+        metadata["label"]
+            .as_str()?
+            .strip_prefix(GRANT_LABEL_PREFIX)
+            .map(str::to_string)
+    }
+
+    /// Open the sealed grant content with the recipient's API key
+    pub fn open_grant(&self, api_key: &[u8]) -> Option<serde_json::Value> {
+        let grant = self.grant.as_ref()?;
+        let content =
+            open_sealed(api_key, &grant.ephemeral_public_key, &grant.sealed_content).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Check whether this collection's `expires_at` (from encrypted metadata) is at or before `now`
+    /// Expiry is logical only - the collection stays on-chain but the API stops returning it
+    pub fn is_expired(&self, api_key: &[u8], now: i64) -> bool {
+        self.decrypt_metadata(api_key)
+            .ok()
+            .and_then(|metadata| metadata["expires_at"].as_i64())
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Check whether this collection is a tombstone record
+    pub fn is_tombstone(&self, api_key: &[u8]) -> bool {
+        if self.label_index.is_some() {
+            return self.matches_label(api_key, TOMBSTONE_LABEL_PREFIX);
+        }
+
+        // Legacy collections have no label index - fall back to decrypting metadata
+        self.decrypt_metadata(api_key)
+            .ok()
+            .and_then(|metadata| metadata["label"].as_str().map(str::to_string))
+            .is_some_and(|label| label.starts_with(TOMBSTONE_LABEL_PREFIX))
+    }
+
+    /// Check whether this collection's label exactly matches `label`
+    /// Uses the label blind index when present, so no metadata decryption is needed
+    pub fn matches_label(&self, api_key: &[u8], label: &str) -> bool {
+        match &self.label_index {
+            Some(index) => {
+                let mac_key = global_key_cache().get_mac_key(api_key, ENCRYPTION_SALT);
+                generate_label_blind_index(&mac_key, label, &self.user_salt).is_ok_and(|expected| {
+                    constant_time_compare_bytes(expected.as_bytes(), index.as_bytes())
+                })
+            }
+            None => self
+                .decrypt_metadata(api_key)
+                .ok()
+                .is_some_and(|metadata| metadata["label"].as_str() == Some(label)),
+        }
+    }
+
+    /// Get the collection ID deleted by this tombstone
+    /// Returns None if this isn't a tombstone or its signature/MAC don't verify
+    pub fn tombstone_target(&self, api_key: &[u8]) -> Option<String> {
+        let metadata = self.decrypt_metadata(api_key).ok()?;
+        let target = metadata["label"]
+            .as_str()?
+            .strip_prefix(TOMBSTONE_LABEL_PREFIX)?
+            .to_string();
+
+        self.verify(Some(api_key)).ok()?;
+        Some(target)
     }
 }
 
@@ -150,6 +521,9 @@ mod tests {
         let collection = EncryptedCollection::new(
             "Test Collection".to_string(),
             r#"{"value": 42}"#.to_string(),
+            None,
+            "application/json",
+            None,
             &api_key,
             api_key_hash,
             &signing_key,
@@ -170,6 +544,9 @@ mod tests {
         let collection = EncryptedCollection::new(
             "Test".to_string(),
             r#"{"value": 42}"#.to_string(),
+            None,
+            "application/json",
+            None,
             &api_key,
             api_key_hash,
             &signing_key,
@@ -190,6 +567,9 @@ mod tests {
         let collection = EncryptedCollection::new(
             "Test".to_string(),
             original_data.to_string(),
+            None,
+            "application/json",
+            None,
             &api_key,
             api_key_hash,
             &signing_key,
@@ -198,8 +578,160 @@ mod tests {
 
         let decrypted = collection.decrypt_payload(&api_key).unwrap();
         assert_eq!(original_data, decrypted);
+        assert_eq!(
+            collection.decrypt_payload_bytes(&api_key).unwrap(),
+            original_data.as_bytes()
+        );
 
         let metadata = collection.decrypt_metadata(&api_key).unwrap();
         assert_eq!(metadata["label"], "Test");
+        assert_eq!(metadata["content_type"], "application/json");
+    }
+
+    #[test]
+    fn test_tombstone_target() {
+        let api_key = generate_api_key();
+        let api_key_hash = hash_api_key_hex(&api_key);
+        let signing_key = generate_signing_key();
+
+        let tombstone =
+            EncryptedCollection::new_tombstone("target-id", &api_key, api_key_hash, &signing_key)
+                .unwrap();
+
+        assert!(tombstone.is_tombstone(&api_key));
+        assert_eq!(
+            tombstone.tombstone_target(&api_key),
+            Some("target-id".to_string())
+        );
+
+        // A different API key cannot decrypt or authenticate the tombstone
+        let other_key = generate_api_key();
+        assert!(!tombstone.is_tombstone(&other_key));
+        assert_eq!(tombstone.tombstone_target(&other_key), None);
+    }
+
+    #[test]
+    fn test_revision_parent_is_signed() {
+        let api_key = generate_api_key();
+        let api_key_hash = hash_api_key_hex(&api_key);
+        let signing_key = generate_signing_key();
+
+        let mut revision = EncryptedCollection::new_revision(
+            "parent-id".to_string(),
+            "Test".to_string(),
+            r#"{"value": 43}"#.to_string(),
+            None,
+            "application/json",
+            None,
+            &api_key,
+            api_key_hash,
+            &signing_key,
+        )
+        .unwrap();
+
+        assert_eq!(revision.parent_collection_id.as_deref(), Some("parent-id"));
+        assert!(revision.verify(Some(&api_key)).is_ok());
+
+        // Re-linking the revision to another parent must break the signature
+        revision.parent_collection_id = Some("other-id".to_string());
+        assert!(revision.verify(Some(&api_key)).is_err());
+    }
+
+    #[test]
+    fn test_matches_label() {
+        let api_key = generate_api_key();
+        let api_key_hash = hash_api_key_hex(&api_key);
+        let signing_key = generate_signing_key();
+
+        let collection = EncryptedCollection::new(
+            "medical-records".to_string(),
+            r#"{"value": 42}"#.to_string(),
+            None,
+            "application/json",
+            None,
+            &api_key,
+            api_key_hash,
+            &signing_key,
+        )
+        .unwrap();
+
+        assert!(collection.label_index.is_some());
+        assert!(collection.matches_label(&api_key, "medical-records"));
+        assert!(!collection.matches_label(&api_key, "medical"));
+        assert!(!collection.matches_label(&generate_api_key(), "medical-records"));
+        assert!(!collection.is_tombstone(&api_key));
+    }
+
+    #[test]
+    fn test_compressed_payload_roundtrip() {
+        let api_key = generate_api_key();
+        let api_key_hash = hash_api_key_hex(&api_key);
+        let signing_key = generate_signing_key();
+        let data = format!(r#"{{"notes": "{}"}}"#, "lab results normal ".repeat(500));
+
+        let compressed = EncryptedCollection::new(
+            "notes".to_string(),
+            data.clone(),
+            None,
+            "application/json",
+            None,
+            &api_key,
+            api_key_hash.clone(),
+            &signing_key,
+        )
+        .unwrap();
+        let uncompressed = EncryptedCollection::new(
+            "notes".to_string(),
+            data.clone(),
+            Some(false),
+            "application/json",
+            None,
+            &api_key,
+            api_key_hash,
+            &signing_key,
+        )
+        .unwrap();
+
+        assert_eq!(compressed.compression, PayloadCompression::Zstd);
+        assert_eq!(uncompressed.compression, PayloadCompression::None);
+        assert!(compressed.encrypted_payload.len() < uncompressed.encrypted_payload.len());
+        assert!(compressed.verify(Some(&api_key)).is_ok());
+        assert_eq!(compressed.decrypt_payload(&api_key).unwrap(), data);
+
+        // The marker is signed: stripping it must not silently yield garbage
+        let mut tampered = compressed.clone();
+        tampered.compression = PayloadCompression::None;
+        assert!(tampered.verify(Some(&api_key)).is_err());
+    }
+
+    #[test]
+    fn test_legacy_collection_without_marker_decrypts() {
+        let api_key = generate_api_key();
+        let api_key_hash = hash_api_key_hex(&api_key);
+        let signing_key = generate_signing_key();
+
+        let collection = EncryptedCollection::new(
+            "legacy".to_string(),
+            r#"{"value": 42}"#.to_string(),
+            None,
+            "application/json",
+            None,
+            &api_key,
+            api_key_hash,
+            &signing_key,
+        )
+        .unwrap();
+
+        // Uncompressed collections serialize exactly as before the marker existed
+        let json = serde_json::to_value(&collection).unwrap();
+        assert!(json.get("compression").is_none());
+
+        let decoded: EncryptedCollection = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.compression, PayloadCompression::None);
+        assert!(decoded.verify(Some(&api_key)).is_ok());
+        assert_eq!(
+            decoded.decrypt_payload(&api_key).unwrap(),
+            r#"{"value": 42}"#
+        );
     }
 }
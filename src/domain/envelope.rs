@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use crate::constants::AES_KEY_SIZE_BYTES;
 use crate::crypto::{
     decrypt_data_with_key, encrypt_data_with_key, global_key_cache, hash_api_key_hex,
+    sharing_public_key_hex,
 };
 use crate::types::{GoudChainError, Result};
 
@@ -16,6 +17,26 @@ pub struct AccountEnvelope {
     pub encrypted_data: String, // Base64(AES-GCM(UserAccount JSON))
     pub api_key_hash: String,   // SHA-256 hash for lookup (NOT the encrypted content)
     pub nonce: String,          // Hex-encoded nonce (12 bytes)
+    /// Public keys other accounts may look up by account ID (absent on older envelopes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directory: Option<AccountDirectoryEntry>,
+    /// Metadata revision of an existing account rather than a new account
+    /// (the directory entry already links revisions to their account ID)
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub revision: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Plaintext public-key listing for an account
+/// Holds public material only: no API key hash, no encrypted metadata
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountDirectoryEntry {
+    pub account_id: String,
+    pub request_public_key: Option<String>, // Client Ed25519 key for signed requests
+    pub sharing_public_key: String,         // X25519 key for collection sharing
 }
 
 /// Envelope for collections (collections already encrypted with user's API key)
@@ -31,6 +52,8 @@ pub struct BlockEnvelopeContainer {
     pub account_envelopes: Vec<AccountEnvelope>,
     pub collection_envelopes: Vec<CollectionEnvelope>,
     pub validator: String, // Plaintext (needed for PoA consensus validation)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pruned_collection_ids: Vec<String>, // Collections dropped by pruning (see Block::prune_collections)
 }
 
 /// Derive envelope-specific encryption key using HKDF
@@ -58,6 +81,13 @@ pub fn encrypt_account_envelope(
         encrypted_data,
         api_key_hash: hash_api_key_hex(api_key),
         nonce,
+        // A revoked key's sharing key must not be advertised any more
+        directory: account.revoked_at.is_none().then(|| AccountDirectoryEntry {
+            account_id: account.account_id.clone(),
+            request_public_key: account.request_public_key.clone(),
+            sharing_public_key: sharing_public_key_hex(api_key),
+        }),
+        revision: account.updated_at.is_some(),
     })
 }
 
@@ -82,7 +112,7 @@ mod tests {
 
     fn create_test_account(api_key: &[u8]) -> UserAccount {
         let signing_key = generate_signing_key();
-        UserAccount::new(api_key, &signing_key, None).unwrap()
+        UserAccount::new(api_key, &signing_key, None, None).unwrap()
     }
 
     #[test]
@@ -97,6 +127,13 @@ mod tests {
         assert_eq!(account.account_id, decrypted.account_id);
         assert_eq!(account.api_key_hash, decrypted.api_key_hash);
         assert_eq!(account.public_key, decrypted.public_key);
+
+        let directory = envelope.directory.unwrap();
+        assert_eq!(directory.account_id, account.account_id);
+        assert_eq!(
+            directory.sharing_public_key,
+            sharing_public_key_hex(&api_key)
+        );
     }
 
     #[test]
@@ -1,11 +1,15 @@
 pub mod block;
 pub mod blockchain;
+pub mod bloom;
+pub mod compression;
 pub mod encrypted_collection;
 pub mod envelope;
 pub mod user_account;
 
 // Re-export commonly used types
-pub use block::Block;
-pub use blockchain::Blockchain;
+pub use block::{Block, BlockHeader};
+pub use blockchain::{
+    BlockLoader, Blockchain, ChainAggregates, CollectionRevision, RegrantedShare, SharedCollection,
+};
 pub use encrypted_collection::EncryptedCollection;
 pub use user_account::UserAccount;
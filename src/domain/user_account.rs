@@ -5,7 +5,8 @@ use uuid::Uuid;
 
 use crate::constants::ENCRYPTION_SALT;
 use crate::crypto::{
-    encrypt_data_with_key, get_public_key_hex, global_key_cache, hash_api_key_hex, sign_message,
+    decrypt_data_with_key, encrypt_data_with_key, get_public_key_hex, global_key_cache,
+    hash_api_key_hex, sign_message,
 };
 use crate::types::Result;
 
@@ -17,54 +18,136 @@ pub struct UserAccount {
     pub created_at: i64,
     pub metadata_encrypted: Option<String>, // Optional encrypted metadata (e.g., email, username)
     pub signature: String,
+    /// Client-held Ed25519 public key (hex) used to verify signed write requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_public_key: Option<String>,
+    /// When this revision replaced the account's metadata (None for the original record)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<i64>,
+    /// Set on the last revision under an API key that was rotated away; that key no
+    /// longer authenticates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<i64>,
 }
 
 impl UserAccount {
     /// Create a new user account with an API key
     /// Metadata can be any JSON value and will be serialized + encrypted
+    /// `request_public_key` optionally registers a client key for signed write requests
     pub fn new(
         api_key: &[u8],
         signing_key: &SigningKey,
         metadata: Option<serde_json::Value>,
+        request_public_key: Option<String>,
     ) -> Result<Self> {
-        let account_id = Uuid::new_v4().to_string();
-        let api_key_hash = hash_api_key_hex(api_key);
-        let public_key = get_public_key_hex(signing_key);
-        let created_at = Utc::now().timestamp();
-
-        // Encrypt metadata if provided (serialize JSON to string first)
-        let metadata_encrypted = if let Some(meta) = metadata {
-            let meta_string = serde_json::to_string(&meta)
-                .map_err(|e| crate::types::GoudChainError::Internal(e.to_string()))?;
-            let key_cache = global_key_cache();
-            let encryption_key = key_cache.get_encryption_key(api_key, ENCRYPTION_SALT);
-            let (encrypted, _nonce) = encrypt_data_with_key(&meta_string, &encryption_key)?;
-            Some(encrypted)
-        } else {
-            None
+        let mut account = UserAccount {
+            account_id: Uuid::new_v4().to_string(),
+            api_key_hash: hash_api_key_hex(api_key),
+            public_key: String::new(),
+            created_at: Utc::now().timestamp(),
+            metadata_encrypted: encrypt_metadata(api_key, metadata)?,
+            signature: String::new(),
+            request_public_key,
+            updated_at: None,
+            revoked_at: None,
         };
+        account.sign(signing_key);
+        Ok(account)
+    }
+
+    /// Create a revision of this account carrying new metadata
+    /// Account ID, creation time and request key are kept; account lookups resolve
+    /// to the newest revision on the chain
+    pub fn with_metadata(
+        &self,
+        api_key: &[u8],
+        signing_key: &SigningKey,
+        metadata: Option<serde_json::Value>,
+    ) -> Result<Self> {
+        let mut revision = UserAccount {
+            metadata_encrypted: encrypt_metadata(api_key, metadata)?,
+            updated_at: Some(Utc::now().timestamp()),
+            ..self.clone()
+        };
+        revision.sign(signing_key);
+        Ok(revision)
+    }
+
+    /// Create a revision of this account under `new_api_key` (key rotation)
+    /// Metadata is re-encrypted to the new key; account ID, creation time and request key are kept
+    pub fn with_api_key(
+        &self,
+        api_key: &[u8],
+        new_api_key: &[u8],
+        signing_key: &SigningKey,
+    ) -> Result<Self> {
+        let mut revision = UserAccount {
+            api_key_hash: hash_api_key_hex(new_api_key),
+            metadata_encrypted: encrypt_metadata(new_api_key, decrypt_metadata(api_key, self)?)?,
+            updated_at: Some(Utc::now().timestamp()),
+            ..self.clone()
+        };
+        revision.sign(signing_key);
+        Ok(revision)
+    }
 
-        // Sign the account data
+    /// Create the final revision under this account's current API key, marking the key revoked
+    pub fn revoked(&self, signing_key: &SigningKey) -> Self {
+        let now = Utc::now().timestamp();
+        let mut revision = UserAccount {
+            updated_at: Some(now),
+            revoked_at: Some(now),
+            ..self.clone()
+        };
+        revision.sign(signing_key);
+        revision
+    }
+
+    /// Sign the account data (request key, update and revocation times only appended when present)
+    fn sign(&mut self, signing_key: &SigningKey) {
+        self.public_key = get_public_key_hex(signing_key);
         let message = format!(
-            "{}{}{}{}",
-            account_id,
-            api_key_hash,
-            created_at,
-            metadata_encrypted.as_deref().unwrap_or("")
+            "{}{}{}{}{}{}{}",
+            self.account_id,
+            self.api_key_hash,
+            self.created_at,
+            self.metadata_encrypted.as_deref().unwrap_or(""),
+            self.request_public_key.as_deref().unwrap_or(""),
+            self.updated_at.map(|t| t.to_string()).unwrap_or_default(),
+            self.revoked_at.map(|t| t.to_string()).unwrap_or_default()
         );
-        let signature = sign_message(message.as_bytes(), signing_key);
-
-        Ok(UserAccount {
-            account_id,
-            api_key_hash,
-            public_key,
-            created_at,
-            metadata_encrypted,
-            signature,
-        })
+        self.signature = sign_message(message.as_bytes(), signing_key);
     }
 }
 
+/// Encrypt metadata if provided (serialize JSON to string first)
+fn encrypt_metadata(api_key: &[u8], metadata: Option<serde_json::Value>) -> Result<Option<String>> {
+    let Some(meta) = metadata else {
+        return Ok(None);
+    };
+
+    let meta_string = serde_json::to_string(&meta)
+        .map_err(|e| crate::types::GoudChainError::Internal(e.to_string()))?;
+    let key_cache = global_key_cache();
+    let encryption_key = key_cache.get_encryption_key(api_key, ENCRYPTION_SALT);
+    let (encrypted, _nonce) = encrypt_data_with_key(&meta_string, &encryption_key)?;
+    Ok(Some(encrypted))
+}
+
+/// Decrypt an account's metadata (None when it has none)
+fn decrypt_metadata(api_key: &[u8], account: &UserAccount) -> Result<Option<serde_json::Value>> {
+    let Some(encrypted) = &account.metadata_encrypted else {
+        return Ok(None);
+    };
+
+    let key_cache = global_key_cache();
+    let encryption_key = key_cache.get_encryption_key(api_key, ENCRYPTION_SALT);
+    let meta_string = decrypt_data_with_key(encrypted, &encryption_key)?;
+    serde_json::from_str(&meta_string)
+        .map(Some)
+        .map_err(|e| crate::types::GoudChainError::Internal(e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,7 +158,7 @@ mod tests {
         let api_key = b"test_api_key_12345678901234567890";
         let signing_key = generate_signing_key();
 
-        let account = UserAccount::new(api_key, &signing_key, None).unwrap();
+        let account = UserAccount::new(api_key, &signing_key, None, None).unwrap();
 
         assert!(!account.account_id.is_empty());
         assert!(!account.api_key_hash.is_empty());
@@ -89,8 +172,55 @@ mod tests {
         let signing_key = generate_signing_key();
         let metadata = serde_json::json!({"email": "user@example.com"});
 
-        let account = UserAccount::new(api_key, &signing_key, Some(metadata)).unwrap();
+        let account = UserAccount::new(api_key, &signing_key, Some(metadata), None).unwrap();
 
         assert!(account.metadata_encrypted.is_some());
     }
+
+    #[test]
+    fn test_metadata_revision_keeps_identity() {
+        let api_key = b"test_api_key_12345678901234567890";
+        let account = UserAccount::new(api_key, &generate_signing_key(), None, None).unwrap();
+
+        let metadata = serde_json::json!({"username": "alice"});
+        let revision = account
+            .with_metadata(api_key, &generate_signing_key(), Some(metadata))
+            .unwrap();
+
+        assert_eq!(revision.account_id, account.account_id);
+        assert_eq!(revision.api_key_hash, account.api_key_hash);
+        assert_eq!(revision.created_at, account.created_at);
+        assert!(revision.metadata_encrypted.is_some());
+        assert!(revision.updated_at.is_some());
+        assert_ne!(revision.signature, account.signature);
+    }
+
+    #[test]
+    fn test_api_key_revision_rekeys_metadata() {
+        let old_key = b"test_api_key_12345678901234567890";
+        let new_key = b"test_api_key_09876543210987654321";
+        let metadata = serde_json::json!({"username": "alice"});
+        let account = UserAccount::new(
+            old_key,
+            &generate_signing_key(),
+            Some(metadata.clone()),
+            None,
+        )
+        .unwrap();
+
+        let revision = account
+            .with_api_key(old_key, new_key, &generate_signing_key())
+            .unwrap();
+        assert_eq!(revision.account_id, account.account_id);
+        assert_eq!(revision.api_key_hash, hash_api_key_hex(new_key));
+        assert_eq!(
+            decrypt_metadata(new_key, &revision).unwrap(),
+            Some(metadata)
+        );
+        assert!(decrypt_metadata(old_key, &revision).is_err());
+
+        let revoked = account.revoked(&generate_signing_key());
+        assert_eq!(revoked.api_key_hash, account.api_key_hash);
+        assert!(revoked.revoked_at.is_some());
+    }
 }
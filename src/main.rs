@@ -10,32 +10,55 @@ mod storage;
 mod types;
 
 use axum::{
+    extract::DefaultBodyLimit,
     response::{Html, IntoResponse},
     routing::get,
     Extension, Json, Router,
 };
 use clap::Parser;
+use ed25519_dalek::SigningKey;
+use futures_util::future::join_all;
+use std::future::Future;
+use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{error, info};
+use std::time::Duration;
+use tokio::sync::{watch, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
 use utoipa::OpenApi;
 use utoipa_axum::router::OpenApiRouter;
 
+use api::routes::metrics::build_node_metrics;
+use api::websocket::EventType;
 use api::{ApiDoc, RateLimiter, WebSocketBroadcaster};
 use cli::{Cli, Commands};
-use config::Config;
-use constants::NONCE_CLEANUP_INTERVAL_SECONDS;
+use config::{Config, LogConfig, LogFormat};
+use constants::{
+    API_VERSION_PREFIX, AUDIT_RETENTION_INTERVAL_SECONDS, BLOCK_OFFLOAD_INTERVAL_SECONDS,
+    BLOCK_SEAL_CHECK_INTERVAL_SECONDS, IDEMPOTENCY_CLEANUP_INTERVAL_SECONDS,
+    KEY_CACHE_PURGE_INTERVAL_SECONDS, NONCE_CLEANUP_INTERVAL_SECONDS,
+    PEER_EXCHANGE_INTERVAL_SECONDS, PEER_PING_INTERVAL_SECONDS, PRUNE_INTERVAL_SECONDS,
+    SHUTDOWN_GRACE_PERIOD_SECONDS, TOKEN_REVOCATION_CLEANUP_INTERVAL_SECONDS,
+};
+use crypto::{get_public_key_hex, global_key_cache};
 use domain::Block;
-use network::P2PNode;
+use network::{P2PNode, P2PTransport};
 use storage::Migration;
 use storage::{
-    init_data_directory, load_blockchain, AuditLogger, BlockchainStore, NonceStore, RateLimitStore,
+    load_blockchain, open_ephemeral_db, AuditLogger, BlockchainStore, IdempotencyStore, NonceStore,
+    RateLimitStore, TokenRevocationStore,
 };
 
 #[tokio::main]
 async fn main() {
-    // Initialize tracing
-    tracing_subscriber::fmt::init();
+    // Initialize tracing first so every later step (including config errors) is logged
+    if let Err(e) = LogConfig::from_env()
+        .map_err(|e| e.to_string())
+        .and_then(|log_config| init_tracing(&log_config))
+    {
+        eprintln!("Failed to initialize logging: {}", e);
+        std::process::exit(1);
+    }
 
     // Parse CLI arguments
     let cli = Cli::parse();
@@ -64,14 +87,17 @@ async fn main() {
         }
     };
 
-    // Initialize data directory
-    if let Err(e) = init_data_directory() {
-        error!(error = %e, "Failed to initialize data directory");
-        std::process::exit(1);
+    // Restore runs before the store opens RocksDB (the database must be closed)
+    if let Some(Commands::Backup(backup_cmd)) = &cli.command {
+        if let Err(e) = cli::handle_backup_command(backup_cmd, &config) {
+            error!(error = %e, "Backup command failed");
+            std::process::exit(1);
+        }
+        std::process::exit(0);
     }
 
     // Initialize BlockchainStore for persistent blockchain storage
-    let blockchain_store = match BlockchainStore::new() {
+    let blockchain_store = match BlockchainStore::new(&config.rocksdb_path()) {
         Ok(store) => Arc::new(store),
         Err(e) => {
             error!(error = %e, "Failed to initialize BlockchainStore");
@@ -95,17 +121,57 @@ async fn main() {
     let blockchain = match load_blockchain(
         config.node_id.clone(),
         config.validator_config.clone(),
+        config.genesis_timestamp,
         &blockchain_store,
+        &get_available_migrations(),
     ) {
-        Ok(bc) => Arc::new(RwLock::new(bc)), // Changed from Mutex to RwLock for concurrent reads
+        Ok(mut bc) => {
+            // Sign with the persisted node key so published validator keys stay valid across restarts
+            let signing_key = SigningKey::from_bytes(&config.node_signing_seed);
+            info!(
+                public_key = %get_public_key_hex(&signing_key),
+                "Node signing key loaded (publish via VALIDATOR_PUBLIC_KEYS)"
+            );
+            bc.node_signing_key = Some(signing_key);
+            bc.exact_blind_indexes = config.exact_blind_indexes;
+            bc.checkpoint_interval = config.checkpoint_interval;
+            bc.max_reorg_depth = config.max_reorg_depth;
+            bc.min_block_interval_seconds = config.min_block_interval_seconds;
+            bc.block_seal_threshold = config.block_seal_threshold;
+            bc.read_only = config.read_only;
+            if bc.read_only {
+                warn!(
+                    "Starting in read-only maintenance mode (READ_ONLY=true): writes are refused"
+                );
+            }
+            // Count the loaded chain once; /stats then advances the totals block by block
+            bc.refresh_aggregates();
+            Arc::new(RwLock::new(bc)) // Changed from Mutex to RwLock for concurrent reads
+        }
         Err(e) => {
             error!(error = %e, "Failed to load blockchain");
             std::process::exit(1);
         }
     };
 
-    // Initialize rate limiting store (reuses same RocksDB instance)
-    let rate_limit_store = Arc::new(RateLimitStore::new(blockchain_store.get_db()));
+    // Shutdown signal shared by the HTTP server, P2P listener and periodic background tasks
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let mut background_tasks = Vec::new();
+
+    // Rate limits and nonces go to their own RocksDB when EPHEMERAL_DB_PATH is set
+    let ephemeral_db = match &config.ephemeral_db_path {
+        Some(path) => match open_ephemeral_db(path) {
+            Ok(db) => db,
+            Err(e) => {
+                error!(error = %e, "Failed to open ephemeral RocksDB");
+                std::process::exit(1);
+            }
+        },
+        None => blockchain_store.get_db(),
+    };
+
+    // Initialize rate limiting store
+    let rate_limit_store = Arc::new(RateLimitStore::new(Arc::clone(&ephemeral_db)));
 
     // Parse bypass whitelist from environment (comma-separated API key hashes)
     let bypass_keys: Vec<String> = std::env::var("RATE_LIMIT_BYPASS_KEYS")
@@ -115,25 +181,59 @@ async fn main() {
         .map(|s| s.trim().to_string())
         .collect();
 
+    // Parse bypass IP ranges (comma-separated CIDRs or addresses, IPv4 or IPv6)
+    let bypass_cidrs: Vec<String> = std::env::var("RATE_LIMIT_BYPASS_CIDRS")
+        .unwrap_or_default()
+        .split(',')
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| s.trim().to_string())
+        .collect();
+
     info!(
         bypass_count = bypass_keys.len(),
+        bypass_cidr_count = bypass_cidrs.len(),
         "Rate limiting initialized with bypass whitelist"
     );
 
-    let rate_limiter = Arc::new(RateLimiter::new(rate_limit_store, bypass_keys));
+    // Start background task that purges stale rate-limit windows and expired bans
+    let rate_limit_store_cleanup = Arc::clone(&rate_limit_store);
+    let rate_limit_cleanup_interval = config.rate_limit.cleanup_interval_seconds;
+    let token_bucket_refill_seconds = config.rate_limit.burst_seconds as u64;
+    background_tasks.push(spawn_periodic(
+        rate_limit_cleanup_interval,
+        shutdown_rx.clone(),
+        move || {
+            match rate_limit_store_cleanup.cleanup_expired(token_bucket_refill_seconds) {
+                Ok(deleted) => {
+                    if deleted > 0 {
+                        info!("Rate limit cleanup: removed {} stale entries", deleted);
+                    }
+                }
+                Err(e) => {
+                    error!("Rate limit cleanup failed: {}", e);
+                }
+            }
+            async {}
+        },
+    ));
+
+    let rate_limiter = Arc::new(RateLimiter::new(
+        rate_limit_store,
+        bypass_keys,
+        bypass_cidrs,
+        config.rate_limit.clone(),
+    ));
 
-    // Initialize nonce store for replay protection (reuses same RocksDB instance)
-    let nonce_store = Arc::new(NonceStore::new(blockchain_store.get_db()));
+    // Initialize nonce store for replay protection
+    let nonce_store = Arc::new(NonceStore::new(ephemeral_db));
     info!("Nonce store initialized for replay attack prevention");
 
     // Start background task for periodic nonce cleanup
     let nonce_store_cleanup = Arc::clone(&nonce_store);
-    tokio::spawn(async move {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
-            NONCE_CLEANUP_INTERVAL_SECONDS,
-        ));
-        loop {
-            interval.tick().await;
+    background_tasks.push(spawn_periodic(
+        NONCE_CLEANUP_INTERVAL_SECONDS,
+        shutdown_rx.clone(),
+        move || {
             match nonce_store_cleanup.cleanup_expired_nonces() {
                 Ok(deleted) => {
                     if deleted > 0 {
@@ -144,22 +244,253 @@ async fn main() {
                     error!("Nonce cleanup failed: {}", e);
                 }
             }
+            async {}
+        },
+    ));
+
+    // Initialize session token revocation store (reuses same RocksDB instance)
+    let token_revocations = Arc::new(TokenRevocationStore::new(blockchain_store.get_db()));
+
+    // Start background task for periodic revocation cleanup (entries expire with their tokens)
+    let token_revocations_cleanup = Arc::clone(&token_revocations);
+    background_tasks.push(spawn_periodic(
+        TOKEN_REVOCATION_CLEANUP_INTERVAL_SECONDS,
+        shutdown_rx.clone(),
+        move || {
+            if let Err(e) = token_revocations_cleanup.cleanup_expired() {
+                error!("Token revocation cleanup failed: {}", e);
+            }
+            async {}
+        },
+    ));
+
+    // Initialize idempotency key store for safe write retries (reuses same RocksDB instance)
+    let idempotency_store = Arc::new(IdempotencyStore::new(blockchain_store.get_db()));
+
+    // Start background task for periodic idempotency key cleanup
+    let idempotency_store_cleanup = Arc::clone(&idempotency_store);
+    background_tasks.push(spawn_periodic(
+        IDEMPOTENCY_CLEANUP_INTERVAL_SECONDS,
+        shutdown_rx.clone(),
+        move || {
+            if let Err(e) = idempotency_store_cleanup.cleanup_expired() {
+                error!("Idempotency key cleanup failed: {}", e);
+            }
+            async {}
+        },
+    ));
+
+    // Start background task that purges expired derived keys from memory
+    background_tasks.push(spawn_periodic(
+        KEY_CACHE_PURGE_INTERVAL_SECONDS,
+        shutdown_rx.clone(),
+        || {
+            let purged = global_key_cache().purge_expired();
+            if purged > 0 {
+                debug!("Key cache purge: removed {} expired entries", purged);
+            }
+            async {}
+        },
+    ));
+
+    // Start background task that prunes old collection envelopes (opt-in via PRUNE_DEPTH)
+    if let Some(prune_depth) = config.prune_depth {
+        let blockchain_for_pruning = Arc::clone(&blockchain);
+        let store_for_pruning = Arc::clone(&blockchain_store);
+        background_tasks.push(spawn_periodic(
+            PRUNE_INTERVAL_SECONDS,
+            shutdown_rx.clone(),
+            move || {
+                let blockchain = Arc::clone(&blockchain_for_pruning);
+                let store = Arc::clone(&store_for_pruning);
+                async move {
+                    let mut blockchain = blockchain.write().await;
+                    let boundary = blockchain.prune_boundary(prune_depth);
+                    if boundary <= blockchain.pruned_before {
+                        return;
+                    }
+                    let result = store
+                        .prune_before(boundary)
+                        .and_then(|_| blockchain.prune_before(boundary));
+                    match result {
+                        Ok(pruned) => info!(
+                            pruned_blocks = pruned,
+                            pruned_before = boundary,
+                            "Pruned old collection envelopes"
+                        ),
+                        Err(e) => error!("Pruning failed: {}", e),
+                    }
+                }
+            },
+        ));
+        info!(prune_depth, "Pruning enabled for old collection envelopes");
+    }
+
+    // Start background task that evicts old block bodies from memory (opt-in via MAX_IN_MEMORY_BLOCKS)
+    // The first pass runs at startup; offloaded bodies are read back from RocksDB on demand
+    if let Some(max_in_memory_blocks) = config.max_in_memory_blocks {
+        let blockchain_for_offload = Arc::clone(&blockchain);
+        background_tasks.push(spawn_periodic(
+            BLOCK_OFFLOAD_INTERVAL_SECONDS,
+            shutdown_rx.clone(),
+            move || {
+                let blockchain = Arc::clone(&blockchain_for_offload);
+                async move {
+                    let mut blockchain = blockchain.write().await;
+                    let boundary = blockchain.offload_boundary(max_in_memory_blocks);
+                    let offloaded = blockchain.offload_before(boundary);
+                    if offloaded > 0 {
+                        info!(
+                            offloaded_blocks = offloaded,
+                            offloaded_before = boundary,
+                            "Offloaded old block bodies from memory"
+                        );
+                    }
+                }
+            },
+        ));
+        info!(
+            max_in_memory_blocks,
+            "Block offloading enabled for blocks beyond the in-memory limit"
+        );
+    }
+
+    // Start P2P node (async-first)
+    let p2p_transport = match P2PTransport::new(config.p2p_tls.as_ref()) {
+        Ok(transport) => transport,
+        Err(e) => {
+            error!(error = %e, "Failed to initialize P2P transport");
+            std::process::exit(1);
         }
+    };
+    // Initialize WebSocket broadcaster for real-time updates
+    let ws_broadcaster = Arc::new(WebSocketBroadcaster::new());
+    info!("WebSocket broadcaster initialized");
+
+    // Peer topology callback for real-time notifications (avoids layer violation)
+    let ws_for_peers = Arc::clone(&ws_broadcaster);
+    let peer_update_callback = Arc::new(move |peers: Vec<String>| {
+        let ws = Arc::clone(&ws_for_peers);
+        tokio::spawn(async move {
+            ws.broadcast_peer_update(peers).await;
+        });
+    });
+
+    // Reorg callback so subscribers learn when synced blocks replace part of our chain
+    let ws_for_reorgs = Arc::clone(&ws_broadcaster);
+    let reorg_callback = Arc::new(move |old_tip: String, new_tip: String, depth: u64| {
+        let ws = Arc::clone(&ws_for_reorgs);
+        tokio::spawn(async move {
+            ws.broadcast_reorg(old_tip, new_tip, depth).await;
+        });
     });
 
-    // Start P2P node (async-first)
     let p2p_node = Arc::new(P2PNode::new(
         Arc::clone(&blockchain),
         Arc::clone(&blockchain_store),
         config.peers.clone(),
+        config.p2p_advertise_addr.clone(),
+        p2p_transport,
+        Some(peer_update_callback),
+        Some(reorg_callback),
     ));
 
     // Start P2P server in background
     let p2p_clone = Arc::clone(&p2p_node);
-    let p2p_port = config.p2p_port;
-    tokio::spawn(async move {
-        p2p_clone.start_p2p_server(p2p_port).await;
-    });
+    let p2p_bind_addr = config.p2p_bind_addr;
+    let p2p_shutdown = shutdown_rx.clone();
+    background_tasks.push(tokio::spawn(async move {
+        p2p_clone
+            .start_p2p_server(p2p_bind_addr, wait_for_shutdown(p2p_shutdown))
+            .await;
+    }));
+
+    // Catch up with peers once at startup (/health/ready reports 503 until a sync catches up)
+    let p2p_startup_sync = Arc::clone(&p2p_node);
+    background_tasks.push(tokio::spawn(async move {
+        p2p_startup_sync.request_chain_from_peers().await;
+    }));
+
+    // Start background task that grows the peer list via peer exchange
+    let p2p_exchange = Arc::clone(&p2p_node);
+    background_tasks.push(spawn_periodic(
+        PEER_EXCHANGE_INTERVAL_SECONDS,
+        shutdown_rx.clone(),
+        move || {
+            let p2p = Arc::clone(&p2p_exchange);
+            async move {
+                p2p.exchange_peers().await;
+            }
+        },
+    ));
+
+    // Start background heartbeat that tracks peer latency and parks dead peers
+    let p2p_heartbeat = Arc::clone(&p2p_node);
+    background_tasks.push(spawn_periodic(
+        PEER_PING_INTERVAL_SECONDS,
+        shutdown_rx.clone(),
+        move || {
+            let p2p = Arc::clone(&p2p_heartbeat);
+            async move { p2p.ping_peers().await }
+        },
+    ));
+
+    // Start background task that pushes metrics_update events to WebSocket subscribers
+    if config.metrics_update_interval_seconds > 0 {
+        let ws_for_metrics = Arc::clone(&ws_broadcaster);
+        let blockchain_for_metrics = Arc::clone(&blockchain);
+        let p2p_for_metrics = Arc::clone(&p2p_node);
+        let data_dir_for_metrics = config.data_dir.clone();
+        background_tasks.push(spawn_periodic(
+            config.metrics_update_interval_seconds,
+            shutdown_rx.clone(),
+            move || {
+                let ws = Arc::clone(&ws_for_metrics);
+                let blockchain = Arc::clone(&blockchain_for_metrics);
+                let p2p = Arc::clone(&p2p_for_metrics);
+                let data_dir = data_dir_for_metrics.clone();
+                async move {
+                    // Skip the chain scan and disk walk while nobody is listening
+                    if !ws.has_subscribers(EventType::MetricsUpdate).await {
+                        return;
+                    }
+                    let peer_count = p2p.peers.lock().await.len();
+                    let metrics =
+                        build_node_metrics(&*blockchain.read().await, peer_count, &data_dir);
+                    ws.broadcast_metrics_update(&metrics).await;
+                }
+            },
+        ));
+    }
+
+    // Start background task that seals coalesced submissions (opt-in via MIN_BLOCK_INTERVAL_SECONDS)
+    if config.min_block_interval_seconds > 0 {
+        let p2p_for_sealing = Arc::clone(&p2p_node);
+        let ws_for_sealing = Arc::clone(&ws_broadcaster);
+        background_tasks.push(spawn_periodic(
+            BLOCK_SEAL_CHECK_INTERVAL_SECONDS,
+            shutdown_rx.clone(),
+            move || {
+                let p2p = Arc::clone(&p2p_for_sealing);
+                let ws = Arc::clone(&ws_for_sealing);
+                async move {
+                    match p2p.seal_pending_block(false).await {
+                        Ok(Some(block)) => {
+                            ws.broadcast_blockchain_update(block.index, block.hash)
+                                .await
+                        }
+                        Ok(None) => {}
+                        Err(e) => error!("Block sealing failed: {}", e),
+                    }
+                }
+            },
+        ));
+        info!(
+            min_block_interval_seconds = config.min_block_interval_seconds,
+            block_seal_threshold = config.block_seal_threshold,
+            "Block coalescing enabled"
+        );
+    }
 
     // Initialize audit logger for operational security
     let p2p_for_audit = Arc::clone(&p2p_node);
@@ -171,19 +502,23 @@ async fn main() {
         });
     });
 
-    // Initialize WebSocket broadcaster for real-time updates
-    let ws_broadcaster = Arc::new(WebSocketBroadcaster::new());
-    info!("WebSocket broadcaster initialized");
-
     // Create audit event callback for real-time notifications (avoids layer violation)
     let ws_for_audit = Arc::clone(&ws_broadcaster);
-    let audit_event_callback = Arc::new(move |event_type, timestamp, collection_id, metadata| {
-        let ws = Arc::clone(&ws_for_audit);
-        tokio::spawn(async move {
-            ws.broadcast_audit_log_update(event_type, timestamp, collection_id, metadata)
+    let audit_event_callback = Arc::new(
+        move |account_hash, event_type, timestamp, collection_id, metadata| {
+            let ws = Arc::clone(&ws_for_audit);
+            tokio::spawn(async move {
+                ws.broadcast_audit_log_update(
+                    account_hash,
+                    event_type,
+                    timestamp,
+                    collection_id,
+                    metadata,
+                )
                 .await;
-        });
-    });
+            });
+        },
+    );
 
     let audit_logger = Arc::new(AuditLogger::new(
         Arc::clone(&blockchain),
@@ -193,32 +528,86 @@ async fn main() {
     ));
     info!("Audit logger initialized with background flush task and real-time WebSocket updates");
 
+    // Move the audit retention window forward (AUDIT_RETENTION_DAYS; first run at startup)
+    if let Some(retention_days) = config.audit_retention_days {
+        let audit_logger_retention = Arc::clone(&audit_logger);
+        let retention_seconds = (retention_days * 86400) as i64;
+        background_tasks.push(spawn_periodic(
+            AUDIT_RETENTION_INTERVAL_SECONDS,
+            shutdown_rx.clone(),
+            move || {
+                let audit_logger = Arc::clone(&audit_logger_retention);
+                async move {
+                    if let Err(e) = audit_logger.apply_retention(retention_seconds).await {
+                        error!("Audit retention failed: {}", e);
+                    }
+                }
+            },
+        ));
+        info!(retention_days, "Audit log retention enabled");
+    }
+
     // Create shared state for handlers
     let submit_data_state = api::schemas::SubmitDataState {
         audit_logger: Arc::clone(&audit_logger),
         ws_broadcaster: Arc::clone(&ws_broadcaster),
+        token_revocations: Arc::clone(&token_revocations),
     };
 
-    // Build OpenAPI router with all routes organized by module
-    let (api_router, api_spec) = OpenApiRouter::with_openapi(ApiDoc::openapi())
-        .nest("/account", api::routes::account::router())
-        .nest("/data", api::routes::data::router())
-        .nest("/audit", api::routes::audit::router())
-        .nest("/test", api::routes::test::router())
-        .merge(api::routes::health::router())
-        .merge(api::routes::metrics::router())
+    // Batch sub-requests run through the finished API router, installed below
+    let batch_dispatcher = api::routes::batch::BatchDispatcher::new();
+
+    // All routes organized by module (built twice: versioned, and as unversioned aliases)
+    let api_routes = || {
+        OpenApiRouter::new()
+            .nest("/account", api::routes::account::router())
+            .nest("/data", api::routes::data::router(&config))
+            .nest("/audit", api::routes::audit::router())
+            .nest("/admin", api::routes::admin::router())
+            .nest("/test", api::routes::test::router())
+            .merge(api::routes::health::router())
+            .merge(api::routes::metrics::router())
+            .merge(api::routes::batch::router())
+    };
+
+    // Build OpenAPI router: the spec documents the versioned paths
+    let mut openapi = ApiDoc::openapi();
+    api::document_limits(&mut openapi, &config);
+    let (unversioned_router, _) = api_routes().split_for_parts();
+    let (api_router, mut api_spec) = OpenApiRouter::with_openapi(openapi)
+        .nest(API_VERSION_PREFIX, api_routes())
+        // Unversioned paths stay reachable as deprecated aliases until clients move to /v1
+        .merge(OpenApiRouter::from(unversioned_router.layer(
+            axum::middleware::from_fn(api::versioning::deprecated_alias_middleware),
+        )))
+        // Bound handler execution; sits inside the Extension layers so it can read the Config
+        .layer(api::timeout::timeout_layer(&config))
+        .layer(axum::middleware::from_fn(
+            api::timeout::timeout_error_middleware,
+        ))
+        // Resolve the client address from the socket peer and TRUSTED_PROXIES; inside the
+        // Extension layers so it can read the Config
+        .layer(axum::middleware::from_fn(
+            api::security_audit::client_ip_middleware,
+        ))
         // Shared state via Extension middleware
         .layer(Extension(blockchain))
-        .layer(Extension(p2p_node))
+        .layer(Extension(Arc::clone(&p2p_node)))
         .layer(Extension(config.clone()))
-        .layer(Extension(rate_limiter))
+        .layer(Extension(Arc::clone(&rate_limiter)))
         .layer(Extension(nonce_store))
+        .layer(Extension(idempotency_store))
         .layer(Extension(submit_data_state))
         .layer(Extension(Arc::clone(&ws_broadcaster)))
+        .layer(Extension(batch_dispatcher.clone()))
+        // Oversize bodies are refused with 413 before they are buffered
+        .layer(DefaultBodyLimit::max(config.max_request_body_bytes()))
         .split_for_parts();
+    api::versioning::document_unversioned_aliases(&mut api_spec);
 
     // Convert OpenApiRouter to standard Router
     let api_router = api_router;
+    batch_dispatcher.install(api_router.clone());
 
     // Create OpenAPI JSON endpoint
     let openapi_spec_clone = api_spec.clone();
@@ -263,24 +652,43 @@ async fn main() {
     // WebSocket route with required extensions
     let ws_route = Router::new()
         .route("/ws", get(api::websocket::handle_websocket_upgrade))
+        .layer(axum::middleware::from_fn(
+            api::security_audit::client_ip_middleware,
+        ))
         .layer(Extension(Arc::clone(&ws_broadcaster)))
+        .layer(Extension(Arc::clone(&token_revocations)))
+        .layer(Extension(rate_limiter))
         .layer(Extension(config.clone()));
 
     // Merge all routes
     let app = api_router
         .merge(openapi_route)
         .merge(rapidoc_route)
-        .merge(ws_route);
+        .merge(ws_route)
+        // Outermost, so every log line of a request carries its x-request-id
+        .layer(axum::middleware::from_fn(
+            api::request_id::request_id_middleware,
+        ));
 
-    // NOTE: CORS handled by nginx reverse proxy (see nginx/cors.conf)
-    // Removed CorsLayer to prevent duplicate Access-Control-Allow-Origin headers
+    // NOTE: Behind nginx, CORS is handled by the proxy (see nginx/cors.conf) and the layer stays
+    // off to avoid duplicate Access-Control-Allow-Origin headers; ENABLE_CORS is for direct access
+    let app = match &config.cors {
+        Some(cors) => {
+            info!(origins = ?cors.allowed_origins, "CORS enabled");
+            app.layer(api::cors::cors_layer(cors))
+        }
+        None => app,
+    };
 
     let bind_addr = config.http_bind_addr();
 
     info!("\nGoud Chain - Encrypted Blockchain (OpenAPI-Enabled)");
     info!("   Node ID: {}", config.node_id);
     info!("   HTTP API: http://{}", bind_addr);
-    info!("   P2P Port: {}", config.p2p_port);
+    info!("   P2P Listen: {}", config.p2p_bind_addr);
+    if let Some(advertise_addr) = &config.p2p_advertise_addr {
+        info!("   P2P Advertised: {}", advertise_addr);
+    }
     info!("   Storage: RocksDB (high-performance embedded database)");
     info!("\nAPI Documentation:");
     info!("   RapiDoc UI:   http://{}/rapidoc", bind_addr);
@@ -291,9 +699,11 @@ async fn main() {
     info!("\nEndpoint Groups:");
     info!("   Account Management - /account/*");
     info!("   Data Operations    - /data/*");
-    info!("   Health & Status    - /health, /chain, /peers, /sync");
+    info!("   Health & Status    - /health, /health/live, /health/ready, /chain, /block, /peers, /sync, /validator/*, /limits");
     info!("   Metrics & Stats    - /metrics, /stats");
     info!("   Audit Logs         - /audit");
+    info!("   Administration     - /admin/verify, /admin/checkpoint, /admin/backup, /admin/bans/{hash}");
+    info!("   Batch              - /batch");
     info!("   WebSocket          - /ws\n");
 
     // Start async HTTP server
@@ -307,13 +717,143 @@ async fn main() {
 
     info!("HTTP server listening on {}", bind_addr);
 
-    // Serve with graceful shutdown
-    if let Err(e) = axum::serve(listener, app).await {
-        error!(error = %e, "HTTP server error");
-        std::process::exit(1);
+    // Serve until SIGTERM/Ctrl-C, then stop accepting and drain in-flight requests
+    let server_shutdown = wait_for_shutdown(shutdown_rx.clone());
+    let mut server = tokio::spawn(async move {
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(server_shutdown)
+        .await
+    });
+
+    tokio::select! {
+        result = &mut server => {
+            // The server only returns on its own if it failed
+            if let Ok(Err(e)) = result {
+                error!(error = %e, "HTTP server error");
+            }
+            std::process::exit(1);
+        }
+        _ = shutdown_signal() => {
+            info!("Shutdown signal received, draining connections");
+        }
+    }
+
+    let _ = shutdown_tx.send(true);
+    let grace_period = Duration::from_secs(SHUTDOWN_GRACE_PERIOD_SECONDS);
+
+    // Long-lived connections (WebSockets) would hold the drain open forever, so bound it
+    match tokio::time::timeout(grace_period, server).await {
+        Ok(Ok(Err(e))) => error!(error = %e, "HTTP server error during shutdown"),
+        Err(_) => warn!("HTTP connections still open after grace period, closing"),
+        _ => {}
+    }
+
+    // Persist buffered audit events (their blocks are written to RocksDB before returning)
+    if let Err(e) = audit_logger.flush_all_batches().await {
+        error!(error = %e, "Failed to flush audit logs during shutdown");
+    }
+
+    // Queued submissions only live in memory: seal them now rather than waiting out the interval
+    if let Err(e) = p2p_node.seal_pending_block(true).await {
+        error!(error = %e, "Failed to seal pending collections during shutdown");
+    }
+
+    if tokio::time::timeout(grace_period, join_all(background_tasks))
+        .await
+        .is_err()
+    {
+        warn!("Background tasks still running after grace period");
+    }
+
+    info!("Shutdown complete");
+}
+
+/// Install the global tracing subscriber with the configured format and level filter
+fn init_tracing(log_config: &LogConfig) -> Result<(), String> {
+    let filter = tracing_subscriber::EnvFilter::try_new(&log_config.level)
+        .map_err(|e| format!("Invalid LOG_LEVEL: {}", e))?;
+    let subscriber = tracing_subscriber::fmt().with_env_filter(filter);
+
+    match log_config.format {
+        LogFormat::Pretty => subscriber.init(),
+        // Event fields sit at the top level next to timestamp/level/target/message; the
+        // enclosing span (e.g. the request span with request_id) is nested under "span"
+        LogFormat::Json => subscriber
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .init(),
+    }
+    Ok(())
+}
+
+/// Resolve on SIGTERM (Kubernetes/Docker stop) or Ctrl-C
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!(error = %e, "Failed to listen for Ctrl-C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
     }
 }
 
+/// Resolve once shutdown has been signalled on the watch channel
+async fn wait_for_shutdown(mut shutdown: watch::Receiver<bool>) {
+    // An error means the sender is gone, which also means we're shutting down
+    let _ = shutdown.wait_for(|stopping| *stopping).await;
+}
+
+/// Run `task` every `interval_seconds` until shutdown is signalled
+///
+/// An iteration already in progress completes before the loop exits.
+fn spawn_periodic<F, Fut>(
+    interval_seconds: u64,
+    shutdown: watch::Receiver<bool>,
+    mut task: F,
+) -> JoinHandle<()>
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_seconds));
+        let stopped = wait_for_shutdown(shutdown);
+        tokio::pin!(stopped);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => task().await,
+                _ = &mut stopped => break,
+            }
+        }
+    })
+}
+
 /// Get all available migrations
 ///
 /// This function should be updated to include all migration files.
@@ -322,6 +862,7 @@ fn get_available_migrations() -> Vec<Box<dyn Migration>> {
     vec![
         // Example migration demonstrating the migration system
         Box::new(migrations::Migration20240101120000AddMetadataIndex::new()),
+        Box::new(migrations::Migration20261014120000MaterializeBlindIndexes::new()),
         // Add new migrations here in chronological order (oldest first)
         // Example:
         // Box::new(migrations::Migration20240102000000AddAuditTables::new()),
@@ -0,0 +1,192 @@
+//! Migration: materialize_blind_indexes
+//!
+//! Description: Backfills `legacy_blind_index:{block_hash}` → `[owner blind indexes]` for
+//! blocks stored without blind indexes (created before blocks carried an owner filter), so
+//! account and collection lookups can skip them instead of attempting a decrypt on every one.
+//!
+//! Blocks themselves are never rewritten: their blind indexes are covered by the merkle root
+//! and the block hash. The backfill is node-local derived data built from the plaintext
+//! owner hashes in each envelope, so it reveals nothing the stored blocks do not.
+//!
+//! Safety: This migration has direct RocksDB access and runs with full privileges.
+//! Ensure all operations are idempotent and can be safely rolled back.
+
+use crate::constants::LEGACY_BLIND_INDEX_PREFIX;
+use crate::storage::BlockchainStore;
+use crate::storage::Migration;
+use crate::types::{GoudChainError, Result};
+use rocksdb::WriteBatch;
+use tracing::warn;
+
+pub struct Migration20261014120000MaterializeBlindIndexes;
+
+impl Migration20261014120000MaterializeBlindIndexes {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Migration for Migration20261014120000MaterializeBlindIndexes {
+    fn version(&self) -> &str {
+        "20261014120000"
+    }
+
+    fn description(&self) -> &str {
+        "materialize_blind_indexes"
+    }
+
+    fn up(&self, store: &BlockchainStore) -> Result<()> {
+        let db = store.get_db();
+        let mut batch = WriteBatch::default();
+
+        for block in store.load_chain()? {
+            if !block.blind_indexes.is_empty() {
+                continue; // Already filterable on its own
+            }
+
+            // Unreadable blocks keep the decrypt-everything fallback
+            let indexes = match block.owner_blind_indexes() {
+                Ok(indexes) => indexes,
+                Err(e) => {
+                    warn!(
+                        block_index = block.index,
+                        error = %e,
+                        "Cannot backfill blind indexes of block with unreadable envelope container"
+                    );
+                    continue;
+                }
+            };
+
+            // Derived from the block alone, so re-running overwrites with identical values
+            let key = format!("{}{}", LEGACY_BLIND_INDEX_PREFIX, block.hash);
+            let serialized = bincode::serialize(&indexes)
+                .map_err(|e| GoudChainError::SerializationError(e.to_string()))?;
+            batch.put(key.as_bytes(), serialized);
+        }
+
+        // Atomic write
+        db.write(batch)
+            .map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn down(&self, store: &BlockchainStore) -> Result<()> {
+        // The backfill is derived data, so dropping every entry restores the pre-migration state
+        let db = store.get_db();
+        let mut batch = WriteBatch::default();
+
+        for item in db.prefix_iterator(LEGACY_BLIND_INDEX_PREFIX.as_bytes()) {
+            let (key, _) = item.map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+            if !key.starts_with(LEGACY_BLIND_INDEX_PREFIX.as_bytes()) {
+                break;
+            }
+            batch.delete(&key);
+        }
+
+        // Atomic write
+        db.write(batch)
+            .map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{generate_account_blind_index_with_salt, generate_signing_key};
+    use crate::domain::block::BlockConfig;
+    use crate::domain::envelope::CollectionEnvelope;
+    use crate::domain::{Block, EncryptedCollection};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn create_test_store() -> (TempDir, Arc<BlockchainStore>) {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().join("rocksdb");
+        let store = BlockchainStore::new(&temp_path).unwrap();
+        (temp_dir, Arc::new(store))
+    }
+
+    fn save_block_for_owner(
+        store: &BlockchainStore,
+        index: u64,
+        owner_hash: &str,
+        blind_indexes: Vec<String>,
+    ) -> Block {
+        let signing_key = generate_signing_key();
+        let collection = EncryptedCollection::new(
+            "Label".to_string(),
+            "[]".to_string(),
+            None,
+            "application/json",
+            None,
+            b"test_api_key",
+            owner_hash.to_string(),
+            &signing_key,
+        )
+        .unwrap();
+        let block = Block::new(BlockConfig {
+            index,
+            account_envelopes: Vec::new(),
+            collection_envelopes: vec![CollectionEnvelope { collection }],
+            previous_hash: "0".repeat(64),
+            validator: "Validator_1".to_string(),
+            blind_indexes,
+            block_salt: format!("salt{}", index),
+        })
+        .unwrap();
+        store.save_block(&block).unwrap();
+        block
+    }
+
+    #[test]
+    fn test_migration_up_down() {
+        let (_temp_dir, store) = create_test_store();
+        let migration = Migration20261014120000MaterializeBlindIndexes::new();
+
+        let legacy = save_block_for_owner(&store, 0, "owner_a", Vec::new());
+        let filtered = save_block_for_owner(&store, 1, "owner_b", vec!["index".to_string()]);
+
+        // Verify nothing is backfilled before migration
+        assert!(store.load_legacy_blind_indexes().unwrap().is_empty());
+
+        // Apply migration: only the block without blind indexes is backfilled
+        migration.up(&store).unwrap();
+        let backfill = store.load_legacy_blind_indexes().unwrap();
+        assert_eq!(backfill.len(), 1);
+        assert_eq!(
+            backfill.get(&legacy.hash),
+            Some(&vec![generate_account_blind_index_with_salt(
+                "owner_a",
+                &legacy.block_salt
+            )
+            .unwrap()])
+        );
+        assert!(!backfill.contains_key(&filtered.hash));
+
+        // Rollback migration
+        migration.down(&store).unwrap();
+        assert!(store.load_legacy_blind_indexes().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migration_idempotent() {
+        let (_temp_dir, store) = create_test_store();
+        let migration = Migration20261014120000MaterializeBlindIndexes::new();
+
+        save_block_for_owner(&store, 0, "owner_a", Vec::new());
+
+        // Apply twice - should not fail or duplicate entries
+        migration.up(&store).unwrap();
+        migration.up(&store).unwrap();
+        let backfill = store.load_legacy_blind_indexes().unwrap();
+        assert_eq!(backfill.len(), 1);
+        assert_eq!(backfill.values().next().unwrap().len(), 1);
+
+        // Rollback twice - should not fail
+        migration.down(&store).unwrap();
+        migration.down(&store).unwrap();
+    }
+}
@@ -1,11 +1,19 @@
 //! Migration: add_metadata_index
 //!
-//! Description: Example migration that demonstrates adding a new RocksDB key namespace
-//! for indexing block metadata. This is a template showing up/down pattern.
+//! Description: Builds the `audit_index:{account_hash}` → `[block_numbers]` index used by
+//! audit log queries, so existing chains do not need a full scan per query.
+//!
+//! Audit batch labels are encrypted with the owner's key, so node operators cannot tell
+//! audit collections apart from ordinary ones. The index therefore lists every block that
+//! holds a collection owned by the account - a superset of its audit blocks, which the
+//! query path filters by label after decryption.
 //!
 //! Safety: This migration has direct RocksDB access and runs with full privileges.
 //! Ensure all operations are idempotent and can be safely rolled back.
 
+use std::collections::BTreeMap;
+
+use crate::constants::AUDIT_INDEX_PREFIX;
 use crate::storage::BlockchainStore;
 use crate::storage::Migration;
 use crate::types::{GoudChainError, Result};
@@ -29,24 +37,40 @@ impl Migration for Migration20240101120000AddMetadataIndex {
     }
 
     fn up(&self, store: &BlockchainStore) -> Result<()> {
-        // Example: Create a new key namespace for metadata indexing
-        // In a real migration, you would:
-        // 1. Read existing data
-        // 2. Transform it
-        // 3. Write new index entries
-        // 4. Use WriteBatch for atomic operations
-
         let db = store.get_db();
-        let mut batch = WriteBatch::default();
 
-        // Example: Add metadata index marker
-        batch.put(b"metadata:index:enabled", b"true");
+        // Group block numbers by collection owner (owner hash is plaintext metadata)
+        let mut index: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+        for block in store.load_chain()? {
+            for envelope in block.get_envelope_container()?.collection_envelopes {
+                index
+                    .entry(envelope.collection.owner_api_key_hash)
+                    .or_default()
+                    .push(block.index);
+            }
+        }
 
-        // Example: Add migration metadata
-        batch.put(
-            b"metadata:index:created_at",
-            chrono::Utc::now().timestamp().to_string().as_bytes(),
-        );
+        let mut batch = WriteBatch::default();
+
+        for (account_hash, mut blocks) in index {
+            let key = format!("{}{}", AUDIT_INDEX_PREFIX, account_hash);
+
+            // Merge with any entries already written so re-running is harmless
+            if let Some(bytes) = db
+                .get(key.as_bytes())
+                .map_err(|e| GoudChainError::RocksDbError(e.to_string()))?
+            {
+                let existing: Vec<u64> = bincode::deserialize(&bytes)
+                    .map_err(|e| GoudChainError::DeserializationError(e.to_string()))?;
+                blocks.extend(existing);
+            }
+            blocks.sort_unstable();
+            blocks.dedup();
+
+            let serialized = bincode::serialize(&blocks)
+                .map_err(|e| GoudChainError::SerializationError(e.to_string()))?;
+            batch.put(key.as_bytes(), serialized);
+        }
 
         // Atomic write
         db.write(batch)
@@ -56,15 +80,17 @@ impl Migration for Migration20240101120000AddMetadataIndex {
     }
 
     fn down(&self, store: &BlockchainStore) -> Result<()> {
-        // Revert changes made in up()
-        // This MUST restore database to exact state before migration
-
+        // The index is derived data, so dropping every entry restores the pre-migration state
         let db = store.get_db();
         let mut batch = WriteBatch::default();
 
-        // Remove all keys created in up()
-        batch.delete(b"metadata:index:enabled");
-        batch.delete(b"metadata:index:created_at");
+        for item in db.prefix_iterator(AUDIT_INDEX_PREFIX.as_bytes()) {
+            let (key, _) = item.map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+            if !key.starts_with(AUDIT_INDEX_PREFIX.as_bytes()) {
+                break;
+            }
+            batch.delete(&key);
+        }
 
         // Atomic write
         db.write(batch)
@@ -77,40 +103,76 @@ impl Migration for Migration20240101120000AddMetadataIndex {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::generate_signing_key;
+    use crate::domain::block::BlockConfig;
+    use crate::domain::envelope::CollectionEnvelope;
+    use crate::domain::{Block, EncryptedCollection};
     use std::sync::Arc;
     use tempfile::TempDir;
 
     fn create_test_store() -> (TempDir, Arc<BlockchainStore>) {
         let temp_dir = TempDir::new().unwrap();
         let temp_path = temp_dir.path().join("rocksdb");
-        let store = BlockchainStore::new_with_path(&temp_path.to_string_lossy()).unwrap();
+        let store = BlockchainStore::new(&temp_path).unwrap();
         (temp_dir, Arc::new(store))
     }
 
+    fn save_block_for_owner(store: &BlockchainStore, index: u64, owner_hash: &str) {
+        let signing_key = generate_signing_key();
+        let collection = EncryptedCollection::new(
+            "AUDIT:Batch".to_string(),
+            "[]".to_string(),
+            None,
+            "application/json",
+            None,
+            b"test_api_key",
+            owner_hash.to_string(),
+            &signing_key,
+        )
+        .unwrap();
+        let block = Block::new(BlockConfig {
+            index,
+            account_envelopes: Vec::new(),
+            collection_envelopes: vec![CollectionEnvelope { collection }],
+            previous_hash: "0".repeat(64),
+            validator: "Validator_1".to_string(),
+            blind_indexes: Vec::new(),
+            block_salt: "salt".to_string(),
+        })
+        .unwrap();
+        store.save_block(&block).unwrap();
+    }
+
+    fn read_index(store: &BlockchainStore, owner_hash: &str) -> Option<Vec<u64>> {
+        let key = format!("{}{}", AUDIT_INDEX_PREFIX, owner_hash);
+        store
+            .get_db()
+            .get(key.as_bytes())
+            .unwrap()
+            .map(|bytes| bincode::deserialize(&bytes).unwrap())
+    }
+
     #[test]
     fn test_migration_up_down() {
         let (_temp_dir, store) = create_test_store();
         let migration = Migration20240101120000AddMetadataIndex::new();
 
-        let db = store.get_db();
+        save_block_for_owner(&store, 0, "owner_a");
+        save_block_for_owner(&store, 1, "owner_b");
+        save_block_for_owner(&store, 2, "owner_a");
 
-        // Verify keys don't exist before migration
-        assert!(db.get(b"metadata:index:enabled").unwrap().is_none());
+        // Verify index doesn't exist before migration
+        assert!(read_index(&store, "owner_a").is_none());
 
         // Apply migration
         migration.up(&store).unwrap();
-
-        // Verify keys created
-        let enabled = db.get(b"metadata:index:enabled").unwrap();
-        assert!(enabled.is_some());
-        assert_eq!(&enabled.unwrap()[..], b"true");
+        assert_eq!(read_index(&store, "owner_a"), Some(vec![0, 2]));
+        assert_eq!(read_index(&store, "owner_b"), Some(vec![1]));
 
         // Rollback migration
         migration.down(&store).unwrap();
-
-        // Verify keys removed
-        assert!(db.get(b"metadata:index:enabled").unwrap().is_none());
-        assert!(db.get(b"metadata:index:created_at").unwrap().is_none());
+        assert!(read_index(&store, "owner_a").is_none());
+        assert!(read_index(&store, "owner_b").is_none());
     }
 
     #[test]
@@ -118,9 +180,12 @@ mod tests {
         let (_temp_dir, store) = create_test_store();
         let migration = Migration20240101120000AddMetadataIndex::new();
 
-        // Apply twice - should not fail
+        save_block_for_owner(&store, 0, "owner_a");
+
+        // Apply twice - should not fail or duplicate entries
         migration.up(&store).unwrap();
         migration.up(&store).unwrap();
+        assert_eq!(read_index(&store, "owner_a"), Some(vec![0]));
 
         // Rollback twice - should not fail
         migration.down(&store).unwrap();
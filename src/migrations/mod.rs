@@ -7,6 +7,9 @@
 
 // Example migrations (add new migrations as modules here)
 pub mod example_20240101120000_add_metadata_index;
+#[path = "20261014120000_materialize_blind_indexes.rs"]
+pub mod materialize_blind_indexes;
 
 // Re-export migrations for easy registration
 pub use example_20240101120000_add_metadata_index::Migration20240101120000AddMetadataIndex;
+pub use materialize_blind_indexes::Migration20261014120000MaterializeBlindIndexes;
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::domain::{Block, EncryptedCollection, UserAccount};
+use crate::domain::{Block, BlockHeader, EncryptedCollection, UserAccount};
 
 /// P2P network message types
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +9,11 @@ pub enum P2PMessage {
     NewBlock(Block),
     RequestChain,
     ResponseChain(Vec<Block>),
+    RequestBlocksFrom(u64), // Requester's current height (latest block index)
+    ResponseBlockRange {
+        start: u64,         // Index of the first block in `blocks`
+        blocks: Vec<Block>, // Capped at MAX_BLOCKS_PER_SYNC_RESPONSE
+    },
     Peers(Vec<String>),
     NewAccount(UserAccount),
     NewCollection(EncryptedCollection),
@@ -24,4 +29,18 @@ pub enum P2PMessage {
     ChallengeResponse {
         signature: String, // Signature of the nonce
     },
+
+    // Peer exchange (answered with `Peers`)
+    GetPeers,
+
+    // Liveness check: Pong echoes the Ping nonce
+    Ping(u64),
+    Pong(u64),
+
+    // Header-first sync: headers from the given index (capped at MAX_HEADERS_PER_SYNC_RESPONSE)
+    RequestHeaders(u64),
+    ResponseHeaders(Vec<BlockHeader>),
+
+    // Sync handshake: sender's genesis hash, answered with the receiver's
+    GenesisHash(String),
 }
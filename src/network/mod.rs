@@ -1,5 +1,7 @@
 pub mod messages;
 pub mod p2p;
+pub mod transport;
 
 // Re-export commonly used types
 pub use p2p::P2PNode;
+pub use transport::P2PTransport;
@@ -1,20 +1,32 @@
-use std::collections::HashMap;
+use chrono::Utc;
+use futures_util::future::join_all;
+use lru::LruCache;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::io::Read;
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::net::{TcpListener, TcpStream};
-use tokio::sync::{Mutex, RwLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::{Mutex, RwLock, Semaphore};
 use tokio::time::{timeout, Duration};
 use tracing::{error, info, warn};
 
 use crate::constants::{
-    CHECKPOINT_INTERVAL, MAX_MESSAGES_PER_MINUTE, MIN_REPUTATION_THRESHOLD,
-    P2P_CONNECT_TIMEOUT_SECONDS, P2P_READ_TIMEOUT_SECONDS, P2P_WRITE_TIMEOUT_SECONDS,
-    REPUTATION_PENALTY_INVALID_BLOCK, REPUTATION_REWARD_VALID_BLOCK,
+    MAX_BLOCKS_PER_SYNC_RESPONSE, MAX_CONCURRENT_P2P_CONNECTIONS, MAX_HEADERS_PER_SYNC_RESPONSE,
+    MAX_MESSAGES_PER_MINUTE, MAX_P2P_MESSAGE_BYTES, MAX_PEERS, MAX_PEERS_PER_EXCHANGE,
+    MIN_REPUTATION_THRESHOLD, P2P_COMPRESSION_THRESHOLD_BYTES, P2P_READ_TIMEOUT_SECONDS,
+    P2P_WRITE_TIMEOUT_SECONDS, PEER_PING_TIMEOUT_SECONDS, READINESS_MAX_BLOCKS_BEHIND,
+    REPUTATION_PENALTY_INVALID_BLOCK, REPUTATION_PENALTY_MISSED_PING,
+    REPUTATION_REWARD_VALID_BLOCK, SEEN_BLOCK_CACHE_SIZE, ZSTD_COMPRESSION_LEVEL,
 };
-use crate::domain::{Block, Blockchain};
+use crate::domain::{Block, BlockHeader, Blockchain};
 use crate::network::messages::P2PMessage;
-use crate::storage::BlockchainStore;
+use crate::network::transport::{P2PTransport, PeerStream};
+use crate::storage::{BlockchainStore, PeerStore};
 use crate::types::{GoudChainError, Result};
 
 /// Rate limiting tracker for a single peer
@@ -57,6 +69,109 @@ impl RateLimitTracker {
     }
 }
 
+/// Liveness data from the most recent successful ping
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerLiveness {
+    pub last_seen: i64, // Unix timestamp in seconds
+    pub rtt_ms: u64,
+}
+
+/// Outcome of validating a block from a peer against our current chain tip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockCheck {
+    Valid,
+    NonSequential, // Index doesn't follow our tip (timing issue, not malicious)
+    Diverged,      // previous_hash doesn't link to our tip
+    Invalid,       // Hash or merkle root doesn't match contents (peer is penalized)
+}
+
+/// Validate that `block` can be appended directly after `latest`
+fn check_next_block(latest: &Block, block: &Block, peer_addr: &str) -> BlockCheck {
+    // Validate block index is sequential
+    if block.index != latest.index + 1 {
+        warn!(
+            peer = %peer_addr,
+            block_index = block.index,
+            expected_index = latest.index + 1,
+            "Rejected block: non-sequential index"
+        );
+        return BlockCheck::NonSequential;
+    }
+
+    // Validate previous_hash links to our latest block
+    if block.previous_hash != latest.hash {
+        warn!(
+            peer = %peer_addr,
+            block_index = block.index,
+            block_previous_hash = %block.previous_hash,
+            our_latest_hash = %latest.hash,
+            "Rejected block: previous_hash mismatch - chain has diverged"
+        );
+        return BlockCheck::Diverged;
+    }
+
+    // Validate block hash is correct
+    let calculated_hash = block.calculate_hash();
+    if block.hash != calculated_hash {
+        warn!(
+            peer = %peer_addr,
+            block_index = block.index,
+            claimed_hash = %block.hash,
+            calculated_hash = %calculated_hash,
+            "Rejected block: invalid hash"
+        );
+        return BlockCheck::Invalid;
+    }
+
+    // Validate merkle root
+    let calculated_merkle =
+        Block::calculate_merkle_root(&block.encrypted_block_data, &block.blind_indexes);
+    if block.merkle_root != calculated_merkle {
+        warn!(
+            peer = %peer_addr,
+            block_index = block.index,
+            claimed_merkle = %block.merkle_root,
+            calculated_merkle = %calculated_merkle,
+            "Rejected block: invalid merkle root"
+        );
+        return BlockCheck::Invalid;
+    }
+
+    BlockCheck::Valid
+}
+
+/// Callback for peer set changes (WebSocket `peer_update` events)
+/// Keeps the network layer independent of the presentation layer (Layer 4 doesn't depend on Layer 5)
+/// Parameter: the active peer list after the change
+pub type PeerUpdateCallback = Arc<dyn Fn(Vec<String>) + Send + Sync>;
+
+/// Callback for chain reorganizations (WebSocket `chain_reorg` events)
+/// Parameters: old tip hash, new tip hash, number of our blocks rolled back
+pub type ReorgCallback = Arc<dyn Fn(String, String, u64) + Send + Sync>;
+
+/// Network counters exported through `/metrics/prometheus`
+/// Plain atomics, so recording never contends the peer-state mutexes
+#[derive(Debug, Default)]
+pub struct P2PStats {
+    messages_received: AtomicU64,
+    blocks_broadcast: AtomicU64,
+    blocks_received: AtomicU64,
+    blocks_rejected: AtomicU64,
+    chain_requests_served: AtomicU64,
+    rate_limited: AtomicU64,
+    reputation_penalties: AtomicU64,
+}
+
+impl P2PStats {
+    fn record(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Frame body flags (first byte after the length prefix)
+const FRAME_FLAG_RAW: u8 = 0;
+const FRAME_FLAG_ZSTD: u8 = 1;
+
 pub struct P2PNode {
     pub peers: Arc<Mutex<Vec<String>>>,
     pub blockchain: Arc<RwLock<Blockchain>>,
@@ -64,6 +179,20 @@ pub struct P2PNode {
     pub peer_reputation: Arc<Mutex<HashMap<String, i32>>>,
     pub rate_limiters: Arc<Mutex<HashMap<String, RateLimitTracker>>>,
     pub blacklist: Arc<Mutex<Vec<String>>>, // Permanently banned peer addresses
+    pub parked_peers: Arc<Mutex<Vec<String>>>, // Unresponsive peers, restored when they answer a ping
+    pub peer_liveness: Arc<Mutex<HashMap<String, PeerLiveness>>>,
+    peer_store: Arc<PeerStore>, // Write-through persistence for reputation and blacklist
+    transport: Arc<P2PTransport>, // Plaintext TCP or pinned TLS
+    seen_blocks: Arc<Mutex<LruCache<String, ()>>>, // Recently processed/broadcast block hashes
+    connection_limiter: Arc<Semaphore>, // Caps concurrent inbound connections
+    advertised_addr: Option<String>, // Our own address, never added as a peer
+    configured_peers: HashSet<String>, // Seed peers from PEERS, trusted to share peer lists
+    discovered_this_round: Arc<Mutex<HashSet<String>>>, // Held back from sharing until next round
+    best_peer_height: Arc<AtomicU64>, // Highest block index seen from any peer
+    last_successful_sync: Arc<AtomicI64>, // Unix timestamp of the last caught-up sync (0 = never)
+    peer_update_callback: Option<PeerUpdateCallback>, // Notified when the peer set or blacklist changes
+    reorg_callback: Option<ReorgCallback>, // Notified when a sync rolls back blocks from our tip
+    stats: Arc<P2PStats>,                  // Message, block and penalty counters for Prometheus
 }
 
 impl P2PNode {
@@ -72,33 +201,471 @@ impl P2PNode {
         blockchain: Arc<RwLock<Blockchain>>,
         blockchain_store: Arc<BlockchainStore>,
         peers: Vec<String>,
+        advertised_addr: Option<String>,
+        transport: P2PTransport,
+        peer_update_callback: Option<PeerUpdateCallback>,
+        reorg_callback: Option<ReorgCallback>,
     ) -> Self {
         if !peers.is_empty() {
             info!(peers = ?peers, "Configured peers");
         }
 
+        let peer_store = Arc::new(PeerStore::new(blockchain_store.get_db()));
+        let reputation = peer_store.load_reputation().unwrap_or_else(|e| {
+            error!(error = %e, "Failed to load peer reputation, starting neutral");
+            HashMap::new()
+        });
+        let blacklist = peer_store.load_blacklist().unwrap_or_else(|e| {
+            error!(error = %e, "Failed to load peer blacklist, starting empty");
+            Vec::new()
+        });
+        if !reputation.is_empty() || !blacklist.is_empty() {
+            info!(
+                reputation_entries = reputation.len(),
+                blacklisted = blacklist.len(),
+                "Restored persisted peer state"
+            );
+        }
+
+        let configured_peers = peers.iter().cloned().collect();
+
         P2PNode {
             peers: Arc::new(Mutex::new(peers)),
             blockchain,
             blockchain_store,
-            peer_reputation: Arc::new(Mutex::new(HashMap::new())),
+            peer_reputation: Arc::new(Mutex::new(reputation)),
             rate_limiters: Arc::new(Mutex::new(HashMap::new())),
-            blacklist: Arc::new(Mutex::new(Vec::new())),
+            blacklist: Arc::new(Mutex::new(blacklist)),
+            parked_peers: Arc::new(Mutex::new(Vec::new())),
+            peer_liveness: Arc::new(Mutex::new(HashMap::new())),
+            peer_store,
+            transport: Arc::new(transport),
+            seen_blocks: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(SEEN_BLOCK_CACHE_SIZE).unwrap(),
+            ))),
+            connection_limiter: Arc::new(Semaphore::new(MAX_CONCURRENT_P2P_CONNECTIONS)),
+            advertised_addr,
+            configured_peers,
+            discovered_this_round: Arc::new(Mutex::new(HashSet::new())),
+            best_peer_height: Arc::new(AtomicU64::new(0)),
+            last_successful_sync: Arc::new(AtomicI64::new(0)),
+            peer_update_callback,
+            reorg_callback,
+            stats: Arc::new(P2PStats::default()),
+        }
+    }
+
+    /// Tell subscribers the peer topology changed
+    fn notify_peer_update(&self, peers: &[String]) {
+        if let Some(callback) = &self.peer_update_callback {
+            callback(peers.to_vec());
+        }
+    }
+
+    /// Highest block index this node has seen from any peer (0 until a peer reports one)
+    pub fn best_peer_height(&self) -> u64 {
+        self.best_peer_height.load(Ordering::Relaxed)
+    }
+
+    /// When a sync last caught up with a peer's tip (None if it never has)
+    pub fn last_successful_sync(&self) -> Option<i64> {
+        Some(self.last_successful_sync.load(Ordering::Relaxed)).filter(|ts| *ts > 0)
+    }
+
+    /// Whether the node is caught up enough to serve traffic
+    ///
+    /// Ready once any sync has caught up with a peer, or when a known peer tip
+    /// is within READINESS_MAX_BLOCKS_BEHIND blocks of `latest_block`.
+    pub fn is_ready(&self, latest_block: u64) -> bool {
+        let best_peer_height = self.best_peer_height();
+        self.last_successful_sync().is_some()
+            || (best_peer_height > 0
+                && best_peer_height <= latest_block + READINESS_MAX_BLOCKS_BEHIND)
+    }
+
+    /// Ping active and parked peers, record round-trip latency and update the active set
+    ///
+    /// Active peers that miss a ping lose reputation and are parked once they fall below
+    /// MIN_REPUTATION_THRESHOLD (they are not blacklisted). Parked peers that answer are
+    /// restored at neutral reputation.
+    pub async fn ping_peers(&self) {
+        let active = self.peers.lock().await.clone();
+        let parked = self.parked_peers.lock().await.clone();
+
+        let results =
+            join_all(active.iter().chain(parked.iter()).map(|peer| async move {
+                (peer.clone(), Self::ping(&self.transport, peer).await)
+            }))
+            .await;
+
+        let now = Utc::now().timestamp();
+        let mut peers = self.peers.lock().await;
+        let mut parked_peers = self.parked_peers.lock().await;
+        let mut reputation = self.peer_reputation.lock().await;
+        let mut liveness = self.peer_liveness.lock().await;
+        let mut topology_changed = false;
+
+        for (peer, rtt_ms) in results {
+            let was_parked = parked_peers.contains(&peer);
+
+            match rtt_ms {
+                Some(rtt_ms) => {
+                    liveness.insert(
+                        peer.clone(),
+                        PeerLiveness {
+                            last_seen: now,
+                            rtt_ms,
+                        },
+                    );
+                    if was_parked {
+                        parked_peers.retain(|p| p != &peer);
+                        if !peers.contains(&peer) {
+                            peers.push(peer.clone());
+                        }
+                        reputation.insert(peer.clone(), 0);
+                        Self::persist_reputation(&self.peer_store, &peer, 0);
+                        topology_changed = true;
+                        info!(peer = %peer, rtt_ms = rtt_ms, "Parked peer is responsive again");
+                    }
+                }
+                None if !was_parked => {
+                    let score = reputation.entry(peer.clone()).or_insert(0);
+                    *score += REPUTATION_PENALTY_MISSED_PING;
+                    P2PStats::record(&self.stats.reputation_penalties);
+                    Self::persist_reputation(&self.peer_store, &peer, *score);
+
+                    if *score < MIN_REPUTATION_THRESHOLD {
+                        warn!(peer = %peer, reputation = *score, "Parking unresponsive peer");
+                        peers.retain(|p| p != &peer);
+                        parked_peers.push(peer);
+                        topology_changed = true;
+                    }
+                }
+                None => {}
+            }
+        }
+
+        if topology_changed {
+            self.notify_peer_update(&peers);
+        }
+    }
+
+    /// Render P2P counters and gauges in Prometheus text format
+    pub async fn prometheus_metrics(&self) -> String {
+        let counter = |value: &AtomicU64| value.load(Ordering::Relaxed);
+        let active_connections = MAX_CONCURRENT_P2P_CONNECTIONS
+            .saturating_sub(self.connection_limiter.available_permits());
+        let blacklisted = self.blacklist.lock().await.len();
+        let parked = self.parked_peers.lock().await.len();
+
+        format!(
+            "# HELP goud_p2p_messages_received_total P2P messages received from peers\n\
+             # TYPE goud_p2p_messages_received_total counter\n\
+             goud_p2p_messages_received_total {}\n\
+             # HELP goud_p2p_blocks_broadcast_total Blocks this node broadcast to its peers\n\
+             # TYPE goud_p2p_blocks_broadcast_total counter\n\
+             goud_p2p_blocks_broadcast_total {}\n\
+             # HELP goud_p2p_blocks_received_total Blocks from peers added to the chain (gossip and sync)\n\
+             # TYPE goud_p2p_blocks_received_total counter\n\
+             goud_p2p_blocks_received_total {}\n\
+             # HELP goud_p2p_blocks_rejected_total Blocks from peers rejected as invalid\n\
+             # TYPE goud_p2p_blocks_rejected_total counter\n\
+             goud_p2p_blocks_rejected_total {}\n\
+             # HELP goud_p2p_chain_requests_served_total Chain, block range and header requests answered\n\
+             # TYPE goud_p2p_chain_requests_served_total counter\n\
+             goud_p2p_chain_requests_served_total {}\n\
+             # HELP goud_p2p_rate_limited_total Peer messages dropped by the per-peer rate limit\n\
+             # TYPE goud_p2p_rate_limited_total counter\n\
+             goud_p2p_rate_limited_total {}\n\
+             # HELP goud_p2p_reputation_penalties_total Reputation penalties applied to peers\n\
+             # TYPE goud_p2p_reputation_penalties_total counter\n\
+             goud_p2p_reputation_penalties_total {}\n\
+             # HELP goud_p2p_active_connections Inbound P2P connections being handled\n\
+             # TYPE goud_p2p_active_connections gauge\n\
+             goud_p2p_active_connections {}\n\
+             # HELP goud_p2p_blacklisted_peers Permanently banned peers\n\
+             # TYPE goud_p2p_blacklisted_peers gauge\n\
+             goud_p2p_blacklisted_peers {}\n\
+             # HELP goud_p2p_parked_peers Unresponsive peers waiting to answer a ping\n\
+             # TYPE goud_p2p_parked_peers gauge\n\
+             goud_p2p_parked_peers {}\n",
+            counter(&self.stats.messages_received),
+            counter(&self.stats.blocks_broadcast),
+            counter(&self.stats.blocks_received),
+            counter(&self.stats.blocks_rejected),
+            counter(&self.stats.chain_requests_served),
+            counter(&self.stats.rate_limited),
+            counter(&self.stats.reputation_penalties),
+            active_connections,
+            blacklisted,
+            parked
+        )
+    }
+
+    /// Apply a reputation change and write the new score through to RocksDB
+    async fn adjust_reputation(
+        reputation: &Arc<Mutex<HashMap<String, i32>>>,
+        peer_store: &PeerStore,
+        stats: &P2PStats,
+        peer: &str,
+        delta: i32,
+    ) -> i32 {
+        if delta < 0 {
+            P2PStats::record(&stats.reputation_penalties);
+        }
+        let mut r = reputation.lock().await;
+        let score = r.entry(peer.to_string()).or_insert(0);
+        *score += delta;
+        Self::persist_reputation(peer_store, peer, *score);
+        *score
+    }
+
+    /// Persist a reputation score, logging (not failing) on storage errors
+    fn persist_reputation(peer_store: &PeerStore, peer: &str, score: i32) {
+        if let Err(e) = peer_store.save_reputation(peer, score) {
+            error!(peer = %peer, error = %e, "Failed to persist peer reputation");
+        }
+    }
+
+    /// Permanently ban a peer, persisting the ban so it survives restarts
+    async fn blacklist_peer(&self, peer: &str) {
+        let mut blacklist = self.blacklist.lock().await;
+        if blacklist.iter().any(|p| p == peer) {
+            return;
         }
+        blacklist.push(peer.to_string());
+        drop(blacklist);
+
+        if let Err(e) = self.peer_store.add_to_blacklist(peer) {
+            error!(peer = %peer, error = %e, "Failed to persist peer blacklist entry");
+        }
+        self.notify_peer_update(&self.peers.lock().await);
+    }
+
+    /// Send a Ping and wait for the matching Pong, returning the round-trip time in ms
+    async fn ping(transport: &P2PTransport, peer: &str) -> Option<u64> {
+        let nonce: u64 = rand::random();
+        let started = Instant::now();
+
+        match timeout(
+            Duration::from_secs(PEER_PING_TIMEOUT_SECONDS),
+            Self::send_and_receive(transport, peer, &P2PMessage::Ping(nonce)),
+        )
+        .await
+        {
+            Ok(Ok(P2PMessage::Pong(echo))) if echo == nonce => {
+                Some(started.elapsed().as_millis() as u64)
+            }
+            Ok(Ok(_)) => {
+                warn!(peer = %peer, "Invalid response to ping");
+                None
+            }
+            Ok(Err(e)) => {
+                warn!(peer = %peer, error = %e, "Ping failed");
+                None
+            }
+            Err(_) => {
+                warn!(peer = %peer, "Ping timed out");
+                None
+            }
+        }
+    }
+
+    /// Run one peer-exchange round: ask every trusted peer for its peer list and merge new entries
+    ///
+    /// Peers discovered in this round are not queried or re-shared until the next round,
+    /// so a burst of new addresses can't cascade through the mesh in a single pass.
+    /// Returns the number of newly added peers.
+    pub async fn exchange_peers(&self) -> usize {
+        self.start_exchange_round().await;
+        let peers = self.peer_list_sources().await;
+
+        let responses = join_all(peers.iter().map(|peer| async move {
+            match Self::send_and_receive(&self.transport, peer, &P2PMessage::GetPeers).await {
+                Ok(P2PMessage::Peers(addresses)) => addresses,
+                Ok(_) => {
+                    warn!(peer = %peer, "Unexpected response to peer exchange");
+                    Vec::new()
+                }
+                Err(e) => {
+                    warn!(peer = %peer, error = %e, "Failed to exchange peers");
+                    Vec::new()
+                }
+            }
+        }))
+        .await;
+
+        let mut added = 0;
+        for addresses in responses {
+            added += self.merge_peers(addresses).await;
+        }
+
+        if added > 0 {
+            info!(added = added, "Discovered new peers via peer exchange");
+        }
+        added
+    }
+
+    /// Peers whose peer lists we accept: configured seeds, or any peer when pinned TLS has
+    /// authenticated it. Otherwise a discovered address could inject arbitrary host:port
+    /// entries (eclipse/SSRF).
+    async fn peer_list_sources(&self) -> Vec<String> {
+        let authenticated = self.transport.is_tls();
+        self.peers
+            .lock()
+            .await
+            .iter()
+            .filter(|peer| authenticated || self.configured_peers.contains(*peer))
+            .cloned()
+            .collect()
+    }
+
+    /// Discoveries from the previous round become shareable
+    async fn start_exchange_round(&self) {
+        self.discovered_this_round.lock().await.clear();
+    }
+
+    /// Peers we tell others about (excludes addresses learned in the current round)
+    async fn shareable_peers(&self) -> Vec<String> {
+        let discovered = self.discovered_this_round.lock().await.clone();
+        self.peers
+            .lock()
+            .await
+            .iter()
+            .filter(|peer| !discovered.contains(*peer))
+            .take(MAX_PEERS_PER_EXCHANGE)
+            .cloned()
+            .collect()
+    }
+
+    /// Our answer to GetPeers: our advertised address first (so peers dial us where we are
+    /// reachable rather than where we bind), then the peers we share
+    async fn peer_exchange_response(&self) -> Vec<String> {
+        let mut addresses: Vec<String> = self.advertised_addr.iter().cloned().collect();
+        addresses.extend(
+            self.shareable_peers()
+                .await
+                .into_iter()
+                .filter(|peer| self.advertised_addr.as_ref() != Some(peer))
+                .take(MAX_PEERS_PER_EXCHANGE.saturating_sub(addresses.len())),
+        );
+        addresses
+    }
+
+    /// Merge addresses from a Peers response (deduped, capped, skipping ourselves, blacklisted
+    /// and parked peers). New peers start at neutral reputation. Returns how many were added.
+    async fn merge_peers(&self, addresses: Vec<String>) -> usize {
+        let blacklist = self.blacklist.lock().await.clone();
+        let parked = self.parked_peers.lock().await.clone();
+        let mut peers = self.peers.lock().await;
+        let mut discovered = self.discovered_this_round.lock().await;
+        let mut reputation = self.peer_reputation.lock().await;
+        let mut added = 0;
+
+        for address in addresses.into_iter().take(MAX_PEERS_PER_EXCHANGE) {
+            let address = address.trim().to_string();
+
+            if peers.len() >= MAX_PEERS {
+                warn!(
+                    limit = MAX_PEERS,
+                    "Peer list full - ignoring discovered peers"
+                );
+                break;
+            }
+
+            let is_valid = address
+                .rsplit_once(':')
+                .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok());
+            if !is_valid
+                || self.advertised_addr.as_deref() == Some(address.as_str())
+                || peers.contains(&address)
+                || blacklist.contains(&address)
+                || parked.contains(&address)
+            {
+                continue;
+            }
+
+            reputation.entry(address.clone()).or_insert(0);
+            discovered.insert(address.clone());
+            peers.push(address);
+            added += 1;
+        }
+
+        if added > 0 {
+            self.notify_peer_update(&peers);
+        }
+        added
+    }
+
+    /// Seal coalesced pending collections into a block once it is due, then save and broadcast it
+    ///
+    /// Used with MIN_BLOCK_INTERVAL_SECONDS, where submissions are queued instead of each creating
+    /// a block. `force` ignores the interval (graceful shutdown). Returns None when nothing is
+    /// pending, the block isn't due yet, the node is read-only or isn't the next block's validator.
+    pub async fn seal_pending_block(&self, force: bool) -> Result<Option<Block>> {
+        let mut blockchain = self.blockchain.write().await;
+        let next_block_number = blockchain.chain.len() as u64;
+        if blockchain.pending_collections.is_empty()
+            || blockchain.read_only
+            || !(force || blockchain.seal_due_now())
+            || !blockchain
+                .validator_config
+                .is_node_authorized(&blockchain.node_id, next_block_number)
+        {
+            return Ok(None);
+        }
+
+        let collections = blockchain.pending_collections.len();
+        let block = blockchain.add_block()?;
+        if let Err(e) = self.blockchain_store.save_block(&block) {
+            error!(error = %e, "Failed to save sealed block to RocksDB");
+        }
+        if blockchain.is_checkpoint_index(block.index) {
+            if let Err(e) = self
+                .blockchain_store
+                .save_checkpoint(block.index, &block.hash)
+            {
+                error!(error = %e, "Failed to save checkpoint");
+            }
+        }
+        drop(blockchain);
+
+        info!(
+            block = block.index,
+            collections = collections,
+            "Sealed coalesced block"
+        );
+        self.broadcast_block(&block).await;
+        Ok(Some(block))
     }
 
     /// Broadcast a new block to all peers (async)
+    ///
+    /// Each block is broadcast at most once; repeat calls for a recently seen hash are no-ops.
     pub async fn broadcast_block(&self, block: &Block) {
+        if self
+            .seen_blocks
+            .lock()
+            .await
+            .put(block.hash.clone(), ())
+            .is_some()
+        {
+            info!(block_hash = %block.hash, "Block already broadcast, skipping");
+            return;
+        }
+
         let message = P2PMessage::NewBlock(block.clone());
         let peers = self.peers.lock().await.clone();
         let mut handles = vec![];
+        P2PStats::record(&self.stats.blocks_broadcast);
 
         for peer in peers {
             let msg = message.clone();
             let peer_clone = peer.clone();
+            let transport = Arc::clone(&self.transport);
 
             let handle = tokio::spawn(async move {
-                match Self::send_message(&peer_clone, &msg).await {
+                match Self::send_message(&transport, &peer_clone, &msg).await {
                     Ok(_) => {
                         info!(peer = %peer_clone, "Broadcast block");
                     }
@@ -117,84 +684,503 @@ impl P2PNode {
         }
     }
 
-    /// Request the blockchain from all peers (manual sync only - no periodic calls)
+    /// Sync missing blocks from all peers (on startup and via /sync - no periodic calls)
+    ///
+    /// Requests only the blocks after our current tip, paging through the gap
+    /// in chunks of MAX_BLOCKS_PER_SYNC_RESPONSE. Falls back to header-first sync
+    /// when a peer's blocks do not link to our chain (divergence). Records
+    /// `last_successful_sync` when at least one peer's tip was reached.
     pub async fn request_chain_from_peers(&self) {
         let peers = self.peers.lock().await.clone();
         let mut handles = vec![];
 
         for peer in peers {
             let blockchain = Arc::clone(&self.blockchain);
+            let store = Arc::clone(&self.blockchain_store);
             let reputation = Arc::clone(&self.peer_reputation);
-            let peer_clone = peer.clone();
+            let peer_store = Arc::clone(&self.peer_store);
+            let transport = Arc::clone(&self.transport);
+            let best_peer_height = Arc::clone(&self.best_peer_height);
+            let stats = Arc::clone(&self.stats);
+            let reorg_callback = self.reorg_callback.clone();
 
             let handle = tokio::spawn(async move {
-                let message = P2PMessage::RequestChain;
-                match Self::send_and_receive(&peer_clone, &message).await {
-                    Ok(response) => {
-                        if let P2PMessage::ResponseChain(chain) = response {
-                            let mut bc = blockchain.write().await;
-                            match bc.replace_chain(chain) {
-                                Ok(true) => {
-                                    info!(peer = %peer_clone, "Successfully synced chain from peer");
-                                    // Note: Chain replacement means we need to save the entire chain
-                                    // This is a rare operation (only during sync/reorg)
-                                    // For normal block addition, we use incremental saves
-                                    // TODO: Optimize this to only save new blocks
-                                    warn!("Chain replaced - full chain sync to RocksDB not yet implemented");
-                                    // Good peer - increase reputation
-                                    let mut r = reputation.lock().await;
-                                    *r.entry(peer_clone.clone()).or_insert(0) +=
-                                        REPUTATION_REWARD_VALID_BLOCK;
-                                }
-                                Err(e) => {
-                                    warn!(peer = %peer_clone, error = %e, "Failed to replace chain");
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        warn!(peer = %peer_clone, error = %e, "Failed to request chain");
-                    }
-                }
+                Self::sync_from_peer(
+                    &transport,
+                    &peer,
+                    &blockchain,
+                    &store,
+                    &reputation,
+                    &peer_store,
+                    &stats,
+                    &best_peer_height,
+                    reorg_callback.as_ref(),
+                )
+                .await
             });
 
             handles.push(handle);
         }
 
         // Wait for all sync attempts to complete
+        let mut caught_up = handles.is_empty();
         for handle in handles {
-            let _ = handle.await;
+            caught_up |= handle.await.unwrap_or(false);
+        }
+
+        // With no peers there is nothing to catch up on
+        if caught_up {
+            self.last_successful_sync
+                .store(Utc::now().timestamp(), Ordering::Relaxed);
+        }
+    }
+
+    /// Incrementally sync blocks from a single peer
+    /// Returns true once our chain has caught up with the peer's tip
+    #[allow(clippy::too_many_arguments)]
+    async fn sync_from_peer(
+        transport: &P2PTransport,
+        peer: &str,
+        blockchain: &Arc<RwLock<Blockchain>>,
+        store: &Arc<BlockchainStore>,
+        reputation: &Arc<Mutex<HashMap<String, i32>>>,
+        peer_store: &PeerStore,
+        stats: &P2PStats,
+        best_peer_height: &AtomicU64,
+        reorg_callback: Option<&ReorgCallback>,
+    ) -> bool {
+        if !Self::verify_genesis(transport, peer, blockchain).await {
+            return false;
+        }
+
+        loop {
+            let height = match blockchain.read().await.get_latest_block() {
+                Ok(latest) => latest.index,
+                Err(e) => {
+                    error!(error = %e, "Cannot sync without a local chain tip");
+                    return false;
+                }
+            };
+
+            let message = P2PMessage::RequestBlocksFrom(height);
+            let (start, blocks) = match Self::send_and_receive(transport, peer, &message).await {
+                Ok(P2PMessage::ResponseBlockRange { start, blocks }) => (start, blocks),
+                Ok(_) => {
+                    warn!(peer = %peer, "Unexpected response to block range request");
+                    return false;
+                }
+                Err(e) => {
+                    warn!(peer = %peer, error = %e, "Failed to request blocks");
+                    return false;
+                }
+            };
+
+            if blocks.is_empty() {
+                info!(peer = %peer, height = height, "Chain is up to date with peer");
+                return true;
+            }
+
+            if start != height + 1 {
+                warn!(
+                    peer = %peer,
+                    start = start,
+                    expected_start = height + 1,
+                    "Rejected block range: unexpected start index"
+                );
+                return false;
+            }
+
+            let page_len = blocks.len();
+            let mut appended = 0;
+            let mut bc = blockchain.write().await;
+
+            for block in blocks {
+                let latest = match bc.get_latest_block() {
+                    Ok(latest) => latest.clone(),
+                    Err(_) => return false,
+                };
+
+                match check_next_block(&latest, &block, peer) {
+                    BlockCheck::Valid => {
+                        best_peer_height.fetch_max(block.index, Ordering::Relaxed);
+                        Self::append_block(store, &mut bc, block);
+                        P2PStats::record(&stats.blocks_received);
+                        appended += 1;
+                    }
+                    // Another sync appended these blocks concurrently - re-read our tip
+                    BlockCheck::NonSequential => break,
+                    BlockCheck::Diverged => {
+                        drop(bc);
+                        Self::sync_diverged_chain(
+                            transport,
+                            peer,
+                            blockchain,
+                            store,
+                            reputation,
+                            peer_store,
+                            stats,
+                            reorg_callback,
+                        )
+                        .await;
+                        return false;
+                    }
+                    BlockCheck::Invalid => {
+                        P2PStats::record(&stats.blocks_rejected);
+                        Self::adjust_reputation(
+                            reputation,
+                            peer_store,
+                            stats,
+                            peer,
+                            REPUTATION_PENALTY_INVALID_BLOCK,
+                        )
+                        .await;
+                        return false;
+                    }
+                }
+            }
+            drop(bc);
+
+            if appended == 0 {
+                return false;
+            }
+
+            info!(
+                peer = %peer,
+                start = start,
+                count = appended,
+                "Synced block range from peer"
+            );
+
+            // Good peer - increase reputation
+            Self::adjust_reputation(
+                reputation,
+                peer_store,
+                stats,
+                peer,
+                REPUTATION_REWARD_VALID_BLOCK,
+            )
+            .await;
+
+            // A short page means we've reached the peer's tip
+            if page_len < MAX_BLOCKS_PER_SYNC_RESPONSE {
+                return true;
+            }
+        }
+    }
+
+    /// Exchange genesis hashes with a peer before syncing from it
+    ///
+    /// Genesis is deterministic, so a mismatch means configuration drift (different
+    /// SCHEMA_VERSION or validator config): none of the peer's blocks can ever link to ours.
+    async fn verify_genesis(
+        transport: &P2PTransport,
+        peer: &str,
+        blockchain: &Arc<RwLock<Blockchain>>,
+    ) -> bool {
+        let Some(ours) = blockchain.read().await.genesis_hash() else {
+            error!("Cannot sync without a local genesis block");
+            return false;
+        };
+
+        let message = P2PMessage::GenesisHash(ours.clone());
+        match Self::send_and_receive(transport, peer, &message).await {
+            Ok(P2PMessage::GenesisHash(theirs)) if theirs == ours => true,
+            Ok(P2PMessage::GenesisHash(theirs)) => {
+                error!(
+                    peer = %peer,
+                    our_genesis = %ours,
+                    peer_genesis = %theirs,
+                    "FATAL: genesis mismatch with peer, refusing to sync (check SCHEMA_VERSION and validator config)"
+                );
+                false
+            }
+            Ok(_) => {
+                warn!(peer = %peer, "Unexpected response to genesis handshake");
+                false
+            }
+            Err(e) => {
+                warn!(peer = %peer, error = %e, "Genesis handshake failed");
+                false
+            }
         }
     }
 
+    /// Resolve a divergence with a peer using header-first sync
+    ///
+    /// Fetches the peer's headers from our latest checkpoint, validates their
+    /// linkage and validator rotation, then pulls full blocks only from the fork
+    /// point onwards and adopts them if the resulting chain wins (within MAX_REORG_DEPTH).
+    #[allow(clippy::too_many_arguments)]
+    async fn sync_diverged_chain(
+        transport: &P2PTransport,
+        peer: &str,
+        blockchain: &Arc<RwLock<Blockchain>>,
+        store: &Arc<BlockchainStore>,
+        reputation: &Arc<Mutex<HashMap<String, i32>>>,
+        peer_store: &PeerStore,
+        stats: &P2PStats,
+        reorg_callback: Option<&ReorgCallback>,
+    ) {
+        let anchor = blockchain.read().await.header_sync_anchor();
+        let Some(headers) = Self::fetch_headers(transport, peer, anchor).await else {
+            return;
+        };
+
+        let (fork_point, our_len, mut candidate) = {
+            let bc = blockchain.read().await;
+            match bc.find_fork_point(&headers) {
+                Ok(fork_point) => (
+                    fork_point,
+                    bc.chain.len(),
+                    bc.chain[..fork_point as usize].to_vec(),
+                ),
+                Err(e) => {
+                    warn!(peer = %peer, error = %e, "Rejected header chain from peer");
+                    Self::adjust_reputation(
+                        reputation,
+                        peer_store,
+                        stats,
+                        peer,
+                        REPUTATION_PENALTY_INVALID_BLOCK,
+                    )
+                    .await;
+                    return;
+                }
+            }
+        };
+
+        // Headers are contiguous from the anchor, so the fork point indexes into them
+        let missing = &headers[(fork_point - anchor) as usize..];
+        if missing.is_empty() {
+            info!(peer = %peer, "Peer chain has no blocks beyond ours");
+            return;
+        }
+
+        // A shorter chain never replaces ours, so skip downloading its bodies
+        if fork_point as usize + missing.len() < our_len {
+            info!(peer = %peer, "Peer chain is shorter than ours after the fork point");
+            return;
+        }
+
+        info!(
+            peer = %peer,
+            fork_point = fork_point,
+            missing = missing.len(),
+            "Header chain validated, fetching blocks from fork point"
+        );
+
+        while candidate.len() < fork_point as usize + missing.len() {
+            let fetched = candidate.len() - fork_point as usize;
+            let message = P2PMessage::RequestBlocksFrom(missing[fetched].index - 1);
+            let blocks = match Self::send_and_receive(transport, peer, &message).await {
+                Ok(P2PMessage::ResponseBlockRange { blocks, .. }) => blocks,
+                Ok(_) => {
+                    warn!(peer = %peer, "Unexpected response to block range request");
+                    return;
+                }
+                Err(e) => {
+                    warn!(peer = %peer, error = %e, "Failed to request blocks");
+                    return;
+                }
+            };
+
+            if blocks.is_empty() {
+                warn!(peer = %peer, "Peer stopped serving blocks advertised by its headers");
+                return;
+            }
+
+            for block in blocks {
+                let Some(expected) = missing.get(candidate.len() - fork_point as usize) else {
+                    break;
+                };
+                if block.header() != *expected {
+                    warn!(
+                        peer = %peer,
+                        block_index = block.index,
+                        "Rejected block: does not match advertised header"
+                    );
+                    P2PStats::record(&stats.blocks_rejected);
+                    Self::adjust_reputation(
+                        reputation,
+                        peer_store,
+                        stats,
+                        peer,
+                        REPUTATION_PENALTY_INVALID_BLOCK,
+                    )
+                    .await;
+                    return;
+                }
+                candidate.push(block);
+            }
+        }
+
+        let mut bc = blockchain.write().await;
+        let old_tip = bc.chain.last().map(|b| b.hash.clone()).unwrap_or_default();
+        let depth = bc.reorg_depth(&candidate);
+        match bc.replace_chain(candidate) {
+            Ok(true) => {
+                info!(peer = %peer, "Successfully synced chain from peer");
+                // Chain replacement rewrites the entire persisted chain
+                // This is a rare operation (only during sync/reorg)
+                if let Err(e) = store.replace_chain(&bc.chain, &bc.checkpoints) {
+                    error!(error = %e, "Failed to persist replaced chain to RocksDB");
+                }
+                if depth > 0 {
+                    let new_tip = bc.chain.last().map(|b| b.hash.clone()).unwrap_or_default();
+                    warn!(
+                        peer = %peer,
+                        depth,
+                        old_tip = %old_tip,
+                        new_tip = %new_tip,
+                        "Chain reorganization adopted from peer"
+                    );
+                    if let Some(callback) = reorg_callback {
+                        callback(old_tip, new_tip, depth);
+                    }
+                }
+                // Good peer - increase reputation
+                Self::adjust_reputation(
+                    reputation,
+                    peer_store,
+                    stats,
+                    peer,
+                    REPUTATION_REWARD_VALID_BLOCK,
+                )
+                .await;
+            }
+            Err(e) => {
+                warn!(peer = %peer, error = %e, "Failed to replace chain");
+            }
+            _ => {}
+        }
+    }
+
+    /// Page through a peer's block headers starting at `start`
+    async fn fetch_headers(
+        transport: &P2PTransport,
+        peer: &str,
+        start: u64,
+    ) -> Option<Vec<BlockHeader>> {
+        let mut headers = Vec::new();
+
+        loop {
+            let message = P2PMessage::RequestHeaders(start + headers.len() as u64);
+            let page = match Self::send_and_receive(transport, peer, &message).await {
+                Ok(P2PMessage::ResponseHeaders(page)) => page,
+                Ok(_) => {
+                    warn!(peer = %peer, "Unexpected response to header request");
+                    return None;
+                }
+                Err(e) => {
+                    warn!(peer = %peer, error = %e, "Failed to request headers");
+                    return None;
+                }
+            };
+
+            // A short page means we've reached the peer's tip
+            let page_len = page.len();
+            headers.extend(page);
+            if page_len < MAX_HEADERS_PER_SYNC_RESPONSE {
+                return Some(headers);
+            }
+        }
+    }
+
+    /// Append a block received from the network and save it to RocksDB (plus checkpoint if needed)
+    fn append_block(store: &BlockchainStore, blockchain: &mut Blockchain, block: Block) {
+        // Save block to RocksDB (incremental write)
+        if let Err(e) = store.save_block(&block) {
+            error!(error = %e, "Failed to save received block to RocksDB");
+        }
+
+        // Record the checkpoint in memory too, so reorgs can't cross it before a restart
+        if blockchain.is_checkpoint_index(block.index) {
+            blockchain.checkpoints.push(block.hash.clone());
+            if let Err(e) = store.save_checkpoint(block.index, &block.hash) {
+                error!(error = %e, "Failed to save checkpoint");
+            }
+        }
+
+        blockchain.chain.push(block);
+        blockchain.refresh_aggregates();
+    }
+
     /// Start the P2P server to listen for incoming connections (async)
-    pub async fn start_p2p_server(self: Arc<Self>, port: u16) {
-        let bind_addr = format!("0.0.0.0:{}", port);
-        let listener = match TcpListener::bind(&bind_addr).await {
+    ///
+    /// Stops accepting connections once `shutdown` resolves; in-flight handlers finish.
+    pub async fn start_p2p_server(
+        self: Arc<Self>,
+        bind_addr: SocketAddr,
+        shutdown: impl Future<Output = ()> + Send,
+    ) {
+        let listener = match TcpListener::bind(bind_addr).await {
             Ok(listener) => {
-                info!(port = port, "P2P server listening");
+                info!(
+                    bind_addr = %bind_addr,
+                    advertise_addr = ?self.advertised_addr,
+                    tls = self.transport.is_tls(),
+                    "P2P server listening"
+                );
                 listener
             }
             Err(e) => {
-                error!(port = port, error = %e, "Failed to bind P2P server");
+                error!(bind_addr = %bind_addr, error = %e, "Failed to bind P2P server");
                 return;
             }
         };
 
+        self.serve(listener, shutdown).await;
+    }
+
+    /// Accept and handle connections on a bound listener until `shutdown` resolves
+    async fn serve(self: Arc<Self>, listener: TcpListener, shutdown: impl Future<Output = ()>) {
+        tokio::pin!(shutdown);
+
         loop {
-            match listener.accept().await {
+            let accepted = tokio::select! {
+                accepted = listener.accept() => accepted,
+                _ = &mut shutdown => {
+                    info!("P2P server stopped accepting connections");
+                    return;
+                }
+            };
+
+            match accepted {
                 Ok((stream, addr)) => {
                     let peer_addr = addr.to_string();
+
+                    // Reserve a connection slot (permit is released when the task ends)
+                    let permit = match Arc::clone(&self.connection_limiter).try_acquire_owned() {
+                        Ok(permit) => permit,
+                        Err(_) => {
+                            warn!(
+                                peer = %peer_addr,
+                                limit = MAX_CONCURRENT_P2P_CONNECTIONS,
+                                "Rejected peer: connection limit reached"
+                            );
+                            continue;
+                        }
+                    };
+
                     let node = Arc::clone(&self);
 
                     tokio::spawn(async move {
+                        let _permit = permit;
+
                         // Check blacklist BEFORE processing
                         if node.blacklist.lock().await.contains(&peer_addr) {
                             warn!(peer = %peer_addr, "Rejected blacklisted peer");
                             return;
                         }
 
+                        let stream = match node.transport.accept(stream).await {
+                            Ok(stream) => stream,
+                            Err(e) => {
+                                warn!(peer = %peer_addr, error = %e, "Rejected peer connection");
+                                return;
+                            }
+                        };
+
                         // Handle connection
                         if let Err(e) = node.handle_connection(stream, &peer_addr).await {
                             warn!(peer = %peer_addr, error = %e, "Connection handling failed");
@@ -209,7 +1195,11 @@ impl P2PNode {
     }
 
     /// Handle incoming P2P connection (async)
-    async fn handle_connection(&self, mut stream: TcpStream, peer_addr: &str) -> Result<()> {
+    async fn handle_connection(
+        &self,
+        mut stream: Box<dyn PeerStream>,
+        peer_addr: &str,
+    ) -> Result<()> {
         // Check reputation threshold
         {
             let rep = self.peer_reputation.lock().await;
@@ -229,6 +1219,7 @@ impl P2PNode {
                 .entry(peer_addr.to_string())
                 .or_insert_with(RateLimitTracker::new);
             if !tracker.check_and_increment() {
+                P2PStats::record(&self.stats.rate_limited);
                 warn!(peer = %peer_addr, "Rate limit exceeded");
                 return Err(GoudChainError::Unauthorized(
                     "Rate limit exceeded".to_string(),
@@ -236,140 +1227,58 @@ impl P2PNode {
             }
         }
 
-        // Read length-prefixed message with timeout: [4 bytes length][N bytes payload]
-        let mut len_bytes = [0u8; 4];
-        timeout(
-            Duration::from_secs(P2P_READ_TIMEOUT_SECONDS),
-            stream.read_exact(&mut len_bytes),
-        )
-        .await
-        .map_err(|_| {
-            GoudChainError::IoError(std::io::Error::new(
-                std::io::ErrorKind::TimedOut,
-                "Read timeout",
-            ))
-        })?
-        .map_err(GoudChainError::IoError)?;
-
-        let len = u32::from_be_bytes(len_bytes) as usize;
-
-        // Sanity check: reject messages larger than 100MB
-        if len > 100_000_000 {
-            return Err(GoudChainError::InvalidRequestBody(
-                "Message too large".to_string(),
-            ));
-        }
-
-        let mut buffer = vec![0u8; len];
-        timeout(
-            Duration::from_secs(P2P_READ_TIMEOUT_SECONDS),
-            stream.read_exact(&mut buffer),
-        )
-        .await
-        .map_err(|_| {
-            GoudChainError::IoError(std::io::Error::new(
-                std::io::ErrorKind::TimedOut,
-                "Read timeout",
-            ))
-        })?
-        .map_err(GoudChainError::IoError)?;
+        let buffer = Self::read_frame(&mut stream, MAX_P2P_MESSAGE_BYTES).await?;
 
-        let message: P2PMessage = bincode::deserialize(&buffer)
-            .map_err(|e| GoudChainError::DeserializationError(e.to_string()))?;
+        let message = Self::decode_message(&buffer)?;
+        P2PStats::record(&self.stats.messages_received);
 
         match message {
             P2PMessage::NewBlock(block) => {
-                let mut blockchain = self.blockchain.write().await;
-                let latest = blockchain.get_latest_block()?;
-
-                // Check if block already exists (idempotency)
-                if blockchain.chain.iter().any(|b| b.hash == block.hash) {
+                // Drop blocks we've already processed before doing any validation work
+                if self.seen_blocks.lock().await.contains(&block.hash) {
                     info!(
                         block_index = block.index,
                         block_hash = %block.hash,
-                        "Block already exists, skipping"
+                        "Block already seen, skipping"
                     );
                     return Ok(());
                 }
 
-                // Validate block index is sequential
-                if block.index != latest.index + 1 {
-                    warn!(
-                        peer = %peer_addr,
-                        block_index = block.index,
-                        expected_index = latest.index + 1,
-                        "Rejected block: non-sequential index"
-                    );
-                    return Ok(()); // Don't penalize - might be a timing issue
-                }
-
-                // Validate previous_hash links to our latest block
-                if block.previous_hash != latest.hash {
-                    warn!(
-                        peer = %peer_addr,
-                        block_index = block.index,
-                        block_previous_hash = %block.previous_hash,
-                        our_latest_hash = %latest.hash,
-                        "Rejected block: previous_hash mismatch - chain has diverged"
-                    );
-                    // Chain has diverged - manual sync needed
-                    return Ok(());
+                // A self-consistent block shows the peer's tip, even when it doesn't extend ours
+                if block.hash == block.calculate_hash() {
+                    self.best_peer_height
+                        .fetch_max(block.index, Ordering::Relaxed);
                 }
 
-                // Validate block hash is correct
-                let calculated_hash = block.calculate_hash();
-                if block.hash != calculated_hash {
-                    warn!(
-                        peer = %peer_addr,
-                        block_index = block.index,
-                        claimed_hash = %block.hash,
-                        calculated_hash = %calculated_hash,
-                        "Rejected block: invalid hash"
-                    );
-                    // Bad peer - decrease reputation
-                    let mut r = self.peer_reputation.lock().await;
-                    *r.entry(peer_addr.to_string()).or_insert(0) +=
-                        REPUTATION_PENALTY_INVALID_BLOCK;
-                    return Ok(());
-                }
+                let mut blockchain = self.blockchain.write().await;
+                let latest = blockchain.get_latest_block()?.clone();
 
-                // Validate merkle root
-                let calculated_merkle =
-                    Block::calculate_merkle_root(&block.encrypted_block_data, &block.blind_indexes);
-                if block.merkle_root != calculated_merkle {
-                    warn!(
-                        peer = %peer_addr,
-                        block_index = block.index,
-                        claimed_merkle = %block.merkle_root,
-                        calculated_merkle = %calculated_merkle,
-                        "Rejected block: invalid merkle root"
-                    );
-                    // Bad peer - decrease reputation
-                    let mut r = self.peer_reputation.lock().await;
-                    *r.entry(peer_addr.to_string()).or_insert(0) +=
-                        REPUTATION_PENALTY_INVALID_BLOCK;
-                    return Ok(());
+                match check_next_block(&latest, &block, peer_addr) {
+                    BlockCheck::Valid => {}
+                    // Don't penalize - might be a timing issue or a diverged chain
+                    BlockCheck::NonSequential | BlockCheck::Diverged => return Ok(()),
+                    BlockCheck::Invalid => {
+                        // Bad peer - decrease reputation, ban once it drops below threshold
+                        P2PStats::record(&self.stats.blocks_rejected);
+                        let score = Self::adjust_reputation(
+                            &self.peer_reputation,
+                            &self.peer_store,
+                            &self.stats,
+                            peer_addr,
+                            REPUTATION_PENALTY_INVALID_BLOCK,
+                        )
+                        .await;
+                        if score < MIN_REPUTATION_THRESHOLD {
+                            self.blacklist_peer(peer_addr).await;
+                        }
+                        return Ok(());
+                    }
                 }
 
                 // All validations passed - add block
-                blockchain.chain.push(block.clone());
-
-                // Save block to RocksDB (incremental write)
-                if let Err(e) = self.blockchain_store.save_block(&block) {
-                    error!(error = %e, "Failed to save received block to RocksDB");
-                }
-
-                // Save checkpoint if needed
-                #[allow(unknown_lints)]
-                #[allow(clippy::manual_is_multiple_of)]
-                if block.index % CHECKPOINT_INTERVAL == 0 {
-                    if let Err(e) = self
-                        .blockchain_store
-                        .save_checkpoint(block.index, &block.hash)
-                    {
-                        error!(error = %e, "Failed to save checkpoint");
-                    }
-                }
+                Self::append_block(&self.blockchain_store, &mut blockchain, block.clone());
+                self.seen_blocks.lock().await.put(block.hash.clone(), ());
+                P2PStats::record(&self.stats.blocks_received);
 
                 info!(
                     block_index = block.index,
@@ -379,17 +1288,97 @@ impl P2PNode {
                 );
 
                 // Good peer
-                let mut r = self.peer_reputation.lock().await;
-                *r.entry(peer_addr.to_string()).or_insert(0) += REPUTATION_REWARD_VALID_BLOCK;
+                Self::adjust_reputation(
+                    &self.peer_reputation,
+                    &self.peer_store,
+                    &self.stats,
+                    peer_addr,
+                    REPUTATION_REWARD_VALID_BLOCK,
+                )
+                .await;
             }
             // Note: Individual account/collection sync removed in v8_envelope_encryption
             // All data is synced as complete blocks with encrypted envelopes
             // Nodes cannot extract individual accounts without API keys
+            // Pruned blocks fail merkle validation on the receiver, so never serve them
             P2PMessage::RequestChain => {
                 let blockchain = self.blockchain.read().await;
-                let response = P2PMessage::ResponseChain(blockchain.chain.clone());
+                if blockchain.pruned_before > 1 {
+                    warn!(peer = %peer_addr, "Refusing full chain request: node has pruned blocks");
+                    return Err(GoudChainError::DataPruned { block_index: 1 });
+                }
+                let response = P2PMessage::ResponseChain(blockchain.full_chain());
+                Self::send_response(&mut stream, &response).await?;
+                P2PStats::record(&self.stats.chain_requests_served);
+            }
+            P2PMessage::RequestBlocksFrom(height) => {
+                let response = {
+                    let blockchain = self.blockchain.read().await;
+                    if blockchain.is_pruned(height + 1) {
+                        warn!(
+                            peer = %peer_addr,
+                            start = height + 1,
+                            "Refusing block range request: blocks are pruned on this node"
+                        );
+                        return Err(GoudChainError::DataPruned {
+                            block_index: height + 1,
+                        });
+                    }
+                    let blocks: Vec<Block> = blockchain
+                        .chain
+                        .iter()
+                        .filter(|b| b.index > height)
+                        .take(MAX_BLOCKS_PER_SYNC_RESPONSE)
+                        .map(|b| blockchain.full_block(b).into_owned())
+                        .collect();
+                    P2PMessage::ResponseBlockRange {
+                        start: height + 1,
+                        blocks,
+                    }
+                };
+                Self::send_response(&mut stream, &response).await?;
+                P2PStats::record(&self.stats.chain_requests_served);
+            }
+            P2PMessage::RequestHeaders(start) => {
+                let response = {
+                    let blockchain = self.blockchain.read().await;
+                    let headers: Vec<BlockHeader> = blockchain
+                        .chain
+                        .iter()
+                        .filter(|b| b.index >= start)
+                        .take(MAX_HEADERS_PER_SYNC_RESPONSE)
+                        .map(Block::header)
+                        .collect();
+                    P2PMessage::ResponseHeaders(headers)
+                };
                 Self::send_response(&mut stream, &response).await?;
+                P2PStats::record(&self.stats.chain_requests_served);
             }
+            P2PMessage::GetPeers => {
+                let response = P2PMessage::Peers(self.peer_exchange_response().await);
+                Self::send_response(&mut stream, &response).await?;
+            }
+            P2PMessage::Ping(nonce) => {
+                Self::send_response(&mut stream, &P2PMessage::Pong(nonce)).await?;
+            }
+            P2PMessage::GenesisHash(theirs) => {
+                let ours = self
+                    .blockchain
+                    .read()
+                    .await
+                    .genesis_hash()
+                    .unwrap_or_default();
+                if theirs != ours {
+                    error!(
+                        peer = %peer_addr,
+                        our_genesis = %ours,
+                        peer_genesis = %theirs,
+                        "FATAL: genesis mismatch with connecting peer (check SCHEMA_VERSION and validator config)"
+                    );
+                }
+                Self::send_response(&mut stream, &P2PMessage::GenesisHash(ours)).await?;
+            }
+            // Unsolicited Peers pushes are ignored - peers are only learned from our own requests
             _ => {}
         }
 
@@ -397,41 +1386,21 @@ impl P2PNode {
     }
 
     /// Send a message to a peer (async)
-    async fn send_message(peer: &str, message: &P2PMessage) -> Result<()> {
-        let encoded = bincode::serialize(message)
-            .map_err(|e| GoudChainError::SerializationError(e.to_string()))?;
-
-        // Resolve peer address (handles both DNS names like "node1:9000" and IPs)
-        let addrs: Vec<_> = tokio::net::lookup_host(peer)
-            .await
-            .map_err(|e| {
-                GoudChainError::PeerConnectionFailed(format!(
-                    "Failed to resolve peer address {}: {}",
-                    peer, e
-                ))
-            })?
-            .collect();
+    async fn send_message(
+        transport: &P2PTransport,
+        peer: &str,
+        message: &P2PMessage,
+    ) -> Result<()> {
+        let encoded = Self::encode_message(message)?;
 
-        let first_addr = addrs.first().ok_or_else(|| {
-            GoudChainError::PeerConnectionFailed(format!("No addresses found for peer {}", peer))
-        })?;
-
-        // Connect with timeout
-        let mut stream = timeout(
-            Duration::from_secs(P2P_CONNECT_TIMEOUT_SECONDS),
-            TcpStream::connect(first_addr),
-        )
-        .await
-        .map_err(|_| {
-            GoudChainError::PeerConnectionFailed(format!("Connection timeout to {}", peer))
-        })?
-        .map_err(|e| GoudChainError::PeerConnectionFailed(e.to_string()))?;
+        let mut stream = transport.connect(peer).await?;
 
         // Write length-prefixed message with timeout: [4 bytes length][N bytes payload]
         let len = encoded.len() as u32;
         timeout(Duration::from_secs(P2P_WRITE_TIMEOUT_SECONDS), async {
             stream.write_all(&len.to_be_bytes()).await?;
             stream.write_all(&encoded).await?;
+            stream.flush().await?; // TLS buffers records until flushed
             Ok::<_, std::io::Error>(())
         })
         .await
@@ -447,41 +1416,21 @@ impl P2PNode {
     }
 
     /// Send a message and receive a response (async)
-    async fn send_and_receive(peer: &str, message: &P2PMessage) -> Result<P2PMessage> {
-        let encoded = bincode::serialize(message)
-            .map_err(|e| GoudChainError::SerializationError(e.to_string()))?;
-
-        // Resolve peer address (handles both DNS names like "node1:9000" and IPs)
-        let addrs: Vec<_> = tokio::net::lookup_host(peer)
-            .await
-            .map_err(|e| {
-                GoudChainError::PeerConnectionFailed(format!(
-                    "Failed to resolve peer address {}: {}",
-                    peer, e
-                ))
-            })?
-            .collect();
+    async fn send_and_receive(
+        transport: &P2PTransport,
+        peer: &str,
+        message: &P2PMessage,
+    ) -> Result<P2PMessage> {
+        let encoded = Self::encode_message(message)?;
 
-        let first_addr = addrs.first().ok_or_else(|| {
-            GoudChainError::PeerConnectionFailed(format!("No addresses found for peer {}", peer))
-        })?;
-
-        // Connect with timeout
-        let mut stream = timeout(
-            Duration::from_secs(P2P_CONNECT_TIMEOUT_SECONDS),
-            TcpStream::connect(first_addr),
-        )
-        .await
-        .map_err(|_| {
-            GoudChainError::PeerConnectionFailed(format!("Connection timeout to {}", peer))
-        })?
-        .map_err(|e| GoudChainError::PeerConnectionFailed(e.to_string()))?;
+        let mut stream = transport.connect(peer).await?;
 
         // Write length-prefixed message with timeout: [4 bytes length][N bytes payload]
         let len = encoded.len() as u32;
         timeout(Duration::from_secs(P2P_WRITE_TIMEOUT_SECONDS), async {
             stream.write_all(&len.to_be_bytes()).await?;
             stream.write_all(&encoded).await?;
+            stream.flush().await?; // TLS buffers records until flushed
             Ok::<_, std::io::Error>(())
         })
         .await
@@ -493,7 +1442,81 @@ impl P2PNode {
         })?
         .map_err(GoudChainError::IoError)?;
 
-        // Read length-prefixed response with timeout: [4 bytes length][N bytes payload]
+        let buffer = Self::read_frame(&mut stream, MAX_P2P_MESSAGE_BYTES).await?;
+
+        Self::decode_message(&buffer)
+    }
+
+    /// Serialize a message into a frame body: [1 byte flag][bincode, zstd-compressed if large]
+    ///
+    /// Only payloads above P2P_COMPRESSION_THRESHOLD_BYTES are compressed, and only
+    /// when compression actually shrinks them.
+    fn encode_message(message: &P2PMessage) -> Result<Vec<u8>> {
+        let encoded = bincode::serialize(message)
+            .map_err(|e| GoudChainError::SerializationError(e.to_string()))?;
+
+        if encoded.len() > P2P_COMPRESSION_THRESHOLD_BYTES {
+            let compressed = zstd::bulk::compress(&encoded, ZSTD_COMPRESSION_LEVEL)
+                .map_err(|e| GoudChainError::CompressionFailed(e.to_string()))?;
+            if compressed.len() < encoded.len() {
+                let mut frame = Vec::with_capacity(compressed.len() + 1);
+                frame.push(FRAME_FLAG_ZSTD);
+                frame.extend_from_slice(&compressed);
+                return Ok(frame);
+            }
+        }
+
+        let mut frame = Vec::with_capacity(encoded.len() + 1);
+        frame.push(FRAME_FLAG_RAW);
+        frame.extend_from_slice(&encoded);
+        Ok(frame)
+    }
+
+    /// Decode a frame body produced by `encode_message`
+    ///
+    /// Decompression stops at MAX_P2P_MESSAGE_BYTES so a small compressed frame can't
+    /// inflate past the limit the length prefix enforces.
+    fn decode_message(frame: &[u8]) -> Result<P2PMessage> {
+        let (flag, body) = frame
+            .split_first()
+            .ok_or_else(|| GoudChainError::DeserializationError("Empty P2P frame".to_string()))?;
+
+        let decompressed;
+        let payload = match *flag {
+            FRAME_FLAG_RAW => body,
+            FRAME_FLAG_ZSTD => {
+                let decoder = zstd::stream::read::Decoder::new(body)
+                    .map_err(|e| GoudChainError::CompressionFailed(e.to_string()))?;
+                let mut buffer = Vec::new();
+                decoder
+                    .take(MAX_P2P_MESSAGE_BYTES as u64 + 1)
+                    .read_to_end(&mut buffer)
+                    .map_err(|e| GoudChainError::CompressionFailed(e.to_string()))?;
+                if buffer.len() > MAX_P2P_MESSAGE_BYTES {
+                    return Err(GoudChainError::InvalidRequestBody(
+                        "Decompressed message too large".to_string(),
+                    ));
+                }
+                decompressed = buffer;
+                &decompressed
+            }
+            other => {
+                return Err(GoudChainError::DeserializationError(format!(
+                    "Unknown P2P frame flag: {}",
+                    other
+                )))
+            }
+        };
+
+        bincode::deserialize(payload)
+            .map_err(|e| GoudChainError::DeserializationError(e.to_string()))
+    }
+
+    /// Read one length-prefixed frame ([4 bytes length][N bytes body]) with timeouts
+    ///
+    /// The declared length is checked against `max_len` before the buffer is allocated,
+    /// so a hostile length prefix can't force a huge allocation.
+    async fn read_frame<R: AsyncRead + Unpin>(stream: &mut R, max_len: usize) -> Result<Vec<u8>> {
         let mut len_bytes = [0u8; 4];
         timeout(
             Duration::from_secs(P2P_READ_TIMEOUT_SECONDS),
@@ -509,12 +1532,11 @@ impl P2PNode {
         .map_err(GoudChainError::IoError)?;
 
         let len = u32::from_be_bytes(len_bytes) as usize;
-
-        // Sanity check: reject messages larger than 100MB
-        if len > 100_000_000 {
-            return Err(GoudChainError::InvalidRequestBody(
-                "Response too large".to_string(),
-            ));
+        if len > max_len {
+            return Err(GoudChainError::InvalidRequestBody(format!(
+                "Message too large: {} bytes (max {})",
+                len, max_len
+            )));
         }
 
         let mut buffer = vec![0u8; len];
@@ -531,20 +1553,22 @@ impl P2PNode {
         })?
         .map_err(GoudChainError::IoError)?;
 
-        bincode::deserialize(&buffer)
-            .map_err(|e| GoudChainError::DeserializationError(e.to_string()))
+        Ok(buffer)
     }
 
     /// Send a response message through an existing stream (async)
-    async fn send_response(stream: &mut TcpStream, message: &P2PMessage) -> Result<()> {
-        let encoded = bincode::serialize(message)
-            .map_err(|e| GoudChainError::SerializationError(e.to_string()))?;
+    async fn send_response<W: AsyncWrite + Unpin>(
+        stream: &mut W,
+        message: &P2PMessage,
+    ) -> Result<()> {
+        let encoded = Self::encode_message(message)?;
 
         // Write length-prefixed message with timeout: [4 bytes length][N bytes payload]
         let len = encoded.len() as u32;
         timeout(Duration::from_secs(P2P_WRITE_TIMEOUT_SECONDS), async {
             stream.write_all(&len.to_be_bytes()).await?;
             stream.write_all(&encoded).await?;
+            stream.flush().await?; // TLS buffers records until flushed
             Ok::<_, std::io::Error>(())
         })
         .await
@@ -559,3 +1583,659 @@ impl P2PNode {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::block::{generate_block_salt, BlockConfig};
+
+    fn make_block(index: u64, previous_hash: &str) -> Block {
+        Block::new(BlockConfig {
+            index,
+            account_envelopes: Vec::new(),
+            collection_envelopes: Vec::new(),
+            previous_hash: previous_hash.to_string(),
+            validator: "Validator_1".to_string(),
+            blind_indexes: Vec::new(),
+            block_salt: generate_block_salt(),
+        })
+        .unwrap()
+    }
+
+    fn test_node(data_dir: &tempfile::TempDir, peers: Vec<String>) -> P2PNode {
+        let store = Arc::new(BlockchainStore::new(data_dir.path()).unwrap());
+        test_node_with_store(store, peers)
+    }
+
+    fn test_node_with_store(store: Arc<BlockchainStore>, peers: Vec<String>) -> P2PNode {
+        let validator_config = crate::config::ValidatorConfig::single("node1");
+        let blockchain = Blockchain::new("node1".to_string(), validator_config).unwrap();
+
+        P2PNode::new(
+            Arc::new(RwLock::new(blockchain)),
+            store,
+            peers,
+            Some("node1:9000".to_string()),
+            P2PTransport::new(None).unwrap(),
+            None,
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_reputation_and_blacklist_survive_restart() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(BlockchainStore::new(data_dir.path()).unwrap());
+
+        {
+            let node = test_node_with_store(Arc::clone(&store), vec![]);
+            P2PNode::adjust_reputation(
+                &node.peer_reputation,
+                &node.peer_store,
+                &node.stats,
+                "node2:9000",
+                REPUTATION_REWARD_VALID_BLOCK,
+            )
+            .await;
+            node.blacklist_peer("evil:9000").await;
+            node.blacklist_peer("evil:9000").await;
+        }
+
+        let restarted = test_node_with_store(store, vec![]);
+        assert_eq!(
+            restarted.peer_reputation.lock().await.get("node2:9000"),
+            Some(&REPUTATION_REWARD_VALID_BLOCK)
+        );
+        assert_eq!(*restarted.blacklist.lock().await, vec!["evil:9000"]);
+    }
+
+    #[tokio::test]
+    async fn test_merge_peers_filters_and_dedupes() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = test_node(&data_dir, vec!["node2:9000".to_string()]);
+        node.blacklist.lock().await.push("evil:9000".to_string());
+        node.parked_peers.lock().await.push("slow:9000".to_string());
+
+        let added = node
+            .merge_peers(vec![
+                "node1:9000".to_string(),  // ourselves
+                "node2:9000".to_string(),  // already known
+                "evil:9000".to_string(),   // blacklisted
+                "slow:9000".to_string(),   // parked
+                "no-port".to_string(),     // malformed
+                "node3:99999".to_string(), // invalid port
+                " node3:9000 ".to_string(),
+                "node3:9000".to_string(), // duplicate within the response
+            ])
+            .await;
+
+        assert_eq!(added, 1);
+        assert_eq!(
+            *node.peers.lock().await,
+            vec!["node2:9000".to_string(), "node3:9000".to_string()]
+        );
+        assert_eq!(
+            node.peer_reputation.lock().await.get("node3:9000"),
+            Some(&0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_peer_lists_only_requested_from_configured_peers() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = test_node(&data_dir, vec!["node2:9000".to_string()]);
+
+        node.merge_peers(vec!["node3:9000".to_string()]).await;
+        node.start_exchange_round().await;
+
+        // node3 is a peer now, but over plaintext TCP its peer list is never requested
+        assert_eq!(node.peers.lock().await.len(), 2);
+        assert_eq!(
+            node.peer_list_sources().await,
+            vec!["node2:9000".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_peer_changes_notify_subscribers() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut node = test_node(&data_dir, vec!["node2:9000".to_string()]);
+        let updates = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&updates);
+        node.peer_update_callback = Some(Arc::new(move |peers| {
+            recorded.lock().unwrap().push(peers);
+        }));
+
+        // Nothing new, so no event
+        node.merge_peers(vec!["node2:9000".to_string()]).await;
+        assert!(updates.lock().unwrap().is_empty());
+
+        node.merge_peers(vec!["node3:9000".to_string()]).await;
+        node.blacklist_peer("node3:9000").await;
+
+        let updates = updates.lock().unwrap();
+        assert_eq!(updates.len(), 2);
+        assert_eq!(
+            updates[0],
+            vec!["node2:9000".to_string(), "node3:9000".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_peers_respects_cap() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = test_node(&data_dir, vec![]);
+
+        let flood: Vec<String> = (0..MAX_PEERS * 2)
+            .map(|i| format!("peer{}:9000", i))
+            .collect();
+        assert_eq!(node.merge_peers(flood).await, MAX_PEERS);
+        assert_eq!(node.peers.lock().await.len(), MAX_PEERS);
+    }
+
+    #[tokio::test]
+    async fn test_discovered_peers_not_reshared_in_same_round() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = test_node(&data_dir, vec!["node2:9000".to_string()]);
+
+        node.merge_peers(vec!["node3:9000".to_string()]).await;
+        assert_eq!(node.shareable_peers().await, vec!["node2:9000".to_string()]);
+
+        node.start_exchange_round().await;
+        assert_eq!(
+            node.shareable_peers().await,
+            vec!["node2:9000".to_string(), "node3:9000".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_peer_exchange_announces_advertised_address() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = test_node(&data_dir, vec!["node2:9000".to_string()]);
+        assert_eq!(
+            node.peer_exchange_response().await,
+            vec!["node1:9000".to_string(), "node2:9000".to_string()]
+        );
+
+        // A peer receiving the response learns our advertised address
+        let other_dir = tempfile::tempdir().unwrap();
+        let mut other = test_node(&other_dir, vec![]);
+        other.advertised_addr = Some("node2:9000".to_string());
+        assert_eq!(
+            other.merge_peers(node.peer_exchange_response().await).await,
+            1
+        );
+        assert_eq!(*other.peers.lock().await, vec!["node1:9000".to_string()]);
+    }
+
+    /// Serve a node on an ephemeral local port and return its address
+    async fn serve_node(node: P2PNode) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(Arc::new(node).serve(listener, std::future::pending()));
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_ping_records_latency() {
+        let remote_dir = tempfile::tempdir().unwrap();
+        let remote = serve_node(test_node(&remote_dir, vec![])).await;
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = test_node(&data_dir, vec![remote.clone()]);
+        node.ping_peers().await;
+
+        let liveness = node
+            .peer_liveness
+            .lock()
+            .await
+            .get(&remote)
+            .copied()
+            .unwrap();
+        assert!(liveness.last_seen > 0);
+        assert!(node.peer_reputation.lock().await.get(&remote).is_none());
+        assert_eq!(*node.peers.lock().await, vec![remote]);
+    }
+
+    #[tokio::test]
+    async fn test_unresponsive_peer_decays_and_is_parked() {
+        // Bind then drop a listener so the port refuses connections
+        let dead = {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            listener.local_addr().unwrap().to_string()
+        };
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = test_node(&data_dir, vec![dead.clone()]);
+        node.peer_reputation
+            .lock()
+            .await
+            .insert(dead.clone(), MIN_REPUTATION_THRESHOLD);
+
+        node.ping_peers().await;
+
+        assert!(node.peers.lock().await.is_empty());
+        assert_eq!(*node.parked_peers.lock().await, vec![dead.clone()]);
+        assert!(node.blacklist.lock().await.is_empty());
+        assert_eq!(
+            node.peer_reputation.lock().await.get(&dead),
+            Some(&(MIN_REPUTATION_THRESHOLD + REPUTATION_PENALTY_MISSED_PING))
+        );
+
+        // Parked peers are not penalized further
+        node.ping_peers().await;
+        assert_eq!(
+            node.peer_reputation.lock().await.get(&dead),
+            Some(&(MIN_REPUTATION_THRESHOLD + REPUTATION_PENALTY_MISSED_PING))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parked_peer_restored_when_responsive() {
+        let remote_dir = tempfile::tempdir().unwrap();
+        let remote = serve_node(test_node(&remote_dir, vec![])).await;
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = test_node(&data_dir, vec![]);
+        node.parked_peers.lock().await.push(remote.clone());
+        node.peer_reputation
+            .lock()
+            .await
+            .insert(remote.clone(), MIN_REPUTATION_THRESHOLD - 1);
+
+        node.ping_peers().await;
+
+        assert_eq!(*node.peers.lock().await, vec![remote.clone()]);
+        assert!(node.parked_peers.lock().await.is_empty());
+        assert_eq!(node.peer_reputation.lock().await.get(&remote), Some(&0));
+    }
+
+    #[test]
+    fn test_check_next_block_valid() {
+        let genesis = make_block(0, "0");
+        let next = make_block(1, &genesis.hash);
+        assert_eq!(check_next_block(&genesis, &next, "peer"), BlockCheck::Valid);
+    }
+
+    #[test]
+    fn test_check_next_block_non_sequential() {
+        let genesis = make_block(0, "0");
+        let skipped = make_block(2, &genesis.hash);
+        assert_eq!(
+            check_next_block(&genesis, &skipped, "peer"),
+            BlockCheck::NonSequential
+        );
+    }
+
+    #[test]
+    fn test_check_next_block_diverged() {
+        let genesis = make_block(0, "0");
+        let other = make_block(1, "not_our_tip");
+        assert_eq!(
+            check_next_block(&genesis, &other, "peer"),
+            BlockCheck::Diverged
+        );
+    }
+
+    #[test]
+    fn test_check_next_block_tampered() {
+        let genesis = make_block(0, "0");
+
+        let mut bad_hash = make_block(1, &genesis.hash);
+        bad_hash.hash = "tampered".to_string();
+        assert_eq!(
+            check_next_block(&genesis, &bad_hash, "peer"),
+            BlockCheck::Invalid
+        );
+
+        let mut bad_merkle = make_block(1, &genesis.hash);
+        bad_merkle.blind_indexes.push("injected".to_string());
+        assert_eq!(
+            check_next_block(&genesis, &bad_merkle, "peer"),
+            BlockCheck::Invalid
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_frame_rejects_oversized_length_before_reading() {
+        let (mut client, mut server) = tokio::io::duplex(64);
+        client.write_all(&u32::MAX.to_be_bytes()).await.unwrap();
+
+        // Only the prefix was sent: returning immediately proves no body read was attempted
+        let result = P2PNode::read_frame(&mut server, MAX_P2P_MESSAGE_BYTES).await;
+        assert!(matches!(
+            result,
+            Err(GoudChainError::InvalidRequestBody(msg)) if msg.contains("too large")
+        ));
+
+        let (mut client, mut server) = tokio::io::duplex(64);
+        client.write_all(&3u32.to_be_bytes()).await.unwrap();
+        client.write_all(b"abc").await.unwrap();
+        assert_eq!(
+            P2PNode::read_frame(&mut server, MAX_P2P_MESSAGE_BYTES)
+                .await
+                .unwrap(),
+            b"abc"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oversized_message_drops_connection() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let addr = serve_node(test_node(&data_dir, vec![])).await;
+
+        let mut stream = tokio::net::TcpStream::connect(&addr).await.unwrap();
+        stream
+            .write_all(&0xFFFF_FFFFu32.to_be_bytes())
+            .await
+            .unwrap();
+
+        // Server closes the connection well before its read timeout
+        let mut response = Vec::new();
+        let read = timeout(
+            Duration::from_secs(P2P_READ_TIMEOUT_SECONDS / 2),
+            stream.read_to_end(&mut response),
+        )
+        .await
+        .expect("connection should be closed promptly");
+        assert!(read.is_err() || response.is_empty());
+    }
+
+    #[test]
+    fn test_small_messages_are_sent_uncompressed() {
+        let frame = P2PNode::encode_message(&P2PMessage::Ping(42)).unwrap();
+        assert_eq!(frame[0], FRAME_FLAG_RAW);
+        assert!(matches!(
+            P2PNode::decode_message(&frame).unwrap(),
+            P2PMessage::Ping(42)
+        ));
+    }
+
+    #[test]
+    fn test_large_messages_are_compressed_and_roundtrip() {
+        let peers: Vec<String> = (0..2000).map(|i| format!("node{}:9000", i)).collect();
+        let message = P2PMessage::Peers(peers.clone());
+        let raw_len = bincode::serialize(&message).unwrap().len();
+        assert!(raw_len > P2P_COMPRESSION_THRESHOLD_BYTES);
+
+        let frame = P2PNode::encode_message(&message).unwrap();
+        assert_eq!(frame[0], FRAME_FLAG_ZSTD);
+        assert!(frame.len() < raw_len);
+
+        match P2PNode::decode_message(&frame).unwrap() {
+            P2PMessage::Peers(decoded) => assert_eq!(decoded, peers),
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_malformed_frames_are_rejected() {
+        assert!(P2PNode::decode_message(&[]).is_err());
+        assert!(P2PNode::decode_message(&[7, 0, 0]).is_err());
+        assert!(P2PNode::decode_message(&[FRAME_FLAG_ZSTD, 1, 2, 3]).is_err());
+    }
+
+    /// Deliver one message to `node` over an in-memory stream, as an inbound peer would
+    async fn deliver(node: &P2PNode, peer_addr: &str, message: &P2PMessage) {
+        let (mut client, server) = tokio::io::duplex(64 * 1024);
+        let frame = P2PNode::encode_message(message).unwrap();
+        client
+            .write_all(&(frame.len() as u32).to_be_bytes())
+            .await
+            .unwrap();
+        client.write_all(&frame).await.unwrap();
+        node.handle_connection(Box::new(server), peer_addr)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_block_is_processed_once() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = test_node(&data_dir, vec![]);
+        let genesis_hash = node.blockchain.read().await.chain[0].hash.clone();
+        let block = make_block(1, &genesis_hash);
+
+        deliver(&node, "10.0.0.2:5000", &P2PMessage::NewBlock(block.clone())).await;
+        deliver(&node, "10.0.0.2:5000", &P2PMessage::NewBlock(block.clone())).await;
+
+        assert_eq!(node.blockchain.read().await.chain.len(), 2);
+        assert!(node.seen_blocks.lock().await.contains(&block.hash));
+        // Only the first delivery reached validation and earned a reward
+        assert_eq!(
+            node.peer_reputation.lock().await.get("10.0.0.2:5000"),
+            Some(&REPUTATION_REWARD_VALID_BLOCK)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_network_counters_in_prometheus_output() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = test_node(&data_dir, vec![]);
+        let genesis_hash = node.blockchain.read().await.chain[0].hash.clone();
+        let valid = make_block(1, &genesis_hash);
+        let mut tampered = make_block(2, &valid.hash);
+        tampered.hash = "tampered".to_string();
+
+        deliver(&node, "10.0.0.2:5000", &P2PMessage::NewBlock(valid)).await;
+        deliver(&node, "10.0.0.3:5000", &P2PMessage::NewBlock(tampered)).await;
+        deliver(&node, "10.0.0.2:5000", &P2PMessage::RequestHeaders(0)).await;
+        node.blacklist_peer("evil:9000").await;
+
+        let metrics = node.prometheus_metrics().await;
+        for line in [
+            "goud_p2p_messages_received_total 3",
+            "goud_p2p_blocks_received_total 1",
+            "goud_p2p_blocks_rejected_total 1",
+            "goud_p2p_chain_requests_served_total 1",
+            "goud_p2p_reputation_penalties_total 1",
+            "goud_p2p_blocks_broadcast_total 0",
+            "goud_p2p_active_connections 0",
+            "goud_p2p_blacklisted_peers 1",
+        ] {
+            assert!(
+                metrics.contains(line),
+                "missing `{}` in:\n{}",
+                line,
+                metrics
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_peer_block_ahead_marks_best_height() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = test_node(&data_dir, vec![]);
+        assert_eq!(node.best_peer_height(), 0);
+
+        // A block far past our tip is rejected but still reveals the peer's height
+        let ahead = make_block(5, "unknown_parent");
+        deliver(&node, "10.0.0.2:5000", &P2PMessage::NewBlock(ahead)).await;
+
+        assert_eq!(node.blockchain.read().await.chain.len(), 1);
+        assert_eq!(node.best_peer_height(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_waits_for_sync() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = test_node(&data_dir, vec![]);
+        assert!(!node.is_ready(0));
+
+        // A peer tip far ahead keeps the node unready
+        let ahead = make_block(READINESS_MAX_BLOCKS_BEHIND + 5, "unknown_parent");
+        deliver(&node, "10.0.0.2:5000", &P2PMessage::NewBlock(ahead)).await;
+        assert!(!node.is_ready(0));
+        assert!(node.is_ready(5));
+
+        // With no peers to catch up on, a sync completes immediately
+        node.request_chain_from_peers().await;
+        assert!(node.last_successful_sync().is_some());
+        assert!(node.is_ready(0));
+    }
+
+    #[tokio::test]
+    async fn test_block_is_broadcast_once() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let peer = listener.local_addr().unwrap().to_string();
+        let connections = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = Arc::clone(&connections);
+        tokio::spawn(async move {
+            while listener.accept().await.is_ok() {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            }
+        });
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = test_node(&data_dir, vec![peer]);
+        let genesis_hash = node.blockchain.read().await.chain[0].hash.clone();
+        let block = make_block(1, &genesis_hash);
+
+        node.broadcast_block(&block).await;
+        node.broadcast_block(&block).await;
+
+        // Let the accept loop catch up with the connection backlog
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(connections.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_server_stops_accepting_on_shutdown() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = Arc::new(test_node(&data_dir, vec![]));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+
+        let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(Arc::clone(&node).serve(listener, async {
+            let _ = stop_rx.await;
+        }));
+        assert!(P2PNode::ping(&node.transport, &addr).await.is_some());
+
+        stop_tx.send(()).unwrap();
+        timeout(Duration::from_secs(1), server)
+            .await
+            .expect("server should stop promptly")
+            .unwrap();
+
+        // The listener is closed, so new connections are refused
+        assert!(tokio::net::TcpStream::connect(&addr).await.is_err());
+    }
+
+    /// A block after `previous` with an ordered timestamp (plain blocks use jittered ones)
+    fn linked_block(previous: &Block, seconds_after: i64) -> Block {
+        let mut block = make_block(previous.index + 1, &previous.hash);
+        block.timestamp = previous.timestamp + seconds_after;
+        block.hash = block.calculate_hash();
+        block
+    }
+
+    #[tokio::test]
+    async fn test_diverged_chain_synced_from_fork_point() {
+        let remote_dir = tempfile::tempdir().unwrap();
+        let remote = test_node(&remote_dir, vec![]);
+        let genesis = remote.blockchain.read().await.chain[0].clone();
+        let shared = linked_block(&genesis, 1);
+        let their_second = linked_block(&shared, 2);
+        let their_third = linked_block(&their_second, 1);
+        remote.blockchain.write().await.chain.extend([
+            shared.clone(),
+            their_second,
+            their_third.clone(),
+        ]);
+        let remote_hashes: Vec<String> = remote
+            .blockchain
+            .read()
+            .await
+            .chain
+            .iter()
+            .map(|b| b.hash.clone())
+            .collect();
+        let addr = serve_node(remote).await;
+
+        let local_dir = tempfile::tempdir().unwrap();
+        let local = test_node(&local_dir, vec![addr.clone()]);
+        let our_second = linked_block(&shared, 1);
+        local
+            .blockchain
+            .write()
+            .await
+            .chain
+            .extend([shared, our_second]);
+
+        local.request_chain_from_peers().await;
+
+        let local_hashes: Vec<String> = local
+            .blockchain
+            .read()
+            .await
+            .chain
+            .iter()
+            .map(|b| b.hash.clone())
+            .collect();
+        assert_eq!(local_hashes, remote_hashes);
+        assert_eq!(
+            local
+                .blockchain_store
+                .load_chain()
+                .unwrap()
+                .last()
+                .unwrap()
+                .hash,
+            their_third.hash
+        );
+        assert_eq!(
+            local.peer_reputation.lock().await.get(&addr),
+            Some(&REPUTATION_REWARD_VALID_BLOCK)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mismatched_genesis_refuses_sync() {
+        let remote_dir = tempfile::tempdir().unwrap();
+        let remote = test_node(&remote_dir, vec![]);
+        {
+            // A genesis built from a different validator config
+            let mut chain = remote.blockchain.write().await;
+            chain.chain[0] = Block::new(BlockConfig {
+                index: 0,
+                account_envelopes: Vec::new(),
+                collection_envelopes: Vec::new(),
+                previous_hash: crate::constants::GENESIS_PREVIOUS_HASH.to_string(),
+                validator: "Validator_2".to_string(),
+                blind_indexes: Vec::new(),
+                block_salt: String::from("genesis_salt"),
+            })
+            .unwrap();
+            let next = linked_block(&chain.chain[0], 1);
+            chain.chain.push(next);
+        }
+        let addr = serve_node(remote).await;
+
+        let local_dir = tempfile::tempdir().unwrap();
+        let local = test_node(&local_dir, vec![addr.clone()]);
+        let local_genesis = local.blockchain.read().await.chain[0].hash.clone();
+        local.request_chain_from_peers().await;
+
+        let chain = &local.blockchain.read().await.chain;
+        assert_eq!(chain.len(), 1);
+        assert_eq!(chain[0].hash, local_genesis);
+        assert_eq!(local.best_peer_height(), 0);
+        assert!(local.last_successful_sync().is_none());
+        assert!(local.peer_reputation.lock().await.get(&addr).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_request_headers_returns_headers_from_start() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = test_node(&data_dir, vec![]);
+        let genesis = node.blockchain.read().await.chain[0].clone();
+        let block = linked_block(&genesis, 1);
+        node.blockchain.write().await.chain.push(block.clone());
+        let addr = serve_node(node).await;
+        let transport = P2PTransport::new(None).unwrap();
+
+        let headers = P2PNode::fetch_headers(&transport, &addr, 1).await.unwrap();
+        assert_eq!(headers, vec![block.header()]);
+    }
+}
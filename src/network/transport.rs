@@ -0,0 +1,416 @@
+//! P2P transport: plaintext TCP, or TLS with pinned peer certificates.
+//!
+//! With TLS enabled both sides present a certificate (mutual TLS) and accept a peer
+//! only if the SHA-256 fingerprint of its leaf certificate is in the configured pin set.
+//! Certificates are typically self-signed, so no CA chain or hostname is checked.
+
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio::time::{timeout, Duration};
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::crypto::{ring, verify_tls12_signature, verify_tls13_signature};
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use tokio_rustls::rustls::server::danger::{ClientCertVerified, ClientCertVerifier};
+use tokio_rustls::rustls::{
+    ClientConfig, DigitallySignedStruct, DistinguishedName, Error as TlsError, ServerConfig,
+    SignatureScheme,
+};
+use tokio_rustls::{TlsAcceptor, TlsConnector};
+
+use crate::config::P2pTlsConfig;
+use crate::constants::P2P_CONNECT_TIMEOUT_SECONDS;
+use crate::types::{GoudChainError, Result};
+
+/// Byte stream carrying length-prefixed P2P frames (plain TCP or TLS)
+pub trait PeerStream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> PeerStream for T {}
+
+/// Opens outbound and wraps inbound P2P connections
+pub struct P2PTransport {
+    tls: Option<TlsContext>,
+}
+
+struct TlsContext {
+    connector: TlsConnector,
+    acceptor: TlsAcceptor,
+}
+
+impl P2PTransport {
+    /// Create a transport; `None` keeps connections plaintext
+    pub fn new(tls: Option<&P2pTlsConfig>) -> Result<Self> {
+        let Some(tls) = tls else {
+            return Ok(Self { tls: None });
+        };
+
+        let certs = CertificateDer::pem_file_iter(&tls.cert_path)
+            .and_then(|certs| certs.collect::<std::result::Result<Vec<_>, _>>())
+            .map_err(|e| {
+                GoudChainError::ConfigError(format!(
+                    "Failed to read P2P TLS certificate {}: {}",
+                    tls.cert_path, e
+                ))
+            })?;
+        let key = PrivateKeyDer::from_pem_file(&tls.key_path).map_err(|e| {
+            GoudChainError::ConfigError(format!(
+                "Failed to read P2P TLS key {}: {}",
+                tls.key_path, e
+            ))
+        })?;
+
+        let verifier = Arc::new(PinnedCertVerifier::new(&tls.peer_fingerprints)?);
+        let provider = Arc::new(ring::default_provider());
+
+        let client_config = ClientConfig::builder_with_provider(Arc::clone(&provider))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| GoudChainError::ConfigError(e.to_string()))?
+            .dangerous()
+            .with_custom_certificate_verifier(verifier.clone())
+            .with_client_auth_cert(certs.clone(), key.clone_key())
+            .map_err(|e| GoudChainError::ConfigError(format!("Invalid P2P TLS key: {}", e)))?;
+
+        let server_config = ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .map_err(|e| GoudChainError::ConfigError(e.to_string()))?
+            .with_client_cert_verifier(verifier)
+            .with_single_cert(certs, key)
+            .map_err(|e| GoudChainError::ConfigError(format!("Invalid P2P TLS key: {}", e)))?;
+
+        Ok(Self {
+            tls: Some(TlsContext {
+                connector: TlsConnector::from(Arc::new(client_config)),
+                acceptor: TlsAcceptor::from(Arc::new(server_config)),
+            }),
+        })
+    }
+
+    /// Whether connections are encrypted
+    pub fn is_tls(&self) -> bool {
+        self.tls.is_some()
+    }
+
+    /// Connect to a peer ("host:port"), performing the TLS handshake when enabled
+    pub async fn connect(&self, peer: &str) -> Result<Box<dyn PeerStream>> {
+        // Resolve peer address (handles both DNS names like "node1:9000" and IPs)
+        let addrs: Vec<_> = tokio::net::lookup_host(peer)
+            .await
+            .map_err(|e| {
+                GoudChainError::PeerConnectionFailed(format!(
+                    "Failed to resolve peer address {}: {}",
+                    peer, e
+                ))
+            })?
+            .collect();
+
+        let first_addr = addrs.first().ok_or_else(|| {
+            GoudChainError::PeerConnectionFailed(format!("No addresses found for peer {}", peer))
+        })?;
+
+        // Connect (and handshake) with timeout
+        timeout(Duration::from_secs(P2P_CONNECT_TIMEOUT_SECONDS), async {
+            let stream = TcpStream::connect(first_addr)
+                .await
+                .map_err(|e| GoudChainError::PeerConnectionFailed(e.to_string()))?;
+
+            let Some(tls) = &self.tls else {
+                return Ok(Box::new(stream) as Box<dyn PeerStream>);
+            };
+
+            // The pinned verifier ignores the name, but rustls still needs a valid one
+            let host = peer.rsplit_once(':').map_or(peer, |(host, _)| host);
+            let server_name = ServerName::try_from(host.to_string()).map_err(|e| {
+                GoudChainError::PeerConnectionFailed(format!("Invalid peer host {}: {}", host, e))
+            })?;
+
+            let stream = tls
+                .connector
+                .connect(server_name, stream)
+                .await
+                .map_err(|e| {
+                    GoudChainError::PeerConnectionFailed(format!(
+                        "TLS handshake with {} failed: {}",
+                        peer, e
+                    ))
+                })?;
+            Ok(Box::new(stream) as Box<dyn PeerStream>)
+        })
+        .await
+        .map_err(|_| {
+            GoudChainError::PeerConnectionFailed(format!("Connection timeout to {}", peer))
+        })?
+    }
+
+    /// Wrap an accepted connection, performing the TLS handshake when enabled
+    pub async fn accept(&self, stream: TcpStream) -> Result<Box<dyn PeerStream>> {
+        let Some(tls) = &self.tls else {
+            return Ok(Box::new(stream));
+        };
+
+        let stream = timeout(
+            Duration::from_secs(P2P_CONNECT_TIMEOUT_SECONDS),
+            tls.acceptor.accept(stream),
+        )
+        .await
+        .map_err(|_| GoudChainError::PeerConnectionFailed("TLS handshake timeout".to_string()))?
+        .map_err(|e| {
+            GoudChainError::PeerConnectionFailed(format!("TLS handshake failed: {}", e))
+        })?;
+
+        Ok(Box::new(stream))
+    }
+}
+
+/// Lowercase hex SHA-256 of a DER certificate (the format used for pinning)
+pub fn certificate_fingerprint(cert: &[u8]) -> String {
+    hex::encode(Sha256::digest(cert))
+}
+
+/// Accepts exactly the certificates whose fingerprints are pinned (both directions)
+#[derive(Debug)]
+struct PinnedCertVerifier {
+    pinned: HashSet<String>,
+    provider: Arc<tokio_rustls::rustls::crypto::CryptoProvider>,
+}
+
+impl PinnedCertVerifier {
+    /// Normalize fingerprints ("AB:CD..." or "abcd...") and reject malformed ones
+    fn new(fingerprints: &[String]) -> Result<Self> {
+        let mut pinned = HashSet::new();
+        for fingerprint in fingerprints {
+            let normalized = fingerprint.replace(':', "").to_lowercase();
+            if normalized.len() != 64 || hex::decode(&normalized).is_err() {
+                return Err(GoudChainError::ConfigError(format!(
+                    "Invalid P2P TLS peer fingerprint: {}",
+                    fingerprint
+                )));
+            }
+            pinned.insert(normalized);
+        }
+
+        if pinned.is_empty() {
+            return Err(GoudChainError::ConfigError(
+                "P2P TLS requires at least one pinned peer fingerprint".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            pinned,
+            provider: Arc::new(ring::default_provider()),
+        })
+    }
+
+    fn check_pinned(&self, cert: &CertificateDer<'_>) -> std::result::Result<(), TlsError> {
+        if self.pinned.contains(&certificate_fingerprint(cert)) {
+            Ok(())
+        } else {
+            Err(TlsError::General(
+                "Peer certificate fingerprint is not pinned".to_string(),
+            ))
+        }
+    }
+}
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, TlsError> {
+        self.check_pinned(end_entity)?;
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, TlsError> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, TlsError> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+impl ClientCertVerifier for PinnedCertVerifier {
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        &[]
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _now: UnixTime,
+    ) -> std::result::Result<ClientCertVerified, TlsError> {
+        self.check_pinned(end_entity)?;
+        Ok(ClientCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, TlsError> {
+        ServerCertVerifier::verify_tls12_signature(self, message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, TlsError> {
+        ServerCertVerifier::verify_tls13_signature(self, message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        ServerCertVerifier::supported_verify_schemes(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Write a fresh self-signed cert/key pair, returning its (unpinned) config and fingerprint
+    fn node_tls(dir: &tempfile::TempDir, name: &str) -> (P2pTlsConfig, String) {
+        let certified = rcgen::generate_simple_self_signed(vec![name.to_string()]).unwrap();
+        let cert_path = dir.path().join(format!("{}.pem", name));
+        let key_path = dir.path().join(format!("{}.key", name));
+        std::fs::write(&cert_path, certified.cert.pem()).unwrap();
+        std::fs::write(&key_path, certified.key_pair.serialize_pem()).unwrap();
+
+        let config = P2pTlsConfig {
+            cert_path: cert_path.to_str().unwrap().to_string(),
+            key_path: key_path.to_str().unwrap().to_string(),
+            peer_fingerprints: vec![],
+        };
+        (config, certificate_fingerprint(certified.cert.der()))
+    }
+
+    /// Accept one connection on `server` and echo a single 5-byte message back
+    async fn echo_once(server: P2PTransport) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            if let Ok(mut stream) = server.accept(stream).await {
+                let mut buf = [0u8; 5];
+                if stream.read_exact(&mut buf).await.is_ok() {
+                    stream.write_all(&buf).await.unwrap();
+                    stream.flush().await.unwrap();
+                }
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_pinned_peers_complete_tls_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut server_tls, server_fp) = node_tls(&dir, "node1");
+        let (mut client_tls, client_fp) = node_tls(&dir, "node2");
+        server_tls.peer_fingerprints = vec![client_fp];
+        // Colon-separated uppercase (openssl output) is accepted too
+        client_tls.peer_fingerprints = vec![server_fp
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| std::str::from_utf8(pair).unwrap().to_uppercase())
+            .collect::<Vec<_>>()
+            .join(":")];
+
+        let server = P2PTransport::new(Some(&server_tls)).unwrap();
+        let client = P2PTransport::new(Some(&client_tls)).unwrap();
+        assert!(client.is_tls());
+
+        let addr = echo_once(server).await;
+        let mut stream = client.connect(&addr).await.unwrap();
+        stream.write_all(b"hello").await.unwrap();
+        stream.flush().await.unwrap();
+
+        let mut echoed = [0u8; 5];
+        stream.read_exact(&mut echoed).await.unwrap();
+        assert_eq!(&echoed, b"hello");
+    }
+
+    #[tokio::test]
+    async fn test_unpinned_certificate_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut server_tls, _) = node_tls(&dir, "node1");
+        let (mut client_tls, client_fp) = node_tls(&dir, "node2");
+        let (_, stranger_fp) = node_tls(&dir, "stranger");
+        server_tls.peer_fingerprints = vec![client_fp];
+        client_tls.peer_fingerprints = vec![stranger_fp];
+
+        let server = P2PTransport::new(Some(&server_tls)).unwrap();
+        let client = P2PTransport::new(Some(&client_tls)).unwrap();
+
+        let addr = echo_once(server).await;
+        assert!(matches!(
+            client.connect(&addr).await,
+            Err(GoudChainError::PeerConnectionFailed(msg)) if msg.contains("TLS handshake")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_plaintext_transport_passes_bytes_through() {
+        let transport = P2PTransport::new(None).unwrap();
+        assert!(!transport.is_tls());
+
+        let addr = echo_once(P2PTransport::new(None).unwrap()).await;
+        let mut stream = transport.connect(&addr).await.unwrap();
+        stream.write_all(b"plain").await.unwrap();
+
+        let mut echoed = [0u8; 5];
+        stream.read_exact(&mut echoed).await.unwrap();
+        assert_eq!(&echoed, b"plain");
+    }
+
+    #[test]
+    fn test_invalid_fingerprints_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut tls, _) = node_tls(&dir, "node1");
+
+        tls.peer_fingerprints = vec![];
+        assert!(P2PTransport::new(Some(&tls)).is_err());
+
+        tls.peer_fingerprints = vec!["not-a-fingerprint".to_string()];
+        assert!(P2PTransport::new(Some(&tls)).is_err());
+    }
+}
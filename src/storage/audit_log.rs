@@ -7,26 +7,33 @@
 //! - Encrypted with user's API key (only they can decrypt their logs)
 //! - Batched to reduce blockchain bloat (10s or 50 events)
 //! - RocksDB index for fast queries (non-authoritative cache)
+//! - Optional retention (AUDIT_RETENTION_DAYS): older entries come back `invalidated` and are
+//!   hidden by default, and blocks holding only expired batches drop out of the index
 
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex as StdMutex};
 
-use chrono::Utc;
+use futures_util::stream::{self, Stream};
 use sha2::{Digest, Sha256};
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
 use tokio::time::{interval, Duration};
 use tracing::{error, info, warn};
+use zeroize::Zeroizing;
 
 use crate::constants::{
-    AUDIT_BATCH_INTERVAL_SECONDS, AUDIT_BATCH_SIZE, AUDIT_IP_HASH_LENGTH, AUDIT_LABEL_PREFIX,
+    AUDIT_BATCH_INTERVAL_SECONDS, AUDIT_BATCH_SIZE, AUDIT_INDEX_PREFIX, AUDIT_IP_HASH_LENGTH,
+    AUDIT_LABEL_PREFIX, DEFAULT_CONTENT_TYPE, SECURITY_AUDIT_MAX_TRACKED_SOURCES,
+    SECURITY_AUDIT_THROTTLE_SECONDS,
+};
+use crate::crypto::{
+    generate_signing_key, get_public_key_hex, hash_api_key_hex, sign_message, verify_signature,
 };
-use crate::crypto::{generate_signing_key, hash_api_key_hex};
 use crate::domain::{Block, Blockchain, EncryptedCollection};
 use crate::storage::BlockchainStore;
 use crate::types::{
     AuditEventType, AuditLogBatch, AuditLogEntry, AuditLogFilter, AuditLogResponse, GoudChainError,
-    Result,
+    Result, SharedClock, SignedAuditBatch,
 };
 
 /// Callback function type for block broadcasting
@@ -35,9 +42,18 @@ pub type BroadcastCallback = Arc<dyn Fn(&Block) + Send + Sync>;
 
 /// Callback function type for audit log event broadcasting (WebSocket)
 /// This allows AuditLogger to remain independent of the presentation layer (Layer 3 doesn't depend on Layer 5)
-/// Parameters: event_type, timestamp, collection_id, metadata
+/// Parameters: account_hash, event_type, timestamp, collection_id, metadata
 pub type AuditEventCallback =
-    Arc<dyn Fn(AuditEventType, i64, Option<String>, serde_json::Value) + Send + Sync>;
+    Arc<dyn Fn(String, AuditEventType, i64, Option<String>, serde_json::Value) + Send + Sync>;
+
+/// Where the retention window currently starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RetentionCutoff {
+    /// Entries before this (Unix milliseconds) are past retention
+    timestamp_ms: i64,
+    /// First block created at or after the cutoff; earlier blocks hold only expired batches
+    boundary_block: u64,
+}
 
 /// Audit logger - manages audit log batching and storage on blockchain
 pub struct AuditLogger {
@@ -60,9 +76,20 @@ pub struct AuditLogger {
     flush_task: Option<JoinHandle<()>>,
 
     /// Cache: account_hash → API key bytes (for encryption)
-    /// Note: In production, this should use secure memory (zeroization)
+    /// Keys are only held while the account has pending logs: each entry is removed
+    /// when its batch is flushed and wiped from memory on drop
     /// Uses std::sync::Mutex for synchronous access from non-async contexts
-    api_key_cache: Arc<StdMutex<HashMap<String, Vec<u8>>>>,
+    api_key_cache: Arc<StdMutex<HashMap<String, Zeroizing<Vec<u8>>>>>,
+
+    /// Last time each (IP hash, event type) security event was logged (Unix seconds)
+    /// Throttles the node-level stream so floods of bad requests cannot flood the chain
+    security_event_times: StdMutex<HashMap<(String, AuditEventType), i64>>,
+
+    /// Retention window start, set by `apply_retention` (None keeps every entry current)
+    retention_cutoff: StdMutex<Option<RetentionCutoff>>,
+
+    /// Time source for entry timestamps, security event throttling and retention
+    clock: SharedClock,
 }
 
 impl AuditLogger {
@@ -78,6 +105,23 @@ impl AuditLogger {
         blockchain_store: Arc<BlockchainStore>,
         broadcast_callback: Option<BroadcastCallback>,
         audit_event_callback: Option<AuditEventCallback>,
+    ) -> Arc<Self> {
+        Self::with_clock(
+            blockchain,
+            blockchain_store,
+            broadcast_callback,
+            audit_event_callback,
+            SharedClock::default(),
+        )
+    }
+
+    /// Create an audit logger that timestamps and expires entries with `clock`
+    pub fn with_clock(
+        blockchain: Arc<RwLock<Blockchain>>,
+        blockchain_store: Arc<BlockchainStore>,
+        broadcast_callback: Option<BroadcastCallback>,
+        audit_event_callback: Option<AuditEventCallback>,
+        clock: SharedClock,
     ) -> Arc<Self> {
         let logger = Arc::new(Self {
             blockchain,
@@ -87,6 +131,9 @@ impl AuditLogger {
             pending_logs: Arc::new(StdMutex::new(HashMap::new())),
             flush_task: None,
             api_key_cache: Arc::new(StdMutex::new(HashMap::new())),
+            security_event_times: StdMutex::new(HashMap::new()),
+            retention_cutoff: StdMutex::new(None),
+            clock,
         });
 
         // Start background flush task
@@ -106,24 +153,26 @@ impl AuditLogger {
     ) -> Result<()> {
         let account_hash = hash_api_key_hex(api_key);
 
-        // Cache API key for encryption during flush
-        {
-            let mut cache = self.api_key_cache.lock().unwrap();
-            if !cache.contains_key(&account_hash) {
-                cache.insert(account_hash.clone(), api_key.to_vec());
-            }
-        }
-
         let entry = AuditLogEntry {
             event_type,
-            timestamp: Utc::now().timestamp_millis(),
+            timestamp: self.clock.now_millis(),
             collection_id: collection_id.clone(),
             ip_hash: hash_ip_truncated(client_ip),
             metadata: metadata.clone(),
             invalidated: false,
+            verified: false,
         };
 
         let mut pending = self.pending_logs.lock().unwrap();
+
+        // Cache API key for encryption during flush (under the pending lock so a
+        // concurrent flush cannot drop the key while this entry is still queued)
+        self.api_key_cache
+            .lock()
+            .unwrap()
+            .entry(account_hash.clone())
+            .or_insert_with(|| Zeroizing::new(api_key.to_vec()));
+
         let logs = pending.entry(account_hash.clone()).or_default();
         logs.push(entry.clone());
 
@@ -151,7 +200,7 @@ impl AuditLogger {
 
             // Execute callback in background (non-blocking)
             tokio::spawn(async move {
-                callback(event_type, timestamp, collection_id, metadata);
+                callback(account_hash, event_type, timestamp, collection_id, metadata);
             });
         }
 
@@ -180,29 +229,60 @@ impl AuditLogger {
         })
     }
 
+    /// Log a security event (failed login, rate limit block, bad credentials) to the node-level stream
+    /// These requests have no trusted API key, so entries are encrypted under the node audit key.
+    /// Repeats from the same IP are dropped for SECURITY_AUDIT_THROTTLE_SECONDS, and once
+    /// SECURITY_AUDIT_MAX_TRACKED_SOURCES sources are tracked in a window new ones are dropped too,
+    /// so an address-rotating flood can neither grow the map nor the chain without bound.
+    /// Returns whether the event was recorded.
+    pub fn log_security_event(
+        &self,
+        node_audit_key: &[u8],
+        event_type: AuditEventType,
+        client_ip: &str,
+        metadata: serde_json::Value,
+    ) -> Result<bool> {
+        let now = self.clock.now();
+        {
+            let mut times = self.security_event_times.lock().unwrap();
+            times.retain(|_, logged_at| now - *logged_at < SECURITY_AUDIT_THROTTLE_SECONDS);
+
+            let key = (hash_ip_truncated(client_ip), event_type);
+            if times.contains_key(&key) || times.len() >= SECURITY_AUDIT_MAX_TRACKED_SOURCES {
+                return Ok(false);
+            }
+            times.insert(key, now);
+        }
+
+        self.log(node_audit_key, event_type, None, client_ip, metadata)?;
+        Ok(true)
+    }
+
     /// Flush all pending audit log batches to blockchain
     /// Creates EncryptedCollection for each user's batch
     /// Can be called manually after auditable operations for immediate flushing
     pub async fn flush_all_batches(&self) -> Result<()> {
+        // Read-only maintenance mode creates no blocks: events stay buffered until writes resume
+        if self.blockchain.read().await.read_only {
+            return Ok(());
+        }
+
         // Collect all batches and API keys (drop locks before async work)
-        let batches_to_flush: Vec<(String, Vec<AuditLogEntry>, Vec<u8>)> = {
+        let batches_to_flush: Vec<(String, Vec<AuditLogEntry>, Zeroizing<Vec<u8>>)> = {
             let mut pending = self.pending_logs.lock().unwrap();
 
             if pending.is_empty() {
                 return Ok(()); // No logs to flush
             }
 
-            let api_key_cache = self.api_key_cache.lock().unwrap();
+            let mut api_key_cache = self.api_key_cache.lock().unwrap();
             let mut result = Vec::new();
 
             for (account_hash, entries) in pending.drain() {
-                if entries.is_empty() {
-                    continue;
-                }
-
-                // Get cached API key for encryption
-                let api_key = match api_key_cache.get(&account_hash) {
-                    Some(key) => key.clone(),
+                // Take the cached API key out: it is no longer needed once this batch is flushed
+                let api_key = match api_key_cache.remove(&account_hash) {
+                    Some(key) if !entries.is_empty() => key,
+                    Some(_) => continue,
                     None => {
                         warn!(
                             account_hash = %account_hash,
@@ -221,12 +301,6 @@ impl AuditLogger {
         let mut flushed_count = 0;
 
         for (account_hash, entries, api_key) in batches_to_flush {
-            // Create audit batch collection
-            let batch = AuditLogBatch::new(entries.clone());
-            let batch_json = serde_json::to_string(&batch).map_err(|e| {
-                GoudChainError::AuditLogError(format!("Failed to serialize batch: {}", e))
-            })?;
-
             // Get node signing key
             let signing_key = {
                 let bc = self.blockchain.read().await;
@@ -235,10 +309,26 @@ impl AuditLogger {
                     .unwrap_or_else(generate_signing_key)
             };
 
+            // Create audit batch and sign its exact serialized bytes
+            let batch = AuditLogBatch::new(entries.clone());
+            let batch_json = serde_json::to_string(&batch).map_err(|e| {
+                GoudChainError::AuditLogError(format!("Failed to serialize batch: {}", e))
+            })?;
+            let signed_json = serde_json::to_string(&SignedAuditBatch {
+                signature: sign_message(batch_json.as_bytes(), &signing_key),
+                batch: batch_json,
+            })
+            .map_err(|e| {
+                GoudChainError::AuditLogError(format!("Failed to serialize signed batch: {}", e))
+            })?;
+
             // Create encrypted collection with AUDIT: prefix
             let collection = EncryptedCollection::new(
                 format!("{}Batch", AUDIT_LABEL_PREFIX),
-                batch_json,
+                signed_json,
+                None,
+                DEFAULT_CONTENT_TYPE,
+                None,
                 &api_key,
                 account_hash.clone(),
                 &signing_key,
@@ -250,17 +340,30 @@ impl AuditLogger {
 
             // Check if this node is the validator for the next block
             let next_block_number = bc.chain.len() as u64 + 1;
-            let is_validator = crate::domain::blockchain::is_authorized_validator(
-                &bc.node_id,
-                next_block_number,
-                &bc.validator_config,
-            );
-
-            if is_validator {
+            let is_validator = bc
+                .validator_config
+                .is_node_authorized(&bc.node_id, next_block_number);
+
+            if is_validator && !bc.seal_due(self.clock.now()) {
+                // Blocks are coalesced (MIN_BLOCK_INTERVAL_SECONDS): the sealer puts this batch
+                // into our next block, so index that block now
+                let block_index = bc.chain.len() as u64;
+                drop(bc);
+                if let Err(e) = self.update_audit_index(&account_hash, block_index) {
+                    warn!(error = %e, block = block_index, "Failed to update audit index");
+                }
+                info!(
+                    account_hash = %account_hash,
+                    events = entries.len(),
+                    block = block_index,
+                    "Audit log batch queued for the next coalesced block"
+                );
+            } else if is_validator {
                 // Only create block if this node is the current validator
                 match bc.add_block() {
                     Ok(block) => {
                         let block_index = block.index;
+                        let is_checkpoint = bc.is_checkpoint_index(block_index);
                         drop(bc); // Release lock before I/O
 
                         // Save block to RocksDB
@@ -268,6 +371,21 @@ impl AuditLogger {
                             error!(error = %e, "Failed to save audit log block to RocksDB");
                         }
 
+                        // Save checkpoint if needed
+                        if is_checkpoint {
+                            if let Err(e) = self
+                                .blockchain_store
+                                .save_checkpoint(block_index, &block.hash)
+                            {
+                                error!(error = %e, "Failed to save checkpoint");
+                            }
+                        }
+
+                        // Index the block so queries skip the rest of the chain
+                        if let Err(e) = self.update_audit_index(&account_hash, block_index) {
+                            warn!(error = %e, block = block_index, "Failed to update audit index");
+                        }
+
                         // Broadcast block to peers via callback (if provided)
                         if let Some(ref broadcast) = self.broadcast_callback {
                             broadcast(&block);
@@ -330,90 +448,52 @@ impl AuditLogger {
     ) -> Result<AuditLogResponse> {
         let account_hash = hash_api_key_hex(api_key);
 
-        // Get block indexes containing user's audit logs (from RocksDB index)
-        // If index is empty/missing, fall back to scanning entire blockchain
-        let block_indexes = self.get_audit_block_indexes(&account_hash)?;
+        let cutoff = *self.retention_cutoff.lock().unwrap();
+        let boundary = cutoff.map_or(0, |cutoff| cutoff.boundary_block);
 
         let blockchain = self.blockchain.read().await;
-        let indexes_to_scan: Vec<u64> = if block_indexes.is_empty() {
-            // No index available, scan all blocks
-            (0..blockchain.chain.len() as u64).collect()
-        } else {
-            block_indexes
-        };
+        let (indexes_to_scan, full_scan) = self.indexes_to_scan(
+            &account_hash,
+            blockchain.chain.len(),
+            filter.include_invalidated,
+        )?;
 
         // Load blocks and decrypt audit logs
         let mut all_entries = Vec::new();
+        let mut discovered_blocks = Vec::new();
 
         for block_index in indexes_to_scan {
             if let Some(block) = blockchain.chain.get(block_index as usize) {
-                // Get collections owned by user
-                match block.get_collections_by_owner(api_key) {
-                    Ok(collections) => {
-                        for collection in collections {
-                            // Filter for audit collections only
-                            if let Ok(metadata) = collection.decrypt_metadata(api_key) {
-                                if let Some(label) = metadata["label"].as_str() {
-                                    if !label.starts_with(AUDIT_LABEL_PREFIX) {
-                                        continue;
-                                    }
-
-                                    // Decrypt audit batch
-                                    match collection.decrypt_payload(api_key) {
-                                        Ok(payload) => {
-                                            match serde_json::from_str::<AuditLogBatch>(&payload) {
-                                                Ok(batch) => all_entries.extend(batch.entries),
-                                                Err(e) => warn!(
-                                                    error = %e,
-                                                    "Failed to parse audit batch"
-                                                ),
-                                            }
-                                        }
-                                        Err(e) => warn!(error = %e, "Failed to decrypt audit log"),
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => warn!(error = %e, block = block_index, "Failed to get collections"),
+                let signer_key = validator_public_key(&blockchain, &block.validator);
+                let mut entries = decrypt_block_entries(
+                    &blockchain.full_block(block),
+                    api_key,
+                    signer_key.as_deref(),
+                );
+                mark_expired(&mut entries, cutoff);
+                if full_scan && !entries.is_empty() && block_index >= boundary {
+                    discovered_blocks.push(block_index);
                 }
+                all_entries.extend(entries);
             }
         }
 
         drop(blockchain);
 
-        // Apply filters
-        all_entries.retain(|entry| {
-            // Filter invalidated logs
-            if !filter.include_invalidated && entry.invalidated {
-                return false;
-            }
-
-            // Filter by event type
-            if let Some(event_type) = filter.event_type {
-                if entry.event_type != event_type {
-                    return false;
-                }
-            }
-
-            // Filter by timestamp range
-            if let Some(start_ts) = filter.start_ts {
-                if entry.timestamp < start_ts {
-                    return false;
-                }
-            }
-
-            if let Some(end_ts) = filter.end_ts {
-                if entry.timestamp > end_ts {
-                    return false;
-                }
+        // Backfill the index from the full scan so the next query is indexed
+        // (blocks before the retention boundary stay out of it)
+        discovered_blocks.dedup();
+        for block_index in discovered_blocks {
+            if let Err(e) = self.update_audit_index(&account_hash, block_index) {
+                warn!(error = %e, block = block_index, "Failed to backfill audit index");
             }
+        }
 
-            true
-        });
+        // Apply filters
+        all_entries.retain(|entry| filter.matches(entry));
 
         // Sort by timestamp (newest first)
-        all_entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        all_entries.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp));
 
         // Paginate
         let total = all_entries.len();
@@ -435,27 +515,158 @@ impl AuditLogger {
         })
     }
 
+    /// Stream every audit entry matching the filter, one block's worth at a time
+    /// Entries come out in chain order (oldest block first) so nothing has to be
+    /// buffered for sorting; the blockchain lock is only held while a block is decrypted
+    pub async fn export_logs(
+        self: &Arc<Self>,
+        api_key: Zeroizing<Vec<u8>>,
+        filter: AuditLogFilter,
+    ) -> Result<impl Stream<Item = Vec<AuditLogEntry>>> {
+        let account_hash = hash_api_key_hex(&api_key);
+        let cutoff = *self.retention_cutoff.lock().unwrap();
+        let chain_len = self.blockchain.read().await.chain.len();
+        let (mut indexes_to_scan, _) =
+            self.indexes_to_scan(&account_hash, chain_len, filter.include_invalidated)?;
+        indexes_to_scan.sort_unstable();
+
+        let state = (
+            Arc::clone(self),
+            api_key,
+            filter,
+            indexes_to_scan.into_iter(),
+        );
+        Ok(stream::unfold(
+            state,
+            |(logger, api_key, filter, mut indexes)| async move {
+                // Skip blocks without matching entries so every chunk is non-empty
+                for block_index in indexes.by_ref() {
+                    let mut entries = {
+                        let blockchain = logger.blockchain.read().await;
+                        match blockchain.chain.get(block_index as usize) {
+                            Some(block) => {
+                                let signer_key =
+                                    validator_public_key(&blockchain, &block.validator);
+                                decrypt_block_entries(
+                                    &blockchain.full_block(block),
+                                    &api_key,
+                                    signer_key.as_deref(),
+                                )
+                            }
+                            None => continue,
+                        }
+                    };
+                    mark_expired(&mut entries, cutoff);
+                    entries.retain(|entry| filter.matches(entry));
+                    if !entries.is_empty() {
+                        return Some((entries, (logger, api_key, filter, indexes)));
+                    }
+                }
+                None
+            },
+        ))
+    }
+
+    /// Block indexes a query must read for a user, and whether that is a full scan
+    /// Uses the RocksDB index when present, otherwise every block in the chain. Blocks before
+    /// the retention boundary are skipped unless expired entries are wanted; those are no
+    /// longer indexed, so that whole range is read.
+    fn indexes_to_scan(
+        &self,
+        account_hash: &str,
+        chain_len: usize,
+        include_invalidated: bool,
+    ) -> Result<(Vec<u64>, bool)> {
+        let boundary = self
+            .retention_cutoff
+            .lock()
+            .unwrap()
+            .map_or(0, |cutoff| cutoff.boundary_block)
+            .min(chain_len as u64);
+        let first = if include_invalidated { 0 } else { boundary };
+
+        let Some(block_indexes) = self.get_audit_block_indexes(account_hash)? else {
+            return Ok(((first..chain_len as u64).collect(), true));
+        };
+
+        let mut indexes: Vec<u64> = (first..boundary).collect();
+        indexes.extend(block_indexes.into_iter().filter(|&index| index >= boundary));
+        Ok((indexes, false))
+    }
+
+    /// Move the retention window to `retention_seconds` before now (periodic maintenance)
+    /// Entries older than that are reported `invalidated`, and blocks created before it are
+    /// removed from every user's index. Returns how many index references were removed.
+    pub async fn apply_retention(&self, retention_seconds: i64) -> Result<usize> {
+        let timestamp_ms = self.clock.now_millis() - retention_seconds * 1000;
+        let boundary_block = {
+            let blockchain = self.blockchain.read().await;
+            // Block timestamps only increase, and a batch's entries predate its block
+            blockchain
+                .chain
+                .partition_point(|block| block.timestamp * 1000 < timestamp_ms) as u64
+        };
+        *self.retention_cutoff.lock().unwrap() = Some(RetentionCutoff {
+            timestamp_ms,
+            boundary_block,
+        });
+
+        let db = self.blockchain_store.get_db();
+        let mut removed = 0;
+        for item in db.prefix_iterator(AUDIT_INDEX_PREFIX.as_bytes()) {
+            let (key, value) = item.map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+            if !key.starts_with(AUDIT_INDEX_PREFIX.as_bytes()) {
+                break; // Stop iteration when prefix changes
+            }
+
+            let mut indexes: Vec<u64> = bincode::deserialize(&value).map_err(|e| {
+                GoudChainError::AuditLogError(format!("Failed to deserialize index: {}", e))
+            })?;
+            let before = indexes.len();
+            indexes.retain(|&index| index >= boundary_block);
+            if indexes.len() == before {
+                continue;
+            }
+            removed += before - indexes.len();
+
+            // An empty index still marks the account as indexed (no full scan on query)
+            let serialized = bincode::serialize(&indexes).map_err(|e| {
+                GoudChainError::AuditLogError(format!("Failed to serialize index: {}", e))
+            })?;
+            db.put(&key, serialized)
+                .map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+        }
+
+        if removed > 0 {
+            info!(
+                boundary_block,
+                removed, "Audit retention removed expired blocks from the index"
+            );
+        }
+        Ok(removed)
+    }
+
     /// Get block indexes containing audit logs for a user (from RocksDB index)
-    /// This is a performance optimization to avoid scanning entire blockchain
-    fn get_audit_block_indexes(&self, account_hash: &str) -> Result<Vec<u64>> {
-        let key = format!("audit_index:{}", account_hash);
+    /// This is a performance optimization to avoid scanning entire blockchain.
+    /// None if the index was never written (e.g. a chain older than the index); an empty
+    /// index means every indexed block has passed retention
+    fn get_audit_block_indexes(&self, account_hash: &str) -> Result<Option<Vec<u64>>> {
+        let key = format!("{}{}", AUDIT_INDEX_PREFIX, account_hash);
         let db = self.blockchain_store.get_db();
 
         match db.get(key.as_bytes()) {
-            Ok(Some(bytes)) => bincode::deserialize(&bytes).map_err(|e| {
+            Ok(Some(bytes)) => bincode::deserialize(&bytes).map(Some).map_err(|e| {
                 GoudChainError::AuditLogError(format!("Failed to deserialize index: {}", e))
             }),
-            Ok(None) => Ok(Vec::new()), // No audit logs for this user
+            Ok(None) => Ok(None),
             Err(e) => Err(GoudChainError::RocksDbError(e.to_string())),
         }
     }
 
     /// Update RocksDB index: audit_index:{account_hash} → [block_numbers]
     /// This enables fast lookup of blocks containing user's audit logs
-    /// NOTE: Currently unused, reserved for future performance optimization
-    #[allow(dead_code)]
     fn update_audit_index(&self, account_hash: &str, block_index: u64) -> Result<()> {
-        let key = format!("audit_index:{}", account_hash);
+        let key = format!("{}{}", AUDIT_INDEX_PREFIX, account_hash);
         let db = self.blockchain_store.get_db();
 
         // Read existing indexes
@@ -484,6 +695,106 @@ impl AuditLogger {
     }
 }
 
+/// Decrypt the audit batches a user owns in one block
+/// Non-audit collections and undecryptable batches are skipped with a warning;
+/// `signer_public_key` is the trusted key of the block's validator, if known
+fn decrypt_block_entries(
+    block: &Block,
+    api_key: &[u8],
+    signer_public_key: Option<&str>,
+) -> Vec<AuditLogEntry> {
+    let collections = match block.get_collections_by_owner(api_key) {
+        Ok(collections) => collections,
+        Err(e) => {
+            warn!(error = %e, block = block.index, "Failed to get collections");
+            return Vec::new();
+        }
+    };
+
+    let mut entries = Vec::new();
+    for collection in collections {
+        // Filter for audit collections only
+        let is_audit = collection
+            .decrypt_metadata(api_key)
+            .ok()
+            .and_then(|metadata| metadata["label"].as_str().map(str::to_string))
+            .is_some_and(|label| label.starts_with(AUDIT_LABEL_PREFIX));
+        if !is_audit {
+            continue;
+        }
+
+        // Decrypt audit batch
+        match collection.decrypt_payload(api_key) {
+            Ok(payload) => match open_audit_payload(&payload, signer_public_key) {
+                Ok(batch_entries) => entries.extend(batch_entries),
+                Err(e) => warn!(error = %e, "Failed to parse audit batch"),
+            },
+            Err(e) => warn!(error = %e, "Failed to decrypt audit log"),
+        }
+    }
+    entries
+}
+
+/// Flag entries from before the retention window as invalidated
+fn mark_expired(entries: &mut [AuditLogEntry], cutoff: Option<RetentionCutoff>) {
+    if let Some(cutoff) = cutoff {
+        for entry in entries.iter_mut() {
+            entry.invalidated |= entry.timestamp < cutoff.timestamp_ms;
+        }
+    }
+}
+
+/// Parse a decrypted audit payload, checking the node signature against the trusted key
+/// Legacy unsigned batches and batches that fail verification are kept but marked unverified
+fn open_audit_payload(
+    payload: &str,
+    signer_public_key: Option<&str>,
+) -> Result<Vec<AuditLogEntry>> {
+    let (batch_json, verified) = match serde_json::from_str::<SignedAuditBatch>(payload) {
+        Ok(signed) => {
+            let verified = signer_public_key.is_some_and(|public_key| {
+                verify_signature(signed.batch.as_bytes(), &signed.signature, public_key).is_ok()
+            });
+            if !verified {
+                warn!("Audit batch signature could not be verified");
+            }
+            (signed.batch, verified)
+        }
+        Err(_) => (payload.to_string(), false),
+    };
+
+    let batch: AuditLogBatch = serde_json::from_str(&batch_json)
+        .map_err(|e| GoudChainError::AuditLogError(format!("Invalid audit batch: {}", e)))?;
+    Ok(batch
+        .entries
+        .into_iter()
+        .map(|mut entry| {
+            entry.verified = verified;
+            entry
+        })
+        .collect())
+}
+
+/// Trusted signing key for a block's validator
+/// Uses the published VALIDATOR_PUBLIC_KEYS entry, falling back to this node's own key for its blocks
+fn validator_public_key(blockchain: &Blockchain, validator: &str) -> Option<String> {
+    if let Some(public_key) = blockchain
+        .validator_config
+        .get_validator_public_key(validator)
+    {
+        return Some(public_key.to_string());
+    }
+
+    let own_validator = blockchain
+        .validator_config
+        .node_to_validator
+        .get(&blockchain.node_id)?;
+    match &blockchain.node_signing_key {
+        Some(signing_key) if own_validator == validator => Some(get_public_key_hex(signing_key)),
+        _ => None,
+    }
+}
+
 /// Hash IP address and truncate to 8 characters for privacy
 /// Uses SHA256(ip) and takes first AUDIT_IP_HASH_LENGTH bytes
 fn hash_ip_truncated(ip: &str) -> String {
@@ -497,6 +808,8 @@ fn hash_ip_truncated(ip: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::MockClock;
+    use chrono::Utc;
 
     #[test]
     fn test_hash_ip_truncated() {
@@ -512,4 +825,296 @@ mod tests {
         let hash3 = hash_ip_truncated("192.168.1.2");
         assert_ne!(hash, hash3);
     }
+
+    #[tokio::test]
+    async fn test_api_key_evicted_after_flush() {
+        let (_data_dir, logger) = single_validator_logger();
+
+        let api_key = crate::crypto::generate_api_key();
+        logger
+            .log(
+                &api_key,
+                AuditEventType::DataSubmitted,
+                None,
+                "127.0.0.1",
+                serde_json::json!({}),
+            )
+            .unwrap();
+        assert_eq!(logger.api_key_cache.lock().unwrap().len(), 1);
+
+        logger.flush_all_batches().await.unwrap();
+        assert!(logger.api_key_cache.lock().unwrap().is_empty());
+        assert!(logger.pending_logs.lock().unwrap().is_empty());
+    }
+
+    fn single_validator_logger() -> (tempfile::TempDir, Arc<AuditLogger>) {
+        single_validator_logger_with_clock(SharedClock::default())
+    }
+
+    fn single_validator_logger_with_clock(
+        clock: SharedClock,
+    ) -> (tempfile::TempDir, Arc<AuditLogger>) {
+        let validator_config = crate::config::ValidatorConfig::single("node1");
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(BlockchainStore::new(data_dir.path()).unwrap());
+        let blockchain = Arc::new(RwLock::new(
+            Blockchain::new("node1".to_string(), validator_config).unwrap(),
+        ));
+        (
+            data_dir,
+            AuditLogger::with_clock(blockchain, store, None, None, clock),
+        )
+    }
+
+    async fn log_and_flush(logger: &AuditLogger, api_key: &[u8]) {
+        logger
+            .log(
+                api_key,
+                AuditEventType::DataSubmitted,
+                None,
+                "127.0.0.1",
+                serde_json::json!({}),
+            )
+            .unwrap();
+        logger.flush_all_batches().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_query_reads_only_indexed_blocks() {
+        let (_data_dir, logger) = single_validator_logger();
+        let api_key = crate::crypto::generate_api_key();
+        let other_key = crate::crypto::generate_api_key();
+        let account_hash = hash_api_key_hex(&api_key);
+
+        // Interleave the user's audit blocks with another user's blocks
+        for _ in 0..5 {
+            log_and_flush(&logger, &api_key).await;
+            log_and_flush(&logger, &other_key).await;
+            log_and_flush(&logger, &other_key).await;
+        }
+
+        let indexed = logger
+            .get_audit_block_indexes(&account_hash)
+            .unwrap()
+            .unwrap();
+        assert_eq!(indexed, vec![1, 4, 7, 10, 13]);
+
+        let chain_len = logger.blockchain.read().await.chain.len();
+        let (to_scan, full_scan) = logger
+            .indexes_to_scan(&account_hash, chain_len, false)
+            .unwrap();
+        assert_eq!(to_scan, indexed);
+        assert!(!full_scan);
+
+        let response = logger
+            .query_logs(&api_key, AuditLogFilter::default(), 0, 50)
+            .await
+            .unwrap();
+        assert_eq!(response.total, 5);
+
+        // Narrowing the index hides blocks outside it, proving they are not read
+        let db = logger.blockchain_store.get_db();
+        let key = format!("{}{}", AUDIT_INDEX_PREFIX, account_hash);
+        db.put(key.as_bytes(), bincode::serialize(&vec![4u64, 10]).unwrap())
+            .unwrap();
+        let response = logger
+            .query_logs(&api_key, AuditLogFilter::default(), 0, 50)
+            .await
+            .unwrap();
+        assert_eq!(response.total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_full_scan_backfills_index() {
+        let (_data_dir, logger) = single_validator_logger();
+        let api_key = crate::crypto::generate_api_key();
+        let account_hash = hash_api_key_hex(&api_key);
+
+        log_and_flush(&logger, &api_key).await;
+        log_and_flush(&logger, &api_key).await;
+
+        // Simulate a chain written before the index existed
+        let db = logger.blockchain_store.get_db();
+        db.delete(format!("{}{}", AUDIT_INDEX_PREFIX, account_hash).as_bytes())
+            .unwrap();
+
+        let response = logger
+            .query_logs(&api_key, AuditLogFilter::default(), 0, 50)
+            .await
+            .unwrap();
+        assert_eq!(response.total, 2);
+        assert_eq!(
+            logger.get_audit_block_indexes(&account_hash).unwrap(),
+            Some(vec![1, 2])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retention_hides_and_unindexes_expired_entries() {
+        let (_data_dir, logger) = single_validator_logger();
+        let api_key = crate::crypto::generate_api_key();
+        let account_hash = hash_api_key_hex(&api_key);
+
+        for _ in 0..3 {
+            log_and_flush(&logger, &api_key).await;
+        }
+
+        // A generous window leaves everything current
+        assert_eq!(logger.apply_retention(86400).await.unwrap(), 0);
+        let response = logger
+            .query_logs(&api_key, AuditLogFilter::default(), 0, 50)
+            .await
+            .unwrap();
+        assert_eq!(response.total, 3);
+        assert!(response.logs.iter().all(|entry| !entry.invalidated));
+
+        // A window ending in the future expires every block
+        assert_eq!(logger.apply_retention(-60).await.unwrap(), 3);
+        assert_eq!(
+            logger.get_audit_block_indexes(&account_hash).unwrap(),
+            Some(vec![])
+        );
+        let response = logger
+            .query_logs(&api_key, AuditLogFilter::default(), 0, 50)
+            .await
+            .unwrap();
+        assert_eq!(response.total, 0);
+
+        let include_invalidated = AuditLogFilter {
+            include_invalidated: true,
+            ..Default::default()
+        };
+        let response = logger
+            .query_logs(&api_key, include_invalidated, 0, 50)
+            .await
+            .unwrap();
+        assert_eq!(response.total, 3);
+        assert!(response.logs.iter().all(|entry| entry.invalidated));
+    }
+
+    #[tokio::test]
+    async fn test_security_events_throttled_per_ip() {
+        let clock = Arc::new(MockClock::new(Utc::now().timestamp()));
+        let (_data_dir, logger) = single_validator_logger_with_clock(Arc::clone(&clock).into());
+        let node_key = crate::crypto::derive_node_audit_key(b"test_session_secret");
+
+        let log = |ip: &str| {
+            logger
+                .log_security_event(
+                    &node_key,
+                    AuditEventType::LoginFailed,
+                    ip,
+                    serde_json::json!({}),
+                )
+                .unwrap()
+        };
+        assert!(log("10.0.0.1"));
+        assert!(!log("10.0.0.1"));
+        assert!(log("10.0.0.2"));
+
+        // The throttle lifts once its window has passed
+        clock.advance(SECURITY_AUDIT_THROTTLE_SECONDS - 1);
+        assert!(!log("10.0.0.1"));
+        clock.advance(1);
+        assert!(log("10.0.0.1"));
+
+        logger.flush_all_batches().await.unwrap();
+        let filter = AuditLogFilter {
+            event_type: Some(AuditEventType::LoginFailed),
+            ..Default::default()
+        };
+        let response = logger.query_logs(&node_key, filter, 0, 50).await.unwrap();
+        assert_eq!(response.total, 3);
+    }
+
+    #[test]
+    fn test_security_event_sources_capped_per_window() {
+        let clock = Arc::new(MockClock::new(Utc::now().timestamp()));
+        let (_data_dir, logger) = single_validator_logger_with_clock(Arc::clone(&clock).into());
+        let node_key = crate::crypto::derive_node_audit_key(b"test_session_secret");
+
+        let log = |ip: &str| {
+            logger
+                .log_security_event(
+                    &node_key,
+                    AuditEventType::RateLimited,
+                    ip,
+                    serde_json::json!({}),
+                )
+                .unwrap()
+        };
+        for i in 0..SECURITY_AUDIT_MAX_TRACKED_SOURCES {
+            assert!(log(&format!("source-{}", i)));
+        }
+        assert!(!log("one-too-many"));
+        assert!(
+            logger.security_event_times.lock().unwrap().len() <= SECURITY_AUDIT_MAX_TRACKED_SOURCES
+        );
+
+        // Expired entries free their slots
+        clock.advance(SECURITY_AUDIT_THROTTLE_SECONDS);
+        assert!(log("one-too-many"));
+    }
+
+    fn sample_entry() -> AuditLogEntry {
+        AuditLogEntry {
+            event_type: AuditEventType::DataSubmitted,
+            timestamp: 1000,
+            collection_id: None,
+            ip_hash: "aaaaaaaa".to_string(),
+            metadata: serde_json::json!({}),
+            invalidated: false,
+            verified: false,
+        }
+    }
+
+    #[test]
+    fn test_tampered_batch_fails_verification() {
+        let signing_key = generate_signing_key();
+        let public_key = get_public_key_hex(&signing_key);
+        let batch_json = serde_json::to_string(&AuditLogBatch::new(vec![sample_entry()])).unwrap();
+        let signed = SignedAuditBatch {
+            signature: sign_message(batch_json.as_bytes(), &signing_key),
+            batch: batch_json,
+        };
+
+        let payload = serde_json::to_string(&signed).unwrap();
+        let entries = open_audit_payload(&payload, Some(&public_key)).unwrap();
+        assert!(entries[0].verified);
+
+        // Flip one byte of the signed batch (ip_hash "aaaaaaaa" -> "baaaaaaa")
+        let mut tampered = signed.clone();
+        tampered.batch = tampered.batch.replacen("aaaaaaaa", "baaaaaaa", 1);
+        let payload = serde_json::to_string(&tampered).unwrap();
+        let entries = open_audit_payload(&payload, Some(&public_key)).unwrap();
+        assert!(!entries[0].verified);
+
+        // Signature from another key is not trusted either
+        let other_key = get_public_key_hex(&generate_signing_key());
+        let payload = serde_json::to_string(&signed).unwrap();
+        assert!(!open_audit_payload(&payload, Some(&other_key)).unwrap()[0].verified);
+    }
+
+    #[test]
+    fn test_legacy_unsigned_batch_is_unverified() {
+        let payload = serde_json::to_string(&AuditLogBatch::new(vec![sample_entry()])).unwrap();
+        let entries = open_audit_payload(&payload, None).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(!entries[0].verified);
+    }
+
+    #[tokio::test]
+    async fn test_flushed_batches_verify_against_own_validator_key() {
+        let (_data_dir, logger) = single_validator_logger();
+        let api_key = crate::crypto::generate_api_key();
+        log_and_flush(&logger, &api_key).await;
+
+        let response = logger
+            .query_logs(&api_key, AuditLogFilter::default(), 0, 50)
+            .await
+            .unwrap();
+        assert_eq!(response.total, 1);
+        assert!(response.logs[0].verified);
+    }
 }
@@ -7,34 +7,107 @@
 //! - `metadata:schema_version` → String
 //! - `metadata:node_id` → String
 //! - `checkpoint:{index}` → Block hash
+//! - `collection_block:{collection_id}` → u64 index of the block holding the collection
+//! - `metadata:collection_index` → marker set once the collection index covers the chain
+//! - `metadata:pruned_before` → u64; blocks 1..n have had their collection envelopes pruned
+//! - `legacy_blind_index:{block_hash}` → bincode `Vec<String>` of owner blind indexes for
+//!   blocks stored without any (written by the materialize_blind_indexes migration)
+//! - `migrations` column family → applied migration records (see `MigrationStore`)
 //!
 //! **Performance Benefits:**
 //! - O(1) block writes (vs O(n) for JSON full serialization)
 //! - O(1) block reads by index
 //! - Snappy compression (50% disk space reduction)
 //! - Incremental writes (only new blocks, not entire chain)
+//!
+//! **Backups:** `create_backup` takes consistent, incremental RocksDB backups of a
+//! running node; `restore_backup` rebuilds a database directory from the latest one.
 
-use rocksdb::DB;
-use std::sync::Arc;
+use rocksdb::backup::{BackupEngine, BackupEngineOptions, RestoreOptions};
+use rocksdb::{Env, DB};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 use tracing::{info, warn};
 
-use crate::constants::{CHECKPOINT_INTERVAL, ROCKSDB_PATH};
+use crate::constants::{
+    BACKUP_RETENTION_COUNT, LEGACY_BLIND_INDEX_PREFIX, MIGRATIONS_COLUMN_FAMILY,
+};
 use crate::domain::Block;
+use crate::storage::migration_store::MigrationStore;
 use crate::types::{GoudChainError, Result};
 
+/// A checkpoint that does not match the stored chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckpointMismatch {
+    pub index: u64,
+    pub checkpoint_hash: Option<String>, // None if the checkpoint is missing
+    pub block_hash: Option<String>, // Recomputed hash; None if the block is missing or unreadable
+    pub reason: String,
+}
+
+/// Result of `BlockchainStore::verify_checkpoints`
+#[derive(Debug, Clone)]
+pub struct CheckpointReport {
+    pub chain_length: u64,
+    pub checked: usize,
+    pub mismatches: Vec<CheckpointMismatch>,
+}
+
+impl CheckpointReport {
+    pub fn is_valid(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// A completed RocksDB backup
+#[derive(Debug, Clone)]
+pub struct BackupInfo {
+    pub backup_id: u32,
+    pub timestamp: i64, // Unix seconds
+    pub size_bytes: u64,
+    pub num_files: u32,
+}
+
 /// Thread-safe wrapper around RocksDB for blockchain storage
 pub struct BlockchainStore {
     db: Arc<DB>,
+    backup_lock: Mutex<()>, // BackupEngine does not support concurrent writers on one directory
+}
+
+/// Open a BackupEngine on `backup_dir`, creating the directory if needed
+fn open_backup_engine(backup_dir: &Path) -> Result<BackupEngine> {
+    std::fs::create_dir_all(backup_dir)
+        .map_err(|e| GoudChainError::DirectoryCreationFailed(e.to_string()))?;
+    let opts = BackupEngineOptions::new(backup_dir)
+        .map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+    let env = Env::new().map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+    BackupEngine::open(&opts, &env).map_err(|e| GoudChainError::RocksDbError(e.to_string()))
+}
+
+/// The backup with the highest ID (backup IDs always increase)
+fn latest_backup(engine: &BackupEngine) -> Option<BackupInfo> {
+    engine
+        .get_backup_info()
+        .into_iter()
+        .max_by_key(|info| info.backup_id)
+        .map(|info| BackupInfo {
+            backup_id: info.backup_id,
+            timestamp: info.timestamp,
+            size_bytes: info.size,
+            num_files: info.num_files,
+        })
 }
 
 impl BlockchainStore {
-    /// Initialize RocksDB at the configured path with optimized settings
+    /// Initialize RocksDB at `path` (the configured data directory's rocksdb/) with optimized settings
     /// Tuned for free-tier cloud environments (slow disks, limited IOPS)
-    pub fn new() -> Result<Self> {
-        info!(path = %ROCKSDB_PATH, "Opening RocksDB for blockchain storage");
+    pub fn new(path: &Path) -> Result<Self> {
+        info!(path = %path.display(), "Opening RocksDB for blockchain storage");
 
         let mut opts = rocksdb::Options::default();
         opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
 
         // Memory buffers (64MB for free-tier RAM constraints)
         opts.set_write_buffer_size(64 * 1024 * 1024);
@@ -60,33 +133,26 @@ impl BlockchainStore {
         // Free-tier friendly: reduce fsync frequency (batch writes)
         opts.set_use_fsync(false); // Use fdatasync instead (faster)
 
-        let db = DB::open(&opts, ROCKSDB_PATH)
+        let db = DB::open_cf(&opts, path, [MIGRATIONS_COLUMN_FAMILY])
             .map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
 
         info!("BlockchainStore initialized with free-tier optimized settings");
 
-        Ok(Self { db: Arc::new(db) })
-    }
-
-    /// Initialize RocksDB at a custom path (for testing)
-    #[cfg(test)]
-    pub fn new_with_path(path: &str) -> Result<Self> {
-        let mut opts = rocksdb::Options::default();
-        opts.create_if_missing(true);
-        opts.set_write_buffer_size(64 * 1024 * 1024);
-        opts.set_max_write_buffer_number(3);
-        opts.set_min_write_buffer_number_to_merge(1);
-        opts.set_compression_type(rocksdb::DBCompressionType::Snappy);
-        opts.set_manual_wal_flush(false);
-        opts.set_level_compaction_dynamic_level_bytes(true);
-        opts.set_max_background_jobs(2);
-        opts.set_allow_mmap_reads(false);
-        opts.set_allow_mmap_writes(false);
-        opts.set_use_fsync(false);
+        let store = Self {
+            db: Arc::new(db),
+            backup_lock: Mutex::new(()),
+        };
 
-        let db = DB::open(&opts, path).map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+        // Databases from before the `migrations` column family kept their records in the default one
+        let imported = MigrationStore::new(&store).import_legacy_records()?;
+        if imported > 0 {
+            info!(
+                count = imported,
+                "Moved legacy migration records into the migrations column family"
+            );
+        }
 
-        Ok(Self { db: Arc::new(db) })
+        Ok(store)
     }
 
     /// Get the underlying RocksDB instance (for rate limiting and other extensions)
@@ -103,6 +169,8 @@ impl BlockchainStore {
         let block_key = format!("block:{}", block.index);
         batch.put(block_key.as_bytes(), &block_bytes);
 
+        Self::index_collections(&mut batch, block);
+
         let chain_length = block.index + 1;
         batch.put(b"metadata:chain_length", chain_length.to_le_bytes());
 
@@ -123,7 +191,7 @@ impl BlockchainStore {
         Ok(())
     }
 
-    /// Save checkpoint (block hash at checkpoint interval)
+    /// Save checkpoint (block hash at checkpoint interval, or at the tip on demand)
     pub fn save_checkpoint(&self, block_index: u64, block_hash: &str) -> Result<()> {
         let checkpoint_key = format!("checkpoint:{}", block_index);
 
@@ -146,6 +214,144 @@ impl BlockchainStore {
         Ok(())
     }
 
+    /// Atomically replace the persisted chain after a sync/reorg
+    ///
+    /// Deletes every stored block and checkpoint, then re-saves `blocks` and a
+    /// checkpoint for each block whose hash is in `checkpoints`, in a single
+    /// WriteBatch so a crash can never leave a mix of the old and new chains on disk.
+    pub fn replace_chain(&self, blocks: &[Block], checkpoints: &[String]) -> Result<()> {
+        let mut batch = rocksdb::WriteBatch::default();
+
+        // Delete old blocks, checkpoints and collection index entries
+        // (including any stale tail beyond the new length)
+        for prefix in [
+            b"block:".as_slice(),
+            b"checkpoint:".as_slice(),
+            b"collection_block:".as_slice(),
+        ] {
+            for item in self.db.prefix_iterator(prefix) {
+                let (key, _) = item.map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+                if !key.starts_with(prefix) {
+                    break; // Prefix scan completed
+                }
+                batch.delete(&key);
+            }
+        }
+
+        for block in blocks {
+            // Offloaded blocks keep the body already stored for them
+            let stored;
+            let block = if block.is_offloaded() {
+                stored = self
+                    .load_block(block.index)?
+                    .filter(|stored| stored.hash == block.hash && !stored.is_offloaded())
+                    .ok_or_else(|| {
+                        GoudChainError::SaveFailed(format!(
+                            "No stored body for offloaded block {}",
+                            block.index
+                        ))
+                    })?;
+                &stored
+            } else {
+                block
+            };
+            let block_bytes = bincode::serialize(block)
+                .map_err(|e| GoudChainError::SaveFailed(format!("Bincode serialization: {}", e)))?;
+            let block_key = format!("block:{}", block.index);
+            batch.put(block_key.as_bytes(), &block_bytes);
+            Self::index_collections(&mut batch, block);
+
+            if checkpoints.contains(&block.hash) {
+                let checkpoint_key = format!("checkpoint:{}", block.index);
+                batch.put(checkpoint_key.as_bytes(), block.hash.as_bytes());
+            }
+        }
+
+        let chain_length = blocks.len() as u64;
+        batch.put(b"metadata:chain_length", chain_length.to_le_bytes());
+        batch.put(b"metadata:collection_index", []);
+
+        // Write with sync for durability (same guarantees as save_block)
+        let mut write_opts = rocksdb::WriteOptions::default();
+        write_opts.set_sync(true);
+        write_opts.disable_wal(false);
+
+        self.db
+            .write_opt(batch, &write_opts)
+            .map_err(|e| GoudChainError::SaveFailed(e.to_string()))?;
+
+        info!(
+            chain_length = chain_length,
+            "Chain replaced in RocksDB with sync"
+        );
+        Ok(())
+    }
+
+    /// Add `collection_block:` entries for every collection in `block` to `batch`
+    fn index_collections(batch: &mut rocksdb::WriteBatch, block: &Block) {
+        match block.get_collection_ids() {
+            Ok(collection_ids) => {
+                for collection_id in collection_ids {
+                    let index_key = format!("collection_block:{}", collection_id);
+                    batch.put(index_key.as_bytes(), block.index.to_le_bytes());
+                }
+            }
+            Err(e) => {
+                warn!(
+                    block_index = block.index,
+                    error = %e,
+                    "Cannot index collections of block with unreadable envelope container"
+                );
+            }
+        }
+    }
+
+    /// Look up the index of the block holding `collection_id`
+    pub fn get_collection_block(&self, collection_id: &str) -> Result<Option<u64>> {
+        let index_key = format!("collection_block:{}", collection_id);
+        match self.db.get(index_key.as_bytes()) {
+            Ok(Some(bytes)) => {
+                let mut index_bytes = [0u8; 8];
+                index_bytes.copy_from_slice(&bytes);
+                Ok(Some(u64::from_le_bytes(index_bytes)))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => Err(GoudChainError::RocksDbError(e.to_string())),
+        }
+    }
+
+    /// Build the collection index for chains persisted before it existed
+    ///
+    /// Runs once: the marker it sets is kept up to date by `save_block` and `replace_chain`.
+    pub fn ensure_collection_index(&self, chain: &[Block]) -> Result<()> {
+        let indexed = self
+            .db
+            .get(b"metadata:collection_index")
+            .map_err(|e| GoudChainError::RocksDbError(e.to_string()))?
+            .is_some();
+        if indexed {
+            return Ok(());
+        }
+
+        let mut batch = rocksdb::WriteBatch::default();
+        for block in chain {
+            Self::index_collections(&mut batch, block);
+        }
+        batch.put(b"metadata:collection_index", []);
+
+        let mut write_opts = rocksdb::WriteOptions::default();
+        write_opts.set_sync(true);
+        self.db
+            .write_opt(batch, &write_opts)
+            .map_err(|e| GoudChainError::SaveFailed(e.to_string()))?;
+
+        info!(
+            chain_length = chain.len(),
+            "Collection index built in RocksDB"
+        );
+        Ok(())
+    }
+
     /// Save blockchain metadata (schema version, node ID)
     pub fn save_metadata(&self, node_id: &str, schema_version: &str) -> Result<()> {
         // Use sync write for metadata (critical for schema version tracking)
@@ -227,6 +433,21 @@ impl BlockchainStore {
         Ok(chain)
     }
 
+    /// Load a single block by index (None past the chain tip)
+    pub fn load_block(&self, index: u64) -> Result<Option<Block>> {
+        let block_key = format!("block:{}", index);
+        match self.db.get(block_key.as_bytes()) {
+            Ok(Some(bytes)) => bincode::deserialize(&bytes)
+                .map(Some)
+                .map_err(|e| GoudChainError::LoadFailed(format!("Bincode deserialization: {}", e))),
+            Ok(None) => Ok(None),
+            Err(e) => Err(GoudChainError::LoadFailed(format!(
+                "Failed to read block {}: {}",
+                index, e
+            ))),
+        }
+    }
+
     /// Load blockchain metadata (schema version)
     pub fn load_metadata(&self) -> Result<(String, String)> {
         let node_id = match self.db.get(b"metadata:node_id") {
@@ -257,37 +478,302 @@ impl BlockchainStore {
         Ok((node_id, schema_version))
     }
 
-    /// Load checkpoints from RocksDB
+    /// Load checkpoints from RocksDB, ordered by block index
+    ///
+    /// Every stored checkpoint is returned, so checkpoints taken on demand or under
+    /// an earlier CHECKPOINT_INTERVAL keep protecting the chain after a restart.
     pub fn load_checkpoints(&self) -> Result<Vec<String>> {
-        let mut checkpoints = Vec::new();
-        let mut index = CHECKPOINT_INTERVAL;
+        let checkpoints: Vec<String> = self.stored_checkpoints()?.into_values().collect();
 
-        // Scan for checkpoints (every CHECKPOINT_INTERVAL blocks)
-        loop {
-            let checkpoint_key = format!("checkpoint:{}", index);
-            match self.db.get(checkpoint_key.as_bytes()) {
-                Ok(Some(bytes)) => {
-                    let hash = String::from_utf8(bytes).map_err(|e| {
-                        GoudChainError::LoadFailed(format!("Invalid checkpoint hash: {}", e))
-                    })?;
-                    checkpoints.push(hash);
-                    index += CHECKPOINT_INTERVAL;
+        info!(
+            checkpoint_count = checkpoints.len(),
+            "Checkpoints loaded from RocksDB"
+        );
+        Ok(checkpoints)
+    }
+
+    /// Every stored checkpoint keyed by block index
+    /// (RocksDB orders keys as strings, so `checkpoint:100` would come before `checkpoint:20`)
+    fn stored_checkpoints(&self) -> Result<BTreeMap<u64, String>> {
+        let mut stored = BTreeMap::new();
+        for item in self.db.prefix_iterator(b"checkpoint:") {
+            let (key, value) = item.map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+            let Some(index) = key.strip_prefix(b"checkpoint:") else {
+                break; // Prefix scan completed
+            };
+            let index = std::str::from_utf8(index)
+                .ok()
+                .and_then(|s| s.parse::<u64>().ok())
+                .ok_or_else(|| GoudChainError::LoadFailed("Invalid checkpoint key".to_string()))?;
+            stored.insert(index, String::from_utf8_lossy(&value).into_owned());
+        }
+        Ok(stored)
+    }
+
+    /// Verify persisted checkpoints against the stored chain
+    ///
+    /// Recomputes the hash of every block at a multiple of `checkpoint_interval` or
+    /// with a stored checkpoint, and compares it with both the block's stored hash
+    /// and the checkpoint. Also reports missing checkpoints on the interval and
+    /// checkpoints past the chain tip. Detects on-disk corruption; nothing is repaired.
+    pub fn verify_checkpoints(&self, checkpoint_interval: u64) -> Result<CheckpointReport> {
+        let chain_length = match self.db.get(b"metadata:chain_length") {
+            Ok(Some(bytes)) => {
+                let mut length_bytes = [0u8; 8];
+                length_bytes.copy_from_slice(&bytes);
+                u64::from_le_bytes(length_bytes)
+            }
+            Ok(None) => 0,
+            Err(e) => {
+                return Err(GoudChainError::LoadFailed(format!(
+                    "Failed to read chain length: {}",
+                    e
+                )))
+            }
+        };
+
+        // Every stored checkpoint, including on-demand and stray ones off the interval
+        let mut stored = self.stored_checkpoints()?;
+
+        // Blocks on the interval must have a checkpoint; others are checked if they have one
+        let mut indexes: BTreeSet<u64> = stored
+            .keys()
+            .copied()
+            .filter(|&index| index < chain_length)
+            .collect();
+        if checkpoint_interval > 0 {
+            indexes.extend(
+                (1..)
+                    .map(|n| n * checkpoint_interval)
+                    .take_while(|&index| index < chain_length),
+            );
+        }
+
+        let mut mismatches = Vec::new();
+        let mut checked = 0;
+        for index in indexes {
+            checked += 1;
+            let checkpoint_hash = stored.remove(&index);
+            let mismatch = |block_hash: Option<String>, reason: &str| CheckpointMismatch {
+                index,
+                checkpoint_hash: checkpoint_hash.clone(),
+                block_hash,
+                reason: reason.to_string(),
+            };
+
+            let block_key = format!("block:{}", index);
+            let block = match self.db.get(block_key.as_bytes()) {
+                Ok(Some(bytes)) => bincode::deserialize::<Block>(&bytes).ok(),
+                Ok(None) => {
+                    mismatches.push(mismatch(None, "Block is missing"));
+                    continue;
                 }
-                Ok(None) => break, // No more checkpoints
                 Err(e) => {
                     return Err(GoudChainError::LoadFailed(format!(
-                        "Failed to read checkpoint: {}",
-                        e
+                        "Failed to read block {}: {}",
+                        index, e
                     )))
                 }
+            };
+
+            match block {
+                None => mismatches.push(mismatch(None, "Block cannot be decoded")),
+                Some(block) => {
+                    let recomputed = block.calculate_hash();
+                    if recomputed != block.hash {
+                        mismatches.push(mismatch(
+                            Some(recomputed),
+                            "Block contents do not match its stored hash",
+                        ));
+                    } else if checkpoint_hash.is_none() {
+                        mismatches.push(mismatch(Some(recomputed), "Checkpoint is missing"));
+                    } else if checkpoint_hash.as_deref() != Some(recomputed.as_str()) {
+                        mismatches.push(mismatch(
+                            Some(recomputed),
+                            "Checkpoint does not match block hash",
+                        ));
+                    }
+                }
             }
         }
 
+        // Checkpoints left over are past the tip
+        for (index, checkpoint_hash) in stored {
+            checked += 1;
+            mismatches.push(CheckpointMismatch {
+                index,
+                checkpoint_hash: Some(checkpoint_hash),
+                block_hash: None,
+                reason: "Checkpoint has no matching block".to_string(),
+            });
+        }
+
+        if mismatches.is_empty() {
+            info!(checked = checked, "Checkpoint verification passed");
+        } else {
+            warn!(
+                checked = checked,
+                mismatches = mismatches.len(),
+                "Checkpoint verification found mismatches"
+            );
+        }
+
+        Ok(CheckpointReport {
+            chain_length,
+            checked,
+            mismatches,
+        })
+    }
+
+    /// Prune the collection envelopes of every block before `index`
+    ///
+    /// Headers (hash, previous_hash, merkle_root), blind indexes and account envelopes
+    /// are kept, so the chain still validates and accounts still authenticate. Blocks
+    /// pruned by an earlier run are skipped. Returns how many blocks were rewritten.
+    pub fn prune_before(&self, index: u64) -> Result<usize> {
+        let already_pruned = self.load_pruned_before()?;
+        if index <= already_pruned {
+            return Ok(0);
+        }
+
+        let mut batch = rocksdb::WriteBatch::default();
+        let mut pruned = 0;
+        for block_index in already_pruned.max(1)..index {
+            let block_key = format!("block:{}", block_index);
+            let bytes = match self.db.get(block_key.as_bytes()) {
+                Ok(Some(bytes)) => bytes,
+                Ok(None) => break, // Past the chain tip
+                Err(e) => {
+                    return Err(GoudChainError::LoadFailed(format!(
+                        "Failed to read block {}: {}",
+                        block_index, e
+                    )))
+                }
+            };
+            let mut block: Block = bincode::deserialize(&bytes).map_err(|e| {
+                GoudChainError::LoadFailed(format!("Bincode deserialization: {}", e))
+            })?;
+            block.prune_collections()?;
+
+            let block_bytes = bincode::serialize(&block)
+                .map_err(|e| GoudChainError::SaveFailed(format!("Bincode serialization: {}", e)))?;
+            batch.put(block_key.as_bytes(), &block_bytes);
+            pruned += 1;
+        }
+        batch.put(b"metadata:pruned_before", index.to_le_bytes());
+
+        let mut write_opts = rocksdb::WriteOptions::default();
+        write_opts.set_sync(true);
+        self.db
+            .write_opt(batch, &write_opts)
+            .map_err(|e| GoudChainError::SaveFailed(e.to_string()))?;
+
+        // Reclaim the space of the overwritten bodies now rather than at the next compaction
+        self.db
+            .compact_range(Some(b"block:".as_slice()), Some(b"block;".as_slice()));
+
         info!(
-            checkpoint_count = checkpoints.len(),
-            "Checkpoints loaded from RocksDB"
+            pruned_blocks = pruned,
+            pruned_before = index,
+            "Pruned collection envelopes from RocksDB"
         );
-        Ok(checkpoints)
+        Ok(pruned)
+    }
+
+    /// First block index whose collections are still stored (0 if nothing was pruned)
+    pub fn load_pruned_before(&self) -> Result<u64> {
+        match self.db.get(b"metadata:pruned_before") {
+            Ok(Some(bytes)) => {
+                let mut index_bytes = [0u8; 8];
+                index_bytes.copy_from_slice(&bytes);
+                Ok(u64::from_le_bytes(index_bytes))
+            }
+            Ok(None) => Ok(0),
+            Err(e) => Err(GoudChainError::LoadFailed(format!(
+                "Failed to read pruned_before: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Backfilled owner blind indexes, keyed by block hash
+    pub fn load_legacy_blind_indexes(&self) -> Result<HashMap<String, Vec<String>>> {
+        let mut indexes = HashMap::new();
+        for item in self
+            .db
+            .prefix_iterator(LEGACY_BLIND_INDEX_PREFIX.as_bytes())
+        {
+            let (key, value) = item.map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+            let Some(block_hash) = key.strip_prefix(LEGACY_BLIND_INDEX_PREFIX.as_bytes()) else {
+                break; // Prefix scan completed
+            };
+            let block_indexes: Vec<String> = bincode::deserialize(&value)
+                .map_err(|e| GoudChainError::DeserializationError(e.to_string()))?;
+            indexes.insert(
+                String::from_utf8_lossy(block_hash).into_owned(),
+                block_indexes,
+            );
+        }
+        Ok(indexes)
+    }
+
+    /// Back up the database into `backup_dir` without stopping the node
+    ///
+    /// Flushes the WAL and memtables first so the backup holds every synced write,
+    /// verifies the new backup, then keeps only the latest BACKUP_RETENTION_COUNT.
+    /// Backups are incremental: unchanged SST files are shared between them.
+    pub fn create_backup(&self, backup_dir: &Path) -> Result<BackupInfo> {
+        let _guard = self
+            .backup_lock
+            .lock()
+            .map_err(|_| GoudChainError::Internal("Backup lock poisoned".to_string()))?;
+        let mut engine = open_backup_engine(backup_dir)?;
+
+        self.db
+            .flush_wal(true)
+            .map_err(|e| GoudChainError::RocksDbError(format!("WAL flush failed: {}", e)))?;
+        engine
+            .create_new_backup_flush(&self.db, true)
+            .map_err(|e| GoudChainError::RocksDbError(format!("Backup failed: {}", e)))?;
+
+        let latest = latest_backup(&engine)
+            .ok_or_else(|| GoudChainError::Internal("Backup not recorded".to_string()))?;
+        engine.verify_backup(latest.backup_id).map_err(|e| {
+            GoudChainError::RocksDbError(format!("Backup verification failed: {}", e))
+        })?;
+        engine
+            .purge_old_backups(BACKUP_RETENTION_COUNT)
+            .map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+
+        info!(
+            backup_id = latest.backup_id,
+            size_bytes = latest.size_bytes,
+            path = %backup_dir.display(),
+            "RocksDB backup created"
+        );
+        Ok(latest)
+    }
+
+    /// Restore the latest backup in `backup_dir` into `db_dir`
+    ///
+    /// The database must not be open: run this before the node starts. Existing
+    /// files in `db_dir` are replaced by the backup's contents.
+    pub fn restore_backup(backup_dir: &Path, db_dir: &Path) -> Result<BackupInfo> {
+        let mut engine = open_backup_engine(backup_dir)?;
+        let latest = latest_backup(&engine).ok_or_else(|| {
+            GoudChainError::LoadFailed(format!("No backups found in {}", backup_dir.display()))
+        })?;
+
+        engine
+            .restore_from_latest_backup(db_dir, db_dir, &RestoreOptions::default())
+            .map_err(|e| GoudChainError::RocksDbError(format!("Restore failed: {}", e)))?;
+
+        info!(
+            backup_id = latest.backup_id,
+            path = %db_dir.display(),
+            "RocksDB restored from backup"
+        );
+        Ok(latest)
     }
 
     /// Check if RocksDB contains blockchain data
@@ -324,7 +810,38 @@ impl BlockchainStore {
                 .map_err(|e| GoudChainError::SaveFailed(e.to_string()))?;
         }
 
+        // Delete collection index entries
+        for item in self.db.prefix_iterator(b"collection_block:") {
+            let (key, _) = item.map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+            if !key.starts_with(b"collection_block:") {
+                break; // Prefix scan completed
+            }
+            self.db
+                .delete(&key)
+                .map_err(|e| GoudChainError::SaveFailed(e.to_string()))?;
+        }
+
+        // Delete backfilled blind indexes
+        for item in self
+            .db
+            .prefix_iterator(LEGACY_BLIND_INDEX_PREFIX.as_bytes())
+        {
+            let (key, _) = item.map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+            if !key.starts_with(LEGACY_BLIND_INDEX_PREFIX.as_bytes()) {
+                break; // Prefix scan completed
+            }
+            self.db
+                .delete(&key)
+                .map_err(|e| GoudChainError::SaveFailed(e.to_string()))?;
+        }
+
         // Delete metadata
+        self.db
+            .delete(b"metadata:collection_index")
+            .map_err(|e| GoudChainError::SaveFailed(e.to_string()))?;
+        self.db
+            .delete(b"metadata:pruned_before")
+            .map_err(|e| GoudChainError::SaveFailed(e.to_string()))?;
         self.db
             .delete(b"metadata:chain_length")
             .map_err(|e| GoudChainError::SaveFailed(e.to_string()))?;
@@ -343,6 +860,7 @@ impl BlockchainStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::DEFAULT_CHECKPOINT_INTERVAL as CHECKPOINT_INTERVAL;
     use crate::domain::block::BlockConfig;
     use rocksdb::DB;
     use std::sync::Arc;
@@ -351,7 +869,10 @@ mod tests {
     fn create_test_store() -> BlockchainStore {
         let temp_dir = std::env::temp_dir().join(format!("test_rocksdb_{}", rand::random::<u64>()));
         let db = DB::open_default(&temp_dir).expect("Failed to open test DB");
-        BlockchainStore { db: Arc::new(db) }
+        BlockchainStore {
+            db: Arc::new(db),
+            backup_lock: Mutex::new(()),
+        }
     }
 
     #[test]
@@ -391,4 +912,353 @@ mod tests {
         assert_eq!(node_id, "node1");
         assert_eq!(schema_version, "v6_rocksdb");
     }
+
+    fn make_chain(length: u64) -> Vec<Block> {
+        let mut chain: Vec<Block> = Vec::new();
+        for index in 0..length {
+            let previous_hash = chain
+                .last()
+                .map(|b| b.hash.clone())
+                .unwrap_or_else(|| "0".to_string());
+            chain.push(
+                Block::new(BlockConfig {
+                    index,
+                    account_envelopes: Vec::new(),
+                    collection_envelopes: Vec::new(),
+                    previous_hash,
+                    validator: "Validator_1".to_string(),
+                    blind_indexes: Vec::new(),
+                    block_salt: "test_salt".to_string(),
+                })
+                .unwrap(),
+            );
+        }
+        chain
+    }
+
+    /// Hashes of the blocks in `chain` that fall on the default checkpoint interval
+    #[allow(unknown_lints)]
+    #[allow(clippy::manual_is_multiple_of)]
+    fn interval_checkpoints(chain: &[Block]) -> Vec<String> {
+        chain
+            .iter()
+            .filter(|b| b.index > 0 && b.index % CHECKPOINT_INTERVAL == 0)
+            .map(|b| b.hash.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_replace_chain_overwrites_blocks() {
+        let store = create_test_store();
+
+        let old_chain = make_chain(5);
+        for block in &old_chain {
+            store.save_block(block).unwrap();
+        }
+
+        // Shorter replacement must not leave stale blocks behind
+        let new_chain = make_chain(3);
+        store.replace_chain(&new_chain, &[]).unwrap();
+
+        let loaded = store.load_chain().unwrap();
+        assert_eq!(loaded.len(), 3);
+        for (loaded_block, block) in loaded.iter().zip(&new_chain) {
+            assert_eq!(loaded_block.hash, block.hash);
+        }
+        assert!(store.db.get(b"block:4").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_replace_chain_rewrites_checkpoints() {
+        let store = create_test_store();
+
+        // Stale checkpoint from the old chain
+        store
+            .save_checkpoint(CHECKPOINT_INTERVAL, "old_checkpoint_hash")
+            .unwrap();
+
+        let new_chain = make_chain(CHECKPOINT_INTERVAL + 1);
+        store
+            .replace_chain(&new_chain, &interval_checkpoints(&new_chain))
+            .unwrap();
+
+        let checkpoints = store.load_checkpoints().unwrap();
+        assert_eq!(
+            checkpoints,
+            vec![new_chain[CHECKPOINT_INTERVAL as usize].hash.clone()]
+        );
+    }
+
+    #[test]
+    fn test_verify_checkpoints_reports_mismatches() {
+        let store = create_test_store();
+        let chain = make_chain(2 * CHECKPOINT_INTERVAL + 1);
+        store
+            .replace_chain(&chain, &interval_checkpoints(&chain))
+            .unwrap();
+
+        let report = store.verify_checkpoints(CHECKPOINT_INTERVAL).unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.checked, 2);
+        assert_eq!(report.chain_length, 2 * CHECKPOINT_INTERVAL + 1);
+
+        // Corrupt one checkpoint, tamper with the other block, add a stray checkpoint
+        store
+            .save_checkpoint(CHECKPOINT_INTERVAL, "corrupted_hash")
+            .unwrap();
+        let mut tampered = chain[2 * CHECKPOINT_INTERVAL as usize].clone();
+        tampered.timestamp += 1;
+        store.save_block(&tampered).unwrap();
+        store
+            .save_checkpoint(5 * CHECKPOINT_INTERVAL, "stray_hash")
+            .unwrap();
+
+        let report = store.verify_checkpoints(CHECKPOINT_INTERVAL).unwrap();
+        assert!(!report.is_valid());
+        let indexes: Vec<u64> = report.mismatches.iter().map(|m| m.index).collect();
+        assert_eq!(
+            indexes,
+            vec![
+                CHECKPOINT_INTERVAL,
+                2 * CHECKPOINT_INTERVAL,
+                5 * CHECKPOINT_INTERVAL
+            ]
+        );
+        assert_eq!(
+            report.mismatches[0].block_hash.as_deref(),
+            Some(chain[CHECKPOINT_INTERVAL as usize].hash.as_str())
+        );
+        assert_eq!(
+            report.mismatches[1].reason,
+            "Block contents do not match its stored hash"
+        );
+        assert_eq!(report.mismatches[2].block_hash, None);
+    }
+
+    #[test]
+    fn test_checkpoints_off_the_interval() {
+        let store = create_test_store();
+        let chain = make_chain(CHECKPOINT_INTERVAL + 30);
+        let on_demand = chain[25].hash.clone();
+        let mut checkpoints = interval_checkpoints(&chain);
+        checkpoints.push(on_demand.clone());
+        store.replace_chain(&chain, &checkpoints).unwrap();
+        let later = CHECKPOINT_INTERVAL + 20;
+        store
+            .save_checkpoint(later, &chain[later as usize].hash)
+            .unwrap();
+
+        // Loaded in block order, not key order ("checkpoint:25" sorts last as a string)
+        assert_eq!(
+            store.load_checkpoints().unwrap(),
+            vec![
+                on_demand,
+                chain[CHECKPOINT_INTERVAL as usize].hash.clone(),
+                chain[later as usize].hash.clone()
+            ]
+        );
+        let report = store.verify_checkpoints(CHECKPOINT_INTERVAL).unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.checked, 3);
+
+        // Checkpoints off the interval are verified against their blocks too
+        store.save_checkpoint(25, "corrupted_hash").unwrap();
+        let report = store.verify_checkpoints(CHECKPOINT_INTERVAL).unwrap();
+        let indexes: Vec<u64> = report.mismatches.iter().map(|m| m.index).collect();
+        assert_eq!(indexes, vec![25]);
+    }
+
+    #[test]
+    fn test_prune_before_keeps_headers() {
+        let store = create_test_store();
+        let chain = make_chain(CHECKPOINT_INTERVAL + 1);
+        store
+            .replace_chain(&chain, &interval_checkpoints(&chain))
+            .unwrap();
+        assert_eq!(store.load_pruned_before().unwrap(), 0);
+
+        assert_eq!(store.prune_before(10).unwrap(), 9); // Genesis is never pruned
+        assert_eq!(store.load_pruned_before().unwrap(), 10);
+        // Already-pruned blocks are skipped
+        assert_eq!(store.prune_before(10).unwrap(), 0);
+        assert_eq!(store.prune_before(12).unwrap(), 2);
+
+        let loaded = store.load_chain().unwrap();
+        assert_eq!(
+            loaded.iter().map(|b| &b.hash).collect::<Vec<_>>(),
+            chain.iter().map(|b| &b.hash).collect::<Vec<_>>()
+        );
+        assert!(store
+            .verify_checkpoints(CHECKPOINT_INTERVAL)
+            .unwrap()
+            .is_valid());
+    }
+
+    #[test]
+    fn test_replace_chain_keeps_offloaded_bodies() {
+        let store = create_test_store();
+        let mut chain = make_chain(5);
+        store.replace_chain(&chain, &[]).unwrap();
+
+        let body = chain[2].encrypted_block_data.clone();
+        chain[2].offload_body();
+        assert!(chain[2].is_offloaded());
+        store.replace_chain(&chain, &[]).unwrap();
+        assert_eq!(
+            store.load_block(2).unwrap().unwrap().encrypted_block_data,
+            body
+        );
+        assert!(store.load_block(5).unwrap().is_none());
+
+        // A different block under the same index has no stored body to keep
+        let mut other = make_chain(3);
+        other[2].timestamp += 1;
+        other[2].hash = other[2].calculate_hash();
+        store.replace_chain(&other, &[]).unwrap();
+        chain[2].offload_body();
+        assert!(store.replace_chain(&chain, &[]).is_err());
+    }
+
+    #[test]
+    fn test_separate_data_directories_coexist() {
+        let node1 = tempfile::tempdir().unwrap();
+        let node2 = tempfile::tempdir().unwrap();
+        let store1 = BlockchainStore::new(&node1.path().join("rocksdb")).unwrap();
+        let store2 = BlockchainStore::new(&node2.path().join("rocksdb")).unwrap();
+
+        store1.replace_chain(&make_chain(3), &[]).unwrap();
+        assert_eq!(store1.load_chain().unwrap().len(), 3);
+        assert!(store2.load_chain().unwrap().is_empty());
+
+        // RocksDB's lock file keeps a second node off a directory already in use
+        assert!(BlockchainStore::new(&node1.path().join("rocksdb")).is_err());
+    }
+
+    #[test]
+    fn test_backup_and_restore_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_dir = temp_dir.path().join("rocksdb");
+        let backup_dir = temp_dir.path().join("backups");
+        let chain = make_chain(CHECKPOINT_INTERVAL + 1);
+
+        {
+            let store = BlockchainStore::new(&db_dir).unwrap();
+            store
+                .replace_chain(&chain, &interval_checkpoints(&chain))
+                .unwrap();
+            let backup = store.create_backup(&backup_dir).unwrap();
+            assert!(backup.num_files > 0);
+
+            // Writes after the backup are not part of it
+            store
+                .save_checkpoint(5 * CHECKPOINT_INTERVAL, "later")
+                .unwrap();
+        }
+
+        std::fs::remove_dir_all(&db_dir).unwrap();
+        BlockchainStore::restore_backup(&backup_dir, &db_dir).unwrap();
+
+        let store = BlockchainStore::new(&db_dir).unwrap();
+        let hashes: Vec<String> = store
+            .load_chain()
+            .unwrap()
+            .into_iter()
+            .map(|b| b.hash)
+            .collect();
+        assert_eq!(
+            hashes,
+            chain.iter().map(|b| b.hash.clone()).collect::<Vec<_>>()
+        );
+        assert!(store
+            .verify_checkpoints(CHECKPOINT_INTERVAL)
+            .unwrap()
+            .is_valid());
+    }
+
+    #[test]
+    fn test_restore_without_backup_fails() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let result = BlockchainStore::restore_backup(
+            &temp_dir.path().join("backups"),
+            &temp_dir.path().join("rocksdb"),
+        );
+        assert!(result.is_err());
+    }
+
+    /// A block after the tip of `chain` holding a single fresh collection
+    fn block_with_collection(chain: &[Block]) -> (Block, String) {
+        use crate::crypto::{generate_api_key, generate_signing_key, hash_api_key_hex};
+        use crate::domain::envelope::CollectionEnvelope;
+        use crate::domain::EncryptedCollection;
+
+        let api_key = generate_api_key();
+        let collection = EncryptedCollection::new(
+            "label".to_string(),
+            r#"{"value": 1}"#.to_string(),
+            None,
+            "application/json",
+            None,
+            &api_key,
+            hash_api_key_hex(&api_key),
+            &generate_signing_key(),
+        )
+        .unwrap();
+        let collection_id = collection.collection_id.clone();
+
+        let block = Block::new(BlockConfig {
+            index: chain.len() as u64,
+            account_envelopes: Vec::new(),
+            collection_envelopes: vec![CollectionEnvelope { collection }],
+            previous_hash: chain.last().unwrap().hash.clone(),
+            validator: "Validator_1".to_string(),
+            blind_indexes: Vec::new(),
+            block_salt: "test_salt".to_string(),
+        })
+        .unwrap();
+        (block, collection_id)
+    }
+
+    #[test]
+    fn test_collection_index_follows_saves_and_reorgs() {
+        let store = create_test_store();
+
+        let mut chain = make_chain(2);
+        let (block, collection_id) = block_with_collection(&chain);
+        chain.push(block);
+        for block in &chain {
+            store.save_block(block).unwrap();
+        }
+        assert_eq!(store.get_collection_block(&collection_id).unwrap(), Some(2));
+
+        // The reorged chain holds the collection one block later
+        let mut reorged = make_chain(3);
+        let (moved, moved_id) = block_with_collection(&reorged);
+        reorged.push(moved);
+        store.replace_chain(&reorged, &[]).unwrap();
+        assert_eq!(store.get_collection_block(&collection_id).unwrap(), None);
+        assert_eq!(store.get_collection_block(&moved_id).unwrap(), Some(3));
+    }
+
+    #[test]
+    fn test_ensure_collection_index_backfills_once() {
+        let store = create_test_store();
+
+        let mut chain = make_chain(1);
+        let (block, collection_id) = block_with_collection(&chain);
+        chain.push(block);
+        for block in &chain {
+            store.save_block(block).unwrap();
+        }
+
+        // Simulate a chain persisted before the index existed
+        let index_key = format!("collection_block:{}", collection_id);
+        store.db.delete(index_key.as_bytes()).unwrap();
+        store.ensure_collection_index(&chain).unwrap();
+        assert_eq!(store.get_collection_block(&collection_id).unwrap(), Some(1));
+
+        // Later loads trust the maintained index instead of rescanning
+        store.db.delete(index_key.as_bytes()).unwrap();
+        store.ensure_collection_index(&chain).unwrap();
+        assert_eq!(store.get_collection_block(&collection_id).unwrap(), None);
+    }
 }
@@ -0,0 +1,211 @@
+//! Idempotency key storage using RocksDB.
+//! Layer 3: Persistence - Remembers responses to keyed write requests so client retries replay them.
+//!
+//! **Storage Schema:**
+//! - `idempotency:{key_hash}` → bincode `StoredResponse` (expires after IDEMPOTENCY_KEY_TTL_SECONDS)
+//!
+//! The key hash covers the client's key and the request scope (method, path and caller), so two
+//! clients picking the same key never see each other's responses. Requests still in progress are
+//! tracked in memory, so a concurrent retry is refused instead of creating a second block.
+
+use chrono::Utc;
+use rocksdb::DB;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info};
+
+use crate::constants::IDEMPOTENCY_KEY_TTL_SECONDS;
+use crate::types::{GoudChainError, Result};
+
+const IDEMPOTENCY_PREFIX: &str = "idempotency:";
+
+/// Response recorded for an idempotency key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredResponse {
+    /// Unix timestamp after which the key may be reused
+    pub expires_at: i64,
+    /// SHA-256 (hex) of the original request body
+    pub fingerprint: String,
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
+/// Idempotency key store with RocksDB persistence and an in-memory in-flight set
+pub struct IdempotencyStore {
+    db: Arc<DB>,
+    in_flight: Mutex<HashSet<String>>,
+}
+
+/// Exclusive claim on a key while its request runs; released on drop
+pub struct IdempotencyReservation {
+    store: Arc<IdempotencyStore>,
+    key_hash: String,
+}
+
+impl IdempotencyStore {
+    /// Create a new idempotency store
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db,
+            in_flight: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Claim a key for a request (None if another request with the key is still running)
+    pub fn reserve(store: &Arc<Self>, key_hash: &str) -> Option<IdempotencyReservation> {
+        let claimed = store.in_flight.lock().unwrap().insert(key_hash.to_string());
+        claimed.then(|| IdempotencyReservation {
+            store: Arc::clone(store),
+            key_hash: key_hash.to_string(),
+        })
+    }
+
+    /// Response recorded for a key (expired entries are removed and reported as absent)
+    pub fn get(&self, key_hash: &str) -> Result<Option<StoredResponse>> {
+        let key = format!("{}{}", IDEMPOTENCY_PREFIX, key_hash);
+        let value = self.db.get(key.as_bytes()).map_err(|e| {
+            GoudChainError::RocksDbError(format!("Failed to read idempotency key: {}", e))
+        })?;
+
+        let Some(bytes) = value else {
+            return Ok(None);
+        };
+        let stored: StoredResponse = bincode::deserialize(&bytes).map_err(|e| {
+            GoudChainError::DeserializationError(format!("Invalid idempotency record: {}", e))
+        })?;
+
+        if stored.expires_at > Utc::now().timestamp() {
+            Ok(Some(stored))
+        } else {
+            self.db.delete(key.as_bytes()).ok();
+            Ok(None)
+        }
+    }
+
+    fn put(&self, key_hash: &str, stored: &StoredResponse) -> Result<()> {
+        let key = format!("{}{}", IDEMPOTENCY_PREFIX, key_hash);
+        let bytes = bincode::serialize(stored)
+            .map_err(|e| GoudChainError::SerializationError(e.to_string()))?;
+        self.db.put(key.as_bytes(), bytes).map_err(|e| {
+            GoudChainError::RocksDbError(format!("Failed to store idempotency key: {}", e))
+        })?;
+
+        debug!("Idempotency key stored until {}", stored.expires_at);
+        Ok(())
+    }
+
+    /// Remove responses whose keys have expired (periodic maintenance)
+    pub fn cleanup_expired(&self) -> Result<u32> {
+        let now = Utc::now().timestamp();
+        let mut deleted_count = 0u32;
+
+        for item in self.db.prefix_iterator(IDEMPOTENCY_PREFIX.as_bytes()) {
+            let (key, value) = item.map_err(|e| {
+                GoudChainError::RocksDbError(format!("Failed to iterate idempotency keys: {}", e))
+            })?;
+
+            if !key.starts_with(IDEMPOTENCY_PREFIX.as_bytes()) {
+                break; // Stop iteration when prefix changes
+            }
+
+            let expired = bincode::deserialize::<StoredResponse>(&value)
+                .map(|stored| stored.expires_at <= now)
+                .unwrap_or(true);
+            if expired {
+                self.db.delete(&key).ok();
+                deleted_count += 1;
+            }
+        }
+
+        if deleted_count > 0 {
+            info!("Cleaned up {} expired idempotency keys", deleted_count);
+        }
+
+        Ok(deleted_count)
+    }
+}
+
+impl IdempotencyReservation {
+    /// Record the response so retries with the same key replay it
+    pub fn complete(
+        self,
+        fingerprint: String,
+        status: u16,
+        content_type: Option<String>,
+        body: Vec<u8>,
+    ) -> Result<()> {
+        let stored = StoredResponse {
+            expires_at: Utc::now().timestamp() + IDEMPOTENCY_KEY_TTL_SECONDS,
+            fingerprint,
+            status,
+            content_type,
+            body,
+        };
+        self.store.put(&self.key_hash, &stored)
+    }
+}
+
+impl Drop for IdempotencyReservation {
+    fn drop(&mut self) {
+        self.store.in_flight.lock().unwrap().remove(&self.key_hash);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocksdb::Options;
+
+    fn create_test_store() -> Arc<IdempotencyStore> {
+        let path = format!("/tmp/goud_idempotency_test_{}", rand::random::<u64>());
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        Arc::new(IdempotencyStore::new(Arc::new(
+            DB::open(&opts, path).unwrap(),
+        )))
+    }
+
+    #[test]
+    fn test_reservation_is_exclusive_until_dropped() {
+        let store = create_test_store();
+
+        let reservation = IdempotencyStore::reserve(&store, "key-1").unwrap();
+        assert!(IdempotencyStore::reserve(&store, "key-1").is_none());
+        assert!(IdempotencyStore::reserve(&store, "key-2").is_some());
+
+        drop(reservation);
+        assert!(IdempotencyStore::reserve(&store, "key-1").is_some());
+    }
+
+    #[test]
+    fn test_completed_response_is_replayed_until_expiry() {
+        let store = create_test_store();
+        assert!(store.get("key-1").unwrap().is_none());
+
+        IdempotencyStore::reserve(&store, "key-1")
+            .unwrap()
+            .complete(
+                "fingerprint".to_string(),
+                201,
+                Some("application/json".to_string()),
+                b"{\"ok\":true}".to_vec(),
+            )
+            .unwrap();
+
+        let stored = store.get("key-1").unwrap().unwrap();
+        assert_eq!(stored.status, 201);
+        assert_eq!(stored.fingerprint, "fingerprint");
+        assert_eq!(stored.body, b"{\"ok\":true}");
+
+        let expired = StoredResponse {
+            expires_at: Utc::now().timestamp() - 1,
+            ..stored
+        };
+        store.put("key-2", &expired).unwrap();
+        assert_eq!(store.cleanup_expired().unwrap(), 1);
+        assert!(store.get("key-2").unwrap().is_none());
+        assert!(store.get("key-1").unwrap().is_some());
+    }
+}
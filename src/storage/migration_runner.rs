@@ -24,7 +24,7 @@ impl MigrationRunner {
     /// Get list of pending migrations (not yet applied)
     ///
     /// Compares available migrations against applied migrations to determine
-    /// which ones need to be run.
+    /// which ones need to be run. Returned in version (timestamp) order.
     pub fn get_pending_migrations<'a>(
         &self,
         available: &'a [Box<dyn Migration>],
@@ -33,11 +33,12 @@ impl MigrationRunner {
         let applied = migration_store.get_applied_migrations()?;
         let applied_versions: Vec<String> = applied.iter().map(|m| m.version.clone()).collect();
 
-        let pending: Vec<&'a dyn Migration> = available
+        let mut pending: Vec<&'a dyn Migration> = available
             .iter()
             .map(|b| b.as_ref())
             .filter(|m| !applied_versions.contains(&m.version().to_string()))
             .collect();
+        pending.sort_by(|a, b| a.version().cmp(b.version()));
 
         Ok(pending)
     }
@@ -117,9 +118,10 @@ impl MigrationRunner {
 
     /// Execute all pending migrations in order
     ///
-    /// Migrations are executed sequentially. If any migration fails,
-    /// execution stops and the error is returned. Successfully applied
-    /// migrations are not rolled back automatically.
+    /// Migrations are executed sequentially in version order. If any migration
+    /// fails, the migrations already applied in this run are rolled back
+    /// (newest first) so the current schema marker returns to where it started,
+    /// and the original error is returned.
     pub fn apply_pending(&self, available: &[Box<dyn Migration>]) -> Result<(usize, Vec<String>)> {
         let pending = self.get_pending_migrations(available)?;
 
@@ -130,13 +132,28 @@ impl MigrationRunner {
 
         info!(count = pending.len(), "Applying pending migrations");
 
-        let mut applied_versions = Vec::new();
+        let mut applied: Vec<&dyn Migration> = Vec::new();
 
         for migration in pending {
-            self.apply_migration(migration)?;
-            applied_versions.push(migration.version().to_string());
+            if let Err(e) = self.apply_migration(migration) {
+                for done in applied.iter().rev() {
+                    if let Err(rollback_err) = self.rollback_migration(*done) {
+                        error!(
+                            version = %done.version(),
+                            error = %rollback_err,
+                            "Failed to roll back migration after run failure"
+                        );
+                        return Err(rollback_err);
+                    }
+                }
+                return Err(e);
+            }
+            applied.push(migration);
         }
 
+        let applied_versions: Vec<String> =
+            applied.iter().map(|m| m.version().to_string()).collect();
+
         info!(
             count = applied_versions.len(),
             "All pending migrations applied"
@@ -145,6 +162,26 @@ impl MigrationRunner {
         Ok((applied_versions.len(), applied_versions))
     }
 
+    /// Record all available migrations as applied without running them
+    ///
+    /// Used when a fresh database is created at the current schema: there is no
+    /// legacy data to transform, so future runs should only pick up newer migrations.
+    pub fn baseline(&self, available: &[Box<dyn Migration>]) -> Result<usize> {
+        let migration_store = MigrationStore::new(&self.store);
+        let pending = self.get_pending_migrations(available)?;
+
+        for migration in &pending {
+            migration_store.mark_applied(&MigrationMetadata {
+                version: migration.version().to_string(),
+                description: migration.description().to_string(),
+                applied_at: chrono::Utc::now().timestamp(),
+                checksum: migration.checksum(),
+            })?;
+        }
+
+        Ok(pending.len())
+    }
+
     /// Rollback a single migration (down)
     ///
     /// Executes the migration's down() method and removes it from applied list.
@@ -314,7 +351,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let temp_path = temp_dir.path().join("rocksdb");
 
-        let store = BlockchainStore::new_with_path(&temp_path.to_string_lossy()).unwrap();
+        let store = BlockchainStore::new(&temp_path).unwrap();
         (temp_dir, Arc::new(store))
     }
 
@@ -468,4 +505,86 @@ mod tests {
         assert_eq!(status.pending.len(), 1);
         assert_eq!(status.current_schema, Some("20240101000000".to_string()));
     }
+
+    #[test]
+    fn test_apply_pending_runs_in_version_order() {
+        let (_temp_dir, store) = create_test_store();
+        let runner = MigrationRunner::new(store);
+
+        let migrations: Vec<Box<dyn Migration>> = vec![
+            Box::new(TestMigration {
+                version: "20240102000000".to_string(),
+                description: "second".to_string(),
+                should_fail: false,
+            }),
+            Box::new(TestMigration {
+                version: "20240101000000".to_string(),
+                description: "first".to_string(),
+                should_fail: false,
+            }),
+        ];
+
+        let (_, versions) = runner.apply_pending(&migrations).unwrap();
+        assert_eq!(versions, vec!["20240101000000", "20240102000000"]);
+    }
+
+    #[test]
+    fn test_apply_pending_rolls_back_run_on_failure() {
+        let (_temp_dir, store) = create_test_store();
+        let runner = MigrationRunner::new(store.clone());
+
+        let existing: Vec<Box<dyn Migration>> = vec![Box::new(TestMigration {
+            version: "20240101000000".to_string(),
+            description: "existing".to_string(),
+            should_fail: false,
+        })];
+        runner.apply_pending(&existing).unwrap();
+
+        let migrations: Vec<Box<dyn Migration>> = vec![
+            Box::new(TestMigration {
+                version: "20240101000000".to_string(),
+                description: "existing".to_string(),
+                should_fail: false,
+            }),
+            Box::new(TestMigration {
+                version: "20240102000000".to_string(),
+                description: "second".to_string(),
+                should_fail: false,
+            }),
+            Box::new(TestMigration {
+                version: "20240103000000".to_string(),
+                description: "broken".to_string(),
+                should_fail: true,
+            }),
+        ];
+
+        assert!(runner.apply_pending(&migrations).is_err());
+
+        let migration_store = MigrationStore::new(&store);
+        assert!(migration_store.is_applied("20240101000000").unwrap());
+        assert!(!migration_store.is_applied("20240102000000").unwrap());
+        assert!(!migration_store.is_applied("20240103000000").unwrap());
+        assert_eq!(
+            migration_store.get_current_schema().unwrap(),
+            Some("20240101000000".to_string())
+        );
+    }
+
+    #[test]
+    fn test_baseline_marks_without_running() {
+        let (_temp_dir, store) = create_test_store();
+        let runner = MigrationRunner::new(store);
+
+        let migrations: Vec<Box<dyn Migration>> = vec![Box::new(TestMigration {
+            version: "20240101000000".to_string(),
+            description: "would_fail".to_string(),
+            should_fail: true,
+        })];
+
+        assert_eq!(runner.baseline(&migrations).unwrap(), 1);
+        assert!(runner
+            .get_pending_migrations(&migrations)
+            .unwrap()
+            .is_empty());
+    }
 }
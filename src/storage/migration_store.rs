@@ -3,19 +3,24 @@
 //! This module provides the `MigrationStore` which manages migration metadata
 //! in RocksDB using atomic operations. It tracks which migrations have been applied
 //! and maintains the current schema version.
+//!
+//! Records live in the dedicated `migrations` column family so they never mix
+//! with chain data and survive any rewrite of the default keyspace.
 
+use crate::constants::MIGRATIONS_COLUMN_FAMILY;
 use crate::storage::blockchain_store::BlockchainStore;
 use crate::storage::migration::MigrationMetadata;
 use crate::types::{GoudChainError, Result};
-use rocksdb::WriteBatch;
+use rocksdb::{ColumnFamily, WriteBatch, DB};
 
 /// RocksDB key prefixes for migration state
+const MIGRATION_KEY_PREFIX: &str = "migration:";
 const MIGRATION_APPLIED_PREFIX: &str = "migration:applied:";
 const MIGRATION_CURRENT_SCHEMA: &str = "migration:current_schema";
 
 /// Manages migration state in RocksDB
 ///
-/// Uses the following key schema:
+/// Uses the following key schema (in the `migrations` column family):
 /// - `migration:applied:{version}` → `MigrationMetadata` (Bincode serialized)
 /// - `migration:current_schema` → `String` (UTF-8 encoded version)
 pub struct MigrationStore<'a> {
@@ -28,13 +33,59 @@ impl<'a> MigrationStore<'a> {
         Self { store }
     }
 
+    /// Move migration records written before the `migrations` column family existed
+    ///
+    /// Older nodes kept `migration:*` keys in the default keyspace. They are copied into the
+    /// column family (records already there win) and removed from the default keyspace in one
+    /// `WriteBatch`, so already-applied migrations are never re-run. Returns how many keys moved.
+    pub fn import_legacy_records(&self) -> Result<usize> {
+        let db = self.store.get_db();
+        let cf = Self::cf(&db)?;
+        let mut batch = WriteBatch::default();
+        let mut moved = 0;
+
+        for item in db.prefix_iterator(MIGRATION_KEY_PREFIX.as_bytes()) {
+            let (key, value) = item.map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+            if !key.starts_with(MIGRATION_KEY_PREFIX.as_bytes()) {
+                break; // Prefix scan completed
+            }
+
+            let exists = db
+                .get_cf(cf, &key)
+                .map_err(|e| GoudChainError::RocksDbError(e.to_string()))?
+                .is_some();
+            if !exists {
+                batch.put_cf(cf, &key, &value);
+            }
+            batch.delete(&key);
+            moved += 1;
+        }
+
+        if moved > 0 {
+            db.write(batch)
+                .map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
+        }
+
+        Ok(moved)
+    }
+
+    /// Resolve the migrations column family handle
+    fn cf(db: &DB) -> Result<&ColumnFamily> {
+        db.cf_handle(MIGRATIONS_COLUMN_FAMILY).ok_or_else(|| {
+            GoudChainError::RocksDbError(format!(
+                "Column family '{}' not found",
+                MIGRATIONS_COLUMN_FAMILY
+            ))
+        })
+    }
+
     /// Get list of all applied migrations, sorted by version (oldest first)
     pub fn get_applied_migrations(&self) -> Result<Vec<MigrationMetadata>> {
         let mut migrations = Vec::new();
         let db = self.store.get_db();
 
         // Scan all keys with migration:applied: prefix
-        let iter = db.prefix_iterator(MIGRATION_APPLIED_PREFIX.as_bytes());
+        let iter = db.prefix_iterator_cf(Self::cf(&db)?, MIGRATION_APPLIED_PREFIX.as_bytes());
 
         for item in iter {
             let (key, value) = item.map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
@@ -63,7 +114,7 @@ impl<'a> MigrationStore<'a> {
         let key = format!("{}{}", MIGRATION_APPLIED_PREFIX, version);
         let db = self.store.get_db();
 
-        match db.get(key.as_bytes()) {
+        match db.get_cf(Self::cf(&db)?, key.as_bytes()) {
             Ok(Some(_)) => Ok(true),
             Ok(None) => Ok(false),
             Err(e) => Err(GoudChainError::RocksDbError(e.to_string())),
@@ -77,6 +128,7 @@ impl<'a> MigrationStore<'a> {
         let key = format!("{}{}", MIGRATION_APPLIED_PREFIX, metadata.version);
         let db = self.store.get_db();
 
+        let cf = Self::cf(&db)?;
         let mut batch = WriteBatch::default();
 
         // Serialize metadata
@@ -84,8 +136,9 @@ impl<'a> MigrationStore<'a> {
             .map_err(|e| GoudChainError::SerializationError(e.to_string()))?;
 
         // Add to batch: migration metadata + current schema update
-        batch.put(key.as_bytes(), &serialized);
-        batch.put(
+        batch.put_cf(cf, key.as_bytes(), &serialized);
+        batch.put_cf(
+            cf,
             MIGRATION_CURRENT_SCHEMA.as_bytes(),
             metadata.version.as_bytes(),
         );
@@ -113,10 +166,11 @@ impl<'a> MigrationStore<'a> {
         let key = format!("{}{}", MIGRATION_APPLIED_PREFIX, version);
         let db = self.store.get_db();
 
+        let cf = Self::cf(&db)?;
         let mut batch = WriteBatch::default();
 
         // Remove migration metadata
-        batch.delete(key.as_bytes());
+        batch.delete_cf(cf, key.as_bytes());
 
         // Determine new current schema (previous migration or empty)
         let remaining = self
@@ -126,12 +180,13 @@ impl<'a> MigrationStore<'a> {
             .collect::<Vec<_>>();
 
         if let Some(latest) = remaining.last() {
-            batch.put(
+            batch.put_cf(
+                cf,
                 MIGRATION_CURRENT_SCHEMA.as_bytes(),
                 latest.version.as_bytes(),
             );
         } else {
-            batch.delete(MIGRATION_CURRENT_SCHEMA.as_bytes());
+            batch.delete_cf(cf, MIGRATION_CURRENT_SCHEMA.as_bytes());
         }
 
         // Atomic write
@@ -145,7 +200,7 @@ impl<'a> MigrationStore<'a> {
     pub fn get_current_schema(&self) -> Result<Option<String>> {
         let db = self.store.get_db();
 
-        match db.get(MIGRATION_CURRENT_SCHEMA.as_bytes()) {
+        match db.get_cf(Self::cf(&db)?, MIGRATION_CURRENT_SCHEMA.as_bytes()) {
             Ok(Some(bytes)) => {
                 let version =
                     String::from_utf8(bytes.to_vec()).map_err(GoudChainError::Utf8Error)?;
@@ -159,10 +214,11 @@ impl<'a> MigrationStore<'a> {
     /// Clear all migration state (dangerous - for testing/reset only)
     pub fn clear_all_migrations(&self) -> Result<()> {
         let db = self.store.get_db();
+        let cf = Self::cf(&db)?;
         let mut batch = WriteBatch::default();
 
         // Delete all migration:applied:* keys
-        let iter = db.prefix_iterator(MIGRATION_APPLIED_PREFIX.as_bytes());
+        let iter = db.prefix_iterator_cf(cf, MIGRATION_APPLIED_PREFIX.as_bytes());
         for item in iter {
             let (key, _) = item.map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
 
@@ -172,11 +228,11 @@ impl<'a> MigrationStore<'a> {
                 break;
             }
 
-            batch.delete(&key);
+            batch.delete_cf(cf, &key);
         }
 
         // Delete current schema
-        batch.delete(MIGRATION_CURRENT_SCHEMA.as_bytes());
+        batch.delete_cf(cf, MIGRATION_CURRENT_SCHEMA.as_bytes());
 
         db.write(batch)
             .map_err(|e| GoudChainError::RocksDbError(e.to_string()))?;
@@ -195,7 +251,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let temp_path = temp_dir.path().join("rocksdb");
 
-        let store = BlockchainStore::new_with_path(&temp_path.to_string_lossy()).unwrap();
+        let store = BlockchainStore::new(&temp_path).unwrap();
         (temp_dir, Arc::new(store))
     }
 
@@ -227,6 +283,47 @@ mod tests {
         assert_eq!(retrieved.description, metadata.description);
     }
 
+    #[test]
+    fn test_legacy_records_move_into_column_family() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().join("rocksdb");
+        let metadata = MigrationMetadata {
+            version: "20240101120000".to_string(),
+            description: "legacy_migration".to_string(),
+            applied_at: 1704110400,
+            checksum: "abc123".to_string(),
+        };
+
+        // Write the records the way older nodes did, in the default keyspace
+        {
+            let store = BlockchainStore::new(&temp_path).unwrap();
+            let db = store.get_db();
+            let key = format!("{}{}", MIGRATION_APPLIED_PREFIX, metadata.version);
+            db.put(key.as_bytes(), bincode::serialize(&metadata).unwrap())
+                .unwrap();
+            db.put(
+                MIGRATION_CURRENT_SCHEMA.as_bytes(),
+                metadata.version.as_bytes(),
+            )
+            .unwrap();
+        }
+
+        // Reopening imports them, so the migration is not pending again
+        let store = BlockchainStore::new(&temp_path).unwrap();
+        let migration_store = MigrationStore::new(&store);
+        assert!(migration_store.is_applied(&metadata.version).unwrap());
+        assert_eq!(
+            migration_store.get_current_schema().unwrap(),
+            Some(metadata.version.clone())
+        );
+        assert!(store
+            .get_db()
+            .get(MIGRATION_CURRENT_SCHEMA.as_bytes())
+            .unwrap()
+            .is_none());
+        assert_eq!(migration_store.import_legacy_records().unwrap(), 0);
+    }
+
     #[test]
     fn test_get_applied_migrations_sorted() {
         let (_temp_dir, store) = create_test_store();
@@ -1,34 +1,51 @@
 pub mod audit_log;
 pub mod blockchain_store;
+pub mod idempotency_store;
 pub mod migration;
 pub mod migration_runner;
 pub mod migration_store;
 pub mod nonce_store;
+pub mod peer_store;
 pub mod rate_limit_store;
+pub mod token_revocation_store;
 
-use std::fs;
+use rocksdb::DB;
+use std::path::Path;
+use std::sync::Arc;
 use tracing::{info, warn};
 
-use crate::constants::{DATA_DIRECTORY, SCHEMA_VERSION};
+use crate::constants::{
+    DEFAULT_BLOCK_SEAL_THRESHOLD, DEFAULT_CHECKPOINT_INTERVAL, DEFAULT_MAX_REORG_DEPTH,
+    SCHEMA_VERSION,
+};
 use crate::crypto::generate_signing_key;
-use crate::domain::Blockchain;
-use crate::types::{GoudChainError, Result};
+use crate::domain::{BlockLoader, Blockchain, ChainAggregates};
+use crate::types::{GoudChainError, Result, SharedClock};
 
 // Re-export storage modules
 pub use self::audit_log::AuditLogger;
-pub use self::blockchain_store::BlockchainStore;
+pub use self::blockchain_store::{
+    BackupInfo, BlockchainStore, CheckpointMismatch, CheckpointReport,
+};
+pub use self::idempotency_store::IdempotencyStore;
 pub use self::migration::Migration;
 pub use self::migration_runner::MigrationRunner;
 pub use self::migration_store::MigrationStore;
-pub use self::nonce_store::NonceStore;
-pub use self::rate_limit_store::{BanLevel, RateLimitStore};
+pub use self::nonce_store::{NonceReservation, NonceStore};
+pub use self::peer_store::PeerStore;
+pub use self::rate_limit_store::{BanLevel, BanRecord, RateLimitStore, ViolationRecord};
+pub use self::token_revocation_store::TokenRevocationStore;
 
 /// Load the blockchain from RocksDB or create a new one
-/// Handles schema versioning automatically
+/// Handles schema versioning automatically: a schema bump runs the registered
+/// migrations forward, and startup fails (leaving the stored schema untouched)
+/// if any of them fail. A new chain's genesis block carries `genesis_timestamp`
 pub fn load_blockchain(
     node_id: String,
     validator_config: crate::config::ValidatorConfig,
-    store: &BlockchainStore,
+    genesis_timestamp: i64,
+    store: &Arc<BlockchainStore>,
+    migrations: &[Box<dyn Migration>],
 ) -> Result<Blockchain> {
     // Check if RocksDB has data
     if !store.is_empty() {
@@ -41,25 +58,32 @@ pub fn load_blockchain(
             warn!(
                 old_schema = %stored_schema,
                 new_schema = %SCHEMA_VERSION,
-                "Schema version mismatch - clearing RocksDB and creating new blockchain"
+                "Schema version mismatch - running pending migrations"
             );
-            store.clear_all()?;
-            info!("Creating new blockchain with schema {}", SCHEMA_VERSION);
-            let blockchain = Blockchain::new(node_id.clone(), validator_config.clone())?;
-
-            // Save genesis block to RocksDB
-            if let Some(genesis) = blockchain.chain.first() {
-                store.save_block(genesis)?;
-                info!("Genesis block saved to RocksDB");
-            }
-
+            let (count, versions) =
+                MigrationRunner::new(Arc::clone(store)).apply_pending(migrations)?;
             store.save_metadata(&node_id, SCHEMA_VERSION)?;
-            return Ok(blockchain);
+            info!(
+                count = count,
+                versions = ?versions,
+                schema_version = %SCHEMA_VERSION,
+                "Schema migrated"
+            );
         }
 
         // Load chain from RocksDB
         let chain = store.load_chain()?;
         let checkpoints = store.load_checkpoints()?;
+        store.ensure_collection_index(&chain)?;
+
+        // The stored genesis wins; a different GENESIS_TIMESTAMP only affects new chains
+        if let Some(genesis) = chain.first().filter(|g| g.timestamp != genesis_timestamp) {
+            warn!(
+                stored = genesis.timestamp,
+                configured = genesis_timestamp,
+                "Stored genesis timestamp differs from GENESIS_TIMESTAMP - keeping the stored chain"
+            );
+        }
 
         info!(
             chain_length = chain.len(),
@@ -78,26 +102,63 @@ pub fn load_blockchain(
             pending_collections: Vec::new(),
             node_signing_key: Some(generate_signing_key()),
             validator_config,
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+            max_reorg_depth: DEFAULT_MAX_REORG_DEPTH,
+            pruned_before: store.load_pruned_before()?,
+            offloaded_before: 0,
+            block_loader: block_loader(store),
+            exact_blind_indexes: false,
+            legacy_blind_indexes: store.load_legacy_blind_indexes()?,
+            aggregates: ChainAggregates::default(),
+            read_only: false,
+            min_block_interval_seconds: 0,
+            block_seal_threshold: DEFAULT_BLOCK_SEAL_THRESHOLD,
+            last_sealed_at: 0,
+            clock: SharedClock::default(),
         })
     } else {
         // RocksDB is empty - create new blockchain
         info!("No existing blockchain found, creating new one");
-        let blockchain = Blockchain::new(node_id.clone(), validator_config)?;
+        // Nothing to transform in a fresh database
+        MigrationRunner::new(Arc::clone(store)).baseline(migrations)?;
+        let mut blockchain = Blockchain::with_genesis_timestamp(
+            node_id.clone(),
+            validator_config,
+            genesis_timestamp,
+        )?;
+        blockchain.block_loader = block_loader(store);
 
         // Save genesis block to RocksDB
         if let Some(genesis) = blockchain.chain.first() {
             store.save_block(genesis)?;
             info!("Genesis block saved to RocksDB");
         }
+        store.ensure_collection_index(&blockchain.chain)?;
 
         store.save_metadata(&node_id, SCHEMA_VERSION)?;
         Ok(blockchain)
     }
 }
 
-/// Initialize the data directory
-pub fn init_data_directory() -> Result<()> {
-    fs::create_dir_all(DATA_DIRECTORY)
-        .map_err(|e| GoudChainError::DirectoryCreationFailed(e.to_string()))?;
-    Ok(())
+/// Read blocks offloaded from memory back from `store`
+fn block_loader(store: &Arc<BlockchainStore>) -> BlockLoader {
+    let store = Arc::clone(store);
+    BlockLoader::new(move |index| store.load_block(index))
+}
+
+/// Open the RocksDB that holds ephemeral data (rate limits and nonces) apart from the chain
+/// Everything in it expires on its own, so it can be wiped or compacted while the node is
+/// stopped without touching chain data, and chain backups stay free of its churn
+pub fn open_ephemeral_db(path: &Path) -> Result<Arc<DB>> {
+    info!(path = %path.display(), "Opening RocksDB for ephemeral data");
+
+    let mut opts = rocksdb::Options::default();
+    opts.create_if_missing(true);
+    opts.set_write_buffer_size(16 * 1024 * 1024); // Small keys, short-lived
+    opts.set_compression_type(rocksdb::DBCompressionType::Snappy);
+    opts.set_max_background_jobs(1);
+
+    DB::open(&opts, path)
+        .map(Arc::new)
+        .map_err(|e| GoudChainError::RocksDbError(e.to_string()))
 }
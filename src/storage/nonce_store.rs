@@ -8,15 +8,18 @@
 //! - LRU cache for hot nonces (100,000 entries for high throughput)
 //! - Automatic cleanup of expired nonces via RocksDB TTL
 //! - Fast O(1) lookups for nonce validation
+//!
+//! Nonces whose requests are still running are tracked in memory, so a concurrent replay is
+//! refused before the first request has recorded its nonce.
 
-use chrono::Utc;
 use lru::LruCache;
 use rocksdb::DB;
+use std::collections::HashSet;
 use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
 use tracing::{debug, info};
 
-use crate::types::{GoudChainError, Result};
+use crate::types::{GoudChainError, Result, SharedClock};
 
 /// Nonce expiration window (5 minutes as per security requirement)
 pub const NONCE_EXPIRATION_SECONDS: i64 = 300;
@@ -30,16 +33,29 @@ struct NonceEntry {
     expiry_timestamp: i64,
 }
 
-/// Nonce store with RocksDB persistence and LRU cache
+/// Nonce store with RocksDB persistence, LRU cache and an in-memory in-flight set
 pub struct NonceStore {
     db: Arc<DB>,
     /// LRU cache for hot nonces (O(1) lookups)
     cache: Arc<Mutex<LruCache<String, NonceEntry>>>,
+    clock: SharedClock,
+    in_flight: Mutex<HashSet<String>>,
+}
+
+/// Exclusive claim on a nonce while its request runs; released on drop unless committed
+pub struct NonceReservation {
+    store: Arc<NonceStore>,
+    nonce: String,
 }
 
 impl NonceStore {
     /// Create a new nonce store
     pub fn new(db: Arc<DB>) -> Self {
+        Self::with_clock(db, SharedClock::default())
+    }
+
+    /// Create a nonce store that measures expiry with `clock`
+    pub fn with_clock(db: Arc<DB>, clock: SharedClock) -> Self {
         info!(
             "Initializing nonce store with {}-second expiration",
             NONCE_EXPIRATION_SECONDS
@@ -49,12 +65,27 @@ impl NonceStore {
             cache: Arc::new(Mutex::new(LruCache::new(
                 NonZeroUsize::new(NONCE_CACHE_SIZE).unwrap(),
             ))),
+            clock,
+            in_flight: Mutex::new(HashSet::new()),
         }
     }
 
+    /// Claim a nonce for a request (None if it was already used or another request holds it)
+    pub fn reserve(store: &Arc<Self>, nonce: &str) -> Result<Option<NonceReservation>> {
+        let mut in_flight = store.in_flight.lock().unwrap();
+        if in_flight.contains(nonce) || store.is_nonce_used(nonce)? {
+            return Ok(None);
+        }
+        in_flight.insert(nonce.to_string());
+        Ok(Some(NonceReservation {
+            store: Arc::clone(store),
+            nonce: nonce.to_string(),
+        }))
+    }
+
     /// Check if a nonce has been used (and is still valid)
     pub fn is_nonce_used(&self, nonce: &str) -> Result<bool> {
-        let now = Utc::now().timestamp();
+        let now = self.clock.now();
 
         // Check cache first (O(1) lookup)
         {
@@ -109,7 +140,7 @@ impl NonceStore {
 
     /// Record a nonce as used (with expiration)
     pub fn record_nonce(&self, nonce: &str) -> Result<()> {
-        let now = Utc::now().timestamp();
+        let now = self.clock.now();
         let expiry_timestamp = now + NONCE_EXPIRATION_SECONDS;
 
         // Store in RocksDB
@@ -134,7 +165,7 @@ impl NonceStore {
     /// Clean up expired nonces from RocksDB (periodic maintenance)
     /// This is called periodically to prevent RocksDB from growing indefinitely
     pub fn cleanup_expired_nonces(&self) -> Result<u32> {
-        let now = Utc::now().timestamp();
+        let now = self.clock.now();
         let mut deleted_count = 0u32;
 
         // Iterate over all nonce keys
@@ -171,9 +202,25 @@ impl NonceStore {
     }
 }
 
+impl NonceReservation {
+    /// Record the nonce as used once its request has succeeded
+    /// It stays in flight until recorded, so no concurrent request slips in between.
+    pub fn commit(self) -> Result<()> {
+        self.store.record_nonce(&self.nonce)
+    }
+}
+
+impl Drop for NonceReservation {
+    fn drop(&mut self) {
+        self.store.in_flight.lock().unwrap().remove(&self.nonce);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::MockClock;
+    use chrono::Utc;
     use rocksdb::{Options, DB};
 
     fn create_test_db() -> Arc<DB> {
@@ -292,4 +339,36 @@ mod tests {
         assert!(!store.is_nonce_used(expired_nonce).unwrap());
         assert!(store.is_nonce_used(valid_nonce).unwrap());
     }
+
+    #[test]
+    fn test_nonce_expires_after_window() {
+        let clock = Arc::new(MockClock::new(1_700_000_000));
+        let store = NonceStore::with_clock(create_test_db(), Arc::clone(&clock).into());
+
+        store.record_nonce("windowed-nonce").unwrap();
+        clock.advance(NONCE_EXPIRATION_SECONDS - 1);
+        assert!(store.is_nonce_used("windowed-nonce").unwrap());
+
+        clock.advance(1);
+        assert!(!store.is_nonce_used("windowed-nonce").unwrap());
+        assert_eq!(store.cleanup_expired_nonces().unwrap(), 0); // Already removed by the lookup
+    }
+
+    #[test]
+    fn test_reservation_is_exclusive_until_released() {
+        let store = Arc::new(NonceStore::new(create_test_db()));
+
+        let reservation = NonceStore::reserve(&store, "nonce-1").unwrap().unwrap();
+        assert!(NonceStore::reserve(&store, "nonce-1").unwrap().is_none());
+        assert!(NonceStore::reserve(&store, "nonce-2").unwrap().is_some());
+
+        // A failed request releases its nonce for a retry
+        drop(reservation);
+        let reservation = NonceStore::reserve(&store, "nonce-1").unwrap().unwrap();
+
+        // A committed nonce stays used after the reservation ends
+        reservation.commit().unwrap();
+        assert!(store.is_nonce_used("nonce-1").unwrap());
+        assert!(NonceStore::reserve(&store, "nonce-1").unwrap().is_none());
+    }
 }
@@ -0,0 +1,187 @@
+//! Peer reputation and blacklist storage using RocksDB.
+//! Layer 3: Persistence - Keeps P2P trust state across restarts so banned peers can't
+//! reset themselves by waiting for a reboot.
+//!
+//! **Storage Schema:**
+//! - `peer_rep:{peer_addr}` → Bincode-serialized PeerReputationRecord
+//! - `peer_blacklist:{peer_addr}` → blacklist timestamp (i64)
+//!
+//! Scores not updated for PEER_REPUTATION_MAX_AGE_SECONDS are dropped on load, so old
+//! reputation decays back to neutral. Blacklist entries are permanent.
+
+use chrono::Utc;
+use rocksdb::DB;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+use crate::constants::PEER_REPUTATION_MAX_AGE_SECONDS;
+use crate::types::{GoudChainError, Result};
+
+const PEER_REPUTATION_PREFIX: &str = "peer_rep:";
+const PEER_BLACKLIST_PREFIX: &str = "peer_blacklist:";
+
+/// Persisted reputation score with the time it last changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PeerReputationRecord {
+    score: i32,
+    updated_at: i64,
+}
+
+/// Write-through store for peer reputation scores and the peer blacklist
+pub struct PeerStore {
+    db: Arc<DB>,
+}
+
+impl PeerStore {
+    /// Create a new peer store using the shared RocksDB instance
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db }
+    }
+
+    /// Persist the current reputation score for a peer
+    pub fn save_reputation(&self, peer: &str, score: i32) -> Result<()> {
+        let key = format!("{}{}", PEER_REPUTATION_PREFIX, peer);
+        let record = PeerReputationRecord {
+            score,
+            updated_at: Utc::now().timestamp(),
+        };
+        let bytes = bincode::serialize(&record)
+            .map_err(|e| GoudChainError::SerializationError(e.to_string()))?;
+
+        self.db.put(key.as_bytes(), bytes).map_err(|e| {
+            GoudChainError::RocksDbError(format!("Failed to save peer reputation: {}", e))
+        })?;
+
+        debug!(peer = %peer, score = score, "Saved peer reputation");
+        Ok(())
+    }
+
+    /// Load all reputation scores, deleting entries older than the maximum age
+    pub fn load_reputation(&self) -> Result<HashMap<String, i32>> {
+        let cutoff = Utc::now().timestamp() - PEER_REPUTATION_MAX_AGE_SECONDS;
+        let mut scores = HashMap::new();
+        let mut expired = 0u32;
+
+        for (peer, value) in self.scan(PEER_REPUTATION_PREFIX)? {
+            let record: PeerReputationRecord = bincode::deserialize(&value)
+                .map_err(|e| GoudChainError::DeserializationError(e.to_string()))?;
+
+            if record.updated_at < cutoff {
+                let key = format!("{}{}", PEER_REPUTATION_PREFIX, peer);
+                self.db.delete(key.as_bytes()).ok();
+                expired += 1;
+            } else {
+                scores.insert(peer, record.score);
+            }
+        }
+
+        if expired > 0 {
+            info!(expired = expired, "Dropped stale peer reputation entries");
+        }
+
+        Ok(scores)
+    }
+
+    /// Permanently blacklist a peer address
+    pub fn add_to_blacklist(&self, peer: &str) -> Result<()> {
+        let key = format!("{}{}", PEER_BLACKLIST_PREFIX, peer);
+        self.db
+            .put(key.as_bytes(), Utc::now().timestamp().to_be_bytes())
+            .map_err(|e| {
+                GoudChainError::RocksDbError(format!("Failed to blacklist peer: {}", e))
+            })?;
+
+        info!(peer = %peer, "Peer blacklisted");
+        Ok(())
+    }
+
+    /// Load all blacklisted peer addresses
+    pub fn load_blacklist(&self) -> Result<Vec<String>> {
+        Ok(self
+            .scan(PEER_BLACKLIST_PREFIX)?
+            .into_iter()
+            .map(|(peer, _)| peer)
+            .collect())
+    }
+
+    /// Collect (peer address, value) pairs stored under a key prefix
+    fn scan(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        let mut entries = Vec::new();
+
+        for item in self.db.prefix_iterator(prefix.as_bytes()) {
+            let (key, value) = item.map_err(|e| {
+                GoudChainError::RocksDbError(format!("Failed to iterate peer data: {}", e))
+            })?;
+
+            // Stop iteration when prefix changes
+            let Some(peer) = key.strip_prefix(prefix.as_bytes()) else {
+                break;
+            };
+
+            entries.push((String::from_utf8_lossy(peer).into_owned(), value.to_vec()));
+        }
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_store() -> PeerStore {
+        let temp_dir = std::env::temp_dir().join(format!("test_peers_{}", rand::random::<u64>()));
+        let db = DB::open_default(&temp_dir).expect("Failed to open test DB");
+        PeerStore::new(Arc::new(db))
+    }
+
+    #[test]
+    fn test_reputation_roundtrip() {
+        let store = create_test_store();
+
+        store.save_reputation("node2:9000", 7).unwrap();
+        store.save_reputation("node3:9000", -4).unwrap();
+        store.save_reputation("node2:9000", 8).unwrap();
+
+        let scores = store.load_reputation().unwrap();
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores.get("node2:9000"), Some(&8));
+        assert_eq!(scores.get("node3:9000"), Some(&-4));
+    }
+
+    #[test]
+    fn test_stale_reputation_decays() {
+        let store = create_test_store();
+        let stale = PeerReputationRecord {
+            score: -50,
+            updated_at: Utc::now().timestamp() - PEER_REPUTATION_MAX_AGE_SECONDS - 1,
+        };
+        store
+            .db
+            .put(b"peer_rep:old:9000", bincode::serialize(&stale).unwrap())
+            .unwrap();
+        store.save_reputation("fresh:9000", -50).unwrap();
+
+        let scores = store.load_reputation().unwrap();
+        assert_eq!(scores.get("old:9000"), None);
+        assert_eq!(scores.get("fresh:9000"), Some(&-50));
+
+        // Stale entries are deleted, not just skipped
+        assert!(store.db.get(b"peer_rep:old:9000").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_blacklist_roundtrip() {
+        let store = create_test_store();
+        assert!(store.load_blacklist().unwrap().is_empty());
+
+        store.add_to_blacklist("10.0.0.5:41234").unwrap();
+        store.add_to_blacklist("evil:9000").unwrap();
+
+        let mut blacklist = store.load_blacklist().unwrap();
+        blacklist.sort();
+        assert_eq!(blacklist, vec!["10.0.0.5:41234", "evil:9000"]);
+    }
+}
@@ -2,19 +2,22 @@
 //! Layer 3: Persistence - Handles rate limit tracking, violation counts, and ban management.
 //!
 //! **Storage Schema:**
-//! - `ratelimit:{api_key_hash}:{window_start}` → request count (u32) with TTL
+//! - `ratelimit:{api_key_hash}:{bucket}:{window_start}` → request count (u32)
+//! - `tokenbucket:{api_key_hash}:{bucket}` → Bincode-serialized TokenBucket
 //! - `violations:{api_key_hash}` → Bincode-serialized ViolationRecord
 //! - `bans:{api_key_hash}` → Bincode-serialized BanRecord
 //! - `ip_bans:{ip_hash}` → expiry timestamp (i64)
 //!
+//! RocksDB has no TTL here: `cleanup_expired` purges past windows, idle token buckets
+//! and expired bans. Violation records are kept so penalties keep escalating.
+//!
 //! **Performance Optimizations:**
 //! - LRU cache for hot API keys (10,000 entries, >95% hit rate)
-//! - Sliding window with 1-second buckets
+//! - Sliding window with 1-second buckets, or token bucket refilled on read
 //! - Batch writes for violation updates
 
-use chrono::Utc;
 use lru::LruCache;
-use rocksdb::DB;
+use rocksdb::{WriteBatch, DB};
 use serde::{Deserialize, Serialize};
 use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
@@ -24,7 +27,7 @@ use crate::constants::{
     BAN_IP_24HR_SECONDS, BAN_WRITE_1HR_SECONDS, BAN_WRITE_5MIN_SECONDS, RATE_LIMIT_CACHE_SIZE,
     RATE_LIMIT_WINDOW_SECONDS, VIOLATION_COOLDOWN_SECONDS,
 };
-use crate::types::{GoudChainError, Result};
+use crate::types::{GoudChainError, Result, SharedClock};
 
 /// Ban levels for graduated penalties
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -96,8 +99,7 @@ impl ViolationRecord {
         }
     }
 
-    pub fn record_violation(&mut self) {
-        let now = Utc::now().timestamp();
+    pub fn record_violation(&mut self, now: i64) {
         self.count += 1;
         self.last_violation_timestamp = now;
         self.violation_timestamps.push(now);
@@ -118,8 +120,7 @@ pub struct BanRecord {
 }
 
 impl BanRecord {
-    pub fn new(level: BanLevel) -> Self {
-        let now = Utc::now().timestamp();
+    pub fn new(level: BanLevel, now: i64) -> Self {
         let expires_at = level.duration_seconds().map(|dur| now + dur as i64);
 
         Self {
@@ -129,9 +130,9 @@ impl BanRecord {
         }
     }
 
-    pub fn is_expired(&self) -> bool {
+    pub fn is_expired(&self, now: i64) -> bool {
         match self.expires_at {
-            Some(expiry) => Utc::now().timestamp() >= expiry,
+            Some(expiry) => now >= expiry,
             None => false, // Permanent bans never expire
         }
     }
@@ -145,30 +146,62 @@ struct RateLimitCacheEntry {
     window_start: i64,
 }
 
+/// Token bucket state for one API key and endpoint bucket
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill_ms: i64,
+}
+
+/// Outcome of consuming a token from a bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenBucketResult {
+    pub allowed: bool,
+    pub remaining: u32,
+}
+
 /// Rate limiting storage with RocksDB backend and LRU cache
 pub struct RateLimitStore {
     db: Arc<DB>,
     cache: Arc<Mutex<LruCache<String, RateLimitCacheEntry>>>,
+    token_buckets: Arc<Mutex<LruCache<String, TokenBucket>>>,
+    clock: SharedClock,
 }
 
 impl RateLimitStore {
     /// Initialize rate limit store using existing RocksDB instance
     pub fn new(db: Arc<DB>) -> Self {
+        Self::with_clock(db, SharedClock::default())
+    }
+
+    /// Initialize a store that measures windows, refills and ban expiry with `clock`
+    pub fn with_clock(db: Arc<DB>, clock: SharedClock) -> Self {
         info!("Initializing RateLimitStore with LRU cache");
 
         let cache_size = NonZeroUsize::new(RATE_LIMIT_CACHE_SIZE).unwrap();
         let cache = Arc::new(Mutex::new(LruCache::new(cache_size)));
+        let token_buckets = Arc::new(Mutex::new(LruCache::new(cache_size)));
 
-        Self { db, cache }
+        Self {
+            db,
+            cache,
+            token_buckets,
+            clock,
+        }
+    }
+
+    /// Clock the store measures time with
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
     }
 
-    /// Increment request count for API key in current time window
+    /// Increment request count for API key and endpoint bucket in current time window
     /// Returns current count after increment
-    pub fn increment_request_count(&self, api_key_hash: &str, is_write: bool) -> Result<u32> {
-        let now = Utc::now().timestamp();
+    pub fn increment_request_count(&self, api_key_hash: &str, bucket: &str) -> Result<u32> {
+        let now = self.clock.now();
         let window_start = now - (now % RATE_LIMIT_WINDOW_SECONDS as i64);
 
-        let cache_key = format!("{}:{}", api_key_hash, window_start);
+        let cache_key = format!("{}:{}:{}", api_key_hash, bucket, window_start);
 
         // Check cache first
         {
@@ -177,6 +210,7 @@ impl RateLimitStore {
                 entry.count += 1;
                 debug!(
                     api_key_hash = %api_key_hash,
+                    bucket = bucket,
                     count = entry.count,
                     window_start = window_start,
                     "Cache hit: incremented request count"
@@ -186,7 +220,7 @@ impl RateLimitStore {
         }
 
         // Cache miss - read from RocksDB
-        let db_key = format!("ratelimit:{}:{}", api_key_hash, window_start);
+        let db_key = format!("ratelimit:{}:{}:{}", api_key_hash, bucket, window_start);
         let current_count = match self.db.get(db_key.as_bytes()) {
             Ok(Some(bytes)) => {
                 let mut count_bytes = [0u8; 4];
@@ -227,19 +261,108 @@ impl RateLimitStore {
             api_key_hash = %api_key_hash,
             count = new_count,
             window_start = window_start,
-            is_write = is_write,
+            bucket = bucket,
             "Incremented request count"
         );
 
         Ok(new_count)
     }
 
-    /// Get current request count for API key in current window
+    /// Consume one token from the API key's bucket, refilling for the time elapsed since last use
+    /// New buckets start full, so clients can burst up to `capacity` before being throttled
+    pub fn consume_token(
+        &self,
+        api_key_hash: &str,
+        bucket: &str,
+        capacity: u32,
+        refill_per_second: u32,
+    ) -> Result<TokenBucketResult> {
+        self.consume_token_at(
+            api_key_hash,
+            bucket,
+            capacity,
+            refill_per_second,
+            self.clock.now_millis(),
+        )
+    }
+
+    fn consume_token_at(
+        &self,
+        api_key_hash: &str,
+        bucket: &str,
+        capacity: u32,
+        refill_per_second: u32,
+        now_ms: i64,
+    ) -> Result<TokenBucketResult> {
+        let db_key = format!("tokenbucket:{}:{}", api_key_hash, bucket);
+
+        // Hold the cache lock across read-modify-write so concurrent requests can't double-spend
+        let mut cache = self.token_buckets.lock().unwrap();
+
+        let stored = match cache.get(&db_key) {
+            Some(state) => Some(*state),
+            None => match self.db.get(db_key.as_bytes()) {
+                Ok(Some(bytes)) => Some(
+                    bincode::deserialize::<TokenBucket>(&bytes)
+                        .map_err(|e| GoudChainError::DeserializationError(e.to_string()))?,
+                ),
+                Ok(None) => None,
+                Err(e) => {
+                    return Err(GoudChainError::RocksDbError(format!(
+                        "Failed to read token bucket: {}",
+                        e
+                    )))
+                }
+            },
+        };
+
+        let mut state = match stored {
+            Some(state) => {
+                let elapsed_ms = (now_ms - state.last_refill_ms).max(0) as f64;
+                let refilled = state.tokens + elapsed_ms * refill_per_second as f64 / 1000.0;
+                TokenBucket {
+                    tokens: refilled.min(capacity as f64),
+                    last_refill_ms: now_ms,
+                }
+            }
+            None => TokenBucket {
+                tokens: capacity as f64,
+                last_refill_ms: now_ms,
+            },
+        };
+
+        let allowed = state.tokens >= 1.0;
+        if allowed {
+            state.tokens -= 1.0;
+        }
+
+        let bytes = bincode::serialize(&state)
+            .map_err(|e| GoudChainError::SerializationError(e.to_string()))?;
+        self.db.put(db_key.as_bytes(), &bytes).map_err(|e| {
+            GoudChainError::RocksDbError(format!("Failed to update token bucket: {}", e))
+        })?;
+        cache.put(db_key, state);
+
+        debug!(
+            api_key_hash = %api_key_hash,
+            bucket = bucket,
+            tokens = state.tokens,
+            allowed = allowed,
+            "Consumed token"
+        );
+
+        Ok(TokenBucketResult {
+            allowed,
+            remaining: state.tokens as u32,
+        })
+    }
+
+    /// Get current request count for API key and endpoint bucket in current window
     #[allow(dead_code)]
-    fn get_current_count(&self, api_key_hash: &str) -> Result<u32> {
-        let now = Utc::now().timestamp();
+    fn get_current_count(&self, api_key_hash: &str, bucket: &str) -> Result<u32> {
+        let now = self.clock.now();
         let window_start = now - (now % RATE_LIMIT_WINDOW_SECONDS as i64);
-        let cache_key = format!("{}:{}", api_key_hash, window_start);
+        let cache_key = format!("{}:{}:{}", api_key_hash, bucket, window_start);
 
         // Check cache
         {
@@ -250,7 +373,7 @@ impl RateLimitStore {
         }
 
         // Read from RocksDB
-        let db_key = format!("ratelimit:{}:{}", api_key_hash, window_start);
+        let db_key = format!("ratelimit:{}:{}:{}", api_key_hash, bucket, window_start);
         match self.db.get(db_key.as_bytes()) {
             Ok(Some(bytes)) => {
                 let mut count_bytes = [0u8; 4];
@@ -282,7 +405,7 @@ impl RateLimitStore {
             }
         };
 
-        record.record_violation();
+        record.record_violation(self.clock.now());
 
         // Save updated record
         let bytes = bincode::serialize(&record)
@@ -318,7 +441,7 @@ impl RateLimitStore {
     /// Apply a ban to an API key
     pub fn apply_ban(&self, api_key_hash: &str, level: BanLevel) -> Result<()> {
         let key = format!("bans:{}", api_key_hash);
-        let record = BanRecord::new(level.clone());
+        let record = BanRecord::new(level.clone(), self.clock.now());
 
         let bytes = bincode::serialize(&record)
             .map_err(|e| GoudChainError::SerializationError(e.to_string()))?;
@@ -346,7 +469,7 @@ impl RateLimitStore {
                     .map_err(|e| GoudChainError::DeserializationError(e.to_string()))?;
 
                 // Check if ban has expired
-                if record.is_expired() {
+                if record.is_expired(self.clock.now()) {
                     // Delete expired ban
                     self.db
                         .delete(key.as_bytes())
@@ -364,10 +487,25 @@ impl RateLimitStore {
         }
     }
 
+    /// Lift an API key's ban and reset its violation history (operator action)
+    /// The next violation starts again from the first penalty level
+    pub fn clear_ban(&self, api_key_hash: &str) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        batch.delete(format!("bans:{}", api_key_hash).as_bytes());
+        batch.delete(format!("violations:{}", api_key_hash).as_bytes());
+        self.db
+            .write(batch)
+            .map_err(|e| GoudChainError::RocksDbError(format!("Failed to clear ban: {}", e)))?;
+
+        warn!(api_key_hash = %api_key_hash, "Cleared ban and violation history");
+
+        Ok(())
+    }
+
     /// Ban an IP address for 24 hours (after complete blacklist)
     pub fn ban_ip(&self, ip_hash: &str) -> Result<()> {
         let key = format!("ip_bans:{}", ip_hash);
-        let expires_at = Utc::now().timestamp() + BAN_IP_24HR_SECONDS as i64;
+        let expires_at = self.clock.now() + BAN_IP_24HR_SECONDS as i64;
 
         self.db
             .put(key.as_bytes(), expires_at.to_le_bytes())
@@ -392,8 +530,7 @@ impl RateLimitStore {
                 expiry_bytes.copy_from_slice(&bytes);
                 let expires_at = i64::from_le_bytes(expiry_bytes);
 
-                let now = Utc::now().timestamp();
-                if now >= expires_at {
+                if self.clock.now() >= expires_at {
                     // Ban expired, delete it
                     self.db
                         .delete(key.as_bytes())
@@ -411,29 +548,96 @@ impl RateLimitStore {
         }
     }
 
-    /// Clear all rate limit data (for testing)
-    #[allow(dead_code)]
-    pub fn clear_all(&self) -> Result<()> {
-        warn!("Clearing all rate limit data");
+    /// Delete stale rate-limit state: past windows, fully refilled token buckets,
+    /// and expired API key and IP bans. Returns the number of deleted keys.
+    /// Buckets idle for `token_bucket_idle_seconds` have refilled and are equivalent to new ones.
+    pub fn cleanup_expired(&self, token_bucket_idle_seconds: u64) -> Result<u32> {
+        let now = self.clock.now();
+        let window_seconds = RATE_LIMIT_WINDOW_SECONDS as i64;
+        let idle_ms = token_bucket_idle_seconds as i64 * 1000;
+
+        let mut batch = WriteBatch::default();
+        let mut deleted_count = 0u32;
+
+        // Window start is the last key segment (also matches keys written before endpoint buckets)
+        deleted_count += self.collect_stale(&mut batch, "ratelimit:", |key, _| {
+            std::str::from_utf8(key)
+                .ok()
+                .and_then(|k| k.rsplit(':').next())
+                .and_then(|w| w.parse::<i64>().ok())
+                .is_some_and(|window_start| window_start + window_seconds <= now)
+        })?;
 
-        // This is a simplified version - in production you'd iterate through keys
-        // For now, we rely on TTL and periodic cleanup
+        deleted_count += self.collect_stale(&mut batch, "tokenbucket:", |_, value| {
+            bincode::deserialize::<TokenBucket>(value)
+                .is_ok_and(|state| now * 1000 - state.last_refill_ms >= idle_ms)
+        })?;
 
-        Ok(())
+        deleted_count += self.collect_stale(&mut batch, "bans:", |_, value| {
+            bincode::deserialize::<BanRecord>(value).is_ok_and(|record| record.is_expired(now))
+        })?;
+
+        deleted_count += self.collect_stale(&mut batch, "ip_bans:", |_, value| {
+            <[u8; 8]>::try_from(value).is_ok_and(|bytes| i64::from_le_bytes(bytes) <= now)
+        })?;
+
+        self.db.write(batch).map_err(|e| {
+            GoudChainError::RocksDbError(format!("Failed to purge rate limit data: {}", e))
+        })?;
+
+        if deleted_count > 0 {
+            info!(deleted = deleted_count, "Purged stale rate limit entries");
+        }
+
+        Ok(deleted_count)
+    }
+
+    /// Queue deletion of every key under `prefix` whose entry is stale
+    fn collect_stale(
+        &self,
+        batch: &mut WriteBatch,
+        prefix: &str,
+        is_stale: impl Fn(&[u8], &[u8]) -> bool,
+    ) -> Result<u32> {
+        let mut count = 0u32;
+
+        for item in self.db.prefix_iterator(prefix.as_bytes()) {
+            let (key, value) = item.map_err(|e| {
+                GoudChainError::RocksDbError(format!("Failed to iterate {}: {}", prefix, e))
+            })?;
+
+            // Stop iteration when prefix changes
+            let Some(suffix) = key.strip_prefix(prefix.as_bytes()) else {
+                break;
+            };
+
+            if is_stale(suffix, &value) {
+                batch.delete(&key);
+                count += 1;
+            }
+        }
+
+        Ok(count)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::MockClock;
+    use chrono::Utc;
     use rocksdb::DB;
     use std::sync::Arc;
 
     fn create_test_store() -> RateLimitStore {
+        create_test_store_with_clock(SharedClock::default())
+    }
+
+    fn create_test_store_with_clock(clock: SharedClock) -> RateLimitStore {
         let temp_dir =
             std::env::temp_dir().join(format!("test_ratelimit_{}", rand::random::<u64>()));
         let db = DB::open_default(&temp_dir).expect("Failed to open test DB");
-        RateLimitStore::new(Arc::new(db))
+        RateLimitStore::with_clock(Arc::new(db), clock)
     }
 
     #[test]
@@ -441,14 +645,24 @@ mod tests {
         let store = create_test_store();
         let api_key_hash = "test_key_123";
 
-        let count1 = store.increment_request_count(api_key_hash, true).unwrap();
+        let count1 = store
+            .increment_request_count(api_key_hash, "submit")
+            .unwrap();
         assert_eq!(count1, 1);
 
-        let count2 = store.increment_request_count(api_key_hash, true).unwrap();
+        let count2 = store
+            .increment_request_count(api_key_hash, "submit")
+            .unwrap();
         assert_eq!(count2, 2);
 
-        let count3 = store.increment_request_count(api_key_hash, true).unwrap();
+        let count3 = store
+            .increment_request_count(api_key_hash, "submit")
+            .unwrap();
         assert_eq!(count3, 3);
+
+        // Other endpoint buckets count independently
+        let other = store.increment_request_count(api_key_hash, "list").unwrap();
+        assert_eq!(other, 1);
     }
 
     #[test]
@@ -458,20 +672,181 @@ mod tests {
 
         // Under limit (9 requests)
         for _ in 0..9 {
-            let count = store.increment_request_count(api_key_hash, true).unwrap();
+            let count = store
+                .increment_request_count(api_key_hash, "submit")
+                .unwrap();
             assert!(count <= 10); // 10 writes/sec limit
         }
 
         // At limit (10th request)
-        let count = store.increment_request_count(api_key_hash, true).unwrap();
+        let count = store
+            .increment_request_count(api_key_hash, "submit")
+            .unwrap();
         assert_eq!(count, 10);
 
         // Over limit (11th request)
-        let count = store.increment_request_count(api_key_hash, true).unwrap();
+        let count = store
+            .increment_request_count(api_key_hash, "submit")
+            .unwrap();
         assert_eq!(count, 11);
         assert!(count > 10); // Exceeded limit
     }
 
+    #[test]
+    fn test_token_bucket_burst_then_idle() {
+        let store = create_test_store();
+        let key = "test_key_burst";
+        let start = 1_700_000_000_000;
+
+        // A fresh bucket absorbs a full burst, then throttles
+        for i in 0..10 {
+            let result = store
+                .consume_token_at(key, "submit", 10, 5, start + i)
+                .unwrap();
+            assert!(result.allowed);
+            assert_eq!(result.remaining, 9 - i as u32);
+        }
+        assert!(
+            !store
+                .consume_token_at(key, "submit", 10, 5, start + 10)
+                .unwrap()
+                .allowed
+        );
+
+        // Idling refills at the configured rate (5 tokens/sec → 1 token per 200ms)
+        let result = store
+            .consume_token_at(key, "submit", 10, 5, start + 410)
+            .unwrap();
+        assert!(result.allowed);
+        assert_eq!(result.remaining, 1);
+
+        // A long idle refills to capacity but never beyond it
+        let later = start + 60_000;
+        for i in 0..10 {
+            assert!(
+                store
+                    .consume_token_at(key, "submit", 10, 5, later + i)
+                    .unwrap()
+                    .allowed
+            );
+        }
+        assert!(
+            !store
+                .consume_token_at(key, "submit", 10, 5, later + 10)
+                .unwrap()
+                .allowed
+        );
+    }
+
+    #[test]
+    fn test_token_bucket_catches_sustained_abuse() {
+        let store = create_test_store();
+        let key = "test_key_sustained";
+        let start = 1_700_000_000_000;
+
+        // 20 req/sec against a 5/sec refill: the burst allowance drains and most requests fail
+        let allowed = (0..100)
+            .filter(|i| {
+                store
+                    .consume_token_at(key, "submit", 10, 5, start + i * 50)
+                    .unwrap()
+                    .allowed
+            })
+            .count();
+        // 10 burst tokens + ~5 seconds of refill
+        assert!((34..=36).contains(&allowed), "allowed {}", allowed);
+    }
+
+    #[test]
+    fn test_token_bucket_persists_across_cache_eviction() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("test_ratelimit_{}", rand::random::<u64>()));
+        let db = Arc::new(DB::open_default(&temp_dir).expect("Failed to open test DB"));
+        let start = 1_700_000_000_000;
+
+        let store = RateLimitStore::new(Arc::clone(&db));
+        for i in 0..3 {
+            store
+                .consume_token_at("key", "list", 3, 1, start + i)
+                .unwrap();
+        }
+
+        // A fresh store (empty cache) reads the drained bucket back from RocksDB
+        let reopened = RateLimitStore::new(db);
+        assert!(
+            !reopened
+                .consume_token_at("key", "list", 3, 1, start + 3)
+                .unwrap()
+                .allowed
+        );
+    }
+
+    #[test]
+    fn test_cleanup_purges_stale_entries() {
+        let store = create_test_store();
+        let now = Utc::now().timestamp();
+
+        // Old windows (current and legacy key formats)
+        store
+            .db
+            .put(b"ratelimit:old_key:submit:1000", 5u32.to_le_bytes())
+            .unwrap();
+        store
+            .db
+            .put(b"ratelimit:old_key:2000", 5u32.to_le_bytes())
+            .unwrap();
+
+        // Idle and recently used token buckets
+        store
+            .consume_token_at("idle_key", "list", 10, 10, (now - 3600) * 1000)
+            .unwrap();
+        store.consume_token("busy_key", "list", 10, 10).unwrap();
+
+        // Expired, active and permanent API key bans
+        let expired = BanRecord {
+            level: BanLevel::WriteBlock5Min,
+            expires_at: Some(now - 10),
+            created_at: now - 310,
+        };
+        store
+            .db
+            .put(b"bans:expired_key", bincode::serialize(&expired).unwrap())
+            .unwrap();
+        store
+            .apply_ban("active_key", BanLevel::WriteBlock1Hr)
+            .unwrap();
+        store
+            .apply_ban("permanent_key", BanLevel::PermanentWriteBan)
+            .unwrap();
+
+        // Expired and active IP bans
+        store
+            .db
+            .put(b"ip_bans:expired_ip", (now - 1).to_le_bytes())
+            .unwrap();
+        store.ban_ip("active_ip").unwrap();
+
+        store.record_violation("expired_key").unwrap();
+
+        assert_eq!(store.cleanup_expired(60).unwrap(), 5);
+
+        let exists = |key: &str| store.db.get(key.as_bytes()).unwrap().is_some();
+        assert!(!exists("ratelimit:old_key:submit:1000"));
+        assert!(!exists("ratelimit:old_key:2000"));
+        assert!(!exists("tokenbucket:idle_key:list"));
+        assert!(!exists("bans:expired_key"));
+        assert!(!exists("ip_bans:expired_ip"));
+
+        assert!(exists("tokenbucket:busy_key:list"));
+        assert!(exists("bans:active_key"));
+        assert!(exists("bans:permanent_key"));
+        assert!(exists("violations:expired_key"));
+        assert!(store.is_ip_banned("active_ip").unwrap());
+
+        // Rerunning finds nothing left to delete
+        assert_eq!(store.cleanup_expired(60).unwrap(), 0);
+    }
+
     #[test]
     fn test_violation_tracking() {
         let store = create_test_store();
@@ -526,6 +901,28 @@ mod tests {
         assert!(ban.expires_at.is_none()); // Permanent
     }
 
+    #[test]
+    fn test_clear_ban_resets_violations() {
+        let store = create_test_store();
+        let api_key_hash = "test_key_clear";
+
+        store.record_violation(api_key_hash).unwrap();
+        store.record_violation(api_key_hash).unwrap();
+        store
+            .apply_ban(api_key_hash, BanLevel::PermanentWriteBan)
+            .unwrap();
+
+        store.clear_ban(api_key_hash).unwrap();
+        assert!(store.get_ban_status(api_key_hash).unwrap().is_none());
+        assert_eq!(store.get_violations(api_key_hash).unwrap().count, 0);
+
+        // Clearing a key with nothing recorded is a no-op
+        store.clear_ban("test_key_never_banned").unwrap();
+
+        // Penalties start over from the first violation
+        assert_eq!(store.record_violation(api_key_hash).unwrap().count, 1);
+    }
+
     #[test]
     fn test_ip_ban() {
         let store = create_test_store();
@@ -538,4 +935,45 @@ mod tests {
         store.ban_ip(ip_hash).unwrap();
         assert!(store.is_ip_banned(ip_hash).unwrap());
     }
+
+    #[test]
+    fn test_windows_and_bans_follow_the_clock() {
+        let clock = Arc::new(MockClock::new(1_700_000_000));
+        let store = create_test_store_with_clock(Arc::clone(&clock).into());
+        let api_key_hash = "test_key_clock";
+
+        // Counts reset when the window rolls over
+        assert_eq!(
+            store
+                .increment_request_count(api_key_hash, "submit")
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            store
+                .increment_request_count(api_key_hash, "submit")
+                .unwrap(),
+            2
+        );
+        clock.advance(RATE_LIMIT_WINDOW_SECONDS as i64);
+        assert_eq!(
+            store
+                .increment_request_count(api_key_hash, "submit")
+                .unwrap(),
+            1
+        );
+
+        // A timed ban lasts exactly its duration
+        store
+            .apply_ban(api_key_hash, BanLevel::WriteBlock5Min)
+            .unwrap();
+        clock.advance(BAN_WRITE_5MIN_SECONDS as i64 - 1);
+        assert!(store.get_ban_status(api_key_hash).unwrap().is_some());
+        clock.advance(1);
+        assert!(store.get_ban_status(api_key_hash).unwrap().is_none());
+
+        store.ban_ip("ip_clock").unwrap();
+        clock.advance(BAN_IP_24HR_SECONDS as i64);
+        assert!(!store.is_ip_banned("ip_clock").unwrap());
+    }
 }
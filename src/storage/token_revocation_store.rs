@@ -0,0 +1,244 @@
+//! Session token and API key revocation storage using RocksDB.
+//! Layer 3: Persistence - Tracks revoked JWT ids until the token would have expired anyway,
+//! and API keys retired by key rotation.
+//!
+//! **Storage Schema:**
+//! - `revoked_token:{jti}` → expiry timestamp (i64)
+//! - `revoked_api_key:{sha256(api_key)}` → revocation timestamp (i64)
+//! - `revoked_key_hash:{api_key_hash}` → revocation timestamp (i64)
+//!
+//! Token entries are only needed while the token could still pass signature and expiry checks,
+//! so each one expires with its token and is removed lazily or by periodic cleanup. Rotated API
+//! keys never become valid again, so their entries are permanent. A rotated key is recorded
+//! twice: by a SHA-256 fingerprint, so API key requests are refused without the slow key hash,
+//! and by its lookup hash, which session tokens carry.
+//!
+//! Revocations are node-local: they are not replicated, so a token revoked on one node
+//! is still accepted by the others until it expires. Rotated keys are also refused on other
+//! nodes once the rotation block arrives, because the account no longer resolves under them.
+
+use chrono::Utc;
+use rocksdb::DB;
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info};
+
+use crate::types::{GoudChainError, Result};
+
+const REVOKED_TOKEN_PREFIX: &str = "revoked_token:";
+const REVOKED_API_KEY_PREFIX: &str = "revoked_api_key:";
+const REVOKED_KEY_HASH_PREFIX: &str = "revoked_key_hash:";
+
+/// Revocation set for session tokens, keyed by JWT id
+pub struct TokenRevocationStore {
+    db: Arc<DB>,
+    revoke_lock: Mutex<()>, // Serializes check-and-revoke in `revoke_once`
+}
+
+impl TokenRevocationStore {
+    /// Create a new token revocation store
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db,
+            revoke_lock: Mutex::new(()),
+        }
+    }
+
+    /// Revoke a token id until `expires_at` (Unix timestamp in seconds)
+    pub fn revoke(&self, jti: &str, expires_at: i64) -> Result<()> {
+        let key = format!("{}{}", REVOKED_TOKEN_PREFIX, jti);
+        self.db
+            .put(key.as_bytes(), expires_at.to_be_bytes())
+            .map_err(|e| GoudChainError::RocksDbError(format!("Failed to revoke token: {}", e)))?;
+
+        debug!("Token revoked until {}: {}", expires_at, jti);
+        Ok(())
+    }
+
+    /// Revoke a token id unless it is already revoked, as a single compare-and-set
+    /// Returns false if another caller revoked it first, so only one caller can consume a token
+    pub fn revoke_once(&self, jti: &str, expires_at: i64) -> Result<bool> {
+        let _guard = self
+            .revoke_lock
+            .lock()
+            .map_err(|_| GoudChainError::Internal("Token revocation lock poisoned".to_string()))?;
+        if self.is_revoked(jti)? {
+            return Ok(false);
+        }
+        self.revoke(jti, expires_at)?;
+        Ok(true)
+    }
+
+    /// Check if a token id has been revoked (and the revocation has not expired)
+    pub fn is_revoked(&self, jti: &str) -> Result<bool> {
+        let key = format!("{}{}", REVOKED_TOKEN_PREFIX, jti);
+        let value = self.db.get(key.as_bytes()).map_err(|e| {
+            GoudChainError::RocksDbError(format!("Failed to check token revocation: {}", e))
+        })?;
+
+        match value {
+            Some(bytes) => {
+                let expires_at = i64::from_be_bytes(bytes.try_into().map_err(|_| {
+                    GoudChainError::RocksDbError("Invalid revocation expiry format".to_string())
+                })?);
+
+                if expires_at > Utc::now().timestamp() {
+                    Ok(true)
+                } else {
+                    // Token has expired on its own, revocation no longer needed
+                    self.db.delete(key.as_bytes()).ok();
+                    Ok(false)
+                }
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Permanently revoke an API key retired by key rotation
+    pub fn revoke_api_key(&self, api_key: &[u8], api_key_hash: &str) -> Result<()> {
+        let revoked_at = Utc::now().timestamp().to_be_bytes();
+        let keys = [
+            format!("{}{}", REVOKED_API_KEY_PREFIX, api_key_fingerprint(api_key)),
+            format!("{}{}", REVOKED_KEY_HASH_PREFIX, api_key_hash),
+        ];
+        for key in keys {
+            self.db.put(key.as_bytes(), revoked_at).map_err(|e| {
+                GoudChainError::RocksDbError(format!("Failed to revoke API key: {}", e))
+            })?;
+        }
+
+        debug!("API key revoked: {}", api_key_hash);
+        Ok(())
+    }
+
+    /// Check if a raw API key was retired by key rotation (no key hashing needed)
+    pub fn is_api_key_revoked(&self, api_key: &[u8]) -> Result<bool> {
+        let key = format!("{}{}", REVOKED_API_KEY_PREFIX, api_key_fingerprint(api_key));
+        self.contains(&key)
+    }
+
+    /// Check if the API key behind a lookup hash was retired by key rotation
+    pub fn is_key_hash_revoked(&self, api_key_hash: &str) -> Result<bool> {
+        let key = format!("{}{}", REVOKED_KEY_HASH_PREFIX, api_key_hash);
+        self.contains(&key)
+    }
+
+    fn contains(&self, key: &str) -> Result<bool> {
+        self.db
+            .get(key.as_bytes())
+            .map(|value| value.is_some())
+            .map_err(|e| {
+                GoudChainError::RocksDbError(format!("Failed to check API key revocation: {}", e))
+            })
+    }
+
+    /// Remove revocation entries whose tokens have expired (periodic maintenance)
+    pub fn cleanup_expired(&self) -> Result<u32> {
+        let now = Utc::now().timestamp();
+        let mut deleted_count = 0u32;
+
+        for item in self.db.prefix_iterator(REVOKED_TOKEN_PREFIX.as_bytes()) {
+            let (key, value) = item.map_err(|e| {
+                GoudChainError::RocksDbError(format!("Failed to iterate revoked tokens: {}", e))
+            })?;
+
+            if !key.starts_with(REVOKED_TOKEN_PREFIX.as_bytes()) {
+                break; // Stop iteration when prefix changes
+            }
+
+            if let Ok(expiry_bytes) = value.as_ref().try_into() {
+                if i64::from_be_bytes(expiry_bytes) <= now {
+                    self.db.delete(&key).ok();
+                    deleted_count += 1;
+                }
+            }
+        }
+
+        if deleted_count > 0 {
+            info!("Cleaned up {} expired token revocations", deleted_count);
+        }
+
+        Ok(deleted_count)
+    }
+}
+
+/// SHA-256 of a raw API key (keys are 32 random bytes, so an unsalted digest reveals nothing)
+fn api_key_fingerprint(api_key: &[u8]) -> String {
+    hex::encode(Sha256::digest(api_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocksdb::Options;
+
+    fn create_test_store() -> TokenRevocationStore {
+        let path = format!("/tmp/goud_revocation_test_{}", rand::random::<u64>());
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        TokenRevocationStore::new(Arc::new(DB::open(&opts, path).unwrap()))
+    }
+
+    #[test]
+    fn test_revoke_token() {
+        let store = create_test_store();
+        let expires_at = Utc::now().timestamp() + 3600;
+
+        assert!(!store.is_revoked("token-1").unwrap());
+        store.revoke("token-1", expires_at).unwrap();
+        assert!(store.is_revoked("token-1").unwrap());
+        assert!(!store.is_revoked("token-2").unwrap());
+    }
+
+    #[test]
+    fn test_revoke_once_admits_a_single_caller() {
+        let store = Arc::new(create_test_store());
+        let expires_at = Utc::now().timestamp() + 3600;
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let store = Arc::clone(&store);
+                std::thread::spawn(move || store.revoke_once("token-1", expires_at).unwrap())
+            })
+            .collect();
+        let winners = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|won| *won)
+            .count();
+
+        assert_eq!(winners, 1);
+        assert!(store.is_revoked("token-1").unwrap());
+    }
+
+    #[test]
+    fn test_revocation_expires_with_token() {
+        let store = create_test_store();
+        let now = Utc::now().timestamp();
+
+        store.revoke("expired", now - 1).unwrap();
+        store.revoke("active", now + 3600).unwrap();
+        assert!(!store.is_revoked("expired").unwrap());
+
+        store.revoke("expired-again", now - 1).unwrap();
+        assert_eq!(store.cleanup_expired().unwrap(), 1);
+        assert!(store.is_revoked("active").unwrap());
+    }
+
+    #[test]
+    fn test_rotated_api_key_stays_revoked() {
+        let store = create_test_store();
+
+        assert!(!store.is_api_key_revoked(b"old-key").unwrap());
+        assert!(!store.is_key_hash_revoked("old-hash").unwrap());
+
+        store.revoke_api_key(b"old-key", "old-hash").unwrap();
+        assert!(store.is_api_key_revoked(b"old-key").unwrap());
+        assert!(store.is_key_hash_revoked("old-hash").unwrap());
+        assert!(!store.is_api_key_revoked(b"new-key").unwrap());
+
+        // Token cleanup leaves rotated keys alone
+        store.cleanup_expired().unwrap();
+        assert!(store.is_api_key_revoked(b"old-key").unwrap());
+    }
+}
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 /// Audit event types (stored as u8 enum for size optimization)
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
 #[repr(u8)]
 pub enum AuditEventType {
     /// Account created (POST /account/create)
@@ -18,6 +18,30 @@ pub enum AuditEventType {
     DataListed = 3,
     /// User logged in with API key (POST /account/login)
     AccountLogin = 4,
+    /// Collection deleted via tombstone (DELETE /data/{id})
+    DataDeleted = 5,
+    /// Session token refreshed (POST /account/refresh)
+    SessionRefreshed = 6,
+    /// Session token revoked on logout (POST /account/logout)
+    AccountLogout = 7,
+    /// Login attempt rejected (node-level security stream)
+    LoginFailed = 8,
+    /// Request blocked by the rate limiter (node-level security stream)
+    RateLimited = 9,
+    /// Request rejected for missing or invalid credentials (node-level security stream)
+    Unauthorized = 10,
+    /// Collection shared with another account (POST /data/{id}/share)
+    DataShared = 11,
+    /// Share grant revoked (DELETE /data/{id}/share/{grant_id})
+    ShareRevoked = 12,
+    /// Account metadata replaced (PUT /account/metadata)
+    AccountMetadataUpdated = 13,
+    /// API key rotated; logged under the new key (POST /account/rotate_key)
+    ApiKeyRotated = 14,
+    /// Encrypted collection envelope fetched for client-side decryption (GET /data/{id}/envelope)
+    EnvelopeFetched = 15,
+    /// Audit log exported (GET /audit/export)
+    AuditLogsExported = 16,
 }
 
 impl std::fmt::Display for AuditEventType {
@@ -28,6 +52,45 @@ impl std::fmt::Display for AuditEventType {
             Self::DataDecrypted => write!(f, "DataDecrypted"),
             Self::DataListed => write!(f, "DataListed"),
             Self::AccountLogin => write!(f, "AccountLogin"),
+            Self::DataDeleted => write!(f, "DataDeleted"),
+            Self::SessionRefreshed => write!(f, "SessionRefreshed"),
+            Self::AccountLogout => write!(f, "AccountLogout"),
+            Self::LoginFailed => write!(f, "LoginFailed"),
+            Self::RateLimited => write!(f, "RateLimited"),
+            Self::Unauthorized => write!(f, "Unauthorized"),
+            Self::DataShared => write!(f, "DataShared"),
+            Self::ShareRevoked => write!(f, "ShareRevoked"),
+            Self::AccountMetadataUpdated => write!(f, "AccountMetadataUpdated"),
+            Self::ApiKeyRotated => write!(f, "ApiKeyRotated"),
+            Self::EnvelopeFetched => write!(f, "EnvelopeFetched"),
+            Self::AuditLogsExported => write!(f, "AuditLogsExported"),
+        }
+    }
+}
+
+impl std::str::FromStr for AuditEventType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "AccountCreated" => Ok(Self::AccountCreated),
+            "DataSubmitted" => Ok(Self::DataSubmitted),
+            "DataDecrypted" => Ok(Self::DataDecrypted),
+            "DataListed" => Ok(Self::DataListed),
+            "AccountLogin" => Ok(Self::AccountLogin),
+            "DataDeleted" => Ok(Self::DataDeleted),
+            "SessionRefreshed" => Ok(Self::SessionRefreshed),
+            "AccountLogout" => Ok(Self::AccountLogout),
+            "LoginFailed" => Ok(Self::LoginFailed),
+            "RateLimited" => Ok(Self::RateLimited),
+            "Unauthorized" => Ok(Self::Unauthorized),
+            "DataShared" => Ok(Self::DataShared),
+            "ShareRevoked" => Ok(Self::ShareRevoked),
+            "AccountMetadataUpdated" => Ok(Self::AccountMetadataUpdated),
+            "ApiKeyRotated" => Ok(Self::ApiKeyRotated),
+            "EnvelopeFetched" => Ok(Self::EnvelopeFetched),
+            "AuditLogsExported" => Ok(Self::AuditLogsExported),
+            other => Err(format!("Unknown audit event type: {}", other)),
         }
     }
 }
@@ -59,6 +122,15 @@ pub struct AuditLogEntry {
     /// Soft delete flag (set when retention policy triggers)
     /// NOTE: Blockchain data is immutable, this flag only affects UI visibility
     pub invalidated: bool,
+
+    /// Whether the batch signature verified against the block validator's key
+    /// Set when reading; omitted (false) for unsigned or unverifiable batches
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub verified: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
 }
 
 /// Batch of audit log entries (stored as single EncryptedCollection)
@@ -85,6 +157,17 @@ impl AuditLogBatch {
     }
 }
 
+/// Audit batch with the producing node's Ed25519 signature
+/// `batch` holds the exact serialized `AuditLogBatch` bytes that were signed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedAuditBatch {
+    /// Serialized `AuditLogBatch` JSON
+    pub batch: String,
+
+    /// Hex Ed25519 signature over `batch`
+    pub signature: String,
+}
+
 /// API response for audit log queries (GET /api/audit)
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AuditLogResponse {
@@ -120,6 +203,35 @@ pub struct AuditLogFilter {
     pub include_invalidated: bool,
 }
 
+impl AuditLogFilter {
+    /// Check whether an entry passes every filter
+    pub fn matches(&self, entry: &AuditLogEntry) -> bool {
+        if !self.include_invalidated && entry.invalidated {
+            return false;
+        }
+
+        if let Some(event_type) = self.event_type {
+            if entry.event_type != event_type {
+                return false;
+            }
+        }
+
+        if let Some(start_ts) = self.start_ts {
+            if entry.timestamp < start_ts {
+                return false;
+            }
+        }
+
+        if let Some(end_ts) = self.end_ts {
+            if entry.timestamp > end_ts {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,6 +253,7 @@ mod tests {
             ip_hash: "1a2b3c4d".to_string(),
             metadata: serde_json::json!({"account_id": "test123"}),
             invalidated: false,
+            verified: false,
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -159,6 +272,7 @@ mod tests {
                 ip_hash: "12345678".to_string(),
                 metadata: serde_json::json!({}),
                 invalidated: false,
+                verified: false,
             },
             AuditLogEntry {
                 event_type: AuditEventType::DataDecrypted,
@@ -167,6 +281,7 @@ mod tests {
                 ip_hash: "87654321".to_string(),
                 metadata: serde_json::json!({}),
                 invalidated: false,
+                verified: false,
             },
         ];
 
@@ -174,4 +289,23 @@ mod tests {
         assert_eq!(batch.entry_count, 2);
         assert_eq!(batch.entries.len(), 2);
     }
+
+    #[test]
+    fn test_audit_event_type_from_str_roundtrip() {
+        for event in [
+            AuditEventType::AccountCreated,
+            AuditEventType::DataDeleted,
+            AuditEventType::AccountLogout,
+            AuditEventType::LoginFailed,
+            AuditEventType::RateLimited,
+            AuditEventType::Unauthorized,
+            AuditEventType::DataShared,
+            AuditEventType::ShareRevoked,
+            AuditEventType::AccountMetadataUpdated,
+            AuditEventType::ApiKeyRotated,
+        ] {
+            assert_eq!(event.to_string().parse::<AuditEventType>(), Ok(event));
+        }
+        assert!("NotAnEvent".parse::<AuditEventType>().is_err());
+    }
 }
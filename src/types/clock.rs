@@ -0,0 +1,137 @@
+//! Time source abstraction
+//! Layer 0: Foundation - Injectable "now" for expiry, bans, windows and timestamp checks
+//!
+//! Components that compare against the current time take a [`SharedClock`] instead of
+//! calling `Utc::now()` directly. Production code uses the default [`SystemClock`]; tests
+//! swap in a [`MockClock`] and move it forward to exercise expiry and rollover without
+//! sleeping.
+
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+/// Source of the current time
+pub trait Clock: Send + Sync {
+    /// Current time in Unix milliseconds
+    fn now_millis(&self) -> i64;
+
+    /// Current time in Unix seconds
+    fn now(&self) -> i64 {
+        self.now_millis().div_euclid(1000)
+    }
+
+    /// Current time as a `DateTime`
+    fn now_datetime(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(self.now_millis()).unwrap_or_default()
+    }
+}
+
+/// Wall-clock time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> i64 {
+        Utc::now().timestamp_millis()
+    }
+}
+
+/// Manually driven time for tests (starts where it is set and only moves when told to)
+#[derive(Debug)]
+pub struct MockClock {
+    millis: AtomicI64,
+}
+
+impl MockClock {
+    /// Create a clock stopped at `timestamp` (Unix seconds)
+    pub fn new(timestamp: i64) -> Self {
+        Self {
+            millis: AtomicI64::new(timestamp * 1000),
+        }
+    }
+
+    /// Jump to `timestamp` (Unix seconds)
+    pub fn set(&self, timestamp: i64) {
+        self.millis.store(timestamp * 1000, Ordering::SeqCst);
+    }
+
+    /// Move forward by `seconds`
+    pub fn advance(&self, seconds: i64) {
+        self.advance_millis(seconds * 1000);
+    }
+
+    /// Move forward by `millis`
+    pub fn advance_millis(&self, millis: i64) {
+        self.millis.fetch_add(millis, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now_millis(&self) -> i64 {
+        self.millis.load(Ordering::SeqCst)
+    }
+}
+
+/// Clock handle shared between components (the system clock unless one is injected)
+#[derive(Clone)]
+pub struct SharedClock(Arc<dyn Clock>);
+
+impl SharedClock {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self(clock)
+    }
+}
+
+impl Default for SharedClock {
+    fn default() -> Self {
+        Self(Arc::new(SystemClock))
+    }
+}
+
+impl From<Arc<MockClock>> for SharedClock {
+    fn from(clock: Arc<MockClock>) -> Self {
+        Self(clock)
+    }
+}
+
+impl std::ops::Deref for SharedClock {
+    type Target = dyn Clock;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Debug for SharedClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedClock")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_only_moves_when_told() {
+        let mock = Arc::new(MockClock::new(1_700_000_000));
+        let clock = SharedClock::from(Arc::clone(&mock));
+        assert_eq!(clock.now(), 1_700_000_000);
+        assert_eq!(clock.now_millis(), 1_700_000_000_000);
+
+        mock.advance(30);
+        mock.advance_millis(999);
+        assert_eq!(clock.now(), 1_700_000_030);
+        assert_eq!(clock.now_millis(), 1_700_000_030_999);
+
+        mock.set(0);
+        assert_eq!(clock.now_datetime().timestamp(), 0);
+    }
+
+    #[test]
+    fn test_default_clock_follows_wall_time() {
+        let clock = SharedClock::default();
+        assert!((clock.now() - Utc::now().timestamp()).abs() <= 1);
+    }
+}
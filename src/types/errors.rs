@@ -6,6 +6,8 @@ use axum::{
 use serde::Serialize;
 use thiserror::Error;
 
+use crate::types::validation::FieldError;
+
 /// All possible errors in the Goud Chain system
 #[derive(Error, Debug)]
 #[allow(dead_code)]
@@ -17,6 +19,9 @@ pub enum GoudChainError {
     #[error("Decryption failed: incorrect API key or corrupted data")]
     DecryptionFailed,
 
+    #[error("Compression failed: {0}")]
+    CompressionFailed(String),
+
     #[error("Invalid signature")]
     InvalidSignature,
 
@@ -56,6 +61,12 @@ pub enum GoudChainError {
         block_number: u64,
     },
 
+    #[error("Validator unavailable: {0}")]
+    ValidatorUnavailable(String),
+
+    #[error("Node is in read-only maintenance mode: writes are temporarily disabled")]
+    ReadOnlyMode,
+
     #[error("Empty blockchain: cannot get latest block")]
     EmptyBlockchain,
 
@@ -66,6 +77,9 @@ pub enum GoudChainError {
     #[error("Failed to connect to peer: {0}")]
     PeerConnectionFailed(String),
 
+    #[error("Peer unreachable: {0}")]
+    PeerUnreachable(String), // No connection was opened, so nothing was sent
+
     #[error("Failed to serialize message: {0}")]
     SerializationError(String),
 
@@ -96,12 +110,31 @@ pub enum GoudChainError {
     #[error("Data not found: {0}")]
     DataNotFound(String),
 
+    #[error("Revision conflict: expected version {expected_version}, head is version {head_version} ({head_collection_id})")]
+    RevisionConflict {
+        expected_version: usize,
+        head_collection_id: String,
+        head_version: usize,
+    },
+
+    #[error("Label conflict: label is already used by collection {collection_id}")]
+    LabelConflict { collection_id: String },
+
+    #[error("Data pruned: block {block_index} body is no longer stored on this node")]
+    DataPruned { block_index: u64 },
+
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
+    #[error("Unauthorized: Account not found")]
+    AccountNotFound,
+
     #[error("Authentication failed")]
     AuthenticationFailed, // Generic error for all auth failures (prevents info leakage)
 
+    #[error("Insufficient token scope: {0} access required")]
+    InsufficientScope(String),
+
     // Rate limiting errors - DoS Protection
     #[error(
         "Rate limit exceeded: {retry_after} seconds until reset (violation #{violation_count})"
@@ -134,16 +167,36 @@ pub enum GoudChainError {
     #[error("JSON too deep: maximum {max_depth} levels allowed")]
     JsonTooDeep { max_depth: usize },
 
+    #[error("Invalid content type: {0}")]
+    InvalidContentType(String),
+
+    #[error("Validation failed: {}", describe_field_errors(.errors))]
+    ValidationFailed { errors: Vec<FieldError> },
+
     // Request signing errors - Replay Attack Prevention
     #[error("Signature verification failed: {0}")]
     InvalidRequestSignature(String),
 
     #[error("Replay attack detected: nonce already used")]
-    NonceReused,
+    ReplayDetected,
 
     #[error("Request timestamp expired (older than 5 minutes)")]
     RequestExpired,
 
+    // Idempotency errors - Safe retries
+    #[error("Idempotency key in use: the original request is still in progress")]
+    IdempotencyKeyInUse,
+
+    #[error("Idempotency key was already used with a different request")]
+    IdempotencyKeyMismatch,
+
+    #[error("Idempotency key already used: account {account_id} was created and its API key is shown only once")]
+    IdempotencyKeyUsed { account_id: String },
+
+    // Request timeout - Resource exhaustion protection
+    #[error("Request timed out after {timeout_seconds} seconds")]
+    RequestTimeout { timeout_seconds: u64 },
+
     // Configuration errors
     #[error("Configuration error: {0}")]
     ConfigError(String),
@@ -192,14 +245,22 @@ impl GoudChainError {
             Self::InvalidRequestBody(_)
             | Self::InvalidLabel(_)
             | Self::InvalidJson(_)
-            | Self::JsonTooDeep { .. } => 400,
+            | Self::JsonTooDeep { .. }
+            | Self::InvalidContentType(_) => 400,
             Self::Unauthorized(_)
+            | Self::AccountNotFound
             | Self::AuthenticationFailed
             | Self::DecryptionFailed
             | Self::InvalidRequestSignature(_)
-            | Self::NonceReused
             | Self::RequestExpired => 401,
+            Self::InsufficientScope(_) => 403,
             Self::DataNotFound(_) | Self::KeyNotFound(_) => 404,
+            Self::ReplayDetected
+            | Self::RevisionConflict { .. }
+            | Self::LabelConflict { .. }
+            | Self::IdempotencyKeyInUse
+            | Self::IdempotencyKeyUsed { .. } => 409,
+            Self::DataPruned { .. } => 410,
             Self::PayloadTooLarge { .. } => 413,
             Self::InvalidSignature
             | Self::InvalidBlockHash(_)
@@ -208,19 +269,180 @@ impl GoudChainError {
             | Self::FutureTimestamp(_)
             | Self::InvalidTimestamp(_)
             | Self::InvalidValidator { .. }
-            | Self::NotAuthorizedValidator { .. } => 422,
+            | Self::NotAuthorizedValidator { .. }
+            | Self::IdempotencyKeyMismatch
+            | Self::ValidationFailed { .. } => 422,
             Self::RateLimitExceeded { .. }
             | Self::ApiKeyBanned { .. }
             | Self::IpAddressBanned { .. } => 429,
+            Self::ValidatorUnavailable(_) | Self::ReadOnlyMode => 503,
+            Self::RequestTimeout { .. } => 504,
             _ => 500,
         }
     }
+
+    /// Stable machine-readable error code (clients branch on this, not the message)
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            Self::EncryptionFailed(_) => "ENCRYPTION_FAILED",
+            Self::DecryptionFailed => "DECRYPTION_FAILED",
+            Self::CompressionFailed(_) => "COMPRESSION_FAILED",
+            Self::InvalidSignature => "INVALID_SIGNATURE",
+            Self::HexDecodingError(_) => "INVALID_HEX",
+            Self::Base64DecodingError(_) => "INVALID_BASE64",
+            Self::InvalidBlockHash(_) => "INVALID_BLOCK_HASH",
+            Self::BrokenChain(_) => "BROKEN_CHAIN",
+            Self::InvalidMerkleRoot(_) => "INVALID_MERKLE_ROOT",
+            Self::FutureTimestamp(_) => "FUTURE_TIMESTAMP",
+            Self::InvalidTimestamp(_) => "INVALID_TIMESTAMP",
+            Self::InvalidValidator { .. } => "INVALID_VALIDATOR",
+            Self::NotAuthorizedValidator { .. } => "NOT_VALIDATOR",
+            Self::ValidatorUnavailable(_) => "VALIDATOR_UNAVAILABLE",
+            Self::ReadOnlyMode => "READ_ONLY",
+            Self::EmptyBlockchain => "EMPTY_BLOCKCHAIN",
+            Self::NoPendingData => "NO_PENDING_DATA",
+            Self::PeerConnectionFailed(_) => "PEER_CONNECTION_FAILED",
+            Self::PeerUnreachable(_) => "PEER_UNREACHABLE",
+            Self::SerializationError(_) => "SERIALIZATION_ERROR",
+            Self::DeserializationError(_) => "DESERIALIZATION_ERROR",
+            Self::SaveFailed(_) => "SAVE_FAILED",
+            Self::LoadFailed(_) => "LOAD_FAILED",
+            Self::DirectoryCreationFailed(_) => "DIRECTORY_CREATION_FAILED",
+            Self::RocksDbError(_) => "STORAGE_ERROR",
+            Self::KeyNotFound(_) => "KEY_NOT_FOUND",
+            Self::InvalidRequestBody(_) => "INVALID_REQUEST_BODY",
+            Self::DataNotFound(_) => "DATA_NOT_FOUND",
+            Self::RevisionConflict { .. } => "REVISION_CONFLICT",
+            Self::LabelConflict { .. } => "LABEL_CONFLICT",
+            Self::DataPruned { .. } => "DATA_PRUNED",
+            Self::Unauthorized(_) => "UNAUTHORIZED",
+            Self::AccountNotFound => "ACCOUNT_NOT_FOUND",
+            Self::AuthenticationFailed => "AUTHENTICATION_FAILED",
+            Self::InsufficientScope(_) => "INSUFFICIENT_SCOPE",
+            Self::RateLimitExceeded { .. } => "RATE_LIMITED",
+            Self::ApiKeyBanned { .. } => "API_KEY_BANNED",
+            Self::IpAddressBanned { .. } => "IP_BANNED",
+            Self::PayloadTooLarge { .. } => "PAYLOAD_TOO_LARGE",
+            Self::InvalidLabel(_) => "INVALID_LABEL",
+            Self::InvalidJson(_) => "INVALID_JSON",
+            Self::JsonTooDeep { .. } => "JSON_TOO_DEEP",
+            Self::InvalidContentType(_) => "INVALID_CONTENT_TYPE",
+            Self::ValidationFailed { .. } => "VALIDATION_FAILED",
+            Self::InvalidRequestSignature(_) => "INVALID_REQUEST_SIGNATURE",
+            Self::ReplayDetected => "REPLAY_DETECTED",
+            Self::RequestExpired => "REQUEST_EXPIRED",
+            Self::IdempotencyKeyInUse => "IDEMPOTENCY_KEY_IN_USE",
+            Self::IdempotencyKeyMismatch => "IDEMPOTENCY_KEY_MISMATCH",
+            Self::IdempotencyKeyUsed { .. } => "IDEMPOTENCY_KEY_USED",
+            Self::RequestTimeout { .. } => "REQUEST_TIMEOUT",
+            Self::ConfigError(_) => "CONFIG_ERROR",
+            Self::AuditLogError(_) => "AUDIT_LOG_ERROR",
+            Self::MigrationNotFound(_) => "MIGRATION_NOT_FOUND",
+            Self::MigrationAlreadyApplied(_) => "MIGRATION_ALREADY_APPLIED",
+            Self::MigrationFailed(_) => "MIGRATION_FAILED",
+            Self::InvalidMigrationVersion(_) => "INVALID_MIGRATION_VERSION",
+            Self::MigrationRollbackFailed(_) => "MIGRATION_ROLLBACK_FAILED",
+            Self::NoMigrationsToRollback => "NO_MIGRATIONS_TO_ROLLBACK",
+            Self::Internal(_) => "INTERNAL_ERROR",
+            Self::Utf8Error(_) => "INVALID_UTF8",
+            Self::IoError(_) => "IO_ERROR",
+        }
+    }
+
+    /// Structured fields for variants that carry them (None for message-only errors)
+    pub fn details(&self) -> Option<serde_json::Value> {
+        match self {
+            Self::InvalidBlockHash(index)
+            | Self::BrokenChain(index)
+            | Self::InvalidMerkleRoot(index)
+            | Self::InvalidTimestamp(index)
+            | Self::DataPruned { block_index: index } => {
+                Some(serde_json::json!({ "block_index": index }))
+            }
+            Self::FutureTimestamp(timestamp) => Some(serde_json::json!({ "timestamp": timestamp })),
+            Self::RevisionConflict {
+                expected_version,
+                head_collection_id,
+                head_version,
+            } => Some(serde_json::json!({
+                "expected_version": expected_version,
+                "head_collection_id": head_collection_id,
+                "head_version": head_version,
+            })),
+            Self::LabelConflict { collection_id } => {
+                Some(serde_json::json!({ "collection_id": collection_id }))
+            }
+            Self::InvalidValidator {
+                index,
+                expected,
+                actual,
+            } => Some(serde_json::json!({
+                "block_index": index,
+                "expected_validator": expected,
+                "actual_validator": actual,
+            })),
+            Self::NotAuthorizedValidator {
+                expected_validator,
+                block_number,
+                ..
+            } => Some(serde_json::json!({
+                "expected_validator": expected_validator,
+                "block_number": block_number,
+            })),
+            Self::InsufficientScope(scope) => Some(serde_json::json!({ "required_scope": scope })),
+            Self::RateLimitExceeded {
+                retry_after,
+                violation_count,
+            } => Some(serde_json::json!({
+                "retry_after": retry_after,
+                "violation_count": violation_count,
+            })),
+            Self::ApiKeyBanned {
+                ban_level,
+                expires_at,
+            } => Some(serde_json::json!({
+                "ban_level": ban_level,
+                "expires_at": expires_at,
+            })),
+            Self::IdempotencyKeyUsed { account_id } => {
+                Some(serde_json::json!({ "account_id": account_id }))
+            }
+            Self::IpAddressBanned { expires_at } => {
+                Some(serde_json::json!({ "expires_at": expires_at }))
+            }
+            Self::PayloadTooLarge {
+                actual_bytes,
+                max_bytes,
+            } => Some(serde_json::json!({
+                "actual_bytes": actual_bytes,
+                "max_bytes": max_bytes,
+            })),
+            Self::JsonTooDeep { max_depth } => Some(serde_json::json!({ "max_depth": max_depth })),
+            Self::ValidationFailed { errors } => Some(serde_json::json!({ "errors": errors })),
+            Self::RequestTimeout { timeout_seconds } => {
+                Some(serde_json::json!({ "timeout_seconds": timeout_seconds }))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// One-line summary of field errors for the error message (`label: ...; data: ...`)
+fn describe_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 /// Error response wrapper for axum
 #[derive(Serialize)]
 struct ApiError {
     error: String,
+    code: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<serde_json::Value>,
 }
 
 /// Implement IntoResponse for GoudChainError to return HTTP responses
@@ -230,7 +452,69 @@ impl IntoResponse for GoudChainError {
             StatusCode::from_u16(self.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
         let body = Json(ApiError {
             error: self.to_string(),
+            code: self.error_code(),
+            details: self.details(),
         });
         (status, body).into_response()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn response_body(error: GoudChainError) -> (StatusCode, serde_json::Value) {
+        let response = error.into_response();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_error_response_includes_code() {
+        let (status, body) = response_body(GoudChainError::AccountNotFound).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "ACCOUNT_NOT_FOUND");
+        assert_eq!(body["error"], "Unauthorized: Account not found");
+        assert!(body.get("details").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_error_response_includes_details() {
+        let (status, body) = response_body(GoudChainError::RateLimitExceeded {
+            retry_after: 3,
+            violation_count: 2,
+        })
+        .await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body["code"], "RATE_LIMITED");
+        assert_eq!(body["details"]["retry_after"], 3);
+        assert_eq!(body["details"]["violation_count"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_validation_failed_lists_field_errors() {
+        let (status, body) = response_body(GoudChainError::ValidationFailed {
+            errors: vec![
+                FieldError::new("label", "exceeds 100 characters").with_limit(100),
+                FieldError::new("expires_at", "is not in the future"),
+            ],
+        })
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "VALIDATION_FAILED");
+        assert_eq!(
+            body["error"],
+            "Validation failed: label: exceeds 100 characters; expires_at: is not in the future"
+        );
+        assert_eq!(
+            body["details"]["errors"],
+            serde_json::json!([
+                {"field": "label", "reason": "exceeds 100 characters", "limit": 100},
+                {"field": "expires_at", "reason": "is not in the future"}
+            ])
+        );
+    }
+}
@@ -0,0 +1,19 @@
+//! API key hashing scheme selection
+//! Layer 0: Foundation - Shared by config (selection) and crypto (hashing)
+
+/// Argon2id cost parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Params {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+/// Key derivation function behind an account's `api_key_hash`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyKdf {
+    /// Iterated HKDF-SHA256 (original scheme, unprefixed hex hashes)
+    Hkdf,
+    /// Memory-hard Argon2id (`argon2id$m=..,t=..,p=..$<hex>` hashes)
+    Argon2id(Argon2Params),
+}
@@ -0,0 +1,123 @@
+//! Latency histograms for Prometheus export
+//! Layer 0: Foundation - Lock-free counters that any layer can record into
+//!
+//! Buckets are plain atomics so recording on the hot write/decrypt path costs a
+//! few relaxed increments. Rendering produces the standard cumulative
+//! `_bucket{le=...}`, `_sum` and `_count` series.
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Bucket upper bounds in seconds (Prometheus `le` labels, +Inf is implicit)
+const LATENCY_HISTOGRAM_BUCKETS_SECONDS: [f64; 12] = [
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+];
+const BUCKET_COUNT: usize = LATENCY_HISTOGRAM_BUCKETS_SECONDS.len();
+
+/// Time spent creating a block from pending accounts and collections
+pub static BLOCK_PRODUCTION_SECONDS: Histogram = Histogram::new(
+    "goud_block_production_seconds",
+    "Time spent producing a block from pending data",
+);
+
+/// Time spent encrypting, MACing and signing a new collection
+pub static ENCRYPTION_SECONDS: Histogram = Histogram::new(
+    "goud_encryption_seconds",
+    "Time spent encrypting and signing a collection",
+);
+
+/// Time spent decrypting a collection payload
+pub static DECRYPT_SECONDS: Histogram = Histogram::new(
+    "goud_decrypt_seconds",
+    "Time spent decrypting a collection payload",
+);
+
+/// Fixed-bucket latency histogram
+pub struct Histogram {
+    name: &'static str,
+    help: &'static str,
+    buckets: [AtomicU64; BUCKET_COUNT], // Non-cumulative counts per bucket
+    count: AtomicU64,
+    sum_nanos: AtomicU64,
+}
+
+impl Histogram {
+    pub const fn new(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            buckets: [const { AtomicU64::new(0) }; BUCKET_COUNT],
+            count: AtomicU64::new(0),
+            sum_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Record one observation
+    pub fn observe(&self, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64();
+        if let Some(index) = LATENCY_HISTOGRAM_BUCKETS_SECONDS
+            .iter()
+            .position(|bound| seconds <= *bound)
+        {
+            self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_nanos
+            .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// Render in Prometheus text format
+    pub fn render_prometheus(&self) -> String {
+        let mut output = format!(
+            "# HELP {name} {help}\n# TYPE {name} histogram\n",
+            name = self.name,
+            help = self.help
+        );
+
+        let mut cumulative = 0u64;
+        for (bound, bucket) in LATENCY_HISTOGRAM_BUCKETS_SECONDS.iter().zip(&self.buckets) {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let _ = writeln!(
+                output,
+                "{}_bucket{{le=\"{}\"}} {}",
+                self.name, bound, cumulative
+            );
+        }
+
+        let count = self.count.load(Ordering::Relaxed);
+        let sum_seconds = self.sum_nanos.load(Ordering::Relaxed) as f64 / 1e9;
+        let _ = write!(
+            output,
+            "{name}_bucket{{le=\"+Inf\"}} {count}\n{name}_sum {sum:.6}\n{name}_count {count}\n",
+            name = self.name,
+            count = count,
+            sum = sum_seconds
+        );
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let histogram = Histogram::new("test_seconds", "Test histogram");
+        histogram.observe(Duration::from_micros(300)); // le 0.0005
+        histogram.observe(Duration::from_millis(20)); // le 0.025
+        histogram.observe(Duration::from_secs(10)); // +Inf only
+
+        let output = histogram.render_prometheus();
+        assert!(output.contains("# TYPE test_seconds histogram"));
+        assert!(output.contains("test_seconds_bucket{le=\"0.0005\"} 1"));
+        assert!(output.contains("test_seconds_bucket{le=\"0.01\"} 1"));
+        assert!(output.contains("test_seconds_bucket{le=\"0.025\"} 2"));
+        assert!(output.contains("test_seconds_bucket{le=\"2.5\"} 2"));
+        assert!(output.contains("test_seconds_bucket{le=\"+Inf\"} 3"));
+        assert!(output.contains("test_seconds_sum 10.020300"));
+        assert!(output.contains("test_seconds_count 3"));
+    }
+}
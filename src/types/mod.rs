@@ -1,7 +1,12 @@
 pub mod audit;
+pub mod clock;
 pub mod errors;
+pub mod kdf;
+pub mod metrics;
 pub mod validation;
 
 // Re-export commonly used types
 pub use audit::*;
+pub use clock::{Clock, MockClock, SharedClock, SystemClock};
 pub use errors::{GoudChainError, Result};
+pub use kdf::{ApiKeyKdf, Argon2Params};
@@ -4,21 +4,97 @@
 //! **Validations:**
 //! - Label: Max 100 chars, no control characters (regex-based)
 //! - JSON: Valid structure, max 10 levels depth
+//! - Content type: `type/subtype` media type (optional parameters), max 100 chars
+//! - Binary payloads: standard base64
 //! - Performance: <1ms per validation
+//!
+//! Request validators collect every violation as a `FieldError` and report them together
+//! (`GoudChainError::ValidationFailed`, 422) instead of stopping at the first.
 
+use base64::{engine::general_purpose, Engine as _};
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::Serialize;
+use std::fmt;
 
 use crate::types::{GoudChainError, Result};
 
 // Validation constants (inlined to maintain layer 0 independence)
 const LABEL_REGEX: &str = r"^[a-zA-Z0-9 \-_.,!?()]+$";
 const MAX_JSON_DEPTH: usize = 10;
+const CONTENT_TYPE_REGEX: &str = r"^[a-zA-Z0-9][a-zA-Z0-9!#$&^_.+-]*/[a-zA-Z0-9][a-zA-Z0-9!#$&^_.+-]*(\s*;\s*[a-zA-Z0-9!#$&^_.+-]+=[a-zA-Z0-9!#$&^_.+-]+)*$";
+const MAX_CONTENT_TYPE_LENGTH: usize = 100;
 
 lazy_static! {
     /// Compiled regex for label validation (initialized once)
     static ref LABEL_VALIDATOR: Regex = Regex::new(LABEL_REGEX)
         .expect("Failed to compile label validation regex");
+
+    /// Compiled regex for media type validation (RFC 6838 restricted names)
+    static ref CONTENT_TYPE_VALIDATOR: Regex = Regex::new(CONTENT_TYPE_REGEX)
+        .expect("Failed to compile content type validation regex");
+}
+
+/// One violated input rule, reported back to the client by field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    /// Request field that failed (`label`, or `[2].label` for the third batch item)
+    pub field: String,
+    /// What is wrong with the value
+    pub reason: String,
+    /// The limit the value broke, for size and depth rules
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+impl FieldError {
+    pub fn new(field: &str, reason: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            reason: reason.into(),
+            limit: None,
+        }
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Report a failed check from this module against the field it checked
+    pub fn from_error(field: &str, error: GoudChainError) -> Self {
+        match error {
+            GoudChainError::InvalidLabel(reason)
+            | GoudChainError::InvalidJson(reason)
+            | GoudChainError::InvalidContentType(reason)
+            | GoudChainError::InvalidRequestBody(reason) => Self::new(field, reason),
+            GoudChainError::JsonTooDeep { max_depth } => {
+                Self::new(field, format!("nested deeper than {} levels", max_depth))
+                    .with_limit(max_depth)
+            }
+            GoudChainError::PayloadTooLarge {
+                actual_bytes,
+                max_bytes,
+            } => Self::new(
+                field,
+                format!("{} bytes exceeds {} bytes", actual_bytes, max_bytes),
+            )
+            .with_limit(max_bytes),
+            other => Self::new(field, other.to_string()),
+        }
+    }
+
+    /// Place the error inside a larger request: `label` under `[2]` becomes `[2].label`
+    pub fn under(mut self, prefix: &str) -> Self {
+        self.field = format!("{}.{}", prefix, self.field);
+        self
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.reason)
+    }
 }
 
 /// Validate label: max 100 chars, no control characters
@@ -51,6 +127,45 @@ pub fn validate_json_structure(data: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validate content type: `type/subtype` media type, max 100 chars
+/// Restricted to token characters so it is always safe to echo as a Content-Type header
+pub fn validate_content_type(content_type: &str) -> Result<()> {
+    if content_type.len() > MAX_CONTENT_TYPE_LENGTH {
+        return Err(GoudChainError::InvalidContentType(format!(
+            "Content type exceeds {} characters",
+            MAX_CONTENT_TYPE_LENGTH
+        )));
+    }
+
+    if !CONTENT_TYPE_VALIDATOR.is_match(content_type) {
+        return Err(GoudChainError::InvalidContentType(format!(
+            "'{}' is not a type/subtype media type",
+            content_type
+        )));
+    }
+
+    Ok(())
+}
+
+/// Lowercased `type/subtype` of a content type, without parameters
+/// Media types are case-insensitive, so comparisons against well-known types go through this
+pub fn media_type_essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Validate a binary payload: must be standard base64
+pub fn validate_base64_payload(data: &str) -> Result<()> {
+    general_purpose::STANDARD.decode(data).map_err(|e| {
+        GoudChainError::InvalidRequestBody(format!("Binary payload is not valid base64: {}", e))
+    })?;
+    Ok(())
+}
+
 /// Recursively check JSON depth to prevent deeply nested attacks
 fn check_json_depth(value: &serde_json::Value, current_depth: usize) -> Result<()> {
     if current_depth > MAX_JSON_DEPTH {
@@ -179,6 +294,38 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_content_type() {
+        assert!(validate_content_type("application/json").is_ok());
+        assert!(validate_content_type("application/octet-stream").is_ok());
+        assert!(validate_content_type("text/plain; charset=utf-8").is_ok());
+        assert!(validate_content_type("application/vnd.api+json").is_ok());
+
+        assert!(validate_content_type("").is_err());
+        assert!(validate_content_type("json").is_err());
+        assert!(validate_content_type("text/plain\r\nX-Injected: 1").is_err());
+        let long = format!("application/{}", "x".repeat(100));
+        assert!(validate_content_type(&long).is_err());
+    }
+
+    #[test]
+    fn test_media_type_essence() {
+        assert_eq!(
+            media_type_essence("Application/JSON; charset=utf-8"),
+            "application/json"
+        );
+        assert_eq!(
+            media_type_essence("application/octet-stream"),
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn test_validate_base64_payload() {
+        assert!(validate_base64_payload("aGVsbG8=").is_ok());
+        assert!(validate_base64_payload("not base64!").is_err());
+    }
+
     #[test]
     fn test_validation_performance() {
         use std::time::Instant;
@@ -14,8 +14,13 @@ fn test_envelope_encryption_privacy() {
     // Create an account
     let api_key = generate_api_key();
     let signing_key = generate_signing_key();
-    let account =
-        UserAccount::new(&api_key, &signing_key, Some(serde_json::json!("Test User"))).unwrap();
+    let account = UserAccount::new(
+        &api_key,
+        &signing_key,
+        Some(serde_json::json!("Test User")),
+        None,
+    )
+    .unwrap();
     let account_id = account.account_id.clone();
 
     // Add account to blockchain WITH API key for envelope encryption
@@ -29,6 +34,9 @@ fn test_envelope_encryption_privacy() {
     let collection = EncryptedCollection::new(
         "Secret Data".to_string(),
         "Confidential payload".to_string(),
+        None,
+        "application/json",
+        None,
         &api_key,
         api_key_hash.clone(),
         node_signing_key,
@@ -87,23 +95,26 @@ fn test_envelope_encryption_privacy() {
     );
     println!("✅ Block data stored as Base64-encoded envelope container");
 
-    // 7. Verify blind indexes field present (lazy generation)
+    // 7. Verify blind indexes hold only the owner bloom filter
     assert!(
         block_json.contains("blind_indexes"),
         "Block should have blind_indexes field"
     );
-    println!("✅ Blind indexes field present (lazy generation)");
-
-    if !block.blind_indexes.is_empty() {
-        let blind_index = &block.blind_indexes[0];
-        assert!(
-            blind_index.len() == 64,
-            "Blind index should be 64 chars (SHA256)"
-        );
-        println!("✅ Blind indexes are cryptographically secure hashes");
-    } else {
-        println!("✅ Blind indexes use lazy generation (computed at search time)");
-    }
+    assert_eq!(
+        block.blind_indexes.len(),
+        1,
+        "Block should carry one bloom filter"
+    );
+    assert!(
+        block.blind_indexes[0].starts_with("bloom1:"),
+        "Blind index entry should be an encoded bloom filter"
+    );
+    assert!(
+        !block_json.contains(&api_key_hash),
+        "❌ PRIVACY VIOLATION: API key hash visible in block JSON"
+    );
+    assert!(block.may_contain_owner(&api_key_hash));
+    println!("✅ Blind indexes are a bloom filter over salted owner indexes");
 
     // 8. Verify block_salt is present (used for HKDF envelope key derivation)
     assert!(
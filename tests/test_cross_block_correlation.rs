@@ -28,6 +28,9 @@ fn test_phase5_per_user_salt_prevents_cross_block_correlation() {
         let collection = EncryptedCollection::new(
             format!("Attacker Collection #{}", i),
             format!("{{\"secret\": {}}}", i),
+            None,
+            "application/json",
+            None,
             &attacker_api_key,
             attacker_key_hash.clone(),
             &attacker_signing_key,
@@ -189,6 +192,9 @@ fn test_user_salt_is_unique_per_collection() {
         let collection = EncryptedCollection::new(
             format!("Test #{}", i),
             format!("{{\"data\": {}}}", i),
+            None,
+            "application/json",
+            None,
             &api_key,
             api_key_hash.clone(),
             &signing_key,
@@ -222,6 +228,9 @@ fn test_user_salt_included_in_signature() {
     let collection = EncryptedCollection::new(
         "Test".to_string(),
         r#"{"test": "data"}"#.to_string(),
+        None,
+        "application/json",
+        None,
         &api_key,
         api_key_hash,
         &signing_key,
@@ -15,5 +15,6 @@ pub fn test_validator_config() -> ValidatorConfig {
         validators: vec!["Validator_1".to_string(), "Validator_2".to_string()],
         node_to_validator,
         validator_to_address,
+        validator_public_keys: HashMap::new(),
     }
 }
@@ -29,6 +29,9 @@ fn test_timestamp_jitter_prevents_pattern_analysis() {
             let collection = EncryptedCollection::new(
                 format!("Collection #{}-{}", i, j),
                 format!("{{\"data\": {}}}", i * 2 + j),
+                None,
+                "application/json",
+                None,
                 &api_key,
                 api_key_hash.clone(),
                 &signing_key,
@@ -51,6 +54,9 @@ fn test_timestamp_jitter_prevents_pattern_analysis() {
             let dummy = EncryptedCollection::new(
                 format!("Dummy #{}", i),
                 format!("{{\"filler\": {}}}", i),
+                None,
+                "application/json",
+                None,
                 &api_key,
                 api_key_hash.clone(),
                 &signing_key,
@@ -93,7 +93,11 @@ export default function CollectionsPage() {
   const handleDecrypt = async (id: string) => {
     try {
       const result = await decryptData(id)
-      setDecryptedData((prev) => new Map(prev).set(id, result.data))
+      const text =
+        result.encoding === 'json'
+          ? JSON.stringify(result.data)
+          : String(result.data)
+      setDecryptedData((prev) => new Map(prev).set(id, text))
       setExpandedRows((prev) => new Set(prev).add(id))
       success('Data decrypted successfully')
     } catch (err) {
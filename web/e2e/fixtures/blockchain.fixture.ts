@@ -61,21 +61,13 @@ export const test = base.extend<BlockchainFixtures>({
        * Get block at specific height
        */
       async getBlock(height: number): Promise<any> {
-        const response = await page.request.get(`${apiUrl}/chain`)
+        const response = await page.request.get(`${apiUrl}/block/${height}`)
 
         if (!response.ok()) {
-          throw new Error(`Failed to get chain: ${await response.text()}`)
-        }
-
-        const data = await response.json()
-
-        if (height >= data.blocks.length) {
-          throw new Error(
-            `Block ${height} does not exist (chain length: ${data.blocks.length})`
-          )
+          throw new Error(`Failed to get block ${height}: ${await response.text()}`)
         }
 
-        return data.blocks[height]
+        return response.json()
       },
 
       /**
@@ -68,6 +68,8 @@ export const mockApiResponses = {
     session_token: 'mock_session_' + Math.random().toString(36).substr(2, 16),
     account_id: 'mock_account_123',
     expires_in: 3600,
+    scope: 'all',
+    remember_me: false,
   } satisfies LoginResponse,
 
   submitData: {
@@ -117,9 +119,10 @@ export const mockApiResponses = {
     peers: ['peer_1', 'peer_2'],
     count: 2,
     reputation: {
-      peer_1: 100,
-      peer_2: 95,
+      peer_1: { score: 100, last_seen: 1704067200, rtt_ms: 2 },
+      peer_2: { score: 95, last_seen: 1704067200, rtt_ms: 5 },
     },
+    parked: [],
   } satisfies PeersResponse,
 
   auditLogs: {
@@ -163,7 +166,7 @@ export async function setupApiMocks(page: Page) {
   })
 
   // Chain endpoint
-  await page.route('**/chain', async (route) => {
+  await page.route(/\/chain(\?.*)?$/, async (route) => {
     await route.fulfill({
       status: 200,
       contentType: 'application/json',
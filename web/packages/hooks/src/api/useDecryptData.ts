@@ -13,6 +13,7 @@ export function useDecryptData() {
         method: 'POST',
         headers: {
           Authorization: `Bearer ${apiKey}`,
+          'X-Nonce': crypto.randomUUID(),
         },
       })
 
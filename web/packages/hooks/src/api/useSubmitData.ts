@@ -18,6 +18,7 @@ export function useSubmitData() {
         headers: {
           'Content-Type': 'application/json',
           Authorization: `Bearer ${apiKey}`,
+          'X-Nonce': crypto.randomUUID(),
         },
         body: JSON.stringify(data),
       })
@@ -1,6 +1,7 @@
 export interface ApiError {
   error: string
-  details?: string
+  code: string
+  details?: Record<string, unknown>
 }
 
 export interface CreateAccountRequest {
@@ -14,19 +15,35 @@ export interface CreateAccountResponse {
   account_id: string
 }
 
+export type TokenScope = 'read' | 'write' | 'all'
+
 export interface LoginRequest {
   api_key: string
+  scope?: TokenScope
+  ttl_seconds?: number
+  remember_me?: boolean
 }
 
 export interface LoginResponse {
   session_token: string
   account_id: string
   expires_in: number
+  scope: TokenScope
+  remember_me: boolean
+}
+
+export interface AccountPublicKeyResponse {
+  account_id: string
+  public_key: string | null
+  sharing_public_key: string
 }
 
 export interface SubmitDataRequest {
   label: string
   data: string
+  compress?: boolean
+  content_type?: string
+  expires_at?: number
 }
 
 export interface SubmitDataResponse {
@@ -43,16 +60,37 @@ export interface ListCollectionsResponse {
     created_at: number
     block_number: number
     data_count: number
+    shared: boolean
   }>
 }
 
 export interface DecryptDataResponse {
   collection_id: string
   label: string
-  data: string
+  /** Parsed value when `encoding` is `json`, otherwise a string */
+  data: unknown
+  encoding: 'json' | 'text' | 'base64'
+  content_type: string
+  expires_at?: number
+  shared: boolean
   created_at: number
 }
 
+export interface ShareCollectionRequest {
+  recipient_public_key: string
+}
+
+export interface ShareGrantResponse {
+  message: string
+  grant_id: string
+  collection_id: string
+  block_number: number
+}
+
+export interface SharingKeyResponse {
+  sharing_public_key: string
+}
+
 export interface AuditLogEntry {
   event_type: string
   timestamp: number
@@ -60,6 +98,7 @@ export interface AuditLogEntry {
   ip_hash: string
   metadata: Record<string, unknown>
   invalidated: boolean
+  verified?: boolean
 }
 
 export interface AuditLogsResponse {
@@ -85,8 +124,15 @@ export interface MetricsResponse {
   }
 }
 
+export interface PeerReputation {
+  score: number
+  last_seen?: number
+  rtt_ms?: number
+}
+
 export interface PeersResponse {
   peers: string[]
   count: number
-  reputation: Record<string, number>
+  reputation: Record<string, PeerReputation>
+  parked: string[]
 }